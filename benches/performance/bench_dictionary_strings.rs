@@ -0,0 +1,96 @@
+//! Performance benchmark for dictionary encoded string columns
+//!
+//! Compares converting a high-repetition `Dictionary(Int32, Utf8)` column (each distinct
+//! value encoded once per batch) against the same values materialized as a plain `Utf8`
+//! column, on both the flat fast path and the general row loop.
+
+use arrow::array::{ArrayRef, DictionaryArray, Int32Array, Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Int32Type, Schema};
+use arrow::record_batch::RecordBatch;
+use arrow_zerobus_sdk_wrapper::wrapper::conversion;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::sync::Arc;
+
+/// `id` column plus `num_columns` string columns cycling through `cardinality` values
+fn create_batch(
+    num_rows: usize,
+    num_columns: usize,
+    cardinality: usize,
+    dictionary: bool,
+) -> RecordBatch {
+    let values = StringArray::from_iter_values(
+        (0..cardinality).map(|i| format!("warehouse-region-{:04}", i)),
+    );
+    let mut fields = vec![Field::new("id", DataType::Int64, false)];
+    let mut columns: Vec<ArrayRef> =
+        vec![Arc::new(Int64Array::from_iter_values(0..num_rows as i64))];
+    for column in 0..num_columns {
+        let keys = Int32Array::from_iter_values(
+            (0..num_rows).map(|row| ((row * 7 + column) % cardinality) as i32),
+        );
+        let array: ArrayRef = Arc::new(
+            DictionaryArray::<Int32Type>::try_new(keys, Arc::new(values.clone())).unwrap(),
+        );
+        let array = if dictionary {
+            array
+        } else {
+            arrow::compute::cast(&array, &DataType::Utf8).unwrap()
+        };
+        fields.push(Field::new(
+            format!("column_{}", column),
+            array.data_type().clone(),
+            true,
+        ));
+        columns.push(array);
+    }
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).unwrap()
+}
+
+fn bench_dictionary_strings(c: &mut Criterion) {
+    let mut group = c.benchmark_group("dictionary_strings");
+
+    for (num_rows, num_columns, cardinality) in [(10_000, 10, 16), (1_000, 100, 8)] {
+        let label = format!(
+            "{}_columns_{}_rows_{}_values",
+            num_columns, num_rows, cardinality
+        );
+        group.throughput(Throughput::Elements(num_rows as u64));
+
+        for (name, dictionary) in [("materialized", false), ("dictionary", true)] {
+            let batch = create_batch(num_rows, num_columns, cardinality, dictionary);
+            let descriptor =
+                conversion::generate_protobuf_descriptor(batch.schema().as_ref()).unwrap();
+
+            group.bench_with_input(
+                BenchmarkId::new(format!("{}_fast_path", name), &label),
+                &batch,
+                |b, batch| {
+                    b.iter(|| {
+                        black_box(conversion::record_batch_to_protobuf_bytes(
+                            black_box(batch),
+                            &descriptor,
+                        ))
+                    });
+                },
+            );
+
+            group.bench_with_input(
+                BenchmarkId::new(format!("{}_row_by_row", name), &label),
+                &batch,
+                |b, batch| {
+                    b.iter(|| {
+                        black_box(conversion::record_batch_to_protobuf_bytes_row_by_row(
+                            black_box(batch),
+                            &descriptor,
+                        ))
+                    });
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_dictionary_strings);
+criterion_main!(benches);
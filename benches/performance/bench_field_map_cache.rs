@@ -0,0 +1,80 @@
+//! Performance benchmark for descriptor field map caching
+//!
+//! Compares converting many small batches with field maps rebuilt per batch
+//! (`record_batch_to_protobuf_bytes`) against maps built once and reused
+//! (`record_batch_to_protobuf_bytes_with_maps`), as the wrapper does for a stable descriptor.
+
+use arrow::array::{ArrayRef, Int64Array};
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use arrow_zerobus_sdk_wrapper::wrapper::conversion::{self, DescriptorFieldMaps, ErrorRedaction};
+use arrow_zerobus_sdk_wrapper::{FieldNameTransform, FloatPolicy, NullElementPolicy};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::sync::Arc;
+
+/// Number of batches converted per iteration
+const BATCHES: usize = 100;
+
+fn create_test_batch(num_columns: usize, num_rows: usize) -> RecordBatch {
+    let fields: Vec<Field> = (0..num_columns)
+        .map(|i| Field::new(format!("column_{}", i), DataType::Int64, false))
+        .collect();
+    let columns: Vec<ArrayRef> = (0..num_columns)
+        .map(|i| {
+            Arc::new(Int64Array::from_iter_values(
+                (0..num_rows).map(|row| (row * num_columns + i) as i64),
+            )) as ArrayRef
+        })
+        .collect();
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).unwrap()
+}
+
+fn bench_field_map_cache(c: &mut Criterion) {
+    let mut group = c.benchmark_group("field_map_cache");
+
+    // Wide schemas with few rows per batch are where per-batch map building shows up
+    for (num_columns, num_rows) in [(50, 1), (200, 10), (200, 100)] {
+        let batch = create_test_batch(num_columns, num_rows);
+        let descriptor = conversion::generate_protobuf_descriptor(batch.schema().as_ref()).unwrap();
+        let label = format!("{}_columns_{}_rows", num_columns, num_rows);
+
+        group.bench_with_input(BenchmarkId::new("rebuilt", &label), &batch, |b, batch| {
+            b.iter(|| {
+                for _ in 0..BATCHES {
+                    black_box(conversion::record_batch_to_protobuf_bytes(
+                        black_box(batch),
+                        &descriptor,
+                    ));
+                }
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("cached", &label), &batch, |b, batch| {
+            let maps = DescriptorFieldMaps::new(&descriptor);
+            b.iter(|| {
+                for _ in 0..BATCHES {
+                    black_box(conversion::record_batch_to_protobuf_bytes_with_maps(
+                        black_box(batch),
+                        &maps,
+                        &FieldNameTransform::None,
+                        FloatPolicy::Passthrough,
+                        false,
+                        false,
+                        false,
+                        NullElementPolicy::SkipNullElements,
+                        TimeUnit::Microsecond,
+                        ErrorRedaction::default(),
+                        None,
+                        1,
+                        None,
+                    ));
+                }
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_field_map_cache);
+criterion_main!(benches);
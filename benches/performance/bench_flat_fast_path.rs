@@ -0,0 +1,86 @@
+//! Performance benchmark for the flat-schema conversion fast path
+//!
+//! Compares `record_batch_to_protobuf_bytes` on an all-primitive batch (per-column
+//! encoders built once, then applied to every row) against the general per-field,
+//! per-row encoder (`record_batch_to_protobuf_bytes_row_by_row`).
+
+use arrow::array::{ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use arrow_zerobus_sdk_wrapper::wrapper::conversion;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::sync::Arc;
+
+/// Flat batch cycling through Int64, Float64, Utf8 and Boolean columns
+fn create_flat_batch(num_columns: usize, num_rows: usize) -> RecordBatch {
+    let (fields, columns): (Vec<Field>, Vec<ArrayRef>) = (0..num_columns)
+        .map(|i| {
+            let name = format!("column_{}", i);
+            match i % 4 {
+                0 => (
+                    Field::new(name, DataType::Int64, true),
+                    Arc::new(Int64Array::from_iter_values(
+                        (0..num_rows).map(|row| (row * num_columns + i) as i64),
+                    )) as ArrayRef,
+                ),
+                1 => (
+                    Field::new(name, DataType::Float64, true),
+                    Arc::new(Float64Array::from_iter_values(
+                        (0..num_rows).map(|row| row as f64 * 0.5),
+                    )) as ArrayRef,
+                ),
+                2 => (
+                    Field::new(name, DataType::Utf8, true),
+                    Arc::new(StringArray::from_iter_values(
+                        (0..num_rows).map(|row| format!("value_{}", row)),
+                    )) as ArrayRef,
+                ),
+                _ => (
+                    Field::new(name, DataType::Boolean, true),
+                    Arc::new(BooleanArray::from_iter(
+                        (0..num_rows).map(|row| Some(row % 2 == 0)),
+                    )) as ArrayRef,
+                ),
+            }
+        })
+        .unzip();
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).unwrap()
+}
+
+fn bench_flat_fast_path(c: &mut Criterion) {
+    let mut group = c.benchmark_group("flat_fast_path");
+
+    for (num_columns, num_rows) in [(10, 10_000), (50, 1_000), (200, 100)] {
+        let batch = create_flat_batch(num_columns, num_rows);
+        let descriptor = conversion::generate_protobuf_descriptor(batch.schema().as_ref()).unwrap();
+        let label = format!("{}_columns_{}_rows", num_columns, num_rows);
+        group.throughput(Throughput::Elements(num_rows as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("row_by_row", &label),
+            &batch,
+            |b, batch| {
+                b.iter(|| {
+                    black_box(conversion::record_batch_to_protobuf_bytes_row_by_row(
+                        black_box(batch),
+                        &descriptor,
+                    ))
+                });
+            },
+        );
+
+        group.bench_with_input(BenchmarkId::new("fast_path", &label), &batch, |b, batch| {
+            b.iter(|| {
+                black_box(conversion::record_batch_to_protobuf_bytes(
+                    black_box(batch),
+                    &descriptor,
+                ))
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_flat_fast_path);
+criterion_main!(benches);
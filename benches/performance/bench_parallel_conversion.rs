@@ -0,0 +1,104 @@
+//! Performance benchmark for parallel row conversion
+//!
+//! Compares converting a large batch with a wide, nested schema sequentially against
+//! converting it in parallel row chunks (`WrapperConfiguration::with_conversion_parallelism`).
+//! Run with `cargo bench --bench parallel_conversion --features parallel`; without the
+//! `parallel` feature every variant converts sequentially.
+
+use arrow::array::{ArrayRef, Float64Array, Int64Array, StringArray, StructArray};
+use arrow::datatypes::{DataType, Field, Fields, Schema, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use arrow_zerobus_sdk_wrapper::wrapper::conversion::{self, DescriptorFieldMaps, ErrorRedaction};
+use arrow_zerobus_sdk_wrapper::{FieldNameTransform, FloatPolicy, NullElementPolicy};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::sync::Arc;
+
+/// Flat columns of each type at the top level
+const FLAT_COLUMNS: usize = 20;
+/// Nested struct columns, each with `FLAT_COLUMNS` fields of each type
+const STRUCT_COLUMNS: usize = 4;
+
+/// `FLAT_COLUMNS` each of Int64, Float64 and Utf8 columns
+fn flat_columns(num_rows: usize) -> Vec<(Arc<Field>, ArrayRef)> {
+    (0..FLAT_COLUMNS)
+        .flat_map(|i| {
+            [
+                (
+                    Arc::new(Field::new(format!("int_{i}"), DataType::Int64, false)),
+                    Arc::new(Int64Array::from_iter_values(
+                        (0..num_rows).map(|row| (row * 31 + i) as i64),
+                    )) as ArrayRef,
+                ),
+                (
+                    Arc::new(Field::new(format!("float_{i}"), DataType::Float64, false)),
+                    Arc::new(Float64Array::from_iter_values(
+                        (0..num_rows).map(|row| row as f64 * 0.5 + i as f64),
+                    )) as ArrayRef,
+                ),
+                (
+                    Arc::new(Field::new(format!("str_{i}"), DataType::Utf8, true)),
+                    Arc::new(StringArray::from_iter_values(
+                        (0..num_rows).map(|row| format!("value-{row}-{i}")),
+                    )) as ArrayRef,
+                ),
+            ]
+        })
+        .collect()
+}
+
+fn create_wide_nested_batch(num_rows: usize) -> RecordBatch {
+    let mut columns = flat_columns(num_rows);
+    for s in 0..STRUCT_COLUMNS {
+        let (fields, arrays): (Vec<_>, Vec<_>) = flat_columns(num_rows).into_iter().unzip();
+        let nested = StructArray::new(Fields::from(fields), arrays, None);
+        columns.push((
+            Arc::new(Field::new(
+                format!("nested_{s}"),
+                DataType::Struct(nested.fields().clone()),
+                false,
+            )),
+            Arc::new(nested) as ArrayRef,
+        ));
+    }
+    let (fields, arrays): (Vec<_>, Vec<_>) = columns.into_iter().unzip();
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays).unwrap()
+}
+
+fn bench_parallel_conversion(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel_conversion");
+    group.sample_size(10);
+
+    for num_rows in [1_000, 10_000] {
+        let batch = create_wide_nested_batch(num_rows);
+        let descriptor = conversion::generate_protobuf_descriptor(batch.schema().as_ref()).unwrap();
+        let maps = DescriptorFieldMaps::new(&descriptor);
+
+        for parallelism in [1, 2, 4, 8] {
+            let label = format!("{}_rows_parallelism_{}", num_rows, parallelism);
+            group.bench_with_input(BenchmarkId::new("convert", &label), &batch, |b, batch| {
+                b.iter(|| {
+                    black_box(conversion::record_batch_to_protobuf_bytes_with_maps(
+                        black_box(batch),
+                        &maps,
+                        &FieldNameTransform::None,
+                        FloatPolicy::Passthrough,
+                        false,
+                        false,
+                        false,
+                        NullElementPolicy::SkipNullElements,
+                        TimeUnit::Microsecond,
+                        ErrorRedaction::default(),
+                        None,
+                        parallelism,
+                        None,
+                    ))
+                });
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_parallel_conversion);
+criterion_main!(benches);
@@ -0,0 +1,168 @@
+//! Typed builder for `WrapperConfiguration`
+//!
+//! The builder tracks the required endpoint and table name in its type, so a
+//! configuration missing either does not compile. Everything else is optional and
+//! applied on top of `WrapperConfiguration::new`; `build` then runs `validate`.
+
+use crate::config::WrapperConfiguration;
+use crate::error::ZerobusError;
+
+/// Marker for a required builder field that has not been set yet
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Missing;
+
+/// Marker for a required builder field that has been set
+#[derive(Debug, Clone)]
+pub struct Provided(String);
+
+/// Setting applied to the configuration when it is built
+type Adjustment = Box<dyn FnOnce(WrapperConfiguration) -> WrapperConfiguration + Send>;
+
+/// Builder for `WrapperConfiguration` with compile-time required fields
+///
+/// `build` is only available once both `endpoint` and `table_name` have been set. The
+/// existing `WrapperConfiguration::new` and `with_*` methods remain available; any of
+/// them can be used through `configure`.
+///
+/// # Example
+///
+/// ```no_run
+/// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+/// use std::time::Duration;
+///
+/// # fn example() -> Result<(), arrow_zerobus_sdk_wrapper::ZerobusError> {
+/// let config = WrapperConfiguration::builder()
+///     .endpoint("https://workspace.cloud.databricks.com")
+///     .table_name("catalog.schema.events")
+///     .credentials("client_id", "client_secret")
+///     .unity_catalog("https://workspace.cloud.databricks.com")
+///     .configure(|config| config.with_send_deadline(Duration::from_secs(30)))
+///     .build()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Leaving out a required field is a compile error:
+///
+/// ```compile_fail
+/// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+///
+/// let config = WrapperConfiguration::builder()
+///     .endpoint("https://workspace.cloud.databricks.com")
+///     .build(); // no table name: `build` does not exist yet
+/// ```
+pub struct WrapperConfigurationBuilder<E = Missing, T = Missing> {
+    endpoint: E,
+    table_name: T,
+    adjustments: Vec<Adjustment>,
+}
+
+impl WrapperConfigurationBuilder {
+    /// Create a builder with neither endpoint nor table name set
+    pub fn new() -> Self {
+        Self {
+            endpoint: Missing,
+            table_name: Missing,
+            adjustments: Vec::new(),
+        }
+    }
+}
+
+impl Default for WrapperConfigurationBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E, T> WrapperConfigurationBuilder<E, T> {
+    /// Set the Zerobus endpoint (required)
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Zerobus endpoint URL
+    pub fn endpoint(self, endpoint: impl Into<String>) -> WrapperConfigurationBuilder<Provided, T> {
+        WrapperConfigurationBuilder {
+            endpoint: Provided(endpoint.into()),
+            table_name: self.table_name,
+            adjustments: self.adjustments,
+        }
+    }
+
+    /// Set the target table name (required)
+    ///
+    /// # Arguments
+    ///
+    /// * `table_name` - Target table name
+    pub fn table_name(
+        self,
+        table_name: impl Into<String>,
+    ) -> WrapperConfigurationBuilder<E, Provided> {
+        WrapperConfigurationBuilder {
+            endpoint: self.endpoint,
+            table_name: Provided(table_name.into()),
+            adjustments: self.adjustments,
+        }
+    }
+
+    /// Set OAuth2 credentials (see `WrapperConfiguration::with_credentials`)
+    ///
+    /// # Arguments
+    ///
+    /// * `client_id` - OAuth2 client ID
+    /// * `client_secret` - OAuth2 client secret
+    pub fn credentials(
+        self,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+    ) -> Self {
+        let (client_id, client_secret) = (client_id.into(), client_secret.into());
+        self.configure(move |config| config.with_credentials(client_id, client_secret))
+    }
+
+    /// Set the Unity Catalog URL (see `WrapperConfiguration::with_unity_catalog`)
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - Unity Catalog URL
+    pub fn unity_catalog(self, url: impl Into<String>) -> Self {
+        let url = url.into();
+        self.configure(move |config| config.with_unity_catalog(url))
+    }
+
+    /// Apply any other `WrapperConfiguration` setting
+    ///
+    /// Settings are applied in the order they were added, after the required fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `adjust` - Function applying settings, typically a chain of `with_*` calls
+    pub fn configure(
+        mut self,
+        adjust: impl FnOnce(WrapperConfiguration) -> WrapperConfiguration + Send + 'static,
+    ) -> Self {
+        self.adjustments.push(Box::new(adjust));
+        self
+    }
+}
+
+impl WrapperConfigurationBuilder<Provided, Provided> {
+    /// Build and validate the configuration
+    ///
+    /// # Returns
+    ///
+    /// Returns the configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigurationError` if `WrapperConfiguration::validate` fails.
+    pub fn build(self) -> Result<WrapperConfiguration, ZerobusError> {
+        let Provided(endpoint) = self.endpoint;
+        let Provided(table_name) = self.table_name;
+        let config = self.adjustments.into_iter().fold(
+            WrapperConfiguration::new(endpoint, table_name),
+            |config, adjust| adjust(config),
+        );
+        config.validate()?;
+        Ok(config)
+    }
+}
@@ -1,6 +1,6 @@
 //! Configuration loader for Zerobus SDK Wrapper
 //!
-//! This module handles loading configuration from YAML files and environment variables.
+//! This module handles loading configuration from YAML/TOML files and environment variables.
 
 use crate::config::WrapperConfiguration;
 use crate::error::ZerobusError;
@@ -38,6 +38,8 @@ pub struct DebugYaml {
     pub flush_interval_secs: Option<u64>,
     pub max_file_size: Option<u64>,
     pub max_files_retained: Option<usize>, // New flag
+    pub global_max_files: Option<usize>,
+    pub proto_header: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -132,6 +134,12 @@ pub fn load_from_yaml<P: AsRef<Path>>(path: P) -> Result<WrapperConfiguration, Z
             if let Some(max_files) = debug.max_files_retained {
                 config.debug_max_files_retained = Some(max_files);
             }
+            if debug.global_max_files.is_some() {
+                config.debug_global_max_files = debug.global_max_files;
+            }
+        }
+        if let Some(proto_header) = debug.proto_header {
+            config.debug_proto_header = proto_header;
         }
     }
 
@@ -147,6 +155,199 @@ pub fn load_from_yaml<P: AsRef<Path>>(path: P) -> Result<WrapperConfiguration, Z
     Ok(config)
 }
 
+/// Configuration file structure for `WrapperConfiguration::from_toml_file` and
+/// `WrapperConfiguration::from_yaml_file`
+///
+/// Credentials are never read from the file itself: `client_id_env` and
+/// `client_secret_env` name the environment variables holding them. Unknown keys
+/// (including a plaintext `client_id`/`client_secret`) are rejected.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    pub zerobus_endpoint: String,
+    pub table_name: String,
+    pub unity_catalog_url: Option<String>,
+    /// Environment variable holding the OAuth2 client ID
+    pub client_id_env: Option<String>,
+    /// Environment variable holding the OAuth2 client secret
+    pub client_secret_env: Option<String>,
+    pub zerobus_writer_disabled: Option<bool>,
+    pub observability: Option<ObservabilityYaml>,
+    pub debug: Option<DebugYaml>,
+    pub retry: Option<RetryYaml>,
+    /// Retry settings for SDK/stream creation
+    pub connect_retry: Option<RetryYaml>,
+    /// Error message substrings that force a retry (stopgap pending structured errors)
+    pub retryable_error_patterns: Option<Vec<String>>,
+    /// Error message substrings that stop retrying immediately
+    pub fatal_error_patterns: Option<Vec<String>>,
+}
+
+impl ConfigFile {
+    /// Build and validate the `WrapperConfiguration` described by this file
+    ///
+    /// Unset retry values keep their defaults, so e.g. only `max_attempts` can be overridden.
+    ///
+    /// # Returns
+    ///
+    /// Returns `WrapperConfiguration` if successful, or `ZerobusError` if a referenced
+    /// environment variable is missing or the configuration is invalid.
+    pub fn into_configuration(self) -> Result<WrapperConfiguration, ZerobusError> {
+        let mut config = WrapperConfiguration::new(self.zerobus_endpoint, self.table_name);
+
+        if let Some(url) = self.unity_catalog_url {
+            config = config.with_unity_catalog(url);
+        }
+
+        match (self.client_id_env, self.client_secret_env) {
+            (Some(id_var), Some(secret_var)) => {
+                config =
+                    config.with_credentials(read_env_ref(&id_var)?, read_env_ref(&secret_var)?);
+            }
+            (None, None) => {}
+            _ => {
+                return Err(ZerobusError::ConfigurationError(
+                    "client_id_env and client_secret_env must be set together".to_string(),
+                ))
+            }
+        }
+
+        if let Some(disabled) = self.zerobus_writer_disabled {
+            config = config.with_zerobus_writer_disabled(disabled);
+        }
+
+        if let Some(obs) = self.observability {
+            if obs.enabled.unwrap_or(false) {
+                use crate::config::OtlpSdkConfig;
+                config = config.with_observability(OtlpSdkConfig {
+                    endpoint: obs.endpoint,
+                    output_dir: obs.output_dir.map(std::path::PathBuf::from),
+                    write_interval_secs: obs.write_interval_secs.unwrap_or(5),
+                    log_level: obs.log_level.unwrap_or_else(|| "info".to_string()),
+                });
+            }
+        }
+
+        if let Some(debug) = self.debug {
+            if let Some(arrow_enabled) = debug.arrow_enabled {
+                config.debug_arrow_enabled = arrow_enabled;
+            }
+            if let Some(protobuf_enabled) = debug.protobuf_enabled {
+                config.debug_protobuf_enabled = protobuf_enabled;
+            }
+            // Legacy debug.enabled enables both formats unless either is set explicitly
+            if let Some(enabled) = debug.enabled {
+                config.debug_enabled = enabled;
+                if enabled && debug.arrow_enabled.is_none() && debug.protobuf_enabled.is_none() {
+                    config.debug_arrow_enabled = true;
+                    config.debug_protobuf_enabled = true;
+                }
+            }
+            if let Some(output_dir) = debug.output_dir {
+                config.debug_output_dir = Some(std::path::PathBuf::from(output_dir));
+            }
+            if let Some(interval) = debug.flush_interval_secs {
+                config.debug_flush_interval_secs = interval;
+            }
+            if debug.max_file_size.is_some() {
+                config.debug_max_file_size = debug.max_file_size;
+            }
+            if debug.max_files_retained.is_some() {
+                config.debug_max_files_retained = debug.max_files_retained;
+            }
+            if debug.global_max_files.is_some() {
+                config.debug_global_max_files = debug.global_max_files;
+            }
+            if let Some(proto_header) = debug.proto_header {
+                config.debug_proto_header = proto_header;
+            }
+        }
+
+        if let Some(retry) = self.retry {
+            let max_attempts = retry.max_attempts.unwrap_or(config.retry_max_attempts);
+            let base_delay_ms = retry.base_delay_ms.unwrap_or(config.retry_base_delay_ms);
+            let max_delay_ms = retry.max_delay_ms.unwrap_or(config.retry_max_delay_ms);
+            config = config.with_retry_config(max_attempts, base_delay_ms, max_delay_ms);
+        }
+
+        if let Some(retry) = self.connect_retry {
+            let max_attempts = retry.max_attempts.unwrap_or(config.retry_max_attempts);
+            let base_delay_ms = retry
+                .base_delay_ms
+                .unwrap_or(config.connect_retry_base_delay_ms);
+            let max_delay_ms = retry
+                .max_delay_ms
+                .unwrap_or(config.connect_retry_max_delay_ms);
+            config = config.with_connect_retry_config(max_attempts, base_delay_ms, max_delay_ms);
+        }
+
+        if let Some(patterns) = self.retryable_error_patterns {
+            config = config.with_retryable_error_patterns(patterns);
+        }
+        if let Some(patterns) = self.fatal_error_patterns {
+            config = config.with_fatal_error_patterns(patterns);
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+/// Value of the environment variable a config file references
+fn read_env_ref(var: &str) -> Result<String, ZerobusError> {
+    std::env::var(var).map_err(|_| {
+        ZerobusError::ConfigurationError(format!(
+            "Environment variable '{}' referenced by config file is not set",
+            var
+        ))
+    })
+}
+
+fn read_config_file(path: &Path) -> Result<String, ZerobusError> {
+    std::fs::read_to_string(path).map_err(|e| {
+        ZerobusError::ConfigurationError(format!(
+            "Failed to read config file {}: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+/// Load configuration from a TOML file (see `ConfigFile` for the layout)
+///
+/// # Arguments
+///
+/// * `path` - Path to TOML configuration file
+///
+/// # Returns
+///
+/// Returns `WrapperConfiguration` if successful, or `ZerobusError` if loading fails.
+pub fn load_from_toml_file<P: AsRef<Path>>(path: P) -> Result<WrapperConfiguration, ZerobusError> {
+    let content = read_config_file(path.as_ref())?;
+    let file: ConfigFile = toml::from_str(&content)
+        .map_err(|e| ZerobusError::ConfigurationError(format!("Failed to parse TOML: {}", e)))?;
+    file.into_configuration()
+}
+
+/// Load configuration from a YAML file (see `ConfigFile` for the layout)
+///
+/// Unlike `load_from_yaml`, credentials are read from the environment variables
+/// named by `client_id_env`/`client_secret_env`, and unknown keys are rejected.
+///
+/// # Arguments
+///
+/// * `path` - Path to YAML configuration file
+///
+/// # Returns
+///
+/// Returns `WrapperConfiguration` if successful, or `ZerobusError` if loading fails.
+pub fn load_from_yaml_file<P: AsRef<Path>>(path: P) -> Result<WrapperConfiguration, ZerobusError> {
+    let content = read_config_file(path.as_ref())?;
+    let file: ConfigFile = serde_yaml::from_str(&content)
+        .map_err(|e| ZerobusError::ConfigurationError(format!("Failed to parse YAML: {}", e)))?;
+    file.into_configuration()
+}
+
 /// Load configuration from environment variables
 ///
 /// Reads configuration from environment variables with the following prefixes:
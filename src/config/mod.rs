@@ -2,7 +2,15 @@
 //!
 //! This module handles configuration loading, validation, and management.
 
+pub mod builder;
 pub mod loader;
 pub mod types;
 
-pub use types::{OtlpConfig, OtlpSdkConfig, WrapperConfiguration};
+pub use builder::WrapperConfigurationBuilder;
+
+pub use types::{
+    BatchTransform, CompressionType, CustomEncoder, CustomEncoderFn, DeliveryMode,
+    DescriptorValidationMode, FieldNameTransform, FloatPolicy, NullElementPolicy, OtlpConfig,
+    OtlpSdkConfig, OversizedBatchPolicy, SchemaEvolution, StringNullPolicy, WrapperConfiguration,
+    DATABRICKS_ENDPOINT_DOMAINS,
+};
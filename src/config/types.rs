@@ -3,9 +3,21 @@
 //! This module defines the configuration structures and validation logic.
 
 use crate::error::ZerobusError;
+use crate::wrapper::conversion::{
+    DescriptorLimits, ErrorRedaction, ABSOLUTE_MAX_NESTING_DEPTH, MAX_FIELDS_PER_MESSAGE,
+    MAX_FIELD_NAME_LENGTH, MAX_NESTING_DEPTH,
+};
+use crate::wrapper::zerobus::BackoffObserver;
+use arrow::array::Array;
+use arrow::datatypes::{SchemaRef, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use prost_types::FieldDescriptorProto;
 use secrecy::SecretString;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
 /// OpenTelemetry configuration
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -75,6 +87,286 @@ impl Default for OtlpSdkConfig {
     }
 }
 
+/// Known Databricks cloud domains used by [`WrapperConfiguration::with_databricks_endpoint_check`]
+///
+/// Zerobus endpoints and Unity Catalog URLs are subdomains of one of these, e.g.
+/// `https://<workspace_id>.zerobus.<region>.cloud.databricks.com`.
+pub const DATABRICKS_ENDPOINT_DOMAINS: &[&str] = &[
+    "cloud.databricks.com",
+    "gcp.databricks.com",
+    "azuredatabricks.net",
+    "databricks.azure.cn",
+    "cloud.databricks.us",
+];
+
+/// How the wrapper reacts when a batch's descriptor differs from the active one
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SchemaEvolution {
+    /// Descriptors are not reconciled (default)
+    ///
+    /// Each batch is encoded with its own descriptor over the existing stream, so a schema
+    /// change typically surfaces as a stream closure.
+    #[default]
+    Strict,
+    /// New optional fields are appended to the active descriptor
+    ///
+    /// Existing fields keep their field numbers, and the stream is recreated with the
+    /// evolved descriptor. Changes that are not add-only are rejected with a
+    /// `ConfigurationError` (see `conversion::evolve_descriptor`).
+    AddOnly,
+}
+
+/// Handling of non-finite (`NaN`, `+Inf`, `-Inf`) Float32/Float64 values during encoding
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FloatPolicy {
+    /// Values are encoded verbatim as IEEE-754 bits (default)
+    #[default]
+    Passthrough,
+    /// The row fails with a `ConversionError` naming the field and value
+    ErrorOnNonFinite,
+    /// The field is omitted from the message, as if the value were null
+    CoerceToNull,
+    /// The value is encoded as `0.0`
+    CoerceToZero,
+}
+
+/// Handling of null elements inside repeated (list) fields during encoding
+///
+/// Proto3 repeated fields cannot represent a null element, so each policy changes what
+/// consumers see: skipping shortens the list, a default is indistinguishable from a real
+/// zero value, and an error fails the row.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NullElementPolicy {
+    /// Null elements are omitted from the list (default)
+    #[default]
+    SkipNullElements,
+    /// The row fails with a `ConversionError` naming the field and element
+    ErrorOnNullElement,
+    /// Null elements are written as the element type's proto3 default (0, `false`, an
+    /// empty string/bytes, or an empty message)
+    EmitDefaultForNull,
+}
+
+/// Reaction to a batch larger than `WrapperConfiguration::max_batch_bytes`
+///
+/// Batch size is the Arrow in-memory size of the rows being sent (the size reported in
+/// `TransmissionResult::batch_size_bytes`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OversizedBatchPolicy {
+    /// The batch fails with a `ConfigurationError` and nothing is sent (default)
+    #[default]
+    Reject,
+    /// The batch is sent as consecutive sub-batches within the limit, and their results
+    /// are combined into one `TransmissionResult` with the original row indices
+    ///
+    /// A sub-batch failing at batch level stops the split: its error becomes the result's
+    /// `error`, and the rows not sent are reported in `failed_rows` with it. The earlier
+    /// sub-batches were already ingested, so resending the whole batch duplicates them;
+    /// resend only the failed rows.
+    AutoSplit,
+    /// Only the leading rows within the limit are sent; the trailing rows are reported
+    /// as failed rows and a warning is logged
+    Truncate,
+}
+
+/// How null values of string columns are kept apart from empty strings
+///
+/// Null strings are omitted from the row's message while empty strings are written as
+/// zero-length values, but a plain proto3 reader sees both as an unset field. Tables that
+/// must tell them apart need either presence information or a sentinel value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum StringNullPolicy {
+    /// Nulls are omitted and read back like empty strings unless the descriptor declares
+    /// presence (e.g. with `explicit_field_presence`) (default)
+    #[default]
+    Omit,
+    /// Generated descriptors declare nullable string fields proto3 `optional` (in a
+    /// synthetic oneof, as `explicit_field_presence` does for every nullable field), so an
+    /// empty string reads back as present and a null as absent
+    ExplicitPresence,
+    /// Nulls in string columns are written as this value instead of being omitted
+    Sentinel(String),
+}
+
+/// Delivery guarantee for records sent to Zerobus
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DeliveryMode {
+    /// Every record's acknowledgment is awaited; a row is successful once Zerobus has
+    /// acknowledged it, and unacknowledged rows are retried or reported as failed (default)
+    #[default]
+    AtLeastOnce,
+    /// Records are handed to the stream and counted successful without awaiting their
+    /// acknowledgments (at-most-once): rows lost after submission are never reported
+    FireAndForget,
+}
+
+/// Compression codec for the ingest stream
+///
+/// Text-heavy Protobuf records typically shrink 3-5x with gzip and slightly more with zstd,
+/// at some CPU cost on both ends. The Zerobus SDK does not currently expose a codec for the
+/// ingest stream, so only `None` is accepted (see
+/// `WrapperConfiguration::with_transmission_compression`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CompressionType {
+    /// Records are sent uncompressed (default)
+    #[default]
+    None,
+    /// gzip-compressed stream
+    Gzip,
+    /// zstd-compressed stream
+    Zstd,
+}
+
+/// How descriptor validation reports violations of the soft limits
+///
+/// Hard limits (`WrapperConfiguration::descriptor_max_fields` and
+/// `descriptor_max_nesting_depth`, capped at their absolute maximums) and invalid field
+/// numbers are always errors; see `conversion::validate_protobuf_descriptor_with_limits`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DescriptorValidationMode {
+    /// Soft limit violations fail validation with a `ConfigurationError` (default)
+    #[default]
+    Strict,
+    /// Soft limit violations are logged as warnings and the descriptor is accepted
+    WarnSoftLimits,
+}
+
+/// Rename applied to Arrow field names before they are matched to Protobuf fields
+///
+/// Used both when generating a descriptor from an Arrow schema and when looking up
+/// descriptor fields during encoding, so the two always agree. Applies to nested
+/// struct fields as well as top-level columns.
+#[derive(Clone, Default)]
+pub enum FieldNameTransform {
+    /// Field names are used as-is (default)
+    #[default]
+    None,
+    /// `userId` / `UserID` become `user_id`
+    ToSnakeCase,
+    /// `user_id` becomes `userId`
+    ToCamelCase,
+    /// Caller-provided rename
+    Custom(Arc<dyn Fn(&str) -> String + Send + Sync>),
+}
+
+impl FieldNameTransform {
+    /// Apply the transform to an Arrow field name
+    pub fn apply<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        match self {
+            FieldNameTransform::None => Cow::Borrowed(name),
+            FieldNameTransform::ToSnakeCase => Cow::Owned(to_snake_case(name)),
+            FieldNameTransform::ToCamelCase => Cow::Owned(to_camel_case(name)),
+            FieldNameTransform::Custom(transform) => Cow::Owned(transform(name)),
+        }
+    }
+}
+
+impl std::fmt::Debug for FieldNameTransform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldNameTransform::None => f.write_str("None"),
+            FieldNameTransform::ToSnakeCase => f.write_str("ToSnakeCase"),
+            FieldNameTransform::ToCamelCase => f.write_str("ToCamelCase"),
+            FieldNameTransform::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// Convert `camelCase` / `PascalCase` to `snake_case`
+///
+/// An underscore starts each word: at a lower-to-upper boundary (`userId`) and before
+/// the last capital of an acronym followed by lowercase (`HTTPServer` -> `http_server`).
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower)
+            {
+                out.push('_');
+            }
+        }
+        out.extend(c.to_lowercase());
+    }
+    out
+}
+
+/// Convert `snake_case` to `camelCase`
+///
+/// Leading underscores are kept; other underscores are dropped and capitalize the next
+/// character.
+fn to_camel_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut capitalize_next = false;
+    for c in name.chars() {
+        if c == '_' && !out.trim_start_matches('_').is_empty() {
+            capitalize_next = true;
+        } else if capitalize_next {
+            out.extend(c.to_uppercase());
+            capitalize_next = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Transform applied to each batch just before it is sent
+///
+/// See `WrapperConfiguration::with_batch_transform`.
+#[derive(Clone)]
+pub struct BatchTransform(
+    pub Arc<dyn Fn(RecordBatch) -> Result<RecordBatch, ZerobusError> + Send + Sync>,
+);
+
+impl BatchTransform {
+    /// Apply the transform to a batch
+    pub fn apply(&self, batch: RecordBatch) -> Result<RecordBatch, ZerobusError> {
+        (self.0)(batch)
+    }
+}
+
+impl std::fmt::Debug for BatchTransform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("BatchTransform(..)")
+    }
+}
+
+/// Signature of a custom value encoder (see `WrapperConfiguration::with_custom_encoder`)
+pub type CustomEncoderFn = dyn Fn(&FieldDescriptorProto, &dyn Array, usize, &mut Vec<u8>) -> Option<Result<(), ZerobusError>>
+    + Send
+    + Sync;
+
+/// Hook encoding values the built-in conversion doesn't support
+///
+/// See `WrapperConfiguration::with_custom_encoder`.
+#[derive(Clone)]
+pub struct CustomEncoder(pub Arc<CustomEncoderFn>);
+
+impl CustomEncoder {
+    /// Encode the value at `row_idx` of `array` for `field`, or `None` to use the
+    /// built-in encoding
+    pub fn encode(
+        &self,
+        field: &FieldDescriptorProto,
+        array: &dyn Array,
+        row_idx: usize,
+        buffer: &mut Vec<u8>,
+    ) -> Option<Result<(), ZerobusError>> {
+        (self.0)(field, array, row_idx, buffer)
+    }
+}
+
+impl std::fmt::Debug for CustomEncoder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CustomEncoder(..)")
+    }
+}
+
 /// Complete configuration for initializing the wrapper
 ///
 /// Represents all configuration needed to initialize a ZerobusWrapper instance,
@@ -117,12 +409,40 @@ pub struct WrapperConfiguration {
     /// When Some(n), keeps last n rotated files, automatically deleting oldest when limit exceeded
     /// When None, unlimited retention (no automatic cleanup)
     pub debug_max_files_retained: Option<usize>,
+    /// Maximum number of rotated debug files across all tables sharing the output directory
+    /// (default: None = no global limit)
+    pub debug_global_max_files: Option<usize>,
+    /// Whether Protobuf debug files start with a format header (default: false)
+    ///
+    /// See `wrapper::debug::parse_proto_header` for the layout.
+    pub debug_proto_header: bool,
     /// Maximum retry attempts for transient failures (default: 5)
     pub retry_max_attempts: u32,
     /// Base delay in milliseconds for exponential backoff (default: 100)
     pub retry_base_delay_ms: u64,
     /// Maximum delay in milliseconds for exponential backoff (default: 30000)
     pub retry_max_delay_ms: u64,
+    /// Maximum attempts for SDK/stream creation (default: None)
+    ///
+    /// When set, creating the SDK or a stream is retried under its own connect retry
+    /// settings, separately from the per-batch retry. When `None`, creation failures are
+    /// retried by the per-batch retry together with the rest of the send.
+    pub connect_retry_max_attempts: Option<u32>,
+    /// Base delay in milliseconds for connect retry backoff (default: 100)
+    pub connect_retry_base_delay_ms: u64,
+    /// Maximum delay in milliseconds for connect retry backoff (default: 30000)
+    pub connect_retry_max_delay_ms: u64,
+    /// Base delay in milliseconds before recreating a closed stream (default: 100)
+    ///
+    /// Grows exponentially with full jitter across stream recreation attempts; see
+    /// `with_stream_recreate_backoff`.
+    pub stream_recreate_base_delay_ms: u64,
+    /// Maximum delay in milliseconds before recreating a closed stream (default: 1000)
+    pub stream_recreate_max_delay_ms: u64,
+    /// Error message substrings that force a retry (default: empty)
+    pub retryable_error_patterns: Vec<String>,
+    /// Error message substrings that stop retrying immediately (default: empty)
+    pub fatal_error_patterns: Vec<String>,
     /// Disable Zerobus SDK transmission while maintaining debug file output (default: false)
     ///
     /// When `true`, the wrapper will skip all Zerobus SDK calls (initialization,
@@ -138,6 +458,213 @@ pub struct WrapperConfiguration {
     /// - CI/CD testing without credentials
     /// - Performance testing of conversion logic
     pub zerobus_writer_disabled: bool,
+    /// Track serialized size of each row in `TransmissionResult::row_sizes` (default: false)
+    ///
+    /// Opt-in because it allocates an extra `(row_idx, size)` entry per converted row.
+    pub track_row_sizes: bool,
+    /// Keep the serialized bytes of rows that fail transmission in
+    /// `TransmissionResult::failed_row_bytes` (default: false)
+    ///
+    /// Memory-heavy for large batches with many failures, so opt-in.
+    pub capture_failed_bytes: bool,
+    /// Mark nullable fields as proto3 `optional` in auto-generated descriptors (default: false)
+    ///
+    /// When enabled, each nullable non-repeated field gets `proto3_optional = true` and a
+    /// synthetic oneof, so Zerobus can distinguish a null (absent) value from a zero value.
+    /// Has no effect on descriptors passed to `send_batch_with_descriptor`.
+    pub explicit_field_presence: bool,
+    /// Maximum number of in-flight record futures buffered per batch before draining (default: 1000)
+    ///
+    /// Acts as a hard cap: pending acknowledgments are flushed and awaited *before* another
+    /// record is buffered once this many are outstanding.
+    pub max_pending_futures: usize,
+    /// Longest a buffered record waits for the next flush (default: None = no age limit)
+    ///
+    /// See `with_max_buffer_age`.
+    pub max_buffer_age: Option<Duration>,
+    /// Hosts or parent domains the endpoint URLs must belong to (default: None = no check)
+    ///
+    /// When set, `zerobus_endpoint` and `unity_catalog_url` hosts must equal an entry or be a
+    /// subdomain of one. Opt-in so custom/self-hosted endpoints are never blocked by default.
+    pub allowed_endpoint_hosts: Option<Vec<String>>,
+    /// Check descriptors passed to `send_batch_with_descriptor` against the batch schema (default: false)
+    ///
+    /// When enabled, a provided descriptor that is missing Arrow columns or disagrees on a
+    /// field's type or repeated label is rejected with a `ConfigurationError` listing every
+    /// mismatch, instead of silently skipping fields during conversion.
+    pub validate_descriptor_schema: bool,
+    /// Column-count divergence tolerated between a provided descriptor and the batch
+    /// (default: None = warn only)
+    ///
+    /// Divergence counts batch columns the descriptor has no field for (skipped during
+    /// conversion) plus descriptor fields the batch has no column for (left unset). Any
+    /// divergence is logged; above this tolerance the batch is rejected with a
+    /// `ConfigurationError`.
+    pub column_mismatch_tolerance: Option<usize>,
+    /// Maximum time `shutdown()` waits for the stream to close (default: 30s)
+    ///
+    /// If the server hangs during close, shutdown logs a warning and proceeds instead of
+    /// blocking process exit indefinitely.
+    pub shutdown_timeout: Duration,
+    /// Copy Arrow schema metadata into debug descriptor options (default: false)
+    ///
+    /// Each schema metadata entry listed in `schema_metadata_keys` is added to the root
+    /// message's `options` of the descriptor written to the debug `.pb` file, so it can be
+    /// correlated with the source schema. The descriptor sent to Zerobus is unchanged.
+    /// Has no effect on descriptors passed to `send_batch_with_descriptor`.
+    pub propagate_schema_metadata: bool,
+    /// Schema metadata keys copied by `propagate_schema_metadata` (default: empty)
+    ///
+    /// See `with_schema_metadata_keys`.
+    pub schema_metadata_keys: Vec<String>,
+    /// Secondary table every batch is also written to (default: None)
+    ///
+    /// Intended for table migrations: each batch is sent to both `table_name` and this table
+    /// concurrently, and the mirror outcome is reported in `TransmissionResult::mirror_result`.
+    pub mirror_table_name: Option<String>,
+    /// Treat mirror table failures as failures of the send (default: false)
+    ///
+    /// When `false`, a mirror failure is only reported in `mirror_result` and never masks
+    /// primary success.
+    pub mirror_failures_fatal: bool,
+    /// Maximum bytes buffered across all concurrent sends of this wrapper (default: None)
+    ///
+    /// Each batch reserves its Arrow memory size from a shared budget before it is
+    /// converted and sent, and releases it on completion, so concurrent `send_batch`
+    /// calls wait instead of growing memory without bound. A batch larger than the whole
+    /// budget is sent alone. `None` means unlimited.
+    pub memory_budget_bytes: Option<usize>,
+    /// Withhold encoder detail from per-row conversion errors (default: false)
+    ///
+    /// Row errors then only identify the row index and field, so no cell content can
+    /// reach logs.
+    pub redact_values_in_errors: bool,
+    /// Field names masked in per-row conversion errors (default: None)
+    ///
+    /// Matching names are replaced by `<sensitive:HASH>` (see `conversion::mask_field_name`).
+    pub sensitive_fields: Option<Vec<String>>,
+    /// Maximum records per second sent to `table_name` (default: None = unlimited)
+    ///
+    /// Enforced by a token bucket shared by every wrapper sending to the same table.
+    /// Error 6006 and failure-rate backoff are checked first and still take precedence.
+    pub rate_limit_records_per_sec: Option<u32>,
+    /// Reaction to schema changes between batches (default: `SchemaEvolution::Strict`)
+    pub schema_evolution: SchemaEvolution,
+    /// Rename applied to Arrow field names (default: `FieldNameTransform::None`)
+    ///
+    /// Used for generated descriptors and for matching descriptor fields during encoding.
+    pub field_name_transform: FieldNameTransform,
+    /// Transform applied to each batch before it is converted and sent (default: None)
+    pub batch_transform: Option<BatchTransform>,
+    /// Arrow schema every incoming batch must match (default: None)
+    ///
+    /// Checked before `batch_transform`; see `with_expected_schema`.
+    pub expected_schema: Option<SchemaRef>,
+    /// Hook for encoding values the built-in conversion doesn't support (default: None)
+    pub custom_encoder: Option<CustomEncoder>,
+    /// Protobuf package for descriptor files written by the debug writer (default: None)
+    ///
+    /// Written descriptors are always `FileDescriptorProto`s with `syntax = "proto3"`.
+    pub proto_package: Option<String>,
+    /// Failed conversions tolerated per batch before conversion is aborted (default: None)
+    ///
+    /// `None` converts every row regardless of failures.
+    pub max_failures_before_abort: Option<usize>,
+    /// Failed rows of a batch stored individually in `failed_rows` (default: None)
+    ///
+    /// `None` stores every failure. See `with_max_tracked_failures`.
+    pub max_tracked_failures: Option<usize>,
+    /// Handling of NaN/Infinity float values (default: `FloatPolicy::Passthrough`)
+    pub float_policy: FloatPolicy,
+    /// Validate that string values are UTF-8 before encoding them (default: false)
+    pub validate_utf8: bool,
+    /// Prefix for observability span and metric names (default: None = `zerobus`)
+    pub telemetry_namespace: Option<String>,
+    /// Accept Int8/Int32 columns (nonzero = true) for bool descriptor fields (default: false)
+    pub lenient_bool_coercion: bool,
+    /// Write the proto3 default for null non-repeated scalar fields instead of omitting them
+    /// (default: false)
+    pub emit_defaults: bool,
+    /// Handling of null elements inside repeated fields
+    /// (default: `NullElementPolicy::SkipNullElements`)
+    pub null_element_policy: NullElementPolicy,
+    /// Unit timestamp values are written in, whatever the Arrow column's unit
+    /// (default: `TimeUnit::Microsecond`)
+    pub timestamp_target_unit: TimeUnit,
+    /// Retry conversion with a generated descriptor when a provided one converts no rows
+    /// (default: false)
+    pub descriptor_fallback: bool,
+    /// Recreate the stream before a send if it has been idle longer than this
+    /// (default: None = never)
+    pub idle_stream_timeout: Option<Duration>,
+    /// Deadline for sending a batch, including retries (default: None = no deadline)
+    pub send_deadline: Option<Duration>,
+    /// Rounds of automatically re-sending rows that failed transiently in transmission
+    /// (default: 0 = disabled)
+    pub auto_retry_failed_rows: u32,
+    /// Compression codec for the ingest stream (default: `CompressionType::None`)
+    pub transmission_compression: CompressionType,
+    /// How descriptor soft limit violations are reported
+    /// (default: `DescriptorValidationMode::Strict`)
+    pub descriptor_validation_mode: DescriptorValidationMode,
+    /// Hard maximum fields per descriptor message (default: 2000, the protocol maximum)
+    pub descriptor_max_fields: usize,
+    /// Hard maximum descriptor nesting depth (default: 10, at most 100)
+    pub descriptor_max_nesting_depth: usize,
+    /// Soft maximum fields per descriptor message (default: None)
+    pub descriptor_soft_max_fields: Option<usize>,
+    /// Soft maximum descriptor nesting depth (default: None)
+    pub descriptor_soft_max_nesting_depth: Option<usize>,
+    /// Row chunks converted concurrently per batch (default: 1 = sequential)
+    ///
+    /// Only takes effect with the `parallel` feature.
+    pub conversion_parallelism: usize,
+    /// Notified when a backoff for the table starts and ends (default: None)
+    pub backoff_observer: Option<Arc<dyn BackoffObserver>>,
+    /// Longest column name accepted when generating a descriptor
+    /// (default: 255, the Databricks limit)
+    pub max_field_name_length: usize,
+    /// Flush the stream at the end of every batch, whatever its outcome, waiting for its
+    /// acknowledgments (default: false)
+    pub flush_every_batch: bool,
+    /// Wait before retrying when the first record fails because the table schema is not yet
+    /// propagated (default: None = use the regular 100ms stream recreation delay)
+    pub schema_propagation_wait: Option<Duration>,
+    /// Fail the whole batch if any row fails conversion, instead of sending the rows that
+    /// converted (default: false)
+    pub conversion_errors_fatal: bool,
+    /// Largest batch, in bytes, sent as a whole (default: None = no limit)
+    ///
+    /// Larger batches are handled according to `oversized_batch_policy`.
+    pub max_batch_bytes: Option<u64>,
+    /// Reaction to batches larger than `max_batch_bytes` (default: `OversizedBatchPolicy::Reject`)
+    pub oversized_batch_policy: OversizedBatchPolicy,
+    /// How null strings are distinguished from empty strings (default: `StringNullPolicy::Omit`)
+    pub string_null_policy: StringNullPolicy,
+    /// Rename applied when looking up fields of a provided descriptor (default: None)
+    ///
+    /// Applied after `field_name_transform`; see `with_field_name_resolver`.
+    pub field_name_resolver: Option<FieldNameTransform>,
+    /// Column whose value names failed rows in per-row error messages (default: None)
+    pub error_key_column: Option<String>,
+    /// Whether record acknowledgments are awaited (default: `DeliveryMode::AtLeastOnce`)
+    pub delivery_mode: DeliveryMode,
+    /// Check the descriptor for `expected_schema` against the table in `new` (default: false)
+    ///
+    /// Requires `expected_schema`; see `with_validate_table_schema_on_startup`.
+    pub validate_table_schema_on_startup: bool,
+    /// Register the descriptor with Zerobus before the first record of each stream (default: false)
+    ///
+    /// See `with_explicit_schema_handshake`.
+    pub explicit_schema_handshake: bool,
+    /// Retry a generated descriptor's first-record rejection with an alternate type mapping (default: false)
+    ///
+    /// See `with_descriptor_auto_recovery`.
+    pub descriptor_auto_recovery: bool,
+    /// Directory of the write-ahead log of unacknowledged records (default: None = disabled)
+    ///
+    /// See `with_wal_dir` and `ZerobusWrapper::recover_wal`.
+    pub wal_dir: Option<PathBuf>,
 }
 
 impl WrapperConfiguration {
@@ -174,13 +701,133 @@ impl WrapperConfiguration {
             debug_flush_interval_secs: 5,
             debug_max_file_size: None,
             debug_max_files_retained: Some(10),
+            debug_global_max_files: None,
+            debug_proto_header: false,
             retry_max_attempts: 5,
             retry_base_delay_ms: 100,
             retry_max_delay_ms: 30000,
+            connect_retry_max_attempts: None,
+            connect_retry_base_delay_ms: 100,
+            connect_retry_max_delay_ms: 30000,
+            stream_recreate_base_delay_ms: 100,
+            stream_recreate_max_delay_ms: 1000,
+            retryable_error_patterns: Vec::new(),
+            fatal_error_patterns: Vec::new(),
             zerobus_writer_disabled: false,
+            track_row_sizes: false,
+            capture_failed_bytes: false,
+            explicit_field_presence: false,
+            max_pending_futures: 1000,
+            max_buffer_age: None,
+            allowed_endpoint_hosts: None,
+            validate_descriptor_schema: false,
+            column_mismatch_tolerance: None,
+            shutdown_timeout: Duration::from_secs(30),
+            propagate_schema_metadata: false,
+            schema_metadata_keys: Vec::new(),
+            mirror_table_name: None,
+            mirror_failures_fatal: false,
+            memory_budget_bytes: None,
+            redact_values_in_errors: false,
+            sensitive_fields: None,
+            rate_limit_records_per_sec: None,
+            schema_evolution: SchemaEvolution::Strict,
+            field_name_transform: FieldNameTransform::None,
+            batch_transform: None,
+            expected_schema: None,
+            custom_encoder: None,
+            proto_package: None,
+            max_failures_before_abort: None,
+            max_tracked_failures: None,
+            float_policy: FloatPolicy::Passthrough,
+            validate_utf8: false,
+            telemetry_namespace: None,
+            lenient_bool_coercion: false,
+            emit_defaults: false,
+            null_element_policy: NullElementPolicy::SkipNullElements,
+            timestamp_target_unit: TimeUnit::Microsecond,
+            descriptor_fallback: false,
+            idle_stream_timeout: None,
+            send_deadline: None,
+            auto_retry_failed_rows: 0,
+            transmission_compression: CompressionType::None,
+            descriptor_validation_mode: DescriptorValidationMode::Strict,
+            descriptor_max_fields: MAX_FIELDS_PER_MESSAGE,
+            descriptor_max_nesting_depth: MAX_NESTING_DEPTH,
+            descriptor_soft_max_fields: None,
+            descriptor_soft_max_nesting_depth: None,
+            conversion_parallelism: 1,
+            backoff_observer: None,
+            max_field_name_length: MAX_FIELD_NAME_LENGTH,
+            flush_every_batch: false,
+            schema_propagation_wait: None,
+            conversion_errors_fatal: false,
+            max_batch_bytes: None,
+            oversized_batch_policy: OversizedBatchPolicy::Reject,
+            string_null_policy: StringNullPolicy::Omit,
+            field_name_resolver: None,
+            error_key_column: None,
+            delivery_mode: DeliveryMode::AtLeastOnce,
+            validate_table_schema_on_startup: false,
+            explicit_schema_handshake: false,
+            descriptor_auto_recovery: false,
+            wal_dir: None,
         }
     }
 
+    /// Start a typed builder, which requires an endpoint and table name at compile time
+    ///
+    /// See [`WrapperConfigurationBuilder`](crate::config::WrapperConfigurationBuilder).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// # fn example() -> Result<(), arrow_zerobus_sdk_wrapper::ZerobusError> {
+    /// let config = WrapperConfiguration::builder()
+    ///     .endpoint("https://workspace.cloud.databricks.com")
+    ///     .table_name("my_table")
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn builder() -> crate::config::WrapperConfigurationBuilder {
+        crate::config::WrapperConfigurationBuilder::new()
+    }
+
+    /// Load and validate a configuration from a TOML file
+    ///
+    /// See `config::loader::ConfigFile` for the file layout. Credentials are read from
+    /// the environment variables named by `client_id_env`/`client_secret_env`, never
+    /// from the file itself.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// // zerobus_endpoint = "https://workspace.cloud.databricks.com"
+    /// // table_name = "my_table"
+    /// // client_id_env = "ZEROBUS_CLIENT_ID"
+    /// // client_secret_env = "ZEROBUS_CLIENT_SECRET"
+    /// //
+    /// // [retry]
+    /// // max_attempts = 3
+    /// let config = WrapperConfiguration::from_toml_file("zerobus.toml")?;
+    /// # Ok::<(), arrow_zerobus_sdk_wrapper::ZerobusError>(())
+    /// ```
+    pub fn from_toml_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, ZerobusError> {
+        crate::config::loader::load_from_toml_file(path)
+    }
+
+    /// Load and validate a configuration from a YAML file
+    ///
+    /// Same layout and credential handling as `from_toml_file`.
+    pub fn from_yaml_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, ZerobusError> {
+        crate::config::loader::load_from_yaml_file(path)
+    }
+
     /// Set OAuth2 credentials
     ///
     /// # Arguments
@@ -344,6 +991,76 @@ impl WrapperConfiguration {
         self
     }
 
+    /// Set maximum number of rotated debug files across all tables
+    ///
+    /// `debug_max_files_retained` applies per table and file type, so a process writing
+    /// many tables to one debug directory can still fill a shared volume. With a global
+    /// limit, the debug writers of all wrappers in the process with the same output
+    /// directory share a tracker: whenever any of them rotates a file, the oldest rotated
+    /// Arrow and Protobuf files of any table are deleted until at most `max_files` remain.
+    /// Files still being written to are never deleted. Both limits can be combined.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_files` - Maximum rotated files across all tables, or `None` for no global limit
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    /// use std::path::PathBuf;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_debug_arrow_enabled(true)
+    /// .with_debug_output(PathBuf::from("./debug_output"))
+    /// .with_debug_global_max_files(Some(100));
+    /// ```
+    pub fn with_debug_global_max_files(mut self, max_files: Option<usize>) -> Self {
+        self.debug_global_max_files = max_files;
+        self
+    }
+
+    /// Set whether Protobuf debug files start with a format header
+    ///
+    /// When enabled, each Protobuf debug file (including rotated ones) begins with a magic
+    /// marker, a version byte, and the fingerprint of the descriptor in effect, so reading
+    /// tools can check compatibility before parsing. Parse it with
+    /// `wrapper::debug::parse_proto_header`.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to write the header
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    /// use std::path::PathBuf;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_debug_protobuf_enabled(true)
+    /// .with_debug_output(PathBuf::from("./debug_output"))
+    /// .with_debug_proto_header(true);
+    /// ```
+    pub fn with_debug_proto_header(mut self, enabled: bool) -> Self {
+        self.debug_proto_header = enabled;
+        self
+    }
+
     /// Set retry configuration
     ///
     /// # Arguments
@@ -363,11 +1080,18 @@ impl WrapperConfiguration {
         self
     }
 
-    /// Set writer disabled mode
+    /// Set retry configuration for SDK/stream creation
+    ///
+    /// Connection establishment (e.g. a transient network failure during token fetch) often
+    /// warrants different tuning than steady-state ingest, e.g. retrying connection
+    /// aggressively while failing ingest fast. Once connect retries are exhausted the send
+    /// fails without being retried again by the per-batch retry.
     ///
     /// # Arguments
     ///
-    /// * `disabled` - If `true`, disables Zerobus SDK transmission while maintaining debug output
+    /// * `max_attempts` - Maximum connect attempts
+    /// * `base_delay_ms` - Base delay in milliseconds for exponential backoff
+    /// * `max_delay_ms` - Maximum delay in milliseconds
     ///
     /// # Returns
     ///
@@ -377,66 +1101,2160 @@ impl WrapperConfiguration {
     ///
     /// ```no_run
     /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
-    /// use std::path::PathBuf;
     ///
     /// let config = WrapperConfiguration::new(
     ///     "https://workspace.cloud.databricks.com".to_string(),
     ///     "my_table".to_string(),
     /// )
-    /// .with_debug_output(PathBuf::from("./debug_output"))
-    /// .with_zerobus_writer_disabled(true);
+    /// .with_retry_config(1, 100, 1000)
+    /// .with_connect_retry_config(10, 200, 10000);
     /// ```
-    pub fn with_zerobus_writer_disabled(mut self, disabled: bool) -> Self {
-        self.zerobus_writer_disabled = disabled;
+    pub fn with_connect_retry_config(
+        mut self,
+        max_attempts: u32,
+        base_delay_ms: u64,
+        max_delay_ms: u64,
+    ) -> Self {
+        self.connect_retry_max_attempts = Some(max_attempts);
+        self.connect_retry_base_delay_ms = base_delay_ms;
+        self.connect_retry_max_delay_ms = max_delay_ms;
         self
     }
 
-    /// Validate configuration
+    /// Set the backoff before recreating a stream that closed mid-batch
     ///
-    /// Checks that all required fields are present and valid.
+    /// When the stream closes during a batch, the wrapper recreates it and retries the
+    /// unacknowledged rows up to 3 times. The wait before each recreation doubles from
+    /// `base_delay_ms` up to `max_delay_ms`, with full jitter as for `with_retry_config`,
+    /// so wrappers hit by the same outage don't reconnect in lockstep.
+    /// `schema_propagation_wait` still applies instead when the table is not ready yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_delay_ms` - Delay bound in milliseconds before the first recreation
+    /// * `max_delay_ms` - Maximum delay bound in milliseconds
     ///
     /// # Returns
     ///
-    /// Returns `Ok(())` if configuration is valid, or `Err(ZerobusError)` if invalid.
+    /// Self for method chaining
     ///
-    /// # Errors
+    /// # Example
     ///
-    /// Returns `ConfigurationError` if:
-    /// - `zerobus_endpoint` is not a valid URL starting with `https://` or `http://`
-    /// - `debug_enabled` is true but `debug_output_dir` is not provided
-    /// - `zerobus_writer_disabled` is true but `debug_enabled` is false
-    /// - `retry_max_attempts` is 0
-    /// - `debug_flush_interval_secs` is 0
-    pub fn validate(&self) -> Result<(), ZerobusError> {
-        // Validate endpoint URL
-        if !self.zerobus_endpoint.starts_with("https://")
-            && !self.zerobus_endpoint.starts_with("http://")
-        {
-            return Err(ZerobusError::ConfigurationError(format!(
-                "zerobus_endpoint must start with 'https://' or 'http://', got: '{}'",
-                self.zerobus_endpoint
-            )));
-        }
-
-        // Validate table name: Unity Catalog format (catalog.schema.table, schema.table, or table)
-        // Each part must contain only ASCII letters, digits, and underscores (Zerobus requirement)
-        // Dots are allowed as separators between catalog, schema, and table name parts
-        // Unity Catalog format:
-        //   1 part: table (index 0 = table)
-        //   2 parts: schema.table (index 0 = schema, index 1 = table)
-        //   3 parts: catalog.schema.table (index 0 = catalog, index 1 = schema, index 2 = table)
-        let parts: Vec<&str> = self.table_name.split('.').collect();
-        let num_parts = parts.len();
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_stream_recreate_backoff(250, 4000);
+    /// ```
+    pub fn with_stream_recreate_backoff(mut self, base_delay_ms: u64, max_delay_ms: u64) -> Self {
+        self.stream_recreate_base_delay_ms = base_delay_ms;
+        self.stream_recreate_max_delay_ms = max_delay_ms;
+        self
+    }
 
-        if num_parts > 3 {
-            return Err(ZerobusError::ConfigurationError(format!(
-                "table_name has too many parts ({}). Must be in format 'table', 'schema.table', or 'catalog.schema.table'. Got: '{}'",
-                num_parts, self.table_name
-            )));
-        }
+    /// Set error message substrings that make an error retryable
+    ///
+    /// A stopgap until SDK errors are fully structured: lets operators mark a newly
+    /// discovered transient error condition as retryable from configuration, without a
+    /// code release. An error whose message contains any of these substrings is retried
+    /// (by the per-batch and connect retries, and by `with_auto_retry_failed_rows`) even if
+    /// its type normally isn't. Fatal patterns take precedence (see
+    /// `with_fatal_error_patterns`). Expect this to be superseded by structured error
+    /// codes.
+    ///
+    /// # Arguments
+    ///
+    /// * `patterns` - Case-sensitive substrings of the error message (must not be empty)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_retryable_error_patterns(vec!["temporarily unavailable".to_string()]);
+    /// ```
+    pub fn with_retryable_error_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.retryable_error_patterns = patterns;
+        self
+    }
 
-        // Map index to part name based on number of parts and index
-        // 1 part: [0] = table
+    /// Set error message substrings that make an error fatal
+    ///
+    /// The counterpart of `with_retryable_error_patterns`, and likewise a stopgap pending
+    /// structured errors: an error whose message contains any of these substrings stops
+    /// retrying immediately, even if its type is normally retried. Checked before the
+    /// retryable patterns.
+    ///
+    /// # Arguments
+    ///
+    /// * `patterns` - Case-sensitive substrings of the error message (must not be empty)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_fatal_error_patterns(vec!["table does not exist".to_string()]);
+    /// ```
+    pub fn with_fatal_error_patterns(mut self, patterns: Vec<String>) -> Self {
+        self.fatal_error_patterns = patterns;
+        self
+    }
+
+    /// Set writer disabled mode
+    ///
+    /// # Arguments
+    ///
+    /// * `disabled` - If `true`, disables Zerobus SDK transmission while maintaining debug output
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    /// use std::path::PathBuf;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_debug_output(PathBuf::from("./debug_output"))
+    /// .with_zerobus_writer_disabled(true);
+    /// ```
+    pub fn with_zerobus_writer_disabled(mut self, disabled: bool) -> Self {
+        self.zerobus_writer_disabled = disabled;
+        self
+    }
+
+    /// Set per-row size tracking
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - If `true`, `TransmissionResult::row_sizes` is populated with the
+    ///   serialized Protobuf size of every successfully converted row
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_track_row_sizes(true);
+    /// ```
+    pub fn with_track_row_sizes(mut self, enabled: bool) -> Self {
+        self.track_row_sizes = enabled;
+        self
+    }
+
+    /// Set whether to keep the serialized bytes of rows that fail transmission
+    ///
+    /// Rows that convert successfully but are rejected by Zerobus keep their exact
+    /// Protobuf payload, so it can be decoded offline. The bytes are retained until the
+    /// `TransmissionResult` is dropped, which is memory-heavy for large failing batches.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - If `true`, `TransmissionResult::failed_row_bytes` is populated with the
+    ///   serialized bytes of every row that failed transmission
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_capture_failed_bytes(true);
+    /// ```
+    pub fn with_capture_failed_bytes(mut self, enabled: bool) -> Self {
+        self.capture_failed_bytes = enabled;
+        self
+    }
+
+    /// Set proto3 explicit field presence for auto-generated descriptors
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - If `true`, nullable Arrow fields are generated as proto3 `optional`
+    ///   fields (with a synthetic oneof) so null is distinguishable from the default value
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_explicit_field_presence(true);
+    /// ```
+    pub fn with_explicit_field_presence(mut self, enabled: bool) -> Self {
+        self.explicit_field_presence = enabled;
+        self
+    }
+
+    /// Set propagation of Arrow schema metadata into auto-generated debug descriptors
+    ///
+    /// Only the keys listed with `with_schema_metadata_keys` are copied, and only into the
+    /// descriptor written to the debug `.pb` file: the descriptor a stream is created with
+    /// never carries them.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - If `true`, the selected `Schema::metadata()` entries are copied into
+    ///   the debug descriptor's message options (see `conversion::schema_metadata_options`)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_propagate_schema_metadata(true);
+    /// ```
+    pub fn with_propagate_schema_metadata(mut self, enabled: bool) -> Self {
+        self.propagate_schema_metadata = enabled;
+        self
+    }
+
+    /// Select the schema metadata keys `propagate_schema_metadata` copies
+    ///
+    /// Keys must be Protobuf identifiers (letters, digits and underscores, not starting
+    /// with a digit), since each becomes an option name. Listed keys missing from a
+    /// batch's schema are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - Metadata keys to copy, e.g. `source_system` and `source_version`
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_propagate_schema_metadata(true)
+    /// .with_schema_metadata_keys(vec!["source_version".to_string()]);
+    /// ```
+    pub fn with_schema_metadata_keys(mut self, keys: Vec<String>) -> Self {
+        self.schema_metadata_keys = keys;
+        self
+    }
+
+    /// Set a mirror table to dual-write every batch to
+    ///
+    /// # Arguments
+    ///
+    /// * `table_name` - Secondary table name (same format as the primary `table_name`)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "main.default.events".to_string(),
+    /// )
+    /// .with_mirror_table("main.default.events_v2".to_string());
+    /// ```
+    pub fn with_mirror_table(mut self, table_name: String) -> Self {
+        self.mirror_table_name = Some(table_name);
+        self
+    }
+
+    /// Set whether mirror table failures fail the send
+    ///
+    /// # Arguments
+    ///
+    /// * `fatal` - If `true`, a failed mirror send sets `success = false` and `error` on the
+    ///   combined result even when the primary send succeeded
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    pub fn with_mirror_failures_fatal(mut self, fatal: bool) -> Self {
+        self.mirror_failures_fatal = fatal;
+        self
+    }
+
+    /// Configuration used for the mirror table, if one is set
+    ///
+    /// Identical to this configuration except for the table name. Observability is
+    /// disabled for the mirror, since it is already initialized by the primary.
+    pub(crate) fn mirror_configuration(&self) -> Option<WrapperConfiguration> {
+        self.mirror_table_name
+            .as_ref()
+            .map(|mirror| WrapperConfiguration {
+                table_name: mirror.clone(),
+                mirror_table_name: None,
+                // The mirror shares the primary wrapper's budget instead of its own
+                memory_budget_bytes: None,
+                observability_enabled: false,
+                observability_config: None,
+                ..self.clone()
+            })
+    }
+
+    /// Set maximum number of in-flight record futures per batch
+    ///
+    /// # Arguments
+    ///
+    /// * `max_pending` - Maximum buffered futures before awaiting acknowledgments (must be > 0)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_max_pending_futures(250);
+    /// ```
+    pub fn with_max_pending_futures(mut self, max_pending: usize) -> Self {
+        self.max_pending_futures = max_pending;
+        self
+    }
+
+    /// Set the longest a buffered record waits before the stream is flushed
+    ///
+    /// Records are flushed every `max_pending_futures` records or 10MB, which favours
+    /// throughput: a slow trickle of records (e.g. under `rate_limit_records_per_sec`)
+    /// waits for the buffer to fill. With a maximum age, buffered records are also flushed
+    /// and acknowledged once the oldest of them has waited `max_age`, checked before each
+    /// record is sent, so latency stays bounded at low volume.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_age` - Age of the oldest un-flushed record that triggers a flush (must be > 0)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    /// use std::time::Duration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_max_buffer_age(Duration::from_millis(200));
+    /// ```
+    pub fn with_max_buffer_age(mut self, max_age: Duration) -> Self {
+        self.max_buffer_age = Some(max_age);
+        self
+    }
+
+    /// Set validation of provided descriptors against the batch schema
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - If `true`, descriptors passed to `send_batch_with_descriptor` are checked
+    ///   with `validate_descriptor_against_schema` before conversion and mismatches are rejected
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_descriptor_schema_validation(true);
+    /// ```
+    pub fn with_descriptor_schema_validation(mut self, enabled: bool) -> Self {
+        self.validate_descriptor_schema = enabled;
+        self
+    }
+
+    /// Set the column-count divergence tolerated for provided descriptors
+    ///
+    /// A cheap guard against sending a batch with a descriptor generated from a wider or
+    /// narrower schema, distinct from the full type-level check enabled by
+    /// `with_descriptor_schema_validation`.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_mismatched_columns` - Maximum number of batch columns missing from the
+    ///   descriptor plus descriptor fields missing from the batch (`0` requires an exact
+    ///   match)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_column_mismatch_tolerance(0);
+    /// ```
+    pub fn with_column_mismatch_tolerance(mut self, max_mismatched_columns: usize) -> Self {
+        self.column_mismatch_tolerance = Some(max_mismatched_columns);
+        self
+    }
+
+    /// Set graceful-shutdown timeout
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum time `shutdown()` waits for the stream to close (must be > 0)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    /// use std::time::Duration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_shutdown_timeout(Duration::from_secs(10));
+    /// ```
+    pub fn with_shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    /// Set the idle time after which the stream is recreated before the next send
+    ///
+    /// A stream left open but unused may be dropped by the server without notice, so the
+    /// next batch only finds out when its first record fails. With this set, a send that
+    /// finds no batch was sent for longer than `timeout` closes the stream first and
+    /// sends on a new one.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Idle time after which the stream is recreated (must be > 0)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    /// use std::time::Duration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_idle_stream_timeout(Duration::from_secs(300));
+    /// ```
+    pub fn with_idle_stream_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_stream_timeout = Some(timeout);
+        self
+    }
+
+    /// Set a deadline for sending each batch
+    ///
+    /// Covers stream creation, writing and acknowledging every record, and retries. A
+    /// batch that misses the deadline is abandoned and returned as a batch-level
+    /// `TransmissionError`; records already written before the deadline may still be
+    /// ingested, so re-sending the batch can duplicate rows.
+    ///
+    /// The Zerobus SDK does not accept a deadline on `create_stream` or `ingest_record`,
+    /// so the deadline cannot be propagated to the server; it is enforced on the client
+    /// with `tokio::time::timeout`, and server-side work is not cancelled.
+    ///
+    /// # Arguments
+    ///
+    /// * `deadline` - Maximum time to send a batch (must be > 0)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    /// use std::time::Duration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_send_deadline(Duration::from_secs(30));
+    /// ```
+    pub fn with_send_deadline(mut self, deadline: Duration) -> Self {
+        self.send_deadline = Some(deadline);
+        self
+    }
+
+    /// Automatically re-send rows that failed in transmission
+    ///
+    /// When a batch partially succeeds, `send_batch` and `send_batch_with_descriptor`
+    /// re-send only the rows whose transmission failed with a retryable error
+    /// (`ZerobusError::is_retryable`), up to `max_rounds` times, before returning. Rows that
+    /// succeed in a later round are folded into `successful_rows` (with their ack IDs), and
+    /// rows that still fail keep the error of their last round. Conversion failures are
+    /// never re-sent, and rounds stop early once no retryable failures remain or a round
+    /// fails at batch level. Each round is a full send with its own retries; the mirror
+    /// table, if any, is not re-sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_rounds` - Maximum re-send rounds (0 disables automatic re-sending)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_auto_retry_failed_rows(2);
+    /// ```
+    pub fn with_auto_retry_failed_rows(mut self, max_rounds: u32) -> Self {
+        self.auto_retry_failed_rows = max_rounds;
+        self
+    }
+
+    /// Set the compression codec for the ingest stream
+    ///
+    /// Compressing the stream would cut bandwidth for large, text-heavy batches (typically
+    /// 3-5x for gzip, slightly more for zstd). However, the Zerobus SDK this crate is built
+    /// on (`databricks-zerobus-ingest-sdk` 0.1.0) has no codec option for stream creation, so
+    /// any codec other than `CompressionType::None` fails validation with a
+    /// `ConfigurationError` rather than being silently ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `compression` - Codec for the ingest stream
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::{CompressionType, WrapperConfiguration};
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_transmission_compression(CompressionType::None);
+    /// ```
+    pub fn with_transmission_compression(mut self, compression: CompressionType) -> Self {
+        self.transmission_compression = compression;
+        self
+    }
+
+    /// Set maximum total in-memory size across concurrent batches
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - Byte budget shared by all in-progress sends (must be > 0 and at most
+    ///   `u32::MAX`)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_memory_budget(256 * 1024 * 1024);
+    /// ```
+    pub fn with_memory_budget(mut self, bytes: usize) -> Self {
+        self.memory_budget_bytes = Some(bytes);
+        self
+    }
+
+    /// Set whether per-row conversion errors withhold encoder detail
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether row errors only identify the row index and field
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_redact_values_in_errors(true);
+    /// ```
+    pub fn with_redact_values_in_errors(mut self, enabled: bool) -> Self {
+        self.redact_values_in_errors = enabled;
+        self
+    }
+
+    /// Set field names to mask in per-row conversion errors
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - Field names (top-level or nested) that must not appear in errors
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_sensitive_fields(vec!["email".to_string(), "ssn".to_string()]);
+    /// ```
+    pub fn with_sensitive_fields(mut self, fields: Vec<String>) -> Self {
+        self.sensitive_fields = Some(fields);
+        self
+    }
+
+    /// Set client-side rate limit for the target table
+    ///
+    /// # Arguments
+    ///
+    /// * `records_per_sec` - Maximum records per second across all sends to the table (must be > 0)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_rate_limit(5000);
+    /// ```
+    pub fn with_rate_limit(mut self, records_per_sec: u32) -> Self {
+        self.rate_limit_records_per_sec = Some(records_per_sec);
+        self
+    }
+
+    /// Set how schema changes between batches are handled
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - `SchemaEvolution::AddOnly` to evolve the descriptor when new optional
+    ///   fields appear, or `SchemaEvolution::Strict` to leave descriptors unreconciled
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::{SchemaEvolution, WrapperConfiguration};
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_schema_evolution(SchemaEvolution::AddOnly);
+    /// ```
+    pub fn with_schema_evolution(mut self, mode: SchemaEvolution) -> Self {
+        self.schema_evolution = mode;
+        self
+    }
+
+    /// Set the rename applied to Arrow field names
+    ///
+    /// Use when Arrow column names follow a different convention than the Zerobus table,
+    /// e.g. camelCase columns written to a snake_case table. Column names are validated
+    /// after the transform.
+    ///
+    /// # Arguments
+    ///
+    /// * `transform` - Rename applied to every Arrow field name, including nested fields
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::{FieldNameTransform, WrapperConfiguration};
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_field_name_transform(FieldNameTransform::ToSnakeCase);
+    /// ```
+    pub fn with_field_name_transform(mut self, transform: FieldNameTransform) -> Self {
+        self.field_name_transform = transform;
+        self
+    }
+
+    /// Set a transform applied to each batch just before it is sent
+    ///
+    /// Lets callers drop or mask columns (e.g. remove a PII column) without mutating
+    /// their upstream batch. The transformed batch is what gets converted, sent and
+    /// written to debug files, so row indices in `TransmissionResult` refer to it:
+    /// pass the transformed batch, not the original, to `extract_failed_batch` /
+    /// `extract_successful_batch` if the transform changes the rows. An error from the
+    /// transform is returned from the send call.
+    ///
+    /// # Arguments
+    ///
+    /// * `transform` - Function mapping each outgoing batch to the batch actually sent
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::{WrapperConfiguration, ZerobusError};
+    /// use std::sync::Arc;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_batch_transform(Arc::new(|batch| {
+    ///     // Drop the `email` column before sending
+    ///     let mut batch = batch;
+    ///     if let Ok(idx) = batch.schema().index_of("email") {
+    ///         batch.remove_column(idx);
+    ///     }
+    ///     Ok::<_, ZerobusError>(batch)
+    /// }));
+    /// ```
+    pub fn with_batch_transform(
+        mut self,
+        transform: Arc<dyn Fn(RecordBatch) -> Result<RecordBatch, ZerobusError> + Send + Sync>,
+    ) -> Self {
+        self.batch_transform = Some(BatchTransform(transform));
+        self
+    }
+
+    /// Require every incoming batch to match an expected Arrow schema
+    ///
+    /// A guardrail against upstream schema drift: a batch whose columns were added,
+    /// removed or retyped compared to `schema` fails with a batch-level
+    /// `ConfigurationError` listing the differences, before any transform or conversion.
+    /// Unlike `with_descriptor_schema_validation`, which checks the batch against the
+    /// descriptor, this detects that the producer changed.
+    ///
+    /// Columns are compared by name and data type; column order, nullability and
+    /// metadata may differ (see `conversion::diff_schemas`).
+    ///
+    /// # Arguments
+    ///
+    /// * `schema` - Schema the producer is expected to send
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow::datatypes::{DataType, Field, Schema};
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    /// use std::sync::Arc;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_expected_schema(Arc::new(Schema::new(vec![
+    ///     Field::new("id", DataType::Int64, false),
+    ///     Field::new("name", DataType::Utf8, true),
+    /// ])));
+    /// ```
+    pub fn with_expected_schema(mut self, schema: SchemaRef) -> Self {
+        self.expected_schema = Some(schema);
+        self
+    }
+
+    /// Set a hook for encoding Arrow values the built-in conversion doesn't support
+    ///
+    /// The hook is called for every non-null value (including nested struct fields and
+    /// whole list values of repeated fields) with the target field descriptor, the Arrow
+    /// array and the row index, before the built-in encoding. Returning `Some(Ok(()))`
+    /// means the hook wrote the value, `Some(Err(_))` fails the row, and `None` falls
+    /// through to the built-in encoding (which fails the row for unsupported types).
+    ///
+    /// The hook is responsible for the wire format: it must append a complete Protobuf
+    /// field to the buffer - the tag (`field.number` and the wire type) followed by the
+    /// value - matching the descriptor's field type. Nothing it writes is validated.
+    /// Setting a hook disables the flat-schema fast path, since every value must be
+    /// offered to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `encoder` - Hook called as `encoder(field, array, row_idx, buffer)`
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow::array::{Array, Int8Array};
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    /// use std::sync::Arc;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_custom_encoder(Arc::new(|field, array, row, buffer| {
+    ///     // Encode Int8 columns as int32 varints
+    ///     let values = array.as_any().downcast_ref::<Int8Array>()?;
+    ///     let tag = (field.number.unwrap_or(0) as u64) << 3; // wire type 0 (varint)
+    ///     for mut v in [tag, values.value(row) as i64 as u64] {
+    ///         while v >= 0x80 {
+    ///             buffer.push((v as u8) | 0x80);
+    ///             v >>= 7;
+    ///         }
+    ///         buffer.push(v as u8);
+    ///     }
+    ///     Some(Ok(()))
+    /// }));
+    /// ```
+    pub fn with_custom_encoder(mut self, encoder: Arc<CustomEncoderFn>) -> Self {
+        self.custom_encoder = Some(CustomEncoder(encoder));
+        self
+    }
+
+    /// Set Protobuf package for written descriptor files
+    ///
+    /// Debug descriptor files (`zerobus/descriptors/*.pb`) are written as a
+    /// `FileDescriptorProto` with `syntax = "proto3"`; this sets its `package`, for
+    /// tooling that expects one. Does not affect the descriptor sent to Zerobus.
+    ///
+    /// # Arguments
+    ///
+    /// * `package` - Protobuf package name (e.g. `com.example.events`)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_debug_output("./debug_output".into())
+    /// .with_proto_package("com.example.events".to_string());
+    /// ```
+    pub fn with_proto_package(mut self, package: String) -> Self {
+        self.proto_package = Some(package);
+        self
+    }
+
+    /// Set maximum failed rows before a batch's conversion is aborted
+    ///
+    /// Once more than `max_failures` rows of a batch have failed conversion, the remaining
+    /// rows are not converted; they are reported as failed with an error saying the batch
+    /// was aborted, and rows converted so far are still sent. Bounds the work spent on a
+    /// hopeless batch, e.g. one with the wrong schema where every row fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_failures` - Failed rows tolerated before aborting (0 = abort after the first)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_max_failures_before_abort(500);
+    /// ```
+    pub fn with_max_failures_before_abort(mut self, max_failures: usize) -> Self {
+        self.max_failures_before_abort = Some(max_failures);
+        self
+    }
+
+    /// Set maximum failed rows of a batch whose errors are kept
+    ///
+    /// Once `max_tracked` failures of a batch are stored in `TransmissionResult::failed_rows`,
+    /// further failures are only counted in `failed_count`, and the result's
+    /// `failures_truncated` flag is set. Bounds the memory held by a batch where millions of
+    /// rows fail, while keeping counts exact. The lowest row indices are kept. Untracked rows
+    /// are not re-sent by `auto_retry_failed_rows` and have no entry in error type counts.
+    ///
+    /// Conversion still materializes a batch's errors before they are truncated; combine
+    /// with `with_max_failures_before_abort` to bound that as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_tracked` - Failed rows stored per batch (0 = count failures only)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_max_tracked_failures(10_000);
+    /// ```
+    pub fn with_max_tracked_failures(mut self, max_tracked: usize) -> Self {
+        self.max_tracked_failures = Some(max_tracked);
+        self
+    }
+
+    /// Set how NaN/Infinity float values are encoded
+    ///
+    /// Applies to Float32/Float64 columns, including list elements and nested struct
+    /// fields. With `FloatPolicy::ErrorOnNonFinite` the affected rows are reported as
+    /// failed rows instead of being sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - Handling of non-finite values
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::{FloatPolicy, WrapperConfiguration};
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_float_policy(FloatPolicy::CoerceToNull);
+    /// ```
+    pub fn with_float_policy(mut self, policy: FloatPolicy) -> Self {
+        self.float_policy = policy;
+        self
+    }
+
+    /// Set UTF-8 validation of string values
+    ///
+    /// Arrow string arrays are normally guaranteed to hold valid UTF-8, so this is off by
+    /// default. Enable it for batches built from raw buffers or binary sources, where an
+    /// invalid sequence would otherwise be sent and rejected by Zerobus. Rows with invalid
+    /// UTF-8 in a string field are reported as failed rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - If `true`, Utf8/LargeUtf8 values are validated before encoding
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_validate_utf8(true);
+    /// ```
+    pub fn with_validate_utf8(mut self, enabled: bool) -> Self {
+        self.validate_utf8 = enabled;
+        self
+    }
+
+    /// Set the namespace prefixed to observability span and metric names
+    ///
+    /// Span names become `{namespace}.send_batch` and metric names
+    /// `{namespace}.batch.size_bytes` etc. instead of starting with `zerobus`, so several
+    /// services exporting to the same backend don't collide. The namespace must start
+    /// with an ASCII letter and contain only ASCII letters, digits and underscores.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - Metric name segment, e.g. `"orders_ingest"`
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_telemetry_namespace("orders_ingest".to_string());
+    /// ```
+    pub fn with_telemetry_namespace(mut self, namespace: String) -> Self {
+        self.telemetry_namespace = Some(namespace);
+        self
+    }
+
+    /// Accept integer-backed booleans for bool descriptor fields
+    ///
+    /// Some upstreams store booleans as Int8 or Int32 (0/1) columns while the descriptor
+    /// declares a protobuf `bool`. By default such columns fail every row with
+    /// "Expected BooleanArray"; with this enabled, they are encoded with any nonzero value
+    /// as `true`.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - If `true`, Int8/Int32 columns are accepted for bool fields
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_lenient_bool_coercion(true);
+    /// ```
+    pub fn with_lenient_bool_coercion(mut self, enabled: bool) -> Self {
+        self.lenient_bool_coercion = enabled;
+        self
+    }
+
+    /// Emit proto3 default values for null fields
+    ///
+    /// By default a null value is omitted from the row's message, as proto3 does for unset
+    /// fields. Some consumers expect every field to be present; with this enabled, a null
+    /// value in a non-repeated scalar field is written as the field's proto3 default (0,
+    /// `false`, or an empty string/bytes) instead. Nested message and repeated fields are
+    /// still omitted, as proto3 has no default for them on the wire. Note that with
+    /// `explicit_field_presence`, a written default reads back as present.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - If `true`, null scalar values are written as their default
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_emit_defaults(true);
+    /// ```
+    pub fn with_emit_defaults(mut self, enabled: bool) -> Self {
+        self.emit_defaults = enabled;
+        self
+    }
+
+    /// Set how null elements inside repeated fields are encoded
+    ///
+    /// Applies to list columns, including lists of structs and lists nested in struct
+    /// fields. By default null elements are skipped, which shortens the list on the wire;
+    /// with `NullElementPolicy::ErrorOnNullElement` the affected rows are reported as
+    /// failed rows instead of being sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - Handling of null list elements
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::{NullElementPolicy, WrapperConfiguration};
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_null_element_policy(NullElementPolicy::EmitDefaultForNull);
+    /// ```
+    pub fn with_null_element_policy(mut self, policy: NullElementPolicy) -> Self {
+        self.null_element_policy = policy;
+        self
+    }
+
+    /// Set the unit timestamp values are written in
+    ///
+    /// Timestamp columns are encoded as Int64 counts since the epoch. By default they are
+    /// normalized to microseconds, the unit Databricks `TIMESTAMP` columns use; tables that
+    /// expect milliseconds or nanoseconds can set that unit instead. Values are converted
+    /// from each Arrow column's own unit, truncating toward zero when the target unit is
+    /// coarser. Applies to every timestamp column, including list elements and nested
+    /// struct fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `unit` - Unit to write timestamps in
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow::datatypes::TimeUnit;
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_timestamp_target_unit(TimeUnit::Millisecond);
+    /// ```
+    pub fn with_timestamp_target_unit(mut self, unit: TimeUnit) -> Self {
+        self.timestamp_target_unit = unit;
+        self
+    }
+
+    /// Fall back to a generated descriptor when a provided one converts no rows
+    ///
+    /// A descriptor passed to `send_batch_with_descriptor` that doesn't match the batch
+    /// (e.g. a wrong field type) fails every row. With this enabled, such a batch is
+    /// converted once more with a descriptor generated from the Arrow schema before giving
+    /// up, and a warning is logged, since it points at a problem with the provided
+    /// descriptor. Batches where at least one row converts are never retried.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - If `true`, retry conversion once with a generated descriptor
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_descriptor_fallback(true);
+    /// ```
+    pub fn with_descriptor_fallback(mut self, enabled: bool) -> Self {
+        self.descriptor_fallback = enabled;
+        self
+    }
+
+    /// Set how descriptor soft limit violations are reported
+    ///
+    /// In `DescriptorValidationMode::Strict` (default) a descriptor exceeding a soft limit
+    /// is rejected like one exceeding a hard limit. In `WarnSoftLimits` a warning is
+    /// logged and the descriptor is used. Hard limits are always enforced.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - How soft limit violations are reported
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::{DescriptorValidationMode, WrapperConfiguration};
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_descriptor_soft_limits(Some(500), Some(4))
+    /// .with_descriptor_validation_mode(DescriptorValidationMode::WarnSoftLimits);
+    /// ```
+    pub fn with_descriptor_validation_mode(mut self, mode: DescriptorValidationMode) -> Self {
+        self.descriptor_validation_mode = mode;
+        self
+    }
+
+    /// Set self-imposed soft limits on descriptor size
+    ///
+    /// Checked for every message in provided and generated descriptors. Whether a
+    /// violation is an error or a warning depends on `descriptor_validation_mode`.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_fields` - Soft maximum fields per message (None = no soft limit)
+    /// * `max_nesting_depth` - Soft maximum nesting depth, the top-level message being
+    ///   depth 0 (None = no soft limit)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_descriptor_soft_limits(Some(500), None);
+    /// ```
+    pub fn with_descriptor_soft_limits(
+        mut self,
+        max_fields: Option<usize>,
+        max_nesting_depth: Option<usize>,
+    ) -> Self {
+        self.descriptor_soft_max_fields = max_fields;
+        self.descriptor_soft_max_nesting_depth = max_nesting_depth;
+        self
+    }
+
+    /// Lower the hard limits on descriptor size
+    ///
+    /// Descriptors exceeding a hard limit are always rejected. The field limit defaults to,
+    /// and may not exceed, the protocol maximum of 2000 fields per message. The nesting
+    /// depth defaults to 10 and may be raised up to 100 (see `with_max_nesting_depth`).
+    ///
+    /// # Arguments
+    ///
+    /// * `max_fields` - Maximum fields per message
+    /// * `max_nesting_depth` - Maximum nesting depth, the top-level message being depth 0
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_descriptor_hard_limits(1000, 5);
+    /// ```
+    pub fn with_descriptor_hard_limits(
+        mut self,
+        max_fields: usize,
+        max_nesting_depth: usize,
+    ) -> Self {
+        self.descriptor_max_fields = max_fields;
+        self.descriptor_max_nesting_depth = max_nesting_depth;
+        self
+    }
+
+    /// Set the maximum descriptor nesting depth
+    ///
+    /// Provided and generated descriptors nested deeper than this are rejected. The
+    /// default of 10 matches most Zerobus deployments; deployments that support deeper
+    /// (or only shallower) nesting can align it with the server. Values above 100 are
+    /// rejected by `validate`, as deeper recursion risks a stack overflow.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_nesting_depth` - Maximum nesting depth, the top-level message being depth 0
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_max_nesting_depth(16);
+    /// ```
+    pub fn with_max_nesting_depth(mut self, max_nesting_depth: usize) -> Self {
+        self.descriptor_max_nesting_depth = max_nesting_depth;
+        self
+    }
+
+    /// Set how many row chunks of a batch are converted to Protobuf concurrently
+    ///
+    /// Conversion is CPU-bound and by default runs on a single thread, which becomes the
+    /// bottleneck for large batches with wide or nested schemas. With `parallelism > 1`,
+    /// each batch is split into that many contiguous row chunks that are converted on the
+    /// rayon thread pool (so at most one chunk per pool thread runs at a time). Results
+    /// keep their row indices and order, and rows are still sent sequentially.
+    ///
+    /// Requires the `parallel` feature; without it conversion stays sequential and a
+    /// warning is logged when the wrapper is created.
+    ///
+    /// # Arguments
+    ///
+    /// * `parallelism` - Row chunks converted concurrently (must be > 0; 1 = sequential)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_conversion_parallelism(4);
+    /// ```
+    pub fn with_conversion_parallelism(mut self, parallelism: usize) -> Self {
+        self.conversion_parallelism = parallelism;
+        self
+    }
+
+    /// Set an observer notified when a backoff for the table starts and ends
+    ///
+    /// Pushes the transitions that `ZerobusWrapper::backoff_status` can only be polled
+    /// for, e.g. to page when a table enters error 6006 backoff and resolve the alert
+    /// once it clears. See `BackoffObserver` for delivery guarantees.
+    ///
+    /// # Arguments
+    ///
+    /// * `observer` - Receives `on_backoff_start` / `on_backoff_end` callbacks
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::{BackoffKind, BackoffObserver, WrapperConfiguration};
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// struct Pager;
+    ///
+    /// impl BackoffObserver for Pager {
+    ///     fn on_backoff_start(&self, table: &str, kind: BackoffKind, expected: Duration) {
+    ///         eprintln!("{table} entered {kind:?} backoff for ~{expected:?}");
+    ///     }
+    ///
+    ///     fn on_backoff_end(&self, table: &str, kind: BackoffKind) {
+    ///         eprintln!("{table} left {kind:?} backoff");
+    ///     }
+    /// }
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_backoff_observer(Arc::new(Pager));
+    /// ```
+    pub fn with_backoff_observer(mut self, observer: Arc<dyn BackoffObserver>) -> Self {
+        self.backoff_observer = Some(observer);
+        self
+    }
+
+    /// Set the longest column name accepted when generating a descriptor
+    ///
+    /// Databricks rejects column names longer than 255 characters, but only when the
+    /// stream is created, with an error that doesn't name the column. Names (after
+    /// `field_name_transform`, including nested struct fields) are checked while the
+    /// descriptor is generated, so an over-long name fails the send with a
+    /// `ConfigurationError` naming the column and its length before any network call.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_length` - Longest accepted column name (must be > 0)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_max_field_name_length(128);
+    /// ```
+    pub fn with_max_field_name_length(mut self, max_length: usize) -> Self {
+        self.max_field_name_length = max_length;
+        self
+    }
+
+    /// Flush the stream at the end of every batch, unconditionally
+    ///
+    /// By default the stream is flushed at the end of a batch only when every row was
+    /// sent (and never with `DeliveryMode::FireAndForget`). With this enabled it is flushed
+    /// after every batch, including partially successful ones. The SDK sends records in
+    /// the background without a flush; flushing waits until everything ingested on the
+    /// stream so far is acknowledged, so the batch only returns once it is durable. The
+    /// flush is best-effort: failures are logged and don't change the batch result. This
+    /// trades throughput for durability.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to flush after every batch
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_flush_every_batch(true);
+    /// ```
+    pub fn with_flush_every_batch(mut self, enabled: bool) -> Self {
+        self.flush_every_batch = enabled;
+        self
+    }
+
+    /// Set the wait before retrying when the table schema is not yet propagated
+    ///
+    /// A freshly created table may not accept records for a few seconds, so the stream
+    /// closes on the first record. The stream is normally recreated after 100ms, which can
+    /// use up all recreation attempts before the table is ready. When the first record of
+    /// a batch fails with a "schema not yet propagated" error (see
+    /// `wrapper::zerobus::is_schema_not_ready`), the send waits this long instead before
+    /// recreating the stream. The number of attempts is unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `wait` - Delay before retrying after a not-ready error (must be > 0)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    /// use std::time::Duration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_schema_propagation_wait(Duration::from_secs(5));
+    /// ```
+    pub fn with_schema_propagation_wait(mut self, wait: Duration) -> Self {
+        self.schema_propagation_wait = Some(wait);
+        self
+    }
+
+    /// Treat any row conversion failure as a batch-level error
+    ///
+    /// By default rows that fail conversion are reported in `failed_rows` (for
+    /// quarantine) and the rest of the batch is sent. With this enabled, a batch with any
+    /// conversion failure is not sent at all: the send reports a batch-level
+    /// `ConversionError` with the number of failed rows and the first few errors. Suited
+    /// to pipelines where a conversion failure is a bug that should halt processing.
+    ///
+    /// # Arguments
+    ///
+    /// * `fatal` - If `true`, conversion failures fail the whole batch
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_conversion_errors_fatal(true);
+    /// ```
+    pub fn with_conversion_errors_fatal(mut self, fatal: bool) -> Self {
+        self.conversion_errors_fatal = fatal;
+        self
+    }
+
+    /// Set the largest batch, in bytes, sent as a whole
+    ///
+    /// Oversized rows are already rejected one by one; this bounds whole batches. What
+    /// happens to a larger batch is set with `with_oversized_batch_policy` (by default it
+    /// is rejected). Size is measured as the Arrow in-memory size of the batch's rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_bytes` - Maximum batch size in bytes (must be > 0)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::{OversizedBatchPolicy, WrapperConfiguration};
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_max_batch_bytes(64 * 1024 * 1024)
+    /// .with_oversized_batch_policy(OversizedBatchPolicy::AutoSplit);
+    /// ```
+    pub fn with_max_batch_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_batch_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Set the reaction to batches larger than `max_batch_bytes`
+    ///
+    /// Has no effect unless `with_max_batch_bytes` is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - Reject, split or truncate oversized batches
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::{OversizedBatchPolicy, WrapperConfiguration};
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_max_batch_bytes(64 * 1024 * 1024)
+    /// .with_oversized_batch_policy(OversizedBatchPolicy::Truncate);
+    /// ```
+    pub fn with_oversized_batch_policy(mut self, policy: OversizedBatchPolicy) -> Self {
+        self.oversized_batch_policy = policy;
+        self
+    }
+
+    /// Set how null strings are distinguished from empty strings
+    ///
+    /// `StringNullPolicy::ExplicitPresence` only affects auto-generated descriptors; a
+    /// descriptor passed to `send_batch_with_descriptor` must declare presence itself.
+    /// `StringNullPolicy::Sentinel` applies to top-level Utf8/LargeUtf8 columns, whatever
+    /// the descriptor. With `emit_defaults`, null strings are written as empty strings, so
+    /// combine it only with `Sentinel`.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - Keep nulls omitted, declare string presence, or write a sentinel
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::{StringNullPolicy, WrapperConfiguration};
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_string_null_policy(StringNullPolicy::ExplicitPresence);
+    /// ```
+    pub fn with_string_null_policy(mut self, policy: StringNullPolicy) -> Self {
+        self.string_null_policy = policy;
+        self
+    }
+
+    /// Set the rename used to match Arrow columns to a provided descriptor's fields
+    ///
+    /// Use when the descriptor is fixed (passed to `send_batch_with_descriptor`) but the
+    /// Arrow column names differ from its field names, e.g. only by case or a known
+    /// prefix. Unlike `with_field_name_transform`, the resolver never changes a
+    /// descriptor: it is applied, after the transform, only when looking up the provided
+    /// descriptor's fields (including the `validate_descriptor_schema` and column count
+    /// checks). Descriptors generated from the Arrow schema, including those of
+    /// `send_batches` and `send_ipc_reader`, are matched without it.
+    ///
+    /// # Arguments
+    ///
+    /// * `resolver` - Maps a (transformed) Arrow field name to the descriptor field name
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    /// use std::sync::Arc;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_field_name_resolver(Arc::new(|name| name.to_lowercase()));
+    /// ```
+    pub fn with_field_name_resolver(
+        mut self,
+        resolver: Arc<dyn Fn(&str) -> String + Send + Sync>,
+    ) -> Self {
+        self.field_name_resolver = Some(FieldNameTransform::Custom(resolver));
+        self
+    }
+
+    /// Name failed rows by a key column in per-row error messages
+    ///
+    /// Each per-row error (conversion or transmission) is suffixed with
+    /// `[key <column>=<value>]`, the failed row's value in the named top-level column, so
+    /// quarantine reports can be read in business terms. A null key is shown as `null`;
+    /// if the batch has no such column, messages are left unchanged. The value is shown
+    /// as `<redacted>` when `redact_values_in_errors` is enabled or the column is one of
+    /// `sensitive_fields`. `TransmissionResult::failed_row_keys` returns the keys
+    /// directly.
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - Name of the key column in the Arrow batch
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_error_key_column("order_id");
+    /// ```
+    pub fn with_error_key_column(mut self, column: impl Into<String>) -> Self {
+        self.error_key_column = Some(column.into());
+        self
+    }
+
+    /// Set the delivery guarantee for records sent to Zerobus
+    ///
+    /// `DeliveryMode::FireAndForget` trades the at-least-once guarantee for latency, for
+    /// telemetry that tolerates loss. Each record is handed to the stream and its row
+    /// reported successful immediately: `successful_rows` means "submitted", not
+    /// "acknowledged", rows rejected by the server or lost when the stream closes are
+    /// neither reported nor retried, and `ack_ids` has no entry for them. Only failures
+    /// to submit a record (e.g. a closed stream) are reported and retried.
+    ///
+    /// The end-of-batch flush is skipped too, since the SDK's flush waits for the server
+    /// to acknowledge everything sent; the stream sends queued records in the background.
+    /// Enable `flush_every_batch` to flush after each batch anyway.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - Await acknowledgments (at-least-once) or not (at-most-once)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::{DeliveryMode, WrapperConfiguration};
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "telemetry".to_string(),
+    /// )
+    /// .with_delivery_mode(DeliveryMode::FireAndForget);
+    /// ```
+    pub fn with_delivery_mode(mut self, mode: DeliveryMode) -> Self {
+        self.delivery_mode = mode;
+        self
+    }
+
+    /// Check the generated descriptor against the target table when the wrapper is created
+    ///
+    /// `ZerobusWrapper::new` generates the descriptor for `expected_schema`, reads the
+    /// table's columns from Unity Catalog and fails with a `ConfigurationError` listing
+    /// every mismatch (unknown fields, incompatible types, non-nullable columns without a
+    /// field). This catches descriptor/table drift at startup instead of on the first
+    /// send, which would otherwise close the stream. The check needs network access to
+    /// Unity Catalog and is skipped in writer disabled mode; use
+    /// `ZerobusWrapper::validate_table_schema` to run it on demand instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to validate against the table on startup
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow::datatypes::{DataType, Field, Schema};
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    /// use std::sync::Arc;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "catalog.schema.events".to_string(),
+    /// )
+    /// .with_expected_schema(Arc::new(Schema::new(vec![
+    ///     Field::new("id", DataType::Int64, false),
+    /// ])))
+    /// .with_validate_table_schema_on_startup(true);
+    /// ```
+    pub fn with_validate_table_schema_on_startup(mut self, enabled: bool) -> Self {
+        self.validate_table_schema_on_startup = enabled;
+        self
+    }
+
+    /// Perform an explicit schema handshake when a stream is created
+    ///
+    /// The descriptor is registered with Zerobus before the stream carries any data record,
+    /// and if Zerobus rejects it stream creation fails immediately with a
+    /// `ConfigurationError` naming the handshake, instead of a connection error or a stream
+    /// closed on the first record. This tells schema registration failures apart from data
+    /// failures. The SDK sends the descriptor in its stream creation request, so no extra
+    /// round trip is made; only how a descriptor rejection is reported changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to perform the handshake
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_explicit_schema_handshake(true);
+    /// ```
+    pub fn with_explicit_schema_handshake(mut self, enabled: bool) -> Self {
+        self.explicit_schema_handshake = enabled;
+        self
+    }
+
+    /// Recover from a generated descriptor the table rejects on the first record
+    ///
+    /// A stream that closes on the first record usually means the table doesn't accept
+    /// the descriptor, e.g. a timestamp column written as an Int64 epoch value. With this
+    /// enabled, when that happens to a batch sent with an auto-generated descriptor and
+    /// stream recreation retries are exhausted, the batch is converted once more with an
+    /// alternate type mapping (timestamps as ISO 8601 strings) and sent again. Which mapping
+    /// was attempted is logged; an accepted mapping is kept for later batches.
+    ///
+    /// Applies to `send_batch`, `send_batch_with_token`, `send_batches` and
+    /// `send_ipc_reader`. Never retried are:
+    /// - batches sent with a provided descriptor (`send_batch_with_descriptor`)
+    /// - records converted before sending (`send_prepared`, `send_serialized`, `recover_wal`)
+    /// - batches without a column the alternate mapping changes
+    /// - streaming sends (`send_batch_streaming_results`, `submit_batch`), whose failures
+    ///   are reported as they become final
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - If `true`, retry once with the alternate type mapping
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_descriptor_auto_recovery(true);
+    /// ```
+    pub fn with_descriptor_auto_recovery(mut self, enabled: bool) -> Self {
+        self.descriptor_auto_recovery = enabled;
+        self
+    }
+
+    /// Keep records in a write-ahead log until Zerobus acknowledges them
+    ///
+    /// Before a batch's first record is submitted, its records are written to a segment
+    /// file in a subdirectory of `dir` named after the table, and synced to disk.
+    /// Acknowledgments are recorded as they arrive and the segment is removed once every
+    /// record is acknowledged. Records of a failed send stay in the log (and are also
+    /// reported to the caller as failed rows as usual). On startup,
+    /// `ZerobusWrapper::recover_wal` re-sends the records whose acknowledgment was not
+    /// recorded. The directory is created if needed; several tables can share it, but only
+    /// one wrapper per table should use it at a time. Nothing is logged in writer disabled
+    /// mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - Directory for the write-ahead log segment files
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    /// use std::path::PathBuf;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "events".to_string(),
+    /// )
+    /// .with_wal_dir(PathBuf::from("/var/lib/ingest/wal"));
+    /// ```
+    pub fn with_wal_dir(mut self, dir: PathBuf) -> Self {
+        self.wal_dir = Some(dir);
+        self
+    }
+
+    /// Render the effective configuration for logs and bug reports, with secrets redacted
+    ///
+    /// Every field is shown as in the `Debug` output, except that `client_id` and
+    /// `client_secret` read `***redacted***`. Their values are never exposed: like
+    /// `Debug`, this never reads the `SecretString`s.
+    ///
+    /// # Returns
+    ///
+    /// Returns a multi-line, copy-pasteable configuration snapshot.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://workspace.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_credentials("client_id".to_string(), "client_secret".to_string());
+    /// println!("{}", config.redacted_summary());
+    /// ```
+    pub fn redacted_summary(&self) -> String {
+        // `SecretString`'s own `Debug` placeholder, replaced by a uniform marker
+        let secret_debug = format!("Secret([REDACTED {}])", std::any::type_name::<String>());
+        format!("{:#?}", self).replace(&secret_debug, "***redacted***")
+    }
+
+    /// Limits for validating provided and generated descriptors
+    pub(crate) fn descriptor_limits(&self) -> DescriptorLimits {
+        DescriptorLimits {
+            mode: self.descriptor_validation_mode,
+            max_fields: self.descriptor_max_fields,
+            max_nesting_depth: self.descriptor_max_nesting_depth,
+            soft_max_fields: self.descriptor_soft_max_fields,
+            soft_max_nesting_depth: self.descriptor_soft_max_nesting_depth,
+        }
+    }
+
+    /// Redaction settings for per-row conversion errors
+    pub(crate) fn error_redaction(&self) -> ErrorRedaction<'_> {
+        ErrorRedaction {
+            redact_values: self.redact_values_in_errors,
+            sensitive_fields: self.sensitive_fields.as_deref().unwrap_or_default(),
+        }
+    }
+
+    /// Restrict endpoint URLs to an allowlist of hosts
+    ///
+    /// Catches typo'd endpoints at configuration time instead of surfacing them as
+    /// confusing connection errors later. Each entry matches the host exactly or any
+    /// subdomain of it (e.g. `cloud.databricks.com` matches
+    /// `1234.zerobus.us-west-2.cloud.databricks.com`).
+    ///
+    /// # Arguments
+    ///
+    /// * `hosts` - Allowed hosts or parent domains (case-insensitive)
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://zerobus.internal.example.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_allowed_endpoint_hosts(vec!["internal.example.com".to_string()]);
+    /// ```
+    pub fn with_allowed_endpoint_hosts(mut self, hosts: Vec<String>) -> Self {
+        self.allowed_endpoint_hosts = Some(hosts);
+        self
+    }
+
+    /// Restrict endpoint URLs to known Databricks domains
+    ///
+    /// Shorthand for [`with_allowed_endpoint_hosts`](Self::with_allowed_endpoint_hosts)
+    /// with [`DATABRICKS_ENDPOINT_DOMAINS`]. Additionally verifies that `zerobus_endpoint`
+    /// and `unity_catalog_url` belong to the same Databricks cloud.
+    ///
+    /// # Returns
+    ///
+    /// Self for method chaining
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    ///
+    /// let config = WrapperConfiguration::new(
+    ///     "https://1234.zerobus.us-west-2.cloud.databricks.com".to_string(),
+    ///     "my_table".to_string(),
+    /// )
+    /// .with_databricks_endpoint_check();
+    /// ```
+    pub fn with_databricks_endpoint_check(self) -> Self {
+        self.with_allowed_endpoint_hosts(
+            DATABRICKS_ENDPOINT_DOMAINS
+                .iter()
+                .map(|d| d.to_string())
+                .collect(),
+        )
+    }
+
+    /// Validate configuration
+    ///
+    /// Checks that all required fields are present and valid.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` if configuration is valid, or `Err(ZerobusError)` if invalid.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigurationError` if:
+    /// - `zerobus_endpoint` is not a valid URL starting with `https://` or `http://`
+    /// - `debug_enabled` is true but `debug_output_dir` is not provided
+    /// - `zerobus_writer_disabled` is true but `debug_enabled` is false
+    /// - `retry_max_attempts` is 0
+    /// - `connect_retry_max_attempts` is `Some(0)`, or its max delay is below its base delay
+    /// - `stream_recreate_max_delay_ms` is below `stream_recreate_base_delay_ms`
+    /// - `retryable_error_patterns` or `fatal_error_patterns` contains an empty pattern
+    /// - `debug_flush_interval_secs` is 0
+    /// - `max_pending_futures` is 0
+    /// - `shutdown_timeout` is zero
+    /// - `max_buffer_age` is `Some` zero duration
+    /// - `idle_stream_timeout` is `Some` zero duration
+    /// - `send_deadline` is `Some` zero duration
+    /// - `schema_propagation_wait` is `Some` zero duration
+    /// - `conversion_parallelism` is 0
+    /// - `max_field_name_length` is 0
+    /// - `transmission_compression` is not `CompressionType::None` (unsupported by the SDK)
+    /// - `descriptor_max_fields` is 0 or exceeds the protocol maximum, or
+    ///   `descriptor_max_nesting_depth` is 0 or exceeds 100, or a descriptor soft limit
+    ///   exceeds its hard limit
+    /// - `memory_budget_bytes` is `Some(0)` or exceeds `u32::MAX`
+    /// - `max_batch_bytes` is `Some(0)`
+    /// - `rate_limit_records_per_sec` is `Some(0)`
+    /// - `validate_table_schema_on_startup` is true but `expected_schema` is not set
+    /// - `propagate_schema_metadata` is true but `schema_metadata_keys` is empty, or a key
+    ///   is not a Protobuf identifier
+    /// - `mirror_table_name` is invalid or equal to `table_name`
+    /// - `allowed_endpoint_hosts` is set and an endpoint host is not in it, or the
+    ///   Zerobus endpoint and Unity Catalog URL are on different Databricks clouds
+    pub fn validate(&self) -> Result<(), ZerobusError> {
+        // Validate endpoint URL (surrounding whitespace is trimmed by the wrapper)
+        let endpoint = self.zerobus_endpoint.trim();
+        if !endpoint.starts_with("https://") && !endpoint.starts_with("http://") {
+            return Err(ZerobusError::ConfigurationError(format!(
+                "zerobus_endpoint must start with 'https://' or 'http://', got: '{}'",
+                self.zerobus_endpoint
+            )));
+        }
+
+        // Validate table name: Unity Catalog format (catalog.schema.table, schema.table, or table)
+        // Each part must contain only ASCII letters, digits, and underscores (Zerobus requirement)
+        // Dots are allowed as separators between catalog, schema, and table name parts
+        // Unity Catalog format:
+        //   1 part: table (index 0 = table)
+        //   2 parts: schema.table (index 0 = schema, index 1 = table)
+        //   3 parts: catalog.schema.table (index 0 = catalog, index 1 = schema, index 2 = table)
+        let parts: Vec<&str> = self.table_name.split('.').collect();
+        let num_parts = parts.len();
+
+        if num_parts > 3 {
+            return Err(ZerobusError::ConfigurationError(format!(
+                "table_name has too many parts ({}). Must be in format 'table', 'schema.table', or 'catalog.schema.table'. Got: '{}'",
+                num_parts, self.table_name
+            )));
+        }
+
+        // Map index to part name based on number of parts and index
+        // 1 part: [0] = table
         // 2 parts: [0] = schema, [1] = table
         // 3 parts: [0] = catalog, [1] = schema, [2] = table
         let get_part_name = |idx: usize, total: usize| -> &'static str {
@@ -518,6 +3336,280 @@ impl WrapperConfiguration {
             )));
         }
 
+        // Validate connect retry configuration (only when set)
+        if let Some(max_attempts) = self.connect_retry_max_attempts {
+            if max_attempts == 0 {
+                return Err(ZerobusError::ConfigurationError(
+                    "connect_retry_max_attempts must be > 0".to_string(),
+                ));
+            }
+            if self.connect_retry_max_delay_ms < self.connect_retry_base_delay_ms {
+                return Err(ZerobusError::ConfigurationError(format!(
+                    "connect_retry_max_delay_ms ({}) must be >= connect_retry_base_delay_ms ({})",
+                    self.connect_retry_max_delay_ms, self.connect_retry_base_delay_ms
+                )));
+            }
+        }
+
+        // Validate stream recreation backoff
+        if self.stream_recreate_max_delay_ms < self.stream_recreate_base_delay_ms {
+            return Err(ZerobusError::ConfigurationError(format!(
+                "stream_recreate_max_delay_ms ({}) must be >= stream_recreate_base_delay_ms ({})",
+                self.stream_recreate_max_delay_ms, self.stream_recreate_base_delay_ms
+            )));
+        }
+
+        // An empty pattern would match every error
+        for (name, patterns) in [
+            ("retryable_error_patterns", &self.retryable_error_patterns),
+            ("fatal_error_patterns", &self.fatal_error_patterns),
+        ] {
+            if patterns.iter().any(String::is_empty) {
+                return Err(ZerobusError::ConfigurationError(format!(
+                    "{} must not contain empty patterns",
+                    name
+                )));
+            }
+        }
+
+        // Validate in-flight buffer cap
+        if self.max_pending_futures == 0 {
+            return Err(ZerobusError::ConfigurationError(
+                "max_pending_futures must be > 0".to_string(),
+            ));
+        }
+
+        // Validate shutdown timeout
+        if self.shutdown_timeout.is_zero() {
+            return Err(ZerobusError::ConfigurationError(
+                "shutdown_timeout must be > 0".to_string(),
+            ));
+        }
+
+        // Validate buffer age
+        if self.max_buffer_age.is_some_and(|max_age| max_age.is_zero()) {
+            return Err(ZerobusError::ConfigurationError(
+                "max_buffer_age must be > 0".to_string(),
+            ));
+        }
+
+        // Validate idle stream timeout
+        if self
+            .idle_stream_timeout
+            .is_some_and(|timeout| timeout.is_zero())
+        {
+            return Err(ZerobusError::ConfigurationError(
+                "idle_stream_timeout must be > 0".to_string(),
+            ));
+        }
+
+        // Validate send deadline
+        if self
+            .send_deadline
+            .is_some_and(|deadline| deadline.is_zero())
+        {
+            return Err(ZerobusError::ConfigurationError(
+                "send_deadline must be > 0".to_string(),
+            ));
+        }
+
+        // Validate schema propagation wait
+        if self
+            .schema_propagation_wait
+            .is_some_and(|wait| wait.is_zero())
+        {
+            return Err(ZerobusError::ConfigurationError(
+                "schema_propagation_wait must be > 0".to_string(),
+            ));
+        }
+
+        // Validate conversion parallelism
+        if self.conversion_parallelism == 0 {
+            return Err(ZerobusError::ConfigurationError(
+                "conversion_parallelism must be > 0".to_string(),
+            ));
+        }
+
+        // Validate column name length limit
+        if self.max_field_name_length == 0 {
+            return Err(ZerobusError::ConfigurationError(
+                "max_field_name_length must be > 0".to_string(),
+            ));
+        }
+
+        // The SDK has no codec option for the ingest stream; fail rather than ignore it
+        if self.transmission_compression != CompressionType::None {
+            return Err(ZerobusError::ConfigurationError(format!(
+                "transmission_compression {:?} is not supported: the Zerobus SDK does not expose a codec for the ingest stream",
+                self.transmission_compression
+            )));
+        }
+
+        // Validate memory budget (permits are acquired as u32)
+        if let Some(budget) = self.memory_budget_bytes {
+            if budget == 0 || budget > u32::MAX as usize {
+                return Err(ZerobusError::ConfigurationError(format!(
+                    "memory_budget_bytes must be between 1 and {}. Got: {}",
+                    u32::MAX,
+                    budget
+                )));
+            }
+        }
+
+        if self.max_batch_bytes == Some(0) {
+            return Err(ZerobusError::ConfigurationError(
+                "max_batch_bytes must be > 0".to_string(),
+            ));
+        }
+
+        // Validate telemetry namespace: a single metric name segment
+        if let Some(namespace) = &self.telemetry_namespace {
+            let valid = namespace.starts_with(|c: char| c.is_ascii_alphabetic())
+                && namespace
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                return Err(ZerobusError::ConfigurationError(format!(
+                    "telemetry_namespace must start with an ASCII letter and contain only ASCII letters, digits, and underscores. Got: '{}'",
+                    namespace
+                )));
+            }
+        }
+
+        // Validate rate limit
+        if self.rate_limit_records_per_sec == Some(0) {
+            return Err(ZerobusError::ConfigurationError(
+                "rate_limit_records_per_sec must be > 0".to_string(),
+            ));
+        }
+
+        // Startup table schema validation needs a schema to generate the descriptor from
+        if self.validate_table_schema_on_startup && self.expected_schema.is_none() {
+            return Err(ZerobusError::ConfigurationError(
+                "validate_table_schema_on_startup requires expected_schema".to_string(),
+            ));
+        }
+
+        // Validate mirror table (same rules as table_name, and must differ from it)
+        if let Some(mirror) = self.mirror_configuration() {
+            if mirror.table_name == self.table_name {
+                return Err(ZerobusError::ConfigurationError(format!(
+                    "mirror_table_name must differ from table_name. Got: '{}'",
+                    mirror.table_name
+                )));
+            }
+            mirror.validate().map_err(|e| match e {
+                ZerobusError::ConfigurationError(msg) => {
+                    ZerobusError::ConfigurationError(format!("mirror_table_name: {}", msg))
+                }
+                other => other,
+            })?;
+        }
+
+        // Validate endpoint hosts against allowlist (opt-in)
+        if let Some(allowed) = &self.allowed_endpoint_hosts {
+            self.validate_endpoint_hosts(allowed)?;
+        }
+
+        // Validate descriptor limits: hard limits within the protocol maximums,
+        // soft limits within the hard limits
+        if self.descriptor_max_fields == 0 || self.descriptor_max_fields > MAX_FIELDS_PER_MESSAGE {
+            return Err(ZerobusError::ConfigurationError(format!(
+                "descriptor_max_fields must be between 1 and {}, got: {}",
+                MAX_FIELDS_PER_MESSAGE, self.descriptor_max_fields
+            )));
+        }
+        if self.descriptor_max_nesting_depth == 0
+            || self.descriptor_max_nesting_depth > ABSOLUTE_MAX_NESTING_DEPTH
+        {
+            return Err(ZerobusError::ConfigurationError(format!(
+                "descriptor_max_nesting_depth must be between 1 and {}, got: {}",
+                ABSOLUTE_MAX_NESTING_DEPTH, self.descriptor_max_nesting_depth
+            )));
+        }
+        if let Some(soft_max) = self.descriptor_soft_max_fields {
+            if soft_max > self.descriptor_max_fields {
+                return Err(ZerobusError::ConfigurationError(format!(
+                    "descriptor_soft_max_fields ({}) must not exceed descriptor_max_fields ({})",
+                    soft_max, self.descriptor_max_fields
+                )));
+            }
+        }
+        if let Some(soft_max) = self.descriptor_soft_max_nesting_depth {
+            if soft_max > self.descriptor_max_nesting_depth {
+                return Err(ZerobusError::ConfigurationError(format!(
+                    "descriptor_soft_max_nesting_depth ({}) must not exceed descriptor_max_nesting_depth ({})",
+                    soft_max, self.descriptor_max_nesting_depth
+                )));
+            }
+        }
+
+        // Schema metadata keys become option names, so they must be Protobuf identifiers
+        if self.propagate_schema_metadata {
+            if self.schema_metadata_keys.is_empty() {
+                return Err(ZerobusError::ConfigurationError(
+                    "propagate_schema_metadata requires schema_metadata_keys (the metadata keys to copy)"
+                        .to_string(),
+                ));
+            }
+            if let Some(key) = self
+                .schema_metadata_keys
+                .iter()
+                .find(|key| !crate::wrapper::conversion::is_option_name(key))
+            {
+                return Err(ZerobusError::ConfigurationError(format!(
+                    "schema_metadata_keys must be Protobuf identifiers (letters, digits, underscores), got: '{}'",
+                    key
+                )));
+            }
+        }
+
+        // Validate proto package: dot-separated Protobuf identifiers
+        if let Some(package) = &self.proto_package {
+            let valid = package
+                .split('.')
+                .all(crate::wrapper::conversion::is_option_name);
+            if !valid {
+                return Err(ZerobusError::ConfigurationError(format!(
+                    "proto_package must be dot-separated identifiers (letters, digits, underscores), got: '{}'",
+                    package
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check `zerobus_endpoint` and `unity_catalog_url` hosts against the allowlist
+    fn validate_endpoint_hosts(&self, allowed: &[String]) -> Result<(), ZerobusError> {
+        if allowed.iter().all(|h| h.trim().is_empty()) {
+            return Err(ZerobusError::ConfigurationError(
+                "allowed_endpoint_hosts must contain at least one non-empty host".to_string(),
+            ));
+        }
+
+        let zerobus_host = endpoint_host("zerobus_endpoint", &self.zerobus_endpoint, allowed)?;
+        let uc_host = match &self.unity_catalog_url {
+            Some(url) => Some(endpoint_host("unity_catalog_url", url, allowed)?),
+            None => None,
+        };
+
+        // Cross-check: both URLs must be on the same Databricks cloud when derivable.
+        // The target catalog itself cannot be derived from the URLs, so it is not checked.
+        if let Some(uc_host) = uc_host {
+            if let (Some(endpoint_cloud), Some(uc_cloud)) = (
+                matching_domain(&zerobus_host, DATABRICKS_ENDPOINT_DOMAINS),
+                matching_domain(&uc_host, DATABRICKS_ENDPOINT_DOMAINS),
+            ) {
+                if endpoint_cloud != uc_cloud {
+                    return Err(ZerobusError::ConfigurationError(format!(
+                        "zerobus_endpoint host '{}' ({}) and unity_catalog_url host '{}' ({}) are on different Databricks clouds. Both must point at the workspace that owns table '{}'",
+                        zerobus_host, endpoint_cloud, uc_host, uc_cloud, self.table_name
+                    )));
+                }
+            }
+        }
+
         Ok(())
     }
 }
@@ -576,3 +3668,39 @@ impl OtlpSdkConfig {
         Ok(())
     }
 }
+
+/// Extract the host of `url` and verify it matches an entry in `allowed`
+fn endpoint_host(field: &str, url: &str, allowed: &[String]) -> Result<String, ZerobusError> {
+    let host = url::Url::parse(url.trim())
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_ascii_lowercase()))
+        .ok_or_else(|| {
+            ZerobusError::ConfigurationError(format!(
+                "{} must be a valid URL with a host. Got: '{}'",
+                field, url
+            ))
+        })?;
+
+    if matching_domain(&host, allowed).is_none() {
+        return Err(ZerobusError::ConfigurationError(format!(
+            "{} host '{}' does not match any allowed endpoint host {:?}. Check the URL for typos, or adjust allowed_endpoint_hosts for custom/self-hosted endpoints",
+            field, host, allowed
+        )));
+    }
+
+    Ok(host)
+}
+
+/// Return the first entry of `domains` that `host` equals or is a subdomain of
+fn matching_domain<S: AsRef<str>>(host: &str, domains: &[S]) -> Option<String> {
+    domains.iter().find_map(|domain| {
+        let domain = domain
+            .as_ref()
+            .trim()
+            .trim_start_matches('.')
+            .to_ascii_lowercase();
+        let is_match =
+            !domain.is_empty() && (host == domain || host.ends_with(&format!(".{}", domain)));
+        is_match.then_some(domain)
+    })
+}
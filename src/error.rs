@@ -3,6 +3,7 @@
 //! This module defines all error types used throughout the wrapper,
 //! providing clear, actionable error messages for developers.
 
+use std::time::Duration;
 use thiserror::Error;
 
 /// Error type for wrapper operations
@@ -53,6 +54,18 @@ pub enum ZerobusError {
     /// Occurs when authentication token refresh fails.
     #[error("Token refresh error: {0}")]
     TokenRefreshError(String),
+
+    /// Server throttling error
+    ///
+    /// Occurs when Zerobus rejects a request because the client is sending too fast.
+    /// Carries the server-provided retry-after hint when one was supplied.
+    #[error("Throttled: {message}")]
+    Throttled {
+        /// Error message
+        message: String,
+        /// Minimum delay the server asked for before retrying
+        retry_after: Option<Duration>,
+    },
 }
 
 impl ZerobusError {
@@ -61,17 +74,67 @@ impl ZerobusError {
     /// Returns true for transient errors that should be retried:
     /// - ConnectionError
     /// - TransmissionError (if transient)
+    /// - Throttled
     pub fn is_retryable(&self) -> bool {
         matches!(
             self,
-            ZerobusError::ConnectionError(_) | ZerobusError::TransmissionError(_)
+            ZerobusError::ConnectionError(_)
+                | ZerobusError::TransmissionError(_)
+                | ZerobusError::Throttled { .. }
         )
     }
 
+    /// Server-provided retry-after hint, if any
+    ///
+    /// Returns the delay the server asked for before the next attempt. Only
+    /// `Throttled` errors carry a hint.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            ZerobusError::Throttled { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+
     /// Check if the error indicates token expiration
     ///
     /// Returns true if the error suggests the authentication token has expired.
     pub fn is_token_expired(&self) -> bool {
         matches!(self, ZerobusError::AuthenticationError(_))
     }
+
+    /// Rewrite the error message, keeping the variant (and any retry-after hint)
+    pub(crate) fn map_message(self, f: impl FnOnce(String) -> String) -> Self {
+        match self {
+            ZerobusError::ConfigurationError(m) => ZerobusError::ConfigurationError(f(m)),
+            ZerobusError::AuthenticationError(m) => ZerobusError::AuthenticationError(f(m)),
+            ZerobusError::ConnectionError(m) => ZerobusError::ConnectionError(f(m)),
+            ZerobusError::ConversionError(m) => ZerobusError::ConversionError(f(m)),
+            ZerobusError::TransmissionError(m) => ZerobusError::TransmissionError(f(m)),
+            ZerobusError::RetryExhausted(m) => ZerobusError::RetryExhausted(f(m)),
+            ZerobusError::TokenRefreshError(m) => ZerobusError::TokenRefreshError(f(m)),
+            ZerobusError::Throttled {
+                message,
+                retry_after,
+            } => ZerobusError::Throttled {
+                message: f(message),
+                retry_after,
+            },
+        }
+    }
+
+    /// Name of the error variant (e.g. `"ConversionError"`)
+    ///
+    /// Used as the error type key in `ErrorStatistics` and exported metrics.
+    pub fn error_type(&self) -> &'static str {
+        match self {
+            ZerobusError::ConfigurationError(_) => "ConfigurationError",
+            ZerobusError::AuthenticationError(_) => "AuthenticationError",
+            ZerobusError::ConnectionError(_) => "ConnectionError",
+            ZerobusError::ConversionError(_) => "ConversionError",
+            ZerobusError::TransmissionError(_) => "TransmissionError",
+            ZerobusError::RetryExhausted(_) => "RetryExhausted",
+            ZerobusError::TokenRefreshError(_) => "TokenRefreshError",
+            ZerobusError::Throttled { .. } => "Throttled",
+        }
+    }
 }
@@ -33,6 +33,44 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! # Standalone conversion
+//!
+//! The Arrow to Protobuf conversion can be used on its own, without a wrapper or
+//! network access (e.g. to benchmark or tune conversion):
+//!
+//! ```
+//! use arrow::array::{Int64Array, StringArray};
+//! use arrow::datatypes::{DataType, Field, Schema};
+//! use arrow::record_batch::RecordBatch;
+//! use arrow_zerobus_sdk_wrapper::{generate_protobuf_descriptor, record_batch_to_protobuf_bytes};
+//! use std::sync::Arc;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let schema = Schema::new(vec![
+//!     Field::new("id", DataType::Int64, false),
+//!     Field::new("name", DataType::Utf8, true),
+//! ]);
+//! let batch = RecordBatch::try_new(
+//!     Arc::new(schema),
+//!     vec![
+//!         Arc::new(Int64Array::from(vec![1, 2])),
+//!         Arc::new(StringArray::from(vec![Some("a"), None])),
+//!     ],
+//! )?;
+//!
+//! let descriptor = generate_protobuf_descriptor(batch.schema().as_ref())?;
+//! let result = record_batch_to_protobuf_bytes(&batch, &descriptor);
+//!
+//! // One encoded message per row, tagged with its row index
+//! assert!(result.failed_rows.is_empty());
+//! assert_eq!(result.successful_bytes.len(), 2);
+//! let (row_idx, bytes) = &result.successful_bytes[0];
+//! assert_eq!(*row_idx, 0);
+//! assert_eq!(bytes, &vec![0x08, 1, 0x12, 1, b'a']);
+//! # Ok(())
+//! # }
+//! ```
 
 pub mod config;
 pub mod error;
@@ -43,6 +81,19 @@ pub mod wrapper;
 #[cfg(feature = "python")]
 pub mod python;
 
-pub use config::{OtlpConfig, OtlpSdkConfig, WrapperConfiguration};
+pub use config::{
+    BatchTransform, CompressionType, CustomEncoder, CustomEncoderFn, DeliveryMode,
+    DescriptorValidationMode, FieldNameTransform, FloatPolicy, NullElementPolicy, OtlpConfig,
+    OtlpSdkConfig, OversizedBatchPolicy, SchemaEvolution, StringNullPolicy, WrapperConfiguration,
+    WrapperConfigurationBuilder,
+};
 pub use error::ZerobusError;
-pub use wrapper::{ErrorStatistics, TransmissionResult, ZerobusWrapper};
+pub use wrapper::conversion::{
+    generate_protobuf_descriptor, record_batch_to_protobuf_bytes, ProtobufConversionResult,
+};
+pub use wrapper::table_schema::{compare_descriptor_to_table, ColumnDef, TableSchemaMismatch};
+pub use wrapper::{
+    BackoffKind, BackoffObserver, BackoffStateSnapshot, BackoffStatus, ErrorStatistics,
+    FailurePhase, FlushOutcome, HealthCheckMode, HealthStatus, PendingAcks, PreparedBatch,
+    RowOutcome, RowResult, RowStatus, TokenSendOutcome, TransmissionResult, ZerobusWrapper,
+};
@@ -1,7 +1,9 @@
 //! OpenTelemetry observability integration
 //!
-//! This module integrates with otlp-rust-service for metrics and traces.
+//! This module integrates with otlp-rust-service for metrics and traces, and renders
+//! cumulative send metrics in Prometheus text format.
 
 pub mod otlp;
+pub mod prometheus;
 
-pub use otlp::ObservabilityManager;
+pub use otlp::{ObservabilityManager, TelemetryNames, DEFAULT_TELEMETRY_NAMESPACE};
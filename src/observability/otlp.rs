@@ -10,12 +10,76 @@
 use crate::config::OtlpSdkConfig;
 use crate::error::ZerobusError;
 
-#[cfg(feature = "observability")]
 use std::sync::Arc;
 
 #[cfg(feature = "observability")]
 use otlp_arrow_library::{Config as OtlpLibraryConfig, OtlpLibrary};
 
+/// Span and metric names emitted by `ObservabilityManager`
+///
+/// Every name starts with the telemetry namespace (default: [`DEFAULT_TELEMETRY_NAMESPACE`]),
+/// so several services exporting to the same backend don't collide.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TelemetryNames {
+    namespace: String,
+    send_batch_span: String,
+    send_batch_completed: String,
+    batch_metrics: String,
+    batch_size_bytes: String,
+    batch_success: String,
+    batch_latency_ms: String,
+}
+
+/// Namespace used for span and metric names when none is configured
+pub const DEFAULT_TELEMETRY_NAMESPACE: &str = "zerobus";
+
+impl TelemetryNames {
+    /// Names under `namespace`, or [`DEFAULT_TELEMETRY_NAMESPACE`] if `None`
+    pub fn new(namespace: Option<&str>) -> Self {
+        let namespace = namespace.unwrap_or(DEFAULT_TELEMETRY_NAMESPACE);
+        Self {
+            namespace: namespace.to_string(),
+            send_batch_span: format!("{}.send_batch", namespace),
+            send_batch_completed: format!("{}.send_batch.completed", namespace),
+            batch_metrics: format!("{}.batch.metrics", namespace),
+            batch_size_bytes: format!("{}.batch.size_bytes", namespace),
+            batch_success: format!("{}.batch.success", namespace),
+            batch_latency_ms: format!("{}.batch.latency_ms", namespace),
+        }
+    }
+
+    /// Namespace prefixed to every name
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Name of the batch transmission span (`{namespace}.send_batch`)
+    pub fn send_batch_span(&self) -> &str {
+        &self.send_batch_span
+    }
+
+    /// Name of the batch size metric (`{namespace}.batch.size_bytes`)
+    pub fn batch_size_bytes(&self) -> &str {
+        &self.batch_size_bytes
+    }
+
+    /// Name of the batch success metric (`{namespace}.batch.success`)
+    pub fn batch_success(&self) -> &str {
+        &self.batch_success
+    }
+
+    /// Name of the batch latency metric (`{namespace}.batch.latency_ms`)
+    pub fn batch_latency_ms(&self) -> &str {
+        &self.batch_latency_ms
+    }
+}
+
+impl Default for TelemetryNames {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
 /// Observability manager for collecting metrics and traces
 ///
 /// Wraps the otlp-rust-service library to provide OpenTelemetry
@@ -26,6 +90,7 @@ pub struct ObservabilityManager {
     library: Option<Arc<OtlpLibrary>>,
     #[cfg(not(feature = "observability"))]
     _phantom: std::marker::PhantomData<()>,
+    names: Arc<TelemetryNames>,
 }
 
 impl ObservabilityManager {
@@ -82,6 +147,7 @@ impl ObservabilityManager {
             match OtlpLibrary::new(library_config).await {
                 Ok(library) => Some(Self {
                     library: Some(Arc::new(library)),
+                    names: Arc::new(TelemetryNames::default()),
                 }),
                 Err(e) => {
                     tracing::warn!("Failed to initialize OtlpLibrary: {}", e);
@@ -96,6 +162,21 @@ impl ObservabilityManager {
         }
     }
 
+    /// Emit span and metric names under `namespace` instead of the default `zerobus`
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace` - Telemetry namespace (see `WrapperConfiguration::with_telemetry_namespace`)
+    pub fn with_namespace(mut self, namespace: Option<&str>) -> Self {
+        self.names = Arc::new(TelemetryNames::new(namespace));
+        self
+    }
+
+    /// Span and metric names this manager emits
+    pub fn names(&self) -> &TelemetryNames {
+        &self.names
+    }
+
     /// Record a batch transmission metric
     ///
     /// Uses tracing to record metrics, which are picked up by the otlp-rust-service SDK
@@ -113,29 +194,33 @@ impl ObservabilityManager {
                 // Record metrics via tracing with structured fields
                 // The otlp-rust-service SDK infrastructure picks up these tracing events
                 // and converts them to OpenTelemetry metrics
+                let names = &self.names;
                 tracing::info!(
-                    metric.name = "zerobus.batch.size_bytes",
+                    metric.name = names.batch_size_bytes(),
                     metric.value = batch_size_bytes,
                     metric.unit = "bytes",
                     batch_size_bytes = batch_size_bytes,
                     success = success,
                     latency_ms = latency_ms,
-                    "zerobus.batch.metrics"
+                    "{}",
+                    names.batch_metrics
                 );
 
                 tracing::info!(
-                    metric.name = "zerobus.batch.success",
+                    metric.name = names.batch_success(),
                     metric.value = if success { 1i64 } else { 0i64 },
                     success = success,
-                    "zerobus.batch.metrics"
+                    "{}",
+                    names.batch_metrics
                 );
 
                 tracing::info!(
-                    metric.name = "zerobus.batch.latency_ms",
+                    metric.name = names.batch_latency_ms(),
                     metric.value = latency_ms,
                     metric.unit = "ms",
                     latency_ms = latency_ms,
-                    "zerobus.batch.metrics"
+                    "{}",
+                    names.batch_metrics
                 );
             }
         }
@@ -165,6 +250,7 @@ impl ObservabilityManager {
                 _table_name: table_name.to_string(),
                 start_time,
                 library: self.library.clone(),
+                names: self.names.clone(),
             }
         }
 
@@ -220,6 +306,8 @@ pub struct ObservabilitySpan {
     start_time: std::time::SystemTime,
     #[cfg(feature = "observability")]
     library: Option<Arc<OtlpLibrary>>,
+    #[cfg(feature = "observability")]
+    names: Arc<TelemetryNames>,
 }
 
 impl Drop for ObservabilitySpan {
@@ -237,10 +325,11 @@ impl Drop for ObservabilitySpan {
                 // The otlp-rust-service SDK infrastructure picks up these tracing events
                 // and converts them to OpenTelemetry traces
                 tracing::info!(
-                    span.name = "zerobus.send_batch",
+                    span.name = self.names.send_batch_span(),
                     span.table_name = %self._table_name,
                     span.duration_ms = duration,
-                    "zerobus.send_batch.completed"
+                    "{}",
+                    self.names.send_batch_completed
                 );
             }
         }
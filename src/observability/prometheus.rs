@@ -0,0 +1,186 @@
+//! Cumulative send metrics in Prometheus text exposition format
+//!
+//! `SendMetrics` accumulates counters and a latency histogram over the lifetime of a
+//! `ZerobusWrapper`, independently of the OTLP pipeline, so they can be scraped
+//! directly by Prometheus (see `ZerobusWrapper::metrics_prometheus`).
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use crate::error::ZerobusError;
+
+/// Upper bounds of the batch latency histogram buckets, in milliseconds
+pub const LATENCY_BUCKETS_MS: [u64; 11] = [5, 10, 25, 50, 100, 250, 500, 1000, 2500, 5000, 10000];
+
+/// Cumulative counters for batches sent by one wrapper
+#[derive(Debug, Default)]
+pub(crate) struct SendMetrics {
+    batches: AtomicU64,
+    batches_failed: AtomicU64,
+    rows_succeeded: AtomicU64,
+    bytes: AtomicU64,
+    retries: AtomicU64,
+    streams_created: AtomicU64,
+    stream_reconnects: AtomicU64,
+    /// Failed rows by `ZerobusError::error_type`
+    rows_failed: Mutex<BTreeMap<&'static str, u64>>,
+    /// Count per entry of `LATENCY_BUCKETS_MS`, plus a final `+Inf` bucket (not cumulative)
+    latency_buckets: [AtomicU64; LATENCY_BUCKETS_MS.len() + 1],
+    latency_sum_ms: AtomicU64,
+}
+
+impl SendMetrics {
+    /// Record a finished `send_batch`
+    ///
+    /// `failed_rows` are per-row failures; `batch_error` is set when the whole batch
+    /// failed before any row was processed.
+    pub(crate) fn record_batch(
+        &self,
+        batch_size_bytes: usize,
+        attempts: u32,
+        latency_ms: u64,
+        successful_count: usize,
+        failed_rows: &[(usize, ZerobusError)],
+        batch_error: Option<&ZerobusError>,
+    ) {
+        self.batches.fetch_add(1, Ordering::Relaxed);
+        if batch_error.is_some() {
+            self.batches_failed.fetch_add(1, Ordering::Relaxed);
+        }
+        self.rows_succeeded
+            .fetch_add(successful_count as u64, Ordering::Relaxed);
+        if batch_error.is_none() {
+            self.bytes
+                .fetch_add(batch_size_bytes as u64, Ordering::Relaxed);
+        }
+        self.retries
+            .fetch_add(u64::from(attempts.saturating_sub(1)), Ordering::Relaxed);
+
+        if !failed_rows.is_empty() {
+            let mut rows_failed = self
+                .rows_failed
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            for (_, error) in failed_rows {
+                *rows_failed.entry(error.error_type()).or_insert(0) += 1;
+            }
+        }
+
+        let bucket = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&bound| latency_ms <= bound)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.latency_buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.latency_sum_ms.fetch_add(latency_ms, Ordering::Relaxed);
+    }
+
+    /// Record that a Zerobus stream was created; every stream after the first is a reconnect
+    pub(crate) fn record_stream_created(&self) {
+        if self.streams_created.fetch_add(1, Ordering::Relaxed) > 0 {
+            self.stream_reconnects.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Render all metrics in Prometheus text exposition format
+    ///
+    /// Metric names start with `namespace`, which configuration validation already
+    /// restricts to characters valid in a Prometheus name; every sample is labelled with
+    /// `table`.
+    pub(crate) fn render(&self, namespace: &str, table: &str) -> String {
+        let prefix = namespace;
+        let table = escape_label_value(table);
+        let mut out = String::new();
+
+        let counters = [
+            (
+                "batches_total",
+                "Batches sent, including batches that failed",
+                &self.batches,
+            ),
+            (
+                "batches_failed_total",
+                "Batches that failed before any row was processed",
+                &self.batches_failed,
+            ),
+            (
+                "rows_succeeded_total",
+                "Rows written successfully",
+                &self.rows_succeeded,
+            ),
+            (
+                "bytes_total",
+                "Serialized bytes of batches that were sent",
+                &self.bytes,
+            ),
+            (
+                "retries_total",
+                "Batch send attempts beyond the first",
+                &self.retries,
+            ),
+            (
+                "stream_reconnects_total",
+                "Zerobus streams recreated after the first",
+                &self.stream_reconnects,
+            ),
+        ];
+        for (name, help, value) in counters {
+            let _ = writeln!(out, "# HELP {prefix}_{name} {help}");
+            let _ = writeln!(out, "# TYPE {prefix}_{name} counter");
+            let _ = writeln!(
+                out,
+                "{prefix}_{name}{{table=\"{table}\"}} {}",
+                value.load(Ordering::Relaxed)
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP {prefix}_rows_failed_total Rows that failed, by error type"
+        );
+        let _ = writeln!(out, "# TYPE {prefix}_rows_failed_total counter");
+        let rows_failed = self
+            .rows_failed
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        for (error_type, count) in rows_failed.iter() {
+            let _ = writeln!(
+                out,
+                "{prefix}_rows_failed_total{{table=\"{table}\",error_type=\"{error_type}\"}} {count}"
+            );
+        }
+        drop(rows_failed);
+
+        let name = format!("{prefix}_batch_latency_ms");
+        let _ = writeln!(out, "# HELP {name} Batch send latency in milliseconds");
+        let _ = writeln!(out, "# TYPE {name} histogram");
+        let mut cumulative = 0;
+        for (i, bucket) in self.latency_buckets.iter().enumerate() {
+            cumulative += bucket.load(Ordering::Relaxed);
+            let le = LATENCY_BUCKETS_MS
+                .get(i)
+                .map_or_else(|| "+Inf".to_string(), |bound| bound.to_string());
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{table=\"{table}\",le=\"{le}\"}} {cumulative}"
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{name}_sum{{table=\"{table}\"}} {}",
+            self.latency_sum_ms.load(Ordering::Relaxed)
+        );
+        let _ = writeln!(out, "{name}_count{{table=\"{table}\"}} {cumulative}");
+
+        out
+    }
+}
+
+/// Escape a Prometheus label value (`\`, `"` and newlines)
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
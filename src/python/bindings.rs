@@ -15,7 +15,7 @@ use arrow::datatypes::DataType;
 use arrow::record_batch::RecordBatch;
 use pyo3::exceptions::{PyException, PyNotImplementedError, PyTypeError};
 use pyo3::prelude::*;
-use pyo3::types::{PyDict, PyModule};
+use pyo3::types::{PyBytes, PyDict, PyModule};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -51,6 +51,7 @@ pub fn rust_error_to_python_error(error: ZerobusError) -> PyErr {
         ZerobusError::TransmissionError(msg) => PyErr::new::<PyTransmissionError, _>(msg),
         ZerobusError::RetryExhausted(msg) => PyErr::new::<PyRetryExhausted, _>(msg),
         ZerobusError::TokenRefreshError(msg) => PyErr::new::<PyTokenRefreshError, _>(msg),
+        ZerobusError::Throttled { message, .. } => PyErr::new::<PyTransmissionError, _>(message),
     }
 }
 
@@ -345,13 +346,75 @@ impl PyWrapperConfiguration {
     ///     retry_base_delay_ms: Base delay in milliseconds for exponential backoff
     ///     retry_max_delay_ms: Maximum delay in milliseconds for exponential backoff
     ///     zerobus_writer_disabled: Disable Zerobus SDK transmission while maintaining debug output
+    ///     track_row_sizes: Report serialized size per row in TransmissionResult.row_sizes (default: False)
+    ///     explicit_field_presence: Mark nullable fields as proto3 optional in generated descriptors (default: False)
+    ///     max_pending_futures: Maximum in-flight records per batch before awaiting acknowledgments (default: 1000)
+    ///     allowed_endpoint_hosts: Optional list of allowed endpoint hosts or parent domains (default: None = no check)
+    ///     validate_descriptor_schema: Check descriptors passed to send_batch_with_descriptor against the batch schema (default: False)
+    ///     shutdown_timeout_secs: Maximum seconds shutdown() waits for the stream to close (default: 30)
+    ///     propagate_schema_metadata: Copy selected Arrow schema metadata into the debug descriptor's options (default: False)
+    ///     schema_metadata_keys: Schema metadata keys copied by propagate_schema_metadata (required with it)
+    ///     connect_retry_max_attempts: Maximum attempts for SDK/stream creation, retried separately from batches (default: None = use batch retry)
+    ///     connect_retry_base_delay_ms: Base delay in milliseconds for connect retry backoff
+    ///     connect_retry_max_delay_ms: Maximum delay in milliseconds for connect retry backoff
+    ///     retryable_error_patterns: Error message substrings that force a retry; a stopgap pending structured errors (default: None)
+    ///     fatal_error_patterns: Error message substrings that stop retrying immediately, checked first (default: None)
+    ///     mirror_table_name: Optional secondary table every batch is also written to (default: None)
+    ///     mirror_failures_fatal: Treat mirror table failures as failures of the send (default: False)
+    ///     memory_budget_bytes: Maximum bytes buffered across concurrent sends (default: None = unlimited)
+    ///     redact_values_in_errors: Withhold encoder detail from per-row conversion errors (default: False)
+    ///     sensitive_fields: Field names masked in per-row conversion errors (default: None)
+    ///     rate_limit_records_per_sec: Maximum records per second sent to the table (default: None = unlimited)
+    ///     schema_evolution: Schema change handling: "strict" or "add_only" (default: "strict")
+    ///     field_name_transform: Rename applied to Arrow field names: "none", "snake_case" or "camel_case" (default: "none")
+    ///     capture_failed_bytes: Keep serialized bytes of rows that fail transmission in TransmissionResult.failed_row_bytes (default: False)
+    ///     column_mismatch_tolerance: Column-count divergence tolerated for provided descriptors before rejecting the batch (default: None = warn only)
+    ///     proto_package: Protobuf package for debug descriptor files, which are written as proto3 FileDescriptorProtos (default: None)
+    ///     max_failures_before_abort: Failed conversions tolerated per batch before the remaining rows are aborted (default: None = convert every row)
+    ///     debug_proto_header: Start each Protobuf debug file with a magic/version/descriptor-fingerprint header (default: False)
+    ///     float_policy: NaN/Infinity handling for float columns: "passthrough", "error", "null" or "zero" (default: "passthrough")
+    ///     validate_utf8: Validate that string values are UTF-8, failing rows that are not (default: False)
+    ///     telemetry_namespace: Prefix for observability span and metric names instead of "zerobus" (default: None)
+    ///     lenient_bool_coercion: Accept Int8/Int32 columns (nonzero = True) for bool fields (default: False)
+    ///     emit_defaults: Write the proto3 default for null scalar fields instead of omitting them (default: False)
+    ///     descriptor_fallback: Retry conversion with a generated descriptor when a provided one converts no rows (default: False)
+    ///     idle_stream_timeout_secs: Recreate the stream before a send if it has been idle longer than this many seconds (default: None = never)
+    ///     descriptor_validation_mode: Descriptor soft limit violations: "strict" (error) or "warn_soft_limits" (log a warning) (default: "strict")
+    ///     descriptor_max_fields: Hard maximum fields per descriptor message, at most 2000 (default: 2000)
+    ///     descriptor_max_nesting_depth: Hard maximum descriptor nesting depth, at most 100 (default: 10)
+    ///     descriptor_soft_max_fields: Soft maximum fields per descriptor message (default: None)
+    ///     descriptor_soft_max_nesting_depth: Soft maximum descriptor nesting depth (default: None)
+    ///     send_deadline_ms: Deadline in milliseconds for sending a batch, including retries; enforced client-side (default: None = no deadline)
+    ///     auto_retry_failed_rows: Rounds of re-sending rows that failed transiently in transmission (default: 0 = disabled)
+    ///     transmission_compression: Ingest stream codec: "none", "gzip" or "zstd"; the SDK currently supports only "none" (default: "none")
+    ///     conversion_parallelism: Row chunks of a batch converted concurrently; requires the `parallel` feature (default: 1 = sequential)
+    ///     max_field_name_length: Longest column name accepted when generating a descriptor (default: 255)
+    ///     flush_every_batch: Flush the stream after every batch, even partially successful ones (default: False)
+    ///     debug_global_max_files: Maximum rotated debug files across all tables sharing debug_output_dir (optional, default: None = no global limit)
+    ///     schema_propagation_wait_ms: Wait in milliseconds before retrying when the first record fails because the table schema is not yet propagated (default: None = 100ms)
+    ///     null_element_policy: Handling of null elements inside list columns: "skip", "error" or "default" (default: "skip")
+    ///     conversion_errors_fatal: Fail the whole batch with a ConversionError if any row fails conversion, instead of sending the rest (default: False)
+    ///     timestamp_target_unit: Unit timestamps are written in: "second", "millisecond", "microsecond" or "nanosecond" (default: "microsecond")
+    ///     max_batch_bytes: Largest batch in bytes sent as a whole (optional, default: None = no limit)
+    ///     oversized_batch_policy: Handling of batches over max_batch_bytes: "reject", "auto_split" or "truncate" (default: "reject")
+    ///     string_null_policy: Encoding of null strings: "omit", "presence" or "sentinel" (default: "omit")
+    ///     string_null_sentinel: Value written for null strings, required with string_null_policy="sentinel" (optional)
+    ///     error_key_column: Column whose value names failed rows in per-row error messages (optional)
+    ///     delivery_mode: "at_least_once", or "fire_and_forget" to report rows as sent without awaiting acknowledgments (default: "at_least_once")
+    ///     wal_dir: Directory of a write-ahead log keeping records until acknowledged; re-send leftovers with ZerobusWrapper.recover_wal() (optional)
+    ///     stream_recreate_base_delay_ms: Base delay in milliseconds before recreating a stream that closed mid-batch, doubling with jitter per attempt (default: 100)
+    ///     stream_recreate_max_delay_ms: Maximum delay in milliseconds before recreating a closed stream (default: 1000)
+    ///     max_tracked_failures: Failed rows per batch listed in failed_rows; further failures are only counted (default: None = list every failure)
+    ///     explicit_schema_handshake: Register the descriptor when a stream is created and report its rejection as a configuration error (default: False)
+    ///     max_buffer_age_ms: Flush buffered records once the oldest has waited this many milliseconds (default: None = flush on count/size only)
+    ///     descriptor_auto_recovery: Retry a generated descriptor rejected on the first record once with timestamps as strings (default: False)
     ///
     /// Raises:
     ///     ZerobusError: If configuration is invalid or initialization fails
     ///         - ConfigurationError if debug_enabled is True but debug_output_dir is None
     ///         - ConfigurationError if zerobus_writer_disabled is True but debug_enabled is False
     #[new]
-    #[pyo3(signature = (endpoint, table_name, *, client_id=None, client_secret=None, unity_catalog_url=None, observability_enabled=false, observability_config=None, debug_enabled=false, debug_arrow_enabled=None, debug_protobuf_enabled=None, debug_output_dir=None, debug_flush_interval_secs=5, debug_max_file_size=None, debug_max_files_retained=10, retry_max_attempts=5, retry_base_delay_ms=100, retry_max_delay_ms=30000, zerobus_writer_disabled=false))]
+    #[pyo3(signature = (endpoint, table_name, *, client_id=None, client_secret=None, unity_catalog_url=None, observability_enabled=false, observability_config=None, debug_enabled=false, debug_arrow_enabled=None, debug_protobuf_enabled=None, debug_output_dir=None, debug_flush_interval_secs=5, debug_max_file_size=None, debug_max_files_retained=10, retry_max_attempts=5, retry_base_delay_ms=100, retry_max_delay_ms=30000, zerobus_writer_disabled=false, track_row_sizes=false, explicit_field_presence=false, max_pending_futures=1000, allowed_endpoint_hosts=None, validate_descriptor_schema=false, shutdown_timeout_secs=30, propagate_schema_metadata=false, schema_metadata_keys=None, connect_retry_max_attempts=None, connect_retry_base_delay_ms=100, connect_retry_max_delay_ms=30000, retryable_error_patterns=None, fatal_error_patterns=None, mirror_table_name=None, mirror_failures_fatal=false, memory_budget_bytes=None, redact_values_in_errors=false, sensitive_fields=None, rate_limit_records_per_sec=None, schema_evolution="strict", field_name_transform="none", capture_failed_bytes=false, column_mismatch_tolerance=None, proto_package=None, max_failures_before_abort=None, debug_proto_header=false, float_policy="passthrough", validate_utf8=false, telemetry_namespace=None, lenient_bool_coercion=false, emit_defaults=false, descriptor_fallback=false, idle_stream_timeout_secs=None, descriptor_validation_mode="strict", descriptor_max_fields=2000, descriptor_max_nesting_depth=10, descriptor_soft_max_fields=None, descriptor_soft_max_nesting_depth=None, send_deadline_ms=None, auto_retry_failed_rows=0, transmission_compression="none", conversion_parallelism=1, max_field_name_length=255, flush_every_batch=false, debug_global_max_files=None, schema_propagation_wait_ms=None, null_element_policy="skip", conversion_errors_fatal=false, timestamp_target_unit="microsecond", max_batch_bytes=None, oversized_batch_policy="reject", string_null_policy="omit", string_null_sentinel=None, error_key_column=None, delivery_mode="at_least_once", wal_dir=None, stream_recreate_base_delay_ms=100, stream_recreate_max_delay_ms=1000, max_tracked_failures=None, explicit_schema_handshake=false, max_buffer_age_ms=None, descriptor_auto_recovery=false))]
     pub fn new(
         endpoint: String,
         table_name: String,
@@ -371,6 +434,68 @@ impl PyWrapperConfiguration {
         retry_base_delay_ms: u64,
         retry_max_delay_ms: u64,
         zerobus_writer_disabled: bool,
+        track_row_sizes: bool,
+        explicit_field_presence: bool,
+        max_pending_futures: usize,
+        allowed_endpoint_hosts: Option<Vec<String>>,
+        validate_descriptor_schema: bool,
+        shutdown_timeout_secs: u64,
+        propagate_schema_metadata: bool,
+        schema_metadata_keys: Option<Vec<String>>,
+        connect_retry_max_attempts: Option<u32>,
+        connect_retry_base_delay_ms: u64,
+        connect_retry_max_delay_ms: u64,
+        retryable_error_patterns: Option<Vec<String>>,
+        fatal_error_patterns: Option<Vec<String>>,
+        mirror_table_name: Option<String>,
+        mirror_failures_fatal: bool,
+        memory_budget_bytes: Option<usize>,
+        redact_values_in_errors: bool,
+        sensitive_fields: Option<Vec<String>>,
+        rate_limit_records_per_sec: Option<u32>,
+        schema_evolution: &str,
+        field_name_transform: &str,
+        capture_failed_bytes: bool,
+        column_mismatch_tolerance: Option<usize>,
+        proto_package: Option<String>,
+        max_failures_before_abort: Option<usize>,
+        debug_proto_header: bool,
+        float_policy: &str,
+        validate_utf8: bool,
+        telemetry_namespace: Option<String>,
+        lenient_bool_coercion: bool,
+        emit_defaults: bool,
+        descriptor_fallback: bool,
+        idle_stream_timeout_secs: Option<u64>,
+        descriptor_validation_mode: &str,
+        descriptor_max_fields: usize,
+        descriptor_max_nesting_depth: usize,
+        descriptor_soft_max_fields: Option<usize>,
+        descriptor_soft_max_nesting_depth: Option<usize>,
+        send_deadline_ms: Option<u64>,
+        auto_retry_failed_rows: u32,
+        transmission_compression: &str,
+        conversion_parallelism: usize,
+        max_field_name_length: usize,
+        flush_every_batch: bool,
+        debug_global_max_files: Option<usize>,
+        schema_propagation_wait_ms: Option<u64>,
+        null_element_policy: &str,
+        conversion_errors_fatal: bool,
+        timestamp_target_unit: &str,
+        max_batch_bytes: Option<u64>,
+        oversized_batch_policy: &str,
+        string_null_policy: &str,
+        string_null_sentinel: Option<String>,
+        error_key_column: Option<String>,
+        delivery_mode: &str,
+        wal_dir: Option<String>,
+        stream_recreate_base_delay_ms: u64,
+        stream_recreate_max_delay_ms: u64,
+        max_tracked_failures: Option<usize>,
+        explicit_schema_handshake: bool,
+        max_buffer_age_ms: Option<u64>,
+        descriptor_auto_recovery: bool,
     ) -> PyResult<Self> {
         let mut config = WrapperConfiguration::new(endpoint, table_name);
 
@@ -469,6 +594,286 @@ impl PyWrapperConfiguration {
             config = config.with_zerobus_writer_disabled(true);
         }
 
+        if track_row_sizes {
+            config = config.with_track_row_sizes(true);
+        }
+
+        if explicit_field_presence {
+            config = config.with_explicit_field_presence(true);
+        }
+
+        config = config.with_max_pending_futures(max_pending_futures);
+
+        if let Some(hosts) = allowed_endpoint_hosts {
+            config = config.with_allowed_endpoint_hosts(hosts);
+        }
+
+        if validate_descriptor_schema {
+            config = config.with_descriptor_schema_validation(true);
+        }
+
+        config =
+            config.with_shutdown_timeout(std::time::Duration::from_secs(shutdown_timeout_secs));
+
+        if propagate_schema_metadata {
+            config = config.with_propagate_schema_metadata(true);
+        }
+
+        if let Some(keys) = schema_metadata_keys {
+            config = config.with_schema_metadata_keys(keys);
+        }
+
+        if let Some(max_attempts) = connect_retry_max_attempts {
+            config = config.with_connect_retry_config(
+                max_attempts,
+                connect_retry_base_delay_ms,
+                connect_retry_max_delay_ms,
+            );
+        }
+
+        if let Some(patterns) = retryable_error_patterns {
+            config = config.with_retryable_error_patterns(patterns);
+        }
+        if let Some(patterns) = fatal_error_patterns {
+            config = config.with_fatal_error_patterns(patterns);
+        }
+
+        if let Some(mirror) = mirror_table_name {
+            config = config.with_mirror_table(mirror);
+        }
+
+        if mirror_failures_fatal {
+            config = config.with_mirror_failures_fatal(true);
+        }
+
+        if let Some(bytes) = memory_budget_bytes {
+            config = config.with_memory_budget(bytes);
+        }
+
+        if redact_values_in_errors {
+            config = config.with_redact_values_in_errors(true);
+        }
+
+        if let Some(fields) = sensitive_fields {
+            config = config.with_sensitive_fields(fields);
+        }
+
+        if let Some(records_per_sec) = rate_limit_records_per_sec {
+            config = config.with_rate_limit(records_per_sec);
+        }
+
+        config = config.with_schema_evolution(match schema_evolution {
+            "strict" => crate::config::SchemaEvolution::Strict,
+            "add_only" => crate::config::SchemaEvolution::AddOnly,
+            other => {
+                return Err(PyErr::new::<PyConfigurationError, _>(format!(
+                    "schema_evolution must be 'strict' or 'add_only'. Got: '{}'",
+                    other
+                )))
+            }
+        });
+
+        config = config.with_field_name_transform(match field_name_transform {
+            "none" => crate::config::FieldNameTransform::None,
+            "snake_case" => crate::config::FieldNameTransform::ToSnakeCase,
+            "camel_case" => crate::config::FieldNameTransform::ToCamelCase,
+            other => {
+                return Err(PyErr::new::<PyConfigurationError, _>(format!(
+                    "field_name_transform must be 'none', 'snake_case' or 'camel_case'. Got: '{}'",
+                    other
+                )))
+            }
+        });
+
+        if capture_failed_bytes {
+            config = config.with_capture_failed_bytes(true);
+        }
+
+        if let Some(max_mismatched_columns) = column_mismatch_tolerance {
+            config = config.with_column_mismatch_tolerance(max_mismatched_columns);
+        }
+
+        if let Some(package) = proto_package {
+            config = config.with_proto_package(package);
+        }
+
+        if let Some(max_failures) = max_failures_before_abort {
+            config = config.with_max_failures_before_abort(max_failures);
+        }
+
+        config = config.with_debug_proto_header(debug_proto_header);
+
+        config = config.with_float_policy(match float_policy {
+            "passthrough" => crate::config::FloatPolicy::Passthrough,
+            "error" => crate::config::FloatPolicy::ErrorOnNonFinite,
+            "null" => crate::config::FloatPolicy::CoerceToNull,
+            "zero" => crate::config::FloatPolicy::CoerceToZero,
+            other => {
+                return Err(PyErr::new::<PyConfigurationError, _>(format!(
+                    "float_policy must be 'passthrough', 'error', 'null' or 'zero'. Got: '{}'",
+                    other
+                )))
+            }
+        });
+
+        config = config.with_validate_utf8(validate_utf8);
+
+        if let Some(namespace) = telemetry_namespace {
+            config = config.with_telemetry_namespace(namespace);
+        }
+
+        config = config.with_lenient_bool_coercion(lenient_bool_coercion);
+        config = config.with_emit_defaults(emit_defaults);
+        config = config.with_descriptor_fallback(descriptor_fallback);
+
+        if let Some(secs) = idle_stream_timeout_secs {
+            config = config.with_idle_stream_timeout(std::time::Duration::from_secs(secs));
+        }
+
+        config = config.with_descriptor_validation_mode(match descriptor_validation_mode {
+            "strict" => crate::config::DescriptorValidationMode::Strict,
+            "warn_soft_limits" => crate::config::DescriptorValidationMode::WarnSoftLimits,
+            other => {
+                return Err(PyErr::new::<PyConfigurationError, _>(format!(
+                    "descriptor_validation_mode must be 'strict' or 'warn_soft_limits'. Got: '{}'",
+                    other
+                )))
+            }
+        });
+        config = config
+            .with_descriptor_hard_limits(descriptor_max_fields, descriptor_max_nesting_depth)
+            .with_descriptor_soft_limits(
+                descriptor_soft_max_fields,
+                descriptor_soft_max_nesting_depth,
+            );
+
+        if let Some(ms) = send_deadline_ms {
+            config = config.with_send_deadline(std::time::Duration::from_millis(ms));
+        }
+
+        config = config
+            .with_auto_retry_failed_rows(auto_retry_failed_rows)
+            .with_transmission_compression(match transmission_compression {
+                "none" => crate::config::CompressionType::None,
+                "gzip" => crate::config::CompressionType::Gzip,
+                "zstd" => crate::config::CompressionType::Zstd,
+                other => {
+                    return Err(PyErr::new::<PyConfigurationError, _>(format!(
+                        "transmission_compression must be 'none', 'gzip' or 'zstd'. Got: '{}'",
+                        other
+                    )))
+                }
+            })
+            .with_conversion_parallelism(conversion_parallelism)
+            .with_max_field_name_length(max_field_name_length)
+            .with_flush_every_batch(flush_every_batch)
+            .with_debug_global_max_files(debug_global_max_files);
+
+        if let Some(ms) = schema_propagation_wait_ms {
+            config = config.with_schema_propagation_wait(std::time::Duration::from_millis(ms));
+        }
+
+        config = config.with_null_element_policy(match null_element_policy {
+            "skip" => crate::config::NullElementPolicy::SkipNullElements,
+            "error" => crate::config::NullElementPolicy::ErrorOnNullElement,
+            "default" => crate::config::NullElementPolicy::EmitDefaultForNull,
+            other => {
+                return Err(PyErr::new::<PyConfigurationError, _>(format!(
+                    "null_element_policy must be 'skip', 'error' or 'default'. Got: '{}'",
+                    other
+                )))
+            }
+        });
+
+        config = config.with_conversion_errors_fatal(conversion_errors_fatal);
+
+        config = config.with_timestamp_target_unit(match timestamp_target_unit {
+            "second" => arrow::datatypes::TimeUnit::Second,
+            "millisecond" => arrow::datatypes::TimeUnit::Millisecond,
+            "microsecond" => arrow::datatypes::TimeUnit::Microsecond,
+            "nanosecond" => arrow::datatypes::TimeUnit::Nanosecond,
+            other => {
+                return Err(PyErr::new::<PyConfigurationError, _>(format!(
+                    "timestamp_target_unit must be 'second', 'millisecond', 'microsecond' or 'nanosecond'. Got: '{}'",
+                    other
+                )))
+            }
+        });
+
+        if let Some(max_bytes) = max_batch_bytes {
+            config = config.with_max_batch_bytes(max_bytes);
+        }
+
+        config = config.with_oversized_batch_policy(match oversized_batch_policy {
+            "reject" => crate::config::OversizedBatchPolicy::Reject,
+            "auto_split" => crate::config::OversizedBatchPolicy::AutoSplit,
+            "truncate" => crate::config::OversizedBatchPolicy::Truncate,
+            other => {
+                return Err(PyErr::new::<PyConfigurationError, _>(format!(
+                "oversized_batch_policy must be 'reject', 'auto_split' or 'truncate'. Got: '{}'",
+                other
+            )))
+            }
+        });
+
+        config = config.with_string_null_policy(match (string_null_policy, string_null_sentinel) {
+            ("omit", None) => crate::config::StringNullPolicy::Omit,
+            ("presence", None) => crate::config::StringNullPolicy::ExplicitPresence,
+            ("sentinel", Some(sentinel)) => crate::config::StringNullPolicy::Sentinel(sentinel),
+            ("sentinel", None) => {
+                return Err(PyErr::new::<PyConfigurationError, _>(
+                    "string_null_policy='sentinel' requires string_null_sentinel",
+                ))
+            }
+            ("omit" | "presence", Some(_)) => {
+                return Err(PyErr::new::<PyConfigurationError, _>(
+                    "string_null_sentinel is only used with string_null_policy='sentinel'",
+                ))
+            }
+            (other, _) => {
+                return Err(PyErr::new::<PyConfigurationError, _>(format!(
+                    "string_null_policy must be 'omit', 'presence' or 'sentinel'. Got: '{}'",
+                    other
+                )))
+            }
+        });
+
+        if let Some(column) = error_key_column {
+            config = config.with_error_key_column(column);
+        }
+
+        config = config.with_delivery_mode(match delivery_mode {
+            "at_least_once" => crate::config::DeliveryMode::AtLeastOnce,
+            "fire_and_forget" => crate::config::DeliveryMode::FireAndForget,
+            other => {
+                return Err(PyErr::new::<PyConfigurationError, _>(format!(
+                    "delivery_mode must be 'at_least_once' or 'fire_and_forget'. Got: '{}'",
+                    other
+                )))
+            }
+        });
+
+        if let Some(dir) = wal_dir {
+            config = config.with_wal_dir(PathBuf::from(dir));
+        }
+
+        config = config.with_stream_recreate_backoff(
+            stream_recreate_base_delay_ms,
+            stream_recreate_max_delay_ms,
+        );
+
+        if let Some(max_tracked) = max_tracked_failures {
+            config = config.with_max_tracked_failures(max_tracked);
+        }
+
+        config = config.with_explicit_schema_handshake(explicit_schema_handshake);
+
+        if let Some(ms) = max_buffer_age_ms {
+            config = config.with_max_buffer_age(std::time::Duration::from_millis(ms));
+        }
+
+        config = config.with_descriptor_auto_recovery(descriptor_auto_recovery);
+
         Ok(Self { inner: config })
     }
 
@@ -477,6 +882,14 @@ impl PyWrapperConfiguration {
         Ok(())
     }
 
+    /// Render the effective configuration with client_id and client_secret redacted.
+    ///
+    /// Returns:
+    ///     Multi-line configuration snapshot, safe to paste into logs and bug reports
+    fn redacted_summary(&self) -> String {
+        self.inner.redacted_summary()
+    }
+
     // Getters for configuration fields
     #[getter]
     fn endpoint(&self) -> String {
@@ -558,6 +971,379 @@ impl PyWrapperConfiguration {
     fn zerobus_writer_disabled(&self) -> bool {
         self.inner.zerobus_writer_disabled
     }
+
+    #[getter]
+    fn track_row_sizes(&self) -> bool {
+        self.inner.track_row_sizes
+    }
+
+    #[getter]
+    fn explicit_field_presence(&self) -> bool {
+        self.inner.explicit_field_presence
+    }
+
+    #[getter]
+    fn max_pending_futures(&self) -> usize {
+        self.inner.max_pending_futures
+    }
+
+    #[getter]
+    fn allowed_endpoint_hosts(&self) -> Option<Vec<String>> {
+        self.inner.allowed_endpoint_hosts.clone()
+    }
+
+    #[getter]
+    fn validate_descriptor_schema(&self) -> bool {
+        self.inner.validate_descriptor_schema
+    }
+
+    #[getter]
+    fn shutdown_timeout_secs(&self) -> u64 {
+        self.inner.shutdown_timeout.as_secs()
+    }
+
+    #[getter]
+    fn propagate_schema_metadata(&self) -> bool {
+        self.inner.propagate_schema_metadata
+    }
+
+    #[getter]
+    fn schema_metadata_keys(&self) -> Vec<String> {
+        self.inner.schema_metadata_keys.clone()
+    }
+
+    #[getter]
+    fn connect_retry_max_attempts(&self) -> Option<u32> {
+        self.inner.connect_retry_max_attempts
+    }
+
+    #[getter]
+    fn connect_retry_base_delay_ms(&self) -> u64 {
+        self.inner.connect_retry_base_delay_ms
+    }
+
+    #[getter]
+    fn connect_retry_max_delay_ms(&self) -> u64 {
+        self.inner.connect_retry_max_delay_ms
+    }
+
+    #[getter]
+    fn retryable_error_patterns(&self) -> Vec<String> {
+        self.inner.retryable_error_patterns.clone()
+    }
+
+    #[getter]
+    fn fatal_error_patterns(&self) -> Vec<String> {
+        self.inner.fatal_error_patterns.clone()
+    }
+
+    #[getter]
+    fn mirror_table_name(&self) -> Option<String> {
+        self.inner.mirror_table_name.clone()
+    }
+
+    #[getter]
+    fn mirror_failures_fatal(&self) -> bool {
+        self.inner.mirror_failures_fatal
+    }
+
+    #[getter]
+    fn memory_budget_bytes(&self) -> Option<usize> {
+        self.inner.memory_budget_bytes
+    }
+
+    #[getter]
+    fn redact_values_in_errors(&self) -> bool {
+        self.inner.redact_values_in_errors
+    }
+
+    #[getter]
+    fn sensitive_fields(&self) -> Option<Vec<String>> {
+        self.inner.sensitive_fields.clone()
+    }
+
+    #[getter]
+    fn rate_limit_records_per_sec(&self) -> Option<u32> {
+        self.inner.rate_limit_records_per_sec
+    }
+
+    #[getter]
+    fn schema_evolution(&self) -> String {
+        match self.inner.schema_evolution {
+            crate::config::SchemaEvolution::Strict => "strict".to_string(),
+            crate::config::SchemaEvolution::AddOnly => "add_only".to_string(),
+        }
+    }
+
+    #[getter]
+    fn field_name_transform(&self) -> String {
+        match self.inner.field_name_transform {
+            crate::config::FieldNameTransform::None => "none",
+            crate::config::FieldNameTransform::ToSnakeCase => "snake_case",
+            crate::config::FieldNameTransform::ToCamelCase => "camel_case",
+            crate::config::FieldNameTransform::Custom(_) => "custom",
+        }
+        .to_string()
+    }
+
+    #[getter]
+    fn capture_failed_bytes(&self) -> bool {
+        self.inner.capture_failed_bytes
+    }
+
+    #[getter]
+    fn column_mismatch_tolerance(&self) -> Option<usize> {
+        self.inner.column_mismatch_tolerance
+    }
+
+    #[getter]
+    fn proto_package(&self) -> Option<String> {
+        self.inner.proto_package.clone()
+    }
+
+    #[getter]
+    fn max_failures_before_abort(&self) -> Option<usize> {
+        self.inner.max_failures_before_abort
+    }
+
+    #[getter]
+    fn debug_proto_header(&self) -> bool {
+        self.inner.debug_proto_header
+    }
+
+    #[getter]
+    fn float_policy(&self) -> String {
+        match self.inner.float_policy {
+            crate::config::FloatPolicy::Passthrough => "passthrough",
+            crate::config::FloatPolicy::ErrorOnNonFinite => "error",
+            crate::config::FloatPolicy::CoerceToNull => "null",
+            crate::config::FloatPolicy::CoerceToZero => "zero",
+        }
+        .to_string()
+    }
+
+    #[getter]
+    fn validate_utf8(&self) -> bool {
+        self.inner.validate_utf8
+    }
+
+    #[getter]
+    fn telemetry_namespace(&self) -> Option<String> {
+        self.inner.telemetry_namespace.clone()
+    }
+
+    #[getter]
+    fn lenient_bool_coercion(&self) -> bool {
+        self.inner.lenient_bool_coercion
+    }
+
+    #[getter]
+    fn emit_defaults(&self) -> bool {
+        self.inner.emit_defaults
+    }
+
+    #[getter]
+    fn descriptor_fallback(&self) -> bool {
+        self.inner.descriptor_fallback
+    }
+
+    #[getter]
+    fn idle_stream_timeout_secs(&self) -> Option<u64> {
+        self.inner
+            .idle_stream_timeout
+            .map(|timeout| timeout.as_secs())
+    }
+
+    #[getter]
+    fn descriptor_validation_mode(&self) -> String {
+        match self.inner.descriptor_validation_mode {
+            crate::config::DescriptorValidationMode::Strict => "strict",
+            crate::config::DescriptorValidationMode::WarnSoftLimits => "warn_soft_limits",
+        }
+        .to_string()
+    }
+
+    #[getter]
+    fn descriptor_max_fields(&self) -> usize {
+        self.inner.descriptor_max_fields
+    }
+
+    #[getter]
+    fn descriptor_max_nesting_depth(&self) -> usize {
+        self.inner.descriptor_max_nesting_depth
+    }
+
+    #[getter]
+    fn descriptor_soft_max_fields(&self) -> Option<usize> {
+        self.inner.descriptor_soft_max_fields
+    }
+
+    #[getter]
+    fn descriptor_soft_max_nesting_depth(&self) -> Option<usize> {
+        self.inner.descriptor_soft_max_nesting_depth
+    }
+
+    #[getter]
+    fn send_deadline_ms(&self) -> Option<u64> {
+        self.inner
+            .send_deadline
+            .map(|deadline| deadline.as_millis() as u64)
+    }
+
+    #[getter]
+    fn auto_retry_failed_rows(&self) -> u32 {
+        self.inner.auto_retry_failed_rows
+    }
+
+    #[getter]
+    fn transmission_compression(&self) -> String {
+        match self.inner.transmission_compression {
+            crate::config::CompressionType::None => "none",
+            crate::config::CompressionType::Gzip => "gzip",
+            crate::config::CompressionType::Zstd => "zstd",
+        }
+        .to_string()
+    }
+
+    #[getter]
+    fn conversion_parallelism(&self) -> usize {
+        self.inner.conversion_parallelism
+    }
+
+    #[getter]
+    fn max_field_name_length(&self) -> usize {
+        self.inner.max_field_name_length
+    }
+
+    #[getter]
+    fn flush_every_batch(&self) -> bool {
+        self.inner.flush_every_batch
+    }
+
+    #[getter]
+    fn debug_global_max_files(&self) -> Option<usize> {
+        self.inner.debug_global_max_files
+    }
+
+    #[getter]
+    fn schema_propagation_wait_ms(&self) -> Option<u64> {
+        self.inner
+            .schema_propagation_wait
+            .map(|wait| wait.as_millis() as u64)
+    }
+
+    #[getter]
+    fn null_element_policy(&self) -> String {
+        match self.inner.null_element_policy {
+            crate::config::NullElementPolicy::SkipNullElements => "skip",
+            crate::config::NullElementPolicy::ErrorOnNullElement => "error",
+            crate::config::NullElementPolicy::EmitDefaultForNull => "default",
+        }
+        .to_string()
+    }
+
+    #[getter]
+    fn conversion_errors_fatal(&self) -> bool {
+        self.inner.conversion_errors_fatal
+    }
+
+    #[getter]
+    fn timestamp_target_unit(&self) -> String {
+        match self.inner.timestamp_target_unit {
+            arrow::datatypes::TimeUnit::Second => "second",
+            arrow::datatypes::TimeUnit::Millisecond => "millisecond",
+            arrow::datatypes::TimeUnit::Microsecond => "microsecond",
+            arrow::datatypes::TimeUnit::Nanosecond => "nanosecond",
+        }
+        .to_string()
+    }
+
+    #[getter]
+    fn max_batch_bytes(&self) -> Option<u64> {
+        self.inner.max_batch_bytes
+    }
+
+    #[getter]
+    fn oversized_batch_policy(&self) -> String {
+        match self.inner.oversized_batch_policy {
+            crate::config::OversizedBatchPolicy::Reject => "reject",
+            crate::config::OversizedBatchPolicy::AutoSplit => "auto_split",
+            crate::config::OversizedBatchPolicy::Truncate => "truncate",
+        }
+        .to_string()
+    }
+
+    #[getter]
+    fn string_null_policy(&self) -> String {
+        match self.inner.string_null_policy {
+            crate::config::StringNullPolicy::Omit => "omit",
+            crate::config::StringNullPolicy::ExplicitPresence => "presence",
+            crate::config::StringNullPolicy::Sentinel(_) => "sentinel",
+        }
+        .to_string()
+    }
+
+    #[getter]
+    fn string_null_sentinel(&self) -> Option<String> {
+        match &self.inner.string_null_policy {
+            crate::config::StringNullPolicy::Sentinel(sentinel) => Some(sentinel.clone()),
+            _ => None,
+        }
+    }
+
+    #[getter]
+    fn error_key_column(&self) -> Option<String> {
+        self.inner.error_key_column.clone()
+    }
+
+    #[getter]
+    fn delivery_mode(&self) -> String {
+        match self.inner.delivery_mode {
+            crate::config::DeliveryMode::AtLeastOnce => "at_least_once",
+            crate::config::DeliveryMode::FireAndForget => "fire_and_forget",
+        }
+        .to_string()
+    }
+
+    #[getter]
+    fn wal_dir(&self) -> Option<String> {
+        self.inner
+            .wal_dir
+            .as_ref()
+            .map(|p| p.to_string_lossy().to_string())
+    }
+
+    #[getter]
+    fn stream_recreate_base_delay_ms(&self) -> u64 {
+        self.inner.stream_recreate_base_delay_ms
+    }
+
+    #[getter]
+    fn stream_recreate_max_delay_ms(&self) -> u64 {
+        self.inner.stream_recreate_max_delay_ms
+    }
+
+    #[getter]
+    fn max_tracked_failures(&self) -> Option<usize> {
+        self.inner.max_tracked_failures
+    }
+
+    #[getter]
+    fn explicit_schema_handshake(&self) -> bool {
+        self.inner.explicit_schema_handshake
+    }
+
+    #[getter]
+    fn max_buffer_age_ms(&self) -> Option<u64> {
+        self.inner
+            .max_buffer_age
+            .map(|max_age| max_age.as_millis() as u64)
+    }
+
+    #[getter]
+    fn descriptor_auto_recovery(&self) -> bool {
+        self.inner.descriptor_auto_recovery
+    }
 }
 
 /// Python wrapper for TransmissionResult
@@ -584,10 +1370,11 @@ impl PyTransmissionResult {
     ///     total_rows: Total number of rows in the batch
     ///     successful_count: Number of rows that succeeded
     ///     failed_count: Number of rows that failed
+    ///     row_sizes: Optional list of (row_index, size_bytes) tuples
     ///     message: Optional message (ignored, kept for backward compatibility)
     #[new]
     #[allow(clippy::too_many_arguments)]
-    #[pyo3(signature = (success, *, error=None, attempts=1, latency_ms=None, batch_size_bytes=0, failed_rows=None, successful_rows=None, total_rows=0, successful_count=0, failed_count=0, message=None))]
+    #[pyo3(signature = (success, *, error=None, attempts=1, latency_ms=None, batch_size_bytes=0, failed_rows=None, successful_rows=None, total_rows=0, successful_count=0, failed_count=0, row_sizes=None, message=None))]
     pub fn new(
         success: bool,
         error: Option<String>,
@@ -599,6 +1386,7 @@ impl PyTransmissionResult {
         total_rows: usize,
         successful_count: usize,
         failed_count: usize,
+        row_sizes: Option<Vec<(usize, usize)>>,
         #[allow(unused_variables)] message: Option<String>,
     ) -> Self {
         // Convert string error messages to ZerobusError
@@ -623,6 +1411,8 @@ impl PyTransmissionResult {
                 total_rows,
                 successful_count,
                 failed_count,
+                row_sizes,
+                ..Default::default()
             },
         }
     }
@@ -692,6 +1482,54 @@ impl PyTransmissionResult {
         self.inner.failed_count
     }
 
+    /// Get serialized size per row
+    ///
+    /// Returns a list of tuples (row_index, size_bytes) for rows that were converted,
+    /// or None if track_row_sizes was not enabled.
+    #[getter]
+    pub fn row_sizes(&self) -> Option<Vec<(usize, usize)>> {
+        self.inner.row_sizes.clone()
+    }
+
+    /// Get serialized bytes of rows that failed transmission
+    ///
+    /// Returns a list of tuples (row_index, bytes) with the exact Protobuf payloads
+    /// Zerobus rejected, or None if capture_failed_bytes was not enabled.
+    #[getter]
+    pub fn failed_row_bytes(&self, py: Python) -> Option<Vec<(usize, PyObject)>> {
+        self.inner.failed_row_bytes.as_ref().map(|rows| {
+            rows.iter()
+                .map(|(idx, bytes)| (*idx, PyBytes::new(py, bytes).into()))
+                .collect()
+        })
+    }
+
+    /// Get Zerobus acknowledgment ids of successful rows
+    ///
+    /// Returns a list of tuples (row_index, ack_id), or None if nothing was sent to
+    /// Zerobus (writer disabled mode or a batch-level error).
+    #[getter]
+    pub fn ack_ids(&self) -> Option<Vec<(usize, i64)>> {
+        self.inner.ack_ids.clone()
+    }
+
+    /// Result of the mirror table send, or None if no mirror table is configured
+    #[getter]
+    pub fn mirror_result(&self) -> Option<PyTransmissionResult> {
+        self.inner
+            .mirror_result
+            .as_ref()
+            .map(|result| PyTransmissionResult {
+                inner: (**result).clone(),
+            })
+    }
+
+    /// Whether failed_count includes failures not listed in failed_rows (max_tracked_failures)
+    #[getter]
+    pub fn failures_truncated(&self) -> bool {
+        self.inner.failures_truncated
+    }
+
     /// Get indices of failed rows
     ///
     /// Returns a list of row indices that failed, or empty list if none failed.
@@ -774,6 +1612,7 @@ impl PyTransmissionResult {
                 "ConfigurationError" => matches!(error, ZerobusError::ConfigurationError(_)),
                 "RetryExhausted" => matches!(error, ZerobusError::RetryExhausted(_)),
                 "TokenRefreshError" => matches!(error, ZerobusError::TokenRefreshError(_)),
+                "Throttled" => matches!(error, ZerobusError::Throttled { .. }),
                 _ => false,
             })
     }
@@ -810,6 +1649,31 @@ impl PyTransmissionResult {
         self.inner.group_errors_by_type()
     }
 
+    /// Get failed rows as parallel lists, ready to become Arrow or pandas columns
+    ///
+    /// Entry `i` of each list describes the same failed row, in the order of
+    /// `failed_rows`. Error types are the names used by `group_errors_by_type()`.
+    ///
+    /// Returns:
+    ///     Tuple of (row_indices, error_types, error_messages). All three lists are
+    ///     empty if no rows failed.
+    ///
+    /// Example:
+    ///     >>> indices, types, messages = result.failed_rows_as_arrays()
+    ///     >>> quarantine = pa.table({"row_index": indices, "error_type": types, "error_message": messages})
+    pub fn failed_rows_as_arrays(&self) -> (Vec<usize>, Vec<String>, Vec<String>) {
+        let failed_rows = self.inner.failed_rows.as_deref().unwrap_or_default();
+        let mut row_indices = Vec::with_capacity(failed_rows.len());
+        let mut error_types = Vec::with_capacity(failed_rows.len());
+        let mut error_messages = Vec::with_capacity(failed_rows.len());
+        for (row_idx, error) in failed_rows {
+            row_indices.push(*row_idx);
+            error_types.push(error.error_type().to_string());
+            error_messages.push(error.to_string());
+        }
+        (row_indices, error_types, error_messages)
+    }
+
     /// Get error statistics for this transmission result
     ///
     /// Returns:
@@ -913,6 +1777,126 @@ impl PyZerobusWrapper {
         }
     }
 
+    /// Send an Arrow RecordBatch to Zerobus using a caller-supplied Protobuf descriptor.
+    ///
+    /// Use this when the table has a hand-built or registered descriptor that
+    /// auto-generation from the Arrow schema would not reproduce exactly.
+    ///
+    /// Args:
+    ///     batch: PyArrow RecordBatch to send
+    ///     descriptor_bytes: Serialized `google.protobuf.DescriptorProto`
+    ///         (e.g. `descriptor_proto.SerializeToString()`)
+    ///
+    /// Returns:
+    ///     TransmissionResult indicating success or failure
+    ///
+    /// Raises:
+    ///     ConfigurationError: If the descriptor cannot be decoded or is invalid
+    ///     ZerobusError: If transmission fails after all retry attempts
+    fn send_batch_with_descriptor(
+        &self,
+        py: Python,
+        batch: PyObject,
+        descriptor_bytes: &[u8],
+    ) -> PyResult<PyTransmissionResult> {
+        use prost::Message;
+
+        let descriptor = prost_types::DescriptorProto::decode(descriptor_bytes).map_err(|e| {
+            PyErr::new::<PyConfigurationError, _>(format!(
+                "Failed to decode Protobuf descriptor: {}",
+                e
+            ))
+        })?;
+        crate::wrapper::conversion::validate_protobuf_descriptor(&descriptor)
+            .map_err(rust_error_to_python_error)?;
+
+        let rust_batch = pyarrow_to_rust_batch(py, batch)?;
+
+        let result = self.runtime.block_on(async {
+            self.inner
+                .send_batch_with_descriptor(rust_batch, Some(descriptor))
+                .await
+        });
+
+        match result {
+            Ok(transmission_result) => Ok(PyTransmissionResult {
+                inner: transmission_result,
+            }),
+            Err(e) => Err(rust_error_to_python_error(e)),
+        }
+    }
+
+    /// Send records that are already serialized as Protobuf, without Arrow conversion.
+    ///
+    /// For producers that encode their own messages (e.g. from a schema registry).
+    /// Records over the Zerobus size limit are reported as failed rows, indexed by their
+    /// position in `records`.
+    ///
+    /// Args:
+    ///     records: Serialized Protobuf messages, each encoded with the descriptor
+    ///     descriptor_bytes: Serialized `google.protobuf.DescriptorProto`
+    ///         (e.g. `descriptor_proto.SerializeToString()`)
+    ///
+    /// Returns:
+    ///     TransmissionResult with per-record outcomes
+    ///
+    /// Raises:
+    ///     ConfigurationError: If the descriptor cannot be decoded or is invalid
+    ///     ZerobusError: If transmission fails after all retry attempts
+    fn send_serialized(
+        &self,
+        _py: Python,
+        records: Vec<&PyBytes>,
+        descriptor_bytes: &[u8],
+    ) -> PyResult<PyTransmissionResult> {
+        use prost::Message;
+
+        let descriptor = prost_types::DescriptorProto::decode(descriptor_bytes).map_err(|e| {
+            PyErr::new::<PyConfigurationError, _>(format!(
+                "Failed to decode Protobuf descriptor: {}",
+                e
+            ))
+        })?;
+        let records: Vec<Vec<u8>> = records
+            .iter()
+            .map(|record| record.as_bytes().to_vec())
+            .collect();
+
+        let result = self
+            .runtime
+            .block_on(async { self.inner.send_serialized(records, descriptor).await });
+
+        match result {
+            Ok(transmission_result) => Ok(PyTransmissionResult {
+                inner: transmission_result,
+            }),
+            Err(e) => Err(rust_error_to_python_error(e)),
+        }
+    }
+
+    /// Re-send records left unacknowledged in the write-ahead log by an earlier run.
+    ///
+    /// Call on startup when wal_dir is configured, before sending new batches.
+    ///
+    /// Returns:
+    ///     List of TransmissionResult, one per recovered batch (empty if nothing was left)
+    ///
+    /// A failed batch stops recovery; its result is the last one returned, and its
+    /// unacknowledged records are kept for the next call.
+    ///
+    /// Raises:
+    ///     ConfigurationError: If wal_dir is not configured
+    fn recover_wal(&self, _py: Python) -> PyResult<Vec<PyTransmissionResult>> {
+        let results = self
+            .runtime
+            .block_on(async { self.inner.recover_wal().await })
+            .map_err(rust_error_to_python_error)?;
+        Ok(results
+            .into_iter()
+            .map(|inner| PyTransmissionResult { inner })
+            .collect())
+    }
+
     /// Flush any pending operations and ensure data is transmitted.
     ///
     /// Raises:
@@ -924,6 +1908,75 @@ impl PyZerobusWrapper {
         Ok(())
     }
 
+    /// Check that the wrapper can send, without sending any data.
+    ///
+    /// Suitable for readiness endpoints. Checks backoff state, the SDK client and that an
+    /// OAuth token can be obtained with the configured credentials. In writer disabled
+    /// mode the wrapper is always healthy.
+    ///
+    /// Returns:
+    ///     Dictionary containing:
+    ///     - healthy: Whether the wrapper can currently send
+    ///     - latency_ms: Time the check took in milliseconds (None in writer disabled mode)
+    ///     - error: Why the wrapper is unhealthy (None if healthy)
+    ///     - mode: "live", or "disabled" in writer disabled mode
+    fn health_check(&self, py: Python) -> PyResult<PyObject> {
+        let status = self
+            .runtime
+            .block_on(async { self.inner.health_check().await });
+        let dict = PyDict::new(py);
+        dict.set_item("healthy", status.healthy)?;
+        dict.set_item("latency_ms", status.latency_ms)?;
+        dict.set_item("error", status.error.map(|e| e.to_string()))?;
+        dict.set_item("mode", status.mode.as_str())?;
+        Ok(dict.to_object(py))
+    }
+
+    /// Render cumulative send metrics in Prometheus text exposition format.
+    ///
+    /// Includes batches, rows succeeded, rows failed by error type, bytes, retries,
+    /// stream reconnects and a batch latency histogram, ready to serve from a scrape
+    /// endpoint.
+    ///
+    /// Returns:
+    ///     Metrics as Prometheus text
+    fn metrics_prometheus(&self) -> String {
+        self.inner.metrics_prometheus()
+    }
+
+    /// Export this table's backoff and failure-rate state as JSON.
+    ///
+    /// Persist the result and pass it to import_backoff_state after a restart, so a
+    /// failing table stays in backoff.
+    ///
+    /// Returns:
+    ///     Backoff state as a JSON string
+    fn export_backoff_state(&self) -> PyResult<String> {
+        serde_json::to_string(&self.inner.export_backoff_state()).map_err(|e| {
+            PyErr::new::<PyConfigurationError, _>(format!(
+                "Failed to serialize backoff state: {}",
+                e
+            ))
+        })
+    }
+
+    /// Restore backoff and failure-rate state exported by export_backoff_state.
+    ///
+    /// Args:
+    ///     state: JSON string returned by export_backoff_state
+    ///
+    /// Raises:
+    ///     ConfigurationError: If the state is malformed or belongs to a different table
+    fn import_backoff_state(&self, state: &str) -> PyResult<()> {
+        let snapshot: crate::wrapper::BackoffStateSnapshot =
+            serde_json::from_str(state).map_err(|e| {
+                PyErr::new::<PyConfigurationError, _>(format!("Invalid backoff state: {}", e))
+            })?;
+        self.inner
+            .import_backoff_state(&snapshot)
+            .map_err(rust_error_to_python_error)
+    }
+
     /// Shutdown the wrapper gracefully, closing connections and cleaning up resources.
     ///
     /// Raises:
@@ -3,24 +3,40 @@
 //! This module handles conversion of Arrow RecordBatch data to Protobuf format
 //! required by Zerobus. Reuses conversion logic from cap-gl-consumer-rust.
 
+use crate::config::{
+    CustomEncoder, DescriptorValidationMode, FieldNameTransform, FloatPolicy, NullElementPolicy,
+};
 use crate::error::ZerobusError;
 use crate::wrapper::protobuf_serialization::{encode_tag, encode_varint};
 use arrow::array::*;
-use arrow::datatypes::DataType;
+use arrow::datatypes::{DataType, TimeUnit};
 use arrow::record_batch::RecordBatch;
 use prost_types::{
-    field_descriptor_proto::Label, field_descriptor_proto::Type, DescriptorProto,
-    FieldDescriptorProto,
+    field_descriptor_proto::Label, field_descriptor_proto::Type, uninterpreted_option::NamePart,
+    DescriptorProto, FieldDescriptorProto, FileDescriptorProto, MessageOptions,
+    UninterpretedOption,
 };
+use std::borrow::Borrow;
+use std::ops::Range;
 use std::sync::Arc;
-use tracing::debug;
+use tracing::{debug, warn};
+
+/// Default maximum nesting depth for Protobuf descriptors
+pub const MAX_NESTING_DEPTH: usize = 10;
 
-/// Maximum nesting depth for Protobuf descriptors (prevents stack overflow)
-const MAX_NESTING_DEPTH: usize = 10;
+/// Upper bound on any configured nesting depth
+///
+/// Validation, comparison and encoding recurse once per nesting level, so deeper
+/// descriptors are rejected whatever the configuration to prevent stack overflow.
+pub const ABSOLUTE_MAX_NESTING_DEPTH: usize = 100;
 
 /// Maximum number of fields per message (prevents memory exhaustion)
 /// Zerobus limit: 2000 columns per table
-const MAX_FIELDS_PER_MESSAGE: usize = 2000;
+pub const MAX_FIELDS_PER_MESSAGE: usize = 2000;
+
+/// Maximum length of a column name
+/// Databricks limit: 255 characters per column name
+pub const MAX_FIELD_NAME_LENGTH: usize = 255;
 
 /// Valid Protobuf field number range (1 to 536870911)
 const MIN_FIELD_NUMBER: i32 = 1;
@@ -30,12 +46,43 @@ const MAX_FIELD_NUMBER: i32 = 536870911;
 /// Headers take 19 bytes, so payload limit is 4,194,285 bytes
 const MAX_RECORD_SIZE_BYTES: usize = 4_194_285;
 
+/// Limits checked by `validate_protobuf_descriptor_with_limits`
+///
+/// Hard limits always fail validation and are capped at `MAX_FIELDS_PER_MESSAGE` and
+/// `ABSOLUTE_MAX_NESTING_DEPTH`. Soft limits are self-imposed
+/// thresholds whose violations fail validation or are only logged, depending on `mode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DescriptorLimits {
+    /// How soft limit violations are reported
+    pub mode: DescriptorValidationMode,
+    /// Maximum fields per message
+    pub max_fields: usize,
+    /// Maximum nesting depth (the top-level message is depth 0)
+    pub max_nesting_depth: usize,
+    /// Soft maximum fields per message (None = no soft limit)
+    pub soft_max_fields: Option<usize>,
+    /// Soft maximum nesting depth (None = no soft limit)
+    pub soft_max_nesting_depth: Option<usize>,
+}
+
+impl Default for DescriptorLimits {
+    fn default() -> Self {
+        Self {
+            mode: DescriptorValidationMode::Strict,
+            max_fields: MAX_FIELDS_PER_MESSAGE,
+            max_nesting_depth: MAX_NESTING_DEPTH,
+            soft_max_fields: None,
+            soft_max_nesting_depth: None,
+        }
+    }
+}
+
 /// Validate a Protobuf descriptor to prevent security issues
 ///
 /// Checks for:
 /// - Maximum nesting depth
 /// - Maximum field count per message
-/// - Valid field number ranges
+/// - Every field having a field number, within the valid range
 ///
 /// # Arguments
 ///
@@ -49,181 +96,1752 @@ const MAX_RECORD_SIZE_BYTES: usize = 4_194_285;
 ///
 /// Returns `ConfigurationError` if validation fails.
 pub fn validate_protobuf_descriptor(descriptor: &DescriptorProto) -> Result<(), ZerobusError> {
-    validate_descriptor_recursive(descriptor, 0)
+    validate_protobuf_descriptor_with_limits(descriptor, &DescriptorLimits::default())
+}
+
+/// Validate a Protobuf descriptor against configurable limits
+///
+/// Same checks as `validate_protobuf_descriptor`, with the field count and nesting
+/// depth limits taken from `limits`. Missing field numbers, numbers outside the Protobuf
+/// range and violations of the hard limits always fail; soft limit violations fail in
+/// `DescriptorValidationMode::Strict` and are logged as warnings in
+/// `DescriptorValidationMode::WarnSoftLimits`.
+///
+/// # Arguments
+///
+/// * `descriptor` - Descriptor to validate
+/// * `limits` - Hard and soft limits to check
+///
+/// # Returns
+///
+/// Returns `Ok(())` if valid, or `Err(ZerobusError)` if invalid.
+///
+/// # Errors
+///
+/// Returns `ConfigurationError` if validation fails.
+pub fn validate_protobuf_descriptor_with_limits(
+    descriptor: &DescriptorProto,
+    limits: &DescriptorLimits,
+) -> Result<(), ZerobusError> {
+    validate_descriptor_recursive(descriptor, 0, limits)
+}
+
+/// Report a soft limit violation: an error in strict mode, a warning otherwise
+fn soft_limit_exceeded(limits: &DescriptorLimits, message: String) -> Result<(), ZerobusError> {
+    match limits.mode {
+        DescriptorValidationMode::Strict => Err(ZerobusError::ConfigurationError(message)),
+        DescriptorValidationMode::WarnSoftLimits => {
+            warn!("{}", message);
+            Ok(())
+        }
+    }
 }
 
 fn validate_descriptor_recursive(
     descriptor: &DescriptorProto,
     depth: usize,
+    limits: &DescriptorLimits,
 ) -> Result<(), ZerobusError> {
     // Check nesting depth
-    if depth > MAX_NESTING_DEPTH {
+    let max_nesting_depth = limits.max_nesting_depth.min(ABSOLUTE_MAX_NESTING_DEPTH);
+    if depth > max_nesting_depth {
         return Err(ZerobusError::ConfigurationError(format!(
             "Protobuf descriptor nesting depth ({}) exceeds maximum ({})",
-            depth, MAX_NESTING_DEPTH
+            depth, max_nesting_depth
         )));
     }
+    if let Some(soft_max) = limits.soft_max_nesting_depth {
+        if depth > soft_max {
+            soft_limit_exceeded(
+                limits,
+                format!(
+                    "Protobuf descriptor nesting depth ({}) exceeds soft limit ({})",
+                    depth, soft_max
+                ),
+            )?;
+        }
+    }
+
+    // Check field count
+    let max_fields = limits.max_fields.min(MAX_FIELDS_PER_MESSAGE);
+    if descriptor.field.len() > max_fields {
+        return Err(ZerobusError::ConfigurationError(format!(
+            "Protobuf descriptor field count ({}) exceeds maximum ({})",
+            descriptor.field.len(),
+            max_fields
+        )));
+    }
+    if let Some(soft_max) = limits.soft_max_fields {
+        if descriptor.field.len() > soft_max {
+            soft_limit_exceeded(
+                limits,
+                format!(
+                    "Protobuf descriptor field count ({}) exceeds soft limit ({})",
+                    descriptor.field.len(),
+                    soft_max
+                ),
+            )?;
+        }
+    }
+
+    // Validate each field
+    for field in &descriptor.field {
+        // Validate field number: a missing one would be encoded as the invalid field 0
+        let field_number = field.number.ok_or_else(|| {
+            ZerobusError::ConfigurationError(format!(
+                "Protobuf field '{}' in message '{}' has no field number",
+                field.name.as_deref().unwrap_or("<unnamed>"),
+                descriptor.name.as_deref().unwrap_or("<unnamed>")
+            ))
+        })?;
+        if !(MIN_FIELD_NUMBER..=MAX_FIELD_NUMBER).contains(&field_number) {
+            return Err(ZerobusError::ConfigurationError(format!(
+                "Invalid Protobuf field number for field '{}': {} (must be between {} and {})",
+                field.name.as_deref().unwrap_or("<unnamed>"),
+                field_number,
+                MIN_FIELD_NUMBER,
+                MAX_FIELD_NUMBER
+            )));
+        }
+    }
+
+    // Recursively validate nested types
+    for nested_type in &descriptor.nested_type {
+        validate_descriptor_recursive(nested_type, depth + 1, limits)?;
+    }
+
+    Ok(())
+}
+
+/// A difference between a Protobuf descriptor and the Arrow schema it is used with
+///
+/// Field names are dotted paths for nested Struct fields (e.g. `"address.city"`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DescriptorMismatch {
+    /// Arrow column has no descriptor field of the same name (column is silently skipped)
+    MissingField { field: String },
+    /// Descriptor field type cannot encode the Arrow column type
+    TypeMismatch {
+        field: String,
+        arrow_type: String,
+        descriptor_type: String,
+    },
+    /// Arrow List column paired with a non-repeated field, or vice versa
+    LabelMismatch {
+        field: String,
+        arrow_is_list: bool,
+        descriptor_is_repeated: bool,
+    },
+}
+
+impl std::fmt::Display for DescriptorMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DescriptorMismatch::MissingField { field } => {
+                write!(
+                    f,
+                    "missing field: Arrow column '{}' is not in the descriptor",
+                    field
+                )
+            }
+            DescriptorMismatch::TypeMismatch {
+                field,
+                arrow_type,
+                descriptor_type,
+            } => write!(
+                f,
+                "type mismatch: field='{}', arrow_type={}, descriptor_type={}",
+                field, arrow_type, descriptor_type
+            ),
+            DescriptorMismatch::LabelMismatch {
+                field,
+                arrow_is_list,
+                descriptor_is_repeated,
+            } => write!(
+                f,
+                "label mismatch: field='{}', arrow_is_list={}, descriptor_is_repeated={}",
+                field, arrow_is_list, descriptor_is_repeated
+            ),
+        }
+    }
+}
+
+/// Validate that a Protobuf descriptor matches an Arrow schema
+///
+/// A descriptor that doesn't match the batch leads to silently skipped columns during
+/// conversion and, on the server, to cryptic stream closures. This reports every
+/// difference up front: Arrow columns missing from the descriptor, type mismatches, and
+/// repeated/label mismatches, recursing into nested Struct fields. Descriptor fields with
+/// no matching column are allowed (they are simply left unset).
+///
+/// Type compatibility follows the encoder: besides the type generated by
+/// `generate_protobuf_descriptor`, `sint32`/`sint64` fields accept Int32/Int64 and
+/// string columns, and `uint64` fields accept UInt64 columns.
+///
+/// # Arguments
+///
+/// * `descriptor` - Protobuf descriptor to check
+/// * `schema` - Arrow schema of the batch that will be sent with it
+///
+/// # Returns
+///
+/// Returns `Ok(())` if they match, or `Err` with every mismatch found.
+pub fn validate_descriptor_against_schema(
+    descriptor: &DescriptorProto,
+    schema: &arrow::datatypes::Schema,
+) -> Result<(), Vec<DescriptorMismatch>> {
+    validate_descriptor_against_schema_with_field_names(
+        descriptor,
+        schema,
+        &FieldNameTransform::None,
+    )
+}
+
+/// Validate that a Protobuf descriptor matches an Arrow schema with renamed fields
+///
+/// Same as `validate_descriptor_against_schema`, but Arrow field names are passed through
+/// `field_names` before they are matched to descriptor fields, as during encoding.
+/// Mismatches are reported under the original Arrow names.
+///
+/// # Arguments
+///
+/// * `descriptor` - Protobuf descriptor to check
+/// * `schema` - Arrow schema of the batch that will be sent with it
+/// * `field_names` - Rename applied to Arrow field names
+///
+/// # Returns
+///
+/// Returns `Ok(())` if they match, or `Err` with every mismatch found.
+pub fn validate_descriptor_against_schema_with_field_names(
+    descriptor: &DescriptorProto,
+    schema: &arrow::datatypes::Schema,
+    field_names: &FieldNameTransform,
+) -> Result<(), Vec<DescriptorMismatch>> {
+    let mut mismatches = Vec::new();
+    collect_descriptor_mismatches(
+        descriptor,
+        schema.fields(),
+        field_names,
+        "",
+        0,
+        &mut mismatches,
+    );
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatches)
+    }
+}
+
+fn collect_descriptor_mismatches(
+    descriptor: &DescriptorProto,
+    fields: &arrow::datatypes::Fields,
+    field_names: &FieldNameTransform,
+    path_prefix: &str,
+    depth: usize,
+    mismatches: &mut Vec<DescriptorMismatch>,
+) {
+    let path = |name: &str| format!("{}{}", path_prefix, name);
+
+    for field in fields.iter() {
+        let name = field_names.apply(field.name());
+        let field_desc = match descriptor
+            .field
+            .iter()
+            .find(|f| f.name.as_deref() == Some(name.as_ref()))
+        {
+            Some(field_desc) => field_desc,
+            None => {
+                mismatches.push(DescriptorMismatch::MissingField {
+                    field: path(field.name()),
+                });
+                continue;
+            }
+        };
+
+        let (arrow_is_list, value_type) = match logical_values_type(field.data_type()) {
+            DataType::List(inner) | DataType::LargeList(inner) => (true, inner.data_type()),
+            other => (false, other),
+        };
+        let descriptor_is_repeated = field_desc.label == Some(Label::Repeated as i32);
+        if arrow_is_list != descriptor_is_repeated {
+            mismatches.push(DescriptorMismatch::LabelMismatch {
+                field: path(field.name()),
+                arrow_is_list,
+                descriptor_is_repeated,
+            });
+            continue;
+        }
+
+        let descriptor_type = field_desc.r#type.unwrap_or(Type::String as i32);
+        if !protobuf_type_accepts(value_type, descriptor_type) {
+            mismatches.push(DescriptorMismatch::TypeMismatch {
+                field: path(field.name()),
+                arrow_type: format!("{:?}", value_type),
+                descriptor_type: Type::try_from(descriptor_type)
+                    .map(|t| t.as_str_name().to_string())
+                    .unwrap_or_else(|_| descriptor_type.to_string()),
+            });
+            continue;
+        }
+
+        // Recurse into nested messages (type_name is ".Parent.Nested" - match on the last part)
+        if let DataType::Struct(struct_fields) = value_type {
+            if depth >= ABSOLUTE_MAX_NESTING_DEPTH {
+                continue;
+            }
+            let nested_name = field_desc
+                .type_name
+                .as_deref()
+                .and_then(|name| name.rsplit('.').next())
+                .unwrap_or_default();
+            match descriptor
+                .nested_type
+                .iter()
+                .find(|nt| nt.name.as_deref() == Some(nested_name))
+            {
+                Some(nested) => collect_descriptor_mismatches(
+                    nested,
+                    struct_fields,
+                    field_names,
+                    &format!("{}.", path(field.name())),
+                    depth + 1,
+                    mismatches,
+                ),
+                None => mismatches.push(DescriptorMismatch::TypeMismatch {
+                    field: path(field.name()),
+                    arrow_type: format!("{:?}", value_type),
+                    descriptor_type: format!("TYPE_MESSAGE (unresolved type '{}')", nested_name),
+                }),
+            }
+        }
+    }
+}
+
+/// A difference between an expected Arrow schema and the schema of a batch
+///
+/// See `WrapperConfiguration::with_expected_schema`. Columns are compared by name at the
+/// top level; a change inside a Struct column is reported as that column being retyped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaDifference {
+    /// The batch has a column the expected schema does not
+    AddedColumn { column: String, data_type: String },
+    /// The batch lacks a column of the expected schema
+    RemovedColumn { column: String, data_type: String },
+    /// The column's data type differs from the expected one
+    RetypedColumn {
+        column: String,
+        expected_type: String,
+        actual_type: String,
+    },
+}
+
+impl std::fmt::Display for SchemaDifference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaDifference::AddedColumn { column, data_type } => {
+                write!(f, "added column '{}' ({})", column, data_type)
+            }
+            SchemaDifference::RemovedColumn { column, data_type } => {
+                write!(f, "removed column '{}' ({})", column, data_type)
+            }
+            SchemaDifference::RetypedColumn {
+                column,
+                expected_type,
+                actual_type,
+            } => write!(
+                f,
+                "retyped column '{}': expected {}, got {}",
+                column, expected_type, actual_type
+            ),
+        }
+    }
+}
+
+/// Compare a batch's Arrow schema against the expected schema
+///
+/// # Arguments
+///
+/// * `expected` - Schema the producer is expected to send
+/// * `actual` - Schema of the incoming batch
+///
+/// # Returns
+///
+/// Every difference found: removed and retyped columns in the expected schema's order,
+/// then added columns in the batch's order. Empty if the column names and types match
+/// (column order, nullability and metadata are not compared).
+pub fn diff_schemas(
+    expected: &arrow::datatypes::Schema,
+    actual: &arrow::datatypes::Schema,
+) -> Vec<SchemaDifference> {
+    let mut differences = Vec::new();
+    for field in expected.fields().iter() {
+        match actual.field_with_name(field.name()) {
+            Ok(actual_field) if actual_field.data_type() == field.data_type() => {}
+            Ok(actual_field) => differences.push(SchemaDifference::RetypedColumn {
+                column: field.name().clone(),
+                expected_type: format!("{:?}", field.data_type()),
+                actual_type: format!("{:?}", actual_field.data_type()),
+            }),
+            Err(_) => differences.push(SchemaDifference::RemovedColumn {
+                column: field.name().clone(),
+                data_type: format!("{:?}", field.data_type()),
+            }),
+        }
+    }
+    for field in actual.fields().iter() {
+        if expected.field_with_name(field.name()).is_err() {
+            differences.push(SchemaDifference::AddedColumn {
+                column: field.name().clone(),
+                data_type: format!("{:?}", field.data_type()),
+            });
+        }
+    }
+    differences
+}
+
+/// Whether the encoder can write an Arrow value of `arrow_type` into a field of `protobuf_type`
+fn protobuf_type_accepts(arrow_type: &DataType, protobuf_type: i32) -> bool {
+    if let Ok(expected) = arrow_type_to_protobuf_type(arrow_type) {
+        if expected as i32 == protobuf_type {
+            return true;
+        }
+    }
+    matches!(
+        (arrow_type, Type::try_from(protobuf_type)),
+        (DataType::Decimal128(_, _), Ok(Type::Message))
+            | (DataType::Int32 | DataType::Utf8, Ok(Type::Sint32))
+            | (DataType::Int64 | DataType::Utf8, Ok(Type::Sint64))
+            | (DataType::UInt64, Ok(Type::Uint64))
+    )
+}
+
+/// Evolve `current` to cover the fields of `candidate` without renumbering existing fields
+///
+/// Fields of `candidate` that already exist in `current` (matched by name) must have the
+/// same type, type name and label; they keep their `current` field numbers. New fields are
+/// appended after the highest existing field number, together with the nested types and
+/// synthetic oneofs they reference. Fields of `current` missing from `candidate` are kept,
+/// so batches with the older schema remain encodable.
+///
+/// # Arguments
+///
+/// * `current` - Descriptor the stream was created with
+/// * `candidate` - Descriptor for the new batch
+///
+/// # Returns
+///
+/// Returns the evolved descriptor, equal to `current` if `candidate` adds nothing.
+///
+/// # Errors
+///
+/// Returns `ConfigurationError` if the change is not add-only: an existing field changed
+/// type or label, a new field is required, or a new nested type clashes with an existing one.
+pub fn evolve_descriptor(
+    current: &DescriptorProto,
+    candidate: &DescriptorProto,
+) -> Result<DescriptorProto, ZerobusError> {
+    let incompatible = |field: &str, issue: &str| {
+        ZerobusError::ConfigurationError(format!(
+            "Schema change is not add-only: field='{}', issue='{}'",
+            field, issue
+        ))
+    };
+
+    let mut evolved = current.clone();
+    let mut next_number = current
+        .field
+        .iter()
+        .filter_map(|f| f.number)
+        .max()
+        .unwrap_or(0)
+        + 1;
+
+    for field in &candidate.field {
+        let name = field.name.as_deref().unwrap_or("unknown");
+        if let Some(existing) = current.field.iter().find(|f| f.name == field.name) {
+            if existing.r#type != field.r#type || existing.type_name != field.type_name {
+                return Err(incompatible(name, "type_changed"));
+            }
+            if existing.label != field.label {
+                return Err(incompatible(name, "label_changed"));
+            }
+            if let Some(type_name) = &field.type_name {
+                let nested_name = type_name.rsplit('.').next().unwrap_or(type_name);
+                let find = |d: &DescriptorProto| {
+                    d.nested_type
+                        .iter()
+                        .find(|nt| nt.name.as_deref() == Some(nested_name))
+                        .cloned()
+                };
+                if find(current) != find(candidate) {
+                    return Err(incompatible(name, "nested_type_changed"));
+                }
+            }
+            continue;
+        }
+
+        if field.label == Some(Label::Required as i32) {
+            return Err(incompatible(name, "new_field_required"));
+        }
+
+        let mut added = field.clone();
+        added.number = Some(next_number);
+        next_number += 1;
+
+        // Carry over the nested message type the new field refers to
+        if let Some(type_name) = &field.type_name {
+            let nested_name = type_name.rsplit('.').next().unwrap_or(type_name);
+            if let Some(nested) = candidate
+                .nested_type
+                .iter()
+                .find(|nt| nt.name.as_deref() == Some(nested_name))
+            {
+                match evolved
+                    .nested_type
+                    .iter()
+                    .find(|nt| nt.name.as_deref() == Some(nested_name))
+                {
+                    Some(existing) if existing != nested => {
+                        return Err(incompatible(name, "nested_type_clash"));
+                    }
+                    Some(_) => {}
+                    None => evolved.nested_type.push(nested.clone()),
+                }
+            }
+        }
+
+        // Synthetic oneofs (explicit presence) are re-indexed into the evolved descriptor
+        if let Some(oneof) = field
+            .oneof_index
+            .and_then(|idx| candidate.oneof_decl.get(idx as usize))
+        {
+            added.oneof_index = Some(evolved.oneof_decl.len() as i32);
+            evolved.oneof_decl.push(oneof.clone());
+        }
+
+        evolved.field.push(added);
+    }
+
+    Ok(evolved)
+}
+
+/// Result of converting a RecordBatch to Protobuf
+#[derive(Debug)]
+pub struct ProtobufConversionResult {
+    /// Successful conversions: (row_index, protobuf_bytes)
+    pub successful_bytes: Vec<(usize, Vec<u8>)>,
+    /// Failed conversions: (row_index, error)
+    pub failed_rows: Vec<(usize, ZerobusError)>,
+    /// First row that was not converted because conversion was aborted early
+    ///
+    /// `None` unless the failure count exceeded the `max_failures` passed to
+    /// `record_batch_to_protobuf_bytes_with_maps`. Rows from this index on appear in
+    /// neither `successful_bytes` nor `failed_rows`.
+    pub aborted_at_row: Option<usize>,
+}
+
+/// Convert Arrow RecordBatch to Protobuf bytes
+///
+/// Converts each row in the RecordBatch to Protobuf bytes using the descriptor.
+/// Returns both successful conversions and failed rows.
+///
+/// Columns and struct children are matched to descriptor fields by name, so their
+/// order in the Arrow schema does not need to follow the descriptor's field order,
+/// at the top level or inside nested messages.
+///
+/// # Arguments
+///
+/// * `batch` - RecordBatch to convert
+/// * `descriptor` - Protobuf descriptor that matches the batch schema
+///
+/// # Returns
+///
+/// Returns ProtobufConversionResult with successful bytes and failed rows.
+/// This function processes all rows and collects errors per-row instead of failing fast.
+pub fn record_batch_to_protobuf_bytes(
+    batch: &RecordBatch,
+    descriptor: &DescriptorProto,
+) -> ProtobufConversionResult {
+    record_batch_to_protobuf_bytes_with_redaction(batch, descriptor, ErrorRedaction::default())
+}
+
+/// Controls what per-row conversion error messages may reveal
+///
+/// Row errors always identify the row index and the top-level field. With redaction,
+/// the underlying encoder detail is withheld and sensitive field names are masked.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ErrorRedaction<'a> {
+    /// Replace the encoder detail of row errors with `<redacted>`
+    ///
+    /// Guarantees no cell content can appear in the message, whatever the encoder reports.
+    pub redact_values: bool,
+    /// Field names masked as `<sensitive:HASH>` wherever they appear in row errors
+    ///
+    /// The hash is a stable FNV-1a digest of the name, so operators can match it
+    /// against the configured list without the name being logged.
+    pub sensitive_fields: &'a [String],
+}
+
+impl ErrorRedaction<'_> {
+    /// Name to report for `field`, masked if it is sensitive
+    fn field_name(&self, field: &str) -> String {
+        if self.sensitive_fields.iter().any(|f| f == field) {
+            mask_field_name(field)
+        } else {
+            field.to_string()
+        }
+    }
+
+    /// Encoder detail to report, withheld entirely or with sensitive names masked
+    fn detail(&self, error: &ZerobusError) -> String {
+        if self.redact_values {
+            return "<redacted>".to_string();
+        }
+        let mut detail = error.to_string();
+        for field in self.sensitive_fields {
+            detail = detail.replace(
+                &format!("'{}'", field),
+                &format!("'{}'", mask_field_name(field)),
+            );
+        }
+        detail
+    }
+}
+
+/// Mask a sensitive field name as `<sensitive:HASH>` (32-bit FNV-1a, hex)
+pub fn mask_field_name(name: &str) -> String {
+    let hash = name.bytes().fold(0x811c_9dc5u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    format!("<sensitive:{:08x}>", hash)
+}
+
+/// Convert Arrow RecordBatch to Protobuf bytes, redacting row error messages
+///
+/// Same as `record_batch_to_protobuf_bytes`, but per-row field encoding errors follow
+/// `redaction` so they can be logged under PII rules.
+///
+/// # Arguments
+///
+/// * `batch` - RecordBatch to convert
+/// * `descriptor` - Protobuf descriptor that matches the batch schema
+/// * `redaction` - What row error messages may reveal
+///
+/// # Returns
+///
+/// Returns ProtobufConversionResult with successful bytes and failed rows.
+pub fn record_batch_to_protobuf_bytes_with_redaction(
+    batch: &RecordBatch,
+    descriptor: &DescriptorProto,
+    redaction: ErrorRedaction<'_>,
+) -> ProtobufConversionResult {
+    if batch.num_rows() == 0 {
+        return ProtobufConversionResult {
+            successful_bytes: vec![],
+            failed_rows: vec![],
+            aborted_at_row: None,
+        };
+    }
+
+    // Build field name -> field descriptor map for efficient lookup
+    let field_by_name: std::collections::HashMap<String, &FieldDescriptorProto> = descriptor
+        .field
+        .iter()
+        .filter_map(|f| f.name.as_ref().map(|name| (name.clone(), f)))
+        .collect();
+
+    // Build nested type name -> nested descriptor map
+    let nested_types_by_name: std::collections::HashMap<String, &DescriptorProto> = descriptor
+        .nested_type
+        .iter()
+        .filter_map(|nt| {
+            nt.name.as_ref().map(|name| {
+                // Extract the full type name (e.g., ".ZerobusMessage._metadata" -> "_metadata")
+                // The type_name in FieldDescriptorProto uses format ".ParentMessage.NestedMessage"
+                // We need to match on the nested message name
+                (name.clone(), nt)
+            })
+        })
+        .collect();
+
+    convert_rows(
+        batch,
+        &field_by_name,
+        &nested_types_by_name,
+        &FieldNameTransform::None,
+        FloatPolicy::Passthrough,
+        false,
+        false,
+        false,
+        NullElementPolicy::SkipNullElements,
+        TimeUnit::Microsecond,
+        redaction,
+        None,
+        1,
+        None,
+    )
+}
+
+/// Convert Arrow RecordBatch to dynamic Protobuf messages
+///
+/// Companion to `record_batch_to_protobuf_bytes` for callers that want the decoded
+/// message structure rather than bytes, e.g. to re-serialize into another format. Each
+/// row is encoded as usual and decoded against `descriptor` with `prost-reflect`.
+/// Requires the `reflect` feature.
+///
+/// # Arguments
+///
+/// * `batch` - RecordBatch to convert
+/// * `descriptor` - Protobuf descriptor that matches the batch schema
+///
+/// # Returns
+///
+/// Returns `(row_index, message)` for every row, or `ConversionError` if the descriptor
+/// cannot be loaded or any row fails to convert (the first failing row is reported).
+#[cfg(feature = "reflect")]
+pub fn record_batch_to_dynamic_messages(
+    batch: &RecordBatch,
+    descriptor: &DescriptorProto,
+) -> Result<Vec<(usize, prost_reflect::DynamicMessage)>, ZerobusError> {
+    use prost::Message;
+
+    // prost-reflect has its own prost-types, so the descriptor is handed over as bytes
+    let file_set = prost_types::FileDescriptorSet {
+        file: vec![descriptor_to_file_descriptor(descriptor, None)],
+    };
+    let pool = prost_reflect::DescriptorPool::decode(file_set.encode_to_vec().as_slice()).map_err(
+        |e| ZerobusError::ConversionError(format!("Invalid Protobuf descriptor: {}", e)),
+    )?;
+    let message_name = descriptor.name.as_deref().unwrap_or("ZerobusMessage");
+    let message_descriptor = pool.get_message_by_name(message_name).ok_or_else(|| {
+        ZerobusError::ConversionError(format!(
+            "Message '{}' not found in descriptor",
+            message_name
+        ))
+    })?;
+
+    let conversion = record_batch_to_protobuf_bytes(batch, descriptor);
+    if let Some((_, error)) = conversion.failed_rows.into_iter().next() {
+        return Err(error);
+    }
+
+    conversion
+        .successful_bytes
+        .into_iter()
+        .map(|(row_idx, bytes)| {
+            prost_reflect::DynamicMessage::decode(message_descriptor.clone(), bytes.as_slice())
+                .map(|message| (row_idx, message))
+                .map_err(|e| {
+                    ZerobusError::ConversionError(format!(
+                        "Failed to decode row {} as '{}': {}",
+                        row_idx, message_name, e
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// Field lookup maps for a descriptor, built once and reused across batches
+///
+/// `record_batch_to_protobuf_bytes` rebuilds its field-name and nested-type maps on every
+/// call. Senders with a stable descriptor can build them once and convert with
+/// `record_batch_to_protobuf_bytes_with_maps` instead; `ZerobusWrapper` does this
+/// automatically. For many small batches of wide schemas this removes most of the
+/// per-batch allocation (see the `field_map_cache` benchmark).
+#[derive(Debug, Clone)]
+pub struct DescriptorFieldMaps {
+    /// Descriptor the maps were built from
+    descriptor: DescriptorProto,
+    /// Top-level field descriptors by field name
+    field_by_name: std::collections::HashMap<String, FieldDescriptorProto>,
+    /// Nested message descriptors by message name
+    nested_types_by_name: std::collections::HashMap<String, DescriptorProto>,
+}
+
+impl DescriptorFieldMaps {
+    /// Build the lookup maps for `descriptor`
+    pub fn new(descriptor: &DescriptorProto) -> Self {
+        Self {
+            descriptor: descriptor.clone(),
+            field_by_name: descriptor
+                .field
+                .iter()
+                .filter_map(|f| f.name.clone().map(|name| (name, f.clone())))
+                .collect(),
+            nested_types_by_name: descriptor
+                .nested_type
+                .iter()
+                .filter_map(|nt| nt.name.clone().map(|name| (name, nt.clone())))
+                .collect(),
+        }
+    }
+
+    /// Descriptor the maps were built from
+    pub fn descriptor(&self) -> &DescriptorProto {
+        &self.descriptor
+    }
+
+    /// Whether these maps were built from a descriptor equal to `descriptor`
+    pub fn matches(&self, descriptor: &DescriptorProto) -> bool {
+        self.descriptor == *descriptor
+    }
+}
+
+/// Convert Arrow RecordBatch to Protobuf bytes using prebuilt field maps
+///
+/// Same as `record_batch_to_protobuf_bytes_with_redaction`, without rebuilding the
+/// descriptor lookup maps. Arrow field names are passed through `field_names` before
+/// they are looked up in the descriptor.
+///
+/// With `max_failures`, conversion stops as soon as more than that many rows have
+/// failed, so a hopeless batch (e.g. the wrong schema) doesn't convert every row just
+/// to fail it. The result then has `aborted_at_row` set; rows from there on are left
+/// unconverted.
+///
+/// With `parallelism > 1` and the `parallel` feature, rows are converted in that many
+/// chunks concurrently (see `WrapperConfiguration::with_conversion_parallelism`); the
+/// result is the same as converting sequentially.
+///
+/// # Arguments
+///
+/// * `batch` - RecordBatch to convert
+/// * `maps` - Lookup maps built from a descriptor that matches the batch schema
+/// * `field_names` - Rename applied to Arrow field names (as used to generate the descriptor)
+/// * `float_policy` - Handling of NaN/Infinity float values
+/// * `validate_utf8` - Fail rows whose string values are not valid UTF-8
+/// * `lenient_bool_coercion` - Accept Int8/Int32 columns (nonzero = true) for bool fields
+/// * `emit_defaults` - Write the proto3 default for null non-repeated scalar fields
+/// * `null_elements` - Handling of null elements inside repeated fields
+/// * `timestamp_unit` - Unit timestamp values are written in
+/// * `redaction` - What row error messages may reveal
+/// * `max_failures` - Failed rows tolerated before aborting (`None` = convert every row)
+/// * `parallelism` - Row chunks converted concurrently (1 = sequential)
+/// * `custom_encoder` - Hook for values the built-in encoding doesn't support
+///   (see `WrapperConfiguration::with_custom_encoder`)
+///
+/// # Returns
+///
+/// Returns ProtobufConversionResult with successful bytes and failed rows.
+#[allow(clippy::too_many_arguments)]
+pub fn record_batch_to_protobuf_bytes_with_maps(
+    batch: &RecordBatch,
+    maps: &DescriptorFieldMaps,
+    field_names: &FieldNameTransform,
+    float_policy: FloatPolicy,
+    validate_utf8: bool,
+    lenient_bool_coercion: bool,
+    emit_defaults: bool,
+    null_elements: NullElementPolicy,
+    timestamp_unit: TimeUnit,
+    redaction: ErrorRedaction<'_>,
+    max_failures: Option<usize>,
+    parallelism: usize,
+    custom_encoder: Option<&CustomEncoder>,
+) -> ProtobufConversionResult {
+    convert_rows(
+        batch,
+        &maps.field_by_name,
+        &maps.nested_types_by_name,
+        field_names,
+        float_policy,
+        validate_utf8,
+        lenient_bool_coercion,
+        emit_defaults,
+        null_elements,
+        timestamp_unit,
+        redaction,
+        max_failures,
+        parallelism,
+        custom_encoder,
+    )
+}
+
+/// Convert Arrow RecordBatch to Protobuf bytes without the flat-schema fast path
+///
+/// Same output as `record_batch_to_protobuf_bytes`, always using the general per-field
+/// encoder. Only useful to compare against the fast path (see the `flat_fast_path`
+/// benchmark).
+#[doc(hidden)]
+pub fn record_batch_to_protobuf_bytes_row_by_row(
+    batch: &RecordBatch,
+    descriptor: &DescriptorProto,
+) -> ProtobufConversionResult {
+    let maps = DescriptorFieldMaps::new(descriptor);
+    convert_rows_generic(
+        batch,
+        &maps.field_by_name,
+        &maps.nested_types_by_name,
+        &FieldNameTransform::None,
+        FloatPolicy::Passthrough,
+        false,
+        false,
+        false,
+        NullElementPolicy::SkipNullElements,
+        TimeUnit::Microsecond,
+        ErrorRedaction::default(),
+        None,
+        1,
+        None,
+    )
+}
+
+/// Shared row loop of the `record_batch_to_protobuf_bytes*` functions
+///
+/// Batches whose columns are all flat primitives take a fast path: one encoder per
+/// column is built up front (type dispatch and tag encoding happen once per column
+/// instead of once per cell) and applied across all rows. Anything else - repeated
+/// fields, nested messages, or an Arrow type the descriptor type doesn't map to
+/// directly - goes through the general per-field encoder. Both produce the same bytes.
+#[allow(clippy::too_many_arguments)]
+fn convert_rows<F, N>(
+    batch: &RecordBatch,
+    field_by_name: &std::collections::HashMap<String, F>,
+    nested_types_by_name: &std::collections::HashMap<String, N>,
+    field_names: &FieldNameTransform,
+    float_policy: FloatPolicy,
+    validate_utf8: bool,
+    lenient_bool_coercion: bool,
+    emit_defaults: bool,
+    null_elements: NullElementPolicy,
+    timestamp_unit: TimeUnit,
+    redaction: ErrorRedaction<'_>,
+    max_failures: Option<usize>,
+    parallelism: usize,
+    custom_encoder: Option<&CustomEncoder>,
+) -> ProtobufConversionResult
+where
+    F: Borrow<FieldDescriptorProto> + Sync,
+    N: Borrow<DescriptorProto> + Sync,
+{
+    // A custom encoder must be offered every value, so it rules out the fast path
+    let encoders = match custom_encoder {
+        Some(_) => None,
+        None => flat_column_encoders(
+            batch,
+            field_by_name,
+            field_names,
+            float_policy,
+            validate_utf8,
+            emit_defaults,
+            timestamp_unit,
+        ),
+    };
+    if let Some(encoders) = encoders {
+        return convert_row_chunks(
+            batch.num_rows(),
+            parallelism,
+            max_failures,
+            |rows, max_failures| convert_flat_rows(rows, &encoders, max_failures),
+        );
+    }
+    convert_rows_generic(
+        batch,
+        field_by_name,
+        nested_types_by_name,
+        field_names,
+        float_policy,
+        validate_utf8,
+        lenient_bool_coercion,
+        emit_defaults,
+        null_elements,
+        timestamp_unit,
+        redaction,
+        max_failures,
+        parallelism,
+        custom_encoder,
+    )
+}
+
+/// Encodes one column's value for a row (nothing for null) into a row buffer
+type ColumnEncoder<'a> = Box<dyn Fn(&mut Vec<u8>, usize) + Send + Sync + 'a>;
+
+/// Build per-column encoders if every column of `batch` is a flat primitive
+///
+/// Columns without a descriptor field are skipped, as in the general path. Returns
+/// `None` as soon as a column needs the general encoder.
+fn flat_column_encoders<'a, F: Borrow<FieldDescriptorProto>>(
+    batch: &'a RecordBatch,
+    field_by_name: &std::collections::HashMap<String, F>,
+    field_names: &FieldNameTransform,
+    float_policy: FloatPolicy,
+    validate_utf8: bool,
+    emit_defaults: bool,
+    timestamp_unit: TimeUnit,
+) -> Option<Vec<ColumnEncoder<'a>>> {
+    let schema = batch.schema();
+    let mut encoders = Vec::with_capacity(batch.num_columns());
+    for (field, array) in schema.fields().iter().zip(batch.columns()) {
+        let Some(field_desc) = field_by_name
+            .get(field_names.apply(field.name()).as_ref())
+            .map(Borrow::borrow)
+        else {
+            debug!("Field '{}' not found in descriptor, skipping", field.name());
+            continue;
+        };
+        let encoder = flat_column_encoder(
+            array.as_ref(),
+            field_desc,
+            float_policy,
+            validate_utf8,
+            timestamp_unit,
+        )?;
+        encoders.push(if emit_defaults {
+            with_null_defaults(array.as_ref(), field_desc, encoder)
+        } else {
+            encoder
+        });
+    }
+    Some(encoders)
+}
+
+/// Wrap a column encoder to write the field's proto3 default for null rows
+///
+/// Uses the array's logical nulls, so null dictionary values count as null too.
+fn with_null_defaults<'a>(
+    array: &dyn Array,
+    field_desc: &FieldDescriptorProto,
+    encoder: ColumnEncoder<'a>,
+) -> ColumnEncoder<'a> {
+    let Some(nulls) = array.logical_nulls() else {
+        return encoder;
+    };
+    let mut default = Vec::new();
+    if encode_default_value(&mut default, field_desc).is_err() || default.is_empty() {
+        return encoder;
+    }
+    Box::new(move |buffer, row| {
+        if nulls.is_valid(row) {
+            encoder(buffer, row);
+        } else {
+            buffer.extend_from_slice(&default);
+        }
+    })
+}
+
+/// Encoder for a single non-repeated primitive column
+///
+/// Mirrors the type mapping of `encode_arrow_value_to_protobuf`; returns `None` for
+/// combinations it doesn't cover. Float columns are only covered under
+/// `FloatPolicy::Passthrough`, and string columns only without `validate_utf8`, since
+/// those options can omit a value or fail a row.
+fn flat_column_encoder<'a>(
+    array: &'a dyn Array,
+    field_desc: &FieldDescriptorProto,
+    float_policy: FloatPolicy,
+    validate_utf8: bool,
+    timestamp_unit: TimeUnit,
+) -> Option<ColumnEncoder<'a>> {
+    if validate_utf8
+        && matches!(
+            logical_values_type(array.data_type()),
+            DataType::Utf8 | DataType::LargeUtf8
+        )
+    {
+        return None;
+    }
+    if let DataType::Dictionary(_, _) = array.data_type() {
+        return dictionary_string_encoder(array, field_desc);
+    }
+    if field_desc.label == Some(Label::Repeated as i32) {
+        return None;
+    }
+
+    let field_number = field_desc.number.unwrap_or(0);
+    let tag = |wire_type: u32| {
+        let mut tag = Vec::with_capacity(2);
+        put_varint(
+            &mut tag,
+            u64::from(((field_number as u32) << 3) | wire_type),
+        );
+        tag
+    };
+    let any = array.as_any();
+
+    let encoder: ColumnEncoder<'a> = match field_desc.r#type.unwrap_or(9) {
+        1 if float_policy == FloatPolicy::Passthrough => {
+            let arr = any.downcast_ref::<Float64Array>()?;
+            let tag = tag(1);
+            Box::new(move |buffer, row| {
+                if arr.is_valid(row) {
+                    buffer.extend_from_slice(&tag);
+                    buffer.extend_from_slice(&arr.value(row).to_le_bytes());
+                }
+            })
+        }
+        2 if float_policy == FloatPolicy::Passthrough => {
+            let arr = any.downcast_ref::<Float32Array>()?;
+            let tag = tag(5);
+            Box::new(move |buffer, row| {
+                if arr.is_valid(row) {
+                    buffer.extend_from_slice(&tag);
+                    buffer.extend_from_slice(&arr.value(row).to_le_bytes());
+                }
+            })
+        }
+        3 => {
+            let tag = tag(0);
+            if let Some(arr) = any.downcast_ref::<Int64Array>() {
+                varint_column(arr, tag, |v| v as u64)
+            } else if let Some(arr) = any.downcast_ref::<Date64Array>() {
+                varint_column(arr, tag, |v| v as u64)
+            } else if let Some(arr) = any.downcast_ref::<TimestampMicrosecondArray>() {
+                varint_column(arr, tag, move |v| {
+                    convert_timestamp_unit(v, TimeUnit::Microsecond, timestamp_unit) as u64
+                })
+            } else if let Some(arr) = any.downcast_ref::<TimestampMillisecondArray>() {
+                varint_column(arr, tag, move |v| {
+                    convert_timestamp_unit(v, TimeUnit::Millisecond, timestamp_unit) as u64
+                })
+            } else if let Some(arr) = any.downcast_ref::<TimestampSecondArray>() {
+                varint_column(arr, tag, move |v| {
+                    convert_timestamp_unit(v, TimeUnit::Second, timestamp_unit) as u64
+                })
+            } else if let Some(arr) = any.downcast_ref::<TimestampNanosecondArray>() {
+                varint_column(arr, tag, move |v| {
+                    convert_timestamp_unit(v, TimeUnit::Nanosecond, timestamp_unit) as u64
+                })
+            } else {
+                return None;
+            }
+        }
+        4 => varint_column(any.downcast_ref::<UInt64Array>()?, tag(0), |v| v),
+        5 => {
+            let tag = tag(0);
+            if let Some(arr) = any.downcast_ref::<Int32Array>() {
+                varint_column(arr, tag, |v| v as u64)
+            } else {
+                varint_column(any.downcast_ref::<Date32Array>()?, tag, |v| v as u64)
+            }
+        }
+        8 => varint_column(any.downcast_ref::<BooleanArray>()?, tag(0), u64::from),
+        9 => length_delimited_column(any.downcast_ref::<StringArray>()?, tag(2)),
+        12 => length_delimited_column(any.downcast_ref::<BinaryArray>()?, tag(2)),
+        17 => {
+            if let Some(arr) = any.downcast_ref::<StringArray>() {
+                length_delimited_column(arr, tag(2))
+            } else {
+                let arr = any.downcast_ref::<Int32Array>()?;
+                varint_column(arr, tag(0), |v| ((v << 1) ^ (v >> 31)) as u32 as u64)
+            }
+        }
+        18 => {
+            if let Some(arr) = any.downcast_ref::<StringArray>() {
+                length_delimited_column(arr, tag(2))
+            } else {
+                let arr = any.downcast_ref::<Int64Array>()?;
+                varint_column(arr, tag(0), |v| ((v << 1) ^ (v >> 63)) as u64)
+            }
+        }
+        _ => return None,
+    };
+    Some(encoder)
+}
+
+/// Encoder for a `Dictionary(_, Utf8)` column written to a non-repeated string field
+///
+/// Each dictionary value is encoded (tag, length and bytes) once up front; rows then copy
+/// the encoding of the value their key points at instead of re-encoding repeated strings.
+/// Returns `None` for other dictionary value types or field types.
+fn dictionary_string_encoder<'a>(
+    array: &'a dyn Array,
+    field_desc: &FieldDescriptorProto,
+) -> Option<ColumnEncoder<'a>> {
+    if field_desc.label == Some(Label::Repeated as i32)
+        || field_desc.r#type.unwrap_or(9) != Type::String as i32
+    {
+        return None;
+    }
+    let dictionary = array.as_any_dictionary_opt()?;
+    let values = dictionary.values().as_string_opt::<i32>()?;
+
+    let mut tag = Vec::with_capacity(2);
+    put_varint(
+        &mut tag,
+        u64::from(((field_desc.number.unwrap_or(0) as u32) << 3) | 2),
+    );
+
+    // Null dictionary values encode as nothing (an empty range), like a null row
+    let mut encoded = Vec::new();
+    let mut offsets = Vec::with_capacity(values.len() + 1);
+    offsets.push(0);
+    for value in values.iter() {
+        if let Some(value) = value {
+            encoded.extend_from_slice(&tag);
+            put_varint(&mut encoded, value.len() as u64);
+            encoded.extend_from_slice(value.as_bytes());
+        }
+        offsets.push(encoded.len());
+    }
+
+    let keys = dictionary.keys();
+    let normalized_keys = dictionary.normalized_keys();
+    Some(Box::new(move |buffer, row| {
+        if keys.is_valid(row) {
+            let key = normalized_keys[row];
+            buffer.extend_from_slice(&encoded[offsets[key]..offsets[key + 1]]);
+        }
+    }))
+}
+
+/// Encoder writing each non-null value of `arr` as a tagged varint
+fn varint_column<'a, A: ArrayAccessor + Send + Sync + 'a>(
+    arr: A,
+    tag: Vec<u8>,
+    to_varint: impl Fn(A::Item) -> u64 + Send + Sync + 'a,
+) -> ColumnEncoder<'a> {
+    Box::new(move |buffer, row| {
+        if arr.is_valid(row) {
+            buffer.extend_from_slice(&tag);
+            put_varint(buffer, to_varint(arr.value(row)));
+        }
+    })
+}
+
+/// Encoder writing each non-null value of `arr` as a tagged, length-prefixed field
+fn length_delimited_column<'a, A>(arr: A, tag: Vec<u8>) -> ColumnEncoder<'a>
+where
+    A: ArrayAccessor + 'a,
+    A::Item: AsRef<[u8]>,
+{
+    Box::new(move |buffer, row| {
+        if arr.is_valid(row) {
+            let value = arr.value(row);
+            let bytes = value.as_ref();
+            buffer.extend_from_slice(&tag);
+            put_varint(buffer, bytes.len() as u64);
+            buffer.extend_from_slice(bytes);
+        }
+    })
+}
+
+/// Write `value` as a varint (infallible form of `encode_varint`)
+fn put_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push(((value & 0x7F) | 0x80) as u8);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+/// Row loop of the flat-schema fast path
+fn convert_flat_rows(
+    rows: Range<usize>,
+    encoders: &[ColumnEncoder<'_>],
+    max_failures: Option<usize>,
+) -> ProtobufConversionResult {
+    let mut successful_bytes = Vec::with_capacity(rows.len());
+    let mut failed_rows = Vec::new();
+    let mut aborted_at_row = None;
+
+    for row_idx in rows {
+        if exceeds_max_failures(failed_rows.len(), max_failures) {
+            aborted_at_row = Some(row_idx);
+            break;
+        }
+
+        let mut row_buffer = Vec::new();
+        for encoder in encoders {
+            encoder(&mut row_buffer, row_idx);
+        }
+
+        if row_buffer.len() > MAX_RECORD_SIZE_BYTES {
+            failed_rows.push((row_idx, record_too_large_error(row_buffer.len())));
+        } else {
+            successful_bytes.push((row_idx, row_buffer));
+        }
+    }
+
+    ProtobufConversionResult {
+        successful_bytes,
+        failed_rows,
+        aborted_at_row,
+    }
+}
 
-    // Check field count
-    if descriptor.field.len() > MAX_FIELDS_PER_MESSAGE {
-        return Err(ZerobusError::ConfigurationError(format!(
-            "Protobuf descriptor field count ({}) exceeds maximum ({})",
-            descriptor.field.len(),
-            MAX_FIELDS_PER_MESSAGE
-        )));
+/// Convert rows `0..num_rows` with `convert`, split into up to `parallelism` chunks
+///
+/// With the `parallel` feature and `parallelism > 1`, contiguous row chunks are converted
+/// concurrently on the rayon thread pool and merged back in row order. Chunks convert
+/// every row and `max_failures` is applied while merging, so the result is the same as
+/// converting sequentially (only the work past the abort point isn't saved).
+fn convert_row_chunks<C>(
+    num_rows: usize,
+    parallelism: usize,
+    max_failures: Option<usize>,
+    convert: C,
+) -> ProtobufConversionResult
+where
+    C: Fn(Range<usize>, Option<usize>) -> ProtobufConversionResult + Send + Sync,
+{
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+
+        if parallelism > 1 && num_rows > 1 {
+            let chunk_size = num_rows.div_ceil(parallelism);
+            let chunks: Vec<ProtobufConversionResult> = (0..num_rows)
+                .step_by(chunk_size)
+                .collect::<Vec<_>>()
+                .into_par_iter()
+                .map(|start| convert(start..(start + chunk_size).min(num_rows), None))
+                .collect();
+            return merge_row_chunks(chunks, num_rows, max_failures);
+        }
     }
+    #[cfg(not(feature = "parallel"))]
+    let _ = parallelism;
 
-    // Validate each field
-    for field in &descriptor.field {
-        // Validate field number
-        if let Some(field_number) = field.number {
-            if !(MIN_FIELD_NUMBER..=MAX_FIELD_NUMBER).contains(&field_number) {
-                return Err(ZerobusError::ConfigurationError(format!(
-                    "Invalid Protobuf field number: {} (must be between {} and {})",
-                    field_number, MIN_FIELD_NUMBER, MAX_FIELD_NUMBER
-                )));
+    convert(0..num_rows, max_failures)
+}
+
+/// Merge chunk results (in row order) into one, aborting as the sequential loop would
+#[cfg(feature = "parallel")]
+fn merge_row_chunks(
+    chunks: Vec<ProtobufConversionResult>,
+    num_rows: usize,
+    max_failures: Option<usize>,
+) -> ProtobufConversionResult {
+    let mut successful_bytes = Vec::with_capacity(num_rows);
+    let mut failed_rows = Vec::new();
+
+    for chunk in chunks {
+        let mut converted = chunk.successful_bytes.into_iter().peekable();
+        let mut failed = chunk.failed_rows.into_iter().peekable();
+        loop {
+            let next_failed = failed.peek().map(|(idx, _)| *idx);
+            let next_converted = converted.peek().map(|(idx, _)| *idx);
+            let Some(row_idx) = next_failed.into_iter().chain(next_converted).min() else {
+                break;
+            };
+            if exceeds_max_failures(failed_rows.len(), max_failures) {
+                return ProtobufConversionResult {
+                    successful_bytes,
+                    failed_rows,
+                    aborted_at_row: Some(row_idx),
+                };
+            }
+            if next_failed == Some(row_idx) {
+                failed_rows.extend(failed.next());
+            } else {
+                successful_bytes.extend(converted.next());
             }
         }
     }
 
-    // Recursively validate nested types
-    for nested_type in &descriptor.nested_type {
-        validate_descriptor_recursive(nested_type, depth + 1)?;
+    ProtobufConversionResult {
+        successful_bytes,
+        failed_rows,
+        aborted_at_row: None,
     }
+}
 
+/// Whether `failed` rows is more than the `max_failures` tolerated before aborting
+fn exceeds_max_failures(failed: usize, max_failures: Option<usize>) -> bool {
+    max_failures.is_some_and(|max| failed > max)
+}
+
+/// Error for a row whose encoding exceeds the Zerobus record size limit
+/// Check a serialized record against the Zerobus per-record size limit
+///
+/// # Errors
+///
+/// Returns `ConversionError` if the record exceeds the limit.
+pub(crate) fn check_record_size(record: &[u8]) -> Result<(), ZerobusError> {
+    if record.len() > MAX_RECORD_SIZE_BYTES {
+        return Err(record_too_large_error(record.len()));
+    }
     Ok(())
 }
 
-/// Result of converting a RecordBatch to Protobuf
-#[derive(Debug)]
-pub struct ProtobufConversionResult {
-    /// Successful conversions: (row_index, protobuf_bytes)
-    pub successful_bytes: Vec<(usize, Vec<u8>)>,
-    /// Failed conversions: (row_index, error)
-    pub failed_rows: Vec<(usize, ZerobusError)>,
+fn record_too_large_error(size: usize) -> ZerobusError {
+    ZerobusError::ConversionError(format!(
+        "Record size ({}) exceeds Zerobus limit of {} bytes (4MB). Headers require 19 bytes, leaving {} bytes for payload.",
+        size,
+        MAX_RECORD_SIZE_BYTES + 19,
+        MAX_RECORD_SIZE_BYTES
+    ))
 }
 
-/// Convert Arrow RecordBatch to Protobuf bytes
+/// General row loop: encodes every cell through `encode_arrow_field_to_protobuf`
+#[allow(clippy::too_many_arguments)]
+fn convert_rows_generic<F, N>(
+    batch: &RecordBatch,
+    field_by_name: &std::collections::HashMap<String, F>,
+    nested_types_by_name: &std::collections::HashMap<String, N>,
+    field_names: &FieldNameTransform,
+    float_policy: FloatPolicy,
+    validate_utf8: bool,
+    lenient_bool_coercion: bool,
+    emit_defaults: bool,
+    null_elements: NullElementPolicy,
+    timestamp_unit: TimeUnit,
+    redaction: ErrorRedaction<'_>,
+    max_failures: Option<usize>,
+    parallelism: usize,
+    custom_encoder: Option<&CustomEncoder>,
+) -> ProtobufConversionResult
+where
+    F: Borrow<FieldDescriptorProto> + Sync,
+    N: Borrow<DescriptorProto> + Sync,
+{
+    let schema = batch.schema();
+
+    // Dictionary encoded string columns encode each distinct value once per batch.
+    // Validated strings go through the per-cell encoder, which can fail the row, and
+    // a custom encoder must be offered every value.
+    let dictionary_encoders: Vec<Option<ColumnEncoder<'_>>> = schema
+        .fields()
+        .iter()
+        .zip(batch.columns())
+        .map(|(field, array)| {
+            if validate_utf8 || custom_encoder.is_some() {
+                return None;
+            }
+            let field_desc = field_by_name.get(field_names.apply(field.name()).as_ref())?;
+            dictionary_string_encoder(array.as_ref(), field_desc.borrow())
+        })
+        .collect();
+
+    convert_row_chunks(
+        batch.num_rows(),
+        parallelism,
+        max_failures,
+        |rows, max_failures| {
+            let mut successful_bytes = Vec::new();
+            let mut failed_rows = Vec::new();
+            let mut aborted_at_row = None;
+
+            // Convert each row directly from Arrow to Protobuf
+            // Collect errors per-row instead of failing fast (unless there are too many)
+            for row_idx in rows {
+                if exceeds_max_failures(failed_rows.len(), max_failures) {
+                    aborted_at_row = Some(row_idx);
+                    break;
+                }
+
+                let mut row_buffer = Vec::new();
+                let mut row_failed = false;
+                let mut row_error: Option<ZerobusError> = None;
+
+                // Encode each field directly from Arrow array to Protobuf wire format
+                for (field_idx, field) in schema.fields().iter().enumerate() {
+                    if let Some(encoder) = &dictionary_encoders[field_idx] {
+                        encoder(&mut row_buffer, row_idx);
+                        continue;
+                    }
+                    let array = batch.column(field_idx);
+
+                    // Find field descriptor
+                    if let Some(field_desc) = field_by_name
+                        .get(field_names.apply(field.name()).as_ref())
+                        .map(Borrow::borrow)
+                    {
+                        let field_number = field_desc.number.unwrap_or(0);
+
+                        if let Err(e) = encode_arrow_field_to_protobuf(
+                            &mut row_buffer,
+                            field_number,
+                            field_desc,
+                            array,
+                            row_idx,
+                            field_names,
+                            float_policy,
+                            validate_utf8,
+                            lenient_bool_coercion,
+                            emit_defaults,
+                            null_elements,
+                            timestamp_unit,
+                            Some(nested_types_by_name),
+                            custom_encoder,
+                        ) {
+                            // Collect error for this row instead of returning immediately
+                            row_failed = true;
+                            row_error = Some(ZerobusError::ConversionError(format!(
+                                "Field encoding failed: field='{}', row={}, error={}",
+                                redaction.field_name(field.name()),
+                                row_idx,
+                                redaction.detail(&e)
+                            )));
+                            break; // Stop processing this row
+                        }
+                    } else {
+                        debug!("Field '{}' not found in descriptor, skipping", field.name());
+                    }
+                }
+
+                if row_failed {
+                    // Add to failed rows
+                    if let Some(error) = row_error {
+                        failed_rows.push((row_idx, error));
+                    }
+                } else {
+                    // Validate record size (Zerobus limit: 4MB per message)
+                    if row_buffer.len() > MAX_RECORD_SIZE_BYTES {
+                        failed_rows.push((row_idx, record_too_large_error(row_buffer.len())));
+                    } else {
+                        // Add to successful conversions
+                        successful_bytes.push((row_idx, row_buffer));
+                    }
+                }
+            }
+
+            ProtobufConversionResult {
+                successful_bytes,
+                failed_rows,
+                aborted_at_row,
+            }
+        },
+    )
+}
+
+/// Estimate the serialized Protobuf size of a RecordBatch without encoding it
 ///
-/// Converts each row in the RecordBatch to Protobuf bytes using the descriptor.
-/// Returns both successful conversions and failed rows.
+/// Sums approximate per-field sizes column by column: fixed widths for numeric types,
+/// actual lengths for strings and binary (read from the offset buffers), and recursive
+/// estimates for nested messages. Much cheaper than `record_batch_to_protobuf_bytes`,
+/// so it can be used to decide whether to split a batch before converting it.
+///
+/// The estimate is typically within a few percent of the actual total. Nested message
+/// length prefixes are approximated from the average message size, and rows that would
+/// fail conversion are estimated as if they succeeded.
 ///
 /// # Arguments
 ///
-/// * `batch` - RecordBatch to convert
+/// * `batch` - RecordBatch to estimate
 /// * `descriptor` - Protobuf descriptor that matches the batch schema
 ///
 /// # Returns
 ///
-/// Returns ProtobufConversionResult with successful bytes and failed rows.
-/// This function processes all rows and collects errors per-row instead of failing fast.
-pub fn record_batch_to_protobuf_bytes(
-    batch: &RecordBatch,
-    descriptor: &DescriptorProto,
-) -> ProtobufConversionResult {
-    let schema = batch.schema();
-    let num_rows = batch.num_rows();
-
-    if num_rows == 0 {
-        return ProtobufConversionResult {
-            successful_bytes: vec![],
-            failed_rows: vec![],
-        };
-    }
+/// Approximate total size in bytes of all rows' Protobuf encodings.
+pub fn estimate_protobuf_size(batch: &RecordBatch, descriptor: &DescriptorProto) -> usize {
+    estimate_message_size(batch.schema().fields(), batch.columns(), descriptor)
+}
 
-    // Build field name -> field descriptor map for efficient lookup
-    let field_by_name: std::collections::HashMap<String, &FieldDescriptorProto> = descriptor
+/// Estimate the size of every row of a message whose fields are `columns`
+fn estimate_message_size(
+    fields: &arrow::datatypes::Fields,
+    columns: &[ArrayRef],
+    descriptor: &DescriptorProto,
+) -> usize {
+    let field_by_name: std::collections::HashMap<&str, &FieldDescriptorProto> = descriptor
         .field
         .iter()
-        .filter_map(|f| f.name.as_ref().map(|name| (name.clone(), f)))
+        .filter_map(|f| f.name.as_deref().map(|name| (name, f)))
         .collect();
-
-    // Build nested type name -> nested descriptor map
-    let nested_types_by_name: std::collections::HashMap<String, &DescriptorProto> = descriptor
+    let nested_types: std::collections::HashMap<&str, &DescriptorProto> = descriptor
         .nested_type
         .iter()
-        .filter_map(|nt| {
-            nt.name.as_ref().map(|name| {
-                // Extract the full type name (e.g., ".ZerobusMessage._metadata" -> "_metadata")
-                // The type_name in FieldDescriptorProto uses format ".ParentMessage.NestedMessage"
-                // We need to match on the nested message name
-                (name.clone(), nt)
-            })
-        })
+        .filter_map(|nt| nt.name.as_deref().map(|name| (name, nt)))
         .collect();
 
-    let mut successful_bytes = Vec::new();
-    let mut failed_rows = Vec::new();
-
-    // Convert each row directly from Arrow to Protobuf
-    // Collect errors per-row instead of failing fast
-    for row_idx in 0..num_rows {
-        let mut row_buffer = Vec::new();
-        let mut row_failed = false;
-        let mut row_error: Option<ZerobusError> = None;
-
-        // Encode each field directly from Arrow array to Protobuf wire format
-        for (field_idx, field) in schema.fields().iter().enumerate() {
-            let array = batch.column(field_idx);
+    fields
+        .iter()
+        .zip(columns)
+        .filter_map(|(field, array)| {
+            let field_desc = field_by_name.get(field.name().as_str())?;
+            Some(estimate_field_size(
+                array.as_ref(),
+                field_desc,
+                &nested_types,
+            ))
+        })
+        .sum()
+}
 
-            // Find field descriptor
-            if let Some(field_desc) = field_by_name.get(field.name()) {
-                let field_number = field_desc.number.unwrap_or(0);
+/// Estimate the encoded size of one field across all rows of `array`
+fn estimate_field_size(
+    array: &dyn Array,
+    field_desc: &FieldDescriptorProto,
+    nested_types: &std::collections::HashMap<&str, &DescriptorProto>,
+) -> usize {
+    let tag_len = varint_len((field_desc.number.unwrap_or(0) as u64) << 3);
 
-                if let Err(e) = encode_arrow_field_to_protobuf(
-                    &mut row_buffer,
-                    field_number,
+    if field_desc.label == Some(Label::Repeated as i32) {
+        if let Some(list_array) = array.as_any().downcast_ref::<ListArray>() {
+            // Repeated fields are encoded one tagged element at a time
+            let values = list_array.values();
+            if list_array.null_count() == 0 {
+                let offsets = list_array.value_offsets();
+                let first = offsets[0] as usize;
+                let last = offsets[offsets.len() - 1] as usize;
+                return estimate_values_size(
+                    values.slice(first, last - first).as_ref(),
                     field_desc,
-                    array,
-                    row_idx,
-                    descriptor,
-                    Some(&nested_types_by_name),
-                ) {
-                    // Collect error for this row instead of returning immediately
-                    row_failed = true;
-                    row_error = Some(ZerobusError::ConversionError(format!(
-                        "Field encoding failed: field='{}', row={}, error={}",
-                        field.name(),
-                        row_idx,
-                        e
-                    )));
-                    break; // Stop processing this row
-                }
-            } else {
-                debug!("Field '{}' not found in descriptor, skipping", field.name());
+                    nested_types,
+                    tag_len,
+                );
             }
+            return (0..list_array.len())
+                .filter(|&row| list_array.is_valid(row))
+                .map(|row| {
+                    estimate_values_size(
+                        list_array.value(row).as_ref(),
+                        field_desc,
+                        nested_types,
+                        tag_len,
+                    )
+                })
+                .sum();
         }
+    }
 
-        if row_failed {
-            // Add to failed rows
-            if let Some(error) = row_error {
-                failed_rows.push((row_idx, error));
-            }
-        } else {
-            // Validate record size (Zerobus limit: 4MB per message)
-            if row_buffer.len() > MAX_RECORD_SIZE_BYTES {
-                failed_rows.push((
-                    row_idx,
-                    ZerobusError::ConversionError(format!(
-                        "Record size ({}) exceeds Zerobus limit of {} bytes (4MB). Headers require 19 bytes, leaving {} bytes for payload.",
-                        row_buffer.len(),
-                        MAX_RECORD_SIZE_BYTES + 19,
-                        MAX_RECORD_SIZE_BYTES
-                    )),
-                ));
+    estimate_values_size(array, field_desc, nested_types, tag_len)
+}
+
+/// Estimate the encoded size of every non-null value in `array`, each with its own tag
+fn estimate_values_size(
+    array: &dyn Array,
+    field_desc: &FieldDescriptorProto,
+    nested_types: &std::collections::HashMap<&str, &DescriptorProto>,
+    tag_len: usize,
+) -> usize {
+    let present = array.len() - array.null_count();
+    if present == 0 {
+        return 0;
+    }
+
+    // Length-delimited values: exact lengths from the offset buffers
+    if let Some(arr) = array.as_any().downcast_ref::<StringArray>() {
+        return length_delimited_size(arr.value_offsets(), arr.nulls(), tag_len);
+    }
+    if let Some(arr) = array.as_any().downcast_ref::<BinaryArray>() {
+        return length_delimited_size(arr.value_offsets(), arr.nulls(), tag_len);
+    }
+
+    let any = array.as_any();
+    match field_desc.r#type.unwrap_or(9) {
+        11 => {
+            let Some(nested_desc) = field_desc
+                .type_name
+                .as_deref()
+                .and_then(|name| name.rsplit('.').next())
+                .and_then(|name| nested_types.get(name))
+            else {
+                return 0;
+            };
+            let body = if let Some(decimal) = any.downcast_ref::<Decimal128Array>() {
+                // 16 unscaled bytes plus the scale, each with a one-byte tag
+                let scale_len = varint_len(i64::from(decimal.scale()) as u64);
+                present * (1 + 1 + 16 + 1 + scale_len)
+            } else if let Some(struct_array) = any.downcast_ref::<StructArray>() {
+                // Child columns also hold slots for null structs; only count present rows
+                let all_rows = estimate_message_size(
+                    struct_array.fields(),
+                    struct_array.columns(),
+                    nested_desc,
+                );
+                all_rows * present / array.len()
             } else {
-                // Add to successful conversions
-                successful_bytes.push((row_idx, row_buffer));
-            }
+                return 0;
+            };
+            body + present * (tag_len + varint_len((body / present) as u64))
         }
+        // Double, Fixed64, SFixed64
+        1 | 6 | 16 => present * (tag_len + 8),
+        // Float, Fixed32, SFixed32
+        2 | 7 | 15 => present * (tag_len + 4),
+        // Bool
+        8 => present * (tag_len + 1),
+        // SInt32, SInt64 (zigzag)
+        17 | 18 => varint_values_size(array, tag_len, true),
+        _ => varint_values_size(array, tag_len, false),
     }
+}
 
-    ProtobufConversionResult {
-        successful_bytes,
-        failed_rows,
+/// Size of tagged, length-prefixed values given their offsets
+fn length_delimited_size<O: OffsetSizeTrait>(
+    offsets: &[O],
+    nulls: Option<&arrow::buffer::NullBuffer>,
+    tag_len: usize,
+) -> usize {
+    offsets
+        .windows(2)
+        .enumerate()
+        .filter(|(i, _)| nulls.is_none_or(|n| n.is_valid(*i)))
+        .map(|(_, w)| {
+            let len = (w[1] - w[0]).as_usize();
+            tag_len + varint_len(len as u64) + len
+        })
+        .sum()
+}
+
+/// Size of tagged varint values, converted the same way the encoder converts them
+fn varint_values_size(array: &dyn Array, tag_len: usize, zigzag: bool) -> usize {
+    let sum = |values: &mut dyn Iterator<Item = i64>| -> usize {
+        values
+            .map(|v| {
+                let encoded = if zigzag {
+                    ((v << 1) ^ (v >> 63)) as u64
+                } else {
+                    v as u64
+                };
+                tag_len + varint_len(encoded)
+            })
+            .sum()
+    };
+
+    let any = array.as_any();
+    if let Some(arr) = any.downcast_ref::<Int64Array>() {
+        sum(&mut arr.iter().flatten())
+    } else if let Some(arr) = any.downcast_ref::<Int32Array>() {
+        sum(&mut arr.iter().flatten().map(i64::from))
+    } else if let Some(arr) = any.downcast_ref::<Date32Array>() {
+        sum(&mut arr.iter().flatten().map(i64::from))
+    } else if let Some(arr) = any.downcast_ref::<Date64Array>() {
+        sum(&mut arr.iter().flatten())
+    } else if let Some(arr) = any.downcast_ref::<TimestampMicrosecondArray>() {
+        sum(&mut arr.iter().flatten())
+    } else if let Some(arr) = any.downcast_ref::<TimestampMillisecondArray>() {
+        sum(&mut arr.iter().flatten().map(|v| v.wrapping_mul(1000)))
+    } else if let Some(arr) = any.downcast_ref::<TimestampSecondArray>() {
+        sum(&mut arr.iter().flatten().map(|v| v.wrapping_mul(1_000_000)))
+    } else if let Some(arr) = any.downcast_ref::<TimestampNanosecondArray>() {
+        sum(&mut arr.iter().flatten().map(|v| v / 1000))
+    } else if let Some(arr) = any.downcast_ref::<UInt64Array>() {
+        arr.iter().flatten().map(|v| tag_len + varint_len(v)).sum()
+    } else if let Some(arr) = any.downcast_ref::<UInt32Array>() {
+        sum(&mut arr.iter().flatten().map(i64::from))
+    } else {
+        // Unknown representation: assume a mid-sized varint
+        (array.len() - array.null_count()) * (tag_len + 5)
+    }
+}
+
+/// Convert a timestamp value between Arrow time units
+///
+/// Conversion to a coarser unit truncates toward zero; conversion to a finer unit wraps
+/// on overflow.
+///
+/// # Arguments
+///
+/// * `value` - Timestamp in `from` units
+/// * `from` - Unit of `value`
+/// * `to` - Unit to convert to
+///
+/// # Returns
+///
+/// The timestamp in `to` units.
+pub fn convert_timestamp_unit(value: i64, from: TimeUnit, to: TimeUnit) -> i64 {
+    let per_second = |unit: TimeUnit| match unit {
+        TimeUnit::Second => 1i64,
+        TimeUnit::Millisecond => 1_000,
+        TimeUnit::Microsecond => 1_000_000,
+        TimeUnit::Nanosecond => 1_000_000_000,
+    };
+    let (from, to) = (per_second(from), per_second(to));
+    if to >= from {
+        value.wrapping_mul(to / from)
+    } else {
+        value / (from / to)
     }
 }
 
+/// Number of bytes `value` occupies as a Protobuf varint
+fn varint_len(value: u64) -> usize {
+    (64 - (value | 1).leading_zeros() as usize).div_ceil(7)
+}
+
 /// Encode a field value from Arrow array directly to Protobuf wire format
 ///
 /// This preserves type precision (Int64 vs Int32, Float64 vs Float32, etc.)
@@ -260,22 +1878,98 @@ pub fn record_batch_to_protobuf_bytes(
 /// * `field_desc` - Protobuf field descriptor
 /// * `array` - Arrow array containing the field values
 /// * `row_idx` - Row index to extract value from
-/// * `parent_descriptor` - Parent message descriptor (for nested types)
+/// * `field_names` - Rename applied to Arrow field names before descriptor lookup
+/// * `float_policy` - Handling of NaN/Infinity float values
+/// * `validate_utf8` - Fail rows whose string values are not valid UTF-8
+/// * `lenient_bool_coercion` - Accept Int8/Int32 columns (nonzero = true) for bool fields
+/// * `emit_defaults` - Write the proto3 default for null non-repeated scalar fields
+/// * `null_elements` - Handling of null elements inside repeated fields
+/// * `timestamp_unit` - Unit timestamp values are written in
 /// * `nested_types` - Optional map of nested type names to descriptors
-fn encode_arrow_field_to_protobuf(
+/// * `custom_encoder` - Hook consulted for every non-null value before the built-in encoding
+#[allow(clippy::too_many_arguments)]
+fn encode_arrow_field_to_protobuf<N: Borrow<DescriptorProto>>(
     buffer: &mut Vec<u8>,
     field_number: i32,
     field_desc: &FieldDescriptorProto,
     array: &Arc<dyn Array>,
     row_idx: usize,
-    _parent_descriptor: &DescriptorProto,
-    nested_types: Option<&std::collections::HashMap<String, &DescriptorProto>>,
+    field_names: &FieldNameTransform,
+    float_policy: FloatPolicy,
+    validate_utf8: bool,
+    lenient_bool_coercion: bool,
+    emit_defaults: bool,
+    null_elements: NullElementPolicy,
+    timestamp_unit: TimeUnit,
+    nested_types: Option<&std::collections::HashMap<String, N>>,
+    custom_encoder: Option<&CustomEncoder>,
 ) -> Result<(), ZerobusError> {
+    // Run-end encoded arrays have no null buffer of their own: resolve the run covering
+    // this row first and encode the run's value (which may itself be null)
+    if let DataType::RunEndEncoded(_, _) = array.data_type() {
+        let (values, physical_idx) = run_end_value(array.as_ref(), row_idx)?;
+        return encode_arrow_field_to_protobuf(
+            buffer,
+            field_number,
+            field_desc,
+            &values,
+            physical_idx,
+            field_names,
+            float_policy,
+            validate_utf8,
+            lenient_bool_coercion,
+            emit_defaults,
+            null_elements,
+            timestamp_unit,
+            nested_types,
+            custom_encoder,
+        );
+    }
+
+    // Dictionary encoded arrays: encode the dictionary value the row's key points at.
+    // Top-level Dictionary(_, Utf8) columns are normally handled by
+    // `dictionary_string_encoder` instead, which encodes each value only once.
+    if let DataType::Dictionary(_, _) = array.data_type() {
+        let Some((values, value_idx)) = dictionary_value(array.as_ref(), row_idx)? else {
+            if emit_defaults {
+                encode_default_value(buffer, field_desc)?;
+            }
+            return Ok(());
+        };
+        return encode_arrow_field_to_protobuf(
+            buffer,
+            field_number,
+            field_desc,
+            &values,
+            value_idx,
+            field_names,
+            float_policy,
+            validate_utf8,
+            lenient_bool_coercion,
+            emit_defaults,
+            null_elements,
+            timestamp_unit,
+            nested_types,
+            custom_encoder,
+        );
+    }
+
     if array.is_null(row_idx) {
-        // Protobuf doesn't encode null/optional fields - just skip
+        // Protobuf doesn't encode null/optional fields - just skip, unless defaults are
+        // emitted (scalar fields only)
+        if emit_defaults {
+            encode_default_value(buffer, field_desc)?;
+        }
         return Ok(());
     }
 
+    // A custom encoder gets the first say; `None` falls through to the built-in encoding
+    if let Some(result) = custom_encoder
+        .and_then(|encoder| encoder.encode(field_desc, array.as_ref(), row_idx, buffer))
+    {
+        return result;
+    }
+
     let protobuf_type = field_desc.r#type.unwrap_or(9); // Default to String
     let is_repeated = field_desc.label == Some(Label::Repeated as i32);
 
@@ -313,7 +2007,7 @@ fn encode_arrow_field_to_protobuf(
                         let parts: Vec<&str> =
                             type_name.trim_start_matches('.').split('.').collect();
                         if let Some(last_part) = parts.last() {
-                            nested_map.get(*last_part)
+                            nested_map.get(*last_part).map(Borrow::borrow)
                         } else {
                             None
                         }
@@ -326,7 +2020,18 @@ fn encode_arrow_field_to_protobuf(
                         if let Some(struct_array) = values.as_any().downcast_ref::<StructArray>() {
                             // Encode each element in the list as a nested message
                             for i in start..end {
-                                if !struct_array.is_null(i) {
+                                if struct_array.is_null(i) {
+                                    // A null element has no message to encode; with
+                                    // `EmitDefaultForNull` it becomes an empty message
+                                    if null_element_is_default(
+                                        null_elements,
+                                        field_desc,
+                                        i - start,
+                                    )? {
+                                        encode_tag(buffer, field_number, 2)?;
+                                        encode_varint(buffer, 0)?;
+                                    }
+                                } else {
                                     // Encode as length-delimited (wire type 2)
                                     let wire_type = 2u32;
                                     encode_tag(buffer, field_number, wire_type)?;
@@ -363,8 +2068,8 @@ fn encode_arrow_field_to_protobuf(
                                     for (field_idx, field) in nested_schema.iter().enumerate() {
                                         let nested_array = struct_array.column(field_idx);
 
-                                        if let Some(nested_field_desc) =
-                                            nested_field_by_name.get(field.name())
+                                        if let Some(nested_field_desc) = nested_field_by_name
+                                            .get(field_names.apply(field.name()).as_ref())
                                         {
                                             let nested_field_number =
                                                 nested_field_desc.number.unwrap_or(0);
@@ -375,8 +2080,15 @@ fn encode_arrow_field_to_protobuf(
                                                 nested_field_desc,
                                                 nested_array,
                                                 i, // Use list element index, not row_idx
-                                                nested_desc,
+                                                field_names,
+                                                float_policy,
+                                                validate_utf8,
+                                                lenient_bool_coercion,
+                                                emit_defaults,
+                                                null_elements,
+                                                timestamp_unit,
                                                 Some(&nested_nested_types),
+                                                custom_encoder,
                                             ) {
                                                 // Standardized error format: context, field, element index, details
                                                 return Err(ZerobusError::ConversionError(format!(
@@ -420,13 +2132,21 @@ fn encode_arrow_field_to_protobuf(
             } else {
                 // Repeated primitive or other type - encode each element
                 for i in start..end {
-                    if !values.is_null(i) {
+                    if values.is_null(i) {
+                        if null_element_is_default(null_elements, field_desc, i - start)? {
+                            encode_zero_value(buffer, field_desc)?;
+                        }
+                    } else {
                         encode_arrow_value_to_protobuf(
                             buffer,
                             field_number,
                             field_desc,
                             values,
                             i,
+                            float_policy,
+                            validate_utf8,
+                            lenient_bool_coercion,
+                            timestamp_unit,
                         )?;
                     }
                 }
@@ -446,6 +2166,11 @@ fn encode_arrow_field_to_protobuf(
     // Single nested messages are represented as StructArray in Arrow.
     // We encode them as length-delimited Protobuf messages (wire type 2).
     //
+    // Null vs empty: a null struct was already skipped by the `is_null` check above, so
+    // the field is absent (`has_field()` is false on the receiver). A present struct whose
+    // children are all null is still written, as a zero-length message: the field is
+    // present with every nested field unset. The two are distinguishable on the wire.
+    //
     // Edge case: The type_name format is ".ParentMessage.NestedMessage"
     // We extract the last part after splitting by "." to find the nested descriptor.
     //
@@ -461,7 +2186,7 @@ fn encode_arrow_field_to_protobuf(
                 // The nested type name is the last part after splitting by "."
                 let parts: Vec<&str> = type_name.trim_start_matches('.').split('.').collect();
                 if let Some(last_part) = parts.last() {
-                    nested_map.get(*last_part)
+                    nested_map.get(*last_part).map(Borrow::borrow)
                 } else {
                     None
                 }
@@ -470,6 +2195,17 @@ fn encode_arrow_field_to_protobuf(
             };
 
             if let Some(nested_desc) = nested_descriptor {
+                // Decimal128 stored exactly as a nested { bytes unscaled; int32 scale } message
+                if let Some(decimal_array) = array.as_any().downcast_ref::<Decimal128Array>() {
+                    return encode_decimal128_as_message(
+                        buffer,
+                        field_number,
+                        decimal_array.value(row_idx),
+                        decimal_array.scale(),
+                        nested_desc,
+                    );
+                }
+
                 // Encode nested message
                 if let Some(struct_array) = array.as_any().downcast_ref::<StructArray>() {
                     // Encode as length-delimited (wire type 2)
@@ -502,7 +2238,9 @@ fn encode_arrow_field_to_protobuf(
                     for (field_idx, field) in nested_schema.iter().enumerate() {
                         let nested_array = struct_array.column(field_idx);
 
-                        if let Some(nested_field_desc) = nested_field_by_name.get(field.name()) {
+                        if let Some(nested_field_desc) =
+                            nested_field_by_name.get(field_names.apply(field.name()).as_ref())
+                        {
                             let nested_field_number = nested_field_desc.number.unwrap_or(0);
 
                             if let Err(e) = encode_arrow_field_to_protobuf(
@@ -511,8 +2249,15 @@ fn encode_arrow_field_to_protobuf(
                                 nested_field_desc,
                                 nested_array,
                                 row_idx,
-                                nested_desc,
+                                field_names,
+                                float_policy,
+                                validate_utf8,
+                                lenient_bool_coercion,
+                                emit_defaults,
+                                null_elements,
+                                timestamp_unit,
                                 Some(&nested_nested_types),
+                                custom_encoder,
                             ) {
                                 // Standardized error format: context, field, row, details
                                 return Err(ZerobusError::ConversionError(format!(
@@ -570,7 +2315,7 @@ fn encode_arrow_field_to_protobuf(
             let nested_descriptor = if let Some(nested_map) = nested_types {
                 let parts: Vec<&str> = type_name.trim_start_matches('.').split('.').collect();
                 if let Some(last_part) = parts.last() {
-                    nested_map.get(*last_part)
+                    nested_map.get(*last_part).map(Borrow::borrow)
                 } else {
                     None
                 }
@@ -611,7 +2356,9 @@ fn encode_arrow_field_to_protobuf(
                     for (field_idx, field) in nested_schema.iter().enumerate() {
                         let nested_array = struct_array.column(field_idx);
 
-                        if let Some(nested_field_desc) = nested_field_by_name.get(field.name()) {
+                        if let Some(nested_field_desc) =
+                            nested_field_by_name.get(field_names.apply(field.name()).as_ref())
+                        {
                             let nested_field_number = nested_field_desc.number.unwrap_or(0);
 
                             if let Err(e) = encode_arrow_field_to_protobuf(
@@ -620,8 +2367,15 @@ fn encode_arrow_field_to_protobuf(
                                 nested_field_desc,
                                 nested_array,
                                 row_idx,
-                                nested_desc,
+                                field_names,
+                                float_policy,
+                                validate_utf8,
+                                lenient_bool_coercion,
+                                emit_defaults,
+                                null_elements,
+                                timestamp_unit,
                                 Some(&nested_nested_types),
+                                custom_encoder,
                             ) {
                                 // Standardized error format: context, field, row, details
                                 return Err(ZerobusError::ConversionError(format!(
@@ -659,7 +2413,7 @@ fn encode_arrow_field_to_protobuf(
             let nested_descriptor = if let Some(nested_map) = nested_types {
                 let parts: Vec<&str> = type_name.trim_start_matches('.').split('.').collect();
                 if let Some(last_part) = parts.last() {
-                    nested_map.get(*last_part)
+                    nested_map.get(*last_part).map(Borrow::borrow)
                 } else {
                     None
                 }
@@ -695,7 +2449,9 @@ fn encode_arrow_field_to_protobuf(
                     for (field_idx, field) in nested_schema.iter().enumerate() {
                         let nested_array = struct_array.column(field_idx);
 
-                        if let Some(nested_field_desc) = nested_field_by_name.get(field.name()) {
+                        if let Some(nested_field_desc) =
+                            nested_field_by_name.get(field_names.apply(field.name()).as_ref())
+                        {
                             let nested_field_number = nested_field_desc.number.unwrap_or(0);
 
                             if let Err(e) = encode_arrow_field_to_protobuf(
@@ -704,8 +2460,15 @@ fn encode_arrow_field_to_protobuf(
                                 nested_field_desc,
                                 nested_array,
                                 row_idx,
-                                nested_desc,
+                                field_names,
+                                float_policy,
+                                validate_utf8,
+                                lenient_bool_coercion,
+                                emit_defaults,
+                                null_elements,
+                                timestamp_unit,
                                 Some(&nested_nested_types),
+                                custom_encoder,
                             ) {
                                 // Standardized error format: context, field, row, details
                                 return Err(ZerobusError::ConversionError(format!(
@@ -725,18 +2488,332 @@ fn encode_arrow_field_to_protobuf(
             }
         }
     }
-
-    // Handle primitive types
-    encode_arrow_value_to_protobuf(buffer, field_number, field_desc, array, row_idx)
+
+    // Handle primitive types
+    encode_arrow_value_to_protobuf(
+        buffer,
+        field_number,
+        field_desc,
+        array,
+        row_idx,
+        float_policy,
+        validate_utf8,
+        lenient_bool_coercion,
+        timestamp_unit,
+    )
+}
+
+/// Values array of a run-end encoded array and the index of the run covering `row_idx`
+///
+/// `row_idx` is a logical row index (slice offsets are accounted for); a run spanning
+/// several rows resolves to the same physical index for each of them.
+fn run_end_value(array: &dyn Array, row_idx: usize) -> Result<(ArrayRef, usize), ZerobusError> {
+    use arrow::datatypes::{Int16Type, Int32Type, Int64Type};
+
+    let any = array.as_any();
+    if let Some(run_array) = any.downcast_ref::<RunArray<Int16Type>>() {
+        Ok((
+            run_array.values().clone(),
+            run_array.get_physical_index(row_idx),
+        ))
+    } else if let Some(run_array) = any.downcast_ref::<RunArray<Int32Type>>() {
+        Ok((
+            run_array.values().clone(),
+            run_array.get_physical_index(row_idx),
+        ))
+    } else if let Some(run_array) = any.downcast_ref::<RunArray<Int64Type>>() {
+        Ok((
+            run_array.values().clone(),
+            run_array.get_physical_index(row_idx),
+        ))
+    } else {
+        Err(ZerobusError::ConversionError(format!(
+            "Unsupported run-end encoded array: {:?}",
+            array.data_type()
+        )))
+    }
+}
+
+/// Values array of a dictionary encoded array and the index `row_idx`'s key points at
+///
+/// Returns `None` if the key is null.
+fn dictionary_value(
+    array: &dyn Array,
+    row_idx: usize,
+) -> Result<Option<(ArrayRef, usize)>, ZerobusError> {
+    downcast_dictionary_array!(
+        array => Ok(array.key(row_idx).map(|key| (array.values().clone(), key))),
+        other => Err(ZerobusError::ConversionError(format!(
+            "Unsupported dictionary array: {:?}",
+            other
+        )))
+    )
+}
+
+/// Name of the `bytes` field holding the unscaled value in a decimal message
+pub const DECIMAL_UNSCALED_FIELD: &str = "unscaled";
+
+/// Name of the `int32` field holding the scale in a decimal message
+pub const DECIMAL_SCALE_FIELD: &str = "scale";
+
+/// Descriptor of the nested decimal message for a `Decimal128(precision, scale)` column
+///
+/// Generated descriptors use this for Decimal128 columns, so they can be encoded exactly
+/// (see [`encode_decimal128_as_message`]). The message is named
+/// `Decimal_<precision>_<scale>`, with a negative scale written as `n<scale>`
+/// (e.g. `Decimal_10_2`, `Decimal_5_n3`), so the descriptor records the column's precision
+/// and scale; each value also carries its scale in the [`DECIMAL_SCALE_FIELD`] field.
+///
+/// # Arguments
+///
+/// * `precision` - Decimal precision
+/// * `scale` - Decimal scale
+///
+/// # Returns
+///
+/// Returns the descriptor of the decimal message.
+pub fn decimal_message_descriptor(precision: u8, scale: i8) -> DescriptorProto {
+    use prost_types::FieldDescriptorProto;
+
+    let scale_name = if scale < 0 {
+        format!("n{}", scale.unsigned_abs())
+    } else {
+        scale.to_string()
+    };
+    let field = |name: &str, number: i32, field_type: Type| FieldDescriptorProto {
+        name: Some(name.to_string()),
+        number: Some(number),
+        label: Some(Label::Optional as i32),
+        r#type: Some(field_type as i32),
+        ..Default::default()
+    };
+    DescriptorProto {
+        name: Some(format!("Decimal_{}_{}", precision, scale_name)),
+        field: vec![
+            field(DECIMAL_UNSCALED_FIELD, 1, Type::Bytes),
+            field(DECIMAL_SCALE_FIELD, 2, Type::Int32),
+        ],
+        ..Default::default()
+    }
+}
+
+/// Encode an Arrow Decimal128 value as a nested decimal message
+///
+/// The message must have a `bytes` field named [`DECIMAL_UNSCALED_FIELD`] and an `int32`
+/// field named [`DECIMAL_SCALE_FIELD`]:
+///
+/// ```text
+/// message Decimal {
+///   bytes unscaled = 1; // 16-byte two's-complement big-endian i128
+///   int32 scale = 2;
+/// }
+/// ```
+///
+/// The unscaled value is written as its full 16-byte big-endian representation, so no
+/// precision is lost and the receiver can reconstruct the decimal exactly as
+/// `i128::from_be_bytes(unscaled) / 10^scale`. Used automatically for Decimal128 columns
+/// whose descriptor field is such a nested message, as in generated descriptors (see
+/// [`decimal_message_descriptor`]).
+///
+/// # Arguments
+///
+/// * `buffer` - Buffer to write Protobuf bytes to
+/// * `field_number` - Field number of the decimal message field in the parent message
+/// * `unscaled` - Unscaled i128 value
+/// * `scale` - Decimal scale
+/// * `decimal_descriptor` - Descriptor of the nested decimal message
+///
+/// # Returns
+///
+/// Returns `Ok(())`, or `ConversionError` if the descriptor is not a decimal message.
+pub fn encode_decimal128_as_message(
+    buffer: &mut Vec<u8>,
+    field_number: i32,
+    unscaled: i128,
+    scale: i8,
+    decimal_descriptor: &DescriptorProto,
+) -> Result<(), ZerobusError> {
+    let field_number_of = |name: &str, expected: Type| {
+        decimal_descriptor
+            .field
+            .iter()
+            .find(|f| f.name.as_deref() == Some(name) && f.r#type == Some(expected as i32))
+            .and_then(|f| f.number)
+            .ok_or_else(|| {
+                ZerobusError::ConversionError(format!(
+                    "Invalid decimal message: message='{}', expected {} field '{}'",
+                    decimal_descriptor.name.as_deref().unwrap_or("unknown"),
+                    expected.as_str_name(),
+                    name
+                ))
+            })
+    };
+    let unscaled_number = field_number_of(DECIMAL_UNSCALED_FIELD, Type::Bytes)?;
+    let scale_number = field_number_of(DECIMAL_SCALE_FIELD, Type::Int32)?;
+
+    let mut message = Vec::with_capacity(32);
+    let unscaled_bytes = unscaled.to_be_bytes();
+    encode_tag(&mut message, unscaled_number, 2)?;
+    encode_varint(&mut message, unscaled_bytes.len() as u64)?;
+    message.extend_from_slice(&unscaled_bytes);
+    // int32 is encoded as a sign-extended varint (negative scales take 10 bytes)
+    encode_tag(&mut message, scale_number, 0)?;
+    encode_varint(&mut message, scale as i64 as u64)?;
+
+    encode_tag(buffer, field_number, 2)?;
+    encode_varint(buffer, message.len() as u64)?;
+    buffer.extend_from_slice(&message);
+    Ok(())
+}
+
+/// Value to encode for a float cell under `policy`, or `None` to omit the field
+fn apply_float_policy(
+    value: f64,
+    policy: FloatPolicy,
+    field_desc: &FieldDescriptorProto,
+) -> Result<Option<f64>, ZerobusError> {
+    if value.is_finite() {
+        return Ok(Some(value));
+    }
+    match policy {
+        FloatPolicy::Passthrough => Ok(Some(value)),
+        FloatPolicy::CoerceToNull => Ok(None),
+        FloatPolicy::CoerceToZero => Ok(Some(0.0)),
+        // Standardized error format: context, field, value, issue
+        FloatPolicy::ErrorOnNonFinite => Err(ZerobusError::ConversionError(format!(
+            "Non-finite float value: field='{}', value={}, issue='rejected_by_float_policy'",
+            field_desc.name.as_deref().unwrap_or("unknown"),
+            value
+        ))),
+    }
+}
+
+/// Bytes of string value `row_idx`, or `None` if `array` is not a Utf8/LargeUtf8 array
+///
+/// The bytes are read from the value buffer rather than through `value()`, so an invalid
+/// sequence is never turned into a `&str`. With `validate_utf8` they are checked and
+/// invalid UTF-8 is reported as a `ConversionError`.
+fn string_value_bytes<'a>(
+    array: &'a dyn Array,
+    row_idx: usize,
+    field_desc: &FieldDescriptorProto,
+    validate_utf8: bool,
+) -> Result<Option<&'a [u8]>, ZerobusError> {
+    fn value_bytes<O: OffsetSizeTrait>(arr: &GenericStringArray<O>, row_idx: usize) -> &[u8] {
+        let offsets = arr.value_offsets();
+        &arr.value_data()[offsets[row_idx].as_usize()..offsets[row_idx + 1].as_usize()]
+    }
+
+    let bytes = if let Some(arr) = array.as_string_opt::<i32>() {
+        value_bytes(arr, row_idx)
+    } else if let Some(arr) = array.as_string_opt::<i64>() {
+        value_bytes(arr, row_idx)
+    } else {
+        return Ok(None);
+    };
+    if validate_utf8 {
+        if let Err(e) = std::str::from_utf8(bytes) {
+            // Standardized error format: context, field, position, issue
+            return Err(ZerobusError::ConversionError(format!(
+                "Invalid UTF-8 string value: field='{}', valid_up_to={}, issue='invalid_utf8'",
+                field_desc.name.as_deref().unwrap_or("unknown"),
+                e.valid_up_to()
+            )));
+        }
+    }
+    Ok(Some(bytes))
+}
+
+/// Write the proto3 default value of a field (0, false, or empty string/bytes)
+///
+/// Writes nothing for repeated fields and nested messages, which have no default
+/// value on the wire.
+fn encode_default_value(
+    buffer: &mut Vec<u8>,
+    field_desc: &FieldDescriptorProto,
+) -> Result<(), ZerobusError> {
+    if field_desc.label == Some(Label::Repeated as i32) {
+        return Ok(());
+    }
+    encode_zero_value(buffer, field_desc)
+}
+
+/// Apply `NullElementPolicy` to a null element of a repeated field
+///
+/// Proto3 repeated fields cannot hold nulls, so the element is either dropped
+/// (shortening the list), replaced by the type's default, or fails the row.
+///
+/// # Returns
+///
+/// Returns `true` if the element's default value should be written in its place.
+fn null_element_is_default(
+    policy: NullElementPolicy,
+    field_desc: &FieldDescriptorProto,
+    element: usize,
+) -> Result<bool, ZerobusError> {
+    match policy {
+        NullElementPolicy::SkipNullElements => Ok(false),
+        NullElementPolicy::EmitDefaultForNull => Ok(true),
+        NullElementPolicy::ErrorOnNullElement => Err(ZerobusError::ConversionError(format!(
+            "Null element in repeated field: field='{}', element={}",
+            field_desc.name.as_deref().unwrap_or("unknown"),
+            element
+        ))),
+    }
+}
+
+/// Write the proto3 zero value of a scalar field, whatever its label
+///
+/// Message fields have no scalar zero value and write nothing.
+fn encode_zero_value(
+    buffer: &mut Vec<u8>,
+    field_desc: &FieldDescriptorProto,
+) -> Result<(), ZerobusError> {
+    let field_number = field_desc.number.unwrap_or(0);
+    let Ok(field_type) = Type::try_from(field_desc.r#type.unwrap_or(9)) else {
+        return Ok(());
+    };
+    match field_type {
+        Type::Double | Type::Fixed64 | Type::Sfixed64 => {
+            encode_tag(buffer, field_number, 1)?;
+            buffer.extend_from_slice(&[0; 8]);
+        }
+        Type::Float | Type::Fixed32 | Type::Sfixed32 => {
+            encode_tag(buffer, field_number, 5)?;
+            buffer.extend_from_slice(&[0; 4]);
+        }
+        Type::Int64
+        | Type::Uint64
+        | Type::Int32
+        | Type::Uint32
+        | Type::Bool
+        | Type::Enum
+        | Type::Sint32
+        | Type::Sint64 => {
+            encode_tag(buffer, field_number, 0)?;
+            buffer.push(0);
+        }
+        Type::String | Type::Bytes => {
+            encode_tag(buffer, field_number, 2)?;
+            buffer.push(0);
+        }
+        Type::Message | Type::Group => {}
+    }
+    Ok(())
 }
 
 /// Encode a single Arrow value to Protobuf wire format
+#[allow(clippy::too_many_arguments)]
 fn encode_arrow_value_to_protobuf(
     buffer: &mut Vec<u8>,
     field_number: i32,
     field_desc: &FieldDescriptorProto,
     array: &Arc<dyn Array>,
     row_idx: usize,
+    float_policy: FloatPolicy,
+    validate_utf8: bool,
+    lenient_bool_coercion: bool,
+    timestamp_unit: TimeUnit,
 ) -> Result<(), ZerobusError> {
     let protobuf_type = field_desc.r#type.unwrap_or(9);
 
@@ -749,9 +2826,13 @@ fn encode_arrow_value_to_protobuf(
                 .ok_or_else(|| {
                     ZerobusError::ConversionError("Expected Float64Array".to_string())
                 })?;
+            let Some(value) = apply_float_policy(arr.value(row_idx), float_policy, field_desc)?
+            else {
+                return Ok(());
+            };
             let wire_type = 1u32; // Fixed64
             encode_tag(buffer, field_number, wire_type)?;
-            buffer.extend_from_slice(&arr.value(row_idx).to_le_bytes());
+            buffer.extend_from_slice(&value.to_le_bytes());
             Ok(())
         }
         2 => {
@@ -762,9 +2843,15 @@ fn encode_arrow_value_to_protobuf(
                 .ok_or_else(|| {
                     ZerobusError::ConversionError("Expected Float32Array".to_string())
                 })?;
+            // f32 -> f64 -> f32 is lossless, so the policy check can be shared
+            let Some(value) =
+                apply_float_policy(f64::from(arr.value(row_idx)), float_policy, field_desc)?
+            else {
+                return Ok(());
+            };
             let wire_type = 5u32; // Fixed32
             encode_tag(buffer, field_number, wire_type)?;
-            buffer.extend_from_slice(&arr.value(row_idx).to_le_bytes());
+            buffer.extend_from_slice(&(value as f32).to_le_bytes());
             Ok(())
         }
         3 => {
@@ -787,37 +2874,59 @@ fn encode_arrow_value_to_protobuf(
                 .as_any()
                 .downcast_ref::<arrow::array::TimestampMicrosecondArray>()
             {
-                // TimestampArray stores microseconds as Int64 internally
+                // TimestampArray stores microseconds as Int64 internally, convert to the target unit
                 let wire_type = 0u32; // Varint
                 encode_tag(buffer, field_number, wire_type)?;
-                encode_varint(buffer, arr.value(row_idx) as u64)?;
+                encode_varint(
+                    buffer,
+                    convert_timestamp_unit(
+                        arr.value(row_idx),
+                        TimeUnit::Microsecond,
+                        timestamp_unit,
+                    ) as u64,
+                )?;
                 Ok(())
             } else if let Some(arr) = array
                 .as_any()
                 .downcast_ref::<arrow::array::TimestampMillisecondArray>()
             {
-                // TimestampArray stores milliseconds as Int64 internally, convert to microseconds
+                // TimestampArray stores milliseconds as Int64 internally, convert to the target unit
                 let wire_type = 0u32; // Varint
                 encode_tag(buffer, field_number, wire_type)?;
-                encode_varint(buffer, (arr.value(row_idx) * 1000) as u64)?; // Convert ms to μs
+                encode_varint(
+                    buffer,
+                    convert_timestamp_unit(
+                        arr.value(row_idx),
+                        TimeUnit::Millisecond,
+                        timestamp_unit,
+                    ) as u64,
+                )?;
                 Ok(())
             } else if let Some(arr) = array
                 .as_any()
                 .downcast_ref::<arrow::array::TimestampSecondArray>()
             {
-                // TimestampArray stores seconds as Int64 internally, convert to microseconds
+                // TimestampArray stores seconds as Int64 internally, convert to the target unit
                 let wire_type = 0u32; // Varint
                 encode_tag(buffer, field_number, wire_type)?;
-                encode_varint(buffer, (arr.value(row_idx) * 1_000_000) as u64)?; // Convert s to μs
+                encode_varint(
+                    buffer,
+                    convert_timestamp_unit(arr.value(row_idx), TimeUnit::Second, timestamp_unit)
+                        as u64,
+                )?;
                 Ok(())
             } else if let Some(arr) = array
                 .as_any()
                 .downcast_ref::<arrow::array::TimestampNanosecondArray>()
             {
-                // TimestampArray stores nanoseconds as Int64 internally, convert to microseconds
+                // TimestampArray stores nanoseconds as Int64 internally, convert to the target unit
                 let wire_type = 0u32; // Varint
                 encode_tag(buffer, field_number, wire_type)?;
-                encode_varint(buffer, (arr.value(row_idx) / 1000) as u64)?; // Convert ns to μs
+                encode_varint(
+                    buffer,
+                    convert_timestamp_unit(arr.value(row_idx), TimeUnit::Nanosecond, timestamp_unit)
+                        as u64,
+                )?;
                 Ok(())
             } else {
                 Err(ZerobusError::ConversionError(format!(
@@ -860,26 +2969,35 @@ fn encode_arrow_value_to_protobuf(
         }
         8 => {
             // Bool
-            let arr = array
-                .as_any()
-                .downcast_ref::<BooleanArray>()
-                .ok_or_else(|| {
-                    ZerobusError::ConversionError("Expected BooleanArray".to_string())
-                })?;
+            let any = array.as_any();
+            let value = if let Some(arr) = any.downcast_ref::<BooleanArray>() {
+                arr.value(row_idx)
+            } else if !lenient_bool_coercion {
+                return Err(ZerobusError::ConversionError(
+                    "Expected BooleanArray".to_string(),
+                ));
+            } else if let Some(arr) = any.downcast_ref::<Int8Array>() {
+                // Integer-backed booleans: nonzero is true
+                arr.value(row_idx) != 0
+            } else if let Some(arr) = any.downcast_ref::<Int32Array>() {
+                arr.value(row_idx) != 0
+            } else {
+                return Err(ZerobusError::ConversionError(format!(
+                    "Expected BooleanArray, Int8Array or Int32Array for Bool field, got: {:?}",
+                    array.data_type()
+                )));
+            };
             let wire_type = 0u32; // Varint
             encode_tag(buffer, field_number, wire_type)?;
-            encode_varint(buffer, if arr.value(row_idx) { 1 } else { 0 })?;
+            encode_varint(buffer, if value { 1 } else { 0 })?;
             Ok(())
         }
         9 => {
             // String
-            let arr = array
-                .as_any()
-                .downcast_ref::<StringArray>()
+            let bytes = string_value_bytes(array.as_ref(), row_idx, field_desc, validate_utf8)?
                 .ok_or_else(|| ZerobusError::ConversionError("Expected StringArray".to_string()))?;
             let wire_type = 2u32; // Length-delimited
             encode_tag(buffer, field_number, wire_type)?;
-            let bytes = arr.value(row_idx).as_bytes();
             encode_varint(buffer, bytes.len() as u64)?;
             buffer.extend_from_slice(bytes);
             Ok(())
@@ -901,11 +3019,12 @@ fn encode_arrow_value_to_protobuf(
             // SInt32 (signed int32 with zigzag encoding)
             // Often used for enum values
             // Handle case where Arrow has StringArray but descriptor says SInt32 (enum stored as string)
-            if let Some(arr) = array.as_any().downcast_ref::<StringArray>() {
+            if let Some(bytes) =
+                string_value_bytes(array.as_ref(), row_idx, field_desc, validate_utf8)?
+            {
                 // Enum field stored as string - encode as string instead
                 let wire_type = 2u32; // Length-delimited
                 encode_tag(buffer, field_number, wire_type)?;
-                let bytes = arr.value(row_idx).as_bytes();
                 encode_varint(buffer, bytes.len() as u64)?;
                 buffer.extend_from_slice(bytes);
                 Ok(())
@@ -928,11 +3047,12 @@ fn encode_arrow_value_to_protobuf(
             // SInt64 (signed int64 with zigzag encoding)
             // Often used for enum values
             // Handle case where Arrow has StringArray but descriptor says SInt64 (enum stored as string)
-            if let Some(arr) = array.as_any().downcast_ref::<StringArray>() {
+            if let Some(bytes) =
+                string_value_bytes(array.as_ref(), row_idx, field_desc, validate_utf8)?
+            {
                 // Enum field stored as string - encode as string instead
                 let wire_type = 2u32; // Length-delimited
                 encode_tag(buffer, field_number, wire_type)?;
-                let bytes = arr.value(row_idx).as_bytes();
                 encode_varint(buffer, bytes.len() as u64)?;
                 buffer.extend_from_slice(bytes);
                 Ok(())
@@ -993,23 +3113,422 @@ fn encode_arrow_value_to_protobuf(
 pub fn generate_protobuf_descriptor(
     schema: &arrow::datatypes::Schema,
 ) -> Result<DescriptorProto, ZerobusError> {
-    generate_protobuf_descriptor_internal(schema, "ZerobusMessage")
+    generate_protobuf_descriptor_internal(
+        schema,
+        "ZerobusMessage",
+        FieldPresence::Implicit,
+        &FieldNameTransform::None,
+        MAX_FIELD_NAME_LENGTH,
+    )
+}
+
+/// Generate Protobuf descriptor from Arrow schema with proto3 explicit field presence
+///
+/// Same as [`generate_protobuf_descriptor`], but when `explicit_presence` is `true`
+/// every nullable, non-repeated Arrow field is emitted as a proto3 `optional` field:
+/// `proto3_optional` is set and the field is placed in its own synthetic oneof
+/// (named `_<field>`, as protoc does). This lets the receiver distinguish a null
+/// (absent) value from a value equal to the type's default.
+///
+/// The wire encoding is unchanged: null values are already omitted.
+///
+/// # Arguments
+///
+/// * `schema` - Arrow schema
+/// * `explicit_presence` - Whether to mark nullable fields as proto3 optional
+///
+/// # Returns
+///
+/// Returns DescriptorProto for the schema, or error if generation fails.
+pub fn generate_protobuf_descriptor_with_presence(
+    schema: &arrow::datatypes::Schema,
+    explicit_presence: bool,
+) -> Result<DescriptorProto, ZerobusError> {
+    generate_protobuf_descriptor_internal(
+        schema,
+        "ZerobusMessage",
+        FieldPresence::from_explicit(explicit_presence),
+        &FieldNameTransform::None,
+        MAX_FIELD_NAME_LENGTH,
+    )
+}
+
+/// Generate Protobuf descriptor from Arrow schema with renamed fields
+///
+/// Same as [`generate_protobuf_descriptor_with_presence`], but every field name (including
+/// nested struct fields) is passed through `field_names` first. Column names are
+/// validated after the rename, including against `max_field_name_length`. Encode
+/// batches for this descriptor with the same transform (see
+/// `record_batch_to_protobuf_bytes_with_maps`).
+///
+/// # Arguments
+///
+/// * `schema` - Arrow schema
+/// * `explicit_presence` - Whether to mark nullable fields as proto3 optional
+/// * `field_names` - Rename applied to Arrow field names
+/// * `max_field_name_length` - Longest column name accepted (see `MAX_FIELD_NAME_LENGTH`)
+///
+/// # Returns
+///
+/// Returns DescriptorProto for the schema, or error if generation fails.
+pub fn generate_protobuf_descriptor_with_field_names(
+    schema: &arrow::datatypes::Schema,
+    explicit_presence: bool,
+    field_names: &FieldNameTransform,
+    max_field_name_length: usize,
+) -> Result<DescriptorProto, ZerobusError> {
+    generate_descriptor_with_field_presence(
+        schema,
+        FieldPresence::from_explicit(explicit_presence),
+        field_names,
+        max_field_name_length,
+    )
+}
+
+/// Which nullable fields a generated descriptor declares proto3 `optional`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FieldPresence {
+    /// None: plain proto3 implicit presence
+    Implicit,
+    /// Nullable string fields only (`StringNullPolicy::ExplicitPresence`)
+    NullableStrings,
+    /// Every nullable, non-repeated field (`explicit_field_presence`)
+    Nullable,
+}
+
+impl FieldPresence {
+    fn from_explicit(explicit_presence: bool) -> Self {
+        if explicit_presence {
+            FieldPresence::Nullable
+        } else {
+            FieldPresence::Implicit
+        }
+    }
+}
+
+/// Generate a descriptor with renamed fields, declaring presence for `presence` fields
+pub(crate) fn generate_descriptor_with_field_presence(
+    schema: &arrow::datatypes::Schema,
+    presence: FieldPresence,
+    field_names: &FieldNameTransform,
+    max_field_name_length: usize,
+) -> Result<DescriptorProto, ZerobusError> {
+    generate_protobuf_descriptor_internal(
+        schema,
+        "ZerobusMessage",
+        presence,
+        field_names,
+        max_field_name_length,
+    )
+}
+
+/// Replace nulls in top-level string columns with a sentinel value
+///
+/// Used by `StringNullPolicy::Sentinel`, so a null string is written as the sentinel
+/// rather than omitted (which proto3 readers can't tell from an empty string). Utf8 and
+/// LargeUtf8 columns containing nulls are rebuilt; all other columns, including strings
+/// nested in structs or lists, are kept as they are.
+///
+/// # Arguments
+///
+/// * `batch` - Batch to fill
+/// * `sentinel` - Value written for null strings
+///
+/// # Returns
+///
+/// Returns the batch with string nulls replaced, or error if it cannot be rebuilt.
+pub fn fill_string_nulls(batch: &RecordBatch, sentinel: &str) -> Result<RecordBatch, ZerobusError> {
+    let columns = batch
+        .columns()
+        .iter()
+        .map(|column| -> ArrayRef {
+            if column.null_count() == 0 {
+                return Arc::clone(column);
+            }
+            if let Some(arr) = column.as_string_opt::<i32>() {
+                Arc::new(
+                    arr.iter()
+                        .map(|value| Some(value.unwrap_or(sentinel)))
+                        .collect::<StringArray>(),
+                )
+            } else if let Some(arr) = column.as_string_opt::<i64>() {
+                Arc::new(
+                    arr.iter()
+                        .map(|value| Some(value.unwrap_or(sentinel)))
+                        .collect::<LargeStringArray>(),
+                )
+            } else {
+                Arc::clone(column)
+            }
+        })
+        .collect();
+    RecordBatch::try_new(batch.schema(), columns).map_err(|e| {
+        ZerobusError::ConversionError(format!("Failed to replace null strings: {}", e))
+    })
+}
+
+/// Arrow-to-Protobuf type mapping a generated descriptor is built with
+///
+/// `descriptor_auto_recovery` switches to the alternate mapping when a table rejects a
+/// generated descriptor on the first record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum TypeMapping {
+    /// `arrow_type_to_protobuf_type` as is
+    #[default]
+    Standard,
+    /// Top-level timestamp columns written as ISO 8601 strings rather than Int64 values
+    TimestampsAsStrings,
+}
+
+impl TypeMapping {
+    /// Name of the mapping, for logging
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            TypeMapping::Standard => "standard",
+            TypeMapping::TimestampsAsStrings => "timestamps_as_strings",
+        }
+    }
+
+    /// The mapping to try once this one was rejected, if it changes any column of `schema`
+    pub(crate) fn alternate_for(self, schema: &arrow::datatypes::Schema) -> Option<Self> {
+        match self {
+            TypeMapping::Standard
+                if schema
+                    .fields()
+                    .iter()
+                    .any(|field| matches!(field.data_type(), DataType::Timestamp(_, _))) =>
+            {
+                Some(TypeMapping::TimestampsAsStrings)
+            }
+            _ => None,
+        }
+    }
+
+    /// Rewrite the columns of `batch` this mapping represents differently
+    ///
+    /// Columns nested in structs or lists are kept as they are.
+    pub(crate) fn apply(self, batch: &RecordBatch) -> Result<RecordBatch, ZerobusError> {
+        if self == TypeMapping::Standard {
+            return Ok(batch.clone());
+        }
+        let schema = batch.schema();
+        let mut fields = Vec::with_capacity(schema.fields().len());
+        let mut columns = Vec::with_capacity(batch.num_columns());
+        for (field, column) in schema.fields().iter().zip(batch.columns()) {
+            if matches!(field.data_type(), DataType::Timestamp(_, _)) {
+                let strings = arrow::compute::cast(column, &DataType::Utf8).map_err(|e| {
+                    ZerobusError::ConversionError(format!(
+                        "Failed to write timestamp column '{}' as strings: {}",
+                        field.name(),
+                        e
+                    ))
+                })?;
+                fields.push(Arc::new(
+                    field.as_ref().clone().with_data_type(DataType::Utf8),
+                ));
+                columns.push(strings);
+            } else {
+                fields.push(Arc::clone(field));
+                columns.push(Arc::clone(column));
+            }
+        }
+        let schema = arrow::datatypes::Schema::new_with_metadata(fields, schema.metadata().clone());
+        RecordBatch::try_new(Arc::new(schema), columns).map_err(|e| {
+            ZerobusError::ConversionError(format!(
+                "Failed to apply the {} type mapping: {}",
+                self.as_str(),
+                e
+            ))
+        })
+    }
+}
+
+/// Wrap a message descriptor in a proto3 file descriptor
+///
+/// Generated descriptors are bare messages; tooling that reads `.pb` files usually
+/// expects a `FileDescriptorProto` with `syntax = "proto3"` and a package. The file is
+/// named after the message (`<message>.proto`). When a package is given, type names that
+/// refer to the message itself (".Message.Nested") are re-qualified as
+/// ".package.Message.Nested" so they still resolve.
+///
+/// # Arguments
+///
+/// * `descriptor` - Message descriptor to wrap
+/// * `package` - Optional Protobuf package name
+///
+/// # Returns
+///
+/// Returns a file descriptor containing the message as its only message type.
+pub fn descriptor_to_file_descriptor(
+    descriptor: &DescriptorProto,
+    package: Option<&str>,
+) -> FileDescriptorProto {
+    let message_name = descriptor.name.as_deref().unwrap_or("ZerobusMessage");
+    let mut message = descriptor.clone();
+    if let Some(package) = package {
+        qualify_type_names(&mut message, message_name, package);
+    }
+
+    FileDescriptorProto {
+        name: Some(format!("{}.proto", message_name)),
+        package: package.map(str::to_string),
+        message_type: vec![message],
+        syntax: Some("proto3".to_string()),
+        ..Default::default()
+    }
+}
+
+/// Prefix type names rooted at `message_name` with `package` (recursively)
+fn qualify_type_names(descriptor: &mut DescriptorProto, message_name: &str, package: &str) {
+    let root = format!(".{}", message_name);
+    for field in &mut descriptor.field {
+        if let Some(type_name) = &mut field.type_name {
+            if type_name == &root || type_name.starts_with(&format!("{}.", root)) {
+                *type_name = format!(".{}{}", package, type_name);
+            }
+        }
+    }
+    for nested in &mut descriptor.nested_type {
+        qualify_type_names(nested, message_name, package);
+    }
+}
+
+/// Build message options carrying selected Arrow schema metadata
+///
+/// Each `Schema::metadata()` entry whose key is in `keys` becomes an
+/// `UninterpretedOption` named after the key, with the value in `string_value`, so a
+/// debug descriptor can be traced back to the schema (e.g. source system and version) it
+/// was generated from. Keys that are not valid option names (see `is_option_name`) are
+/// skipped. Entries are sorted by key so the same metadata always yields the same
+/// descriptor bytes.
+///
+/// Uninterpreted options are not meant for a descriptor sent to a server, so the result
+/// belongs on debug output only.
+///
+/// # Arguments
+///
+/// * `schema` - Arrow schema whose metadata to copy
+/// * `keys` - Metadata keys to copy
+///
+/// # Returns
+///
+/// Returns `None` if the schema has none of the keys.
+pub fn schema_metadata_options(
+    schema: &arrow::datatypes::Schema,
+    keys: &[String],
+) -> Option<MessageOptions> {
+    let mut entries: Vec<(&String, &String)> = schema
+        .metadata()
+        .iter()
+        .filter(|(key, _)| keys.contains(key) && is_option_name(key))
+        .collect();
+    if entries.is_empty() {
+        return None;
+    }
+    entries.sort();
+
+    Some(MessageOptions {
+        uninterpreted_option: entries
+            .into_iter()
+            .map(|(key, value)| UninterpretedOption {
+                name: vec![NamePart {
+                    name_part: key.clone(),
+                    is_extension: false,
+                }],
+                string_value: Some(value.as_bytes().to_vec()),
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    })
+}
+
+/// Whether `name` is a Protobuf identifier, usable as an option name
+///
+/// Letters, digits and underscores, not starting with a digit.
+pub fn is_option_name(name: &str) -> bool {
+    name.chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Check that no two fields of a schema share a name (recursively, after renaming)
+///
+/// Arrow allows duplicate field names, but descriptor fields are looked up by name, so
+/// duplicated columns would collapse into one Protobuf field and all but one would be
+/// silently dropped. Nested struct fields (including `List<Struct>` elements) are checked
+/// per struct. Names are compared after `field_names` is applied, since two distinct
+/// Arrow names can map to the same column.
+///
+/// # Arguments
+///
+/// * `schema` - Arrow schema to check
+/// * `field_names` - Rename applied to Arrow field names
+///
+/// # Errors
+///
+/// Returns `ConfigurationError` naming the first duplicated column.
+pub fn validate_unique_field_names(
+    schema: &arrow::datatypes::Schema,
+    field_names: &FieldNameTransform,
+) -> Result<(), ZerobusError> {
+    validate_unique_fields(schema.fields(), field_names)
+}
+
+fn validate_unique_fields(
+    fields: &arrow::datatypes::Fields,
+    field_names: &FieldNameTransform,
+) -> Result<(), ZerobusError> {
+    let mut seen = std::collections::HashSet::with_capacity(fields.len());
+    for field in fields {
+        let field_name = field_names.apply(field.name());
+        if !seen.insert(field_name.clone()) {
+            return Err(duplicate_field_name_error(&field_name));
+        }
+        let nested = match logical_values_type(field.data_type()) {
+            DataType::Struct(sf) => Some(sf),
+            DataType::List(inner) | DataType::LargeList(inner) => match inner.data_type() {
+                DataType::Struct(sf) => Some(sf),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(nested) = nested {
+            validate_unique_fields(nested, field_names)?;
+        }
+    }
+    Ok(())
+}
+
+fn duplicate_field_name_error(field_name: &str) -> ZerobusError {
+    ZerobusError::ConfigurationError(format!(
+        "Column name '{}' appears more than once in the Arrow schema; duplicate columns would be silently dropped",
+        field_name
+    ))
 }
 
 /// Internal function to generate Protobuf descriptor with a given message name
+#[allow(clippy::explicit_counter_loop)]
 fn generate_protobuf_descriptor_internal(
     schema: &arrow::datatypes::Schema,
     message_name: &str,
+    presence: FieldPresence,
+    field_names: &FieldNameTransform,
+    max_field_name_length: usize,
 ) -> Result<DescriptorProto, ZerobusError> {
-    use prost_types::FieldDescriptorProto;
+    use prost_types::{FieldDescriptorProto, OneofDescriptorProto};
 
     let mut fields = Vec::new();
     let mut nested_types = Vec::new();
+    let mut oneof_decls = Vec::new();
+    let mut seen_names = std::collections::HashSet::with_capacity(schema.fields().len());
     let mut field_number = 1;
 
     for field in schema.fields().iter() {
         // Validate column name: ASCII letters, digits, and underscores only (Zerobus requirement)
-        let field_name = field.name();
+        // The name is checked after the transform, since that is what the table sees
+        let field_name = field_names.apply(field.name());
         if !field_name
             .chars()
             .all(|c| c.is_ascii_alphanumeric() || c == '_')
@@ -1019,32 +3538,54 @@ fn generate_protobuf_descriptor_internal(
                 field_name
             )));
         }
+        // Over-long names would only be rejected at stream creation, with a less clear error
+        if field_name.len() > max_field_name_length {
+            return Err(ZerobusError::ConfigurationError(format!(
+                "Column name '{}' is {} characters long, exceeding the maximum of {} (Zerobus requirement)",
+                field_name,
+                field_name.len(),
+                max_field_name_length
+            )));
+        }
+        // Duplicates would share one descriptor field, dropping all but one column
+        if !seen_names.insert(field_name.clone()) {
+            return Err(duplicate_field_name_error(&field_name));
+        }
+
+        // Run-end and dictionary encoded columns are described by their values' type
+        let data_type = logical_values_type(field.data_type());
 
         // Determine if this is a repeated field (List or LargeList)
-        let is_repeated = matches!(
-            field.data_type(),
-            DataType::List(_) | DataType::LargeList(_)
-        );
+        let is_repeated = matches!(data_type, DataType::List(_) | DataType::LargeList(_));
 
         // Extract the inner type for lists to determine the actual field type
-        let (_inner_data_type, field_type) = match field.data_type() {
+        let (_inner_data_type, field_type) = match data_type {
             DataType::List(inner_field) | DataType::LargeList(inner_field) => (
                 inner_field.data_type(),
                 arrow_type_to_protobuf_type(inner_field.data_type())?,
             ),
-            _ => (
-                field.data_type(),
-                arrow_type_to_protobuf_type(field.data_type())?,
-            ),
+            _ => (data_type, arrow_type_to_protobuf_type(data_type)?),
         };
 
-        // Handle nested Struct types (both direct Struct and List<Struct>)
-        let type_name = if field_type == Type::Message {
+        // Decimal128 is written as a nested { bytes unscaled; int32 scale } message, shared by
+        // all columns of the same precision and scale
+        let type_name = if let DataType::Decimal128(precision, scale) = data_type {
+            let decimal = decimal_message_descriptor(*precision, *scale);
+            let decimal_name = decimal.name.clone().unwrap_or_default();
+            if !nested_types
+                .iter()
+                .any(|nested: &DescriptorProto| nested.name == decimal.name)
+            {
+                nested_types.push(decimal);
+            }
+            Some(format!(".{}.{}", message_name, decimal_name))
+        } else if field_type == Type::Message {
+            // Handle nested Struct types (both direct Struct and List<Struct>)
             // Generate nested type descriptor for Struct fields
             // This handles both:
             // 1. Direct Struct fields: DataType::Struct(...)
             // 2. Repeated Struct fields: DataType::List(StructField) or DataType::LargeList(StructField)
-            let struct_fields = match field.data_type() {
+            let struct_fields = match data_type {
                 DataType::Struct(sf) => sf,
                 DataType::List(inner_field) | DataType::LargeList(inner_field) => {
                     // For List<Struct>, extract the Struct fields from the inner type
@@ -1062,18 +3603,23 @@ fn generate_protobuf_descriptor_internal(
                     return Err(ZerobusError::ConversionError(format!(
                         "Field '{}' has Message type but is not a Struct or List<Struct>: {:?}",
                         field.name(),
-                        field.data_type()
+                        data_type
                     )));
                 }
             };
 
-            let nested_message_name = format!("{}_{}", message_name, field.name());
+            let nested_message_name = format!("{}_{}", message_name, field_name);
             let nested_type_name = format!(".{}.{}", message_name, nested_message_name);
 
             // Recursively generate descriptor for nested struct
             let nested_schema = arrow::datatypes::Schema::new(struct_fields.clone());
-            let nested_descriptor =
-                generate_protobuf_descriptor_internal(&nested_schema, &nested_message_name)?;
+            let nested_descriptor = generate_protobuf_descriptor_internal(
+                &nested_schema,
+                &nested_message_name,
+                presence,
+                field_names,
+                max_field_name_length,
+            )?;
 
             nested_types.push(nested_descriptor);
             Some(nested_type_name)
@@ -1081,8 +3627,27 @@ fn generate_protobuf_descriptor_internal(
             None
         };
 
+        // proto3 explicit presence: each optional field lives in its own synthetic oneof.
+        // Repeated fields have no presence, so they are never marked optional.
+        let declares_presence = !is_repeated
+            && field.is_nullable()
+            && match presence {
+                FieldPresence::Implicit => false,
+                FieldPresence::NullableStrings => field_type == Type::String,
+                FieldPresence::Nullable => true,
+            };
+        let (oneof_index, proto3_optional) = if declares_presence {
+            oneof_decls.push(OneofDescriptorProto {
+                name: Some(format!("_{}", field_name)),
+                options: None,
+            });
+            (Some((oneof_decls.len() - 1) as i32), Some(true))
+        } else {
+            (None, None)
+        };
+
         fields.push(FieldDescriptorProto {
-            name: Some(field.name().clone()),
+            name: Some(field_name.into_owned()),
             number: Some(field_number),
             label: Some(if is_repeated {
                 Label::Repeated as i32
@@ -1093,10 +3658,10 @@ fn generate_protobuf_descriptor_internal(
             type_name,
             extendee: None,
             default_value: None,
-            oneof_index: None,
+            oneof_index,
             json_name: None,
             options: None,
-            proto3_optional: None,
+            proto3_optional,
         });
 
         field_number += 1;
@@ -1109,13 +3674,109 @@ fn generate_protobuf_descriptor_internal(
         nested_type: nested_types,
         enum_type: vec![],
         extension_range: vec![],
-        oneof_decl: vec![],
+        oneof_decl: oneof_decls,
         options: None,
         reserved_range: vec![],
         reserved_name: vec![],
     })
 }
 
+/// Logical type of a column: the values' type for run-end and dictionary encoded columns
+fn logical_values_type(data_type: &DataType) -> &DataType {
+    match data_type {
+        DataType::RunEndEncoded(_, values) => values.data_type(),
+        DataType::Dictionary(_, values) => values,
+        other => other,
+    }
+}
+
+/// Arrow types that can be sent with an auto-generated descriptor
+///
+/// Lists the scalar types that both get a descriptor field from
+/// `arrow_type_to_protobuf_type` and are written by the encoder. Timestamps are accepted
+/// in any unit and with any (or no) timezone; one entry is listed per unit, without a
+/// timezone. Nested types are built from these, see `is_type_supported`.
+///
+/// `Int8`, `Int16`, the unsigned integers, `LargeBinary` and `LargeList` columns are
+/// given a descriptor field but are rejected by the encoder, so they are not listed.
+/// `Decimal128` is not listed either: it is supported as a column or struct field, but not
+/// inside lists, dictionaries or run-end encoded columns (see `is_type_supported`).
+///
+/// # Returns
+///
+/// Returns the supported scalar Arrow types.
+pub fn supported_arrow_types() -> Vec<DataType> {
+    use arrow::datatypes::TimeUnit;
+
+    vec![
+        DataType::Int32,
+        DataType::Int64,
+        DataType::Float32,
+        DataType::Float64,
+        DataType::Boolean,
+        DataType::Utf8,
+        DataType::LargeUtf8,
+        DataType::Binary,
+        DataType::Date32,
+        DataType::Date64,
+        DataType::Timestamp(TimeUnit::Second, None),
+        DataType::Timestamp(TimeUnit::Millisecond, None),
+        DataType::Timestamp(TimeUnit::Microsecond, None),
+        DataType::Timestamp(TimeUnit::Nanosecond, None),
+    ]
+}
+
+/// Whether a column of `data_type` can be sent with an auto-generated descriptor
+///
+/// Lets callers validate a schema up front instead of getting per-row conversion
+/// errors. Supported are the scalar types in `supported_arrow_types` (timestamps in
+/// any unit and timezone), and:
+/// - `Decimal128`, written as a nested decimal message (see `decimal_message_descriptor`)
+/// - `Struct` whose fields are all supported
+/// - `List` of a supported scalar type or `Struct`
+/// - `Dictionary` and `RunEndEncoded` whose values are a supported scalar type
+///
+/// # Arguments
+///
+/// * `data_type` - Arrow type of the column
+///
+/// # Returns
+///
+/// Returns `true` if the type is supported.
+pub fn is_type_supported(data_type: &DataType) -> bool {
+    match data_type {
+        DataType::Struct(fields) => fields
+            .iter()
+            .all(|field| is_type_supported(field.data_type())),
+        DataType::List(item) => match item.data_type() {
+            DataType::Struct(_) => is_type_supported(item.data_type()),
+            item_type => is_scalar_type_supported(item_type),
+        },
+        DataType::Dictionary(_, values) => is_scalar_type_supported(values),
+        DataType::RunEndEncoded(_, values) => is_scalar_type_supported(values.data_type()),
+        DataType::Decimal128(_, _) => true,
+        other => is_scalar_type_supported(other),
+    }
+}
+
+/// Whether `data_type` is one of the scalar types in `supported_arrow_types`
+fn is_scalar_type_supported(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Int32
+            | DataType::Int64
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::Boolean
+            | DataType::Utf8
+            | DataType::LargeUtf8
+            | DataType::Binary
+            | DataType::Date32
+            | DataType::Date64
+            | DataType::Timestamp(_, _)
+    )
+}
+
 /// Convert Arrow data type to Protobuf field type
 fn arrow_type_to_protobuf_type(
     arrow_type: &arrow::datatypes::DataType,
@@ -1132,7 +3793,7 @@ fn arrow_type_to_protobuf_type(
         DataType::Boolean => Ok(Type::Bool),
         DataType::Utf8 | DataType::LargeUtf8 => Ok(Type::String),
         DataType::Binary | DataType::LargeBinary => Ok(Type::Bytes),
-        DataType::Timestamp(_, _) => Ok(Type::Int64), // Store as Int64 (microseconds)
+        DataType::Timestamp(_, _) => Ok(Type::Int64), // Store as Int64 (microseconds by default)
         DataType::Date32 => Ok(Type::Int32),          // Date32 stores days since epoch as Int32
         DataType::Date64 => Ok(Type::Int64), // Date64 stores milliseconds since epoch as Int64
         DataType::List(inner_type) | DataType::LargeList(inner_type) => {
@@ -1145,9 +3806,177 @@ fn arrow_type_to_protobuf_type(
             arrow_type_to_protobuf_type(inner_type.data_type())
         }
         DataType::Struct(_) => Ok(Type::Message), // Nested message
+        DataType::Decimal128(_, _) => Ok(Type::Message), // Nested decimal message
+        DataType::RunEndEncoded(_, values) => arrow_type_to_protobuf_type(values.data_type()),
+        DataType::Dictionary(_, values) => arrow_type_to_protobuf_type(values),
         _ => Err(ZerobusError::ConversionError(format!(
             "Unsupported Arrow type: {:?}",
             arrow_type
         ))),
     }
 }
+
+/// Generate a Databricks `CREATE TABLE` statement for an Arrow schema
+///
+/// Maps each Arrow type to its Databricks SQL type, consistent with the Protobuf mapping
+/// used for ingestion, so the resulting table accepts records produced by this crate.
+/// Non-nullable fields are emitted as `NOT NULL`. Identifiers are backtick-quoted.
+///
+/// | Arrow | Databricks |
+/// |-------|------------|
+/// | Int8 / Int16 | TINYINT / SMALLINT |
+/// | Int32, UInt8/16/32 | INT |
+/// | Int64, UInt64 | BIGINT |
+/// | Float32 / Float64 | FLOAT / DOUBLE |
+/// | Boolean | BOOLEAN |
+/// | Utf8, LargeUtf8 | STRING |
+/// | Binary, LargeBinary | BINARY |
+/// | Decimal128/256(p, s) | DECIMAL(p, s) |
+/// | Timestamp with / without timezone | TIMESTAMP / TIMESTAMP_NTZ |
+/// | Date32, Date64 | DATE |
+/// | List, LargeList | ARRAY<...> |
+/// | Struct | STRUCT<...> |
+/// | Map | MAP<..., ...> |
+/// | RunEndEncoded | type of the run values |
+/// | Dictionary | type of the dictionary values |
+///
+/// # Arguments
+///
+/// * `schema` - Arrow schema
+/// * `table_name` - Table name (`table`, `schema.table`, or `catalog.schema.table`)
+///
+/// # Returns
+///
+/// Returns the DDL string, or `ConfigurationError` for an empty schema or invalid names,
+/// or `ConversionError` for unsupported types.
+///
+/// # Example
+///
+/// ```no_run
+/// use arrow::datatypes::{DataType, Field, Schema};
+/// use arrow_zerobus_sdk_wrapper::wrapper::conversion::arrow_schema_to_databricks_ddl;
+///
+/// let schema = Schema::new(vec![Field::new("id", DataType::Int64, false)]);
+/// let ddl = arrow_schema_to_databricks_ddl(&schema, "main.default.events").unwrap();
+/// ```
+pub fn arrow_schema_to_databricks_ddl(
+    schema: &arrow::datatypes::Schema,
+    table_name: &str,
+) -> Result<String, ZerobusError> {
+    if schema.fields().is_empty() {
+        return Err(ZerobusError::ConfigurationError(
+            "Cannot generate CREATE TABLE for a schema with no fields".to_string(),
+        ));
+    }
+
+    let mut quoted_table = Vec::new();
+    for part in table_name.split('.') {
+        validate_ddl_identifier("Table name part", part)?;
+        quoted_table.push(format!("`{}`", part));
+    }
+
+    let mut columns = Vec::with_capacity(schema.fields().len());
+    for field in schema.fields().iter() {
+        columns.push(format!("  {}", ddl_field(field, " ", 0)?));
+    }
+
+    Ok(format!(
+        "CREATE TABLE {} (\n{}\n)",
+        quoted_table.join("."),
+        columns.join(",\n")
+    ))
+}
+
+/// Format a field as `` `name`<sep>TYPE [NOT NULL] `` (`sep` is `" "` for columns, `": "` in structs)
+fn ddl_field(
+    field: &arrow::datatypes::Field,
+    separator: &str,
+    depth: usize,
+) -> Result<String, ZerobusError> {
+    validate_ddl_identifier("Column name", field.name())?;
+    Ok(format!(
+        "`{}`{}{}{}",
+        field.name(),
+        separator,
+        arrow_type_to_databricks_type(field.data_type(), depth)?,
+        if field.is_nullable() { "" } else { " NOT NULL" }
+    ))
+}
+
+fn validate_ddl_identifier(kind: &str, name: &str) -> Result<(), ZerobusError> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(ZerobusError::ConfigurationError(format!(
+            "{} '{}' must be non-empty and contain only ASCII letters, digits, and underscores (Zerobus requirement)",
+            kind, name
+        )));
+    }
+    Ok(())
+}
+
+/// Convert Arrow data type to Databricks SQL type
+fn arrow_type_to_databricks_type(
+    data_type: &DataType,
+    depth: usize,
+) -> Result<String, ZerobusError> {
+    if depth > MAX_NESTING_DEPTH {
+        return Err(ZerobusError::ConfigurationError(format!(
+            "Nesting depth {} exceeds maximum {}",
+            depth, MAX_NESTING_DEPTH
+        )));
+    }
+
+    let sql_type = match data_type {
+        DataType::Int8 => "TINYINT".to_string(),
+        DataType::Int16 => "SMALLINT".to_string(),
+        DataType::Int32 | DataType::UInt8 | DataType::UInt16 | DataType::UInt32 => {
+            "INT".to_string()
+        }
+        DataType::Int64 | DataType::UInt64 => "BIGINT".to_string(),
+        DataType::Float32 => "FLOAT".to_string(),
+        DataType::Float64 => "DOUBLE".to_string(),
+        DataType::Boolean => "BOOLEAN".to_string(),
+        DataType::Utf8 | DataType::LargeUtf8 => "STRING".to_string(),
+        DataType::Binary | DataType::LargeBinary => "BINARY".to_string(),
+        DataType::Decimal128(precision, scale) | DataType::Decimal256(precision, scale) => {
+            format!("DECIMAL({}, {})", precision, scale)
+        }
+        DataType::Timestamp(_, Some(_)) => "TIMESTAMP".to_string(),
+        DataType::Timestamp(_, None) => "TIMESTAMP_NTZ".to_string(),
+        DataType::Date32 | DataType::Date64 => "DATE".to_string(),
+        DataType::List(inner) | DataType::LargeList(inner) => format!(
+            "ARRAY<{}>",
+            arrow_type_to_databricks_type(inner.data_type(), depth + 1)?
+        ),
+        DataType::Struct(fields) => {
+            let mut members = Vec::with_capacity(fields.len());
+            for field in fields.iter() {
+                members.push(ddl_field(field, ": ", depth + 1)?);
+            }
+            format!("STRUCT<{}>", members.join(", "))
+        }
+        DataType::RunEndEncoded(_, values) => {
+            arrow_type_to_databricks_type(values.data_type(), depth)?
+        }
+        DataType::Dictionary(_, values) => arrow_type_to_databricks_type(values, depth)?,
+        DataType::Map(entries, _) => match entries.data_type() {
+            DataType::Struct(kv) if kv.len() == 2 => format!(
+                "MAP<{}, {}>",
+                arrow_type_to_databricks_type(kv[0].data_type(), depth + 1)?,
+                arrow_type_to_databricks_type(kv[1].data_type(), depth + 1)?
+            ),
+            other => {
+                return Err(ZerobusError::ConversionError(format!(
+                    "Unsupported Arrow Map entries type: {:?}",
+                    other
+                )))
+            }
+        },
+        _ => {
+            return Err(ZerobusError::ConversionError(format!(
+                "Unsupported Arrow type for Databricks DDL: {:?}",
+                data_type
+            )))
+        }
+    };
+    Ok(sql_type)
+}
@@ -5,13 +5,16 @@
 
 use crate::error::ZerobusError;
 use crate::utils::file_rotation::rotate_file_if_needed;
+use crate::wrapper::conversion::descriptor_to_file_descriptor;
 use arrow::record_batch::RecordBatch;
 use prost::Message;
 use prost_types::DescriptorProto;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::io::{BufWriter, Write};
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 use tracing::{debug, info, warn};
@@ -19,6 +22,218 @@ use tracing::{debug, info, warn};
 /// Batch size for file rotation (matches BATCH_SIZE in mod.rs)
 const ROTATION_BATCH_SIZE: usize = 1000;
 
+/// Name of the manifest file in the descriptors directory
+pub const DESCRIPTOR_MANIFEST_FILE: &str = "manifest.json";
+
+/// Entry in the descriptors directory `manifest.json`
+///
+/// One entry is appended each time a new descriptor is written, so the manifest records
+/// which descriptor applied from when (e.g. across schema evolution in a single debug run).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DescriptorManifestEntry {
+    /// Descriptor file name, relative to the descriptors directory
+    pub descriptor_file: String,
+    /// Table the descriptor was written for
+    pub table_name: String,
+    /// RFC 3339 timestamp of when the descriptor was first written
+    pub written_at: String,
+    /// Stable fingerprint (FNV-1a 64, hex) of the encoded descriptor
+    pub schema_fingerprint: String,
+    /// Number of top-level fields in the descriptor
+    pub field_count: usize,
+}
+
+/// Magic marker at the start of Protobuf debug files written with a header
+///
+/// See [`DebugWriter::with_proto_header`] and [`parse_proto_header`].
+pub const PROTO_HEADER_MAGIC: &[u8; 8] = b"ZBPROTO\0";
+
+/// Version of the Protobuf debug file header layout
+pub const PROTO_HEADER_VERSION: u8 = 1;
+
+/// Length in bytes of the Protobuf debug file header
+///
+/// Layout: [`PROTO_HEADER_MAGIC`] (8 bytes), version (1 byte), descriptor fingerprint
+/// (16 ASCII hex digits, as in [`DescriptorManifestEntry::schema_fingerprint`]), `\n`.
+pub const PROTO_HEADER_LEN: usize = PROTO_HEADER_MAGIC.len() + 1 + 16 + 1;
+
+/// Fingerprint written when no descriptor has been written yet
+const UNKNOWN_FINGERPRINT: &str = "0000000000000000";
+
+/// Header of a Protobuf debug file, as read by [`parse_proto_header`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtoDebugHeader {
+    /// Header layout version
+    pub version: u8,
+    /// Fingerprint of the descriptor in effect when the file was created
+    ///
+    /// Matches `schema_fingerprint` in the descriptors manifest; all zeros if no
+    /// descriptor had been written yet.
+    pub descriptor_fingerprint: String,
+}
+
+/// Parse the header at the start of a Protobuf debug file
+///
+/// Reading tools can use this to check the file format and find the descriptor (via
+/// the manifest) before parsing records. Records follow at offset [`PROTO_HEADER_LEN`].
+///
+/// # Arguments
+///
+/// * `bytes` - Beginning of the file (at least [`PROTO_HEADER_LEN`] bytes)
+///
+/// # Returns
+///
+/// Returns the parsed header, or `ConfigurationError` if the bytes don't start with a
+/// valid header or its version is unsupported.
+pub fn parse_proto_header(bytes: &[u8]) -> Result<ProtoDebugHeader, ZerobusError> {
+    let invalid = |reason: &str| {
+        ZerobusError::ConfigurationError(format!("Invalid Protobuf debug file header: {}", reason))
+    };
+    if bytes.len() < PROTO_HEADER_LEN {
+        return Err(invalid("file too short"));
+    }
+    if &bytes[..PROTO_HEADER_MAGIC.len()] != PROTO_HEADER_MAGIC {
+        return Err(invalid("missing magic marker"));
+    }
+    let version = bytes[PROTO_HEADER_MAGIC.len()];
+    if version != PROTO_HEADER_VERSION {
+        return Err(invalid(&format!("unsupported version {}", version)));
+    }
+    let fingerprint = &bytes[PROTO_HEADER_MAGIC.len() + 1..PROTO_HEADER_LEN - 1];
+    if !fingerprint.iter().all(u8::is_ascii_hexdigit) || bytes[PROTO_HEADER_LEN - 1] != b'\n' {
+        return Err(invalid("malformed descriptor fingerprint"));
+    }
+    Ok(ProtoDebugHeader {
+        version,
+        descriptor_fingerprint: String::from_utf8_lossy(fingerprint).into_owned(),
+    })
+}
+
+/// Compute a stable fingerprint of encoded descriptor bytes
+///
+/// FNV-1a is used instead of `DefaultHasher` because the fingerprint is persisted in
+/// the manifest and must not change between Rust releases.
+fn descriptor_fingerprint(bytes: &[u8]) -> String {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
+    let hash = bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Active debug files of all writers sharing an output directory
+///
+/// Used to enforce the global file cap (see `DebugWriter::with_global_max_files`). There
+/// is one tracker per output directory in the process; writers register their active
+/// Arrow and Protobuf files, which the cap never deletes.
+struct DebugFileTracker {
+    /// Output directory shared by the registered writers
+    output_dir: PathBuf,
+    /// Names of the files currently being written to, by any writer
+    ///
+    /// Names are unique: Arrow and Protobuf files differ in extension, and each table has
+    /// one active file per type.
+    active_files: std::sync::Mutex<HashSet<std::ffi::OsString>>,
+}
+
+impl DebugFileTracker {
+    /// Tracker shared by all writers with this output directory
+    fn for_output_dir(output_dir: &Path) -> Arc<Self> {
+        static TRACKERS: OnceLock<std::sync::Mutex<HashMap<PathBuf, Arc<DebugFileTracker>>>> =
+            OnceLock::new();
+
+        let output_dir = output_dir
+            .canonicalize()
+            .unwrap_or_else(|_| output_dir.to_path_buf());
+        let mut trackers = TRACKERS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        Arc::clone(trackers.entry(output_dir.clone()).or_insert_with(|| {
+            Arc::new(Self {
+                output_dir,
+                active_files: Default::default(),
+            })
+        }))
+    }
+
+    fn active_files(&self) -> std::sync::MutexGuard<'_, HashSet<std::ffi::OsString>> {
+        self.active_files.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Register `path` as active
+    fn insert_active(active: &mut HashSet<std::ffi::OsString>, path: &Path) {
+        if let Some(name) = path.file_name() {
+            active.insert(name.to_os_string());
+        }
+    }
+
+    /// Unregister `path` as active
+    fn remove_active(active: &mut HashSet<std::ffi::OsString>, path: &Path) {
+        if let Some(name) = path.file_name() {
+            active.remove(name);
+        }
+    }
+
+    /// Replace a writer's active file after rotation
+    fn replace_active(&self, old_path: &Path, new_path: &Path) {
+        let mut active = self.active_files();
+        Self::remove_active(&mut active, old_path);
+        Self::insert_active(&mut active, new_path);
+    }
+
+    /// Delete the oldest inactive debug files, across all tables, beyond `max_files`
+    ///
+    /// Counts the Arrow (`*.arrows`) and Protobuf (`*.proto`) files of every table in the
+    /// output directory, oldest by modification time first. Failed deletions are logged.
+    fn enforce_cap(&self, max_files: usize) {
+        // Holding the lock keeps other writers from rotating while files are selected
+        let active = self.active_files();
+        let mut files: Vec<(std::time::SystemTime, PathBuf)> =
+            [("zerobus/arrow", "arrows"), ("zerobus/proto", "proto")]
+                .iter()
+                .filter_map(|(dir, extension)| {
+                    let entries = std::fs::read_dir(self.output_dir.join(dir)).ok()?;
+                    Some(entries.filter_map(move |entry| {
+                        let path = entry.ok()?.path();
+                        if path.extension().and_then(|s| s.to_str()) != Some(*extension)
+                            || !path.is_file()
+                        {
+                            return None;
+                        }
+                        Some(path)
+                    }))
+                })
+                .flatten()
+                .filter(|path| path.file_name().is_some_and(|name| !active.contains(name)))
+                .map(|path| {
+                    let modified = std::fs::metadata(&path)
+                        .and_then(|m| m.modified())
+                        .unwrap_or(std::time::UNIX_EPOCH);
+                    (modified, path)
+                })
+                .collect();
+
+        if files.len() <= max_files {
+            return;
+        }
+        files.sort();
+        let excess = files.len() - max_files;
+        for (_, file_path) in &files[..excess] {
+            if let Err(e) = std::fs::remove_file(file_path) {
+                warn!("Failed to delete old file {}: {}", file_path.display(), e);
+            } else {
+                info!(
+                    "🗑️  Deleted old debug file (global limit of {} files): {}",
+                    max_files,
+                    file_path.display()
+                );
+            }
+        }
+    }
+}
+
 /// Debug file writer
 ///
 /// Handles writing Arrow RecordBatch and Protobuf files to disk for debugging.
@@ -48,6 +263,20 @@ pub struct DebugWriter {
     arrow_record_count: Arc<Mutex<usize>>,
     /// Number of records written to current Protobuf file
     protobuf_record_count: Arc<Mutex<usize>>,
+    /// Fingerprints of descriptors already written (also serializes manifest updates)
+    written_descriptors: Arc<Mutex<HashSet<String>>>,
+    /// Tables whose Arrow schema has already been written
+    written_schemas: Arc<Mutex<HashSet<String>>>,
+    /// Protobuf package set on written descriptor files (optional)
+    proto_package: Option<String>,
+    /// Whether new Protobuf debug files start with a header (see `parse_proto_header`)
+    proto_header: bool,
+    /// Fingerprint of the most recently written descriptor
+    current_fingerprint: Arc<Mutex<Option<String>>>,
+    /// Most recently written descriptor, so repeated writes skip encoding it
+    last_descriptor: Arc<Mutex<Option<DescriptorProto>>>,
+    /// Shared tracker and limit for the global file cap (optional)
+    global_file_cap: Option<(Arc<DebugFileTracker>, usize)>,
 }
 
 impl DebugWriter {
@@ -106,9 +335,89 @@ impl DebugWriter {
             last_flush: Arc::new(Mutex::new(Instant::now())),
             arrow_record_count: Arc::new(Mutex::new(0)),
             protobuf_record_count: Arc::new(Mutex::new(0)),
+            written_descriptors: Arc::new(Mutex::new(HashSet::new())),
+            written_schemas: Arc::new(Mutex::new(HashSet::new())),
+            proto_package: None,
+            proto_header: false,
+            current_fingerprint: Arc::new(Mutex::new(None)),
+            last_descriptor: Arc::new(Mutex::new(None)),
+            global_file_cap: None,
         })
     }
 
+    /// Set the Protobuf package written into descriptor files
+    ///
+    /// # Arguments
+    ///
+    /// * `package` - Protobuf package name, or `None` for no package
+    pub fn with_proto_package(mut self, package: Option<String>) -> Self {
+        self.proto_package = package;
+        self
+    }
+
+    /// Start each Protobuf debug file with a header
+    ///
+    /// The header ([`PROTO_HEADER_MAGIC`], [`PROTO_HEADER_VERSION`], and the fingerprint
+    /// of the current descriptor) is written once when a file is created, including
+    /// after rotation. A descriptor change mid-file is not reflected in that file's
+    /// header; the manifest records when each descriptor was written.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether to write the header
+    pub fn with_proto_header(mut self, enabled: bool) -> Self {
+        self.proto_header = enabled;
+        self
+    }
+
+    /// Cap the number of rotated files across all tables writing to the output directory
+    ///
+    /// `max_files_retained` applies per table and type, so a process writing many tables
+    /// can still accumulate many files. With a global cap, every rotation by any writer
+    /// with the same output directory deletes the oldest (by modification time) rotated
+    /// Arrow and Protobuf files of any table until at most `max_files` remain. Files that
+    /// a writer is still writing to are never deleted, and other debug files (descriptors,
+    /// schemas) are not counted.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_files` - Maximum rotated files across all tables, or `None` for no cap
+    pub fn with_global_max_files(mut self, max_files: Option<usize>) -> Self {
+        if let Some((tracker, _)) = self.global_file_cap.take() {
+            self.unregister_active_files(&tracker);
+        }
+        self.global_file_cap = max_files.map(|max_files| {
+            let tracker = DebugFileTracker::for_output_dir(&self.output_dir);
+            let mut active = tracker.active_files();
+            for path in [&self.arrow_file_path, &self.protobuf_file_path] {
+                if let Ok(path) = path.try_lock() {
+                    DebugFileTracker::insert_active(&mut active, &path);
+                }
+            }
+            drop(active);
+            (tracker, max_files)
+        });
+        self
+    }
+
+    /// Remove this writer's active files from the global file tracker
+    fn unregister_active_files(&self, tracker: &DebugFileTracker) {
+        let mut active = tracker.active_files();
+        for path in [&self.arrow_file_path, &self.protobuf_file_path] {
+            if let Ok(path) = path.try_lock() {
+                DebugFileTracker::remove_active(&mut active, &path);
+            }
+        }
+    }
+
+    /// Record a rotation with the global file tracker and enforce the global cap, if set
+    fn apply_global_file_cap(&self, old_path: &Path, new_path: &Path) {
+        if let Some((tracker, max_files)) = &self.global_file_cap {
+            tracker.replace_active(old_path, new_path);
+            tracker.enforce_cap(*max_files);
+        }
+    }
+
     /// Generate rotated file path with timestamp
     ///
     /// Extracts the base filename without any existing timestamps before appending a new timestamp.
@@ -232,7 +541,27 @@ impl DebugWriter {
                     e
                 ))
             })?;
-            *writer_guard = Some(BufWriter::new(file));
+            let mut writer = BufWriter::new(file);
+            if self.proto_header {
+                let fingerprint = self.current_fingerprint.lock().await.clone();
+                let mut header = Vec::with_capacity(PROTO_HEADER_LEN);
+                header.extend_from_slice(PROTO_HEADER_MAGIC);
+                header.push(PROTO_HEADER_VERSION);
+                header.extend_from_slice(
+                    fingerprint
+                        .as_deref()
+                        .unwrap_or(UNKNOWN_FINGERPRINT)
+                        .as_bytes(),
+                );
+                header.push(b'\n');
+                writer.write_all(&header).map_err(|e| {
+                    ZerobusError::ConfigurationError(format!(
+                        "Failed to write Protobuf debug file header: {}",
+                        e
+                    ))
+                })?;
+            }
+            *writer_guard = Some(writer);
             info!("✅ Created Protobuf file: {}", file_path.display());
         }
         Ok(())
@@ -287,6 +616,7 @@ impl DebugWriter {
                     // Don't fail rotation if cleanup fails
                 }
             }
+            self.apply_global_file_cap(&old_path, &new_path);
 
             Ok(true)
         } else {
@@ -338,6 +668,7 @@ impl DebugWriter {
                             // Don't fail rotation if cleanup fails
                         }
                     }
+                    self.apply_global_file_cap(&file_path, &new_path);
 
                     return Ok(true);
                 }
@@ -403,6 +734,7 @@ impl DebugWriter {
                     // Don't fail rotation if cleanup fails
                 }
             }
+            self.apply_global_file_cap(&old_path, &new_path);
 
             Ok(true)
         } else {
@@ -460,6 +792,7 @@ impl DebugWriter {
                             // Don't fail rotation if cleanup fails
                         }
                     }
+                    self.apply_global_file_cap(&file_path, &new_path);
 
                     return Ok(true);
                 }
@@ -571,7 +904,17 @@ impl DebugWriter {
         Ok(())
     }
 
-    /// Write Protobuf descriptor to file (once per table)
+    /// Write Protobuf descriptor to file (once per distinct descriptor)
+    ///
+    /// The descriptor is written as a `FileDescriptorProto` with `syntax = "proto3"` and
+    /// the configured package (see [`descriptor_to_file_descriptor`]), with the message
+    /// as its only message type.
+    ///
+    /// The first descriptor for a table is written to `<table>.pb`. Descriptors that differ
+    /// from it (e.g. after schema evolution) are written to `<table>_<fingerprint>.pb`.
+    /// Every newly written descriptor is recorded in `manifest.json` in the descriptors
+    /// directory (see [`DescriptorManifestEntry`]). Repeated calls with an already written
+    /// descriptor are no-ops.
     ///
     /// # Arguments
     ///
@@ -586,9 +929,67 @@ impl DebugWriter {
         table_name: &str,
         descriptor: &DescriptorProto,
     ) -> Result<(), ZerobusError> {
-        // Create descriptors directory
-        let descriptors_dir = self.output_dir.join("zerobus/descriptors");
-        std::fs::create_dir_all(&descriptors_dir).map_err(|e| {
+        // Batches of a table almost always share one descriptor; skip re-encoding it
+        if self.last_descriptor.lock().await.as_ref() == Some(descriptor) {
+            return Ok(());
+        }
+
+        // Serialize descriptor to bytes, wrapped in a proto3 file descriptor
+        let file_descriptor =
+            descriptor_to_file_descriptor(descriptor, self.proto_package.as_deref());
+        let mut descriptor_bytes = Vec::new();
+        file_descriptor.encode(&mut descriptor_bytes).map_err(|e| {
+            ZerobusError::ConfigurationError(format!("Failed to encode Protobuf descriptor: {}", e))
+        })?;
+        let fingerprint = descriptor_fingerprint(&descriptor_bytes);
+        *self.current_fingerprint.lock().await = Some(fingerprint.clone());
+
+        // Lock held for the rest of the write so concurrent calls can't race on the manifest
+        let mut written = self.written_descriptors.lock().await;
+        if !written.contains(&fingerprint) {
+            let descriptors_dir = self.output_dir.join("zerobus/descriptors");
+            let table = table_name.to_string();
+            let entry_fingerprint = fingerprint.clone();
+            let field_count = descriptor.field.len();
+            let new_file = tokio::task::spawn_blocking(move || {
+                Self::write_descriptor_file(
+                    &descriptors_dir,
+                    &table,
+                    &entry_fingerprint,
+                    &descriptor_bytes,
+                    field_count,
+                )
+            })
+            .await
+            .map_err(|e| {
+                ZerobusError::ConfigurationError(format!("Descriptor write task failed: {}", e))
+            })??;
+            if let Some(path) = new_file {
+                let descriptor_name = descriptor.name.as_deref().unwrap_or("unknown");
+                info!("✅ Wrote Protobuf descriptor for table '{}' to: {} (descriptor name: '{}', {} fields, {} nested types)",
+                      table_name, path.display(), descriptor_name,
+                      descriptor.field.len(), descriptor.nested_type.len());
+            }
+            written.insert(fingerprint);
+        }
+        drop(written);
+
+        *self.last_descriptor.lock().await = Some(descriptor.clone());
+        Ok(())
+    }
+
+    /// Write an encoded descriptor file and record it in the manifest (blocking I/O)
+    ///
+    /// Returns the path of the new file, or `None` if the descriptor file already
+    /// existed (e.g. from a previous run).
+    fn write_descriptor_file(
+        descriptors_dir: &Path,
+        table_name: &str,
+        fingerprint: &str,
+        descriptor_bytes: &[u8],
+        field_count: usize,
+    ) -> Result<Option<PathBuf>, ZerobusError> {
+        std::fs::create_dir_all(descriptors_dir).map_err(|e| {
             ZerobusError::ConfigurationError(format!(
                 "Failed to create descriptors directory: {}",
                 e
@@ -596,31 +997,32 @@ impl DebugWriter {
         })?;
 
         // Create filename from table name (sanitize for filesystem)
+        // First descriptor keeps the plain name; differing descriptors get a fingerprint suffix
         let sanitized_table_name = table_name.replace(['.', '/'], "_");
-        let descriptor_file_path = descriptors_dir.join(format!("{}.pb", sanitized_table_name));
+        let mut descriptor_file_path = descriptors_dir.join(format!("{}.pb", sanitized_table_name));
+        if descriptor_file_path.exists()
+            && std::fs::read(&descriptor_file_path).ok().as_deref() != Some(descriptor_bytes)
+        {
+            descriptor_file_path =
+                descriptors_dir.join(format!("{}_{}.pb", sanitized_table_name, fingerprint));
+        }
 
-        // Check if file already exists (only write once per table)
+        // Check if file already exists (e.g. from a previous run)
         if descriptor_file_path.exists() {
             debug!(
                 "Descriptor file already exists for table {}: {}",
                 table_name,
                 descriptor_file_path.display()
             );
-            return Ok(());
+            return Ok(None);
         }
 
-        // Serialize descriptor to bytes
-        let mut descriptor_bytes = Vec::new();
-        descriptor.encode(&mut descriptor_bytes).map_err(|e| {
-            ZerobusError::ConfigurationError(format!("Failed to encode Protobuf descriptor: {}", e))
-        })?;
-
         // Write to file
         let mut file = std::fs::File::create(&descriptor_file_path).map_err(|e| {
             ZerobusError::ConfigurationError(format!("Failed to create descriptor file: {}", e))
         })?;
 
-        file.write_all(&descriptor_bytes).map_err(|e| {
+        file.write_all(descriptor_bytes).map_err(|e| {
             ZerobusError::ConfigurationError(format!("Failed to write descriptor bytes: {}", e))
         })?;
 
@@ -628,10 +1030,122 @@ impl DebugWriter {
             ZerobusError::ConfigurationError(format!("Failed to sync descriptor file: {}", e))
         })?;
 
-        let descriptor_name = descriptor.name.as_deref().unwrap_or("unknown");
-        info!("✅ Wrote Protobuf descriptor for table '{}' to: {} (descriptor name: '{}', {} fields, {} nested types)",
-              table_name, descriptor_file_path.display(), descriptor_name,
-              descriptor.field.len(), descriptor.nested_type.len());
+        // Record the descriptor in the manifest (best effort - the .pb file is already written)
+        let entry = DescriptorManifestEntry {
+            descriptor_file: descriptor_file_path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            table_name: table_name.to_string(),
+            written_at: chrono::Utc::now().to_rfc3339(),
+            schema_fingerprint: fingerprint.to_string(),
+            field_count,
+        };
+        if let Err(e) = Self::append_manifest_entry(descriptors_dir, entry) {
+            warn!("Failed to update descriptor manifest: {}", e);
+        }
+
+        Ok(Some(descriptor_file_path))
+    }
+
+    /// Write the Arrow schema as pretty-printed JSON (once per table)
+    ///
+    /// The schema is written to `zerobus/schema/<table>.json` using Arrow's serde
+    /// representation, so consumers can read it back with `serde_json` without parsing
+    /// the IPC stream. Only the first call per table writes; later calls are no-ops.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_name` - Table name (used for filename)
+    /// * `schema` - Arrow schema to write
+    ///
+    /// # Errors
+    ///
+    /// Returns error if serialization or file writing fails.
+    pub async fn write_schema(
+        &self,
+        table_name: &str,
+        schema: &arrow::datatypes::Schema,
+    ) -> Result<(), ZerobusError> {
+        let mut written = self.written_schemas.lock().await;
+        if written.contains(table_name) {
+            return Ok(());
+        }
+
+        // Create schema directory
+        let schema_dir = self.output_dir.join("zerobus/schema");
+        std::fs::create_dir_all(&schema_dir).map_err(|e| {
+            ZerobusError::ConfigurationError(format!("Failed to create schema directory: {}", e))
+        })?;
+
+        let schema_json = serde_json::to_string_pretty(schema).map_err(|e| {
+            ZerobusError::ConfigurationError(format!("Failed to serialize Arrow schema: {}", e))
+        })?;
+
+        // Create filename from table name (sanitize for filesystem)
+        let sanitized_table_name = table_name.replace(['.', '/'], "_");
+        let schema_file_path = schema_dir.join(format!("{}.json", sanitized_table_name));
+        std::fs::write(&schema_file_path, schema_json).map_err(|e| {
+            ZerobusError::ConfigurationError(format!("Failed to write schema file: {}", e))
+        })?;
+
+        info!(
+            "✅ Wrote Arrow schema for table '{}' to: {} ({} fields)",
+            table_name,
+            schema_file_path.display(),
+            schema.fields().len()
+        );
+
+        written.insert(table_name.to_string());
+        Ok(())
+    }
+
+    /// Append an entry to the descriptors directory `manifest.json`
+    ///
+    /// The manifest is rewritten via a temporary file and rename so readers never see a
+    /// partially written file. An unreadable existing manifest is replaced.
+    fn append_manifest_entry(
+        descriptors_dir: &std::path::Path,
+        entry: DescriptorManifestEntry,
+    ) -> Result<(), ZerobusError> {
+        // Writers for different tables (e.g. a mirror table) can share a debug directory;
+        // serialize the read-modify-write so concurrent updates aren't lost
+        static MANIFEST_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+        let _manifest_guard = MANIFEST_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let manifest_path = descriptors_dir.join(DESCRIPTOR_MANIFEST_FILE);
+
+        let mut entries: Vec<DescriptorManifestEntry> = match std::fs::read(&manifest_path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                warn!(
+                    "Descriptor manifest {} is not valid JSON, starting a new one: {}",
+                    manifest_path.display(),
+                    e
+                );
+                Vec::new()
+            }),
+            Err(_) => Vec::new(),
+        };
+        entries.push(entry);
+
+        let json = serde_json::to_vec_pretty(&entries).map_err(|e| {
+            ZerobusError::ConfigurationError(format!(
+                "Failed to serialize descriptor manifest: {}",
+                e
+            ))
+        })?;
+        let tmp_path = descriptors_dir.join(format!("{}.tmp", DESCRIPTOR_MANIFEST_FILE));
+        std::fs::write(&tmp_path, json).map_err(|e| {
+            ZerobusError::ConfigurationError(format!("Failed to write descriptor manifest: {}", e))
+        })?;
+        std::fs::rename(&tmp_path, &manifest_path).map_err(|e| {
+            ZerobusError::ConfigurationError(format!(
+                "Failed to replace descriptor manifest: {}",
+                e
+            ))
+        })?;
 
         Ok(())
     }
@@ -850,3 +1364,247 @@ impl DebugWriter {
         last_flush.elapsed() >= self.flush_interval
     }
 }
+
+impl Drop for DebugWriter {
+    fn drop(&mut self) {
+        // Files of a dropped writer are no longer active and count towards the global cap
+        if let Some((tracker, _)) = &self.global_file_cap {
+            self.unregister_active_files(tracker);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use prost_types::field_descriptor_proto::Type;
+    use prost_types::FieldDescriptorProto;
+    use tempfile::TempDir;
+
+    fn create_test_descriptor() -> DescriptorProto {
+        let field = |name: &str, number: i32, field_type: Type| FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(1), // Optional
+            r#type: Some(field_type as i32),
+            ..Default::default()
+        };
+        DescriptorProto {
+            name: Some("TestMessage".to_string()),
+            field: vec![field("id", 1, Type::Int64), field("name", 2, Type::String)],
+            ..Default::default()
+        }
+    }
+
+    fn create_test_writer(temp_dir: &TempDir) -> DebugWriter {
+        DebugWriter::new(
+            temp_dir.path().to_path_buf(),
+            "test_table".to_string(),
+            Duration::from_secs(5),
+            None,
+            Some(10),
+        )
+        .unwrap()
+    }
+
+    fn read_manifest(descriptors_dir: &Path) -> Vec<DescriptorManifestEntry> {
+        let manifest_bytes = std::fs::read(descriptors_dir.join(DESCRIPTOR_MANIFEST_FILE)).unwrap();
+        serde_json::from_slice(&manifest_bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_write_descriptor_writes_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let debug_writer = create_test_writer(&temp_dir);
+
+        let descriptor = create_test_descriptor();
+        debug_writer
+            .write_descriptor("test_table", &descriptor)
+            .await
+            .unwrap();
+        // Same descriptor again must not add a manifest entry
+        debug_writer
+            .write_descriptor("test_table", &descriptor)
+            .await
+            .unwrap();
+
+        let entries = read_manifest(&temp_dir.path().join("zerobus/descriptors"));
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].descriptor_file, "test_table.pb");
+        assert_eq!(entries[0].table_name, "test_table");
+        assert_eq!(entries[0].field_count, 2);
+        assert_eq!(entries[0].schema_fingerprint.len(), 16);
+        assert!(chrono::DateTime::parse_from_rfc3339(&entries[0].written_at).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_write_descriptor_manifest_tracks_schema_evolution() {
+        let temp_dir = TempDir::new().unwrap();
+        let debug_writer = create_test_writer(&temp_dir);
+
+        let original = create_test_descriptor();
+        let mut evolved = create_test_descriptor();
+        evolved.field.push(FieldDescriptorProto {
+            name: Some("score".to_string()),
+            number: Some(3),
+            label: Some(1),
+            r#type: Some(Type::Double as i32),
+            ..Default::default()
+        });
+
+        debug_writer
+            .write_descriptor("test_table", &original)
+            .await
+            .unwrap();
+        debug_writer
+            .write_descriptor("test_table", &evolved)
+            .await
+            .unwrap();
+        // Switching back to an already written descriptor must not add an entry
+        debug_writer
+            .write_descriptor("test_table", &original)
+            .await
+            .unwrap();
+
+        let descriptors_dir = temp_dir.path().join("zerobus/descriptors");
+        let entries = read_manifest(&descriptors_dir);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].descriptor_file, "test_table.pb");
+        assert_eq!(entries[1].field_count, 3);
+        assert_ne!(entries[0].schema_fingerprint, entries[1].schema_fingerprint);
+        assert_eq!(
+            entries[1].descriptor_file,
+            format!("test_table_{}.pb", entries[1].schema_fingerprint)
+        );
+        assert!(descriptors_dir.join(&entries[1].descriptor_file).exists());
+    }
+
+    #[tokio::test]
+    async fn test_debug_writer_write_schema() {
+        let temp_dir = TempDir::new().unwrap();
+        let output_dir = temp_dir.path().to_path_buf();
+
+        let writer = DebugWriter::new(
+            output_dir.clone(),
+            "test.table".to_string(),
+            Duration::from_secs(5),
+            None,
+            Some(10),
+        )
+        .unwrap();
+
+        let address = Field::new(
+            "address",
+            DataType::Struct(vec![Field::new("city", DataType::Utf8, true)].into()),
+            true,
+        );
+        let tags = Field::new(
+            "tags",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            true,
+        );
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            address,
+            tags,
+        ]);
+
+        writer.write_schema("test.table", &schema).await.unwrap();
+
+        let schema_path = output_dir.join("zerobus/schema/test_table.json");
+        let json = std::fs::read_to_string(&schema_path).unwrap();
+        let parsed: Schema = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, schema);
+
+        // Written once per table: a later schema does not overwrite it
+        let other = Schema::new(vec![Field::new("other", DataType::Utf8, true)]);
+        writer.write_schema("test.table", &other).await.unwrap();
+        assert_eq!(std::fs::read_to_string(&schema_path).unwrap(), json);
+    }
+
+    #[tokio::test]
+    async fn test_write_protobuf_with_header() {
+        let temp_dir = TempDir::new().unwrap();
+        let output_dir = temp_dir.path().to_path_buf();
+
+        let writer = DebugWriter::new(
+            output_dir.clone(),
+            "test.table".to_string(),
+            Duration::from_secs(5),
+            None,
+            Some(10),
+        )
+        .unwrap()
+        .with_proto_header(true);
+
+        let schema = Schema::new(vec![Field::new("id", DataType::Int64, false)]);
+        let descriptor = crate::wrapper::conversion::generate_protobuf_descriptor(&schema).unwrap();
+        writer
+            .write_descriptor("test.table", &descriptor)
+            .await
+            .unwrap();
+        writer.write_protobuf(&[0x08, 0x01], true).await.unwrap();
+
+        let bytes = std::fs::read(output_dir.join("zerobus/proto/test_table.proto")).unwrap();
+        assert!(bytes.starts_with(PROTO_HEADER_MAGIC));
+        let header = parse_proto_header(&bytes).unwrap();
+        assert_eq!(header.version, PROTO_HEADER_VERSION);
+
+        // The fingerprint identifies the descriptor in the manifest
+        let manifest =
+            std::fs::read_to_string(output_dir.join("zerobus/descriptors/manifest.json")).unwrap();
+        assert!(manifest.contains(&header.descriptor_fingerprint));
+
+        // Records follow the header
+        assert_eq!(&bytes[PROTO_HEADER_LEN..], &[0x08, 0x01, b'\n']);
+
+        // Files without a header are rejected
+        assert!(parse_proto_header(&bytes[PROTO_HEADER_LEN..]).is_err());
+        let mut wrong_version = bytes.clone();
+        wrong_version[PROTO_HEADER_MAGIC.len()] = PROTO_HEADER_VERSION + 1;
+        assert!(parse_proto_header(&wrong_version).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_write_protobuf_header_before_any_descriptor() {
+        let temp_dir = TempDir::new().unwrap();
+        let writer = create_test_writer(&temp_dir).with_proto_header(true);
+
+        writer.write_protobuf(&[0x08, 0x01], true).await.unwrap();
+
+        let bytes = std::fs::read(temp_dir.path().join("zerobus/proto/test_table.proto")).unwrap();
+        let header = parse_proto_header(&bytes).unwrap();
+        assert_eq!(header.descriptor_fingerprint, UNKNOWN_FINGERPRINT);
+        assert_eq!(&bytes[PROTO_HEADER_LEN..], &[0x08, 0x01, b'\n']);
+    }
+
+    #[tokio::test]
+    async fn test_write_descriptor_writes_proto3_file_descriptor_with_package() {
+        let temp_dir = TempDir::new().unwrap();
+        let output_dir = temp_dir.path().to_path_buf();
+
+        let writer = DebugWriter::new(
+            output_dir.clone(),
+            "test.table".to_string(),
+            Duration::from_secs(5),
+            None,
+            Some(10),
+        )
+        .unwrap()
+        .with_proto_package(Some("com.example.events".to_string()));
+
+        let schema = Schema::new(vec![Field::new("id", DataType::Int64, false)]);
+        let descriptor = crate::wrapper::conversion::generate_protobuf_descriptor(&schema).unwrap();
+        writer
+            .write_descriptor("test.table", &descriptor)
+            .await
+            .unwrap();
+
+        let bytes = std::fs::read(output_dir.join("zerobus/descriptors/test_table.pb")).unwrap();
+        let file = prost_types::FileDescriptorProto::decode(bytes.as_slice()).unwrap();
+        assert_eq!(file.package.as_deref(), Some("com.example.events"));
+        assert_eq!(file.syntax.as_deref(), Some("proto3"));
+        assert_eq!(file.message_type, vec![descriptor]);
+    }
+}
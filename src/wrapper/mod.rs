@@ -8,11 +8,18 @@ pub mod conversion;
 pub mod debug;
 pub mod protobuf_serialization;
 pub mod retry;
+pub mod table_schema;
+pub mod wal;
 pub mod zerobus;
 
-use crate::config::WrapperConfiguration;
+pub use zerobus::{BackoffKind, BackoffObserver, BackoffStateSnapshot, BackoffStatus};
+
+use crate::config::{
+    DeliveryMode, FieldNameTransform, OversizedBatchPolicy, SchemaEvolution, WrapperConfiguration,
+};
 use crate::error::ZerobusError;
 use crate::observability::ObservabilityManager;
+use crate::wrapper::conversion::{DescriptorFieldMaps, TypeMapping};
 use crate::wrapper::retry::RetryConfig;
 use arrow::record_batch::RecordBatch;
 use secrecy::ExposeSecret;
@@ -22,10 +29,397 @@ use tracing::{debug, error, info, warn};
 
 /// Internal result from send_batch_internal containing per-row error information
 struct BatchTransmissionResult {
-    /// Successful row indices
+    /// Successful row indices (left empty when a `RowResultSink` reports them instead)
     successful_rows: Vec<usize>,
+    /// Number of successful rows
+    successful_count: usize,
     /// Failed rows with errors
     failed_rows: Vec<(usize, ZerobusError)>,
+    /// Failed rows counted but not stored in `failed_rows` (`max_tracked_failures`)
+    untracked_failures: usize,
+    /// Serialized size of each converted row (only when `track_row_sizes` is enabled)
+    row_sizes: Option<Vec<(usize, usize)>>,
+    /// Serialized bytes of rows that failed transmission (only when
+    /// `capture_failed_bytes` is enabled)
+    failed_row_bytes: Option<Vec<(usize, Vec<u8>)>>,
+    /// Acknowledgment ids of successful rows (`None` when nothing was sent)
+    ack_ids: Option<Vec<(usize, i64)>>,
+    /// The stream closed on the batch's first record in the last attempt, and no row was
+    /// acknowledged (see `descriptor_auto_recovery`)
+    closed_on_first_record: bool,
+}
+
+/// Whether the oldest buffered record has waited at least `max_age` (`max_buffer_age`)
+///
+/// Measured with tokio's clock, so a paused runtime can drive it.
+fn buffer_age_exceeded(
+    oldest_buffered_at: Option<tokio::time::Instant>,
+    max_age: Option<std::time::Duration>,
+) -> bool {
+    match (oldest_buffered_at, max_age) {
+        (Some(buffered_at), Some(max_age)) => buffered_at.elapsed() >= max_age,
+        _ => false,
+    }
+}
+
+/// Keep at most `max_tracked` of `failed_rows`, returning how many were dropped
+///
+/// Dropped failures are only counted (see `WrapperConfiguration::max_tracked_failures`).
+fn cap_tracked_failures(
+    failed_rows: &mut Vec<(usize, ZerobusError)>,
+    max_tracked: Option<usize>,
+) -> usize {
+    match max_tracked {
+        Some(max) if failed_rows.len() > max => {
+            let dropped = failed_rows.len() - max;
+            failed_rows.truncate(max);
+            failed_rows.shrink_to_fit();
+            dropped
+        }
+        _ => 0,
+    }
+}
+
+/// Decide whether pending ingest futures must be drained before buffering the next record
+///
+/// Evaluated *before* a record is handed to the stream, so the in-flight buffer never holds
+/// more than `max_pending` futures and never more than `max_bytes` of payload (unless a single
+/// record is larger than `max_bytes` on its own). An empty buffer is never drained.
+fn should_drain_pending_futures(
+    pending: usize,
+    buffered_bytes: usize,
+    next_record_bytes: usize,
+    max_pending: usize,
+    max_bytes: usize,
+) -> bool {
+    pending > 0
+        && (pending >= max_pending || buffered_bytes.saturating_add(next_record_bytes) > max_bytes)
+}
+
+/// Capacity of the channel backing `send_batch_streaming_results`
+///
+/// Bounded so a slow consumer applies backpressure to the send loop instead of
+/// letting undelivered results pile up in memory.
+const ROW_RESULT_CHANNEL_CAPACITY: usize = 1024;
+
+/// Outcome of a single row, yielded by [`ZerobusWrapper::send_batch_streaming_results`]
+/// and [`PendingAcks::next_ack`]
+#[derive(Debug, Clone)]
+pub struct RowResult {
+    /// Row index in the original batch (0-based)
+    pub row_idx: usize,
+    /// `Ok(())` once the row is acknowledged by Zerobus, or the error that made it fail
+    pub outcome: Result<(), ZerobusError>,
+}
+
+/// Handle to a batch submitted with [`ZerobusWrapper::submit_batch`]
+///
+/// Acknowledgments are collected by a background task while the caller carries on.
+/// Row outcomes can be consumed as they become final with `next_ack`, and `wait` returns
+/// the batch's `TransmissionResult` once every row is final. Outcomes not consumed are
+/// kept until the handle is dropped. Dropping the handle does not cancel the send.
+pub struct PendingAcks {
+    results: tokio::sync::mpsc::Receiver<RowResult>,
+    task: tokio::task::JoinHandle<Result<TransmissionResult, ZerobusError>>,
+}
+
+impl PendingAcks {
+    /// Wait for the next row outcome to become final
+    ///
+    /// Each row is reported exactly once, in acknowledgment order.
+    ///
+    /// # Returns
+    ///
+    /// The next `RowResult`, or `None` once every row has been reported.
+    pub async fn next_ack(&mut self) -> Option<RowResult> {
+        self.results.recv().await
+    }
+
+    /// Wait for every acknowledgment and return the batch result
+    ///
+    /// # Returns
+    ///
+    /// The same `TransmissionResult` `send_batch_with_descriptor` would have returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors `send_batch_with_descriptor` returns, or `TransmissionError` if
+    /// the background task did not complete.
+    pub async fn wait(self) -> Result<TransmissionResult, ZerobusError> {
+        self.task.await.map_err(|e| {
+            ZerobusError::TransmissionError(format!("Background send task failed: {}", e))
+        })?
+    }
+}
+
+/// What a [`ZerobusWrapper::flush_with_outcome`] call actually flushed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlushOutcome {
+    /// Whether a Zerobus stream existed and was flushed
+    ///
+    /// `false` in writer disabled mode and before the first `send_batch` has opened a
+    /// stream: nothing was sent to Zerobus.
+    pub stream_flushed: bool,
+    /// Whether debug files were flushed (`false` if debug output is off or its flush failed)
+    pub debug_flushed: bool,
+}
+
+/// Outcome of a [`ZerobusWrapper::send_batch_with_token`] call
+#[derive(Debug, Clone)]
+pub enum TokenSendOutcome {
+    /// The batch was sent, with this result
+    Sent(Box<TransmissionResult>),
+    /// A batch with the same token was already fully acknowledged, so nothing was sent
+    Duplicate,
+}
+
+/// Maximum number of idempotency tokens a wrapper remembers (oldest are forgotten first)
+const MAX_IDEMPOTENCY_TOKENS: usize = 10_000;
+
+/// Idempotency tokens of fully acknowledged batches, in acknowledgment order
+#[derive(Debug, Default)]
+struct AcknowledgedTokens {
+    order: std::collections::VecDeque<String>,
+    tokens: std::collections::HashSet<String>,
+}
+
+impl AcknowledgedTokens {
+    fn contains(&self, token: &str) -> bool {
+        self.tokens.contains(token)
+    }
+
+    /// Remember `token`, forgetting the oldest tokens beyond `MAX_IDEMPOTENCY_TOKENS`
+    fn insert(&mut self, token: String) {
+        if !self.tokens.insert(token.clone()) {
+            return;
+        }
+        self.order.push_back(token);
+        while self.order.len() > MAX_IDEMPOTENCY_TOKENS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.tokens.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// What a [`ZerobusWrapper::health_check`] checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthCheckMode {
+    /// Backoff state, the SDK client and Unity Catalog credentials were checked
+    Live,
+    /// Writer disabled mode: nothing is sent, so there is nothing to connect to
+    Disabled,
+}
+
+impl HealthCheckMode {
+    /// Lowercase name of the mode (`"live"` or `"disabled"`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HealthCheckMode::Live => "live",
+            HealthCheckMode::Disabled => "disabled",
+        }
+    }
+}
+
+/// Outcome of a [`ZerobusWrapper::health_check`]
+#[derive(Debug, Clone)]
+pub struct HealthStatus {
+    /// Whether the wrapper can currently send
+    pub healthy: bool,
+    /// Time the check took in milliseconds (`None` in writer disabled mode)
+    pub latency_ms: Option<u64>,
+    /// Why the wrapper is unhealthy, if it is
+    pub error: Option<ZerobusError>,
+    /// What was checked
+    pub mode: HealthCheckMode,
+}
+
+/// A batch already converted to Protobuf, produced by [`ZerobusWrapper::prepare_batch`]
+///
+/// Holds the serialized rows and the rows that failed conversion, so the batch can be
+/// sent with [`ZerobusWrapper::send_prepared`] without converting it again. Conversion
+/// is CPU-bound and sending is I/O-bound; preparing separately lets callers run them on
+/// different pools.
+#[derive(Debug, Clone)]
+pub struct PreparedBatch {
+    /// Descriptor the rows were encoded with
+    descriptor: prost_types::DescriptorProto,
+    /// Serialized Protobuf bytes of each converted row, with its row index
+    successful_bytes: Vec<(usize, Vec<u8>)>,
+    /// Rows that failed conversion, with their errors
+    failed_rows: Vec<(usize, ZerobusError)>,
+    /// Rows that failed conversion beyond `max_tracked_failures`, counted but not stored
+    untracked_failures: usize,
+    /// Number of rows in the source batch
+    total_rows: usize,
+    /// In-memory size of the source batch in bytes
+    batch_size_bytes: usize,
+    /// The source batch's `error_key_column`, naming failed rows in their errors
+    error_keys: Option<arrow::array::ArrayRef>,
+}
+
+impl PreparedBatch {
+    /// Protobuf descriptor the rows were encoded with
+    pub fn descriptor(&self) -> &prost_types::DescriptorProto {
+        &self.descriptor
+    }
+
+    /// Serialized Protobuf bytes of each successfully converted row, in row order
+    pub fn successful_bytes(&self) -> &[(usize, Vec<u8>)] {
+        &self.successful_bytes
+    }
+
+    /// Rows that failed conversion, with their errors
+    ///
+    /// Holds at most `max_tracked_failures` rows; see `failed_count` for the total.
+    pub fn failed_rows(&self) -> &[(usize, ZerobusError)] {
+        &self.failed_rows
+    }
+
+    /// Number of rows that failed conversion, including those not stored in `failed_rows`
+    pub fn failed_count(&self) -> usize {
+        self.failed_rows.len() + self.untracked_failures
+    }
+
+    /// Number of rows in the source batch
+    pub fn total_rows(&self) -> usize {
+        self.total_rows
+    }
+
+    /// In-memory size of the source batch in bytes
+    pub fn batch_size_bytes(&self) -> usize {
+        self.batch_size_bytes
+    }
+}
+
+/// Number of row errors quoted in the error of a batch failed by `conversion_errors_fatal`
+const FATAL_CONVERSION_ERROR_SAMPLES: usize = 3;
+
+/// Fail a prepared batch that has conversion failures (`conversion_errors_fatal`)
+///
+/// The error counts the failed rows and quotes the first few row errors.
+fn fatal_conversion_errors(prepared: &PreparedBatch) -> Result<(), ZerobusError> {
+    if prepared.failed_count() == 0 {
+        return Ok(());
+    }
+    let samples: Vec<String> = prepared
+        .failed_rows
+        .iter()
+        .take(FATAL_CONVERSION_ERROR_SAMPLES)
+        .map(|(idx, e)| format!("row {}: {}", idx, e))
+        .collect();
+    Err(ZerobusError::ConversionError(format!(
+        "{} of {} rows failed conversion and conversion errors are fatal; first errors: {}",
+        prepared.failed_count(),
+        prepared.total_rows,
+        samples.join("; ")
+    )))
+}
+
+/// Delivers per-row outcomes to a `send_batch_streaming_results` consumer as they become final
+///
+/// Rows are re-sent when a stream is recreated, so each row is reported at most once:
+/// an acknowledgment is final, and failures are only reported once retries are exhausted.
+struct RowResultSink {
+    tx: tokio::sync::mpsc::Sender<RowResult>,
+    reported: std::sync::Mutex<Vec<bool>>,
+    /// Signalled once every row has been handed to the stream (see `submit_batch`)
+    submitted: std::sync::Mutex<Option<tokio::sync::oneshot::Sender<()>>>,
+    /// Whether the batch's `TransmissionResult` still lists its rows; otherwise only the
+    /// sink reports them, and per-row result vectors are not built
+    keep_rows: bool,
+}
+
+impl RowResultSink {
+    fn new(tx: tokio::sync::mpsc::Sender<RowResult>, num_rows: usize) -> Self {
+        Self {
+            tx,
+            reported: std::sync::Mutex::new(vec![false; num_rows]),
+            submitted: std::sync::Mutex::new(None),
+            keep_rows: false,
+        }
+    }
+
+    /// Also list the rows in the batch's `TransmissionResult` (see `submit_batch`)
+    fn keeping_rows(self) -> Self {
+        Self {
+            keep_rows: true,
+            ..self
+        }
+    }
+
+    /// Signal `submitted` the first time every row has been handed to the stream
+    fn with_submission_signal(self, submitted: tokio::sync::oneshot::Sender<()>) -> Self {
+        Self {
+            submitted: std::sync::Mutex::new(Some(submitted)),
+            ..self
+        }
+    }
+
+    /// Signal that every row has been submitted (only the first call has an effect)
+    fn mark_submitted(&self) {
+        let submitted = self
+            .submitted
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take();
+        if let Some(submitted) = submitted {
+            let _ = submitted.send(());
+        }
+    }
+
+    /// Report a row outcome unless the row was already reported
+    async fn report(&self, row_idx: usize, outcome: Result<(), ZerobusError>) {
+        {
+            let mut reported = self.reported.lock().unwrap_or_else(|poisoned| {
+                warn!("Row result tracking mutex was poisoned, recovering");
+                poisoned.into_inner()
+            });
+            match reported.get_mut(row_idx) {
+                Some(done) if !*done => *done = true,
+                _ => return,
+            }
+        }
+        // Consumer may have dropped the stream; the send itself must still complete
+        let _ = self.tx.send(RowResult { row_idx, outcome }).await;
+    }
+
+    /// Report `error` for every row that has no outcome yet
+    async fn report_remaining(&self, error: &ZerobusError) {
+        let remaining: Vec<usize> = {
+            let reported = self.reported.lock().unwrap_or_else(|poisoned| {
+                warn!("Row result tracking mutex was poisoned, recovering");
+                poisoned.into_inner()
+            });
+            reported
+                .iter()
+                .enumerate()
+                .filter(|(_, done)| !**done)
+                .map(|(idx, _)| idx)
+                .collect()
+        };
+        for row_idx in remaining {
+            self.report(row_idx, Err(error.clone())).await;
+        }
+    }
+}
+
+/// Error to report for rows a send left without an outcome in its `RowResultSink`
+///
+/// Batch-level errors reach no row. Failures beyond `max_tracked_failures` lost their
+/// errors before they could be reported, so they get a generic one.
+fn unreported_rows_error(
+    result: &Result<TransmissionResult, ZerobusError>,
+) -> Option<ZerobusError> {
+    match result {
+        Ok(result) if result.error.is_none() && result.failures_truncated => {
+            Some(ZerobusError::ConversionError(
+                "Row failed; its error was not kept (max_tracked_failures)".to_string(),
+            ))
+        }
+        Ok(result) => result.error.clone(),
+        Err(e) => Some(e.clone()),
+    }
 }
 
 /// Result of a data transmission operation
@@ -57,11 +451,21 @@ struct BatchTransmissionResult {
 ///   - `Some(vec![...])` for successful rows
 /// - **`total_rows`**: Total number of rows in the batch (0 for empty batches)
 /// - **`successful_count`**: Number of rows that succeeded (always equals `successful_rows.len()` if `Some`)
-/// - **`failed_count`**: Number of rows that failed (always equals `failed_rows.len()` if `Some`)
+/// - **`failed_count`**: Number of rows that failed (equals `failed_rows.len()` if `Some`, unless `failures_truncated`)
+/// - **`failures_truncated`**: Whether failures beyond `WrapperConfiguration::max_tracked_failures` were only counted
+/// - **`row_sizes`**: Serialized Protobuf size per converted row
+///   - `None` unless `WrapperConfiguration::track_row_sizes` is enabled
+/// - **`failed_row_bytes`**: Serialized Protobuf bytes of rows that failed transmission
+///   - `None` unless `WrapperConfiguration::capture_failed_bytes` is enabled
+///
+/// Implements `Default` (an empty, unsuccessful result), so code building results can
+/// fill in the fields it needs with `..Default::default()` and keep compiling as fields
+/// are added.
 ///
 /// # Edge Cases
 ///
 /// - **Empty batch** (`total_rows == 0`): Returns `success=true`, `successful_count=0`, `failed_count=0`
+///   - Nothing is transmitted, but the descriptor is still resolved and written in debug mode
 /// - **Batch-level errors**: Authentication/connection errors before processing return `error=Some(...)`, `failed_rows=None`
 /// - **All rows failed**: Returns `success=false`, `failed_rows=Some([...])`, `successful_rows=None`
 /// - **All rows succeeded**: Returns `success=true`, `failed_rows=None`, `successful_rows=Some([...])`
@@ -102,7 +506,7 @@ struct BatchTransmissionResult {
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct TransmissionResult {
     /// Whether transmission succeeded
     ///
@@ -148,8 +552,43 @@ pub struct TransmissionResult {
     pub successful_count: usize,
     /// Number of rows that failed
     ///
-    /// Always equals `failed_rows.len()` if `failed_rows` is `Some`.
+    /// Equals `failed_rows.len()` if `failed_rows` is `Some`, unless `failures_truncated`
+    /// is set.
     pub failed_count: usize,
+    /// Serialized Protobuf size of each successfully converted row
+    ///
+    /// - `None` unless `WrapperConfiguration::track_row_sizes` is enabled
+    /// - `Some(vec![(row_idx, size_bytes), ...])` otherwise, in row order
+    ///
+    /// Rows that failed conversion have no entry. Useful for spotting rows
+    /// approaching the 4MB per-record Zerobus limit.
+    pub row_sizes: Option<Vec<(usize, usize)>>,
+    /// Serialized Protobuf bytes of each row that failed transmission
+    ///
+    /// - `None` unless `WrapperConfiguration::capture_failed_bytes` is enabled
+    /// - `Some(vec![(row_idx, bytes), ...])` otherwise, sorted by row index
+    ///
+    /// These are the exact payloads Zerobus rejected, for decoding offline. Rows that
+    /// failed conversion have no bytes and no entry.
+    pub failed_row_bytes: Option<Vec<(usize, Vec<u8>)>>,
+    /// Zerobus acknowledgment id of each successfully written row
+    ///
+    /// - `None` if nothing was sent to Zerobus (writer disabled mode, batch-level error)
+    /// - `Some(vec![(row_idx, ack_id), ...])` otherwise, sorted by row index
+    pub ack_ids: Option<Vec<(usize, i64)>>,
+    /// Result of sending the same batch to the mirror table
+    ///
+    /// - `None` unless `WrapperConfiguration::mirror_table_name` is set
+    /// - `Some(result)` with the mirror table's own outcome otherwise
+    ///
+    /// The other fields always describe the primary table. A mirror failure only affects
+    /// `success`/`error` when `WrapperConfiguration::mirror_failures_fatal` is enabled.
+    pub mirror_result: Option<Box<TransmissionResult>>,
+    /// Whether some failed rows are counted in `failed_count` but not listed in `failed_rows`
+    ///
+    /// Only set when `WrapperConfiguration::max_tracked_failures` capped the stored
+    /// failures; `failed_rows` then holds the failures with the lowest row indices.
+    pub failures_truncated: bool,
 }
 
 impl TransmissionResult {
@@ -197,12 +636,39 @@ impl TransmissionResult {
         self.successful_rows.clone().unwrap_or_default()
     }
 
+    /// Get the key of each failed row from the original batch
+    ///
+    /// # Arguments
+    ///
+    /// * `original_batch` - The original RecordBatch that was sent
+    /// * `key_column` - Name of the key column (e.g. the configured `error_key_column`)
+    ///
+    /// # Returns
+    ///
+    /// Returns `(row_idx, key)` for each failed row, in the order of `failed_rows`. The key
+    /// is `None` if it is null, or if the batch has no `key_column`.
+    pub fn failed_row_keys(
+        &self,
+        original_batch: &RecordBatch,
+        key_column: &str,
+    ) -> Vec<(usize, Option<String>)> {
+        let keys = original_batch.column_by_name(key_column);
+        self.get_failed_row_indices()
+            .into_iter()
+            .map(|idx| (idx, keys.and_then(|keys| key_value(keys, idx))))
+            .collect()
+    }
+
     /// Extract a RecordBatch containing only the failed rows from the original batch
     ///
     /// # Arguments
     ///
     /// * `original_batch` - The original RecordBatch that was sent
     ///
+    /// With `WrapperConfiguration::with_batch_transform`, row indices refer to the
+    /// transformed batch. They still match the original batch as long as the transform
+    /// only changes columns, not rows.
+    ///
     /// # Returns
     ///
     /// Returns `Some(RecordBatch)` containing only the failed rows, or `None` if there are no failed rows.
@@ -246,6 +712,10 @@ impl TransmissionResult {
     ///
     /// * `original_batch` - The original RecordBatch that was sent
     ///
+    /// With `WrapperConfiguration::with_batch_transform`, row indices refer to the
+    /// transformed batch. They still match the original batch as long as the transform
+    /// only changes columns, not rows.
+    ///
     /// # Returns
     ///
     /// Returns `Some(RecordBatch)` containing only the successful rows, or `None` if there are no successful rows.
@@ -324,15 +794,7 @@ impl TransmissionResult {
 
         if let Some(failed_rows) = &self.failed_rows {
             for (row_idx, error) in failed_rows {
-                let error_type = match error {
-                    ZerobusError::ConfigurationError(_) => "ConfigurationError",
-                    ZerobusError::AuthenticationError(_) => "AuthenticationError",
-                    ZerobusError::ConnectionError(_) => "ConnectionError",
-                    ZerobusError::ConversionError(_) => "ConversionError",
-                    ZerobusError::TransmissionError(_) => "TransmissionError",
-                    ZerobusError::RetryExhausted(_) => "RetryExhausted",
-                    ZerobusError::TokenRefreshError(_) => "TokenRefreshError",
-                };
+                let error_type = error.error_type();
                 grouped
                     .entry(error_type.to_string())
                     .or_default()
@@ -350,44 +812,7 @@ impl TransmissionResult {
     /// Returns an `ErrorStatistics` struct containing comprehensive error analysis
     /// including success/failure rates and error type counts.
     pub fn get_error_statistics(&self) -> ErrorStatistics {
-        let success_rate = if self.total_rows > 0 {
-            self.successful_count as f64 / self.total_rows as f64
-        } else {
-            0.0
-        };
-
-        let failure_rate = if self.total_rows > 0 {
-            self.failed_count as f64 / self.total_rows as f64
-        } else {
-            0.0
-        };
-
-        let mut error_type_counts: std::collections::HashMap<String, usize> =
-            std::collections::HashMap::new();
-
-        if let Some(failed_rows) = &self.failed_rows {
-            for (_, error) in failed_rows {
-                let error_type = match error {
-                    ZerobusError::ConfigurationError(_) => "ConfigurationError",
-                    ZerobusError::AuthenticationError(_) => "AuthenticationError",
-                    ZerobusError::ConnectionError(_) => "ConnectionError",
-                    ZerobusError::ConversionError(_) => "ConversionError",
-                    ZerobusError::TransmissionError(_) => "TransmissionError",
-                    ZerobusError::RetryExhausted(_) => "RetryExhausted",
-                    ZerobusError::TokenRefreshError(_) => "TokenRefreshError",
-                };
-                *error_type_counts.entry(error_type.to_string()).or_insert(0) += 1;
-            }
-        }
-
-        ErrorStatistics {
-            total_rows: self.total_rows,
-            successful_count: self.successful_count,
-            failed_count: self.failed_count,
-            success_rate,
-            failure_rate,
-            error_type_counts,
-        }
+        ErrorStatistics::aggregate(std::slice::from_ref(self))
     }
 
     /// Get all error messages from failed rows
@@ -401,6 +826,91 @@ impl TransmissionResult {
             .map(|rows| rows.iter().map(|(_, error)| error.to_string()).collect())
             .unwrap_or_default()
     }
+
+    /// Get the outcome of every processed row in one list
+    ///
+    /// Unifies `successful_rows` and `failed_rows`, attaching the acknowledgment id
+    /// (`ack_ids`) and serialized size (`row_sizes`) to successful rows where known.
+    /// Failures are attributed to the conversion phase for `ConversionError`s and to the
+    /// transmission phase otherwise.
+    ///
+    /// # Returns
+    ///
+    /// Returns one `RowOutcome` per processed row, sorted by row index. Empty for a
+    /// batch-level error, where no rows were processed.
+    pub fn row_outcomes(&self) -> Vec<RowOutcome> {
+        let ack_ids: std::collections::HashMap<usize, i64> =
+            self.ack_ids.iter().flatten().copied().collect();
+        let row_sizes: std::collections::HashMap<usize, usize> =
+            self.row_sizes.iter().flatten().copied().collect();
+
+        let succeeded = self
+            .successful_rows
+            .iter()
+            .flatten()
+            .map(|&row_idx| RowOutcome {
+                row_idx,
+                status: RowStatus::Succeeded {
+                    ack_id: ack_ids.get(&row_idx).copied(),
+                    bytes: row_sizes.get(&row_idx).copied(),
+                },
+            });
+        let failed = self
+            .failed_rows
+            .iter()
+            .flatten()
+            .map(|(row_idx, error)| RowOutcome {
+                row_idx: *row_idx,
+                status: RowStatus::Failed {
+                    error: error.clone(),
+                    phase: match error {
+                        ZerobusError::ConversionError(_) => FailurePhase::Conversion,
+                        _ => FailurePhase::Transmission,
+                    },
+                },
+            });
+
+        let mut outcomes: Vec<RowOutcome> = succeeded.chain(failed).collect();
+        outcomes.sort_by_key(|outcome| outcome.row_idx);
+        outcomes
+    }
+}
+
+/// Outcome of a single row, returned by [`TransmissionResult::row_outcomes`]
+#[derive(Debug, Clone)]
+pub struct RowOutcome {
+    /// Row index in the original batch (0-based)
+    pub row_idx: usize,
+    /// Whether the row was written, with per-row details
+    pub status: RowStatus,
+}
+
+/// Whether a row was written, see [`RowOutcome`]
+#[derive(Debug, Clone)]
+pub enum RowStatus {
+    /// The row was written (or converted, in writer disabled mode)
+    Succeeded {
+        /// Zerobus acknowledgment id (`None` when nothing was sent to Zerobus)
+        ack_id: Option<i64>,
+        /// Serialized Protobuf size (`None` unless `track_row_sizes` is enabled)
+        bytes: Option<usize>,
+    },
+    /// The row failed
+    Failed {
+        /// Why the row failed
+        error: ZerobusError,
+        /// Where the row failed
+        phase: FailurePhase,
+    },
+}
+
+/// Stage at which a row failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailurePhase {
+    /// Arrow to Protobuf conversion
+    Conversion,
+    /// Sending to Zerobus
+    Transmission,
 }
 
 /// Error statistics for a transmission result
@@ -420,6 +930,53 @@ pub struct ErrorStatistics {
     pub error_type_counts: std::collections::HashMap<String, usize>,
 }
 
+impl ErrorStatistics {
+    /// Compute error statistics across several transmission results
+    ///
+    /// Row counts and error type counts are summed, and the rates are computed over the
+    /// combined row count, so large batches weigh more than small ones. Mirror results
+    /// are not included; aggregate them separately if needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `results` - Transmission results to combine, e.g. every result of a batch job
+    ///
+    /// # Returns
+    ///
+    /// Returns the combined statistics; rates are 0.0 when there are no rows.
+    pub fn aggregate(results: &[TransmissionResult]) -> Self {
+        let mut error_type_counts: std::collections::HashMap<String, usize> =
+            std::collections::HashMap::new();
+        let (mut total_rows, mut successful_count, mut failed_count) = (0, 0, 0);
+        for result in results {
+            total_rows += result.total_rows;
+            successful_count += result.successful_count;
+            failed_count += result.failed_count;
+            for (_, error) in result.failed_rows.iter().flatten() {
+                *error_type_counts
+                    .entry(error.error_type().to_string())
+                    .or_insert(0) += 1;
+            }
+        }
+
+        let rate = |count: usize| {
+            if total_rows > 0 {
+                count as f64 / total_rows as f64
+            } else {
+                0.0
+            }
+        };
+        ErrorStatistics {
+            total_rows,
+            successful_count,
+            failed_count,
+            success_rate: rate(successful_count),
+            failure_rate: rate(failed_count),
+            error_type_counts,
+        }
+    }
+}
+
 /// Main wrapper for sending data to Zerobus
 ///
 /// Thread-safe wrapper that handles Arrow RecordBatch to Protobuf conversion,
@@ -427,18 +984,42 @@ pub struct ErrorStatistics {
 pub struct ZerobusWrapper {
     /// Configuration (immutable)
     config: Arc<WrapperConfiguration>,
+    /// Zerobus endpoint after trimming and validation
+    endpoint: Arc<str>,
     /// Zerobus SDK instance (thread-safe)
     sdk: Arc<Mutex<Option<databricks_zerobus_ingest_sdk::ZerobusSdk>>>,
     /// Active stream (lazy initialization)
     stream: Arc<Mutex<Option<databricks_zerobus_ingest_sdk::ZerobusStream>>>,
+    /// Descriptor the table's schema has evolved to (`SchemaEvolution::AddOnly` only)
+    active_descriptor: Arc<std::sync::Mutex<Option<prost_types::DescriptorProto>>>,
+    /// Field lookup maps of the most recently used descriptor
+    field_maps: Arc<std::sync::Mutex<Option<Arc<DescriptorFieldMaps>>>>,
+    /// Type mapping of generated descriptors (changed by `descriptor_auto_recovery` only)
+    type_mapping: Arc<std::sync::Mutex<TypeMapping>>,
     /// Retry configuration
     retry_config: RetryConfig,
+    /// Retry configuration for SDK/stream creation (None = covered by `retry_config`)
+    connect_retry_config: Option<RetryConfig>,
+    /// Backoff before recreating a stream that closed mid-batch
+    stream_recreate_backoff: RetryConfig,
     /// Observability manager (optional)
     observability: Option<ObservabilityManager>,
     /// Debug writer (optional)
     debug_writer: Option<Arc<crate::wrapper::debug::DebugWriter>>,
-    /// Track if we've written the descriptor for this table (once per table)
-    descriptor_written: Arc<tokio::sync::Mutex<bool>>,
+    /// Wrapper for the mirror table (optional, see `WrapperConfiguration::mirror_table_name`)
+    mirror: Option<Arc<ZerobusWrapper>>,
+    /// Byte budget shared by concurrent sends, one permit per byte (optional)
+    memory_budget: Option<Arc<tokio::sync::Semaphore>>,
+    /// When the last batch finished sending on the stream (for `idle_stream_timeout`)
+    last_send: Arc<std::sync::Mutex<Option<tokio::time::Instant>>>,
+    /// Batches currently sending on the stream (an idle stream is only closed with none)
+    sends_in_flight: Arc<std::sync::atomic::AtomicUsize>,
+    /// Cumulative send metrics for `metrics_prometheus`
+    metrics: Arc<crate::observability::prometheus::SendMetrics>,
+    /// Tokens of batches acknowledged by `send_batch_with_token`
+    acknowledged_tokens: Arc<std::sync::Mutex<AcknowledgedTokens>>,
+    /// Write-ahead log of unacknowledged records (optional, see `WrapperConfiguration::wal_dir`)
+    wal: Option<Arc<crate::wrapper::wal::WriteAheadLog>>,
 }
 
 impl ZerobusWrapper {
@@ -504,6 +1085,18 @@ impl ZerobusWrapper {
         // Validate configuration
         config.validate()?;
 
+        #[cfg(not(feature = "parallel"))]
+        if config.conversion_parallelism > 1 {
+            warn!(
+                "conversion_parallelism is {} but the `parallel` feature is not enabled; rows will be converted sequentially",
+                config.conversion_parallelism
+            );
+        }
+
+        if let Some(observer) = &config.backoff_observer {
+            zerobus::register_backoff_observer(&config.table_name, observer);
+        }
+
         // Validate and normalize endpoint (required for both enabled and disabled modes)
         let normalized_endpoint = Self::validate_and_normalize_endpoint(&config.zerobus_endpoint)?;
 
@@ -548,11 +1141,36 @@ impl ZerobusWrapper {
             config.retry_max_attempts,
             config.retry_base_delay_ms,
             config.retry_max_delay_ms,
+        )
+        .with_error_patterns(
+            config.retryable_error_patterns.clone(),
+            config.fatal_error_patterns.clone(),
+        );
+
+        let connect_retry_config = config.connect_retry_max_attempts.map(|max_attempts| {
+            RetryConfig::new(
+                max_attempts,
+                config.connect_retry_base_delay_ms,
+                config.connect_retry_max_delay_ms,
+            )
+            .with_error_patterns(
+                config.retryable_error_patterns.clone(),
+                config.fatal_error_patterns.clone(),
+            )
+        });
+
+        // Only the delays are used; the attempts are bounded by the recreation loop
+        let stream_recreate_backoff = RetryConfig::new(
+            1,
+            config.stream_recreate_base_delay_ms,
+            config.stream_recreate_max_delay_ms,
         );
 
         // Initialize observability if enabled
         let observability = if config.observability_enabled {
-            ObservabilityManager::new_async(config.observability_config.clone()).await
+            ObservabilityManager::new_async(config.observability_config.clone())
+                .await
+                .map(|manager| manager.with_namespace(config.telemetry_namespace.as_deref()))
         } else {
             None
         };
@@ -590,7 +1208,13 @@ impl ZerobusWrapper {
                     Duration::from_secs(config.debug_flush_interval_secs),
                     config.debug_max_file_size,
                     config.debug_max_files_retained,
-                ) {
+                )
+                .map(|writer| {
+                    writer
+                        .with_proto_package(config.proto_package.clone())
+                        .with_proto_header(config.debug_proto_header)
+                        .with_global_max_files(config.debug_global_max_files)
+                }) {
                     Ok(writer) => {
                         info!(
                             "Debug file output enabled: {} (Arrow: {}, Protobuf: {})",
@@ -628,15 +1252,67 @@ impl ZerobusWrapper {
             None
         };
 
-        Ok(Self {
+        let wal = match &config.wal_dir {
+            Some(dir) => {
+                info!("Write-ahead log enabled: {}", dir.display());
+                Some(Arc::new(crate::wrapper::wal::WriteAheadLog::new(
+                    dir.clone(),
+                    &config.table_name,
+                )?))
+            }
+            None => None,
+        };
+
+        let memory_budget = config
+            .memory_budget_bytes
+            .map(|bytes| Arc::new(tokio::sync::Semaphore::new(bytes)));
+
+        // Initialize mirror table wrapper for dual-write if configured
+        let mirror = match config.mirror_configuration() {
+            Some(mirror_config) => {
+                info!("Mirroring batches to table: {}", mirror_config.table_name);
+                let mut mirror = Box::pin(Self::new(mirror_config)).await?;
+                // Mirror sends count against the same budget as primary sends
+                mirror.memory_budget = memory_budget.clone();
+                Some(Arc::new(mirror))
+            }
+            None => None,
+        };
+
+        let wrapper = Self {
             config: Arc::new(config),
+            endpoint: normalized_endpoint.into(),
             sdk,
             stream: Arc::new(Mutex::new(None)),
+            active_descriptor: Arc::new(std::sync::Mutex::new(None)),
+            field_maps: Arc::new(std::sync::Mutex::new(None)),
+            type_mapping: Arc::new(std::sync::Mutex::new(TypeMapping::default())),
             retry_config,
+            connect_retry_config,
+            stream_recreate_backoff,
             observability,
             debug_writer,
-            descriptor_written: Arc::new(tokio::sync::Mutex::new(false)),
-        })
+            mirror,
+            memory_budget,
+            last_send: Arc::new(std::sync::Mutex::new(None)),
+            sends_in_flight: Arc::new(std::sync::atomic::AtomicUsize::new(0)),
+            metrics: Arc::new(Default::default()),
+            acknowledged_tokens: Arc::new(std::sync::Mutex::new(Default::default())),
+            wal,
+        };
+
+        // Catch descriptor/table drift before the first send closes the stream
+        if wrapper.config.validate_table_schema_on_startup {
+            match &wrapper.config.expected_schema {
+                Some(_) if wrapper.config.zerobus_writer_disabled => {
+                    info!("Writer disabled - skipping table schema validation");
+                }
+                Some(schema) => wrapper.validate_table_schema(schema).await?,
+                None => {}
+            }
+        }
+
+        Ok(wrapper)
     }
 
     /// Send a data batch to Zerobus
@@ -666,6 +1342,9 @@ impl ZerobusWrapper {
     /// Converts Arrow RecordBatch to Protobuf format and transmits to Zerobus
     /// with automatic retry on transient failures.
     ///
+    /// If a mirror table is configured, the batch is sent to it concurrently and its
+    /// outcome is reported in `TransmissionResult::mirror_result`.
+    ///
     /// # Arguments
     ///
     /// * `batch` - Arrow RecordBatch to send
@@ -684,908 +1363,3662 @@ impl ZerobusWrapper {
         batch: RecordBatch,
         descriptor: Option<prost_types::DescriptorProto>,
     ) -> Result<TransmissionResult, ZerobusError> {
-        let start_time = std::time::Instant::now();
-        let batch_size_bytes = batch.get_array_memory_size();
+        let batch = self.transform_batch(batch)?;
+        self.send_transformed_batch(batch, descriptor).await
+    }
 
-        debug!(
-            "Sending batch with {} rows, {} bytes",
-            batch.num_rows(),
-            batch_size_bytes
-        );
+    /// Send a data batch unless a batch with the same idempotency token was acknowledged
+    ///
+    /// Use this to make retries of the same logical batch safe: a caller that did not
+    /// see the outcome of a send (e.g. after a crash of its own retry logic) can resend
+    /// with the same token, and the batch is skipped if it already went through.
+    ///
+    /// # Guarantees
+    ///
+    /// Deduplication happens in the wrapper: the Zerobus SDK has no ingest metadata for
+    /// idempotency tokens, so they are not passed to the server. A token is remembered
+    /// once every row of its batch was acknowledged, by this wrapper and its clones, for
+    /// the wrapper's lifetime (up to the 10,000 most recent tokens). Tokens are not
+    /// persisted across restarts or shared between wrappers. A batch that partially
+    /// failed is not remembered, so resending it may duplicate its successful rows; resend
+    /// only `extract_failed_batch` under a new token instead. Concurrent sends with the
+    /// same token are not deduplicated against each other.
+    ///
+    /// Only acknowledged sends remember their token: with `zerobus_writer_disabled` or
+    /// `DeliveryMode::FireAndForget` nothing is acknowledged, so every batch is sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch` - Arrow RecordBatch to send
+    /// * `token` - Caller-chosen token identifying the logical batch
+    ///
+    /// # Returns
+    ///
+    /// Returns `TokenSendOutcome::Sent` with the transmission result, or
+    /// `TokenSendOutcome::Duplicate` if the token was already acknowledged.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors `send_batch` returns.
+    pub async fn send_batch_with_token(
+        &self,
+        batch: RecordBatch,
+        token: impl Into<String>,
+    ) -> Result<TokenSendOutcome, ZerobusError> {
+        let token = token.into();
+        if self
+            .acknowledged_tokens
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .contains(&token)
+        {
+            debug!(
+                "Skipping batch with already acknowledged idempotency token {}",
+                token
+            );
+            return Ok(TokenSendOutcome::Duplicate);
+        }
 
-        // Write Arrow batch to debug file if Arrow debug is enabled
-        if self.config.debug_arrow_enabled {
-            if let Some(ref debug_writer) = self.debug_writer {
-                if let Err(e) = debug_writer.write_arrow(&batch).await {
-                    warn!("Failed to write Arrow debug file: {}", e);
-                    // Don't fail the operation if debug writing fails
-                }
-            }
+        let result = self.send_batch(batch).await?;
+        if self.is_fully_acknowledged(&result) {
+            self.acknowledged_tokens
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .insert(token);
         }
+        Ok(TokenSendOutcome::Sent(Box::new(result)))
+    }
 
-        // Start observability span if enabled
-        let _span = self
-            .observability
-            .as_ref()
-            .map(|obs| obs.start_send_batch_span(&self.config.table_name));
-
-        // Use retry logic for transmission
-        let (result, attempts) = self
-            .retry_config
-            .execute_with_retry_tracked(|| {
-                let batch = batch.clone();
-                let descriptor = descriptor.clone();
-                let wrapper = self.clone();
-                async move { wrapper.send_batch_internal(batch, descriptor).await }
-            })
-            .await;
-
-        let latency_ms = start_time.elapsed().as_millis() as u64;
+    /// Whether every row of `result` was acknowledged by Zerobus
+    fn is_fully_acknowledged(&self, result: &TransmissionResult) -> bool {
+        !self.config.zerobus_writer_disabled
+            && self.config.delivery_mode == DeliveryMode::AtLeastOnce
+            && result.success
+            && result.failed_count == 0
+    }
 
-        // Record metrics if observability is enabled
-        if let Some(obs) = &self.observability {
-            let success = result.is_ok();
-            obs.record_batch_sent(batch_size_bytes, success, latency_ms)
-                .await;
+    /// Check the batch against the expected schema, then apply the batch transform, if any
+    fn transform_batch(&self, batch: RecordBatch) -> Result<RecordBatch, ZerobusError> {
+        self.check_expected_schema(&batch)?;
+        match &self.config.batch_transform {
+            Some(transform) => transform.apply(batch),
+            None => Ok(batch),
         }
+    }
 
-        let total_rows = batch.num_rows();
-
-        // Handle empty batch edge case
-        if total_rows == 0 {
-            return Ok(TransmissionResult {
-                success: true, // Empty batch is considered successful
-                error: None,
-                attempts,
-                latency_ms: Some(latency_ms),
-                batch_size_bytes,
-                failed_rows: None,
-                successful_rows: None,
-                total_rows: 0,
-                successful_count: 0,
-                failed_count: 0,
-            });
+    /// Fail if the batch's schema differs from `WrapperConfiguration::expected_schema`
+    fn check_expected_schema(&self, batch: &RecordBatch) -> Result<(), ZerobusError> {
+        let Some(expected) = &self.config.expected_schema else {
+            return Ok(());
+        };
+        let differences = crate::wrapper::conversion::diff_schemas(expected, &batch.schema());
+        if differences.is_empty() {
+            return Ok(());
         }
+        Err(ZerobusError::ConfigurationError(format!(
+            "Batch schema differs from the expected schema ({} difference(s)): {}",
+            differences.len(),
+            differences
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>()
+                .join("; ")
+        )))
+    }
 
-        match result {
-            Ok(batch_result) => {
-                // Merge conversion and transmission errors
-                let mut all_failed_rows = batch_result.failed_rows;
-                let successful_rows = batch_result.successful_rows;
-
-                let successful_count = successful_rows.len();
-                let failed_count = all_failed_rows.len();
-
-                // Determine overall success: true if ANY rows succeeded
-                // Edge case: If all rows failed, success is false
-                let overall_success = successful_count > 0;
-
-                // Sort failed rows by index for consistency
-                all_failed_rows.sort_by_key(|(idx, _)| *idx);
+    /// Send an already transformed batch, applying `max_batch_bytes`
+    async fn send_transformed_batch(
+        &self,
+        batch: RecordBatch,
+        descriptor: Option<prost_types::DescriptorProto>,
+    ) -> Result<TransmissionResult, ZerobusError> {
+        let Some(max_bytes) = self.config.max_batch_bytes else {
+            return self.send_to_tables(batch, descriptor).await;
+        };
+        let max_bytes = usize::try_from(max_bytes).unwrap_or(usize::MAX);
+        let batch_bytes = batch_bytes(&batch);
+        if batch_bytes <= max_bytes {
+            return self.send_to_tables(batch, descriptor).await;
+        }
 
-                // Update failure rate tracking (only counts network/transmission errors)
-                crate::wrapper::zerobus::update_failure_rate(
-                    &self.config.table_name,
-                    total_rows,
-                    &all_failed_rows,
+        let start_time = std::time::Instant::now();
+        let total_rows = batch.num_rows();
+        match self.config.oversized_batch_policy {
+            OversizedBatchPolicy::Reject => Err(ZerobusError::ConfigurationError(format!(
+                "Batch of {} bytes exceeds max_batch_bytes ({} bytes)",
+                batch_bytes, max_bytes
+            ))),
+            OversizedBatchPolicy::AutoSplit => {
+                let mut results = Vec::new();
+                let mut row_offset = 0;
+                while row_offset < total_rows {
+                    let num_rows = rows_within_bytes(&batch, row_offset, max_bytes).max(1);
+                    let result = self
+                        .send_to_tables(batch.slice(row_offset, num_rows), descriptor.clone())
+                        .await?;
+                    if let Some(error) = result.error.clone() {
+                        // The remaining rows are not sent: report them, and any rows of
+                        // the failed sub-batch its result leaves out, with its error
+                        let reported: std::collections::HashSet<usize> = result
+                            .successful_rows
+                            .iter()
+                            .flatten()
+                            .copied()
+                            .chain(result.failed_rows.iter().flatten().map(|(idx, _)| *idx))
+                            .map(|idx| row_offset + idx)
+                            .collect();
+                        let unsent: Vec<usize> = (row_offset..total_rows)
+                            .filter(|idx| !reported.contains(idx))
+                            .collect();
+                        warn!(
+                            "Sub-batch at row {} failed, {} rows of the split batch were not sent: {}",
+                            row_offset,
+                            unsent.len(),
+                            error
+                        );
+                        results.push((row_offset, result));
+                        results.push((
+                            0,
+                            failed_rows_result(unsent, error, self.config.max_tracked_failures),
+                        ));
+                        break;
+                    }
+                    results.push((row_offset, result));
+                    row_offset += num_rows;
+                }
+                debug!(
+                    "Split batch of {} bytes into {} sub-batches of at most {} bytes",
+                    batch_bytes,
+                    results.len(),
+                    max_bytes
                 );
-
-                Ok(TransmissionResult {
-                    success: overall_success,
-                    error: None, // No batch-level error, only per-row errors
-                    attempts,
-                    latency_ms: Some(latency_ms),
-                    batch_size_bytes,
-                    failed_rows: if all_failed_rows.is_empty() {
-                        None
-                    } else {
-                        Some(all_failed_rows)
-                    },
-                    successful_rows: if successful_rows.is_empty() {
-                        None
-                    } else {
-                        Some(successful_rows)
-                    },
-                    total_rows,
-                    successful_count,
-                    failed_count,
-                })
+                let latency_ms = start_time.elapsed().as_millis() as u64;
+                Ok(aggregate_results(results, total_rows, Some(latency_ms)))
             }
-            Err(e) => {
-                error!("Failed to send batch after retries: {}", e);
-                // Batch-level error (e.g., authentication, connection before processing)
-                // Edge case: Batch-level errors occur before per-row processing
-                Ok(TransmissionResult {
-                    success: false,
-                    error: Some(e),
-                    attempts,
-                    latency_ms: Some(latency_ms),
-                    batch_size_bytes,
-                    failed_rows: None, // Batch-level error, no per-row processing occurred
-                    successful_rows: None,
+            OversizedBatchPolicy::Truncate => {
+                let kept = rows_within_bytes(&batch, 0, max_bytes);
+                warn!(
+                    "Batch of {} bytes exceeds max_batch_bytes ({} bytes): sending {} of {} rows, dropping the rest",
+                    batch_bytes, max_bytes, kept, total_rows
+                );
+                let sent = if kept > 0 {
+                    self.send_to_tables(batch.slice(0, kept), descriptor)
+                        .await?
+                } else {
+                    aggregate_results(Vec::new(), 0, None)
+                };
+                let dropped = failed_rows_result(
+                    (kept..total_rows).collect(),
+                    ZerobusError::ConfigurationError(format!(
+                        "Row dropped: batch exceeds max_batch_bytes ({} bytes)",
+                        max_bytes
+                    )),
+                    self.config.max_tracked_failures,
+                );
+                let latency_ms = start_time.elapsed().as_millis() as u64;
+                Ok(aggregate_results(
+                    vec![(0, sent), (0, dropped)],
                     total_rows,
-                    successful_count: 0,
-                    failed_count: 0, // Batch-level error, no per-row processing
-                })
+                    Some(latency_ms),
+                ))
             }
         }
     }
 
-    /// Internal method to send a batch (without retry wrapper)
-    /// Returns per-row transmission information
-    async fn send_batch_internal(
+    /// Send a batch to the primary table (and mirror, if configured)
+    async fn send_to_tables(
         &self,
         batch: RecordBatch,
         descriptor: Option<prost_types::DescriptorProto>,
-    ) -> Result<BatchTransmissionResult, ZerobusError> {
-        // CRITICAL: Check if writer is disabled FIRST, before any SDK initialization or credential access
-        // This prevents errors when credentials are not provided (which is allowed when writer is disabled)
-        if self.config.zerobus_writer_disabled {
-            // When writer is disabled, we still perform conversion and write debug files,
-            // but skip all SDK calls. This enables local development and testing without credentials.
-            debug!(
-                "Writer disabled mode enabled - skipping SDK initialization and Zerobus SDK calls"
+    ) -> Result<TransmissionResult, ZerobusError> {
+        let Some(mirror) = &self.mirror else {
+            let result = self.send_primary_batch(batch, descriptor).await;
+            self.record_last_error(&self.config.table_name, &result);
+            return result;
+        };
+
+        // Dual-write: send to the primary and mirror tables concurrently
+        let (primary, mirrored) = tokio::join!(
+            self.send_primary_batch(batch.clone(), descriptor.clone()),
+            mirror.send_batch_with_row_results(batch, descriptor, None)
+        );
+        self.record_last_error(&self.config.table_name, &primary);
+        self.record_last_error(&mirror.config.table_name, &mirrored);
+        let mut result = primary?;
+        let mirror_result = mirrored.unwrap_or_else(|e| TransmissionResult {
+            error: Some(e),
+            batch_size_bytes: result.batch_size_bytes,
+            total_rows: result.total_rows,
+            ..Default::default()
+        });
+
+        if !mirror_result.success && mirror_result.total_rows > 0 {
+            warn!(
+                "Mirror table {} send failed ({} of {} rows failed)",
+                mirror.config.table_name, mirror_result.failed_count, mirror_result.total_rows
             );
-            // Continue to conversion and debug file writing below, then return early
-        } else {
-            // 1. Ensure SDK is initialized (only when writer is NOT disabled)
-            {
-                let mut sdk_guard = self.sdk.lock().await;
-                if sdk_guard.is_none() {
-                    let unity_catalog_url = self
-                        .config
-                        .unity_catalog_url
+            if self.config.mirror_failures_fatal && result.error.is_none() {
+                result.success = false;
+                result.error = Some(ZerobusError::TransmissionError(format!(
+                    "Mirror table {} send failed: {}",
+                    mirror.config.table_name,
+                    mirror_result
+                        .error
                         .as_ref()
-                        .ok_or_else(|| {
-                            ZerobusError::ConfigurationError(
-                                "unity_catalog_url is required".to_string(),
-                            )
-                        })?
-                        .clone();
-
-                    let sdk = crate::wrapper::zerobus::create_sdk(
-                        self.config.zerobus_endpoint.clone(),
-                        unity_catalog_url,
-                    )
-                    .await?;
-                    *sdk_guard = Some(sdk);
-                }
+                        .map(|e| e.to_string())
+                        .unwrap_or_else(|| "all rows failed".to_string())
+                )));
             }
         }
+        result.mirror_result = Some(Box::new(mirror_result));
+        Ok(result)
+    }
 
-        // 2. Get Protobuf descriptor (use provided one or generate from Arrow schema)
-        let descriptor = if let Some(provided_descriptor) = descriptor {
-            // Validate user-provided descriptor to prevent security issues
-            crate::wrapper::conversion::validate_protobuf_descriptor(&provided_descriptor)
-                .map_err(|e| {
-                    ZerobusError::ConfigurationError(format!("Invalid Protobuf descriptor: {}", e))
-                })?;
-            let descriptor_name = provided_descriptor.name.as_deref().unwrap_or("unknown");
-            info!("🔍 [DEBUG] Using provided Protobuf descriptor: name='{}', fields={}, nested_types={}", 
-                  descriptor_name, provided_descriptor.field.len(), provided_descriptor.nested_type.len());
-            provided_descriptor
-        } else {
-            debug!("Auto-generating Protobuf descriptor from Arrow schema");
-            let generated =
-                crate::wrapper::conversion::generate_protobuf_descriptor(batch.schema().as_ref())
-                    .map_err(|e| {
-                    ZerobusError::ConversionError(format!(
-                        "Failed to generate Protobuf descriptor: {}",
-                        e
-                    ))
-                })?;
-            // Validate generated descriptor (should always pass, but safety check)
-            crate::wrapper::conversion::validate_protobuf_descriptor(&generated).map_err(|e| {
-                ZerobusError::ConversionError(format!(
-                    "Generated Protobuf descriptor failed validation: {}",
-                    e
-                ))
-            })?;
-            let descriptor_name = generated.name.as_deref().unwrap_or("unknown");
-            info!("🔍 [DEBUG] Auto-generated Protobuf descriptor: name='{}', fields={}, nested_types={}", 
-                  descriptor_name, generated.field.len(), generated.nested_type.len());
-            generated
+    /// Record the batch-level error of a send, if any, as the table's last error
+    fn record_last_error(
+        &self,
+        table_name: &str,
+        result: &Result<TransmissionResult, ZerobusError>,
+    ) {
+        let error = match result {
+            Ok(result) => result.error.as_ref(),
+            Err(e) => Some(e),
         };
+        if let Some(error) = error {
+            crate::wrapper::zerobus::record_last_error(table_name, error);
+        }
+    }
 
-        // Write descriptor to file once per table (if either Arrow or Protobuf debug is enabled)
-        if self.config.debug_arrow_enabled || self.config.debug_protobuf_enabled {
-            if let Some(ref debug_writer) = self.debug_writer {
-                let mut written_guard = self.descriptor_written.lock().await;
-                if !*written_guard {
-                    if let Err(e) = debug_writer
-                        .write_descriptor(&self.config.table_name, &descriptor)
-                        .await
-                    {
-                        warn!("Failed to write Protobuf descriptor to debug file: {}", e);
-                        // Don't fail the operation if descriptor writing fails
-                    } else {
-                        *written_guard = true;
-                    }
-                }
-            }
+    /// Send a batch to the primary table, re-sending transmission-failed rows if configured
+    ///
+    /// See `WrapperConfiguration::with_auto_retry_failed_rows`.
+    async fn send_primary_batch(
+        &self,
+        batch: RecordBatch,
+        descriptor: Option<prost_types::DescriptorProto>,
+    ) -> Result<TransmissionResult, ZerobusError> {
+        let result = self
+            .send_batch_with_row_results(batch.clone(), descriptor.clone(), None)
+            .await?;
+        let max_rounds = self.config.auto_retry_failed_rows;
+        if max_rounds == 0 {
+            return Ok(result);
         }
 
-        // 3. Convert Arrow RecordBatch to Protobuf bytes (one per row)
-        // This now returns ProtobufConversionResult with per-row conversion errors
-        let conversion_result =
-            crate::wrapper::conversion::record_batch_to_protobuf_bytes(&batch, &descriptor);
+        let is_retryable = |error: &ZerobusError| self.retry_config.is_retryable(error);
+        Ok(retry_failed_rows(result, max_rounds, is_retryable, |rows| {
+            let batch = batch.clone();
+            let descriptor = descriptor.clone();
+            let wrapper = self.clone();
+            async move {
+                let indices =
+                    arrow::array::UInt32Array::from_iter_values(rows.iter().map(|&r| r as u32));
+                let retry_batch =
+                    arrow::compute::take_record_batch(&batch, &indices).map_err(|e| {
+                        ZerobusError::ConversionError(format!(
+                            "Failed to extract rows to re-send: {}",
+                            e
+                        ))
+                    })?;
+                wrapper
+                    .send_batch_with_row_results(retry_batch, descriptor, None)
+                    .await
+            }
+        })
+        .await)
+    }
 
-        // Track conversion errors (will be merged with transmission errors later)
-        let conversion_errors = conversion_result.failed_rows;
+    /// Send several batches sharing one schema as a single logical table
+    ///
+    /// Each batch is converted as by `send_batch` with no descriptor (the generated
+    /// descriptor and its field maps are cached across batches), and the batches are sent
+    /// in order over the same stream. The returned result aggregates all batches, with row
+    /// indices offset by the number of rows in the preceding batches: row `r` of batch `b`
+    /// is reported as `rows_before(b) + r`.
+    ///
+    /// Sending stops at the first batch-level error, which is returned in `error`. Rows of
+    /// the failed and any later batches appear in neither `successful_rows` nor
+    /// `failed_rows`, but still count towards `total_rows`.
+    ///
+    /// # Arguments
+    ///
+    /// * `batches` - Arrow RecordBatches to send, all with the same schema
+    ///
+    /// # Returns
+    ///
+    /// Returns a single `TransmissionResult` covering every batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConversionError` if the batches do not share the same schema, or the
+    /// error `send_batch` returns for a batch that cannot be converted.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::ZerobusWrapper;
+    ///
+    /// # async fn example(
+    /// #     wrapper: ZerobusWrapper,
+    /// #     batches: Vec<arrow::record_batch::RecordBatch>,
+    /// # ) -> Result<(), arrow_zerobus_sdk_wrapper::ZerobusError> {
+    /// let result = wrapper.send_batches(batches).await?;
+    /// println!("{} of {} rows written", result.successful_count, result.total_rows);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_batches(
+        &self,
+        batches: Vec<RecordBatch>,
+    ) -> Result<TransmissionResult, ZerobusError> {
+        let start_time = std::time::Instant::now();
 
-        // Write Protobuf bytes to debug file if Protobuf debug is enabled (only successful conversions)
-        // Flush after each batch to ensure files are immediately available for debugging
-        // CRITICAL: Write protobuf files BEFORE Zerobus write attempts, so we have them even if Zerobus fails
-        if self.config.debug_protobuf_enabled {
-            if let Some(ref debug_writer) = self.debug_writer {
-                info!(
-                    "Writing {} protobuf messages to debug file",
-                    conversion_result.successful_bytes.len()
-                );
-                let num_rows = conversion_result.successful_bytes.len();
-                for (idx, (_, bytes)) in conversion_result.successful_bytes.iter().enumerate() {
-                    // Flush immediately after last row in batch
-                    let flush_immediately = idx == num_rows - 1;
-                    if let Err(e) = debug_writer.write_protobuf(bytes, flush_immediately).await {
-                        warn!("Failed to write Protobuf debug file: {}", e);
-                        // Don't fail the operation if debug writing fails
-                    } else if flush_immediately {
-                        info!(
-                            "✅ Flushed protobuf debug file after batch ({} messages)",
-                            num_rows
-                        );
-                    }
-                }
-            } else {
-                warn!("⚠️  Debug writer is None - protobuf debug files will not be written. Check debug_protobuf_enabled and debug_output_dir config.");
-            }
+        let batches = batches
+            .into_iter()
+            .map(|batch| self.transform_batch(batch))
+            .collect::<Result<Vec<_>, _>>()?;
+        let Some(first) = batches.first() else {
+            return Ok(aggregate_results(Vec::new(), 0, None));
+        };
+        let schema = first.schema();
+        if let Some((idx, _)) = batches
+            .iter()
+            .enumerate()
+            .find(|(_, batch)| batch.schema().fields() != schema.fields())
+        {
+            return Err(ZerobusError::ConversionError(format!(
+                "All batches must share the same schema; batch {} differs from batch 0",
+                idx
+            )));
         }
 
-        // Check if writer is disabled - if so, skip all SDK calls and return success
-        // Performance: Operations complete in <50ms (excluding file I/O) when writer disabled
-        // This enables performance testing of conversion logic without network overhead
-        if self.config.zerobus_writer_disabled {
-            debug!(
-                "Writer disabled mode enabled - skipping Zerobus SDK calls. Debug files written successfully."
-            );
-            // Return success with conversion results tracked
-            // All successfully converted rows are considered successful when writer is disabled
-            let successful_indices: Vec<usize> = conversion_result
-                .successful_bytes
-                .iter()
-                .map(|(idx, _)| *idx)
-                .collect();
-            return Ok(BatchTransmissionResult {
-                successful_rows: successful_indices,
-                failed_rows: conversion_errors,
-            });
+        let total_rows = batches.iter().map(|batch| batch.num_rows()).sum();
+        let mut results = Vec::with_capacity(batches.len());
+        let mut row_offset = 0;
+        for batch in batches {
+            let num_rows = batch.num_rows();
+            let result = self.send_transformed_batch(batch, None).await?;
+            let batch_failed = result.error.is_some();
+            results.push((row_offset, result));
+            if batch_failed {
+                break;
+            }
+            row_offset += num_rows;
         }
 
-        // Get SDK reference (lock is released, so we can lock again for stream creation)
-        // This is safe because we only reach here when writer is NOT disabled, so SDK was initialized above
-        let sdk_guard = self.sdk.lock().await;
-        let sdk = sdk_guard.as_ref().ok_or_else(|| {
-            ZerobusError::ConfigurationError(
-                "SDK not initialized - this should not happen".to_string(),
-            )
-        })?;
+        let latency_ms = start_time.elapsed().as_millis() as u64;
+        Ok(aggregate_results(results, total_rows, Some(latency_ms)))
+    }
 
-        // 4. Ensure stream is created
-        // Expose secrets only when needed for API calls
-        let client_id = self
-            .config
-            .client_id
-            .as_ref()
-            .ok_or_else(|| ZerobusError::ConfigurationError("client_id is required".to_string()))?
-            .expose_secret()
-            .clone();
-        let client_secret = self
-            .config
-            .client_secret
+    /// Send every batch of an Arrow IPC file or stream as a single logical table
+    ///
+    /// The format is detected from the leading bytes: the IPC file format (`ARROW1`
+    /// magic) is read into memory, since its footer is at the end; the IPC stream format
+    /// is decoded and sent one batch at a time. As with `send_batches`, each batch is
+    /// converted as by `send_batch` with no descriptor, the batches are sent in order over
+    /// the same stream, and row `r` of batch `b` is reported as `rows_before(b) + r`.
+    ///
+    /// Sending stops at the first batch-level error, or at the first batch that cannot be
+    /// read or has a different schema, which is returned in `error`. `total_rows` counts
+    /// the rows of the batches read up to that point.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - Arrow IPC file or stream bytes
+    ///
+    /// # Returns
+    ///
+    /// Returns a single `TransmissionResult` covering every batch sent.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConversionError` if the input is not Arrow IPC data or its first batch
+    /// cannot be read, or the error `send_batch` returns for a batch that cannot be
+    /// converted.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::ZerobusWrapper;
+    ///
+    /// # async fn example(wrapper: ZerobusWrapper) -> Result<(), Box<dyn std::error::Error>> {
+    /// let file = std::fs::File::open("events.arrow")?;
+    /// let result = wrapper.send_ipc_reader(file).await?;
+    /// println!("{} of {} rows written", result.successful_count, result.total_rows);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_ipc_reader<R: std::io::Read + Send>(
+        &self,
+        reader: R,
+    ) -> Result<TransmissionResult, ZerobusError> {
+        let start_time = std::time::Instant::now();
+
+        // Decode, transform and check one batch against the first batch's schema
+        let next_batch = |idx: usize,
+                          batch: Result<RecordBatch, arrow::error::ArrowError>,
+                          schema: Option<&arrow::datatypes::SchemaRef>| {
+            let batch = batch.map_err(|e| {
+                ZerobusError::ConversionError(format!(
+                    "Failed to read Arrow IPC batch {}: {}",
+                    idx, e
+                ))
+            })?;
+            let batch = self.transform_batch(batch)?;
+            match schema {
+                Some(schema) if batch.schema().fields() != schema.fields() => {
+                    Err(ZerobusError::ConversionError(format!(
+                        "All batches must share the same schema; batch {} differs from batch 0",
+                        idx
+                    )))
+                }
+                _ => Ok(batch),
+            }
+        };
+
+        let mut batches = read_ipc_batches(reader)?.enumerate();
+        let Some((_, first)) = batches.next() else {
+            return Ok(aggregate_results(Vec::new(), 0, None));
+        };
+        let mut batch = next_batch(0, first, None)?;
+        let schema = batch.schema();
+
+        let mut results = Vec::new();
+        let mut row_offset = 0;
+        let mut read_error = None;
+        loop {
+            let num_rows = batch.num_rows();
+            let result = self.send_transformed_batch(batch, None).await?;
+            let batch_failed = result.error.is_some();
+            results.push((row_offset, result));
+            row_offset += num_rows;
+            if batch_failed {
+                break;
+            }
+            let Some((idx, next)) = batches.next() else {
+                break;
+            };
+            match next_batch(idx, next, Some(&schema)) {
+                Ok(next) => batch = next,
+                Err(e) => {
+                    read_error = Some(e);
+                    break;
+                }
+            }
+        }
+
+        let latency_ms = start_time.elapsed().as_millis() as u64;
+        let mut result = aggregate_results(results, row_offset, Some(latency_ms));
+        if let Some(e) = read_error {
+            result.error = Some(e);
+            result.success = false;
+        }
+        Ok(result)
+    }
+
+    /// Convert a batch to Protobuf without sending it
+    ///
+    /// Applies the batch transform, resolves the descriptor exactly as
+    /// `send_batch_with_descriptor` does and converts every row. The returned
+    /// `PreparedBatch` can be sent later with `send_prepared`, which skips conversion, so
+    /// conversion can run separately (e.g. on a CPU pool) from transmission. Rows that
+    /// fail conversion are kept in the prepared batch and reported as failed when it is sent.
+    ///
+    /// With `SchemaEvolution::AddOnly`, preparing a batch evolves the active descriptor
+    /// immediately, as sending it would.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch` - Arrow RecordBatch to convert
+    /// * `descriptor` - Optional Protobuf descriptor (see `send_batch_with_descriptor`)
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the batch transform fails, or if the descriptor is invalid or
+    /// cannot be generated.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::ZerobusWrapper;
+    ///
+    /// # async fn example(
+    /// #     wrapper: ZerobusWrapper,
+    /// #     batch: arrow::record_batch::RecordBatch,
+    /// # ) -> Result<(), arrow_zerobus_sdk_wrapper::ZerobusError> {
+    /// let prepared = wrapper.prepare_batch(batch, None).await?;
+    /// if prepared.failed_rows().is_empty() {
+    ///     let result = wrapper.send_prepared(prepared).await?;
+    ///     println!("{} rows written", result.successful_count);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn prepare_batch(
+        &self,
+        batch: RecordBatch,
+        descriptor: Option<prost_types::DescriptorProto>,
+    ) -> Result<PreparedBatch, ZerobusError> {
+        let batch = self.transform_batch(batch)?;
+        self.write_arrow_debug(&batch).await;
+        self.convert_batch(&batch, descriptor).await
+    }
+
+    /// Send a batch converted earlier with `prepare_batch`
+    ///
+    /// Goes straight to transmission with the same retries, metrics and per-row results
+    /// as `send_batch`; rows that failed conversion are reported in `failed_rows`.
+    /// Only the primary table is written: the mirror table, if configured, is not, since
+    /// its rows are converted with its own configuration. Use `send_batch` to dual-write.
+    ///
+    /// # Arguments
+    ///
+    /// * `prepared` - Batch returned by `prepare_batch` on this wrapper
+    ///
+    /// # Returns
+    ///
+    /// Returns `TransmissionResult` indicating success or failure.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the memory budget is closed.
+    pub async fn send_prepared(
+        &self,
+        prepared: PreparedBatch,
+    ) -> Result<TransmissionResult, ZerobusError> {
+        let prepared = Arc::new(prepared);
+        let kept_segment = Arc::new(KeptSegment::default());
+        self.send_with_retry(prepared.total_rows, prepared.batch_size_bytes, || {
+            let prepared = Arc::clone(&prepared);
+            let kept_segment = Arc::clone(&kept_segment);
+            let wrapper = self.clone();
+            async move {
+                wrapper
+                    .send_prepared_internal(&prepared, None, &kept_segment)
+                    .await
+            }
+        })
+        .await
+    }
+
+    /// Re-send the records left unacknowledged in the write-ahead log by an earlier run
+    ///
+    /// Call on startup, before sending new batches, when `WrapperConfiguration::wal_dir`
+    /// is set. Each segment left behind (one per batch with records that were never
+    /// acknowledged, because the send failed or the process crashed) is sent like
+    /// `send_prepared`, with the same retries, keeping the rows' original indices.
+    /// Acknowledgments are recorded in the segment as they arrive, and it is removed once
+    /// every record is acknowledged, so a crash or failure during recovery only re-sends
+    /// what is still unacknowledged. Delivery is at-least-once: a record acknowledged just
+    /// before a crash may be sent twice.
+    ///
+    /// # Returns
+    ///
+    /// Returns one `TransmissionResult` per recovered segment, oldest first (empty if
+    /// nothing was left to recover).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigurationError` if `wal_dir` is not set or the WAL directory cannot be
+    /// read. A batch-level error sending a segment (reported in its result, the last one
+    /// returned) stops recovery, leaving it and later segments for the next call.
+    pub async fn recover_wal(&self) -> Result<Vec<TransmissionResult>, ZerobusError> {
+        let wal = self.wal.as_ref().ok_or_else(|| {
+            ZerobusError::ConfigurationError("wal_dir is not configured".to_string())
+        })?;
+        let pending = wal.pending_segments()?;
+        let mut results = Vec::with_capacity(pending.len());
+        for pending in pending {
+            info!(
+                "Recovering {} unacknowledged records from WAL segment {}",
+                pending.records.len(),
+                pending.path.display()
+            );
+            let segment = wal.resume_segment(&pending.path)?;
+            let prepared = PreparedBatch {
+                total_rows: pending.records.last().map_or(0, |(idx, _)| idx + 1),
+                batch_size_bytes: pending.records.iter().map(|(_, bytes)| bytes.len()).sum(),
+                descriptor: pending.descriptor,
+                successful_bytes: pending.records,
+                failed_rows: Vec::new(),
+                untracked_failures: 0,
+                error_keys: None,
+            };
+            let result = self
+                .send_with_retry(prepared.total_rows, prepared.batch_size_bytes, || {
+                    self.transmit_prepared(&prepared, None, Some(&segment))
+                })
+                .await?;
+            // Rows still unacknowledged stay in the segment for the next call
+            let delivered = result.error.is_none() && result.failed_count == 0;
+            finish_wal_segment(segment, delivered).await;
+            let stop = result.error.is_some();
+            results.push(result);
+            if stop {
+                break;
+            }
+        }
+        Ok(results)
+    }
+
+    /// Send records that are already serialized as Protobuf, without Arrow conversion
+    ///
+    /// For producers that encode their own messages (e.g. from a schema registry). The
+    /// records are sent like a converted batch, with the same retries, metrics and
+    /// per-row results as `send_prepared`, where the row index is the record's position
+    /// in `records`. Records over the Zerobus size limit are reported as failed rows
+    /// instead of being sent. Only the primary table is written.
+    ///
+    /// # Arguments
+    ///
+    /// * `records` - Serialized Protobuf messages, each encoded with `descriptor`
+    /// * `descriptor` - Protobuf descriptor of the records
+    ///
+    /// # Returns
+    ///
+    /// Returns `TransmissionResult` indicating success or failure.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigurationError` if the descriptor is invalid, or an error if the
+    /// memory budget is closed.
+    pub async fn send_serialized(
+        &self,
+        records: Vec<Vec<u8>>,
+        descriptor: prost_types::DescriptorProto,
+    ) -> Result<TransmissionResult, ZerobusError> {
+        crate::wrapper::conversion::validate_protobuf_descriptor_with_limits(
+            &descriptor,
+            &self.config.descriptor_limits(),
+        )
+        .map_err(|e| {
+            ZerobusError::ConfigurationError(format!("Invalid Protobuf descriptor: {}", e))
+        })?;
+
+        let total_rows = records.len();
+        let batch_size_bytes = records.iter().map(Vec::len).sum();
+        let mut successful_bytes = Vec::with_capacity(total_rows);
+        let mut failed_rows = Vec::new();
+        for (idx, record) in records.into_iter().enumerate() {
+            match crate::wrapper::conversion::check_record_size(&record) {
+                Ok(()) => successful_bytes.push((idx, record)),
+                Err(e) => failed_rows.push((idx, e)),
+            }
+        }
+
+        let untracked_failures =
+            cap_tracked_failures(&mut failed_rows, self.config.max_tracked_failures);
+        self.send_prepared(PreparedBatch {
+            descriptor,
+            successful_bytes,
+            failed_rows,
+            untracked_failures,
+            total_rows,
+            batch_size_bytes,
+            error_keys: None,
+        })
+        .await
+    }
+
+    /// Send a data batch and yield each row's outcome as soon as it is final
+    ///
+    /// Intended for very large batches (100k+ rows): instead of waiting for a single
+    /// `TransmissionResult` holding every row index, the caller receives a `RowResult`
+    /// per row as it is acknowledged or fails, and can process results incrementally
+    /// (e.g. write confirmed rows downstream while the rest are still in flight).
+    ///
+    /// Each row is yielded exactly once. Rows are yielded in acknowledgment order,
+    /// not necessarily in batch order. Conversion failures are yielded first; rows that
+    /// fail transmission are yielded once stream recreation retries are exhausted; on a
+    /// batch-level error every remaining row is yielded with that error.
+    ///
+    /// The batch is sent on a background task, so this must be called from within a
+    /// Tokio runtime. The result channel is bounded, so a slow consumer slows the send.
+    /// Dropping the stream does not cancel the send. Row indices, acknowledgment ids and
+    /// row sizes are not also collected into a `TransmissionResult`, so memory does not
+    /// grow with the batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch` - Arrow RecordBatch to send
+    /// * `descriptor` - Optional Protobuf descriptor (see `send_batch_with_descriptor`)
+    ///
+    /// # Returns
+    ///
+    /// A stream of `RowResult`, one per row in `batch`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::{ZerobusWrapper, WrapperConfiguration};
+    /// use futures::StreamExt;
+    ///
+    /// # async fn example(wrapper: ZerobusWrapper, batch: arrow::record_batch::RecordBatch) {
+    /// let mut results = wrapper.send_batch_streaming_results(batch, None);
+    /// while let Some(row) = results.next().await {
+    ///     if let Err(e) = row.outcome {
+    ///         eprintln!("row {} failed: {}", row.row_idx, e);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn send_batch_streaming_results(
+        &self,
+        batch: RecordBatch,
+        descriptor: Option<prost_types::DescriptorProto>,
+    ) -> impl futures::Stream<Item = RowResult> + Send + Unpin + 'static {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(ROW_RESULT_CHANNEL_CAPACITY);
+        let wrapper = self.clone();
+
+        tokio::spawn(async move {
+            let batch = match wrapper.transform_batch(batch.clone()) {
+                Ok(transformed) => transformed,
+                Err(e) => {
+                    let sink = RowResultSink::new(tx, batch.num_rows());
+                    sink.report_remaining(&e).await;
+                    return;
+                }
+            };
+            let sink = Arc::new(RowResultSink::new(tx, batch.num_rows()));
+            let result = wrapper
+                .send_batch_with_row_results(batch, descriptor, Some(Arc::clone(&sink)))
+                .await;
+            // Batch-level failures never reach per-row processing; report them for every row
+            if let Some(e) = unreported_rows_error(&result) {
+                sink.report_remaining(&e).await;
+            }
+        });
+
+        futures::stream::poll_fn(move |cx| rx.poll_recv(cx))
+    }
+
+    /// Submit a data batch and collect its acknowledgments in the background
+    ///
+    /// Returns once every row has been handed to the stream, without waiting for the
+    /// acknowledgments of the last `max_pending_futures` rows (earlier rows are still
+    /// acknowledged before later ones are submitted, as with `send_batch`). Acknowledgment
+    /// latency then overlaps with whatever the caller does next, such as preparing and
+    /// submitting the following batch. Per-row outcomes and the final result are
+    /// available from the returned `PendingAcks`.
+    ///
+    /// The send runs on a background task, with the same retries and results as
+    /// `send_batch_with_descriptor`, so this must be called from within a Tokio runtime.
+    /// It also returns if the send ends before submitting every row (e.g. on a
+    /// batch-level error); the error is then reported by the handle. As with
+    /// `send_batch_streaming_results`, only the primary table is written.
+    ///
+    /// # Arguments
+    ///
+    /// * `batch` - Arrow RecordBatch to send
+    /// * `descriptor` - Optional Protobuf descriptor (see `send_batch_with_descriptor`)
+    ///
+    /// # Returns
+    ///
+    /// A `PendingAcks` handle for the batch's acknowledgments.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the batch transform fails; nothing is sent in that case.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use arrow_zerobus_sdk_wrapper::ZerobusWrapper;
+    ///
+    /// # async fn example(
+    /// #     wrapper: ZerobusWrapper,
+    /// #     batches: Vec<arrow::record_batch::RecordBatch>,
+    /// # ) -> Result<(), arrow_zerobus_sdk_wrapper::ZerobusError> {
+    /// let mut pending = Vec::new();
+    /// for batch in batches {
+    ///     pending.push(wrapper.submit_batch(batch, None).await?);
+    /// }
+    /// for acks in pending {
+    ///     let result = acks.wait().await?;
+    ///     println!("{} rows acknowledged", result.successful_count);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn submit_batch(
+        &self,
+        batch: RecordBatch,
+        descriptor: Option<prost_types::DescriptorProto>,
+    ) -> Result<PendingAcks, ZerobusError> {
+        let batch = self.transform_batch(batch)?;
+        // Room for every row, so unconsumed outcomes never hold up the send
+        let (tx, results) = tokio::sync::mpsc::channel(batch.num_rows().max(1));
+        let (submitted_tx, submitted) = tokio::sync::oneshot::channel();
+        let sink = Arc::new(
+            RowResultSink::new(tx, batch.num_rows())
+                .with_submission_signal(submitted_tx)
+                .keeping_rows(),
+        );
+        let wrapper = self.clone();
+
+        let task = tokio::spawn(async move {
+            let result = wrapper
+                .send_batch_with_row_results(batch, descriptor, Some(Arc::clone(&sink)))
+                .await;
+            // Batch-level failures never reach per-row processing; report them for every row
+            if let Some(e) = unreported_rows_error(&result) {
+                sink.report_remaining(&e).await;
+            }
+            result
+        });
+
+        // Also resolves (with an error) if the send ends without submitting every row
+        let _ = submitted.await;
+        Ok(PendingAcks { results, task })
+    }
+
+    /// Shared implementation of `send_batch_with_descriptor` and `send_batch_streaming_results`
+    ///
+    /// When `row_results` is set, per-row outcomes are also reported to it as they become final.
+    async fn send_batch_with_row_results(
+        &self,
+        batch: RecordBatch,
+        descriptor: Option<prost_types::DescriptorProto>,
+        row_results: Option<Arc<RowResultSink>>,
+    ) -> Result<TransmissionResult, ZerobusError> {
+        // Write Arrow batch to debug file if Arrow debug is enabled
+        self.write_arrow_debug(&batch).await;
+
+        let total_rows = batch.num_rows();
+        let batch_size_bytes = batch.get_array_memory_size();
+        let kept_segment = Arc::new(KeptSegment::default());
+        self.send_with_retry(total_rows, batch_size_bytes, || {
+            let batch = batch.clone();
+            let descriptor = descriptor.clone();
+            let row_results = row_results.clone();
+            let kept_segment = Arc::clone(&kept_segment);
+            let wrapper = self.clone();
+            async move {
+                wrapper
+                    .send_batch_internal(batch, descriptor, row_results.as_deref(), &kept_segment)
+                    .await
+            }
+        })
+        .await
+    }
+
+    /// Write a batch to the Arrow debug file, if Arrow debug is enabled
+    async fn write_arrow_debug(&self, batch: &RecordBatch) {
+        if self.config.debug_arrow_enabled {
+            if let Some(ref debug_writer) = self.debug_writer {
+                if let Err(e) = debug_writer.write_arrow(batch).await {
+                    warn!("Failed to write Arrow debug file: {}", e);
+                    // Don't fail the operation if debug writing fails
+                }
+            }
+        }
+    }
+
+    /// Run one batch send with retries, metrics and the memory budget, and build its result
+    ///
+    /// `send` performs a single attempt; it is called again for each retry.
+    async fn send_with_retry<F, Fut>(
+        &self,
+        total_rows: usize,
+        batch_size_bytes: usize,
+        mut send: F,
+    ) -> Result<TransmissionResult, ZerobusError>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<BatchTransmissionResult, ZerobusError>>,
+    {
+        let start_time = std::time::Instant::now();
+
+        debug!(
+            "Sending batch with {} rows, {} bytes",
+            total_rows, batch_size_bytes
+        );
+
+        // Reserve this batch's size from the memory budget until the send completes.
+        // Oversized batches reserve the whole budget so they run alone instead of never.
+        let _memory_permit = match &self.memory_budget {
+            Some(budget) => {
+                let limit = self.config.memory_budget_bytes.unwrap_or(usize::MAX);
+                let permits = batch_size_bytes.min(limit) as u32;
+                Some(budget.acquire_many(permits).await.map_err(|_| {
+                    ZerobusError::ConfigurationError("memory budget is closed".to_string())
+                })?)
+            }
+            None => None,
+        };
+
+        // Start observability span if enabled
+        let _span = self
+            .observability
             .as_ref()
-            .ok_or_else(|| {
-                ZerobusError::ConfigurationError("client_secret is required".to_string())
-            })?
-            .expose_secret()
-            .clone();
+            .map(|obs| obs.start_send_batch_span(&self.config.table_name));
+
+        // Use retry logic for transmission, abandoned once the send deadline passes.
+        // The SDK takes no deadline, so it is enforced here rather than by the server.
+        let attempts_started = std::sync::atomic::AtomicU32::new(0);
+        let transmission = self.retry_config.execute_with_retry_tracked(|| {
+            attempts_started.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            send()
+        });
+        let (result, attempts) = match self.config.send_deadline {
+            Some(deadline) => match tokio::time::timeout(deadline, transmission).await {
+                Ok(outcome) => outcome,
+                Err(_) => {
+                    warn!(
+                        "Send deadline of {:?} exceeded for table {}",
+                        deadline, self.config.table_name
+                    );
+                    (
+                        Err(ZerobusError::TransmissionError(format!(
+                            "Send deadline of {:?} exceeded",
+                            deadline
+                        ))),
+                        attempts_started.load(std::sync::atomic::Ordering::Relaxed),
+                    )
+                }
+            },
+            None => transmission.await,
+        };
+
+        let latency_ms = start_time.elapsed().as_millis() as u64;
+
+        // Record metrics if observability is enabled
+        if let Some(obs) = &self.observability {
+            let success = result.is_ok();
+            obs.record_batch_sent(batch_size_bytes, success, latency_ms)
+                .await;
+        }
+
+        match &result {
+            Ok(batch_result) => self.metrics.record_batch(
+                batch_size_bytes,
+                attempts,
+                latency_ms,
+                batch_result.successful_count,
+                &batch_result.failed_rows,
+                None,
+            ),
+            Err(e) => {
+                self.metrics
+                    .record_batch(batch_size_bytes, attempts, latency_ms, 0, &[], Some(e))
+            }
+        }
+
+        // Handle empty batch edge case
+        if total_rows == 0 {
+            return Ok(TransmissionResult {
+                success: true, // Empty batch is considered successful
+                attempts,
+                latency_ms: Some(latency_ms),
+                batch_size_bytes,
+                ..Default::default()
+            });
+        }
+
+        match result {
+            Ok(batch_result) => {
+                // Merge conversion and transmission errors
+                let mut all_failed_rows = batch_result.failed_rows;
+                let untracked_failures = batch_result.untracked_failures;
+                let successful_rows = batch_result.successful_rows;
+
+                let successful_count = batch_result.successful_count;
+                let failed_count = all_failed_rows.len() + untracked_failures;
+
+                // Determine overall success: true if ANY rows succeeded
+                // Edge case: If all rows failed, success is false
+                let overall_success = successful_count > 0;
+
+                // Sort failed rows by index for consistency
+                all_failed_rows.sort_by_key(|(idx, _)| *idx);
+
+                // Update failure rate tracking (only counts network/transmission errors)
+                crate::wrapper::zerobus::update_failure_rate(
+                    &self.config.table_name,
+                    total_rows,
+                    &all_failed_rows,
+                );
+
+                Ok(TransmissionResult {
+                    success: overall_success,
+                    error: None, // No batch-level error, only per-row errors
+                    attempts,
+                    latency_ms: Some(latency_ms),
+                    batch_size_bytes,
+                    failed_rows: if failed_count == 0 {
+                        None
+                    } else {
+                        Some(all_failed_rows)
+                    },
+                    successful_rows: if successful_rows.is_empty() {
+                        None
+                    } else {
+                        Some(successful_rows)
+                    },
+                    total_rows,
+                    successful_count,
+                    failed_count,
+                    row_sizes: batch_result.row_sizes,
+                    failed_row_bytes: batch_result.failed_row_bytes,
+                    ack_ids: batch_result.ack_ids,
+                    failures_truncated: untracked_failures > 0,
+                    ..Default::default()
+                })
+            }
+            Err(e) => {
+                error!("Failed to send batch after retries: {}", e);
+                // Batch-level error (e.g., authentication, connection before processing)
+                // Edge case: Batch-level errors occur before per-row processing
+                Ok(TransmissionResult {
+                    error: Some(e),
+                    attempts,
+                    latency_ms: Some(latency_ms),
+                    batch_size_bytes,
+                    failed_rows: None, // Batch-level error, no per-row processing occurred
+                    total_rows,
+                    failed_count: 0, // Batch-level error, no per-row processing
+                    ..Default::default()
+                })
+            }
+        }
+    }
+
+    /// Which nullable fields generated descriptors declare proto3 `optional`
+    fn field_presence(&self) -> crate::wrapper::conversion::FieldPresence {
+        use crate::wrapper::conversion::FieldPresence;
+        if self.config.explicit_field_presence {
+            FieldPresence::Nullable
+        } else if self.config.string_null_policy
+            == crate::config::StringNullPolicy::ExplicitPresence
+        {
+            FieldPresence::NullableStrings
+        } else {
+            FieldPresence::Implicit
+        }
+    }
+
+    /// Rename matching Arrow field names to a provided descriptor's fields
+    ///
+    /// The configured `field_name_transform`, followed by `field_name_resolver` if set.
+    /// Only for descriptors passed in by the caller: the wrapper never passes a descriptor
+    /// it generated as provided, since the resolver would then break its lookups.
+    fn provided_field_names(&self) -> FieldNameTransform {
+        match self.config.field_name_resolver.clone() {
+            Some(resolver) => {
+                let transform = self.config.field_name_transform.clone();
+                FieldNameTransform::Custom(Arc::new(move |name| {
+                    resolver.apply(&transform.apply(name)).into_owned()
+                }))
+            }
+            None => self.config.field_name_transform.clone(),
+        }
+    }
+
+    /// Auto-generate and validate the Protobuf descriptor for an Arrow schema
+    fn generate_descriptor(
+        &self,
+        schema: &arrow::datatypes::Schema,
+    ) -> Result<prost_types::DescriptorProto, ZerobusError> {
+        debug!("Auto-generating Protobuf descriptor from Arrow schema");
+        let generated = crate::wrapper::conversion::generate_descriptor_with_field_presence(
+            schema,
+            self.field_presence(),
+            &self.config.field_name_transform,
+            self.config.max_field_name_length,
+        )
+        .map_err(|e| match e {
+            // Invalid column names are reported as-is, naming the column to fix
+            ZerobusError::ConfigurationError(_) => e,
+            e => ZerobusError::ConversionError(format!(
+                "Failed to generate Protobuf descriptor: {}",
+                e
+            )),
+        })?;
+        // Validate generated descriptor (should always pass, but safety check)
+        crate::wrapper::conversion::validate_protobuf_descriptor_with_limits(
+            &generated,
+            &self.config.descriptor_limits(),
+        )
+        .map_err(|e| {
+            ZerobusError::ConversionError(format!(
+                "Generated Protobuf descriptor failed validation: {}",
+                e
+            ))
+        })?;
+        let descriptor_name = generated.name.as_deref().unwrap_or("unknown");
+        info!(
+            "🔍 [DEBUG] Auto-generated Protobuf descriptor: name='{}', fields={}, nested_types={}",
+            descriptor_name,
+            generated.field.len(),
+            generated.nested_type.len()
+        );
+        Ok(generated)
+    }
+
+    /// Compare a provided descriptor's fields with the batch's columns
+    ///
+    /// Columns the descriptor has no field for are skipped during conversion, and fields
+    /// with no column are left unset, so a descriptor from a different schema otherwise
+    /// only shows up as downstream rejections. Divergence is logged, and rejected with a
+    /// `ConfigurationError` when it exceeds `column_mismatch_tolerance`.
+    fn check_column_count(
+        &self,
+        descriptor: &prost_types::DescriptorProto,
+        schema: &arrow::datatypes::Schema,
+        field_names: &FieldNameTransform,
+    ) -> Result<(), ZerobusError> {
+        let columns: std::collections::HashSet<String> = schema
+            .fields()
+            .iter()
+            .map(|f| field_names.apply(f.name()).into_owned())
+            .collect();
+        let descriptor_fields: std::collections::HashSet<&str> = descriptor
+            .field
+            .iter()
+            .filter_map(|f| f.name.as_deref())
+            .collect();
+
+        let skipped: Vec<&str> = schema
+            .fields()
+            .iter()
+            .filter(|f| !descriptor_fields.contains(field_names.apply(f.name()).as_ref()))
+            .map(|f| f.name().as_str())
+            .collect();
+        let unset: Vec<&str> = descriptor
+            .field
+            .iter()
+            .filter_map(|f| f.name.as_deref())
+            .filter(|name| !columns.contains(*name))
+            .collect();
+        let mismatched = skipped.len() + unset.len();
+        if mismatched == 0 {
+            return Ok(());
+        }
+
+        let report = format!(
+            "descriptor has {} fields, batch has {} columns: columns not in descriptor={:?}, descriptor fields not in batch={:?}",
+            descriptor.field.len(),
+            schema.fields().len(),
+            skipped,
+            unset
+        );
+        match self.config.column_mismatch_tolerance {
+            Some(tolerance) if mismatched > tolerance => {
+                Err(ZerobusError::ConfigurationError(format!(
+                    "Protobuf descriptor does not match batch columns ({} mismatched, tolerance {}): {}",
+                    mismatched, tolerance, report
+                )))
+            }
+            _ => {
+                warn!("Column count mismatch for table {}: {}", self.config.table_name, report);
+                Ok(())
+            }
+        }
+    }
+
+    /// Reconcile a batch's descriptor with the table's active descriptor
+    ///
+    /// With `SchemaEvolution::AddOnly`, the first descriptor becomes the active one and
+    /// later descriptors are merged into it with `conversion::evolve_descriptor`. When new
+    /// fields are added, the stream created with the old descriptor (`shared_stream`) is
+    /// closed so the next record recreates it with the evolved one. Returns the descriptor
+    /// to encode with.
+    async fn evolve_active_descriptor<S: IngestStream>(
+        &self,
+        shared_stream: &Mutex<Option<S>>,
+        descriptor: prost_types::DescriptorProto,
+    ) -> Result<prost_types::DescriptorProto, ZerobusError> {
+        if self.config.schema_evolution != SchemaEvolution::AddOnly {
+            return Ok(descriptor);
+        }
+
+        let evolved = {
+            let mut active = self
+                .active_descriptor
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let Some(current) = active.as_ref() else {
+                *active = Some(descriptor.clone());
+                return Ok(descriptor);
+            };
+            if *current == descriptor {
+                return Ok(descriptor);
+            }
+            let evolved = crate::wrapper::conversion::evolve_descriptor(current, &descriptor)?;
+            if evolved == *current {
+                return Ok(evolved);
+            }
+            info!(
+                "Schema evolved for table {}: {} -> {} fields",
+                self.config.table_name,
+                current.field.len(),
+                evolved.field.len()
+            );
+            *active = Some(evolved.clone());
+            evolved
+        };
+
+        // The stream was created with the old descriptor; recreate it on the next record
+        let mut stream_guard = shared_stream.lock().await;
+        if let Some(mut stream) = stream_guard.take() {
+            close_with_timeout(stream.close_stream(), self.config.shutdown_timeout).await;
+        }
+        Ok(evolved)
+    }
+
+    /// Field lookup maps for `descriptor`, reused while the descriptor stays the same
+    ///
+    /// Only the most recent descriptor is cached: a stable descriptor is the common case,
+    /// and checking equality is much cheaper than rebuilding the maps.
+    fn field_maps_for(
+        &self,
+        descriptor: &prost_types::DescriptorProto,
+    ) -> Arc<DescriptorFieldMaps> {
+        let mut cached = self
+            .field_maps
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match cached.as_ref() {
+            Some(maps) if maps.matches(descriptor) => Arc::clone(maps),
+            _ => {
+                let maps = Arc::new(DescriptorFieldMaps::new(descriptor));
+                *cached = Some(Arc::clone(&maps));
+                maps
+            }
+        }
+    }
+
+    /// Descriptor to create a stream with: the active (evolved) one if any, else the batch's
+    fn stream_descriptor(
+        &self,
+        descriptor: &prost_types::DescriptorProto,
+    ) -> prost_types::DescriptorProto {
+        self.active_descriptor
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+            .unwrap_or_else(|| descriptor.clone())
+    }
+
+    /// Type mapping generated descriptors are currently built with
+    fn type_mapping(&self) -> TypeMapping {
+        *self
+            .type_mapping
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Drop the active descriptor, so the next one becomes the schema evolution baseline
+    fn forget_active_descriptor(&self) {
+        self.active_descriptor
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take();
+    }
+
+    /// Internal method to send a batch (without retry wrapper)
+    /// Returns per-row transmission information
+    async fn send_batch_internal(
+        &self,
+        batch: RecordBatch,
+        descriptor: Option<prost_types::DescriptorProto>,
+        row_results: Option<&RowResultSink>,
+        kept_segment: &KeptSegment,
+    ) -> Result<BatchTransmissionResult, ZerobusError> {
+        // A zero-row batch has nothing to transmit, but its schema is still resolved to a
+        // descriptor so debug consumers see the descriptor (and Arrow schema) even when the
+        // first batch for a table is empty. No SDK client or stream is created for it.
+        if batch.num_rows() == 0 {
+            self.convert_batch(&batch, descriptor).await?;
+            return Ok(BatchTransmissionResult {
+                successful_rows: Vec::new(),
+                successful_count: 0,
+                failed_rows: Vec::new(),
+                untracked_failures: 0,
+                row_sizes: self.config.track_row_sizes.then(Vec::new),
+                failed_row_bytes: self.config.capture_failed_bytes.then(Vec::new),
+                ack_ids: None,
+                closed_on_first_record: false,
+            });
+        }
+
+        // CRITICAL: Check if writer is disabled FIRST, before any SDK initialization or credential access
+        // This prevents errors when credentials are not provided (which is allowed when writer is disabled)
+        if self.config.zerobus_writer_disabled {
+            // When writer is disabled, we still perform conversion and write debug files,
+            // but skip all SDK calls. This enables local development and testing without credentials.
+            debug!(
+                "Writer disabled mode enabled - skipping SDK initialization and Zerobus SDK calls"
+            );
+            // Continue to conversion and debug file writing below, then return early
+        } else {
+            // 1. Ensure SDK is initialized (only when writer is NOT disabled)
+            self.ensure_sdk().await?;
+        }
+
+        // A generated descriptor the table rejects on the first record is retried with the
+        // alternate type mapping (opt-in). Streaming sends report failures as they become
+        // final, so they are never retried.
+        if self.config.descriptor_auto_recovery && descriptor.is_none() && row_results.is_none() {
+            return self.send_with_auto_recovery(&batch, kept_segment).await;
+        }
+
+        // 2. Convert Arrow RecordBatch to Protobuf bytes (one per row)
+        let prepared = self.convert_batch(&batch, descriptor).await?;
+        if self.config.conversion_errors_fatal {
+            fatal_conversion_errors(&prepared)?;
+        }
+
+        // 3. Transmit the converted rows
+        self.send_prepared_internal(&prepared, row_results, kept_segment)
+            .await
+    }
+
+    /// Convert and transmit a batch with a generated descriptor (`descriptor_auto_recovery`)
+    ///
+    /// If the stream closes on the first record, the batch is sent once more with the
+    /// alternate type mapping; a mapping the table accepts is kept for later batches.
+    async fn send_with_auto_recovery(
+        &self,
+        batch: &RecordBatch,
+        kept_segment: &KeptSegment,
+    ) -> Result<BatchTransmissionResult, ZerobusError> {
+        let mapping = self.type_mapping();
+        let alternate = mapping.alternate_for(batch.schema().as_ref());
+        let (accepted, result) = send_with_descriptor_recovery(
+            &self.config.table_name,
+            mapping,
+            alternate,
+            |attempt| async move {
+                if attempt != mapping {
+                    // The rejected descriptor must not stay the schema evolution baseline
+                    self.forget_active_descriptor();
+                }
+                let prepared = self
+                    .convert_batch_with_mapping(batch, None, attempt)
+                    .await?;
+                if self.config.conversion_errors_fatal {
+                    fatal_conversion_errors(&prepared)?;
+                }
+                self.send_prepared_internal(&prepared, None, kept_segment)
+                    .await
+            },
+        )
+        .await?;
+        if accepted != mapping {
+            *self
+                .type_mapping
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()) = accepted;
+        } else if alternate.is_some() && result.closed_on_first_record {
+            // The alternate was rejected too; later batches use the current mapping again
+            self.forget_active_descriptor();
+        }
+        Ok(result)
+    }
+
+    /// The batch's `error_key_column`, if configured and present
+    fn error_key_column(&self, batch: &RecordBatch) -> Option<arrow::array::ArrayRef> {
+        let column = self.config.error_key_column.as_deref()?;
+        let keys = batch.column_by_name(column).cloned();
+        if keys.is_none() {
+            debug!(
+                "error_key_column '{}' is not in the batch; row errors are not keyed",
+                column
+            );
+        }
+        keys
+    }
+
+    /// Suffix each row error with the row's key (`error_key_column`), if the batch has one
+    fn with_error_keys(
+        &self,
+        prepared: &PreparedBatch,
+        errors: Vec<(usize, ZerobusError)>,
+    ) -> Vec<(usize, ZerobusError)> {
+        let (Some(keys), Some(column)) = (&prepared.error_keys, &self.config.error_key_column)
+        else {
+            return errors;
+        };
+        let redaction = self.config.error_redaction();
+        let sensitive = redaction.sensitive_fields.contains(column);
+        let column = if sensitive {
+            crate::wrapper::conversion::mask_field_name(column)
+        } else {
+            column.clone()
+        };
+        errors
+            .into_iter()
+            .map(|(idx, e)| {
+                let value = if redaction.redact_values || sensitive {
+                    "<redacted>".to_string()
+                } else {
+                    key_value(keys, idx).unwrap_or_else(|| "null".to_string())
+                };
+                (
+                    idx,
+                    e.map_message(|m| format!("{} [key {}={}]", m, column, value)),
+                )
+            })
+            .collect()
+    }
+
+    /// Create the Zerobus SDK client on first use
+    async fn ensure_sdk(&self) -> Result<(), ZerobusError> {
+        let mut sdk_guard = self.sdk.lock().await;
+        if sdk_guard.is_none() {
+            let unity_catalog_url = self
+                .config
+                .unity_catalog_url
+                .as_ref()
+                .ok_or_else(|| {
+                    ZerobusError::ConfigurationError("unity_catalog_url is required".to_string())
+                })?
+                .clone();
+
+            let sdk = connect_with_retry(self.connect_retry_config.as_ref(), || {
+                crate::wrapper::zerobus::create_sdk(
+                    self.endpoint.to_string(),
+                    unity_catalog_url.clone(),
+                )
+            })
+            .await?;
+            *sdk_guard = Some(sdk);
+        }
+        Ok(())
+    }
+
+    /// Resolve the descriptor for a batch and convert its rows to Protobuf
+    ///
+    /// Shared by `send_batch_internal` and `prepare_batch`. Rows that fail conversion, or
+    /// are left unconverted by `max_failures_before_abort`, are returned as failed rows.
+    async fn convert_batch(
+        &self,
+        batch: &RecordBatch,
+        descriptor: Option<prost_types::DescriptorProto>,
+    ) -> Result<PreparedBatch, ZerobusError> {
+        self.convert_batch_with_mapping(batch, descriptor, self.type_mapping())
+            .await
+    }
+
+    /// `convert_batch`, generating the descriptor (if none is given) with `mapping`
+    async fn convert_batch_with_mapping(
+        &self,
+        batch: &RecordBatch,
+        descriptor: Option<prost_types::DescriptorProto>,
+        mapping: TypeMapping,
+    ) -> Result<PreparedBatch, ZerobusError> {
+        // Duplicate column names would collapse into one descriptor field whichever
+        // descriptor is used, silently dropping data
+        crate::wrapper::conversion::validate_unique_field_names(
+            batch.schema().as_ref(),
+            &self.config.field_name_transform,
+        )?;
+
+        // A generated descriptor describes the batch as rewritten by the type mapping
+        let mapped;
+        let batch = if descriptor.is_none() && mapping != TypeMapping::Standard {
+            mapped = mapping.apply(batch)?;
+            &mapped
+        } else {
+            batch
+        };
+
+        // Get Protobuf descriptor (use provided one or generate from Arrow schema)
+        let provided = descriptor.is_some();
+        let lookup_names = self.provided_field_names();
+        let descriptor = if let Some(provided_descriptor) = descriptor {
+            // Validate user-provided descriptor to prevent security issues
+            crate::wrapper::conversion::validate_protobuf_descriptor_with_limits(
+                &provided_descriptor,
+                &self.config.descriptor_limits(),
+            )
+            .map_err(|e| {
+                ZerobusError::ConfigurationError(format!("Invalid Protobuf descriptor: {}", e))
+            })?;
+            if self.config.validate_descriptor_schema {
+                crate::wrapper::conversion::validate_descriptor_against_schema_with_field_names(
+                    &provided_descriptor,
+                    batch.schema().as_ref(),
+                    &lookup_names,
+                )
+                .map_err(|mismatches| {
+                    let report: Vec<String> = mismatches.iter().map(|m| m.to_string()).collect();
+                    ZerobusError::ConfigurationError(format!(
+                        "Protobuf descriptor does not match Arrow schema ({} mismatches): {}",
+                        report.len(),
+                        report.join("; ")
+                    ))
+                })?;
+            }
+            self.check_column_count(&provided_descriptor, batch.schema().as_ref(), &lookup_names)?;
+            let descriptor_name = provided_descriptor.name.as_deref().unwrap_or("unknown");
+            info!("🔍 [DEBUG] Using provided Protobuf descriptor: name='{}', fields={}, nested_types={}", 
+                  descriptor_name, provided_descriptor.field.len(), provided_descriptor.nested_type.len());
+            provided_descriptor
+        } else {
+            self.generate_descriptor(batch.schema().as_ref())?
+        };
+
+        let field_names = if provided {
+            &lookup_names
+        } else {
+            &self.config.field_name_transform
+        };
+        let (descriptor, conversion_result) = match self
+            .encode_batch(batch, descriptor, field_names, !provided)
+            .await?
+        {
+            // A provided descriptor that converts no row at all most likely doesn't match the
+            // batch; retry once with one generated from the Arrow schema (opt-in)
+            (_, result)
+                if provided
+                    && self.config.descriptor_fallback
+                    && batch.num_rows() > 0
+                    && result.successful_bytes.is_empty() =>
+            {
+                warn!(
+                    "Provided Protobuf descriptor converted none of {} rows for table {}; retrying with a descriptor generated from the Arrow schema (descriptor_fallback). Check the provided descriptor",
+                    batch.num_rows(),
+                    self.config.table_name
+                );
+                let generated = self.generate_descriptor(batch.schema().as_ref())?;
+                self.encode_batch(batch, generated, &self.config.field_name_transform, true)
+                    .await?
+            }
+            encoded => encoded,
+        };
+
+        // Track conversion errors (will be merged with transmission errors later)
+        let mut failed_rows = conversion_result.failed_rows;
+        let max_tracked = self.config.max_tracked_failures;
+        let mut untracked_failures = cap_tracked_failures(&mut failed_rows, max_tracked);
+
+        // Rows left unconverted by an early abort are failed, so every row is still accounted for
+        if let Some(aborted_at) = conversion_result.aborted_at_row {
+            let max_failures = self.config.max_failures_before_abort.unwrap_or_default();
+            warn!(
+                "Conversion aborted at row {} of {}: more than {} rows failed (max_failures_before_abort)",
+                aborted_at,
+                batch.num_rows(),
+                max_failures
+            );
+            let aborted = aborted_at..batch.num_rows();
+            let room = max_tracked.map_or(usize::MAX, |max| max.saturating_sub(failed_rows.len()));
+            untracked_failures += aborted.len().saturating_sub(room);
+            failed_rows.extend(aborted.take(room).map(|idx| {
+                (
+                    idx,
+                    ZerobusError::ConversionError(format!(
+                        "Conversion aborted: row={} not converted, more than {} rows of the batch failed (max_failures_before_abort)",
+                        idx, max_failures
+                    )),
+                )
+            }));
+        }
+
+        Ok(PreparedBatch {
+            descriptor,
+            successful_bytes: conversion_result.successful_bytes,
+            failed_rows,
+            untracked_failures,
+            total_rows: batch.num_rows(),
+            batch_size_bytes: batch.get_array_memory_size(),
+            error_keys: self.error_key_column(batch),
+        })
+    }
+
+    /// Encode a batch's rows with `descriptor`, after reconciling it with the active one
+    ///
+    /// Returns the descriptor the rows were encoded with alongside the conversion result.
+    /// `generated` tells whether the descriptor was generated from the batch's schema.
+    async fn encode_batch(
+        &self,
+        batch: &RecordBatch,
+        descriptor: prost_types::DescriptorProto,
+        field_names: &FieldNameTransform,
+        generated: bool,
+    ) -> Result<
+        (
+            prost_types::DescriptorProto,
+            crate::wrapper::conversion::ProtobufConversionResult,
+        ),
+        ZerobusError,
+    > {
+        // Reconcile with earlier batches' schema (no-op unless schema evolution is enabled)
+        let descriptor = self
+            .evolve_active_descriptor(&self.stream, descriptor)
+            .await?;
+
+        // Write descriptor to file once per distinct descriptor (if either Arrow or Protobuf
+        // debug is enabled). DebugWriter skips descriptors it has already written and records
+        // new ones (e.g. after schema evolution) in the descriptors manifest.
+        // The Arrow schema is written alongside it, once per table.
+        if self.config.debug_arrow_enabled || self.config.debug_protobuf_enabled {
+            if let Some(ref debug_writer) = self.debug_writer {
+                // Schema metadata (e.g. source system/version) is carried for traceability
+                // in the debug copy only; the stream is created with `descriptor` as is
+                let traced;
+                let debug_descriptor = if generated && self.config.propagate_schema_metadata {
+                    traced = prost_types::DescriptorProto {
+                        options: crate::wrapper::conversion::schema_metadata_options(
+                            batch.schema().as_ref(),
+                            &self.config.schema_metadata_keys,
+                        ),
+                        ..descriptor.clone()
+                    };
+                    &traced
+                } else {
+                    &descriptor
+                };
+                if let Err(e) = debug_writer
+                    .write_descriptor(&self.config.table_name, debug_descriptor)
+                    .await
+                {
+                    warn!("Failed to write Protobuf descriptor to debug file: {}", e);
+                    // Don't fail the operation if descriptor writing fails
+                }
+                if let Err(e) = debug_writer
+                    .write_schema(&self.config.table_name, batch.schema().as_ref())
+                    .await
+                {
+                    warn!("Failed to write Arrow schema to debug file: {}", e);
+                    // Don't fail the operation if schema writing fails
+                }
+            }
+        }
+
+        // Null strings become the sentinel so they survive encoding as a value
+        let filled;
+        let batch = match &self.config.string_null_policy {
+            crate::config::StringNullPolicy::Sentinel(sentinel) => {
+                filled = crate::wrapper::conversion::fill_string_nulls(batch, sentinel)?;
+                &filled
+            }
+            _ => batch,
+        };
+
+        // Convert Arrow RecordBatch to Protobuf bytes (one per row)
+        // This now returns ProtobufConversionResult with per-row conversion errors
+        let conversion_result =
+            crate::wrapper::conversion::record_batch_to_protobuf_bytes_with_maps(
+                batch,
+                &self.field_maps_for(&descriptor),
+                field_names,
+                self.config.float_policy,
+                self.config.validate_utf8,
+                self.config.lenient_bool_coercion,
+                self.config.emit_defaults,
+                self.config.null_element_policy,
+                self.config.timestamp_target_unit,
+                self.config.error_redaction(),
+                self.config.max_failures_before_abort,
+                self.config.conversion_parallelism,
+                self.config.custom_encoder.as_ref(),
+            );
+
+        Ok((descriptor, conversion_result))
+    }
+
+    /// Transmit an already converted batch (without retry wrapper)
+    /// Returns per-row transmission information
+    ///
+    /// With a write-ahead log, the records are kept in a segment until every one is
+    /// acknowledged; `kept_segment` holds the segment of an earlier attempt of the send.
+    async fn send_prepared_internal(
+        &self,
+        prepared: &PreparedBatch,
+        row_results: Option<&RowResultSink>,
+        kept_segment: &KeptSegment,
+    ) -> Result<BatchTransmissionResult, ZerobusError> {
+        // Keep the records in the write-ahead log until acknowledged (if configured)
+        let segment = match &self.wal {
+            Some(wal)
+                if !self.config.zerobus_writer_disabled
+                    && !prepared.successful_bytes.is_empty() =>
+            {
+                let segment = wal
+                    .begin_segment(&prepared.descriptor, &prepared.successful_bytes)
+                    .await?;
+                // This attempt's segment holds every record, superseding an earlier one
+                kept_segment.replace(None).await;
+                Some(segment)
+            }
+            _ => None,
+        };
+        let result = self
+            .transmit_prepared(prepared, row_results, segment.as_ref())
+            .await;
+        if let Some(segment) = segment {
+            // Successful rows are a subset of the converted ones, so equal counts mean
+            // every record was delivered
+            let delivered = result
+                .as_ref()
+                .is_ok_and(|result| result.successful_count == prepared.successful_bytes.len());
+            kept_segment
+                .replace(finish_wal_segment(segment, delivered).await)
+                .await;
+        }
+        result
+    }
+
+    /// Transmit a prepared batch, recording acknowledgments in `segment` if given
+    async fn transmit_prepared(
+        &self,
+        prepared: &PreparedBatch,
+        row_results: Option<&RowResultSink>,
+        segment: Option<&crate::wrapper::wal::WalSegment>,
+    ) -> Result<BatchTransmissionResult, ZerobusError> {
+        if !self.config.zerobus_writer_disabled {
+            self.ensure_sdk().await?;
+        }
+        let descriptor = &prepared.descriptor;
+        let conversion_errors = self.with_error_keys(prepared, prepared.failed_rows.clone());
+
+        // Conversion failures are final - report them before any transmission starts
+        if let Some(sink) = row_results {
+            for (idx, e) in &conversion_errors {
+                sink.report(*idx, Err(e.clone())).await;
+            }
+        }
+
+        // A streaming sink reports every row itself; the result then only carries counts
+        let keep_rows = row_results.is_none_or(|sink| sink.keep_rows);
+
+        // Capture per-row serialized sizes before the bytes are handed to the SDK (opt-in)
+
+        // Capture per-row serialized sizes before the bytes are handed to the SDK (opt-in)
+        let row_sizes = if self.config.track_row_sizes && keep_rows {
+            Some(
+                prepared
+                    .successful_bytes
+                    .iter()
+                    .map(|(idx, bytes)| (*idx, bytes.len()))
+                    .collect::<Vec<_>>(),
+            )
+        } else {
+            None
+        };
+
+        // Write Protobuf bytes to debug file if Protobuf debug is enabled (only successful conversions)
+        // Flush after each batch to ensure files are immediately available for debugging
+        // CRITICAL: Write protobuf files BEFORE Zerobus write attempts, so we have them even if Zerobus fails
+        if self.config.debug_protobuf_enabled {
+            if let Some(ref debug_writer) = self.debug_writer {
+                info!(
+                    "Writing {} protobuf messages to debug file",
+                    prepared.successful_bytes.len()
+                );
+                let num_rows = prepared.successful_bytes.len();
+                for (idx, (_, bytes)) in prepared.successful_bytes.iter().enumerate() {
+                    // Flush immediately after last row in batch
+                    let flush_immediately = idx == num_rows - 1;
+                    if let Err(e) = debug_writer.write_protobuf(bytes, flush_immediately).await {
+                        warn!("Failed to write Protobuf debug file: {}", e);
+                        // Don't fail the operation if debug writing fails
+                    } else if flush_immediately {
+                        info!(
+                            "✅ Flushed protobuf debug file after batch ({} messages)",
+                            num_rows
+                        );
+                    }
+                }
+            } else {
+                warn!("⚠️  Debug writer is None - protobuf debug files will not be written. Check debug_protobuf_enabled and debug_output_dir config.");
+            }
+        }
+
+        // Check if writer is disabled - if so, skip all SDK calls and return success
+        // Performance: Operations complete in <50ms (excluding file I/O) when writer disabled
+        // This enables performance testing of conversion logic without network overhead
+        if self.config.zerobus_writer_disabled {
+            debug!(
+                "Writer disabled mode enabled - skipping Zerobus SDK calls. Debug files written successfully."
+            );
+            // Return success with conversion results tracked
+            // All successfully converted rows are considered successful when writer is disabled
+            if let Some(sink) = row_results {
+                for (idx, _) in &prepared.successful_bytes {
+                    sink.report(*idx, Ok(())).await;
+                }
+            }
+            let successful_indices: Vec<usize> = if keep_rows {
+                prepared
+                    .successful_bytes
+                    .iter()
+                    .map(|(idx, _)| *idx)
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            return Ok(BatchTransmissionResult {
+                successful_rows: successful_indices,
+                successful_count: prepared.successful_bytes.len(),
+                failed_rows: conversion_errors,
+                untracked_failures: prepared.untracked_failures,
+                row_sizes,
+                failed_row_bytes: (self.config.capture_failed_bytes && keep_rows).then(Vec::new),
+                ack_ids: None,
+                closed_on_first_record: false,
+            });
+        }
+
+        // Get SDK reference (lock is released, so we can lock again for stream creation)
+        // This is safe because we only reach here when writer is NOT disabled, so SDK was initialized above
+        let sdk_guard = self.sdk.lock().await;
+        let sdk = sdk_guard.as_ref().ok_or_else(|| {
+            ZerobusError::ConfigurationError(
+                "SDK not initialized - this should not happen".to_string(),
+            )
+        })?;
+
+        // 4. Ensure stream is created
+        // Expose secrets only when needed for API calls
+        let client_id = self
+            .config
+            .client_id
+            .as_ref()
+            .ok_or_else(|| ZerobusError::ConfigurationError("client_id is required".to_string()))?
+            .expose_secret()
+            .clone();
+        let client_secret = self
+            .config
+            .client_secret
+            .as_ref()
+            .ok_or_else(|| {
+                ZerobusError::ConfigurationError("client_secret is required".to_string())
+            })?
+            .expose_secret()
+            .clone();
+
+        // ========================================================================
+        // STEP 5: Check backoff conditions BEFORE attempting any writes
+        // ========================================================================
+        // CRITICAL: Check backoff BEFORE attempting writes, even if stream exists.
+        // This prevents writes during backoff period even if stream was created before
+        // backoff started. We check for:
+        // 1. Error 6006 backoff (server-initiated, pipeline blocked)
+        // 2. High failure rate backoff (client-initiated, >1% failure rate)
+        //
+        // Edge case: Backoff can start during batch processing, so we check again
+        // before each record in the loop below.
+        {
+            use crate::wrapper::zerobus::{check_error_6006_backoff, check_failure_rate_backoff};
+            check_error_6006_backoff(&self.config.table_name).await?;
+            check_failure_rate_backoff(&self.config.table_name).await?;
+        }
+
+        // A stream idle for longer than `idle_stream_timeout` may have been dropped by the
+        // server; recreate it now rather than finding out from a failed first record
+        self.close_idle_stream(&self.stream).await;
+        let _in_flight = SendInFlight::new(&self.sends_in_flight);
+
+        let connect = || {
+            crate::wrapper::zerobus::ensure_stream(
+                sdk,
+                self.config.table_name.clone(),
+                self.stream_descriptor(descriptor),
+                client_id.clone(),
+                client_secret.clone(),
+                self.config.explicit_schema_handshake,
+            )
+        };
+        // Boxed to keep the (deeply nested) send loop future off the caller's stack
+        let RowsSent {
+            successful_indices,
+            transmission_errors,
+            mut acknowledged,
+            closed_on_first_record,
+        } = Box::pin(self.send_rows(&self.stream, connect, prepared, row_results, segment)).await?;
+
+        // Keep the exact payloads of rows that failed transmission (opt-in)
+        let failed_row_bytes = if self.config.capture_failed_bytes && keep_rows {
+            let failed: std::collections::HashSet<usize> =
+                transmission_errors.iter().map(|(idx, _)| *idx).collect();
+            let mut bytes: Vec<(usize, Vec<u8>)> = prepared
+                .successful_bytes
+                .iter()
+                .filter(|(idx, _)| failed.contains(idx))
+                .cloned()
+                .collect();
+            bytes.sort_by_key(|(idx, _)| *idx);
+            Some(bytes)
+        } else {
+            None
+        };
+
+        // Merge conversion errors with transmission errors
+        let mut all_failed_rows = conversion_errors;
+        all_failed_rows.extend(self.with_error_keys(prepared, transmission_errors));
+        // Transmission failures are only final once stream recreation retries are done.
+        // Rows acknowledged in an earlier attempt were already reported as successful.
+        if let Some(sink) = row_results {
+            for (idx, e) in &all_failed_rows {
+                sink.report(*idx, Err(e.clone())).await;
+            }
+        }
+        // Keep the acknowledgment of each row reported as successful
+        let ack_ids = keep_rows.then(|| {
+            acknowledged.retain(|(idx, _)| successful_indices.contains(idx));
+            acknowledged.sort_by_key(|(idx, _)| *idx);
+            acknowledged.dedup_by_key(|(idx, _)| *idx);
+            acknowledged
+        });
+
+        all_failed_rows.sort_by_key(|(idx, _)| *idx);
+        let untracked_failures = prepared.untracked_failures
+            + cap_tracked_failures(&mut all_failed_rows, self.config.max_tracked_failures);
+
+        self.record_send();
+        Ok(BatchTransmissionResult {
+            successful_count: successful_indices.len(),
+            successful_rows: if keep_rows {
+                successful_indices.into_iter().collect()
+            } else {
+                Vec::new()
+            },
+            failed_rows: all_failed_rows,
+            untracked_failures,
+            row_sizes,
+            failed_row_bytes,
+            ack_ids,
+            closed_on_first_record,
+        })
+    }
+
+    /// Send a prepared batch's converted rows on `shared_stream`, recreating it with
+    /// `connect` when it closes
+    ///
+    /// Generic over the stream so the recreation logic can be exercised with a mock.
+    async fn send_rows<S, C, Fut>(
+        &self,
+        shared_stream: &Mutex<Option<S>>,
+        connect: C,
+        prepared: &PreparedBatch,
+        row_results: Option<&RowResultSink>,
+        segment: Option<&crate::wrapper::wal::WalSegment>,
+    ) -> Result<RowsSent, ZerobusError>
+    where
+        S: IngestStream,
+        C: Fn() -> Fut,
+        Fut: std::future::Future<Output = Result<S, ZerobusError>>,
+    {
+        // ========================================================================
+        // STEP 6: Write each row to Zerobus with stream recreation on failure
+        // ========================================================================
+        // This implements a retry loop that handles stream closure and recreation.
+        //
+        // Algorithm:
+        // 1. Ensure stream exists (create if None)
+        // 2. For each row in the batch:
+        //    a. Check backoff again (backoff can start during batch processing)
+        //    b. Drain pending futures if the buffer is full
+        //    c. Re-acquire stream lock (stream may have been cleared)
+        //    d. Recreate stream if it was cleared
+        //    e. Send row to Zerobus
+        //    f. Handle stream closure errors by clearing stream and retrying
+        // 3. If all rows succeed, break
+        // 4. If stream closed, retry up to MAX_STREAM_RECREATE_ATTEMPTS
+        //
+        // Edge cases handled:
+        // - Stream closed immediately after creation (first record fails)
+        //   → Indicates schema mismatch or validation error
+        // - Stream closed mid-batch
+        //   → Clear stream, recreate, and retry the rows not yet acknowledged
+        // - Backoff starts during batch processing
+        //   → Clear stream, break loop, return error
+        //
+        // Performance considerations:
+        // - Lock is released before async operations to avoid blocking
+        // - Stream is only recreated when necessary (not for every row)
+        // - Maximum retry attempts prevent infinite loops
+        //
+        // Thread safety:
+        // - Uses async Mutex to prevent blocking the runtime
+        // - Lock is held only when accessing/modifying stream
+        // - Lock is released before network I/O operations
+        // - Another task may clear or recreate the shared stream at any point, so every
+        //   pending acknowledgment is awaited for its actual outcome, and a row is never
+        //   resent once acknowledged
+        let mut retry_count = 0;
+        const MAX_STREAM_RECREATE_ATTEMPTS: u32 = 3;
+
+        let descriptor = &prepared.descriptor;
+        // Track per-row transmission results across retries: rows acknowledged in any
+        // attempt, and the errors of rows that were still failing in the last one
+        let transmission_errors: Vec<(usize, ZerobusError)>;
+        // Whether the batch was given up on after the stream closed on its first record
+        let mut closed_on_first_record = false;
+        let mut successful_indices: std::collections::BTreeSet<usize> =
+            std::collections::BTreeSet::new();
+        // Acknowledgment ids of every acknowledged row, across all attempts (not needed
+        // when a streaming sink reports the rows)
+        let keep_rows = row_results.is_none_or(|sink| sink.keep_rows);
+        let mut acknowledged: Vec<(usize, i64)> = Vec::new();
+
+        loop {
+            // Ensure stream exists and is valid
+            let mut stream_guard = shared_stream.lock().await;
+            if stream_guard.is_none() {
+                info!(
+                    "Stream not found, creating new stream for table: {}",
+                    self.config.table_name
+                );
+                let stream =
+                    connect_with_retry(self.connect_retry_config.as_ref(), &connect).await?;
+                *stream_guard = Some(stream);
+                self.metrics.record_stream_created();
+                info!("✅ Stream created successfully");
+            }
+            // Verify stream exists before dropping lock
+            if stream_guard.is_none() {
+                return Err(ZerobusError::ConnectionError(
+                    "Stream was None after creation - this should not happen".to_string(),
+                ));
+            }
+            drop(stream_guard); // Release lock before sending data
+
+            // Try to send every converted row not acknowledged by an earlier attempt
+            // Reset tracking for this retry attempt (but preserve across retries for final result)
+            let remaining_rows: Vec<&(usize, Vec<u8>)> = prepared
+                .successful_bytes
+                .iter()
+                .filter(|(idx, _)| !successful_indices.contains(idx))
+                .collect();
+            let mut attempt_transmission_errors: Vec<(usize, ZerobusError)> = Vec::new();
+            let mut attempt_successful_indices: Vec<usize> = Vec::new();
+            let mut all_succeeded = true;
+            let mut failed_at_idx = 0;
+            // The first record failed because the table schema is not yet propagated
+            let mut schema_not_ready = false;
+            // The stream closed on the batch's first record
+            let mut first_record_closed = false;
+
+            // Batch futures for better throughput: collect futures and await in batches
+            // This allows the SDK to queue multiple records before flushing, improving performance
+            // Flush every `max_pending_futures` records (default: 1000) or every 10MB, and
+            // once the oldest buffered record is `max_buffer_age` old (if set)
+            let max_pending_futures = self.config.max_pending_futures;
+            const BATCH_SIZE_BYTES: usize = 10 * 1024 * 1024;
+            // Store futures with their row indices
+            let mut pending_futures: Vec<(usize, IngestFuture)> = Vec::new();
+            let mut total_bytes_buffered = 0usize;
+            let mut oldest_buffered_at: Option<tokio::time::Instant> = None;
+
+            // Process only successfully converted rows
+            for (position, (original_row_idx, bytes)) in remaining_rows.iter().copied().enumerate()
+            {
+                let idx = *original_row_idx;
+                // ========================================================================
+                // STEP 6a: Check backoff before each record
+                // ========================================================================
+                // Edge case: Backoff can start during batch processing (e.g., another thread
+                // encountered error 6006 or high failure rate). We check before each record to prevent writes
+                // during backoff period.
+                {
+                    use crate::wrapper::zerobus::{
+                        check_error_6006_backoff, check_failure_rate_backoff,
+                    };
+                    if let Err(_backoff_err) =
+                        check_error_6006_backoff(&self.config.table_name).await
+                    {
+                        // Backoff error: track per-row and break (backoff is batch-level concern)
+                        // Clear stream so it gets recreated after backoff
+                        let mut stream_guard = shared_stream.lock().await;
+                        *stream_guard = None;
+                        drop(stream_guard);
+                        // Backoff affects remaining rows, but we've processed up to idx
+                        // Mark remaining rows as affected by backoff
+                        for (orig_idx, _) in &remaining_rows[position..] {
+                            attempt_transmission_errors.push((
+                                *orig_idx,
+                                ZerobusError::ConnectionError(
+                                    "Backoff period active - row processing stopped".to_string(),
+                                ),
+                            ));
+                        }
+                        all_succeeded = false;
+                        failed_at_idx = idx;
+                        break;
+                    }
+                    // Also check failure rate backoff
+                    if let Err(_backoff_err) =
+                        check_failure_rate_backoff(&self.config.table_name).await
+                    {
+                        // Backoff error: track per-row and break (backoff is batch-level concern)
+                        // Clear stream so it gets recreated after backoff
+                        let mut stream_guard = shared_stream.lock().await;
+                        *stream_guard = None;
+                        drop(stream_guard);
+                        // Backoff affects remaining rows, but we've processed up to idx
+                        // Mark remaining rows as affected by backoff
+                        for (orig_idx, _) in &remaining_rows[position..] {
+                            attempt_transmission_errors.push((
+                                *orig_idx,
+                                ZerobusError::ConnectionError(
+                                    "High failure rate backoff active - row processing stopped"
+                                        .to_string(),
+                                ),
+                            ));
+                        }
+                        all_succeeded = false;
+                        failed_at_idx = idx;
+                        break;
+                    }
+                }
+
+                // Client-side rate limit (shared per-table), applied only once no backoff
+                // is active so backoff takes precedence
+                if let Some(records_per_sec) = self.config.rate_limit_records_per_sec {
+                    crate::wrapper::zerobus::acquire_rate_limit(
+                        &self.config.table_name,
+                        records_per_sec,
+                    )
+                    .await;
+                }
+
+                // ========================================================================
+                // STEP 6b: Drain pending futures before buffering another record
+                // ========================================================================
+                // Checked *before* the record is handed to the stream so the buffer is a
+                // hard cap: `pending_futures` never exceeds `max_pending_futures` and the
+                // buffered payload never exceeds BATCH_SIZE_BYTES (unless a single record
+                // is larger on its own), even if the byte estimate is off. A slow trickle of
+                // records (e.g. under a rate limit) is also drained once the oldest buffered
+                // record reaches `max_buffer_age`, bounding its latency.
+                if should_drain_pending_futures(
+                    pending_futures.len(),
+                    total_bytes_buffered,
+                    bytes.len(),
+                    max_pending_futures,
+                    BATCH_SIZE_BYTES,
+                ) || buffer_age_exceeded(oldest_buffered_at, self.config.max_buffer_age)
+                {
+                    // Flush stream to send buffered records, then await all pending futures.
+                    // Records may reach the server before a flush fails, so a flush error
+                    // leaves their outcome to the acknowledgments (as at the end of a batch)
+                    let (flush_error, outcomes) = flush_and_await_pending(
+                        shared_stream,
+                        std::mem::take(&mut pending_futures),
+                    )
+                    .await;
+                    if let Some(e) = flush_error {
+                        warn!(
+                            "Failed to flush Zerobus stream during batch, using acknowledgments of {} pending records: {}",
+                            outcomes.len(),
+                            e
+                        );
+                    }
+
+                    // Track results of the pending futures
+                    acknowledge_in_wal(segment, &outcomes).await;
+                    for (pending_idx, outcome) in outcomes {
+                        match outcome {
+                            Ok(ack_id) => {
+                                debug!(
+                                    "✅ Successfully sent record to Zerobus stream (row {}, ack_id={})",
+                                    pending_idx, ack_id
+                                );
+                                attempt_successful_indices.push(pending_idx);
+                                if keep_rows {
+                                    acknowledged.push((pending_idx, ack_id));
+                                }
+                                if let Some(sink) = row_results {
+                                    sink.report(pending_idx, Ok(())).await;
+                                }
+                            }
+                            Err(e) => {
+                                let err_msg = format!("{}", e);
+                                // Check if stream is closed
+                                if err_msg.contains("Stream is closed")
+                                    || err_msg.contains("Stream closed")
+                                {
+                                    let is_first = pending_idx == 0;
+                                    error!(
+                                        "Stream closed: row={}, first_record={}, error={}",
+                                        pending_idx, is_first, err_msg
+                                    );
+                                    if is_first {
+                                        first_record_closed = true;
+                                        schema_not_ready =
+                                            crate::wrapper::zerobus::is_schema_not_ready(&err_msg);
+                                        error!(
+                                            "Diagnostics: Stream closed during batch processing"
+                                        );
+                                        error!("Possible causes:");
+                                        error!("  1. Schema mismatch between descriptor and table");
+                                        error!("  2. Validation error");
+                                        error!("  3. Server-side issue");
+                                    }
+                                    // Clear stream and break to retry
+                                    let mut stream_guard = shared_stream.lock().await;
+                                    *stream_guard = None;
+                                    drop(stream_guard);
+                                    attempt_transmission_errors.push((
+                                        pending_idx,
+                                        ZerobusError::ConnectionError(format!(
+                                            "Stream closed: row={}, error={}",
+                                            pending_idx, err_msg
+                                        )),
+                                    ));
+                                    all_succeeded = false;
+                                    failed_at_idx = pending_idx;
+                                } else {
+                                    // Non-stream-closure errors
+                                    let message = format!(
+                                        "Record ingestion failed: row={}, error={}",
+                                        pending_idx, e
+                                    );
+                                    attempt_transmission_errors.push((
+                                        pending_idx,
+                                        crate::wrapper::zerobus::throttled_error(&e, &message)
+                                            .unwrap_or(ZerobusError::TransmissionError(message)),
+                                    ));
+                                    all_succeeded = false;
+                                }
+                            }
+                        }
+                    }
+                    total_bytes_buffered = 0;
+                    oldest_buffered_at = None;
+                }
+
+                // ========================================================================
+                // STEP 6c: Re-acquire stream lock and ensure stream exists
+                // ========================================================================
+                // We re-acquire the lock for each record because:
+                // 1. Stream may have been cleared by error handling in previous iteration
+                // 2. Lock was released before async operations to avoid blocking
+                // 3. Multiple threads may be sending batches concurrently
+                //
+                // Performance: Lock is held only briefly, released before network I/O.
+                let mut stream_guard = shared_stream.lock().await;
+                if stream_guard.is_none() {
+                    // Stream was cleared (e.g., by error handling), recreate it
+                    info!(
+                        "Stream was cleared, recreating for table: {}",
+                        self.config.table_name
+                    );
+                    let stream =
+                        connect_with_retry(self.connect_retry_config.as_ref(), &connect).await?;
+                    *stream_guard = Some(stream);
+                    self.metrics.record_stream_created();
+                }
+                let stream = stream_guard.as_mut().ok_or_else(|| {
+                    ZerobusError::ConnectionError(
+                        "Stream was None after recreation - this should not happen".to_string(),
+                    )
+                })?;
+
+                // ========================================================================
+                // STEP 6d: Send bytes to Zerobus stream (batched for performance)
+                // ========================================================================
+                // The Zerobus SDK's ingest_record returns a Future that resolves when acknowledged.
+                // We collect futures and await them in batches for better throughput.
+                //
+                // Error handling:
+                // - Stream closed errors: Clear stream, mark failure, break loop to retry
+                // - Other errors: Track per-row and continue
+                // - First record failures: Log detailed diagnostics for schema issues
+                match stream.ingest(bytes.clone()).await {
+                    Ok(ingest_future) => {
+                        // Release lock before collecting future to avoid blocking
+                        drop(stream_guard);
+
+                        // Collect future for batch processing
+                        // Box the future to store in Vec (type erasure for different future types)
+                        if track_acknowledgment(
+                            self.config.delivery_mode,
+                            &mut pending_futures,
+                            idx,
+                            ingest_future,
+                        ) {
+                            total_bytes_buffered += bytes.len();
+                            oldest_buffered_at.get_or_insert_with(tokio::time::Instant::now);
+                        } else {
+                            // Fire-and-forget: the row counts as sent once submitted
+                            attempt_successful_indices.push(idx);
+                            if let Some(sink) = row_results {
+                                sink.report(idx, Ok(())).await;
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        let err_msg = format!("{}", e);
+                        // Check if stream is closed (indicates server-side closure)
+                        if err_msg.contains("Stream is closed") || err_msg.contains("Stream closed")
+                        {
+                            // Standardized error logging with context
+                            let is_first = idx == 0;
+                            error!(
+                                "Stream closed: row={}, first_record={}, error={}",
+                                idx, is_first, err_msg
+                            );
+                            if is_first {
+                                // First record failure indicates schema/validation issues
+                                error!("Diagnostics: This is the FIRST record - stream closed immediately");
+                                error!("Possible causes:");
+                                error!("  1. Schema mismatch between descriptor and table");
+                                error!("  2. Validation error on first record");
+                                error!("  3. Table schema not yet propagated");
+                                error!(
+                                    "Descriptor info: fields={}, nested_types={}",
+                                    descriptor.field.len(),
+                                    descriptor.nested_type.len()
+                                );
+                            }
+                            // Stream closure error: track per-row and continue
+                            // Clear stream so it gets recreated on next iteration
+                            *stream_guard = None;
+                            drop(stream_guard);
+                            let stream_error = ZerobusError::ConnectionError(format!(
+                                "Stream closed: row={}, error={}",
+                                idx, err_msg
+                            ));
+                            attempt_transmission_errors.push((idx, stream_error));
+                            all_succeeded = false;
+                            failed_at_idx = idx;
+                            // Stop handing rows to the closed stream; the pending futures are
+                            // still awaited below, and unacknowledged rows are retried (after a
+                            // longer wait for a table that is not ready yet)
+                            first_record_closed = is_first;
+                            schema_not_ready =
+                                is_first && crate::wrapper::zerobus::is_schema_not_ready(&err_msg);
+                            break;
+                        } else {
+                            // Non-stream-closure errors: track per-row and continue
+                            let transmission_error = ZerobusError::ConnectionError(format!(
+                                "Record creation failed: row={}, error={}",
+                                idx, e
+                            ));
+                            attempt_transmission_errors.push((idx, transmission_error));
+                            all_succeeded = false;
+                            // Continue processing remaining rows instead of returning immediately
+                        }
+                    }
+                }
+            }
+
+            // Every row has been handed to the stream; a `submit_batch` caller can move on
+            // while the remaining acknowledgments are awaited below
+            if let Some(sink) = row_results {
+                sink.mark_submitted();
+            }
+
+            // CRITICAL: Flush and await any remaining pending futures before proceeding
+            // This ensures all queued records are sent and acknowledged, even if we broke early
+            if !pending_futures.is_empty() {
+                // Always flush remaining records before awaiting acknowledgments. The stream
+                // may have been closed, cleared or recreated meanwhile (possibly by another
+                // task); records sent before that are still acknowledged, so every future is
+                // awaited for its actual status rather than failed outright
+                let pending_count = pending_futures.len();
+                let (flush_error, outcomes) =
+                    flush_and_await_pending(shared_stream, std::mem::take(&mut pending_futures))
+                        .await;
+                match flush_error {
+                    Some(e) => warn!(
+                        "Failed to flush Zerobus stream for remaining records (stream may be closed): {}",
+                        e
+                    ),
+                    None => debug!(
+                        "✅ Flushed Zerobus stream for {} remaining pending futures",
+                        pending_count
+                    ),
+                }
+
+                // CRITICAL: Always await all pending futures to get acknowledgment status
+                // Even if stream is closed, we need to know which records succeeded/failed
+                acknowledge_in_wal(segment, &outcomes).await;
+                for (pending_idx, outcome) in outcomes {
+                    match outcome {
+                        Ok(ack_id) => {
+                            debug!(
+                                "✅ Successfully acknowledged record (row {}, ack_id={})",
+                                pending_idx, ack_id
+                            );
+                            attempt_successful_indices.push(pending_idx);
+                            if keep_rows {
+                                acknowledged.push((pending_idx, ack_id));
+                            }
+                            if let Some(sink) = row_results {
+                                sink.report(pending_idx, Ok(())).await;
+                            }
+                        }
+                        Err(e) => {
+                            let err_msg = format!("{}", e);
+                            if err_msg.contains("Stream is closed")
+                                || err_msg.contains("Stream closed")
+                            {
+                                // Stream was closed - clear it and mark as failed
+                                if pending_idx == 0 {
+                                    first_record_closed = true;
+                                    schema_not_ready =
+                                        crate::wrapper::zerobus::is_schema_not_ready(&err_msg);
+                                }
+                                let mut stream_guard = shared_stream.lock().await;
+                                *stream_guard = None;
+                                drop(stream_guard);
+                                attempt_transmission_errors.push((
+                                    pending_idx,
+                                    ZerobusError::ConnectionError(format!(
+                                        "Stream closed before acknowledgment: row={}, error={}",
+                                        pending_idx, err_msg
+                                    )),
+                                ));
+                                all_succeeded = false;
+                            } else {
+                                // Other errors (network, timeout, etc.)
+                                let message = format!(
+                                    "Record acknowledgment failed: row={}, error={}",
+                                    pending_idx, e
+                                );
+                                attempt_transmission_errors.push((
+                                    pending_idx,
+                                    crate::wrapper::zerobus::throttled_error(&e, &message)
+                                        .unwrap_or(ZerobusError::TransmissionError(message)),
+                                ));
+                                all_succeeded = false;
+                            }
+                        }
+                    }
+                }
+            }
 
-        // ========================================================================
-        // STEP 5: Check backoff conditions BEFORE attempting any writes
-        // ========================================================================
-        // CRITICAL: Check backoff BEFORE attempting writes, even if stream exists.
-        // This prevents writes during backoff period even if stream was created before
-        // backoff started. We check for:
-        // 1. Error 6006 backoff (server-initiated, pipeline blocked)
-        // 2. High failure rate backoff (client-initiated, >1% failure rate)
-        //
-        // Edge case: Backoff can start during batch processing, so we check again
-        // before each record in the loop below.
+            // Rows acknowledged in this attempt are never resent by a later one
+            successful_indices.extend(attempt_successful_indices.iter().copied());
+
+            // ========================================================================
+            // STEP 6e: Handle retry logic
+            // ========================================================================
+            // If all rows succeeded, we're done. Otherwise, retry with stream recreation.
+            // The retry mechanism handles transient stream closure issues.
+            //
+            // Edge case: If stream closes repeatedly, it may indicate:
+            // - Schema mismatch (descriptor doesn't match table schema)
+            // - Server-side validation errors
+            // - Network issues causing stream closure
+            //
+            // Performance: Small delay (100ms) prevents tight retry loops.
+            if all_succeeded {
+                // All rows sent successfully - flush the stream. The SDK sends queued records
+                // in the background; its flush only waits until everything ingested so far
+                // is acknowledged, which makes the batch's records durable before returning.
+                // (with flush_every_batch this happens after the loop, whatever the outcome)
+                // (fire-and-forget skips it, since it would wait for acknowledgments)
+                if !self.config.flush_every_batch
+                    && self.config.delivery_mode == DeliveryMode::AtLeastOnce
+                {
+                    let mut stream_guard = shared_stream.lock().await;
+                    if let Some(ref mut stream) = *stream_guard {
+                        if let Err(e) = stream.flush_stream().await {
+                            error!("Failed to flush Zerobus stream after batch: {}", e);
+                            // Don't fail the entire batch if flush fails - records may still be in transit
+                            // But log the error for monitoring
+                        } else {
+                            debug!(
+                                "✅ Flushed Zerobus stream after sending {} records",
+                                attempt_successful_indices.len()
+                            );
+                        }
+                    }
+                }
+                // Rows still failing in this attempt are the final failures
+                transmission_errors = attempt_transmission_errors;
+                break;
+            } else {
+                // Some rows failed due to stream closure - retry with stream recreation
+                retry_count += 1;
+                if retry_count > MAX_STREAM_RECREATE_ATTEMPTS {
+                    // Exhausted retry attempts - use what we have from this attempt
+                    let mut final_transmission_errors = attempt_transmission_errors;
+                    // Mark remaining rows as failed due to stream closure
+                    for (idx, _) in prepared.successful_bytes.iter() {
+                        if !successful_indices.contains(idx)
+                            && !final_transmission_errors.iter().any(|(i, _)| i == idx)
+                        {
+                            final_transmission_errors.push((*idx, ZerobusError::ConnectionError(format!(
+                                "Stream recreation exhausted: row={}, possible_causes='schema_mismatch,validation_error,server_issue'",
+                                idx
+                            ))));
+                        }
+                    }
+                    transmission_errors = final_transmission_errors;
+                    closed_on_first_record = first_record_closed && successful_indices.is_empty();
+                    break;
+                }
+                warn!(
+                    "Stream recreation retry: attempt={}/{}, failed_at_row={}",
+                    retry_count, MAX_STREAM_RECREATE_ATTEMPTS, failed_at_idx
+                );
+                // Growing, jittered delay before retry to avoid tight (and fleet-wide
+                // lockstep) retry loops, or a longer one for a table whose schema is not yet
+                // propagated
+                let backoff = self
+                    .stream_recreate_backoff
+                    .calculate_delay_with_rng(retry_count - 1, &mut rand::thread_rng());
+                wait_before_stream_recreate(
+                    schema_not_ready,
+                    self.config.schema_propagation_wait,
+                    backoff,
+                )
+                .await;
+                // The next attempt starts with fresh tracking and retries only the rows
+                // not acknowledged so far
+            }
+        }
+
+        if self.config.flush_every_batch {
+            flush_after_batch(shared_stream).await;
+        }
+
+        Ok(RowsSent {
+            successful_indices,
+            transmission_errors,
+            acknowledged,
+            closed_on_first_record,
+        })
+    }
+
+    /// Close the stream if no batch was sent for longer than `idle_stream_timeout`
+    ///
+    /// The next record then creates a new stream. Never closes the stream while another
+    /// batch is sending on it. Returns whether the idle timeout had expired.
+    async fn close_idle_stream<S: IngestStream>(&self, shared_stream: &Mutex<Option<S>>) -> bool {
+        let Some(timeout) = self.config.idle_stream_timeout else {
+            return false;
+        };
+
+        // Checked under the stream lock: a send starting after this check finds the
+        // stream gone and creates a new one
+        let mut stream_guard = shared_stream.lock().await;
+        if self
+            .sends_in_flight
+            .load(std::sync::atomic::Ordering::SeqCst)
+            > 0
         {
-            use crate::wrapper::zerobus::{check_error_6006_backoff, check_failure_rate_backoff};
-            check_error_6006_backoff(&self.config.table_name).await?;
-            check_failure_rate_backoff(&self.config.table_name).await?;
+            return false;
+        }
+        let idle = self
+            .last_send
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .is_some_and(|last_send| last_send.elapsed() > timeout);
+        if !idle {
+            return false;
         }
 
-        // ========================================================================
-        // STEP 6: Write each row to Zerobus with stream recreation on failure
-        // ========================================================================
-        // This implements a retry loop that handles stream closure and recreation.
-        //
-        // Algorithm:
-        // 1. Ensure stream exists (create if None)
-        // 2. For each row in the batch:
-        //    a. Check backoff again (backoff can start during batch processing)
-        //    b. Re-acquire stream lock (stream may have been cleared)
-        //    c. Recreate stream if it was cleared
-        //    d. Send row to Zerobus
-        //    e. Handle stream closure errors by clearing stream and retrying
-        // 3. If all rows succeed, break
-        // 4. If stream closed, retry up to MAX_STREAM_RECREATE_ATTEMPTS
-        //
-        // Edge cases handled:
-        // - Stream closed immediately after creation (first record fails)
-        //   → Indicates schema mismatch or validation error
-        // - Stream closed mid-batch
-        //   → Clear stream, recreate, and retry from failed row
-        // - Backoff starts during batch processing
-        //   → Clear stream, break loop, return error
-        //
-        // Performance considerations:
-        // - Lock is released before async operations to avoid blocking
-        // - Stream is only recreated when necessary (not for every row)
-        // - Maximum retry attempts prevent infinite loops
-        //
-        // Thread safety:
-        // - Uses async Mutex to prevent blocking the runtime
-        // - Lock is held only when accessing/modifying stream
-        // - Lock is released before network I/O operations
-        let mut retry_count = 0;
-        const MAX_STREAM_RECREATE_ATTEMPTS: u32 = 3;
+        if let Some(mut stream) = stream_guard.take() {
+            info!(
+                "Stream for table {} idle for more than {:?}, recreating it",
+                self.config.table_name, timeout
+            );
+            close_with_timeout(stream.close_stream(), self.config.shutdown_timeout).await;
+        }
+        true
+    }
+
+    /// Record that a batch finished sending, for `idle_stream_timeout`
+    fn record_send(&self) {
+        *self
+            .last_send
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(tokio::time::Instant::now());
+    }
+
+    /// Flush any pending operations and ensure data is transmitted
+    ///
+    /// Use `flush_with_outcome` to find out whether a stream was actually flushed.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if flush operation fails.
+    pub async fn flush(&self) -> Result<(), ZerobusError> {
+        self.flush_with_outcome().await.map(|_| ())
+    }
+
+    /// Flush any pending operations and report what was flushed
+    ///
+    /// Same as `flush`, but tells the caller whether a Zerobus stream and the debug files
+    /// were flushed. A flush before the first `send_batch` finds no stream and sends
+    /// nothing; outside writer disabled mode this is also logged as a warning, since it
+    /// usually means the caller expected data to have been sent.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `FlushOutcome` describing what was flushed.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if flush operation fails.
+    pub async fn flush_with_outcome(&self) -> Result<FlushOutcome, ZerobusError> {
+        // CRITICAL: Flush Zerobus stream to ensure buffered records are sent
+        // The SDK buffers records internally and requires flush() to transmit them
+        let stream_flushed = {
+            let mut stream_guard = self.stream.lock().await;
+            if let Some(ref mut stream) = *stream_guard {
+                stream.flush().await.map_err(|e| {
+                    ZerobusError::ConnectionError(format!("Failed to flush Zerobus stream: {}", e))
+                })?;
+                debug!("✅ Flushed Zerobus stream");
+                true
+            } else {
+                if !self.config.zerobus_writer_disabled {
+                    warn!(
+                        "flush() called with no active Zerobus stream for table {} - nothing was sent",
+                        self.config.table_name
+                    );
+                }
+                false
+            }
+        };
+
+        // Flush debug files if enabled
+        let mut debug_flushed = false;
+        if let Some(ref debug_writer) = self.debug_writer {
+            match debug_writer.flush().await {
+                Ok(()) => debug_flushed = true,
+                Err(e) => warn!("Failed to flush debug files: {}", e),
+            }
+        }
+
+        // Flush observability if enabled
+        if let Some(ref obs) = self.observability {
+            obs.flush().await?;
+        }
+
+        // Flush mirror table (failures are only fatal if mirror failures are)
+        if let Some(ref mirror) = self.mirror {
+            if let Err(e) = Box::pin(mirror.flush()).await {
+                if self.config.mirror_failures_fatal {
+                    return Err(e);
+                }
+                warn!(
+                    "Failed to flush mirror table {}: {}",
+                    mirror.config.table_name, e
+                );
+            }
+        }
+
+        Ok(FlushOutcome {
+            stream_flushed,
+            debug_flushed,
+        })
+    }
+
+    /// Get the Zerobus endpoint this wrapper connects to
+    ///
+    /// # Returns
+    ///
+    /// The configured endpoint after trimming surrounding whitespace.
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Get the target table name
+    pub fn table_name(&self) -> &str {
+        &self.config.table_name
+    }
+
+    /// Check whether writer disabled mode is enabled (no data is sent to Zerobus)
+    pub fn writer_disabled(&self) -> bool {
+        self.config.zerobus_writer_disabled
+    }
+
+    /// Get the current backoff state for this wrapper's table
+    ///
+    /// Reads the per-table 6006 and failure-rate backoff state without triggering a send,
+    /// for dashboards or deciding whether manual intervention is needed.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `BackoffStatus` with the active backoff kind (if any), the time remaining,
+    /// and the current failure rate.
+    pub fn backoff_status(&self) -> BackoffStatus {
+        crate::wrapper::zerobus::backoff_status(&self.config.table_name)
+    }
+
+    /// Get the current network/transmission failure rate for this wrapper's table
+    ///
+    /// Reads the shared failure-rate tracking without sending; see
+    /// `wrapper::zerobus::failure_rate`.
+    ///
+    /// # Returns
+    ///
+    /// Returns the fraction of rows that failed in the current window (0.0 if none).
+    pub fn failure_rate(&self) -> f64 {
+        crate::wrapper::zerobus::failure_rate(&self.config.table_name)
+    }
+
+    /// Check whether sending to this wrapper's table now would run into a backoff
+    ///
+    /// Lets adaptive producers throttle themselves before tripping a backoff; see
+    /// `wrapper::zerobus::would_backoff`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if a backoff is active or the current failure rate is above the
+    /// 1% backoff threshold.
+    pub fn would_backoff(&self) -> bool {
+        crate::wrapper::zerobus::would_backoff(&self.config.table_name)
+    }
+
+    /// Get the most recent batch-level error for a table
+    ///
+    /// Errors are recorded per table by every wrapper in the process, so this also
+    /// answers for other tables (including this wrapper's mirror table). See
+    /// `wrapper::zerobus::last_error`.
+    ///
+    /// # Arguments
+    ///
+    /// * `table_name` - Table to query
+    ///
+    /// # Returns
+    ///
+    /// Returns when the error was recorded and the error, or `None` if there was none.
+    pub fn last_error(&self, table_name: &str) -> Option<(std::time::Instant, ZerobusError)> {
+        crate::wrapper::zerobus::last_error(table_name)
+    }
+
+    /// Export this table's backoff and failure-rate state
+    ///
+    /// Backoff state is kept in memory, so a supervisor that restarts the process can
+    /// persist the returned snapshot (it is serde-serializable) and hand it to
+    /// `import_backoff_state` on the new wrapper, keeping a failing table in backoff.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `BackoffStateSnapshot` for this wrapper's table.
+    pub fn export_backoff_state(&self) -> BackoffStateSnapshot {
+        crate::wrapper::zerobus::export_backoff_state(&self.config.table_name)
+    }
+
+    /// Restore backoff and failure-rate state exported by `export_backoff_state`
+    ///
+    /// Expired backoffs are ignored and active ones are only extended, never shortened.
+    ///
+    /// # Arguments
+    ///
+    /// * `snapshot` - State exported for this wrapper's table
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigurationError` if the snapshot belongs to a different table.
+    pub fn import_backoff_state(
+        &self,
+        snapshot: &BackoffStateSnapshot,
+    ) -> Result<(), ZerobusError> {
+        if snapshot.table_name != self.config.table_name {
+            return Err(ZerobusError::ConfigurationError(format!(
+                "Backoff state is for table '{}', not '{}'",
+                snapshot.table_name, self.config.table_name
+            )));
+        }
+        crate::wrapper::zerobus::import_backoff_state(snapshot);
+        Ok(())
+    }
+
+    /// Render cumulative send metrics in Prometheus text exposition format
+    ///
+    /// Covers every batch sent since the wrapper was created: batches, rows succeeded,
+    /// rows failed by error type, bytes, retries, stream reconnects, and a batch latency
+    /// histogram. Metrics are tracked whether or not observability is enabled, so they can
+    /// be served from a scrape endpoint without an OTLP collector. Names are prefixed with
+    /// the telemetry namespace (default: `zerobus`) and labelled with the table name.
+    /// Batches sent to the mirror table are not included.
+    ///
+    /// # Returns
+    ///
+    /// Returns the metrics as Prometheus text.
+    pub fn metrics_prometheus(&self) -> String {
+        self.metrics.render(
+            self.config
+                .telemetry_namespace
+                .as_deref()
+                .unwrap_or(crate::observability::DEFAULT_TELEMETRY_NAMESPACE),
+            &self.config.table_name,
+        )
+    }
 
-        // Track per-row transmission results across retries
-        // These will be assigned from attempt_* variables after processing completes
-        let mut transmission_errors: Vec<(usize, ZerobusError)> = Vec::new();
-        let mut successful_indices: Vec<usize> = Vec::new();
+    /// Check that the wrapper can send, without sending any data
+    ///
+    /// Intended for readiness probes. Outside writer disabled mode, the check fails if the
+    /// table is in backoff, the SDK client cannot be created, or an OAuth token cannot be
+    /// obtained from Unity Catalog with the configured credentials. No stream is opened and
+    /// no record is written. In writer disabled mode the wrapper is always healthy.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `HealthStatus`; failures are reported in it rather than as an error.
+    pub async fn health_check(&self) -> HealthStatus {
+        if self.config.zerobus_writer_disabled {
+            return HealthStatus {
+                healthy: true,
+                latency_ms: None,
+                error: None,
+                mode: HealthCheckMode::Disabled,
+            };
+        }
 
-        loop {
-            // Ensure stream exists and is valid
-            let mut stream_guard = self.stream.lock().await;
-            if stream_guard.is_none() {
-                info!(
-                    "Stream not found, creating new stream for table: {}",
-                    self.config.table_name
-                );
-                let stream = crate::wrapper::zerobus::ensure_stream(
-                    sdk,
-                    self.config.table_name.clone(),
-                    descriptor.clone(),
-                    client_id.clone(),
-                    client_secret.clone(),
-                )
-                .await?;
-                *stream_guard = Some(stream);
-                info!("✅ Stream created successfully");
-            }
-            // Verify stream exists before dropping lock
-            if stream_guard.is_none() {
-                return Err(ZerobusError::ConnectionError(
-                    "Stream was None after creation - this should not happen".to_string(),
-                ));
-            }
-            drop(stream_guard); // Release lock before sending data
+        let start_time = std::time::Instant::now();
+        let outcome = self.check_connectivity().await;
+        let latency_ms = start_time.elapsed().as_millis() as u64;
+        if let Err(e) = &outcome {
+            warn!(
+                "Health check failed for table {}: {}",
+                self.config.table_name, e
+            );
+        }
+        HealthStatus {
+            healthy: outcome.is_ok(),
+            latency_ms: Some(latency_ms),
+            error: outcome.err(),
+            mode: HealthCheckMode::Live,
+        }
+    }
 
-            // Try to send all successfully converted rows
-            // Reset tracking for this retry attempt (but preserve across retries for final result)
-            let mut attempt_transmission_errors: Vec<(usize, ZerobusError)> = Vec::new();
-            let mut attempt_successful_indices: Vec<usize> = Vec::new();
-            let mut all_succeeded = true;
-            let mut failed_at_idx = 0;
+    /// Checks behind `health_check`: backoff, SDK client and an OAuth token
+    async fn check_connectivity(&self) -> Result<(), ZerobusError> {
+        crate::wrapper::zerobus::check_error_6006_backoff(&self.config.table_name).await?;
+        crate::wrapper::zerobus::check_failure_rate_backoff(&self.config.table_name).await?;
+        self.ensure_sdk().await?;
 
-            // Batch futures for better throughput: collect futures and await in batches
-            // This allows the SDK to queue multiple records before flushing, improving performance
-            const BATCH_SIZE: usize = 1000; // Flush every 1000 records
-            const BATCH_SIZE_BYTES: usize = 10 * 1024 * 1024; // Or every 10MB
-                                                              // Store futures with their row indices - using a type-erased future
-            type IngestFuture = std::pin::Pin<
-                Box<
-                    dyn std::future::Future<
-                            Output = Result<i64, databricks_zerobus_ingest_sdk::ZerobusError>,
-                        > + Send,
-                >,
-            >;
-            let mut pending_futures: Vec<(usize, IngestFuture)> = Vec::new();
-            let mut total_bytes_buffered = 0usize;
-            let mut should_break_outer = false; // Track if we need to break outer retry loop
+        let unity_catalog_url = self.config.unity_catalog_url.as_ref().ok_or_else(|| {
+            ZerobusError::ConfigurationError("unity_catalog_url is required".to_string())
+        })?;
+        let client_id =
+            self.config.client_id.as_ref().ok_or_else(|| {
+                ZerobusError::ConfigurationError("client_id is required".to_string())
+            })?;
+        let client_secret = self.config.client_secret.as_ref().ok_or_else(|| {
+            ZerobusError::ConfigurationError("client_secret is required".to_string())
+        })?;
+        crate::wrapper::auth::refresh_token(
+            unity_catalog_url,
+            client_id.expose_secret(),
+            client_secret.expose_secret(),
+        )
+        .await
+        .map(|_| ())
+    }
 
-            // Process only successfully converted rows
-            for (original_row_idx, bytes) in conversion_result.successful_bytes.iter() {
-                let idx = *original_row_idx;
-                // ========================================================================
-                // STEP 6a: Check backoff before each record
-                // ========================================================================
-                // Edge case: Backoff can start during batch processing (e.g., another thread
-                // encountered error 6006 or high failure rate). We check before each record to prevent writes
-                // during backoff period.
-                {
-                    use crate::wrapper::zerobus::{
-                        check_error_6006_backoff, check_failure_rate_backoff,
-                    };
-                    if let Err(_backoff_err) =
-                        check_error_6006_backoff(&self.config.table_name).await
-                    {
-                        // Backoff error: track per-row and break (backoff is batch-level concern)
-                        // Clear stream so it gets recreated after backoff
-                        let mut stream_guard = self.stream.lock().await;
-                        *stream_guard = None;
-                        drop(stream_guard);
-                        // Backoff affects remaining rows, but we've processed up to idx
-                        // Mark remaining rows as affected by backoff
-                        for remaining_idx in idx..conversion_result.successful_bytes.len() {
-                            if let Some((orig_idx, _)) =
-                                conversion_result.successful_bytes.get(remaining_idx)
-                            {
-                                attempt_transmission_errors.push((
-                                    *orig_idx,
-                                    ZerobusError::ConnectionError(
-                                        "Backoff period active - row processing stopped"
-                                            .to_string(),
-                                    ),
-                                ));
-                            }
-                        }
-                        all_succeeded = false;
-                        failed_at_idx = idx;
-                        break;
-                    }
-                    // Also check failure rate backoff
-                    if let Err(_backoff_err) =
-                        check_failure_rate_backoff(&self.config.table_name).await
-                    {
-                        // Backoff error: track per-row and break (backoff is batch-level concern)
-                        // Clear stream so it gets recreated after backoff
-                        let mut stream_guard = self.stream.lock().await;
-                        *stream_guard = None;
-                        drop(stream_guard);
-                        // Backoff affects remaining rows, but we've processed up to idx
-                        // Mark remaining rows as affected by backoff
-                        for remaining_idx in idx..conversion_result.successful_bytes.len() {
-                            if let Some((orig_idx, _)) =
-                                conversion_result.successful_bytes.get(remaining_idx)
-                            {
-                                attempt_transmission_errors.push((
-                                    *orig_idx,
-                                    ZerobusError::ConnectionError(
-                                        "High failure rate backoff active - row processing stopped"
-                                            .to_string(),
-                                    ),
-                                ));
-                            }
-                        }
-                        all_succeeded = false;
-                        failed_at_idx = idx;
-                        break;
-                    }
-                }
+    /// Fetch the target table's column definitions from Unity Catalog
+    ///
+    /// Obtains an OAuth token with the configured credentials and reads the table from the
+    /// Unity Catalog tables API. This is the only network call made for table schema
+    /// validation; nothing calls it unless asked to (see
+    /// `WrapperConfiguration::with_validate_table_schema_on_startup`).
+    ///
+    /// # Returns
+    ///
+    /// Returns the table's columns in table order.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigurationError` if `unity_catalog_url` or credentials are missing,
+    /// `TokenRefreshError` if no token can be obtained, or `ConnectionError` if the table
+    /// cannot be read.
+    pub async fn fetch_table_schema(
+        &self,
+    ) -> Result<Vec<crate::wrapper::table_schema::ColumnDef>, ZerobusError> {
+        use crate::wrapper::table_schema::TableSchemaSource;
+        self.table_schema_source()?
+            .table_columns(&self.config.table_name)
+            .await
+    }
 
-                // ========================================================================
-                // STEP 6b: Re-acquire stream lock and ensure stream exists
-                // ========================================================================
-                // We re-acquire the lock for each record because:
-                // 1. Stream may have been cleared by error handling in previous iteration
-                // 2. Lock was released before async operations to avoid blocking
-                // 3. Multiple threads may be sending batches concurrently
-                //
-                // Performance: Lock is held only briefly, released before network I/O.
-                let mut stream_guard = self.stream.lock().await;
-                if stream_guard.is_none() {
-                    // Stream was cleared (e.g., by error handling), recreate it
-                    info!(
-                        "Stream was cleared, recreating for table: {}",
-                        self.config.table_name
-                    );
-                    let stream = crate::wrapper::zerobus::ensure_stream(
-                        sdk,
-                        self.config.table_name.clone(),
-                        descriptor.clone(),
-                        client_id.clone(),
-                        client_secret.clone(),
-                    )
-                    .await?;
-                    *stream_guard = Some(stream);
-                }
-                let stream = stream_guard.as_mut().ok_or_else(|| {
-                    ZerobusError::ConnectionError(
-                        "Stream was None after recreation - this should not happen".to_string(),
-                    )
-                })?;
+    /// Check the descriptor auto-generated for a schema against the target table
+    ///
+    /// Generates the descriptor `send_batch` would use for `schema`, fetches the table's
+    /// columns (see `fetch_table_schema`) and compares them. Fields are matched to columns
+    /// by name; unknown fields, incompatible types and non-nullable columns without a field
+    /// are all reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `schema` - Arrow schema of the batches that will be sent
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigurationError` listing every mismatch if the descriptor does not fit
+    /// the table, or the error from `fetch_table_schema`.
+    pub async fn validate_table_schema(
+        &self,
+        schema: &arrow::datatypes::Schema,
+    ) -> Result<(), ZerobusError> {
+        let descriptor = self.generate_descriptor(schema)?;
+        crate::wrapper::table_schema::check_table_schema(
+            &self.table_schema_source()?,
+            &self.config.table_name,
+            &descriptor,
+        )
+        .await
+    }
 
-                // ========================================================================
-                // STEP 6c: Send bytes to Zerobus stream (batched for performance)
-                // ========================================================================
-                // The Zerobus SDK's ingest_record returns a Future that resolves when acknowledged.
-                // We collect futures and await them in batches for better throughput.
-                //
-                // Error handling:
-                // - Stream closed errors: Clear stream, mark failure, break loop to retry
-                // - Other errors: Track per-row and continue
-                // - First record failures: Log detailed diagnostics for schema issues
-                match stream.ingest_record(bytes.clone()).await {
-                    Ok(ingest_future) => {
-                        // Release lock before collecting future to avoid blocking
-                        drop(stream_guard);
+    /// Unity Catalog table schema source using the configured URL and credentials
+    fn table_schema_source(
+        &self,
+    ) -> Result<crate::wrapper::table_schema::UnityCatalogSchemaSource<'_>, ZerobusError> {
+        let unity_catalog_url = self.config.unity_catalog_url.as_ref().ok_or_else(|| {
+            ZerobusError::ConfigurationError("unity_catalog_url is required".to_string())
+        })?;
+        let client_id =
+            self.config.client_id.as_ref().ok_or_else(|| {
+                ZerobusError::ConfigurationError("client_id is required".to_string())
+            })?;
+        let client_secret = self.config.client_secret.as_ref().ok_or_else(|| {
+            ZerobusError::ConfigurationError("client_secret is required".to_string())
+        })?;
+        Ok(crate::wrapper::table_schema::UnityCatalogSchemaSource {
+            unity_catalog_url,
+            client_id: client_id.expose_secret(),
+            client_secret: client_secret.expose_secret(),
+        })
+    }
 
-                        // Collect future for batch processing
-                        // Box the future to store in Vec (type erasure for different future types)
-                        pending_futures.push((idx, Box::pin(ingest_future)));
-                        total_bytes_buffered += bytes.len();
+    /// Get the number of bytes currently reserved from the memory budget
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(bytes)` held by in-progress sends (including mirror sends), or `None`
+    /// if no budget is configured (see `WrapperConfiguration::with_memory_budget`).
+    pub fn memory_in_use(&self) -> Option<usize> {
+        let budget = self.memory_budget.as_ref()?;
+        let limit = self.config.memory_budget_bytes?;
+        Some(limit - budget.available_permits())
+    }
 
-                        // Periodically flush and await futures to manage memory and ensure progress
-                        if pending_futures.len() >= BATCH_SIZE
-                            || total_bytes_buffered >= BATCH_SIZE_BYTES
-                        {
-                            // Flush stream to send buffered records
-                            {
-                                let mut stream_guard = self.stream.lock().await;
-                                if let Some(ref mut stream) = *stream_guard {
-                                    if let Err(e) = stream.flush().await {
-                                        error!(
-                                            "Failed to flush Zerobus stream during batch: {}",
-                                            e
-                                        );
-                                        // Mark all pending futures as failed
-                                        for (pending_idx, _) in pending_futures.drain(..) {
-                                            attempt_transmission_errors.push((
-                                                pending_idx,
-                                                ZerobusError::ConnectionError(format!(
-                                                    "Flush failed during batch processing: {}",
-                                                    e
-                                                )),
-                                            ));
-                                        }
-                                        all_succeeded = false;
-                                        failed_at_idx = idx;
-                                        break;
-                                    }
-                                }
-                            }
+    /// Shutdown the wrapper gracefully, closing connections and cleaning up resources
+    ///
+    /// Debug files and observability data buffered since the last `flush` are flushed
+    /// before returning, so they are not lost at process exit. These flushes are
+    /// best-effort: failures are logged and do not fail the shutdown.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if shutdown fails.
+    pub async fn shutdown(&self) -> Result<(), ZerobusError> {
+        info!("Shutting down ZerobusWrapper");
 
-                            // Await all pending futures and track results
-                            for (pending_idx, mut future) in pending_futures.drain(..) {
-                                match future.as_mut().await {
-                                    Ok(_ack_id) => {
-                                        debug!(
-                                            "✅ Successfully sent record to Zerobus stream (row {}, ack_id={})",
-                                            pending_idx, _ack_id
-                                        );
-                                        attempt_successful_indices.push(pending_idx);
-                                    }
-                                    Err(e) => {
-                                        let err_msg = format!("{}", e);
-                                        // Check if stream is closed
-                                        if err_msg.contains("Stream is closed")
-                                            || err_msg.contains("Stream closed")
-                                        {
-                                            let is_first = pending_idx == 0;
-                                            error!(
-                                                "Stream closed: row={}, first_record={}, error={}",
-                                                pending_idx, is_first, err_msg
-                                            );
-                                            if is_first {
-                                                error!("Diagnostics: Stream closed during batch processing");
-                                                error!("Possible causes:");
-                                                error!("  1. Schema mismatch between descriptor and table");
-                                                error!("  2. Validation error");
-                                                error!("  3. Server-side issue");
-                                            }
-                                            // Clear stream and break to retry
-                                            let mut stream_guard = self.stream.lock().await;
-                                            *stream_guard = None;
-                                            drop(stream_guard);
-                                            attempt_transmission_errors.push((
-                                                pending_idx,
-                                                ZerobusError::ConnectionError(format!(
-                                                    "Stream closed: row={}, error={}",
-                                                    pending_idx, err_msg
-                                                )),
-                                            ));
-                                            all_succeeded = false;
-                                            failed_at_idx = pending_idx;
-                                            break;
-                                        } else {
-                                            // Non-stream-closure errors
-                                            attempt_transmission_errors.push((
-                                                pending_idx,
-                                                ZerobusError::TransmissionError(format!(
-                                                    "Record ingestion failed: row={}, error={}",
-                                                    pending_idx, e
-                                                )),
-                                            ));
-                                            all_succeeded = false;
-                                        }
-                                    }
-                                }
-                            }
-                            total_bytes_buffered = 0;
+        // Close stream if it exists
+        let mut stream_guard = self.stream.lock().await;
+        if let Some(mut stream) = stream_guard.take() {
+            // Close the stream gracefully, bounded so a hung server can't block process exit
+            // ZerobusStream has a close() method that returns ZerobusResult
+            close_with_timeout(stream.close(), self.config.shutdown_timeout).await;
+        }
+        drop(stream_guard);
 
-                            // If we broke due to stream closure, mark for outer loop break
-                            // But continue to process remaining pending futures below
-                            if !all_succeeded && failed_at_idx > 0 {
-                                should_break_outer = true;
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        let err_msg = format!("{}", e);
-                        // Check if stream is closed (indicates server-side closure)
-                        if err_msg.contains("Stream is closed") || err_msg.contains("Stream closed")
-                        {
-                            // Standardized error logging with context
-                            let is_first = idx == 0;
-                            error!(
-                                "Stream closed: row={}, first_record={}, error={}",
-                                idx, is_first, err_msg
-                            );
-                            if is_first {
-                                // First record failure indicates schema/validation issues
-                                error!("Diagnostics: This is the FIRST record - stream closed immediately");
-                                error!("Possible causes:");
-                                error!("  1. Schema mismatch between descriptor and table");
-                                error!("  2. Validation error on first record");
-                                error!("  3. Table schema not yet propagated");
-                                error!(
-                                    "Descriptor info: fields={}, nested_types={}",
-                                    descriptor.field.len(),
-                                    descriptor.nested_type.len()
-                                );
-                            }
-                            // Stream closure error: track per-row and continue
-                            // Clear stream so it gets recreated on next iteration
-                            *stream_guard = None;
-                            drop(stream_guard);
-                            let stream_error = ZerobusError::ConnectionError(format!(
-                                "Stream closed: row={}, error={}",
-                                idx, err_msg
-                            ));
-                            attempt_transmission_errors.push((idx, stream_error));
-                            all_succeeded = false;
-                            failed_at_idx = idx;
-                            // Mark for outer loop break, but continue to process pending futures
-                            should_break_outer = true;
-                            break;
-                        } else {
-                            // Non-stream-closure errors: track per-row and continue
-                            let transmission_error = ZerobusError::ConnectionError(format!(
-                                "Record creation failed: row={}, error={}",
-                                idx, e
-                            ));
-                            attempt_transmission_errors.push((idx, transmission_error));
-                            all_succeeded = false;
-                            // Continue processing remaining rows instead of returning immediately
-                        }
-                    }
-                }
-            }
+        flush_on_shutdown("debug files", self.debug_writer.as_deref()).await;
+        flush_on_shutdown("observability data", self.observability.as_ref()).await;
+
+        if let Some(ref mirror) = self.mirror {
+            Box::pin(mirror.shutdown()).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Run a connection-establishment step (SDK or stream creation)
+///
+/// With a connect retry config, transient failures are retried under it and exhaustion is
+/// final (`RetryExhausted` is not retried by the per-batch retry). Without one, the step
+/// runs once and failures are left to the per-batch retry.
+async fn connect_with_retry<F, Fut, T>(
+    connect_retry: Option<&RetryConfig>,
+    mut connect: F,
+) -> Result<T, ZerobusError>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, ZerobusError>>,
+{
+    match connect_retry {
+        Some(retry_config) => retry_config.execute_with_retry(connect).await,
+        None => connect().await,
+    }
+}
+
+/// Await a stream close, giving up after `timeout`
+///
+/// Errors and timeouts are logged rather than returned so shutdown always proceeds.
+/// Returns `true` if the close completed (successfully or not) within `timeout`.
+async fn close_with_timeout<F, E>(close: F, timeout: std::time::Duration) -> bool
+where
+    F: std::future::Future<Output = Result<(), E>>,
+    E: std::fmt::Display,
+{
+    match tokio::time::timeout(timeout, close).await {
+        Ok(Ok(())) => {
+            debug!("Stream closed successfully");
+            true
+        }
+        Ok(Err(e)) => {
+            warn!("Error closing Zerobus stream: {}", e);
+            true
+        }
+        Err(_) => {
+            warn!(
+                "Timed out after {:?} closing Zerobus stream, proceeding with shutdown",
+                timeout
+            );
+            false
+        }
+    }
+}
 
-            // CRITICAL: Flush and await any remaining pending futures before proceeding
-            // This ensures all queued records are sent and acknowledged, even if we broke early
-            if !pending_futures.is_empty() {
-                // Always flush remaining records before awaiting acknowledgments
-                // This ensures records are sent even if we broke early due to errors
-                {
-                    let mut stream_guard = self.stream.lock().await;
-                    if let Some(ref mut stream) = *stream_guard {
-                        // Attempt to flush - if stream is closed, this will fail but we still want to await futures
-                        match stream.flush().await {
-                            Ok(_) => {
-                                debug!(
-                                    "✅ Flushed Zerobus stream for {} remaining pending futures",
-                                    pending_futures.len()
-                                );
-                            }
-                            Err(e) => {
-                                warn!("Failed to flush Zerobus stream for remaining records (stream may be closed): {}", e);
-                                // Don't mark futures as failed yet - await them to get actual acknowledgment status
-                                // The stream might be closed, but some records may have been sent before closure
-                            }
-                        }
-                    } else {
-                        warn!("Stream is None when trying to flush remaining records - records may be lost");
-                        // Mark all pending futures as failed since we can't flush
-                        for (pending_idx, _) in pending_futures.drain(..) {
-                            attempt_transmission_errors.push((
-                                pending_idx,
-                                ZerobusError::ConnectionError(
-                                    "Stream was closed before flushing remaining records"
-                                        .to_string(),
-                                ),
-                            ));
-                        }
-                        all_succeeded = false;
-                    }
-                }
+/// Buffered output flushed when the wrapper shuts down
+trait ShutdownFlush {
+    async fn flush_buffered(&self) -> Result<(), ZerobusError>;
+}
 
-                // CRITICAL: Always await all pending futures to get acknowledgment status
-                // Even if stream is closed, we need to know which records succeeded/failed
-                for (pending_idx, mut future) in pending_futures.drain(..) {
-                    match future.as_mut().await {
-                        Ok(_ack_id) => {
-                            debug!(
-                                "✅ Successfully acknowledged record (row {}, ack_id={})",
-                                pending_idx, _ack_id
-                            );
-                            attempt_successful_indices.push(pending_idx);
-                        }
-                        Err(e) => {
-                            let err_msg = format!("{}", e);
-                            if err_msg.contains("Stream is closed")
-                                || err_msg.contains("Stream closed")
-                            {
-                                // Stream was closed - clear it and mark as failed
-                                let mut stream_guard = self.stream.lock().await;
-                                *stream_guard = None;
-                                drop(stream_guard);
-                                attempt_transmission_errors.push((
-                                    pending_idx,
-                                    ZerobusError::ConnectionError(format!(
-                                        "Stream closed before acknowledgment: row={}, error={}",
-                                        pending_idx, err_msg
-                                    )),
-                                ));
-                                all_succeeded = false;
-                            } else {
-                                // Other errors (network, timeout, etc.)
-                                attempt_transmission_errors.push((
-                                    pending_idx,
-                                    ZerobusError::TransmissionError(format!(
-                                        "Record acknowledgment failed: row={}, error={}",
-                                        pending_idx, e
-                                    )),
-                                ));
-                                all_succeeded = false;
-                            }
-                        }
-                    }
-                }
-            }
+impl ShutdownFlush for ObservabilityManager {
+    async fn flush_buffered(&self) -> Result<(), ZerobusError> {
+        self.flush().await
+    }
+}
 
-            // If we broke early due to stream closure, exit the retry loop
-            if should_break_outer {
-                break;
-            }
+impl ShutdownFlush for crate::wrapper::debug::DebugWriter {
+    async fn flush_buffered(&self) -> Result<(), ZerobusError> {
+        self.flush().await
+    }
+}
 
-            // ========================================================================
-            // STEP 6d: Handle retry logic
-            // ========================================================================
-            // If all rows succeeded, we're done. Otherwise, retry with stream recreation.
-            // The retry mechanism handles transient stream closure issues.
-            //
-            // Edge case: If stream closes repeatedly, it may indicate:
-            // - Schema mismatch (descriptor doesn't match table schema)
-            // - Server-side validation errors
-            // - Network issues causing stream closure
-            //
-            // Performance: Small delay (100ms) prevents tight retry loops.
-            if all_succeeded {
-                // All rows sent successfully - flush stream to ensure records are transmitted
-                // CRITICAL: The SDK buffers records internally and requires flush() to send them
-                {
-                    let mut stream_guard = self.stream.lock().await;
-                    if let Some(ref mut stream) = *stream_guard {
-                        if let Err(e) = stream.flush().await {
-                            error!("Failed to flush Zerobus stream after batch: {}", e);
-                            // Don't fail the entire batch if flush fails - records may still be in transit
-                            // But log the error for monitoring
-                        } else {
-                            debug!(
-                                "✅ Flushed Zerobus stream after sending {} records",
-                                attempt_successful_indices.len()
-                            );
-                        }
-                    }
-                }
-                // Update final results with this attempt's results
-                successful_indices = attempt_successful_indices;
-                transmission_errors = attempt_transmission_errors;
+/// Best-effort flush of buffered output during shutdown
+///
+/// Errors are logged rather than returned so shutdown always proceeds. Returns whether
+/// `target` was present and flushed successfully.
+async fn flush_on_shutdown<T: ShutdownFlush>(what: &str, target: Option<&T>) -> bool {
+    let Some(target) = target else {
+        return false;
+    };
+    match target.flush_buffered().await {
+        Ok(()) => {
+            debug!("Flushed {} on shutdown", what);
+            true
+        }
+        Err(e) => {
+            warn!("Failed to flush {} on shutdown: {}", what, e);
+            false
+        }
+    }
+}
+
+/// Display value of a key column at `row`, or `None` if it is null or out of range
+fn key_value(keys: &arrow::array::ArrayRef, row: usize) -> Option<String> {
+    if row >= keys.len() || keys.is_null(row) {
+        return None;
+    }
+    arrow::util::display::array_value_to_string(keys, row).ok()
+}
+
+/// Iterate the batches of Arrow IPC data, in the file or stream format
+///
+/// The file format starts with the `ARROW1` magic and is read fully into memory (its
+/// footer is at the end); the stream format starts with the `0xFFFFFFFF` continuation
+/// marker and is decoded incrementally. Anything else (including pre-1.0 streams without
+/// the marker) is rejected up front rather than misread as a message length.
+fn read_ipc_batches<'a, R: std::io::Read + Send + 'a>(
+    mut reader: R,
+) -> Result<
+    Box<dyn Iterator<Item = Result<RecordBatch, arrow::error::ArrowError>> + Send + 'a>,
+    ZerobusError,
+> {
+    use std::io::Read;
+    const ARROW_FILE_MAGIC: &[u8] = b"ARROW1";
+    const CONTINUATION_MARKER: &[u8] = &[0xFF; 4];
+
+    let read_error = |e: &dyn std::fmt::Display| {
+        ZerobusError::ConversionError(format!("Invalid Arrow IPC data: {}", e))
+    };
+    let mut magic = Vec::with_capacity(ARROW_FILE_MAGIC.len());
+    (&mut reader)
+        .take(ARROW_FILE_MAGIC.len() as u64)
+        .read_to_end(&mut magic)
+        .map_err(|e| read_error(&e))?;
+    if magic == ARROW_FILE_MAGIC {
+        let mut bytes = magic;
+        reader.read_to_end(&mut bytes).map_err(|e| read_error(&e))?;
+        let file = arrow::ipc::reader::FileReader::try_new(std::io::Cursor::new(bytes), None)
+            .map_err(|e| read_error(&e))?;
+        Ok(Box::new(file))
+    } else if magic.starts_with(CONTINUATION_MARKER) {
+        let input = std::io::BufReader::new(std::io::Cursor::new(magic).chain(reader));
+        let stream =
+            arrow::ipc::reader::StreamReader::try_new(input, None).map_err(|e| read_error(&e))?;
+        Ok(Box::new(stream))
+    } else {
+        Err(read_error(
+            &"expected the ARROW1 file magic or a stream continuation marker",
+        ))
+    }
+}
+
+/// Arrow in-memory size of a batch's rows, counting only the sliced part of shared buffers
+fn batch_bytes(batch: &RecordBatch) -> usize {
+    batch
+        .columns()
+        .iter()
+        .map(|column| {
+            column
+                .to_data()
+                .get_slice_memory_size()
+                .unwrap_or_else(|_| column.get_array_memory_size())
+        })
+        .sum()
+}
+
+/// Number of rows from `offset` on that fit in `max_bytes` (0 if even one row doesn't)
+fn rows_within_bytes(batch: &RecordBatch, offset: usize, max_bytes: usize) -> usize {
+    // Size grows with the number of rows, so binary search the longest fitting slice
+    let (mut fits, mut too_many) = (0, batch.num_rows() - offset + 1);
+    while too_many - fits > 1 {
+        let rows = fits + (too_many - fits) / 2;
+        if batch_bytes(&batch.slice(offset, rows)) <= max_bytes {
+            fits = rows;
+        } else {
+            too_many = rows;
+        }
+    }
+    fits
+}
+
+/// Result reporting each of `rows` as failed with `error`, tracking at most `max_tracked`
+fn failed_rows_result(
+    rows: Vec<usize>,
+    error: ZerobusError,
+    max_tracked: Option<usize>,
+) -> TransmissionResult {
+    let failed_count = rows.len();
+    let failed_rows: Vec<(usize, ZerobusError)> = rows
+        .into_iter()
+        .take(max_tracked.unwrap_or(usize::MAX))
+        .map(|idx| (idx, error.clone()))
+        .collect();
+    TransmissionResult {
+        failures_truncated: failed_rows.len() < failed_count,
+        failed_count,
+        failed_rows: Some(failed_rows),
+        total_rows: failed_count,
+        ..Default::default()
+    }
+}
+
+/// Combine per-batch results from `send_batches` into one result
+///
+/// Each entry is `(row_offset, result)`, where `row_offset` is the global index of the
+/// batch's first row. Mirror results are aggregated the same way.
+fn aggregate_results(
+    results: Vec<(usize, TransmissionResult)>,
+    total_rows: usize,
+    latency_ms: Option<u64>,
+) -> TransmissionResult {
+    let mut aggregated = TransmissionResult {
+        success: true,
+        latency_ms,
+        total_rows,
+        ..Default::default()
+    };
+    let mut mirror_results = Vec::new();
+
+    for (offset, result) in results {
+        aggregated.attempts += result.attempts;
+        aggregated.batch_size_bytes += result.batch_size_bytes;
+        aggregated.successful_count += result.successful_count;
+        aggregated.failed_count += result.failed_count;
+        aggregated.failures_truncated |= result.failures_truncated;
+        if let Some(rows) = result.successful_rows {
+            aggregated
+                .successful_rows
+                .get_or_insert_with(Vec::new)
+                .extend(rows.into_iter().map(|idx| offset + idx));
+        }
+        if let Some(rows) = result.failed_rows {
+            aggregated
+                .failed_rows
+                .get_or_insert_with(Vec::new)
+                .extend(rows.into_iter().map(|(idx, e)| (offset + idx, e)));
+        }
+        if let Some(sizes) = result.row_sizes {
+            aggregated
+                .row_sizes
+                .get_or_insert_with(Vec::new)
+                .extend(sizes.into_iter().map(|(idx, size)| (offset + idx, size)));
+        }
+        if let Some(rows) = result.failed_row_bytes {
+            aggregated
+                .failed_row_bytes
+                .get_or_insert_with(Vec::new)
+                .extend(rows.into_iter().map(|(idx, bytes)| (offset + idx, bytes)));
+        }
+        if let Some(ack_ids) = result.ack_ids {
+            aggregated.ack_ids.get_or_insert_with(Vec::new).extend(
+                ack_ids
+                    .into_iter()
+                    .map(|(idx, ack_id)| (offset + idx, ack_id)),
+            );
+        }
+        if let Some(mirror) = result.mirror_result {
+            mirror_results.push((offset, *mirror));
+        }
+        if aggregated.error.is_none() {
+            aggregated.error = result.error;
+        }
+    }
+
+    aggregated.success = aggregated.error.is_none()
+        && (aggregated.successful_count > 0 || aggregated.failed_count == 0);
+    if !mirror_results.is_empty() {
+        aggregated.mirror_result = Some(Box::new(aggregate_results(
+            mirror_results,
+            total_rows,
+            latency_ms,
+        )));
+    }
+    aggregated
+}
+
+/// Re-send the rows of `result` that failed with a retryable error, up to `max_rounds` times
+///
+/// `is_retryable` decides which row errors are re-sent. `resend` sends the given rows (indices into the original batch, in ascending order) and
+/// returns their result, with row indices relative to the rows it was given. Stops early
+/// once no retryable failures remain or a round fails at batch level; that round's error
+/// is logged, and the affected rows keep their previous errors.
+async fn retry_failed_rows<R, F, Fut>(
+    mut result: TransmissionResult,
+    max_rounds: u32,
+    is_retryable: R,
+    mut resend: F,
+) -> TransmissionResult
+where
+    R: Fn(&ZerobusError) -> bool,
+    F: FnMut(Vec<usize>) -> Fut,
+    Fut: std::future::Future<Output = Result<TransmissionResult, ZerobusError>>,
+{
+    for round in 1..=max_rounds {
+        if result.error.is_some() {
+            break;
+        }
+        let mut rows: Vec<usize> = result
+            .failed_rows
+            .iter()
+            .flatten()
+            .filter(|(_, error)| is_retryable(error))
+            .map(|(idx, _)| *idx)
+            .collect();
+        if rows.is_empty() {
+            break;
+        }
+        rows.sort_unstable();
+
+        debug!(
+            "Re-sending {} transmission-failed rows (round {} of {})",
+            rows.len(),
+            round,
+            max_rounds
+        );
+        match resend(rows.clone()).await {
+            Ok(retried) if retried.error.is_none() => {
+                merge_retried_rows(&mut result, &rows, retried)
+            }
+            Ok(retried) => {
+                result.attempts += retried.attempts;
+                warn!(
+                    "Re-sending failed rows stopped after round {}: {}",
+                    round,
+                    retried.error.map(|e| e.to_string()).unwrap_or_default()
+                );
                 break;
-            } else {
-                // Some rows failed due to stream closure - retry with stream recreation
-                retry_count += 1;
-                if retry_count > MAX_STREAM_RECREATE_ATTEMPTS {
-                    // Exhausted retry attempts - use what we have from this attempt
-                    let mut final_transmission_errors = attempt_transmission_errors;
-                    let final_successful_indices = attempt_successful_indices;
-                    // Mark remaining rows as failed due to stream closure
-                    for (idx, _) in conversion_result.successful_bytes.iter() {
-                        if !final_successful_indices.contains(idx)
-                            && !final_transmission_errors.iter().any(|(i, _)| i == idx)
-                        {
-                            final_transmission_errors.push((*idx, ZerobusError::ConnectionError(format!(
-                                "Stream recreation exhausted: row={}, possible_causes='schema_mismatch,validation_error,server_issue'",
-                                idx
-                            ))));
-                        }
-                    }
-                    successful_indices = final_successful_indices;
-                    transmission_errors = final_transmission_errors;
-                    break;
-                }
+            }
+            Err(e) => {
                 warn!(
-                    "Stream recreation retry: attempt={}/{}, failed_at_row={}",
-                    retry_count, MAX_STREAM_RECREATE_ATTEMPTS, failed_at_idx
+                    "Re-sending failed rows stopped after round {}: {}",
+                    round, e
                 );
-                // Small delay before retry to avoid tight retry loops
-                tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
-                // Reset attempt tracking for retry - will retry all remaining rows
-                attempt_successful_indices.clear();
-                attempt_transmission_errors.clear();
-                // Note: all_succeeded will be set to true at start of next loop iteration
+                break;
             }
         }
+    }
+    result
+}
 
-        // Merge conversion errors with transmission errors
-        let mut all_failed_rows = conversion_errors;
-        all_failed_rows.extend(transmission_errors);
-        Ok(BatchTransmissionResult {
-            successful_rows: successful_indices,
-            failed_rows: all_failed_rows,
-        })
+/// Send with `mapping`, then once with `alternate` if the stream closed on the first record
+///
+/// `send` converts and transmits the batch with a generated descriptor built with the
+/// mapping it is given. Returns the mapping of the returned result if the table accepted
+/// it, or `mapping` if the alternate was rejected too (or there is none).
+async fn send_with_descriptor_recovery<F, Fut>(
+    table_name: &str,
+    mapping: TypeMapping,
+    alternate: Option<TypeMapping>,
+    mut send: F,
+) -> Result<(TypeMapping, BatchTransmissionResult), ZerobusError>
+where
+    F: FnMut(TypeMapping) -> Fut,
+    Fut: std::future::Future<Output = Result<BatchTransmissionResult, ZerobusError>>,
+{
+    let result = send(mapping).await?;
+    let Some(alternate) = alternate.filter(|_| result.closed_on_first_record) else {
+        return Ok((mapping, result));
+    };
+    warn!(
+        "Stream for table {} closed on the first record with the '{}' type mapping; retrying once with the '{}' mapping (descriptor_auto_recovery)",
+        table_name,
+        mapping.as_str(),
+        alternate.as_str()
+    );
+    let recovered = send(alternate).await?;
+    if recovered.closed_on_first_record {
+        warn!(
+            "Stream for table {} also closed on the first record with the '{}' type mapping; giving up",
+            table_name,
+            alternate.as_str()
+        );
+        return Ok((mapping, recovered));
     }
+    info!(
+        "Table {} accepted the '{}' type mapping; using it for later batches",
+        table_name,
+        alternate.as_str()
+    );
+    Ok((alternate, recovered))
+}
 
-    /// Flush any pending operations and ensure data is transmitted
-    ///
-    /// # Errors
-    ///
-    /// Returns error if flush operation fails.
-    pub async fn flush(&self) -> Result<(), ZerobusError> {
-        // CRITICAL: Flush Zerobus stream to ensure buffered records are sent
-        // The SDK buffers records internally and requires flush() to transmit them
-        {
-            let mut stream_guard = self.stream.lock().await;
-            if let Some(ref mut stream) = *stream_guard {
-                stream.flush().await.map_err(|e| {
-                    ZerobusError::ConnectionError(format!("Failed to flush Zerobus stream: {}", e))
-                })?;
-                debug!("✅ Flushed Zerobus stream");
+/// Fold the result of re-sending `rows` into the original `result`
+///
+/// Recovered rows move from `failed_rows` to `successful_rows`, rows that failed again
+/// take their new error, and counts and `success` are recomputed.
+fn merge_retried_rows(
+    result: &mut TransmissionResult,
+    rows: &[usize],
+    retried: TransmissionResult,
+) {
+    // Untracked failures were never re-sent and stay failed
+    let untracked = result.failed_count - result.failed_rows.as_ref().map_or(0, Vec::len);
+    result.attempts += retried.attempts;
+    if let (Some(total), Some(extra)) = (result.latency_ms, retried.latency_ms) {
+        result.latency_ms = Some(total + extra);
+    }
+
+    let recovered: std::collections::HashSet<usize> = retried
+        .successful_rows
+        .iter()
+        .flatten()
+        .map(|&idx| rows[idx])
+        .collect();
+    let mut new_errors: std::collections::HashMap<usize, ZerobusError> = retried
+        .failed_rows
+        .into_iter()
+        .flatten()
+        .map(|(idx, error)| (rows[idx], error))
+        .collect();
+
+    if let Some(failed) = &mut result.failed_rows {
+        failed.retain(|(idx, _)| !recovered.contains(idx));
+        for (idx, error) in failed.iter_mut() {
+            if let Some(new_error) = new_errors.remove(idx) {
+                *error = new_error;
             }
         }
+    }
+    result.failed_rows = result
+        .failed_rows
+        .take()
+        .filter(|rows| !rows.is_empty() || untracked > 0);
+    if let Some(failed_bytes) = &mut result.failed_row_bytes {
+        failed_bytes.retain(|(idx, _)| !recovered.contains(idx));
+    }
 
-        // Flush debug files if enabled
-        if let Some(ref debug_writer) = self.debug_writer {
-            if let Err(e) = debug_writer.flush().await {
-                warn!("Failed to flush debug files: {}", e);
+    if !recovered.is_empty() {
+        let successful = result.successful_rows.get_or_insert_with(Vec::new);
+        successful.extend(recovered.iter().copied());
+        successful.sort_unstable();
+    }
+    if let Some(ack_ids) = retried.ack_ids {
+        let merged = result.ack_ids.get_or_insert_with(Vec::new);
+        merged.extend(ack_ids.into_iter().map(|(idx, ack_id)| (rows[idx], ack_id)));
+        merged.sort_by_key(|(idx, _)| *idx);
+    }
+
+    result.successful_count = result.successful_rows.as_ref().map_or(0, Vec::len);
+    result.failed_count = result.failed_rows.as_ref().map_or(0, Vec::len) + untracked;
+    result.success = result.successful_count > 0;
+}
+
+/// Wait before recreating a closed stream for another attempt at a batch
+///
+/// Waits `schema_propagation_wait`, if set, when the first record failed because the
+/// table schema is not yet propagated, and `backoff` (see
+/// `WrapperConfiguration::with_stream_recreate_backoff`) otherwise.
+async fn wait_before_stream_recreate(
+    schema_not_ready: bool,
+    schema_propagation_wait: Option<std::time::Duration>,
+    backoff: std::time::Duration,
+) {
+    let delay = match schema_propagation_wait {
+        Some(wait) if schema_not_ready => {
+            info!(
+                "Table schema not yet propagated, waiting {:?} before retrying",
+                wait
+            );
+            wait
+        }
+        _ => backoff,
+    };
+    tokio::time::sleep(delay).await;
+}
+
+/// Pending acknowledgment of a record handed to an ingest stream, resolving to its ack id
+type IngestFuture = std::pin::Pin<
+    Box<
+        dyn std::future::Future<Output = Result<i64, databricks_zerobus_ingest_sdk::ZerobusError>>
+            + Send,
+    >,
+>;
+
+/// The part of an ingest stream used to send a batch's records
+trait IngestStream: FlushableStream {
+    /// Hand a record to the stream, returning its pending acknowledgment
+    async fn ingest(
+        &mut self,
+        bytes: Vec<u8>,
+    ) -> Result<IngestFuture, databricks_zerobus_ingest_sdk::ZerobusError>;
+
+    /// Close the stream, flushing what was sent on it
+    async fn close_stream(&mut self) -> Result<(), databricks_zerobus_ingest_sdk::ZerobusError>;
+}
+
+impl IngestStream for databricks_zerobus_ingest_sdk::ZerobusStream {
+    async fn ingest(
+        &mut self,
+        bytes: Vec<u8>,
+    ) -> Result<IngestFuture, databricks_zerobus_ingest_sdk::ZerobusError> {
+        Ok(Box::pin(self.ingest_record(bytes).await?))
+    }
+
+    async fn close_stream(&mut self) -> Result<(), databricks_zerobus_ingest_sdk::ZerobusError> {
+        self.close().await
+    }
+}
+
+/// Marks a batch as sending on the stream until dropped (see `close_idle_stream`)
+struct SendInFlight<'a>(&'a std::sync::atomic::AtomicUsize);
+
+impl<'a> SendInFlight<'a> {
+    fn new(sends_in_flight: &'a std::sync::atomic::AtomicUsize) -> Self {
+        sends_in_flight.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Self(sends_in_flight)
+    }
+}
+
+impl Drop for SendInFlight<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+/// Outcome of `ZerobusWrapper::send_rows` across all stream recreation attempts
+struct RowsSent {
+    /// Rows acknowledged in any attempt
+    successful_indices: std::collections::BTreeSet<usize>,
+    /// Rows still failing after the last attempt
+    transmission_errors: Vec<(usize, ZerobusError)>,
+    /// Acknowledgment ids of the acknowledged rows
+    acknowledged: Vec<(usize, i64)>,
+    /// Whether the batch was given up on after the stream closed on its first record
+    closed_on_first_record: bool,
+}
+
+/// The part of an ingest stream used to flush it after a batch
+trait FlushableStream {
+    async fn flush_stream(&mut self) -> Result<(), String>;
+}
+
+impl FlushableStream for databricks_zerobus_ingest_sdk::ZerobusStream {
+    async fn flush_stream(&mut self) -> Result<(), String> {
+        self.flush().await.map_err(|e| e.to_string())
+    }
+}
+
+/// Best-effort flush of the stream at the end of a batch
+///
+/// Used by `WrapperConfiguration::with_flush_every_batch`. Flush errors are logged rather
+/// than returned, since the batch outcome has already been decided. Returns whether a
+/// stream was flushed successfully.
+async fn flush_after_batch<S: FlushableStream>(stream: &Mutex<Option<S>>) -> bool {
+    let mut stream_guard = stream.lock().await;
+    let Some(ref mut stream) = *stream_guard else {
+        return false;
+    };
+    match stream.flush_stream().await {
+        Ok(()) => {
+            debug!("✅ Flushed Zerobus stream at end of batch");
+            true
+        }
+        Err(e) => {
+            error!("Failed to flush Zerobus stream at end of batch: {}", e);
+            false
+        }
+    }
+}
+
+/// Record the acknowledged rows among `outcomes` in the batch's write-ahead log segment
+///
+/// Failures are logged rather than returned: a lost acknowledgment only means the record
+/// is sent again by `ZerobusWrapper::recover_wal`.
+async fn acknowledge_in_wal<T, E>(
+    segment: Option<&crate::wrapper::wal::WalSegment>,
+    outcomes: &[(usize, Result<T, E>)],
+) {
+    let Some(segment) = segment else {
+        return;
+    };
+    let rows: Vec<usize> = outcomes
+        .iter()
+        .filter(|(_, outcome)| outcome.is_ok())
+        .map(|(idx, _)| *idx)
+        .collect();
+    if let Err(e) = segment.acknowledge(&rows).await {
+        warn!(
+            "Failed to record acknowledgment of {} rows in WAL: {}",
+            rows.len(),
+            e
+        );
+    }
+}
+
+/// Remove a write-ahead log segment if every record in it was delivered
+///
+/// Otherwise the segment is kept, with the acknowledgments recorded so far, for
+/// `ZerobusWrapper::recover_wal`. Returns the path of a kept segment.
+async fn finish_wal_segment(
+    segment: crate::wrapper::wal::WalSegment,
+    delivered: bool,
+) -> Option<std::path::PathBuf> {
+    let path = segment.path().to_path_buf();
+    if !delivered {
+        debug!(
+            "Keeping WAL segment {} with unacknowledged records",
+            path.display()
+        );
+        return Some(path);
+    }
+    if let Err(e) = segment.finish().await {
+        warn!("Failed to remove WAL segment {}: {}", path.display(), e);
+    }
+    None
+}
+
+/// Write-ahead log segment kept by an earlier attempt of a send
+///
+/// Every attempt writes all of the batch's records to a new segment, so an earlier
+/// attempt's segment is removed once the next one's is written; only the last attempt's
+/// segment is left for `ZerobusWrapper::recover_wal`.
+#[derive(Default)]
+struct KeptSegment(std::sync::Mutex<Option<std::path::PathBuf>>);
+
+impl KeptSegment {
+    /// Remember `kept` (if any) as this send's segment, removing the earlier one
+    async fn replace(&self, kept: Option<std::path::PathBuf>) {
+        let earlier = std::mem::replace(
+            &mut *self
+                .0
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner()),
+            kept,
+        );
+        if let Some(earlier) = earlier {
+            if let Err(e) = tokio::fs::remove_file(&earlier).await {
+                warn!(
+                    "Failed to remove superseded WAL segment {}: {}",
+                    earlier.display(),
+                    e
+                );
             }
         }
+    }
+}
 
-        // Flush observability if enabled
-        if let Some(ref obs) = self.observability {
-            obs.flush().await?;
+/// Keep a submitted record's acknowledgment to await later, unless fire-and-forget
+///
+/// With `DeliveryMode::FireAndForget` the acknowledgment is dropped without being awaited
+/// (the record is already queued on the stream). Returns whether it was kept.
+fn track_acknowledgment<F>(
+    mode: DeliveryMode,
+    pending: &mut Vec<(usize, F)>,
+    idx: usize,
+    acknowledgment: F,
+) -> bool {
+    match mode {
+        DeliveryMode::AtLeastOnce => {
+            pending.push((idx, acknowledgment));
+            true
         }
-
-        Ok(())
+        DeliveryMode::FireAndForget => false,
     }
+}
 
-    /// Shutdown the wrapper gracefully, closing connections and cleaning up resources
-    ///
-    /// # Errors
-    ///
-    /// Returns error if shutdown fails.
-    pub async fn shutdown(&self) -> Result<(), ZerobusError> {
-        info!("Shutting down ZerobusWrapper");
-
-        // Close stream if it exists
-        let mut stream_guard = self.stream.lock().await;
-        if let Some(mut stream) = stream_guard.take() {
-            // Close the stream gracefully
-            // ZerobusStream has a close() method that returns ZerobusResult
-            if let Err(e) = stream.close().await {
-                warn!("Error closing Zerobus stream: {}", e);
-            } else {
-                debug!("Stream closed successfully");
-            }
+/// Flush the stream during a batch, then await every pending acknowledgment
+///
+/// A flush error does not decide the outcome of the pending records: some may have
+/// reached the server before it, so every future is still awaited for its actual
+/// acknowledgment status. Returns the flush error, if any, and each row's outcome.
+async fn flush_and_await_pending<S: FlushableStream, F: std::future::Future>(
+    stream: &Mutex<Option<S>>,
+    pending: Vec<(usize, F)>,
+) -> (Option<String>, Vec<(usize, F::Output)>) {
+    let flush_error = {
+        let mut stream_guard = stream.lock().await;
+        match stream_guard.as_mut() {
+            Some(stream) => stream.flush_stream().await.err(),
+            None => None,
         }
-
-        Ok(())
+    };
+    let mut outcomes = Vec::with_capacity(pending.len());
+    for (idx, future) in pending {
+        outcomes.push((idx, future.await));
     }
+    (flush_error, outcomes)
 }
 
 // Implement Clone for use in async closures
@@ -1593,12 +5026,24 @@ impl Clone for ZerobusWrapper {
     fn clone(&self) -> Self {
         Self {
             config: Arc::clone(&self.config),
+            endpoint: Arc::clone(&self.endpoint),
             sdk: Arc::clone(&self.sdk),
             stream: Arc::clone(&self.stream),
+            active_descriptor: Arc::clone(&self.active_descriptor),
+            field_maps: Arc::clone(&self.field_maps),
+            type_mapping: Arc::clone(&self.type_mapping),
             retry_config: self.retry_config.clone(),
+            connect_retry_config: self.connect_retry_config.clone(),
+            stream_recreate_backoff: self.stream_recreate_backoff.clone(),
             observability: self.observability.clone(),
             debug_writer: self.debug_writer.as_ref().map(Arc::clone),
-            descriptor_written: Arc::clone(&self.descriptor_written),
+            mirror: self.mirror.as_ref().map(Arc::clone),
+            memory_budget: self.memory_budget.as_ref().map(Arc::clone),
+            last_send: Arc::clone(&self.last_send),
+            sends_in_flight: Arc::clone(&self.sends_in_flight),
+            metrics: Arc::clone(&self.metrics),
+            acknowledged_tokens: Arc::clone(&self.acknowledged_tokens),
+            wal: self.wal.as_ref().map(Arc::clone),
         }
     }
 }
@@ -1612,3 +5057,1130 @@ impl Clone for ZerobusWrapper {
 // - Option<Arc<DebugWriter>>: Send + Sync
 // - Arc<Mutex<bool>>: Send + Sync
 // The compiler automatically derives Send + Sync for this struct, so explicit unsafe impl is not needed.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Simulate the send loop's buffering with many small rows and check the cap is never exceeded
+    #[test]
+    fn test_pending_futures_never_exceed_cap() {
+        let max_pending = 64;
+        let mut pending = 0usize;
+        let mut buffered_bytes = 0usize;
+        let mut max_observed = 0usize;
+
+        for _ in 0..10_000 {
+            let record_bytes = 16;
+            if should_drain_pending_futures(
+                pending,
+                buffered_bytes,
+                record_bytes,
+                max_pending,
+                10 * 1024 * 1024,
+            ) {
+                pending = 0;
+                buffered_bytes = 0;
+            }
+            pending += 1;
+            buffered_bytes += record_bytes;
+            max_observed = max_observed.max(pending);
+        }
+
+        assert_eq!(max_observed, max_pending);
+    }
+
+    #[test]
+    fn test_pending_futures_drained_before_byte_limit() {
+        let max_bytes = 1000;
+        // Buffer is below the count cap, but the next record would exceed the byte limit
+        assert!(should_drain_pending_futures(3, 900, 200, 1000, max_bytes));
+        assert!(!should_drain_pending_futures(3, 700, 200, 1000, max_bytes));
+        // Empty buffer is never drained, even for an oversized record
+        assert!(!should_drain_pending_futures(0, 0, 5000, 1000, max_bytes));
+    }
+
+    /// Simulate a slow trickle of records on tokio's paused clock: the buffer never fills,
+    /// so only the age limit flushes it
+    #[tokio::test(start_paused = true)]
+    async fn test_trickle_of_records_flushed_on_buffer_age() {
+        let max_age = Some(std::time::Duration::from_millis(500));
+        let gap = std::time::Duration::from_millis(200);
+        let mut pending = 0usize;
+        let mut oldest_buffered_at = None;
+        let mut flushed_batches = Vec::new();
+
+        for _ in 0..10 {
+            tokio::time::advance(gap).await;
+            if should_drain_pending_futures(pending, pending * 16, 16, 1000, 10 * 1024 * 1024)
+                || buffer_age_exceeded(oldest_buffered_at, max_age)
+            {
+                flushed_batches.push(pending);
+                pending = 0;
+                oldest_buffered_at = None;
+            }
+            pending += 1;
+            oldest_buffered_at.get_or_insert_with(tokio::time::Instant::now);
+        }
+
+        // A record buffered at t flushes before the first record at or after t + 500ms,
+        // i.e. every third record
+        assert_eq!(flushed_batches, vec![3, 3, 3]);
+
+        // Without an age limit nothing is flushed until the buffer fills
+        assert!(!buffer_age_exceeded(oldest_buffered_at, None));
+        assert!(!buffer_age_exceeded(None, max_age));
+    }
+
+    #[test]
+    fn test_aggregate_results_offsets_failed_row_bytes() {
+        let result = |failed_row_bytes| TransmissionResult {
+            success: true,
+            attempts: 1,
+            total_rows: 2,
+            failed_row_bytes,
+            ..Default::default()
+        };
+        let aggregated = aggregate_results(
+            vec![
+                (0, result(Some(vec![(1, vec![8, 1])]))),
+                (2, result(Some(vec![(0, vec![8, 2])]))),
+            ],
+            4,
+            None,
+        );
+        assert_eq!(
+            aggregated.failed_row_bytes,
+            Some(vec![(1, vec![8, 1]), (2, vec![8, 2])])
+        );
+
+        let aggregated = aggregate_results(vec![(0, result(None))], 2, None);
+        assert_eq!(aggregated.failed_row_bytes, None);
+    }
+
+    #[test]
+    fn test_aggregate_results_offsets_row_indices() {
+        let succeeded = TransmissionResult {
+            success: true,
+            attempts: 1,
+            batch_size_bytes: 10,
+            successful_rows: Some(vec![0, 1, 2]),
+            total_rows: 3,
+            successful_count: 3,
+            row_sizes: Some(vec![(0, 4), (1, 5), (2, 6)]),
+            ack_ids: Some(vec![(0, 10), (1, 11), (2, 12)]),
+            ..Default::default()
+        };
+        let failed = failed_rows_result(
+            vec![0, 1],
+            ZerobusError::ConversionError("bad row".to_string()),
+            None,
+        );
+
+        let aggregated = aggregate_results(vec![(0, succeeded), (3, failed)], 5, None);
+        assert!(aggregated.success);
+        assert_eq!(aggregated.total_rows, 5);
+        assert_eq!(aggregated.successful_count, 3);
+        assert_eq!(aggregated.failed_count, 2);
+        assert_eq!(aggregated.successful_rows, Some(vec![0, 1, 2]));
+        assert_eq!(
+            aggregated
+                .failed_rows
+                .unwrap()
+                .iter()
+                .map(|(idx, _)| *idx)
+                .collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+        assert_eq!(aggregated.row_sizes, Some(vec![(0, 4), (1, 5), (2, 6)]));
+        assert_eq!(aggregated.ack_ids, Some(vec![(0, 10), (1, 11), (2, 12)]));
+        assert_eq!(aggregated.batch_size_bytes, 10);
+
+        // Every row failing makes the aggregate fail
+        let failed = |rows| {
+            failed_rows_result(
+                rows,
+                ZerobusError::ConversionError("bad row".to_string()),
+                None,
+            )
+        };
+        let aggregated =
+            aggregate_results(vec![(0, failed(vec![1])), (2, failed(vec![0]))], 4, None);
+        assert!(!aggregated.success);
+        assert_eq!(aggregated.failed_count, 2);
+    }
+
+    /// Submission is signalled once, on the first mark, while outcomes keep flowing
+    #[tokio::test]
+    async fn test_row_result_sink_signals_submission_once() {
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        let (submitted_tx, mut submitted) = tokio::sync::oneshot::channel();
+        let sink = RowResultSink::new(tx, 2).with_submission_signal(submitted_tx);
+
+        assert!(submitted.try_recv().is_err());
+        sink.mark_submitted();
+        sink.mark_submitted();
+        assert!(submitted.try_recv().is_ok());
+
+        sink.report(1, Ok(())).await;
+        sink.report(1, Ok(())).await;
+        drop(sink);
+        assert_eq!(rx.recv().await.map(|row| row.row_idx), Some(1));
+        assert!(rx.recv().await.is_none());
+    }
+
+    /// With a streaming sink the result carries counts only; the rows go to the sink
+    #[tokio::test]
+    async fn test_streaming_sink_skips_row_vectors() {
+        use arrow::array::Int64Array;
+        use arrow::datatypes::{DataType, Field, Schema};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = WrapperConfiguration::new(
+            "https://test.cloud.databricks.com".to_string(),
+            "test_table".to_string(),
+        )
+        .with_debug_arrow_enabled(true)
+        .with_debug_output(temp_dir.path().to_path_buf())
+        .with_zerobus_writer_disabled(true)
+        .with_track_row_sizes(true);
+        let wrapper = ZerobusWrapper::new(config).await.unwrap();
+        let batch = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)])),
+            vec![Arc::new(Int64Array::from(vec![1, 2, 3]))],
+        )
+        .unwrap();
+
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        let sink = Arc::new(RowResultSink::new(tx, 3));
+        let result = wrapper
+            .send_batch_with_row_results(batch.clone(), None, Some(sink))
+            .await
+            .unwrap();
+        assert_eq!(result.successful_count, 3);
+        assert!(result.successful_rows.is_none());
+        assert!(result.row_sizes.is_none());
+        let mut rows = Vec::new();
+        while let Some(row) = rx.recv().await {
+            rows.push(row.row_idx);
+        }
+        assert_eq!(rows, vec![0, 1, 2]);
+
+        // `submit_batch` keeps the rows in its result
+        let (tx, _rx) = tokio::sync::mpsc::channel(4);
+        let sink = Arc::new(RowResultSink::new(tx, 3).keeping_rows());
+        let result = wrapper
+            .send_batch_with_row_results(batch, None, Some(sink))
+            .await
+            .unwrap();
+        assert_eq!(result.successful_rows, Some(vec![0, 1, 2]));
+        assert_eq!(result.row_sizes.map(|sizes| sizes.len()), Some(3));
+    }
+
+    /// A close that never resolves (hung server) must not block shutdown
+    #[tokio::test]
+    async fn test_close_with_timeout_gives_up_on_hung_close() {
+        let start = std::time::Instant::now();
+        let closed = close_with_timeout(
+            std::future::pending::<Result<(), ZerobusError>>(),
+            std::time::Duration::from_millis(50),
+        )
+        .await;
+
+        assert!(!closed);
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_close_with_timeout_completes_on_close_error() {
+        let closed = close_with_timeout(
+            async { Err(ZerobusError::ConnectionError("close failed".to_string())) },
+            std::time::Duration::from_secs(5),
+        )
+        .await;
+
+        assert!(closed);
+    }
+
+    #[tokio::test]
+    async fn test_connect_with_retry_retries_transient_failures() {
+        let retry_config = RetryConfig::new(4, 1, 5);
+        let mut attempts = 0;
+        let result = connect_with_retry(Some(&retry_config), || {
+            attempts += 1;
+            let attempt = attempts;
+            async move {
+                if attempt < 3 {
+                    Err(ZerobusError::ConnectionError(
+                        "token fetch failed".to_string(),
+                    ))
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn test_connect_with_retry_exhaustion_is_final() {
+        let retry_config = RetryConfig::new(2, 1, 5);
+        let mut attempts = 0;
+        let result: Result<(), _> = connect_with_retry(Some(&retry_config), || {
+            attempts += 1;
+            async { Err(ZerobusError::ConnectionError("unreachable".to_string())) }
+        })
+        .await;
+
+        assert_eq!(attempts, 2);
+        let err = result.unwrap_err();
+        assert!(matches!(err, ZerobusError::RetryExhausted(_)));
+        assert!(!err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_connect_with_retry_without_config_runs_once() {
+        let mut attempts = 0;
+        let result: Result<(), _> = connect_with_retry(None, || {
+            attempts += 1;
+            async { Err(ZerobusError::ConnectionError("unreachable".to_string())) }
+        })
+        .await;
+
+        assert_eq!(attempts, 1);
+        // Original error is preserved so the per-batch retry can retry it
+        assert!(matches!(result, Err(ZerobusError::ConnectionError(_))));
+    }
+
+    /// A send after the idle timeout closes the stream, unless another batch is still
+    /// sending on it; the clock is tokio's paused one
+    #[tokio::test(start_paused = true)]
+    async fn test_idle_stream_recreated_after_timeout() {
+        use std::sync::atomic::Ordering;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = WrapperConfiguration::new(
+            "https://test.cloud.databricks.com".to_string(),
+            "test_table".to_string(),
+        )
+        .with_debug_arrow_enabled(true)
+        .with_debug_output(temp_dir.path().to_path_buf())
+        .with_zerobus_writer_disabled(true)
+        .with_idle_stream_timeout(std::time::Duration::from_secs(60));
+        let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let stream = ClosingStream::new(&received, None);
+        let closed = Arc::clone(&stream.closed);
+        let shared_stream = Mutex::new(Some(stream));
+
+        // Nothing sent yet: the stream can't be stale
+        assert!(!wrapper.close_idle_stream(&shared_stream).await);
+
+        wrapper.record_send();
+        tokio::time::advance(std::time::Duration::from_secs(30)).await;
+        assert!(!wrapper.close_idle_stream(&shared_stream).await);
+
+        // Past the timeout, but another batch is still sending on the stream
+        tokio::time::advance(std::time::Duration::from_secs(31)).await;
+        {
+            let _in_flight = SendInFlight::new(&wrapper.sends_in_flight);
+            assert!(!wrapper.close_idle_stream(&shared_stream).await);
+            assert!(shared_stream.lock().await.is_some());
+            assert!(!closed.load(Ordering::SeqCst));
+        }
+
+        // Once it finished, the next send closes the stream so it is recreated
+        assert!(wrapper.close_idle_stream(&shared_stream).await);
+        assert!(shared_stream.lock().await.is_none());
+        assert!(closed.load(Ordering::SeqCst));
+
+        // Sending again resets the idle time
+        wrapper.record_send();
+        assert!(!wrapper.close_idle_stream(&shared_stream).await);
+    }
+
+    /// Result for `total_rows` rows where `failed` rows failed with the given errors
+    fn partial_result(total_rows: usize, failed: Vec<(usize, ZerobusError)>) -> TransmissionResult {
+        let successful: Vec<usize> = (0..total_rows)
+            .filter(|idx| !failed.iter().any(|(f, _)| f == idx))
+            .collect();
+        TransmissionResult {
+            success: !successful.is_empty(),
+            attempts: 1,
+            latency_ms: Some(10),
+            failed_count: failed.len(),
+            failed_rows: (!failed.is_empty()).then_some(failed),
+            successful_count: successful.len(),
+            ack_ids: Some(
+                successful
+                    .iter()
+                    .map(|&idx| (idx, idx as i64 + 100))
+                    .collect(),
+            ),
+            successful_rows: (!successful.is_empty()).then_some(successful),
+            total_rows,
+            ..Default::default()
+        }
+    }
+
+    /// A mock send fails a subset of rows in the first round; the second round recovers them
+    #[tokio::test]
+    async fn test_retry_failed_rows_recovers_transient_failures() {
+        let connection_error = || ZerobusError::ConnectionError("stream reset".to_string());
+        let initial = partial_result(
+            6,
+            vec![
+                (1, connection_error()),
+                (2, ZerobusError::ConversionError("bad value".to_string())),
+                (3, connection_error()),
+                (5, connection_error()),
+            ],
+        );
+
+        let mut sent = Vec::new();
+        let result = retry_failed_rows(initial, 3, ZerobusError::is_retryable, |rows| {
+            sent.push(rows.clone());
+            let round = sent.len();
+            async move {
+                // Round 1: the second row (original row 3) fails again; round 2: all succeed
+                let failed = if round == 1 {
+                    vec![(1, connection_error())]
+                } else {
+                    vec![]
+                };
+                Ok(partial_result(rows.len(), failed))
+            }
+        })
+        .await;
+
+        // Conversion failures are never re-sent, and rounds stop once nothing is left
+        assert_eq!(sent, vec![vec![1, 3, 5], vec![3]]);
+        assert!(result.success);
+        assert_eq!(result.successful_rows, Some(vec![0, 1, 3, 4, 5]));
+        assert_eq!(result.successful_count, 5);
+        assert_eq!(result.failed_count, 1);
+        assert!(matches!(
+            result.failed_rows.as_deref(),
+            Some([(2, ZerobusError::ConversionError(_))])
+        ));
+        assert_eq!(result.attempts, 3);
+        assert_eq!(result.latency_ms, Some(30));
+        // Ack IDs of recovered rows are mapped back to the original row indices
+        assert_eq!(
+            result.ack_ids,
+            Some(vec![(0, 100), (1, 100), (3, 100), (4, 104), (5, 102)])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_failed_rows_stops_after_max_rounds() {
+        let connection_error = || ZerobusError::ConnectionError("stream reset".to_string());
+        let initial = partial_result(2, vec![(1, connection_error())]);
+
+        let mut rounds = 0;
+        let result = retry_failed_rows(initial, 2, ZerobusError::is_retryable, |rows| {
+            rounds += 1;
+            let round = rounds;
+            async move {
+                Ok(partial_result(
+                    rows.len(),
+                    vec![(
+                        0,
+                        ZerobusError::TransmissionError(format!("round {}", round)),
+                    )],
+                ))
+            }
+        })
+        .await;
+
+        assert_eq!(rounds, 2);
+        assert_eq!(result.successful_rows, Some(vec![0]));
+        // The row keeps the error of its last round
+        match result.failed_rows.as_deref() {
+            Some([(1, ZerobusError::TransmissionError(message))]) => {
+                assert_eq!(message, "round 2")
+            }
+            other => panic!("unexpected failed rows: {:?}", other),
+        }
+
+        // A batch-level error ends re-sending and leaves the result unchanged
+        let initial = partial_result(2, vec![(1, connection_error())]);
+        let result = retry_failed_rows(initial, 3, ZerobusError::is_retryable, |_| async {
+            Err(ZerobusError::AuthenticationError("denied".to_string()))
+        })
+        .await;
+        assert_eq!(result.successful_rows, Some(vec![0]));
+        assert_eq!(result.failed_count, 1);
+    }
+
+    /// A table that is not ready for its first two records gets the longer wait before
+    /// each retry, then succeeds within the stream recreation attempts
+    #[tokio::test(start_paused = true)]
+    async fn test_schema_not_ready_waits_before_retrying() {
+        use std::time::Duration;
+
+        let wait = Duration::from_secs(5);
+        let backoff = Duration::from_millis(100);
+        let not_ready_failures = 2;
+        let start = tokio::time::Instant::now();
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            let outcome = if attempts <= not_ready_failures {
+                Err("Stream closed: status: FailedPrecondition, message: \"Table schema not yet propagated\"")
+            } else {
+                Ok(())
+            };
+            match outcome {
+                Ok(()) => break,
+                Err(message) => {
+                    let schema_not_ready = crate::wrapper::zerobus::is_schema_not_ready(message);
+                    wait_before_stream_recreate(schema_not_ready, Some(wait), backoff).await;
+                }
+            }
+        }
+        assert_eq!(attempts, not_ready_failures + 1);
+        assert_eq!(start.elapsed(), wait * not_ready_failures);
+
+        // Other closures, or no configured wait, get the regular backoff
+        let start = tokio::time::Instant::now();
+        wait_before_stream_recreate(false, Some(wait), backoff).await;
+        wait_before_stream_recreate(true, None, backoff).await;
+        assert_eq!(start.elapsed(), backoff * 2);
+    }
+
+    /// A segment with unacknowledged records is kept for recovery until a later attempt
+    /// of the same send writes its own; a fully acknowledged one is removed
+    #[tokio::test]
+    async fn test_wal_segment_kept_until_acknowledged() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let wal =
+            crate::wrapper::wal::WriteAheadLog::new(temp_dir.path().to_path_buf(), "t").unwrap();
+        let descriptor = prost_types::DescriptorProto::default();
+        let records = vec![(0, vec![1]), (1, vec![2])];
+        let kept_segment = KeptSegment::default();
+
+        // First attempt: row 1 is never acknowledged, so the segment stays
+        let segment = wal.begin_segment(&descriptor, &records).await.unwrap();
+        segment.acknowledge(&[0]).await.unwrap();
+        let kept = finish_wal_segment(segment, false).await;
+        assert!(kept.is_some());
+        kept_segment.replace(kept).await;
+        let pending = wal.pending_segments().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].records, vec![(1, vec![2])]);
+
+        // Retry: its segment supersedes the first one, and is removed once delivered
+        let segment = wal.begin_segment(&descriptor, &records).await.unwrap();
+        kept_segment.replace(None).await;
+        assert!(wal.pending_segments().unwrap().is_empty());
+        assert!(finish_wal_segment(segment, true).await.is_none());
+        assert_eq!(std::fs::read_dir(wal.dir()).unwrap().count(), 0);
+    }
+
+    /// A table that closes the stream on the first record of a generated descriptor with
+    /// an Int64 timestamp field accepts the alternate descriptor with a string field
+    #[tokio::test]
+    async fn test_descriptor_auto_recovery_tries_alternate_mapping() {
+        use arrow::array::{AsArray, Int64Array, TimestampMicrosecondArray};
+        use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+        use prost_types::field_descriptor_proto::Type;
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new(
+                "ts",
+                DataType::Timestamp(TimeUnit::Microsecond, None),
+                false,
+            ),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2])),
+                Arc::new(TimestampMicrosecondArray::from(vec![0, 1_000_000])),
+            ],
+        )
+        .unwrap();
+
+        // Mock table: only a string `ts` field is accepted
+        let sent = std::sync::Mutex::new(Vec::new());
+        let send = |mapping: TypeMapping| {
+            let sent = &sent;
+            let batch = &batch;
+            async move {
+                let mapped = mapping.apply(batch)?;
+                let descriptor = crate::wrapper::conversion::generate_protobuf_descriptor(
+                    mapped.schema().as_ref(),
+                )?;
+                let ts_type = descriptor.field[1].r#type;
+                sent.lock().unwrap().push(ts_type);
+                let accepted = ts_type == Some(Type::String as i32);
+                let rows = 0..mapped.num_rows();
+                Ok(BatchTransmissionResult {
+                    successful_count: if accepted { rows.len() } else { 0 },
+                    successful_rows: if accepted {
+                        rows.clone().collect()
+                    } else {
+                        Vec::new()
+                    },
+                    failed_rows: if accepted {
+                        Vec::new()
+                    } else {
+                        rows.map(|idx| {
+                            (
+                                idx,
+                                ZerobusError::ConnectionError("Stream closed".to_string()),
+                            )
+                        })
+                        .collect()
+                    },
+                    untracked_failures: 0,
+                    row_sizes: None,
+                    failed_row_bytes: None,
+                    ack_ids: None,
+                    closed_on_first_record: !accepted,
+                })
+            }
+        };
+
+        let mapping = TypeMapping::Standard;
+        let alternate = mapping.alternate_for(batch.schema().as_ref());
+        assert_eq!(alternate, Some(TypeMapping::TimestampsAsStrings));
+        let (accepted, result) = send_with_descriptor_recovery("t", mapping, alternate, send)
+            .await
+            .unwrap();
+        assert_eq!(accepted, TypeMapping::TimestampsAsStrings);
+        assert_eq!(result.successful_rows, vec![0, 1]);
+        assert!(result.failed_rows.is_empty());
+        assert_eq!(
+            *sent.lock().unwrap(),
+            vec![Some(Type::Int64 as i32), Some(Type::String as i32)]
+        );
+
+        // Without an alternate the rejection is returned as is, after a single attempt
+        sent.lock().unwrap().clear();
+        let (accepted, result) = send_with_descriptor_recovery("t", mapping, None, send)
+            .await
+            .unwrap();
+        assert_eq!(accepted, TypeMapping::Standard);
+        assert!(result.closed_on_first_record);
+        assert_eq!(sent.lock().unwrap().len(), 1);
+
+        // Timestamps become ISO 8601 strings under the alternate mapping
+        let mapped = TypeMapping::TimestampsAsStrings.apply(&batch).unwrap();
+        assert_eq!(mapped.schema().field(1).data_type(), &DataType::Utf8);
+        assert_eq!(
+            mapped.column(1).as_string::<i32>().value(1),
+            "1970-01-01T00:00:01"
+        );
+        // Batches without timestamps have no alternate
+        assert_eq!(
+            TypeMapping::Standard.alternate_for(&batch.project(&[0]).unwrap().schema()),
+            None
+        );
+    }
+
+    /// Stream recreation delays grow across attempts up to the maximum, with jitter that
+    /// differs between attempts and between wrappers
+    #[test]
+    fn test_stream_recreate_backoff_grows_with_jitter() {
+        use rand::SeedableRng;
+        use std::time::Duration;
+
+        let mut backoff = RetryConfig::new(1, 100, 1000);
+        backoff.jitter = false;
+        let bounds: Vec<Duration> = (0..5)
+            .map(|attempt| backoff.calculate_delay_with_rng(attempt, &mut rand::thread_rng()))
+            .collect();
+        assert_eq!(
+            bounds,
+            [100, 200, 400, 800, 1000]
+                .map(Duration::from_millis)
+                .to_vec()
+        );
+
+        // Seeded RNGs stand in for separate wrappers
+        backoff.jitter = true;
+        let samples: Vec<Vec<Duration>> = (0..50)
+            .map(|seed| {
+                let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+                (0..3)
+                    .map(|attempt| backoff.calculate_delay_with_rng(attempt, &mut rng))
+                    .collect()
+            })
+            .collect();
+        let mean = |attempt: usize| {
+            samples.iter().map(|s| s[attempt]).sum::<Duration>() / samples.len() as u32
+        };
+        for attempt in 0..3 {
+            assert!(samples.iter().all(|s| s[attempt] <= bounds[attempt]));
+            // Not in lockstep: wrappers wait different times on the same attempt
+            let first = samples[0][attempt];
+            assert!(samples.iter().any(|s| s[attempt] != first));
+        }
+        assert!(mean(0) < mean(1) && mean(1) < mean(2));
+    }
+
+    /// Stream that counts flushes, optionally failing them
+    struct MockStream {
+        flushes: usize,
+        fail: bool,
+    }
+
+    impl FlushableStream for MockStream {
+        async fn flush_stream(&mut self) -> Result<(), String> {
+            self.flushes += 1;
+            if self.fail {
+                Err("stream closed".to_string())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flush_after_batch_flushes_every_batch() {
+        let stream = Mutex::new(Some(MockStream {
+            flushes: 0,
+            fail: false,
+        }));
+        for batch in 1..=3 {
+            assert!(flush_after_batch(&stream).await);
+            assert_eq!(stream.lock().await.as_ref().unwrap().flushes, batch);
+        }
+
+        // Flush failures are logged, not propagated
+        let failing = Mutex::new(Some(MockStream {
+            flushes: 0,
+            fail: true,
+        }));
+        assert!(!flush_after_batch(&failing).await);
+        assert_eq!(failing.lock().await.as_ref().unwrap().flushes, 1);
+
+        // Without a stream there is nothing to flush
+        let none: Mutex<Option<MockStream>> = Mutex::new(None);
+        assert!(!flush_after_batch(&none).await);
+    }
+
+    /// Tokens are remembered only for sends Zerobus acknowledged
+    #[tokio::test]
+    async fn test_tokens_remembered_only_for_acknowledged_sends() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = WrapperConfiguration::new(
+            "https://127.0.0.1:1".to_string(),
+            "main.default.tokens".to_string(),
+        )
+        .with_credentials("client_id".to_string(), "client_secret".to_string())
+        .with_unity_catalog("http://127.0.0.1:1".to_string());
+        let delivered = aggregate_results(Vec::new(), 0, None);
+        assert!(delivered.success && delivered.failed_count == 0);
+
+        let wrapper = ZerobusWrapper::new(config.clone()).await.unwrap();
+        assert!(wrapper.is_fully_acknowledged(&delivered));
+
+        // A remembered token is skipped, also by clones, before anything is sent
+        wrapper
+            .acknowledged_tokens
+            .lock()
+            .unwrap()
+            .insert("batch-1".to_string());
+        let batch = RecordBatch::try_new(
+            Arc::new(arrow::datatypes::Schema::new(vec![
+                arrow::datatypes::Field::new("id", arrow::datatypes::DataType::Int64, false),
+            ])),
+            vec![Arc::new(arrow::array::Int64Array::from(vec![1]))],
+        )
+        .unwrap();
+        let outcome = wrapper
+            .clone()
+            .send_batch_with_token(batch, "batch-1")
+            .await
+            .unwrap();
+        assert!(matches!(outcome, TokenSendOutcome::Duplicate));
+
+        let fire_and_forget = ZerobusWrapper::new(
+            config
+                .clone()
+                .with_delivery_mode(DeliveryMode::FireAndForget),
+        )
+        .await
+        .unwrap();
+        assert!(!fire_and_forget.is_fully_acknowledged(&delivered));
+
+        let disabled = ZerobusWrapper::new(
+            config
+                .with_debug_arrow_enabled(true)
+                .with_debug_output(temp_dir.path().to_path_buf())
+                .with_zerobus_writer_disabled(true),
+        )
+        .await
+        .unwrap();
+        assert!(!disabled.is_fully_acknowledged(&delivered));
+    }
+
+    #[test]
+    fn test_acknowledged_tokens_forget_oldest() {
+        let mut tokens = AcknowledgedTokens::default();
+        for i in 0..=MAX_IDEMPOTENCY_TOKENS {
+            tokens.insert(format!("token-{}", i));
+        }
+        // Re-inserting a known token does not count twice
+        tokens.insert(format!("token-{}", MAX_IDEMPOTENCY_TOKENS));
+
+        assert_eq!(tokens.order.len(), MAX_IDEMPOTENCY_TOKENS);
+        assert!(!tokens.contains("token-0"));
+        assert!(tokens.contains("token-1"));
+        assert!(tokens.contains(&format!("token-{}", MAX_IDEMPOTENCY_TOKENS)));
+    }
+
+    /// Observability stand-in that counts flushes, optionally failing them
+    struct MockTelemetry {
+        flushes: std::sync::atomic::AtomicUsize,
+        fail: bool,
+    }
+
+    impl ShutdownFlush for MockTelemetry {
+        async fn flush_buffered(&self) -> Result<(), ZerobusError> {
+            self.flushes
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if self.fail {
+                Err(ZerobusError::ConfigurationError(
+                    "exporter unavailable".to_string(),
+                ))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_flushes_buffered_telemetry() {
+        use std::sync::atomic::Ordering;
+
+        let telemetry = MockTelemetry {
+            flushes: Default::default(),
+            fail: false,
+        };
+        assert!(flush_on_shutdown("observability data", Some(&telemetry)).await);
+        assert_eq!(telemetry.flushes.load(Ordering::SeqCst), 1);
+
+        // Failures are logged, not propagated
+        let failing = MockTelemetry {
+            flushes: Default::default(),
+            fail: true,
+        };
+        assert!(!flush_on_shutdown("observability data", Some(&failing)).await);
+        assert_eq!(failing.flushes.load(Ordering::SeqCst), 1);
+
+        // Disabled outputs are skipped
+        assert!(!flush_on_shutdown::<MockTelemetry>("observability data", None).await);
+
+        // Shutdown of a wrapper with debug output (and no stream) still succeeds
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = WrapperConfiguration::new(
+            "https://test.cloud.databricks.com".to_string(),
+            "test_table".to_string(),
+        )
+        .with_debug_arrow_enabled(true)
+        .with_debug_output(temp_dir.path().to_path_buf())
+        .with_zerobus_writer_disabled(true);
+        let wrapper = ZerobusWrapper::new(config).await.unwrap();
+        assert!(wrapper.shutdown().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_flush_failure_mid_batch_keeps_acknowledged_rows() {
+        type AckFuture = std::pin::Pin<Box<dyn std::future::Future<Output = Result<i64, String>>>>;
+
+        let stream = Mutex::new(Some(MockStream {
+            flushes: 0,
+            fail: true,
+        }));
+        // Rows 0 and 1 reached the server before the flush failed; row 2 did not
+        let pending: Vec<(usize, AckFuture)> = vec![
+            (0, Box::pin(async { Ok(10) })),
+            (1, Box::pin(async { Ok(11) })),
+            (2, Box::pin(async { Err("stream closed".to_string()) })),
+        ];
+
+        let (flush_error, outcomes) = flush_and_await_pending(&stream, pending).await;
+        assert_eq!(flush_error.as_deref(), Some("stream closed"));
+        assert_eq!(stream.lock().await.as_ref().unwrap().flushes, 1);
+        assert_eq!(
+            outcomes,
+            vec![
+                (0, Ok(10)),
+                (1, Ok(11)),
+                (2, Err("stream closed".to_string())),
+            ]
+        );
+
+        // A successful flush reports no error and the same per-row outcomes
+        let stream = Mutex::new(Some(MockStream {
+            flushes: 0,
+            fail: false,
+        }));
+        let pending: Vec<(usize, AckFuture)> = vec![(0, Box::pin(async { Ok(1) }))];
+        let (flush_error, outcomes) = flush_and_await_pending(&stream, pending).await;
+        assert!(flush_error.is_none());
+        assert_eq!(outcomes, vec![(0, Ok(1))]);
+    }
+
+    #[tokio::test]
+    async fn test_fire_and_forget_does_not_await_acknowledgments() {
+        type AckFuture = std::pin::Pin<Box<dyn std::future::Future<Output = Result<i64, String>>>>;
+        let never_acknowledged = || -> AckFuture { Box::pin(std::future::pending()) };
+        let stream = Mutex::new(Some(MockStream {
+            flushes: 0,
+            fail: false,
+        }));
+
+        // Fire-and-forget drops the acknowledgments, so nothing is left to await
+        let mut pending = Vec::new();
+        for idx in 0..3 {
+            assert!(!track_acknowledgment(
+                DeliveryMode::FireAndForget,
+                &mut pending,
+                idx,
+                never_acknowledged()
+            ));
+        }
+        assert!(pending.is_empty());
+        let (_, outcomes) = tokio::time::timeout(
+            std::time::Duration::from_secs(1),
+            flush_and_await_pending(&stream, pending),
+        )
+        .await
+        .expect("no acknowledgment should be awaited");
+        assert!(outcomes.is_empty());
+
+        // At-least-once keeps them, and waits for acknowledgments that never come
+        let mut pending = Vec::new();
+        assert!(track_acknowledgment(
+            DeliveryMode::AtLeastOnce,
+            &mut pending,
+            0,
+            never_acknowledged()
+        ));
+        assert_eq!(pending.len(), 1);
+        assert!(tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            flush_and_await_pending(&stream, pending),
+        )
+        .await
+        .is_err());
+    }
+
+    /// Stream that acknowledges records until it closes after `close_after` of them
+    ///
+    /// The last record taken before closing is never acknowledged, as when the server
+    /// closes the stream with records in flight.
+    struct ClosingStream {
+        received: Arc<std::sync::Mutex<Vec<Vec<u8>>>>,
+        close_after: Option<usize>,
+        taken: usize,
+        closed: Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    impl ClosingStream {
+        fn new(received: &Arc<std::sync::Mutex<Vec<Vec<u8>>>>, close_after: Option<usize>) -> Self {
+            Self {
+                received: Arc::clone(received),
+                close_after,
+                taken: 0,
+                closed: Default::default(),
+            }
+        }
+    }
+
+    impl FlushableStream for ClosingStream {
+        async fn flush_stream(&mut self) -> Result<(), String> {
+            Ok(())
+        }
+    }
+
+    impl IngestStream for ClosingStream {
+        async fn ingest(
+            &mut self,
+            bytes: Vec<u8>,
+        ) -> Result<IngestFuture, databricks_zerobus_ingest_sdk::ZerobusError> {
+            let closed = || {
+                databricks_zerobus_ingest_sdk::ZerobusError::InvalidStateError(
+                    "Stream closed".to_string(),
+                )
+            };
+            if self.close_after == Some(self.taken) {
+                return Err(closed());
+            }
+            self.taken += 1;
+            let mut received = self.received.lock().unwrap();
+            received.push(bytes);
+            let ack_id = received.len() as i64;
+            if self.close_after == Some(self.taken) {
+                Ok(Box::pin(async move { Err(closed()) }))
+            } else {
+                Ok(Box::pin(async move { Ok(ack_id) }))
+            }
+        }
+
+        async fn close_stream(
+            &mut self,
+        ) -> Result<(), databricks_zerobus_ingest_sdk::ZerobusError> {
+            self.close_after = Some(self.taken);
+            self.closed.store(true, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    /// A stream closing mid-batch is recreated, and only the rows it did not acknowledge
+    /// are sent again; acknowledged rows are neither resent nor counted twice
+    #[tokio::test(start_paused = true)]
+    async fn test_stream_closed_mid_batch_resends_unacknowledged_rows() {
+        use arrow::array::Int64Array;
+        use arrow::datatypes::{DataType, Field, Schema};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = WrapperConfiguration::new(
+            "https://test.cloud.databricks.com".to_string(),
+            "main.default.closed_mid_batch".to_string(),
+        )
+        .with_debug_arrow_enabled(true)
+        .with_debug_output(temp_dir.path().to_path_buf())
+        .with_zerobus_writer_disabled(true);
+        let wrapper = ZerobusWrapper::new(config).await.unwrap();
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(Int64Array::from(vec![10, 11, 12, 13, 14]))],
+        )
+        .unwrap();
+        let prepared = wrapper.prepare_batch(batch, None).await.unwrap();
+
+        // The first stream takes rows 0-2 and closes before acknowledging row 2
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let connections = AtomicUsize::new(0);
+        let connect = || {
+            let first = connections.fetch_add(1, Ordering::SeqCst) == 0;
+            let stream = ClosingStream::new(&received, first.then_some(3));
+            async move { Ok(stream) }
+        };
+        let shared_stream: Mutex<Option<ClosingStream>> = Mutex::new(None);
+
+        let sent = wrapper
+            .send_rows(&shared_stream, connect, &prepared, None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(connections.load(Ordering::SeqCst), 2);
+        assert!(sent.transmission_errors.is_empty());
+        assert!(!sent.closed_on_first_record);
+        assert_eq!(
+            sent.successful_indices.into_iter().collect::<Vec<_>>(),
+            vec![0, 1, 2, 3, 4]
+        );
+        let mut acknowledged: Vec<usize> = sent.acknowledged.iter().map(|(idx, _)| *idx).collect();
+        acknowledged.sort_unstable();
+        assert_eq!(acknowledged, vec![0, 1, 2, 3, 4]);
+
+        // Rows 0 and 1 were sent once; row 2 again on the new stream, with rows 3 and 4
+        let rows = prepared.successful_bytes();
+        let sent_rows: Vec<usize> = received
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|bytes| rows.iter().find(|(_, b)| b == bytes).unwrap().0)
+            .collect();
+        assert_eq!(sent_rows, vec![0, 1, 2, 2, 3, 4]);
+    }
+
+    /// With add-only evolution, a batch with a new nullable column closes the stream
+    /// created for the old schema, and the next record recreates it with the evolved
+    /// descriptor
+    #[tokio::test]
+    async fn test_schema_evolution_recreates_stream() {
+        use arrow::array::{Int64Array, StringArray};
+        use arrow::datatypes::{DataType, Field, Schema};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = WrapperConfiguration::new(
+            "https://test.cloud.databricks.com".to_string(),
+            "main.default.evolving".to_string(),
+        )
+        .with_debug_arrow_enabled(true)
+        .with_debug_output(temp_dir.path().to_path_buf())
+        .with_zerobus_writer_disabled(true)
+        .with_schema_evolution(SchemaEvolution::AddOnly);
+        let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+        let original = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)])),
+            vec![Arc::new(Int64Array::from(vec![1, 2]))],
+        )
+        .unwrap();
+        let evolved = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![
+                Field::new("id", DataType::Int64, false),
+                Field::new("email", DataType::Utf8, true),
+            ])),
+            vec![
+                Arc::new(Int64Array::from(vec![3, 4])),
+                Arc::new(StringArray::from(vec![Some("c@example.com"), None])),
+            ],
+        )
+        .unwrap();
+
+        // Send on the shared mock stream, recording the field count of the descriptor
+        // each stream is created with; returns the number of acknowledged rows
+        async fn send(
+            wrapper: &ZerobusWrapper,
+            shared_stream: &Mutex<Option<ClosingStream>>,
+            prepared: &PreparedBatch,
+            connected: &std::sync::Mutex<Vec<usize>>,
+            received: &Arc<std::sync::Mutex<Vec<Vec<u8>>>>,
+        ) -> usize {
+            let connect = || {
+                let fields = wrapper.stream_descriptor(&prepared.descriptor).field.len();
+                connected.lock().unwrap().push(fields);
+                let stream = ClosingStream::new(received, None);
+                async move { Ok(stream) }
+            };
+            wrapper
+                .send_rows(shared_stream, connect, prepared, None, None)
+                .await
+                .unwrap()
+                .successful_indices
+                .len()
+        }
+        let connected = std::sync::Mutex::new(Vec::new());
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let shared_stream: Mutex<Option<ClosingStream>> = Mutex::new(None);
+
+        for _ in 0..2 {
+            let prepared = wrapper.prepare_batch(original.clone(), None).await.unwrap();
+            let sent = send(&wrapper, &shared_stream, &prepared, &connected, &received).await;
+            assert_eq!(sent, 2);
+        }
+        assert_eq!(*connected.lock().unwrap(), vec![1]);
+
+        // Evolving the descriptor closes the open stream
+        let descriptor =
+            crate::wrapper::conversion::generate_protobuf_descriptor(&evolved.schema()).unwrap();
+        wrapper
+            .evolve_active_descriptor(&shared_stream, descriptor)
+            .await
+            .unwrap();
+        assert!(shared_stream.lock().await.is_none());
+
+        let prepared = wrapper.prepare_batch(evolved, None).await.unwrap();
+        let sent = send(&wrapper, &shared_stream, &prepared, &connected, &received).await;
+        assert_eq!(sent, 2);
+        assert_eq!(*connected.lock().unwrap(), vec![1, 2]);
+        assert_eq!(received.lock().unwrap().len(), 6);
+    }
+}
@@ -19,6 +19,10 @@ pub struct RetryConfig {
     pub max_delay_ms: u64,
     /// Enable jitter in backoff calculation (default: true)
     pub jitter: bool,
+    /// Error message substrings that force a retry (see `with_error_patterns`)
+    pub retryable_error_patterns: Vec<String>,
+    /// Error message substrings that stop retrying immediately (see `with_error_patterns`)
+    pub fatal_error_patterns: Vec<String>,
 }
 
 impl Default for RetryConfig {
@@ -28,6 +32,8 @@ impl Default for RetryConfig {
             base_delay_ms: 100,
             max_delay_ms: 30000,
             jitter: true,
+            retryable_error_patterns: Vec::new(),
+            fatal_error_patterns: Vec::new(),
         }
     }
 }
@@ -40,9 +46,41 @@ impl RetryConfig {
             base_delay_ms,
             max_delay_ms,
             jitter: true,
+            retryable_error_patterns: Vec::new(),
+            fatal_error_patterns: Vec::new(),
         }
     }
 
+    /// Override error classification by message substring
+    ///
+    /// A stopgap until SDK errors are fully structured: an error whose message contains a
+    /// fatal pattern is never retried, and otherwise one containing a retryable pattern is
+    /// always retried. Errors matching neither keep their default classification
+    /// (`ZerobusError::is_retryable`).
+    ///
+    /// # Arguments
+    ///
+    /// * `retryable` - Substrings that make an error retryable
+    /// * `fatal` - Substrings that make an error fatal (checked first)
+    pub fn with_error_patterns(mut self, retryable: Vec<String>, fatal: Vec<String>) -> Self {
+        self.retryable_error_patterns = retryable;
+        self.fatal_error_patterns = fatal;
+        self
+    }
+
+    /// Whether `error` should be retried, applying the configured error patterns
+    pub fn is_retryable(&self, error: &ZerobusError) -> bool {
+        if self.retryable_error_patterns.is_empty() && self.fatal_error_patterns.is_empty() {
+            return error.is_retryable();
+        }
+        let message = error.to_string();
+        let matches = |patterns: &[String]| patterns.iter().any(|p| message.contains(p.as_str()));
+        if matches(&self.fatal_error_patterns) {
+            return false;
+        }
+        matches(&self.retryable_error_patterns) || error.is_retryable()
+    }
+
     /// Execute a function with retry logic
     ///
     /// Retries the function with exponential backoff + jitter if it returns
@@ -98,15 +136,14 @@ impl RetryConfig {
                 Err(e) => {
                     last_error = Some(e.clone());
 
-                    // Check if error is retryable
-                    if !e.is_retryable() {
+                    // Check if error is retryable (configured patterns first)
+                    if !self.is_retryable(&e) {
                         return (Err(e), attempt_number);
                     }
 
                     // Don't sleep after the last attempt
                     if attempt < self.max_attempts - 1 {
-                        let delay = self.calculate_delay(attempt);
-                        sleep(delay).await;
+                        sleep(self.delay_for(attempt, &e)).await;
                     }
                 }
             }
@@ -126,6 +163,19 @@ impl RetryConfig {
         )
     }
 
+    /// Delay before retrying after `error` on the given attempt
+    ///
+    /// Honors a server retry-after hint: the delay is the larger of the
+    /// computed backoff and the hint, so a throttled request is never retried
+    /// sooner than the server asked.
+    fn delay_for(&self, attempt: u32, error: &ZerobusError) -> Duration {
+        let backoff = self.calculate_delay(attempt);
+        match error.retry_after() {
+            Some(hint) => backoff.max(hint),
+            None => backoff,
+        }
+    }
+
     /// Calculate delay for the given attempt number
     ///
     /// Uses exponential backoff: delay = base_delay * (2 ^ attempt_number)
@@ -139,6 +189,13 @@ impl RetryConfig {
     ///
     /// Returns the delay duration for this attempt
     fn calculate_delay(&self, attempt: u32) -> Duration {
+        self.calculate_delay_with_rng(attempt, &mut rand::thread_rng())
+    }
+
+    /// Calculate delay for the given attempt number, drawing jitter from `rng`
+    ///
+    /// Same as `calculate_delay`, with the random source injected so tests can seed it.
+    pub(crate) fn calculate_delay_with_rng<R: Rng>(&self, attempt: u32, rng: &mut R) -> Duration {
         // Calculate exponential backoff: base_delay * 2^attempt
         let exponential_delay_ms = self.base_delay_ms.saturating_mul(1 << attempt.min(20));
 
@@ -147,7 +204,6 @@ impl RetryConfig {
 
         // Apply full jitter if enabled
         let delay_ms = if self.jitter {
-            let mut rng = rand::thread_rng();
             rng.gen_range(0..=capped_delay_ms)
         } else {
             capped_delay_ms
@@ -188,4 +244,96 @@ mod tests {
         ));
         assert_eq!(attempts, 3);
     }
+
+    #[test]
+    fn test_delay_for_honors_retry_after_hint() {
+        let config = RetryConfig::new(3, 10, 1000);
+        let throttled = ZerobusError::Throttled {
+            message: "slow down".to_string(),
+            retry_after: Some(Duration::from_secs(5)),
+        };
+        for attempt in 0..3 {
+            assert_eq!(
+                config.delay_for(attempt, &throttled),
+                Duration::from_secs(5)
+            );
+        }
+
+        // Without a hint the computed backoff applies unchanged
+        let mut config = RetryConfig::new(3, 10, 1000);
+        config.jitter = false;
+        let error = ZerobusError::ConnectionError("test error".to_string());
+        assert_eq!(config.delay_for(2, &error), Duration::from_millis(40));
+    }
+
+    #[tokio::test]
+    async fn test_retry_waits_for_retry_after_hint() {
+        let config = RetryConfig::new(2, 1, 1);
+        let hint = Duration::from_millis(200);
+        let mut attempts = 0;
+        let start = std::time::Instant::now();
+        let result = config
+            .execute_with_retry(|| {
+                attempts += 1;
+                let first = attempts == 1;
+                async move {
+                    if first {
+                        Err(ZerobusError::Throttled {
+                            message: "rate limited".to_string(),
+                            retry_after: Some(hint),
+                        })
+                    } else {
+                        Ok("success")
+                    }
+                }
+            })
+            .await;
+        assert_eq!(result.unwrap(), "success");
+        assert_eq!(attempts, 2);
+        assert!(start.elapsed() >= hint);
+    }
+
+    /// Attempts `config` makes against a mock send that always fails with `error`
+    async fn attempts_against_failing_mock(config: &RetryConfig, error: ZerobusError) -> u32 {
+        let (result, attempts) = config
+            .execute_with_retry_tracked(|| {
+                let error = error.clone();
+                async move { Err::<(), _>(error) }
+            })
+            .await;
+        assert!(result.is_err());
+        attempts
+    }
+
+    #[tokio::test]
+    async fn test_error_patterns_override_classification() {
+        let config = RetryConfig::new(3, 1, 1).with_error_patterns(
+            vec!["session briefly unavailable".to_string()],
+            vec!["quota permanently exceeded".to_string()],
+        );
+
+        // Not retryable by type, but matches a retryable pattern
+        let auth = ZerobusError::AuthenticationError("session briefly unavailable".to_string());
+        assert!(!auth.is_retryable());
+        assert!(config.is_retryable(&auth));
+        assert_eq!(attempts_against_failing_mock(&config, auth).await, 3);
+
+        // Retryable by type, but matches a fatal pattern: stops after the first attempt
+        let connection = ZerobusError::ConnectionError("quota permanently exceeded".to_string());
+        assert!(connection.is_retryable());
+        assert!(!config.is_retryable(&connection));
+        assert_eq!(attempts_against_failing_mock(&config, connection).await, 1);
+
+        // Fatal patterns win when both match
+        let both = ZerobusError::ConnectionError(
+            "session briefly unavailable: quota permanently exceeded".to_string(),
+        );
+        assert!(!config.is_retryable(&both));
+
+        // Anything else keeps its default classification
+        let other = ZerobusError::ConnectionError("stream reset".to_string());
+        assert_eq!(attempts_against_failing_mock(&config, other).await, 3);
+        let other = ZerobusError::ConfigurationError("bad table".to_string());
+        assert_eq!(attempts_against_failing_mock(&config, other).await, 1);
+    }
 }
@@ -0,0 +1,413 @@
+//! Target table schema lookup and descriptor validation
+//!
+//! This module reads the target table's column definitions from Unity Catalog and
+//! compares a Protobuf descriptor against them, so descriptor/table drift is caught
+//! before the first send closes the stream.
+
+use crate::error::ZerobusError;
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{DescriptorProto, FieldDescriptorProto};
+use serde::Deserialize;
+use tracing::{debug, info, warn};
+
+/// A column of the target table, as reported by Unity Catalog
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ColumnDef {
+    /// Column name
+    pub name: String,
+    /// Unity Catalog type name (e.g. `LONG`, `STRING`, `STRUCT`)
+    pub type_name: String,
+    /// Full type text (e.g. `bigint`, `array<string>`)
+    #[serde(default)]
+    pub type_text: String,
+    /// Whether the column accepts nulls
+    #[serde(default = "default_nullable")]
+    pub nullable: bool,
+}
+
+fn default_nullable() -> bool {
+    true
+}
+
+/// Table metadata response, of which only the columns are used
+#[derive(Debug, Deserialize)]
+struct TableInfoResponse {
+    #[serde(default)]
+    columns: Vec<ColumnDef>,
+}
+
+/// A difference between a Protobuf descriptor and the target table's columns
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableSchemaMismatch {
+    /// The descriptor has a field the table has no column for
+    UnknownColumn { field: String },
+    /// A non-nullable table column has no descriptor field
+    MissingRequiredColumn { column: String, column_type: String },
+    /// The field's Protobuf type cannot be written to the column's type
+    IncompatibleType {
+        field: String,
+        proto_type: String,
+        column_type: String,
+    },
+}
+
+impl std::fmt::Display for TableSchemaMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TableSchemaMismatch::UnknownColumn { field } => {
+                write!(f, "field '{}' has no column in the table", field)
+            }
+            TableSchemaMismatch::MissingRequiredColumn {
+                column,
+                column_type,
+            } => write!(
+                f,
+                "non-nullable column '{}' ({}) has no descriptor field",
+                column, column_type
+            ),
+            TableSchemaMismatch::IncompatibleType {
+                field,
+                proto_type,
+                column_type,
+            } => write!(
+                f,
+                "field '{}' is {} but the column is {}",
+                field, proto_type, column_type
+            ),
+        }
+    }
+}
+
+/// Compare a Protobuf descriptor against the target table's columns
+///
+/// Field and column names are matched case-insensitively, as Unity Catalog column names
+/// are. Only top-level fields are compared; a nested message is checked against a
+/// `STRUCT` (or `MAP`/`DECIMAL`) column but not field by field.
+///
+/// # Arguments
+///
+/// * `descriptor` - Protobuf descriptor to check
+/// * `columns` - Column definitions of the target table
+///
+/// # Returns
+///
+/// Returns every mismatch found, or `Ok(())` if the descriptor fits the table.
+pub fn compare_descriptor_to_table(
+    descriptor: &DescriptorProto,
+    columns: &[ColumnDef],
+) -> Result<(), Vec<TableSchemaMismatch>> {
+    let mut mismatches = Vec::new();
+    for field in &descriptor.field {
+        let field_name = field.name.as_deref().unwrap_or("");
+        let Some(column) = columns
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(field_name))
+        else {
+            mismatches.push(TableSchemaMismatch::UnknownColumn {
+                field: field_name.to_string(),
+            });
+            continue;
+        };
+        if !is_compatible(field, &column.type_name) {
+            mismatches.push(TableSchemaMismatch::IncompatibleType {
+                field: field_name.to_string(),
+                proto_type: proto_type_name(field),
+                column_type: column_type_name(column),
+            });
+        }
+    }
+    for column in columns.iter().filter(|c| !c.nullable) {
+        let has_field = descriptor.field.iter().any(|f| {
+            f.name
+                .as_deref()
+                .is_some_and(|name| name.eq_ignore_ascii_case(&column.name))
+        });
+        if !has_field {
+            mismatches.push(TableSchemaMismatch::MissingRequiredColumn {
+                column: column.name.clone(),
+                column_type: column_type_name(column),
+            });
+        }
+    }
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatches)
+    }
+}
+
+/// Whether a descriptor field can be written to a column of the given Unity Catalog type
+fn is_compatible(field: &FieldDescriptorProto, type_name: &str) -> bool {
+    let type_name = type_name.to_ascii_uppercase();
+    if field.label == Some(Label::Repeated as i32) {
+        // Maps are encoded as repeated key/value entry messages
+        return type_name == "ARRAY" || (field.r#type() == Type::Message && type_name == "MAP");
+    }
+    let compatible: &[&str] = match field.r#type() {
+        Type::Int32 | Type::Sint32 | Type::Sfixed32 | Type::Uint32 | Type::Fixed32 => {
+            &["INT", "SHORT", "BYTE", "DATE"]
+        }
+        Type::Int64 | Type::Sint64 | Type::Sfixed64 | Type::Uint64 | Type::Fixed64 => {
+            &["LONG", "TIMESTAMP", "TIMESTAMP_NTZ", "DATE"]
+        }
+        Type::Float => &["FLOAT"],
+        Type::Double => &["DOUBLE"],
+        Type::Bool => &["BOOLEAN"],
+        Type::String => &["STRING", "CHAR", "VARCHAR"],
+        Type::Bytes => &["BINARY"],
+        Type::Message | Type::Group => &["STRUCT", "DECIMAL"],
+        Type::Enum => &["STRING", "INT"],
+    };
+    compatible.contains(&type_name.as_str())
+}
+
+/// Protobuf type of a field for mismatch reports (e.g. `repeated TYPE_STRING`)
+fn proto_type_name(field: &FieldDescriptorProto) -> String {
+    let type_name = field.r#type().as_str_name();
+    if field.label == Some(Label::Repeated as i32) {
+        format!("repeated {}", type_name)
+    } else {
+        type_name.to_string()
+    }
+}
+
+/// Column type for mismatch reports, preferring the full type text
+fn column_type_name(column: &ColumnDef) -> String {
+    if column.type_text.is_empty() {
+        column.type_name.clone()
+    } else {
+        column.type_text.clone()
+    }
+}
+
+/// Where the target table's column definitions come from
+pub(crate) trait TableSchemaSource {
+    async fn table_columns(&self, table_name: &str) -> Result<Vec<ColumnDef>, ZerobusError>;
+}
+
+/// Unity Catalog tables API, authenticated with the wrapper's OAuth2 credentials
+pub(crate) struct UnityCatalogSchemaSource<'a> {
+    pub(crate) unity_catalog_url: &'a str,
+    pub(crate) client_id: &'a str,
+    pub(crate) client_secret: &'a str,
+}
+
+impl TableSchemaSource for UnityCatalogSchemaSource<'_> {
+    async fn table_columns(&self, table_name: &str) -> Result<Vec<ColumnDef>, ZerobusError> {
+        let token = crate::wrapper::auth::refresh_token(
+            self.unity_catalog_url,
+            self.client_id,
+            self.client_secret,
+        )
+        .await?;
+
+        let tables_url = format!(
+            "{}/api/2.1/unity-catalog/tables/{}",
+            self.unity_catalog_url.trim_end_matches('/'),
+            table_name
+        );
+        debug!("Fetching table schema from {}", tables_url);
+
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()
+            .map_err(|e| {
+                ZerobusError::ConnectionError(format!("Failed to create HTTP client: {}", e))
+            })?;
+        let response = client
+            .get(&tables_url)
+            .bearer_auth(token)
+            .send()
+            .await
+            .map_err(|e| {
+                ZerobusError::ConnectionError(format!("Failed to send table schema request: {}", e))
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            warn!(
+                "Table schema request failed with status {}: {}",
+                status, error_text
+            );
+            return Err(ZerobusError::ConnectionError(format!(
+                "Table schema request for '{}' failed with status {}: {}",
+                table_name, status, error_text
+            )));
+        }
+
+        let table: TableInfoResponse = response.json().await.map_err(|e| {
+            ZerobusError::ConnectionError(format!("Failed to parse table schema response: {}", e))
+        })?;
+        Ok(table.columns)
+    }
+}
+
+/// Fetch the table's columns and check the descriptor against them
+///
+/// # Errors
+///
+/// Returns the source's error if the columns cannot be fetched, or `ConfigurationError`
+/// listing every mismatch if the descriptor does not fit the table.
+pub(crate) async fn check_table_schema<S: TableSchemaSource>(
+    source: &S,
+    table_name: &str,
+    descriptor: &DescriptorProto,
+) -> Result<(), ZerobusError> {
+    let columns = source.table_columns(table_name).await?;
+    match compare_descriptor_to_table(descriptor, &columns) {
+        Ok(()) => {
+            info!(
+                "Protobuf descriptor matches table '{}' ({} columns)",
+                table_name,
+                columns.len()
+            );
+            Ok(())
+        }
+        Err(mismatches) => Err(ZerobusError::ConfigurationError(format!(
+            "Protobuf descriptor does not match table '{}' ({} mismatch(es)): {}",
+            table_name,
+            mismatches.len(),
+            mismatches
+                .iter()
+                .map(|m| m.to_string())
+                .collect::<Vec<_>>()
+                .join("; ")
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    /// Fixed column list standing in for Unity Catalog
+    struct MockSchemaSource {
+        columns: Vec<ColumnDef>,
+    }
+
+    impl TableSchemaSource for MockSchemaSource {
+        async fn table_columns(&self, _table_name: &str) -> Result<Vec<ColumnDef>, ZerobusError> {
+            Ok(self.columns.clone())
+        }
+    }
+
+    fn column(name: &str, type_name: &str, nullable: bool) -> ColumnDef {
+        ColumnDef {
+            name: name.to_string(),
+            type_name: type_name.to_string(),
+            type_text: type_name.to_ascii_lowercase(),
+            nullable,
+        }
+    }
+
+    fn events_table() -> MockSchemaSource {
+        MockSchemaSource {
+            columns: vec![
+                column("id", "LONG", false),
+                column("name", "STRING", true),
+                column("tags", "ARRAY", true),
+                column("ingested_at", "TIMESTAMP", false),
+            ],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_matching_descriptor_passes() {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("NAME", DataType::Utf8, true),
+            Field::new(
+                "tags",
+                DataType::List(std::sync::Arc::new(Field::new(
+                    "item",
+                    DataType::Utf8,
+                    true,
+                ))),
+                true,
+            ),
+            Field::new("ingested_at", DataType::Int64, false),
+        ]);
+        let descriptor = crate::wrapper::conversion::generate_protobuf_descriptor(&schema)
+            .expect("descriptor should generate");
+
+        check_table_schema(&events_table(), "events", &descriptor)
+            .await
+            .expect("descriptor should match the table");
+    }
+
+    #[tokio::test]
+    async fn test_mismatched_descriptor_reports_every_difference() {
+        // `id` retyped to string, `extra` unknown, `ingested_at` (non-nullable) missing
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Utf8, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("extra", DataType::Float64, true),
+        ]);
+        let descriptor = crate::wrapper::conversion::generate_protobuf_descriptor(&schema)
+            .expect("descriptor should generate");
+
+        let mismatches = compare_descriptor_to_table(&descriptor, &events_table().columns)
+            .expect_err("descriptor should not match the table");
+        assert_eq!(
+            mismatches,
+            vec![
+                TableSchemaMismatch::IncompatibleType {
+                    field: "id".to_string(),
+                    proto_type: "TYPE_STRING".to_string(),
+                    column_type: "long".to_string(),
+                },
+                TableSchemaMismatch::UnknownColumn {
+                    field: "extra".to_string(),
+                },
+                TableSchemaMismatch::MissingRequiredColumn {
+                    column: "ingested_at".to_string(),
+                    column_type: "timestamp".to_string(),
+                },
+            ]
+        );
+
+        let err = check_table_schema(&events_table(), "events", &descriptor)
+            .await
+            .expect_err("validation should fail");
+        match err {
+            ZerobusError::ConfigurationError(msg) => {
+                assert!(msg.contains("3 mismatch(es)"), "{}", msg);
+                assert!(msg.contains("field 'extra'"), "{}", msg);
+            }
+            other => panic!("Expected ConfigurationError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_column_def_parses_unity_catalog_response() {
+        let table: TableInfoResponse = serde_json::from_str(
+            r#"{"name": "events", "columns": [
+                {"name": "id", "type_name": "LONG", "type_text": "bigint", "nullable": false},
+                {"name": "name", "type_name": "STRING", "type_text": "string"}
+            ]}"#,
+        )
+        .expect("response should parse");
+        assert_eq!(
+            table.columns,
+            vec![
+                ColumnDef {
+                    name: "id".to_string(),
+                    type_name: "LONG".to_string(),
+                    type_text: "bigint".to_string(),
+                    nullable: false,
+                },
+                ColumnDef {
+                    name: "name".to_string(),
+                    type_name: "STRING".to_string(),
+                    type_text: "string".to_string(),
+                    nullable: true,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,513 @@
+//! Write-ahead log for unacknowledged records
+//!
+//! This module keeps records that have been handed to Zerobus but not yet acknowledged
+//! in local segment files, so they can be re-sent after a crash.
+//!
+//! Each batch sent gets its own segment file holding the batch's descriptor and records,
+//! written and synced before the first record is submitted. Acknowledgments are appended
+//! as they arrive, and the segment is removed once every record is acknowledged. A segment
+//! left behind by a crash or a failed send therefore holds exactly the records whose
+//! acknowledgment was not recorded, which `ZerobusWrapper::recover_wal` re-sends.
+//!
+//! File I/O on the send path runs on tokio's blocking thread pool.
+
+use crate::error::ZerobusError;
+use prost::Message;
+use prost_types::DescriptorProto;
+use std::collections::{BTreeMap, HashSet};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::{debug, warn};
+
+/// First bytes of every segment file
+const SEGMENT_MAGIC: &[u8] = b"ZBWAL1\n";
+/// Extension of segment files
+const SEGMENT_EXTENSION: &str = "wal";
+
+/// Frame holding the segment's encoded descriptor
+const FRAME_DESCRIPTOR: u8 = b'D';
+/// Frame holding a record's row index and Protobuf bytes
+const FRAME_RECORD: u8 = b'R';
+/// Frame holding the row index of an acknowledged record
+const FRAME_ACK: u8 = b'A';
+
+/// Write-ahead log of one table's unacknowledged records
+///
+/// Segments are named `<timestamp>-<sequence>.wal`, in a subdirectory of the log
+/// directory named after the table (see `table_dir_name`), so several tables can share a
+/// directory. Only one wrapper per table should use a directory at a time, since recovery
+/// re-sends every segment not written by the recovering wrapper itself.
+pub struct WriteAheadLog {
+    /// Directory holding the table's segment files
+    dir: PathBuf,
+    /// Segments of sends still in progress, which recovery must leave alone
+    active: Arc<Mutex<HashSet<PathBuf>>>,
+    /// Sequence number of the next segment
+    next_sequence: AtomicU64,
+}
+
+impl WriteAheadLog {
+    /// Open the write-ahead log of a table, creating its directory if needed
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - Directory holding the write-ahead logs
+    /// * `table_name` - Table the records are sent to
+    ///
+    /// # Returns
+    ///
+    /// Returns the write-ahead log, or error if the directory cannot be created.
+    pub fn new(dir: PathBuf, table_name: &str) -> Result<Self, ZerobusError> {
+        let dir = dir.join(table_dir_name(table_name));
+        std::fs::create_dir_all(&dir).map_err(|e| {
+            ZerobusError::ConfigurationError(format!(
+                "Failed to create WAL directory {}: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+        Ok(Self {
+            dir,
+            active: Arc::new(Mutex::new(HashSet::new())),
+            next_sequence: AtomicU64::new(0),
+        })
+    }
+
+    /// Directory holding the table's segment files
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Start a segment for a batch about to be sent
+    ///
+    /// The descriptor and records are written and synced to disk before this returns, so
+    /// records must only be submitted afterwards. The segment is encoded in memory and
+    /// written on the blocking thread pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `descriptor` - Descriptor the records were encoded with
+    /// * `records` - Row index and Protobuf bytes of each record to be sent
+    ///
+    /// # Returns
+    ///
+    /// Returns the segment, or error if it cannot be written.
+    pub async fn begin_segment(
+        &self,
+        descriptor: &DescriptorProto,
+        records: &[(usize, Vec<u8>)],
+    ) -> Result<WalSegment, ZerobusError> {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let sequence = self.next_sequence.fetch_add(1, Ordering::Relaxed);
+        let path = self
+            .dir
+            .join(format!("{}-{}.{}", timestamp, sequence, SEGMENT_EXTENSION));
+
+        let descriptor_bytes = descriptor.encode_to_vec();
+        let mut contents = Vec::with_capacity(
+            SEGMENT_MAGIC.len()
+                + 5
+                + descriptor_bytes.len()
+                + records
+                    .iter()
+                    .map(|(_, bytes)| 13 + bytes.len())
+                    .sum::<usize>(),
+        );
+        contents.extend_from_slice(SEGMENT_MAGIC);
+        contents.push(FRAME_DESCRIPTOR);
+        write_bytes(&mut contents, &descriptor_bytes).map_err(|e| wal_error(&path, e))?;
+        for (row, bytes) in records {
+            contents.push(FRAME_RECORD);
+            contents.extend_from_slice(&(*row as u64).to_le_bytes());
+            write_bytes(&mut contents, bytes).map_err(|e| wal_error(&path, e))?;
+        }
+
+        let target = path.clone();
+        let file = run_blocking(&path, move || {
+            let mut file = std::fs::File::create(&target)?;
+            let written = file.write_all(&contents).and_then(|()| file.sync_data());
+            if let Err(e) = written {
+                // Nothing was submitted yet, so the partial segment holds nothing to recover
+                let _ = std::fs::remove_file(&target);
+                return Err(e);
+            }
+            Ok(file)
+        })
+        .await?;
+
+        self.active
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(path.clone());
+        debug!(
+            "Started WAL segment {} ({} records)",
+            path.display(),
+            records.len()
+        );
+        Ok(WalSegment {
+            path,
+            file: Arc::new(Mutex::new(file)),
+            active: Arc::clone(&self.active),
+        })
+    }
+
+    /// Reopen a segment left behind by an earlier send, to re-send its records
+    ///
+    /// Acknowledgments are appended to the existing file, and recovery skips the segment
+    /// until the returned handle is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Segment file path, as returned by `pending_segments`
+    ///
+    /// # Returns
+    ///
+    /// Returns the segment, or error if it cannot be opened.
+    pub fn resume_segment(&self, path: &Path) -> Result<WalSegment, ZerobusError> {
+        let file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(path)
+            .map_err(|e| wal_error(path, e))?;
+        self.active
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(path.to_path_buf());
+        Ok(WalSegment {
+            path: path.to_path_buf(),
+            file: Arc::new(Mutex::new(file)),
+            active: Arc::clone(&self.active),
+        })
+    }
+
+    /// Read the segments left behind by earlier sends
+    ///
+    /// Segments of sends still in progress in this process are skipped. Unreadable
+    /// segments are logged and skipped rather than failing recovery of the others.
+    ///
+    /// # Returns
+    ///
+    /// Returns the segments holding unacknowledged records, oldest first. Segments with
+    /// every record acknowledged are removed.
+    pub fn pending_segments(&self) -> Result<Vec<PendingSegment>, ZerobusError> {
+        let entries = std::fs::read_dir(&self.dir).map_err(|e| wal_error(&self.dir, e))?;
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == SEGMENT_EXTENSION))
+            .collect();
+        paths.sort();
+
+        let active = self
+            .active
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        let mut segments = Vec::new();
+        for path in paths.into_iter().filter(|path| !active.contains(path)) {
+            match read_segment(&path) {
+                Ok(segment) if segment.records.is_empty() => {
+                    debug!("WAL segment {} fully acknowledged", path.display());
+                    let _ = std::fs::remove_file(&path);
+                }
+                Ok(segment) => segments.push(segment),
+                Err(e) => warn!("Skipping unreadable WAL segment: {}", e),
+            }
+        }
+        Ok(segments)
+    }
+}
+
+/// Segment of a batch being sent
+///
+/// Dropping the segment without `finish` (e.g. because records were left unacknowledged)
+/// leaves its file for `ZerobusWrapper::recover_wal`.
+pub struct WalSegment {
+    /// Segment file path
+    path: PathBuf,
+    /// Segment file, appended acknowledgment frames
+    file: Arc<Mutex<std::fs::File>>,
+    /// Active segments of the log, which this segment leaves when dropped
+    active: Arc<Mutex<HashSet<PathBuf>>>,
+}
+
+impl WalSegment {
+    /// Segment file path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record that records were acknowledged, so recovery does not re-send them
+    ///
+    /// Acknowledgments are written on the blocking thread pool but not synced; one lost in
+    /// a crash only causes a duplicate on recovery.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - Row indices the records were written with
+    pub async fn acknowledge(&self, rows: &[usize]) -> Result<(), ZerobusError> {
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let mut frames = Vec::with_capacity(rows.len() * 9);
+        for row in rows {
+            frames.push(FRAME_ACK);
+            frames.extend_from_slice(&(*row as u64).to_le_bytes());
+        }
+        let file = Arc::clone(&self.file);
+        run_blocking(&self.path, move || {
+            file.lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .write_all(&frames)
+        })
+        .await
+    }
+
+    /// Remove the segment once every record in it was acknowledged
+    pub async fn finish(self) -> Result<(), ZerobusError> {
+        tokio::fs::remove_file(&self.path)
+            .await
+            .map_err(|e| wal_error(&self.path, e))
+    }
+}
+
+impl Drop for WalSegment {
+    fn drop(&mut self) {
+        self.active
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(&self.path);
+    }
+}
+
+/// Unacknowledged records of a segment left behind by an earlier send
+#[derive(Debug, Clone)]
+pub struct PendingSegment {
+    /// Segment file path
+    pub path: PathBuf,
+    /// Descriptor the records were encoded with
+    pub descriptor: DescriptorProto,
+    /// Row index and Protobuf bytes of each unacknowledged record, in row order
+    pub records: Vec<(usize, Vec<u8>)>,
+}
+
+/// Read a segment file, keeping only its unacknowledged records
+///
+/// A frame cut short by a crash ends the segment; the frames before it are kept.
+///
+/// # Arguments
+///
+/// * `path` - Segment file path
+///
+/// # Returns
+///
+/// Returns the segment's unacknowledged records, or error if the file cannot be read or
+/// is not a segment.
+pub fn read_segment(path: &Path) -> Result<PendingSegment, ZerobusError> {
+    let data = std::fs::read(path).map_err(|e| wal_error(path, e))?;
+    let invalid = |what: &str| {
+        ZerobusError::ConfigurationError(format!(
+            "Invalid WAL segment {}: {}",
+            path.display(),
+            what
+        ))
+    };
+    let mut reader = data
+        .strip_prefix(SEGMENT_MAGIC)
+        .ok_or_else(|| invalid("missing header"))?;
+
+    let mut descriptor = None;
+    let mut records = BTreeMap::new();
+    let mut tag = [0u8; 1];
+    while reader.read_exact(&mut tag).is_ok() {
+        let frame = match tag[0] {
+            FRAME_DESCRIPTOR => read_bytes(&mut reader).map(|bytes| {
+                descriptor = Some(bytes);
+            }),
+            FRAME_RECORD => read_row(&mut reader).and_then(|row| {
+                let bytes = read_bytes(&mut reader)?;
+                records.insert(row, bytes);
+                Ok(())
+            }),
+            FRAME_ACK => read_row(&mut reader).map(|row| {
+                records.remove(&row);
+            }),
+            other => return Err(invalid(&format!("unknown frame type {}", other))),
+        };
+        if frame.is_err() {
+            warn!(
+                "WAL segment {} ends with a partial frame, ignoring it",
+                path.display()
+            );
+            break;
+        }
+    }
+
+    let descriptor = descriptor.ok_or_else(|| invalid("missing descriptor"))?;
+    let descriptor = DescriptorProto::decode(descriptor.as_slice())
+        .map_err(|e| invalid(&format!("undecodable descriptor: {}", e)))?;
+    Ok(PendingSegment {
+        path: path.to_path_buf(),
+        descriptor,
+        records: records.into_iter().collect(),
+    })
+}
+
+/// Name of a table's segment directory
+///
+/// Every byte of the table name other than ASCII letters, digits, `_` and `-` is
+/// percent-encoded, so no two tables share a directory (`a.b` is `a%2Eb`).
+fn table_dir_name(table_name: &str) -> String {
+    let mut name = String::with_capacity(table_name.len());
+    for byte in table_name.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-' {
+            name.push(byte as char);
+        } else {
+            name.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    name
+}
+
+/// Run blocking file I/O on the blocking thread pool
+async fn run_blocking<T, F>(path: &Path, io: F) -> Result<T, ZerobusError>
+where
+    F: FnOnce() -> std::io::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(io)
+        .await
+        .map_err(|e| wal_error(path, std::io::Error::other(e)))?
+        .map_err(|e| wal_error(path, e))
+}
+
+/// Write length-prefixed bytes
+fn write_bytes(writer: &mut impl Write, bytes: &[u8]) -> std::io::Result<()> {
+    writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+    writer.write_all(bytes)
+}
+
+/// Read length-prefixed bytes
+fn read_bytes(reader: &mut impl Read) -> std::io::Result<Vec<u8>> {
+    let mut len = [0u8; 4];
+    reader.read_exact(&mut len)?;
+    let mut bytes = vec![0u8; u32::from_le_bytes(len) as usize];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Read a row index
+fn read_row(reader: &mut impl Read) -> std::io::Result<usize> {
+    let mut row = [0u8; 8];
+    reader.read_exact(&mut row)?;
+    Ok(u64::from_le_bytes(row) as usize)
+}
+
+fn wal_error(path: &Path, e: std::io::Error) -> ZerobusError {
+    ZerobusError::ConfigurationError(format!("WAL I/O error on {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descriptor() -> DescriptorProto {
+        DescriptorProto {
+            name: Some("ZerobusMessage".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_acknowledged_records_are_not_pending() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let wal = WriteAheadLog::new(temp_dir.path().to_path_buf(), "events").unwrap();
+        let records = vec![(0, vec![1]), (1, vec![2]), (2, vec![3])];
+
+        let segment = wal.begin_segment(&descriptor(), &records).await.unwrap();
+        // In progress: recovery leaves it alone
+        assert!(wal.pending_segments().unwrap().is_empty());
+        segment.acknowledge(&[1]).await.unwrap();
+        // Crash: the segment is dropped without `finish`
+        drop(segment);
+
+        let pending = wal.pending_segments().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].descriptor, descriptor());
+        assert_eq!(pending[0].records, vec![(0, vec![1]), (2, vec![3])]);
+    }
+
+    #[tokio::test]
+    async fn test_finished_and_fully_acknowledged_segments_are_removed() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let wal = WriteAheadLog::new(temp_dir.path().to_path_buf(), "events").unwrap();
+
+        let finished = wal
+            .begin_segment(&descriptor(), &[(0, vec![1])])
+            .await
+            .unwrap();
+        finished.finish().await.unwrap();
+        let acknowledged = wal
+            .begin_segment(&descriptor(), &[(0, vec![1])])
+            .await
+            .unwrap();
+        acknowledged.acknowledge(&[0]).await.unwrap();
+        drop(acknowledged);
+
+        assert!(wal.pending_segments().unwrap().is_empty());
+        assert_eq!(std::fs::read_dir(wal.dir()).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_partial_frame_and_other_tables_are_ignored() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let wal = WriteAheadLog::new(temp_dir.path().to_path_buf(), "events").unwrap();
+        // Table names sharing a prefix, or differing only in punctuation, never mix
+        for other in [
+            "other",
+            "events-archive",
+            "events.archive",
+            "events_archive",
+        ] {
+            let other = WriteAheadLog::new(temp_dir.path().to_path_buf(), other).unwrap();
+            drop(
+                other
+                    .begin_segment(&descriptor(), &[(0, vec![9])])
+                    .await
+                    .unwrap(),
+            );
+            assert_eq!(other.pending_segments().unwrap().len(), 1);
+        }
+
+        let segment = wal
+            .begin_segment(&descriptor(), &[(0, vec![1]), (1, vec![2])])
+            .await
+            .unwrap();
+        let path = segment.path().to_path_buf();
+        drop(segment);
+        // A crash mid-append leaves a truncated acknowledgment frame
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        file.write_all(&[FRAME_ACK, 1, 0]).unwrap();
+
+        let pending = wal.pending_segments().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].records, vec![(0, vec![1]), (1, vec![2])]);
+    }
+
+    #[test]
+    fn test_table_dir_names_are_distinct() {
+        assert_eq!(table_dir_name("events"), "events");
+        assert_eq!(
+            table_dir_name("main.default.events"),
+            "main%2Edefault%2Eevents"
+        );
+        assert_eq!(table_dir_name("a/b"), "a%2Fb");
+        assert_ne!(table_dir_name("a.b"), table_dir_name("a%2Eb"));
+    }
+}
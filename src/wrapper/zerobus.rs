@@ -114,10 +114,13 @@ pub async fn check_error_6006_backoff(table_name: &str) -> Result<(), ZerobusErr
 
     // Clean up expired entries to prevent memory leak
     let now = Instant::now();
-    state_guard.retain(|_, (_, backoff_until)| *backoff_until > now);
+    let ended = remove_expired_backoffs(&mut state_guard, now, |(_, backoff_until)| *backoff_until);
+    let backoff_until = state_guard.get(table_name).map(|(_, until)| *until);
+    drop(state_guard);
+    notify_backoff_end(ended, BackoffKind::Error6006);
 
-    if let Some((_, backoff_until)) = state_guard.get(table_name) {
-        if *backoff_until > now {
+    if let Some(backoff_until) = backoff_until {
+        if backoff_until > now {
             let remaining = backoff_until.duration_since(now);
             warn!("⏸️  Error 6006 backoff active for table {} - pipeline writes disabled. Remaining backoff: {:.1}s. Will retry after backoff period.", 
                   table_name, remaining.as_secs_f64());
@@ -144,11 +147,16 @@ pub async fn check_failure_rate_backoff(table_name: &str) -> Result<(), ZerobusE
 
     // Clean up expired entries to prevent memory leak
     let now = Instant::now();
-    backoff_guard.retain(|_, state| state.backoff_until > now);
+    let ended = remove_expired_backoffs(&mut backoff_guard, now, |state| state.backoff_until);
+    let backoff_until = backoff_guard
+        .get(table_name)
+        .map(|state| state.backoff_until);
+    drop(backoff_guard);
+    notify_backoff_end(ended, BackoffKind::FailureRate);
 
-    if let Some(state) = backoff_guard.get(table_name) {
-        if state.backoff_until > now {
-            let remaining = state.backoff_until.duration_since(now);
+    if let Some(backoff_until) = backoff_until {
+        if backoff_until > now {
+            let remaining = backoff_until.duration_since(now);
             warn!("⏸️  High failure rate backoff active for table {} - writes disabled. Remaining backoff: {:.1}s. Will retry after backoff period.", 
                   table_name, remaining.as_secs_f64());
             return Err(ZerobusError::ConnectionError(format!(
@@ -160,6 +168,522 @@ pub async fn check_failure_rate_backoff(table_name: &str) -> Result<(), ZerobusE
     Ok(())
 }
 
+/// Kind of backoff currently blocking writes for a table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackoffKind {
+    /// Server reported error 6006 (pipeline creation temporarily blocked)
+    Error6006,
+    /// Network/transmission failure rate exceeded the 1% threshold
+    FailureRate,
+}
+
+/// Read-only snapshot of a table's backoff state
+#[derive(Debug, Clone, PartialEq)]
+pub struct BackoffStatus {
+    /// Active backoff, or `None` if writes are currently allowed
+    pub kind: Option<BackoffKind>,
+    /// Time until the active backoff ends
+    pub remaining: Option<Duration>,
+    /// Network/transmission failure rate in the current window (0.0 until at least
+    /// 100 rows have been recorded)
+    pub current_failure_rate: f64,
+}
+
+/// Get the current backoff state for a table without triggering a send
+///
+/// If both backoffs are active, the one that ends last is reported, since it is the one
+/// that keeps writes blocked.
+///
+/// # Arguments
+///
+/// * `table_name` - Table to query
+///
+/// # Returns
+///
+/// Returns a `BackoffStatus` snapshot.
+pub fn backoff_status(table_name: &str) -> BackoffStatus {
+    let now = Instant::now();
+
+    let error_6006_until = get_error_6006_state()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(table_name)
+        .map(|(_, backoff_until)| *backoff_until)
+        .filter(|backoff_until| *backoff_until > now);
+
+    let failure_rate_until = get_failure_rate_backoff_state()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(table_name)
+        .map(|state| state.backoff_until)
+        .filter(|backoff_until| *backoff_until > now);
+
+    let active = match (error_6006_until, failure_rate_until) {
+        (Some(e), Some(f)) if f > e => Some((BackoffKind::FailureRate, f)),
+        (Some(e), _) => Some((BackoffKind::Error6006, e)),
+        (None, Some(f)) => Some((BackoffKind::FailureRate, f)),
+        (None, None) => None,
+    };
+
+    let current_failure_rate = get_failure_rate_state()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(table_name)
+        .filter(|state| {
+            now.duration_since(state.last_update) <= FAILURE_RATE_WINDOW
+                && state.total_rows >= MIN_ROWS_FOR_FAILURE_RATE
+        })
+        .map(|state| state.failed_rows as f64 / state.total_rows as f64)
+        .unwrap_or(0.0);
+
+    BackoffStatus {
+        kind: active.map(|(kind, _)| kind),
+        remaining: active.map(|(_, backoff_until)| backoff_until.duration_since(now)),
+        current_failure_rate,
+    }
+}
+
+/// Get a table's network/transmission failure rate in the current window
+///
+/// Unlike `BackoffStatus::current_failure_rate`, the rate is reported before the 100-row
+/// minimum for triggering a backoff is reached, so producers can slow down early.
+///
+/// # Arguments
+///
+/// * `table_name` - Table to query
+///
+/// # Returns
+///
+/// Returns the fraction of recorded rows that failed (0.0 if no rows were recorded in the
+/// last 5 minutes, or since the last failure-rate backoff was triggered).
+pub fn failure_rate(table_name: &str) -> f64 {
+    let now = Instant::now();
+    get_failure_rate_state()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(table_name)
+        .filter(|state| {
+            now.duration_since(state.last_update) <= FAILURE_RATE_WINDOW && state.total_rows > 0
+        })
+        .map(|state| state.failed_rows as f64 / state.total_rows as f64)
+        .unwrap_or(0.0)
+}
+
+/// Check whether sending to a table now would run into a backoff
+///
+/// # Arguments
+///
+/// * `table_name` - Table to query
+///
+/// # Returns
+///
+/// Returns `true` if a backoff is active, or if `failure_rate` is above the 1% threshold,
+/// so sending more rows at the current failure rate would trigger a failure-rate backoff.
+pub fn would_backoff(table_name: &str) -> bool {
+    backoff_status(table_name).kind.is_some() || failure_rate(table_name) > FAILURE_RATE_THRESHOLD
+}
+
+/// Receives notifications when a table's backoff starts and ends
+///
+/// Register one with `WrapperConfiguration::with_backoff_observer`. Backoff state is shared
+/// by every wrapper in the process that sends to the table, so observers are notified of
+/// transitions caused by any of them. Each transition is reported exactly once, even
+/// under concurrent sends: a backoff that is extended while active does not start again.
+///
+/// Callbacks run synchronously on the task that observed the transition, after the
+/// backoff state was updated; keep them fast (e.g. hand off to a channel).
+pub trait BackoffObserver: Send + Sync {
+    /// A backoff of `kind` started for `table_name`, blocking writes for about
+    /// `expected_duration`
+    fn on_backoff_start(&self, table_name: &str, kind: BackoffKind, expected_duration: Duration);
+
+    /// The backoff of `kind` for `table_name` ended and writes are allowed again
+    /// (unless another kind of backoff is still active)
+    fn on_backoff_end(&self, table_name: &str, kind: BackoffKind);
+}
+
+impl std::fmt::Debug for dyn BackoffObserver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("BackoffObserver(..)")
+    }
+}
+
+/// Backoff observers per table (held weakly, so dropped wrappers stop being notified)
+type BackoffObservers =
+    std::collections::HashMap<String, Vec<std::sync::Weak<dyn BackoffObserver>>>;
+
+static BACKOFF_OBSERVERS: OnceLock<std::sync::Mutex<BackoffObservers>> = OnceLock::new();
+
+fn get_backoff_observers() -> &'static std::sync::Mutex<BackoffObservers> {
+    BACKOFF_OBSERVERS.get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()))
+}
+
+/// Notify `observer` of backoff transitions for `table_name` while it is alive
+///
+/// Registering the same observer twice for a table has no effect.
+pub(crate) fn register_backoff_observer(
+    table_name: &str,
+    observer: &std::sync::Arc<dyn BackoffObserver>,
+) {
+    let mut observers = get_backoff_observers()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let table_observers = observers.entry(table_name.to_string()).or_default();
+    table_observers.retain(|registered| registered.strong_count() > 0);
+    let observer = std::sync::Arc::downgrade(observer);
+    if !table_observers
+        .iter()
+        .any(|registered| registered.ptr_eq(&observer))
+    {
+        table_observers.push(observer);
+    }
+}
+
+/// Live observers registered for `table_name`
+fn backoff_observers(table_name: &str) -> Vec<std::sync::Arc<dyn BackoffObserver>> {
+    get_backoff_observers()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(table_name)
+        .map(|observers| {
+            observers
+                .iter()
+                .filter_map(|observer| observer.upgrade())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Report a backoff that started (must be called once, by whoever started it)
+///
+/// When observers are registered and a tokio runtime is available, the end is reported
+/// as soon as the backoff expires rather than on the next backoff check.
+fn notify_backoff_start(table_name: &str, kind: BackoffKind, backoff_until: Instant) {
+    let observers = backoff_observers(table_name);
+    if observers.is_empty() {
+        return;
+    }
+    let expected_duration = backoff_until.saturating_duration_since(Instant::now());
+    for observer in observers {
+        observer.on_backoff_start(table_name, kind, expected_duration);
+    }
+
+    if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+        let table_name = table_name.to_string();
+        runtime.spawn(async move {
+            let mut backoff_until = backoff_until;
+            loop {
+                tokio::time::sleep_until(tokio::time::Instant::from_std(backoff_until)).await;
+                match expire_backoff(&table_name, kind) {
+                    // Extended while we slept
+                    Some(extended_until) => backoff_until = extended_until,
+                    None => break,
+                }
+            }
+        });
+    }
+}
+
+/// Report backoffs that ended (must be called once, by whoever removed them)
+fn notify_backoff_end(table_names: Vec<String>, kind: BackoffKind) {
+    for table_name in table_names {
+        for observer in backoff_observers(&table_name) {
+            observer.on_backoff_end(&table_name, kind);
+        }
+    }
+}
+
+/// Remove expired backoff entries, returning the tables whose backoff ended
+fn remove_expired_backoffs<V>(
+    backoffs: &mut std::collections::HashMap<String, V>,
+    now: Instant,
+    backoff_until: impl Fn(&V) -> Instant,
+) -> Vec<String> {
+    let mut ended = Vec::new();
+    backoffs.retain(|table_name, state| {
+        let active = backoff_until(state) > now;
+        if !active {
+            ended.push(table_name.clone());
+        }
+        active
+    });
+    ended
+}
+
+/// Remove `table_name`'s backoff of `kind` if it has expired, reporting its end
+///
+/// Returns when the backoff now ends if it is still active (it was extended).
+fn expire_backoff(table_name: &str, kind: BackoffKind) -> Option<Instant> {
+    let now = Instant::now();
+    let backoff_until = match kind {
+        BackoffKind::Error6006 => {
+            let mut state_guard = get_error_6006_state()
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let backoff_until = state_guard.get(table_name).map(|(_, until)| *until)?;
+            if backoff_until <= now {
+                state_guard.remove(table_name);
+            }
+            backoff_until
+        }
+        BackoffKind::FailureRate => {
+            let mut backoff_guard = get_failure_rate_backoff_state()
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let backoff_until = backoff_guard
+                .get(table_name)
+                .map(|state| state.backoff_until)?;
+            if backoff_until <= now {
+                backoff_guard.remove(table_name);
+            }
+            backoff_until
+        }
+    };
+    if backoff_until > now {
+        return Some(backoff_until);
+    }
+    notify_backoff_end(vec![table_name.to_string()], kind);
+    None
+}
+
+/// Serializable copy of a table's backoff and failure-rate state
+///
+/// Backoff and failure-rate state lives in process memory, so a restart would otherwise
+/// let a persistently failing table be written to again immediately. Times are stored
+/// as Unix timestamps in milliseconds, since `Instant`s do not survive a restart.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BackoffStateSnapshot {
+    /// Table the state belongs to
+    pub table_name: String,
+    /// When the error 6006 backoff ends, if one is active
+    pub error_6006_backoff_until_unix_ms: Option<u64>,
+    /// When the failure-rate backoff ends, if one is active
+    pub failure_rate_backoff_until_unix_ms: Option<u64>,
+    /// Rows recorded in the current failure-rate window
+    pub window_total_rows: usize,
+    /// Network/transmission failures recorded in the current failure-rate window
+    pub window_failed_rows: usize,
+    /// When the failure-rate window was last updated (`None` if nothing was recorded)
+    pub window_last_update_unix_ms: Option<u64>,
+}
+
+/// Wall-clock Unix time in milliseconds of `instant`
+fn instant_to_unix_ms(instant: Instant, now: Instant, wall_now: std::time::SystemTime) -> u64 {
+    let wall = if instant >= now {
+        wall_now + instant.duration_since(now)
+    } else {
+        wall_now - now.duration_since(instant)
+    };
+    wall.duration_since(std::time::UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// `Instant` of a wall-clock Unix time in milliseconds (`None` if not representable)
+fn unix_ms_to_instant(
+    unix_ms: u64,
+    now: Instant,
+    wall_now: std::time::SystemTime,
+) -> Option<Instant> {
+    let wall = std::time::UNIX_EPOCH + Duration::from_millis(unix_ms);
+    match wall.duration_since(wall_now) {
+        Ok(ahead) => now.checked_add(ahead),
+        Err(behind) => now.checked_sub(behind.duration()),
+    }
+}
+
+/// Most recent batch-level error per table, with when it was recorded
+static LAST_ERROR_STATE: OnceLock<
+    std::sync::Mutex<std::collections::HashMap<String, (Instant, ZerobusError)>>,
+> = OnceLock::new();
+
+/// Maximum number of tables whose last error is retained
+///
+/// Beyond this, recording an error for a new table evicts the table with the oldest one.
+const MAX_LAST_ERROR_TABLES: usize = 1024;
+
+fn get_last_error_state(
+) -> &'static std::sync::Mutex<std::collections::HashMap<String, (Instant, ZerobusError)>> {
+    LAST_ERROR_STATE.get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()))
+}
+
+/// Record a batch-level error as the table's last error
+pub(crate) fn record_last_error(table_name: &str, error: &ZerobusError) {
+    let mut state = get_last_error_state()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if state.len() >= MAX_LAST_ERROR_TABLES && !state.contains_key(table_name) {
+        let oldest = state
+            .iter()
+            .min_by_key(|(_, (at, _))| *at)
+            .map(|(table, _)| table.clone());
+        if let Some(oldest) = oldest {
+            state.remove(&oldest);
+        }
+    }
+    state.insert(table_name.to_string(), (Instant::now(), error.clone()));
+}
+
+/// Get the most recent batch-level error for a table (per-table)
+///
+/// Sends record the error that failed a whole batch (not per-row failures), so operators
+/// can see the last failure of a table without scanning logs. Errors are kept in memory
+/// for up to 1024 tables; a later successful send does not clear them.
+///
+/// # Arguments
+///
+/// * `table_name` - Table to query
+///
+/// # Returns
+///
+/// Returns when the error was recorded and the error, or `None` if the table has not
+/// had a batch-level error.
+pub fn last_error(table_name: &str) -> Option<(Instant, ZerobusError)> {
+    get_last_error_state()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(table_name)
+        .cloned()
+}
+
+/// Export a table's backoff and failure-rate state for persisting across restarts
+///
+/// # Arguments
+///
+/// * `table_name` - Table to export
+///
+/// # Returns
+///
+/// Returns a `BackoffStateSnapshot`; expired backoffs and windows are left out.
+pub fn export_backoff_state(table_name: &str) -> BackoffStateSnapshot {
+    let now = Instant::now();
+    let wall_now = std::time::SystemTime::now();
+    let to_unix_ms = |instant| instant_to_unix_ms(instant, now, wall_now);
+
+    let error_6006_backoff_until_unix_ms = get_error_6006_state()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(table_name)
+        .map(|(_, backoff_until)| *backoff_until)
+        .filter(|backoff_until| *backoff_until > now)
+        .map(to_unix_ms);
+
+    let failure_rate_backoff_until_unix_ms = get_failure_rate_backoff_state()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(table_name)
+        .map(|state| state.backoff_until)
+        .filter(|backoff_until| *backoff_until > now)
+        .map(to_unix_ms);
+
+    let window = get_failure_rate_state()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(table_name)
+        .filter(|state| now.duration_since(state.last_update) <= FAILURE_RATE_WINDOW)
+        .cloned();
+
+    BackoffStateSnapshot {
+        table_name: table_name.to_string(),
+        error_6006_backoff_until_unix_ms,
+        failure_rate_backoff_until_unix_ms,
+        window_total_rows: window.as_ref().map_or(0, |state| state.total_rows),
+        window_failed_rows: window.as_ref().map_or(0, |state| state.failed_rows),
+        window_last_update_unix_ms: window.map(|state| to_unix_ms(state.last_update)),
+    }
+}
+
+/// Restore backoff and failure-rate state exported by `export_backoff_state`
+///
+/// Backoffs that have since expired are ignored, and an active backoff is only ever
+/// extended, never shortened. The failure-rate window is restored unless it has expired
+/// or the table already recorded more recent batches.
+///
+/// # Arguments
+///
+/// * `snapshot` - State to restore, for `snapshot.table_name`
+pub fn import_backoff_state(snapshot: &BackoffStateSnapshot) {
+    let now = Instant::now();
+    let wall_now = std::time::SystemTime::now();
+    let to_instant = |unix_ms| unix_ms_to_instant(unix_ms, now, wall_now);
+    let table_name = &snapshot.table_name;
+
+    if let Some(backoff_until) = snapshot
+        .error_6006_backoff_until_unix_ms
+        .and_then(to_instant)
+        .filter(|backoff_until| *backoff_until > now)
+    {
+        let mut state_guard = get_error_6006_state()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let ended = remove_expired_backoffs(&mut state_guard, now, |(_, until)| *until);
+        let started = !state_guard.contains_key(table_name);
+        let entry = state_guard
+            .entry(table_name.clone())
+            .or_insert((now, backoff_until));
+        entry.1 = entry.1.max(backoff_until);
+        info!(
+            "Restored error 6006 backoff for table {}: {:.1}s remaining",
+            table_name,
+            entry.1.duration_since(now).as_secs_f64()
+        );
+        drop(state_guard);
+        notify_backoff_end(ended, BackoffKind::Error6006);
+        if started {
+            notify_backoff_start(table_name, BackoffKind::Error6006, backoff_until);
+        }
+    }
+
+    if let Some(backoff_until) = snapshot
+        .failure_rate_backoff_until_unix_ms
+        .and_then(to_instant)
+        .filter(|backoff_until| *backoff_until > now)
+    {
+        let mut backoff_guard = get_failure_rate_backoff_state()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let ended = remove_expired_backoffs(&mut backoff_guard, now, |state| state.backoff_until);
+        let started = !backoff_guard.contains_key(table_name);
+        let entry = backoff_guard
+            .entry(table_name.clone())
+            .or_insert(FailureRateBackoffState { backoff_until });
+        entry.backoff_until = entry.backoff_until.max(backoff_until);
+        info!(
+            "Restored failure rate backoff for table {}: {:.1}s remaining",
+            table_name,
+            entry.backoff_until.duration_since(now).as_secs_f64()
+        );
+        drop(backoff_guard);
+        notify_backoff_end(ended, BackoffKind::FailureRate);
+        if started {
+            notify_backoff_start(table_name, BackoffKind::FailureRate, backoff_until);
+        }
+    }
+
+    if let Some(last_update) = snapshot
+        .window_last_update_unix_ms
+        .and_then(to_instant)
+        .filter(|last_update| now.duration_since(*last_update) <= FAILURE_RATE_WINDOW)
+    {
+        let mut state_guard = get_failure_rate_state()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let newer_exists = state_guard
+            .get(table_name)
+            .is_some_and(|state| state.last_update > last_update);
+        if !newer_exists {
+            state_guard.insert(
+                table_name.clone(),
+                FailureRateState {
+                    total_rows: snapshot.window_total_rows,
+                    failed_rows: snapshot.window_failed_rows,
+                    last_update,
+                },
+            );
+        }
+    }
+}
+
 /// Update failure rate tracking after a batch
 /// Only counts network/transmission errors, not conversion errors
 pub fn update_failure_rate(
@@ -249,11 +773,15 @@ pub fn update_failure_rate(
         });
 
         // Clean up expired entries
-        backoff_guard.retain(|_, state| state.backoff_until > now);
-        backoff_guard.insert(
-            table_name.to_string(),
-            FailureRateBackoffState { backoff_until },
-        );
+        let ended = remove_expired_backoffs(&mut backoff_guard, now, |state| state.backoff_until);
+        // Only a backoff that wasn't already active starts one
+        let started = backoff_guard
+            .insert(
+                table_name.to_string(),
+                FailureRateBackoffState { backoff_until },
+            )
+            .is_none();
+        drop(backoff_guard);
 
         warn!(
             "🚫 High failure rate detected for table \"{}\": {:.2}% (threshold: {:.2}%). Triggering backoff for {} seconds (jitter-based, base {}s).",
@@ -267,6 +795,13 @@ pub fn update_failure_rate(
         // Reset failure rate tracking after triggering backoff
         table_state.total_rows = 0;
         table_state.failed_rows = 0;
+
+        // Notify without holding any backoff state lock
+        drop(state_guard);
+        notify_backoff_end(ended, BackoffKind::FailureRate);
+        if started {
+            notify_backoff_start(table_name, BackoffKind::FailureRate, backoff_until);
+        }
     }
 }
 
@@ -281,6 +816,8 @@ pub fn update_failure_rate(
 /// * `descriptor_proto` - Protobuf descriptor for schema
 /// * `client_id` - OAuth2 client ID
 /// * `client_secret` - OAuth2 client secret
+/// * `explicit_schema_handshake` - Register the descriptor before the stream is used, and
+///   report its rejection as `ConfigurationError` (see `open_stream`)
 ///
 /// # Returns
 ///
@@ -291,6 +828,7 @@ pub async fn ensure_stream(
     descriptor_proto: DescriptorProto,
     client_id: String,
     client_secret: String,
+    explicit_schema_handshake: bool,
 ) -> Result<ZerobusStream, ZerobusError> {
     // Check if we're in backoff period for error 6006 (per-table)
     check_error_6006_backoff(&table_name).await?;
@@ -318,12 +856,31 @@ pub async fn ensure_stream(
         descriptor_proto,
     };
 
+    // The SDK's stream options have no compression codec, which is why configuration
+    // validation rejects any `transmission_compression` other than `None`
     #[allow(clippy::default_constructed_unit_structs)]
     let options = StreamConfigurationOptions::default();
 
-    let stream_result = sdk
-        .create_stream(table_properties, client_id, client_secret, Some(options))
-        .await;
+    let stream_result = match open_stream(
+        sdk,
+        table_properties,
+        client_id,
+        client_secret,
+        options,
+        explicit_schema_handshake,
+    )
+    .await
+    {
+        Ok(stream) => Ok(stream),
+        Err(OpenStreamError::Create(e)) => Err(e),
+        Err(OpenStreamError::Handshake(e)) => {
+            error!("❌ Schema handshake failed for table {}: {}", table_name, e);
+            return Err(ZerobusError::ConfigurationError(format!(
+                "Schema handshake failed for table {}: Zerobus rejected the Protobuf descriptor: {}",
+                table_name, e
+            )));
+        }
+    };
 
     match stream_result {
         Ok(stream) => {
@@ -350,7 +907,7 @@ pub async fn ensure_stream(
                 let backoff_until = Instant::now() + backoff_duration;
 
                 // Store backoff state per table
-                {
+                let (ended, started) = {
                     let state = get_error_6006_state();
                     let mut state_guard = state.lock().unwrap_or_else(|poisoned| {
                         warn!(
@@ -361,8 +918,16 @@ pub async fn ensure_stream(
                     });
                     // Clean up expired entries before inserting new one
                     let now = Instant::now();
-                    state_guard.retain(|_, (_, backoff_until)| *backoff_until > now);
-                    state_guard.insert(table_name.clone(), (Instant::now(), backoff_until));
+                    let ended = remove_expired_backoffs(&mut state_guard, now, |(_, until)| *until);
+                    // Only a backoff that wasn't already active starts one
+                    let started = state_guard
+                        .insert(table_name.clone(), (Instant::now(), backoff_until))
+                        .is_none();
+                    (ended, started)
+                };
+                notify_backoff_end(ended, BackoffKind::Error6006);
+                if started {
+                    notify_backoff_start(&table_name, BackoffKind::Error6006, backoff_until);
                 }
 
                 error!("🚫 Error 6006 detected: Data ingestion pipeline for table \"{}\" has failed multiple times recently. Pipeline creation is temporarily blocked.", table_name);
@@ -389,10 +954,655 @@ pub async fn ensure_stream(
                 );
             }
 
-            Err(ZerobusError::ConnectionError(format!(
+            let message = format!(
                 "Failed to create Zerobus stream for table {}: {}",
                 table_name, e
-            )))
+            );
+            if let Some(throttled) = throttled_error(&e, &message) {
+                warn!(
+                    "⏸️  Zerobus throttled stream creation for table {}: retry_after={:?}",
+                    table_name,
+                    throttled.retry_after()
+                );
+                return Err(throttled);
+            }
+
+            Err(ZerobusError::ConnectionError(message))
+        }
+    }
+}
+
+/// Opens Zerobus streams, registering the table's descriptor on the way
+pub(crate) trait StreamOpener {
+    type Stream;
+
+    /// Register the descriptor of `table_properties` before its stream is opened
+    ///
+    /// Does nothing by default: SDKs that send the descriptor in the stream's CreateStream
+    /// request register the schema as part of `open`.
+    async fn register_schema(
+        &self,
+        _table_properties: &TableProperties,
+    ) -> Result<(), databricks_zerobus_ingest_sdk::ZerobusError> {
+        Ok(())
+    }
+
+    /// Open a stream for the table
+    async fn open(
+        &self,
+        table_properties: TableProperties,
+        client_id: String,
+        client_secret: String,
+        options: StreamConfigurationOptions,
+    ) -> Result<Self::Stream, databricks_zerobus_ingest_sdk::ZerobusError>;
+}
+
+// SDK 0.1.0 has no separate schema registration call; CreateStream carries the descriptor
+impl StreamOpener for ZerobusSdk {
+    type Stream = ZerobusStream;
+
+    async fn open(
+        &self,
+        table_properties: TableProperties,
+        client_id: String,
+        client_secret: String,
+        options: StreamConfigurationOptions,
+    ) -> Result<ZerobusStream, databricks_zerobus_ingest_sdk::ZerobusError> {
+        self.create_stream(table_properties, client_id, client_secret, Some(options))
+            .await
+    }
+}
+
+/// Step of `open_stream` that failed
+pub(crate) enum OpenStreamError {
+    /// Zerobus rejected the descriptor during the schema handshake
+    Handshake(databricks_zerobus_ingest_sdk::ZerobusError),
+    /// Stream creation failed for another reason
+    Create(databricks_zerobus_ingest_sdk::ZerobusError),
+}
+
+/// Open a stream, performing the explicit schema handshake first if requested
+///
+/// With `explicit_schema_handshake`, the descriptor is registered before the stream is
+/// opened, and a descriptor rejection while opening also counts as a handshake failure,
+/// so schema problems are told apart from failures of the first data records.
+pub(crate) async fn open_stream<O: StreamOpener>(
+    opener: &O,
+    table_properties: TableProperties,
+    client_id: String,
+    client_secret: String,
+    options: StreamConfigurationOptions,
+    explicit_schema_handshake: bool,
+) -> Result<O::Stream, OpenStreamError> {
+    if explicit_schema_handshake {
+        opener
+            .register_schema(&table_properties)
+            .await
+            .map_err(OpenStreamError::Handshake)?;
+        debug!(
+            "Schema handshake completed for table: {}",
+            table_properties.table_name
+        );
+    }
+    opener
+        .open(table_properties, client_id, client_secret, options)
+        .await
+        .map_err(|e| {
+            if explicit_schema_handshake && is_descriptor_rejection(&e) {
+                OpenStreamError::Handshake(e)
+            } else {
+                OpenStreamError::Create(e)
+            }
+        })
+}
+
+/// gRPC status code for INVALID_ARGUMENT
+const GRPC_INVALID_ARGUMENT: i32 = 3;
+
+/// Whether a stream creation error is Zerobus rejecting the stream's descriptor
+///
+/// Invalid arguments also cover e.g. a malformed token, so only those naming the
+/// descriptor or schema count.
+fn is_descriptor_rejection(error: &databricks_zerobus_ingest_sdk::ZerobusError) -> bool {
+    use databricks_zerobus_ingest_sdk::ZerobusError as SdkError;
+
+    let names_schema = |message: &str| {
+        let message = message.to_lowercase();
+        message.contains("descriptor") || message.contains("schema")
+    };
+    match error {
+        SdkError::InvalidArgument(message) => names_schema(message),
+        SdkError::CreateStreamError(status) | SdkError::StreamClosedError(status) => {
+            i32::from(status.code()) == GRPC_INVALID_ARGUMENT && names_schema(status.message())
+        }
+        _ => false,
+    }
+}
+
+/// gRPC status code for RESOURCE_EXHAUSTED (server-side throttling)
+const GRPC_RESOURCE_EXHAUSTED: i32 = 8;
+
+/// Map a throttling SDK error to `ZerobusError::Throttled`
+///
+/// An SDK error counts as throttling when its gRPC status is `RESOURCE_EXHAUSTED`
+/// or carries a `retry-after` metadata entry. The entry (delay in seconds, as in
+/// HTTP `Retry-After`) is preserved as the error's retry-after hint.
+///
+/// # Returns
+///
+/// Returns `None` for errors that are not throttling responses.
+pub(crate) fn throttled_error(
+    error: &databricks_zerobus_ingest_sdk::ZerobusError,
+    message: &str,
+) -> Option<ZerobusError> {
+    use databricks_zerobus_ingest_sdk::ZerobusError as SdkError;
+
+    let status = match error {
+        SdkError::CreateStreamError(status) | SdkError::StreamClosedError(status) => status,
+        _ => return None,
+    };
+    let retry_after = status
+        .metadata()
+        .get("retry-after")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(Duration::from_secs_f64);
+    if retry_after.is_none() && i32::from(status.code()) != GRPC_RESOURCE_EXHAUSTED {
+        return None;
+    }
+    Some(ZerobusError::Throttled {
+        message: message.to_string(),
+        retry_after,
+    })
+}
+
+/// Error message fragments (lowercase) reporting that a table's schema is not yet usable
+const SCHEMA_NOT_READY_PATTERNS: &[&str] = &[
+    "not yet propagated",
+    "schema propagation",
+    "schema is not ready",
+    "table is not ready",
+];
+
+/// Whether an ingest error reports that the table schema is not yet propagated
+///
+/// Freshly created tables can take a few seconds before they accept records; until then
+/// the server closes the stream on the first record with such an error. Matching is
+/// case-insensitive on the error message.
+///
+/// # Arguments
+///
+/// * `message` - Error message from the SDK
+///
+/// # Returns
+///
+/// Returns `true` if the error means the table is not ready yet.
+pub fn is_schema_not_ready(message: &str) -> bool {
+    let message = message.to_lowercase();
+    SCHEMA_NOT_READY_PATTERNS
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
+
+/// Token bucket for client-side rate limiting (per-table)
+#[derive(Clone, Debug)]
+struct RateLimitBucket {
+    /// Available tokens; negative when callers have reserved tokens they are waiting for
+    tokens: f64,
+    /// Last time tokens were added
+    last_refill: Instant,
+}
+
+static RATE_LIMIT_STATE: OnceLock<
+    std::sync::Mutex<std::collections::HashMap<String, RateLimitBucket>>,
+> = OnceLock::new();
+
+fn get_rate_limit_state(
+) -> &'static std::sync::Mutex<std::collections::HashMap<String, RateLimitBucket>> {
+    RATE_LIMIT_STATE.get_or_init(|| std::sync::Mutex::new(std::collections::HashMap::new()))
+}
+
+/// Fraction of a second's worth of records that may be sent as a burst
+const RATE_LIMIT_BURST_SECS: f64 = 0.1;
+
+/// Wait until the table's rate limit allows sending one more record (per-table)
+///
+/// All wrappers sending to the same table share one token bucket, so concurrent batches
+/// share the budget. The bucket holds at most a 100ms burst, so over any window `T` at most
+/// `records_per_sec * (T + 0.1s)` records pass. Each caller reserves its token before
+/// sleeping, so waiting callers are served in order.
+///
+/// # Arguments
+///
+/// * `table_name` - Table the record is sent to
+/// * `records_per_sec` - Maximum sustained rate (must be > 0)
+pub async fn acquire_rate_limit(table_name: &str, records_per_sec: u32) {
+    let rate = f64::from(records_per_sec.max(1));
+    let burst = (rate * RATE_LIMIT_BURST_SECS).max(1.0);
+
+    let wait = {
+        let mut state = get_rate_limit_state()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let now = Instant::now();
+        let bucket = state
+            .entry(table_name.to_string())
+            .or_insert_with(|| RateLimitBucket {
+                tokens: burst,
+                last_refill: now,
+            });
+        let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(burst);
+        bucket.last_refill = now;
+        bucket.tokens -= 1.0;
+        if bucket.tokens < 0.0 {
+            Some(Duration::from_secs_f64(-bucket.tokens / rate))
+        } else {
+            None
+        }
+    };
+
+    if let Some(wait) = wait {
+        tokio::time::sleep(wait).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_schema_not_ready() {
+        assert!(is_schema_not_ready(
+            "Stream closed: Table schema NOT YET PROPAGATED, retry later"
+        ));
+        assert!(is_schema_not_ready("table is not ready for ingestion"));
+        assert!(!is_schema_not_ready("Stream is closed"));
+        assert!(!is_schema_not_ready("Error Code: 6006"));
+    }
+
+    #[test]
+    fn test_backoff_status_reports_longest_active_backoff() {
+        let table_name = "test_backoff_status_6006";
+        let now = Instant::now();
+        assert_eq!(backoff_status(table_name).kind, None);
+
+        get_error_6006_state()
+            .lock()
+            .unwrap()
+            .insert(table_name.to_string(), (now, now + Duration::from_secs(60)));
+        let status = backoff_status(table_name);
+        assert_eq!(status.kind, Some(BackoffKind::Error6006));
+        assert!(status.remaining.unwrap() <= Duration::from_secs(60));
+        assert!(status.remaining.unwrap() > Duration::from_secs(50));
+
+        // A longer failure-rate backoff takes precedence
+        get_failure_rate_backoff_state().lock().unwrap().insert(
+            table_name.to_string(),
+            FailureRateBackoffState {
+                backoff_until: now + Duration::from_secs(120),
+            },
+        );
+        let status = backoff_status(table_name);
+        assert_eq!(status.kind, Some(BackoffKind::FailureRate));
+        assert!(status.remaining.unwrap() > Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn test_backoff_status_reflects_failure_rate_backoff() {
+        let table_name = "test_table_backoff_status";
+
+        let status = backoff_status(table_name);
+        assert_eq!(status.kind, None);
+        assert_eq!(status.remaining, None);
+        assert_eq!(status.current_failure_rate, 0.0);
+
+        // 1 network failure in 200 rows = 0.5%, below threshold
+        let failed_rows = vec![(
+            0,
+            ZerobusError::ConnectionError("Network error".to_string()),
+        )];
+        update_failure_rate(table_name, 200, &failed_rows);
+
+        let status = backoff_status(table_name);
+        assert_eq!(status.kind, None);
+        assert!((status.current_failure_rate - 0.005).abs() < f64::EPSILON);
+
+        // 4 more failures in 100 rows: 5/300 > 1% triggers backoff
+        let failed_rows: Vec<_> = (0..4)
+            .map(|i| {
+                (
+                    i,
+                    ZerobusError::TransmissionError("Network error".to_string()),
+                )
+            })
+            .collect();
+        update_failure_rate(table_name, 100, &failed_rows);
+
+        let status = backoff_status(table_name);
+        assert_eq!(status.kind, Some(BackoffKind::FailureRate));
+        let remaining = status
+            .remaining
+            .expect("backoff should have remaining time");
+        assert!(remaining > Duration::from_secs(25) && remaining <= Duration::from_secs(45));
+        // Tracking resets once backoff is triggered
+        assert_eq!(status.current_failure_rate, 0.0);
+
+        // Querying the status does not clear or extend the backoff
+        assert!(check_failure_rate_backoff(table_name).await.is_err());
+    }
+
+    #[test]
+    fn test_failure_rate_reflects_recorded_failures() {
+        let table_name = "test_failure_rate_reflects_failures";
+        assert_eq!(failure_rate(table_name), 0.0);
+        assert!(!would_backoff(table_name));
+
+        update_failure_rate(table_name, 40, &[]);
+        assert_eq!(failure_rate(table_name), 0.0);
+
+        // Only network/transmission failures count
+        let failures = vec![
+            (0, ZerobusError::TransmissionError("closed".to_string())),
+            (1, ZerobusError::ConnectionError("reset".to_string())),
+            (2, ZerobusError::ConversionError("bad value".to_string())),
+        ];
+        update_failure_rate(table_name, 40, &failures);
+        assert_eq!(failure_rate(table_name), 2.0 / 80.0);
+
+        // Above the threshold but under the 100-row minimum: no backoff yet, but one is near
+        assert_eq!(backoff_status(table_name).kind, None);
+        assert_eq!(backoff_status(table_name).current_failure_rate, 0.0);
+        assert!(would_backoff(table_name));
+    }
+
+    #[tokio::test]
+    async fn test_backoff_state_survives_restart() {
+        let table_name = "test_backoff_state_restart";
+        let failures: Vec<(usize, ZerobusError)> = (0..10)
+            .map(|row| (row, ZerobusError::TransmissionError("closed".to_string())))
+            .collect();
+        update_failure_rate(table_name, 100, &failures);
+        assert_eq!(
+            backoff_status(table_name).kind,
+            Some(BackoffKind::FailureRate)
+        );
+
+        let persisted = serde_json::to_string(&export_backoff_state(table_name)).unwrap();
+
+        // A restart starts from empty in-memory state
+        get_failure_rate_backoff_state()
+            .lock()
+            .unwrap()
+            .remove(table_name);
+        get_failure_rate_state().lock().unwrap().remove(table_name);
+        assert_eq!(backoff_status(table_name).kind, None);
+        assert!(check_failure_rate_backoff(table_name).await.is_ok());
+
+        let snapshot: BackoffStateSnapshot = serde_json::from_str(&persisted).unwrap();
+        assert_eq!(snapshot.table_name, table_name);
+        assert!(snapshot.error_6006_backoff_until_unix_ms.is_none());
+        import_backoff_state(&snapshot);
+
+        let status = backoff_status(table_name);
+        assert_eq!(status.kind, Some(BackoffKind::FailureRate));
+        assert!(status.remaining.unwrap() > Duration::from_secs(25));
+        assert!(check_failure_rate_backoff(table_name).await.is_err());
+    }
+
+    /// Records backoff transitions as `(kind, started)`
+    #[derive(Default)]
+    struct RecordingObserver {
+        events: std::sync::Mutex<Vec<(BackoffKind, bool)>>,
+    }
+
+    impl BackoffObserver for RecordingObserver {
+        fn on_backoff_start(&self, _table_name: &str, kind: BackoffKind, _expected: Duration) {
+            self.events.lock().unwrap().push((kind, true));
+        }
+
+        fn on_backoff_end(&self, _table_name: &str, kind: BackoffKind) {
+            self.events.lock().unwrap().push((kind, false));
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_backoff_observer_notified_once_per_transition() {
+        let table_name = "test_backoff_observer";
+        let recorder = std::sync::Arc::new(RecordingObserver::default());
+        let observer: std::sync::Arc<dyn BackoffObserver> = recorder.clone();
+        register_backoff_observer(table_name, &observer);
+        register_backoff_observer(table_name, &observer);
+
+        // Concurrent batches over the failure rate threshold start a single backoff
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                tokio::spawn(async move {
+                    let failures: Vec<(usize, ZerobusError)> = (0..10)
+                        .map(|row| (row, ZerobusError::TransmissionError("closed".to_string())))
+                        .collect();
+                    update_failure_rate(table_name, 100, &failures);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
         }
+        assert_eq!(
+            *recorder.events.lock().unwrap(),
+            vec![(BackoffKind::FailureRate, true)]
+        );
+
+        // Once it has expired, concurrent checks report its end once
+        get_failure_rate_backoff_state()
+            .lock()
+            .unwrap()
+            .get_mut(table_name)
+            .unwrap()
+            .backoff_until = Instant::now() - Duration::from_millis(1);
+        let (first, second) = tokio::join!(
+            check_failure_rate_backoff(table_name),
+            check_failure_rate_backoff(table_name)
+        );
+        assert!(first.is_ok() && second.is_ok());
+        assert_eq!(
+            *recorder.events.lock().unwrap(),
+            vec![
+                (BackoffKind::FailureRate, true),
+                (BackoffKind::FailureRate, false)
+            ]
+        );
+
+        // A short error 6006 backoff ends on its own, without another check
+        let until = std::time::SystemTime::now() + Duration::from_millis(100);
+        import_backoff_state(&BackoffStateSnapshot {
+            table_name: table_name.to_string(),
+            error_6006_backoff_until_unix_ms: Some(
+                until
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_millis() as u64,
+            ),
+            failure_rate_backoff_until_unix_ms: None,
+            window_total_rows: 0,
+            window_failed_rows: 0,
+            window_last_update_unix_ms: None,
+        });
+        assert_eq!(
+            recorder.events.lock().unwrap().last(),
+            Some(&(BackoffKind::Error6006, true))
+        );
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        assert_eq!(
+            recorder.events.lock().unwrap().last(),
+            Some(&(BackoffKind::Error6006, false))
+        );
+        assert_eq!(recorder.events.lock().unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_caps_records_per_second() {
+        let table_name = "test_rate_limit_caps";
+        let rate = 200u32;
+        let records = 150usize;
+        let sent = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+        // Two concurrent senders to the same table share one budget
+        let start = Instant::now();
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let sent = std::sync::Arc::clone(&sent);
+                tokio::spawn(async move {
+                    for _ in 0..records {
+                        acquire_rate_limit(table_name, rate).await;
+                        sent.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        let elapsed = start.elapsed().as_secs_f64();
+
+        // 300 records at 200/s with a 20-record burst need at least 1.4s
+        let total = sent.load(std::sync::atomic::Ordering::SeqCst) as f64;
+        let burst = f64::from(rate) * RATE_LIMIT_BURST_SECS;
+        assert!(
+            total <= f64::from(rate) * elapsed + burst,
+            "sent {} records in {:.2}s, above {} records/s",
+            total,
+            elapsed,
+            rate
+        );
+    }
+
+    /// Records the stream creation steps it is asked to perform
+    struct MockOpener {
+        calls: std::sync::Mutex<Vec<&'static str>>,
+        handshake_error: Option<&'static str>,
+        open_error: Option<&'static str>,
+    }
+
+    impl MockOpener {
+        fn new(handshake_error: Option<&'static str>, open_error: Option<&'static str>) -> Self {
+            Self {
+                calls: std::sync::Mutex::new(Vec::new()),
+                handshake_error,
+                open_error,
+            }
+        }
+
+        fn calls(&self) -> Vec<&'static str> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    impl StreamOpener for MockOpener {
+        type Stream = ();
+
+        async fn register_schema(
+            &self,
+            table_properties: &TableProperties,
+        ) -> Result<(), databricks_zerobus_ingest_sdk::ZerobusError> {
+            assert_eq!(
+                table_properties.descriptor_proto.name.as_deref(),
+                Some("ZerobusMessage")
+            );
+            self.calls.lock().unwrap().push("register_schema");
+            match self.handshake_error {
+                Some(message) => Err(
+                    databricks_zerobus_ingest_sdk::ZerobusError::InvalidArgument(
+                        message.to_string(),
+                    ),
+                ),
+                None => Ok(()),
+            }
+        }
+
+        async fn open(
+            &self,
+            _table_properties: TableProperties,
+            _client_id: String,
+            _client_secret: String,
+            _options: StreamConfigurationOptions,
+        ) -> Result<(), databricks_zerobus_ingest_sdk::ZerobusError> {
+            self.calls.lock().unwrap().push("open");
+            match self.open_error {
+                Some(message) => Err(
+                    databricks_zerobus_ingest_sdk::ZerobusError::InvalidArgument(
+                        message.to_string(),
+                    ),
+                ),
+                None => Ok(()),
+            }
+        }
+    }
+
+    async fn open_mock_stream(
+        opener: &MockOpener,
+        explicit_schema_handshake: bool,
+    ) -> Result<(), OpenStreamError> {
+        let table_properties = TableProperties {
+            table_name: "catalog.schema.events".to_string(),
+            descriptor_proto: DescriptorProto {
+                name: Some("ZerobusMessage".to_string()),
+                ..Default::default()
+            },
+        };
+        #[allow(clippy::default_constructed_unit_structs)]
+        let options = StreamConfigurationOptions::default();
+        open_stream(
+            opener,
+            table_properties,
+            "client".to_string(),
+            "secret".to_string(),
+            options,
+            explicit_schema_handshake,
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_schema_handshake_runs_before_stream_is_opened() {
+        let opener = MockOpener::new(None, None);
+        assert!(open_mock_stream(&opener, true).await.is_ok());
+        assert_eq!(opener.calls(), vec!["register_schema", "open"]);
+
+        // Without the option the stream is opened directly
+        let opener = MockOpener::new(None, None);
+        assert!(open_mock_stream(&opener, false).await.is_ok());
+        assert_eq!(opener.calls(), vec!["open"]);
+    }
+
+    #[tokio::test]
+    async fn test_schema_handshake_failure_is_reported_as_handshake() {
+        // A failed handshake stops before the stream is opened
+        let opener = MockOpener::new(Some("descriptor has no fields"), None);
+        assert!(matches!(
+            open_mock_stream(&opener, true).await,
+            Err(OpenStreamError::Handshake(_))
+        ));
+        assert_eq!(opener.calls(), vec!["register_schema"]);
+
+        // A descriptor rejected while opening the stream is a handshake failure too, but
+        // only with the option enabled
+        let opener = MockOpener::new(None, Some("Invalid descriptor: unknown field type"));
+        assert!(matches!(
+            open_mock_stream(&opener, true).await,
+            Err(OpenStreamError::Handshake(_))
+        ));
+        assert!(matches!(
+            open_mock_stream(&opener, false).await,
+            Err(OpenStreamError::Create(_))
+        ));
+
+        // Other invalid arguments are not schema problems
+        let opener = MockOpener::new(None, Some("malformed token"));
+        assert!(matches!(
+            open_mock_stream(&opener, true).await,
+            Err(OpenStreamError::Create(_))
+        ));
     }
 }
@@ -17,6 +17,11 @@ fn test_transmission_result_contract_all_fields() {
         total_rows: 2,
         successful_count: 2,
         failed_count: 0,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     // Verify all fields are accessible
@@ -46,6 +51,11 @@ fn test_transmission_result_contract_consistency() {
         total_rows: 3,
         successful_count: 2,
         failed_count: 1,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     assert_eq!(result.total_rows, result.successful_count + result.failed_count);
@@ -65,6 +75,11 @@ fn test_transmission_result_contract_vector_lengths() {
         total_rows: 3,
         successful_count: 2,
         failed_count: 1,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     if let Some(ref successful) = result.successful_rows {
@@ -90,6 +105,11 @@ fn test_transmission_result_contract_backward_compatibility() {
         total_rows: 0,
         successful_count: 0,
         failed_count: 0,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     // Existing pattern: check success and error
@@ -47,6 +47,11 @@ fn test_error_pattern_analysis_multiple_batches() {
         total_rows: 8,
         successful_count: 5,
         failed_count: 3,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let batch2_result = TransmissionResult {
@@ -63,6 +68,11 @@ fn test_error_pattern_analysis_multiple_batches() {
         total_rows: 7,
         successful_count: 5,
         failed_count: 2,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     // Analyze error patterns across batches
@@ -117,6 +127,11 @@ fn test_error_statistics_aggregation() {
         total_rows: 10,
         successful_count: 5,
         failed_count: 5,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let stats = result.get_error_statistics();
@@ -150,6 +165,11 @@ fn test_error_analysis_for_monitoring() {
             total_rows: 5,
             successful_count: 4,
             failed_count: 1,
+            row_sizes: None,
+            failed_row_bytes: None,
+            ack_ids: None,
+            mirror_result: None,
+            failures_truncated: false,
         },
         TransmissionResult {
             success: true,
@@ -162,6 +182,11 @@ fn test_error_analysis_for_monitoring() {
             total_rows: 4,
             successful_count: 3,
             failed_count: 1,
+            row_sizes: None,
+            failed_row_bytes: None,
+            ack_ids: None,
+            mirror_result: None,
+            failures_truncated: false,
         },
         TransmissionResult {
             success: true,
@@ -174,6 +199,11 @@ fn test_error_analysis_for_monitoring() {
             total_rows: 5,
             successful_count: 5,
             failed_count: 0,
+            row_sizes: None,
+            failed_row_bytes: None,
+            ack_ids: None,
+            mirror_result: None,
+            failures_truncated: false,
         },
     ];
 
@@ -223,6 +253,11 @@ fn test_error_message_analysis() {
         total_rows: 5,
         successful_count: 2,
         failed_count: 3,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let error_messages = result.get_error_messages();
@@ -50,6 +50,11 @@ fn test_quarantine_workflow_partial_success() {
         total_rows: 10,
         successful_count: 7,
         failed_count: 3,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     // Step 1: Verify partial success
@@ -102,6 +107,11 @@ fn test_quarantine_workflow_all_success() {
         total_rows: 10,
         successful_count: 10,
         failed_count: 0,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     // No failed rows to quarantine
@@ -135,6 +145,11 @@ fn test_quarantine_workflow_all_failed() {
         total_rows: 10,
         successful_count: 0,
         failed_count: 10,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     // All rows failed
@@ -170,6 +185,11 @@ fn test_quarantine_workflow_error_type_filtering() {
         total_rows: 10,
         successful_count: 6,
         failed_count: 4,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     // Filter by error type
@@ -226,6 +246,11 @@ fn test_quarantine_workflow_empty_batch() {
         total_rows: 0,
         successful_count: 0,
         failed_count: 0,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     // Empty batch should return None for both extractions
@@ -215,3 +215,53 @@ def test_error_statistics_for_monitoring():
     # Verify error type distribution
     assert "ConversionError" in stats["error_type_counts"]
     assert "TransmissionError" in stats["error_type_counts"]
+
+
+def test_failed_rows_as_arrays_builds_arrow_table():
+    """Test failed_rows_as_arrays() output maps directly to PyArrow columns."""
+    pa = pytest.importorskip("pyarrow")
+
+    result = TransmissionResult(
+        success=True,
+        failed_rows=[
+            (1, "ConversionError: bad value"),
+            (3, "ConnectionError: stream reset"),
+        ],
+        successful_rows=[0, 2],
+        total_rows=4,
+        successful_count=2,
+        failed_count=2,
+    )
+
+    row_indices, error_types, error_messages = result.failed_rows_as_arrays()
+    table = pa.table(
+        {
+            "row_index": row_indices,
+            "error_type": error_types,
+            "error_message": error_messages,
+        }
+    )
+
+    assert table.num_rows == 2
+    assert table.column("row_index").to_pylist() == [1, 3]
+    assert table.column("error_type").to_pylist() == [
+        "ConversionError",
+        "ConnectionError",
+    ]
+    messages = table.column("error_message").to_pylist()
+    assert "bad value" in messages[0]
+    assert "stream reset" in messages[1]
+
+
+def test_failed_rows_as_arrays_empty():
+    """Test failed_rows_as_arrays() with no failed rows."""
+    result = TransmissionResult(
+        success=True,
+        failed_rows=None,
+        successful_rows=[0, 1],
+        total_rows=2,
+        successful_count=2,
+        failed_count=0,
+    )
+
+    assert result.failed_rows_as_arrays() == ([], [], [])
@@ -311,3 +311,168 @@ async def test_wrapper_works_without_credentials_when_disabled():
         import shutil
 
         shutil.rmtree(temp_dir, ignore_errors=True)
+
+
+def _known_descriptor_bytes():
+    """Serialized DescriptorProto for a message with `id: int64` and `name: string`."""
+    descriptor_pb2 = pytest.importorskip("google.protobuf.descriptor_pb2")
+
+    descriptor = descriptor_pb2.DescriptorProto(name="ZerobusMessage")
+    for number, (name, field_type) in enumerate(
+        [
+            ("id", descriptor_pb2.FieldDescriptorProto.TYPE_INT64),
+            ("name", descriptor_pb2.FieldDescriptorProto.TYPE_STRING),
+        ],
+        start=1,
+    ):
+        descriptor.field.add(
+            name=name,
+            number=number,
+            type=field_type,
+            label=descriptor_pb2.FieldDescriptorProto.LABEL_OPTIONAL,
+        )
+    return descriptor.SerializeToString()
+
+
+def test_send_batch_with_descriptor(tmp_path):
+    """Test sending a RecordBatch with a caller-supplied descriptor."""
+    from arrow_zerobus_sdk_wrapper import ZerobusWrapper, WrapperConfiguration
+
+    descriptor_bytes = _known_descriptor_bytes()
+    batch = pa.RecordBatch.from_arrays(
+        [
+            pa.array([1, 2, 3], type=pa.int64()),
+            pa.array(["Alice", "Bob", "Charlie"], type=pa.string()),
+        ],
+        names=["id", "name"],
+    )
+
+    config = WrapperConfiguration(
+        endpoint="https://test.cloud.databricks.com",
+        table_name="test_table",
+        debug_enabled=True,
+        debug_output_dir=str(tmp_path),
+        zerobus_writer_disabled=True,
+    )
+    wrapper = ZerobusWrapper(config)
+
+    result = wrapper.send_batch_with_descriptor(batch, descriptor_bytes)
+
+    assert result.success
+    assert result.successful_count == 3
+    wrapper.shutdown()
+
+
+def test_send_batch_with_invalid_descriptor_raises(tmp_path):
+    """Test that undecodable descriptor bytes raise ConfigurationError."""
+    from arrow_zerobus_sdk_wrapper import (
+        ConfigurationError,
+        ZerobusWrapper,
+        WrapperConfiguration,
+    )
+
+    batch = pa.RecordBatch.from_arrays(
+        [pa.array([1], type=pa.int64())], names=["id"]
+    )
+    config = WrapperConfiguration(
+        endpoint="https://test.cloud.databricks.com",
+        table_name="test_table",
+        debug_enabled=True,
+        debug_output_dir=str(tmp_path),
+        zerobus_writer_disabled=True,
+    )
+    wrapper = ZerobusWrapper(config)
+
+    with pytest.raises(ConfigurationError):
+        wrapper.send_batch_with_descriptor(batch, b"\xff\xff\xff")
+
+
+def test_send_serialized(tmp_path):
+    """Test sending pre-serialized Protobuf records without Arrow conversion."""
+    from arrow_zerobus_sdk_wrapper import ZerobusWrapper, WrapperConfiguration
+
+    descriptor_bytes = _known_descriptor_bytes()
+    # id=1, name="Alice" and id=2, name="Bob"
+    records = [b"\x08\x01\x12\x05Alice", b"\x08\x02\x12\x03Bob"]
+
+    config = WrapperConfiguration(
+        endpoint="https://test.cloud.databricks.com",
+        table_name="test_table",
+        debug_enabled=True,
+        debug_output_dir=str(tmp_path),
+        zerobus_writer_disabled=True,
+    )
+    wrapper = ZerobusWrapper(config)
+
+    result = wrapper.send_serialized(records, descriptor_bytes)
+
+    assert result.success
+    assert result.total_rows == 2
+    assert result.successful_count == 2
+    wrapper.shutdown()
+
+
+def test_send_serialized_with_invalid_descriptor_raises(tmp_path):
+    """Test that undecodable descriptor bytes raise ConfigurationError."""
+    from arrow_zerobus_sdk_wrapper import (
+        ConfigurationError,
+        ZerobusWrapper,
+        WrapperConfiguration,
+    )
+
+    config = WrapperConfiguration(
+        endpoint="https://test.cloud.databricks.com",
+        table_name="test_table",
+        debug_enabled=True,
+        debug_output_dir=str(tmp_path),
+        zerobus_writer_disabled=True,
+    )
+    wrapper = ZerobusWrapper(config)
+
+    with pytest.raises(ConfigurationError):
+        wrapper.send_serialized([b"\x08\x01"], b"\xff\xff\xff")
+
+
+def test_health_check_writer_disabled(tmp_path):
+    """Test that health_check reports healthy in writer disabled mode without connecting."""
+    from arrow_zerobus_sdk_wrapper import ZerobusWrapper, WrapperConfiguration
+
+    config = WrapperConfiguration(
+        endpoint="https://test.cloud.databricks.com",
+        table_name="test_table",
+        debug_enabled=True,
+        debug_output_dir=str(tmp_path),
+        zerobus_writer_disabled=True,
+    )
+    wrapper = ZerobusWrapper(config)
+
+    status = wrapper.health_check()
+
+    assert status == {
+        "healthy": True,
+        "latency_ms": None,
+        "error": None,
+        "mode": "disabled",
+    }
+    wrapper.shutdown()
+
+
+def test_health_check_reports_unreachable_unity_catalog():
+    """Test that health_check reports failures in the result instead of raising."""
+    from arrow_zerobus_sdk_wrapper import ZerobusWrapper, WrapperConfiguration
+
+    config = WrapperConfiguration(
+        endpoint="https://test.cloud.databricks.com",
+        table_name="test_table",
+        client_id="client_id",
+        client_secret="client_secret",
+        unity_catalog_url="https://127.0.0.1:1",
+    )
+    wrapper = ZerobusWrapper(config)
+
+    status = wrapper.health_check()
+
+    assert status["healthy"] is False
+    assert status["mode"] == "live"
+    assert status["latency_ms"] is not None
+    assert status["error"]
@@ -0,0 +1,89 @@
+//! Integration tests for the per-batch transform hook
+//!
+//! Uses writer disabled mode; what was sent is checked through the debug descriptor file.
+
+use arrow::array::{Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use arrow_zerobus_sdk_wrapper::{WrapperConfiguration, ZerobusError, ZerobusWrapper};
+use prost::Message;
+use prost_types::FileDescriptorProto;
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn create_batch() -> RecordBatch {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("email", DataType::Utf8, false),
+        Field::new("name", DataType::Utf8, false),
+    ]);
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int64Array::from(vec![1, 2])),
+            Arc::new(StringArray::from(vec!["a@example.com", "b@example.com"])),
+            Arc::new(StringArray::from(vec!["Alice", "Bob"])),
+        ],
+    )
+    .unwrap()
+}
+
+fn create_config(temp_dir: &TempDir) -> WrapperConfiguration {
+    WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true)
+}
+
+#[tokio::test]
+async fn test_batch_transform_drops_column_before_send() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = create_config(&temp_dir).with_batch_transform(Arc::new(|mut batch| {
+        let idx = batch.schema().index_of("email").unwrap();
+        batch.remove_column(idx);
+        Ok(batch)
+    }));
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    let batch = create_batch();
+    let result = wrapper.send_batch(batch.clone()).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.successful_count, 2);
+    // The caller's batch is untouched
+    assert_eq!(batch.num_columns(), 3);
+
+    let descriptor_path = temp_dir.path().join("zerobus/descriptors/test_table.pb");
+    let file_descriptor =
+        FileDescriptorProto::decode(std::fs::read(descriptor_path).unwrap().as_slice()).unwrap();
+    let descriptor = &file_descriptor.message_type[0];
+    let names: Vec<&str> = descriptor
+        .field
+        .iter()
+        .filter_map(|f| f.name.as_deref())
+        .collect();
+    assert_eq!(names, vec!["id", "name"]);
+
+    wrapper.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_batch_transform_error_is_returned() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = create_config(&temp_dir).with_batch_transform(Arc::new(|_| {
+        Err(ZerobusError::ConversionError(
+            "rejected by transform".to_string(),
+        ))
+    }));
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    let result = wrapper.send_batch(create_batch()).await;
+    assert!(matches!(
+        result,
+        Err(ZerobusError::ConversionError(msg)) if msg == "rejected by transform"
+    ));
+
+    wrapper.shutdown().await.unwrap();
+}
@@ -0,0 +1,90 @@
+//! Integration tests for the provided-descriptor column count guard
+//!
+//! Uses writer disabled mode so batches are converted without real Zerobus streams.
+
+use arrow::array::{Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use arrow_zerobus_sdk_wrapper::wrapper::conversion;
+use arrow_zerobus_sdk_wrapper::{WrapperConfiguration, ZerobusError, ZerobusWrapper};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+async fn create_wrapper(temp_dir: &TempDir, tolerance: Option<usize>) -> ZerobusWrapper {
+    let mut config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true);
+    if let Some(tolerance) = tolerance {
+        config = config.with_column_mismatch_tolerance(tolerance);
+    }
+    ZerobusWrapper::new(config).await.unwrap()
+}
+
+fn create_batch() -> RecordBatch {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, false),
+    ]);
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int64Array::from(vec![1, 2])),
+            Arc::new(StringArray::from(vec!["Alice", "Bob"])),
+        ],
+    )
+    .unwrap()
+}
+
+/// Descriptor generated from a schema with two more columns than the batch
+fn wider_descriptor() -> prost_types::DescriptorProto {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, false),
+        Field::new("email", DataType::Utf8, true),
+        Field::new("score", DataType::Int64, true),
+    ]);
+    conversion::generate_protobuf_descriptor(&schema).unwrap()
+}
+
+#[tokio::test]
+async fn test_wider_descriptor_rejected_beyond_tolerance() {
+    let temp_dir = TempDir::new().unwrap();
+    let wrapper = create_wrapper(&temp_dir, Some(1)).await;
+
+    let result = wrapper
+        .send_batch_with_descriptor(create_batch(), Some(wider_descriptor()))
+        .await
+        .unwrap();
+    assert!(!result.success);
+    assert!(matches!(
+        &result.error,
+        Some(ZerobusError::ConfigurationError(msg))
+            if msg.contains("2 mismatched, tolerance 1") && msg.contains("\"email\"")
+    ));
+
+    wrapper.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_wider_descriptor_within_tolerance() {
+    let temp_dir = TempDir::new().unwrap();
+    // Without a tolerance the mismatch is only logged
+    for tolerance in [None, Some(2)] {
+        let wrapper = create_wrapper(&temp_dir, tolerance).await;
+        let result = wrapper
+            .send_batch_with_descriptor(create_batch(), Some(wider_descriptor()))
+            .await
+            .unwrap();
+        assert!(
+            result.success,
+            "tolerance {:?}: {:?}",
+            tolerance, result.error
+        );
+        assert_eq!(result.successful_count, 2);
+        wrapper.shutdown().await.unwrap();
+    }
+}
@@ -1,7 +1,7 @@
 //! Integration tests for configuration
 
 use arrow_zerobus_sdk_wrapper::config::loader;
-use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+use arrow_zerobus_sdk_wrapper::{CompressionType, WrapperConfiguration, ZerobusError};
 use std::fs;
 use tempfile::TempDir;
 
@@ -45,6 +45,31 @@ fn test_config_with_credentials() {
     );
 }
 
+#[test]
+fn test_config_redacted_summary() {
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "catalog.schema.events".to_string(),
+    )
+    .with_credentials(
+        "my-client-id-1234".to_string(),
+        "super-secret-value-5678".to_string(),
+    )
+    .with_unity_catalog("https://test.cloud.databricks.com".to_string());
+
+    let summary = config.redacted_summary();
+    assert!(summary.contains("https://test.cloud.databricks.com"));
+    assert!(summary.contains("catalog.schema.events"));
+    assert_eq!(summary.matches("***redacted***").count(), 2, "{}", summary);
+    assert!(summary.contains("retry_max_attempts: 5"));
+
+    // Neither the summary nor `Debug` ever shows the secrets
+    for rendered in [summary, format!("{:?}", config), format!("{:#?}", config)] {
+        assert!(!rendered.contains("my-client-id-1234"));
+        assert!(!rendered.contains("super-secret-value-5678"));
+    }
+}
+
 #[test]
 fn test_config_validate_success() {
     let config = WrapperConfiguration::new(
@@ -83,3 +108,742 @@ client_secret: test_client_secret
     assert_eq!(config.zerobus_endpoint, "https://test.cloud.databricks.com");
     assert_eq!(config.table_name, "test_table");
 }
+
+/// Asserts the fields set by the TOML/YAML config files below
+fn assert_config_file_fields(config: &WrapperConfiguration) {
+    use secrecy::ExposeSecret;
+
+    assert_eq!(config.zerobus_endpoint, "https://test.cloud.databricks.com");
+    assert_eq!(config.table_name, "catalog.schema.events");
+    assert_eq!(
+        config.unity_catalog_url.as_deref(),
+        Some("https://unity-catalog-url")
+    );
+    assert_eq!(
+        config
+            .client_id
+            .as_ref()
+            .map(|s| s.expose_secret().as_str()),
+        Some("file_client_id")
+    );
+    assert_eq!(
+        config
+            .client_secret
+            .as_ref()
+            .map(|s| s.expose_secret().as_str()),
+        Some("file_client_secret")
+    );
+    assert!(config.zerobus_writer_disabled);
+    assert!(config.observability_enabled);
+    let otlp = config.observability_config.as_ref().unwrap();
+    assert_eq!(otlp.endpoint.as_deref(), Some("http://localhost:4317"));
+    assert_eq!(otlp.write_interval_secs, 10);
+    assert_eq!(otlp.log_level, "debug");
+    assert!(config.debug_arrow_enabled);
+    assert!(!config.debug_protobuf_enabled);
+    assert_eq!(
+        config.debug_output_dir,
+        Some(std::path::PathBuf::from("/tmp/zerobus-debug"))
+    );
+    assert_eq!(config.debug_flush_interval_secs, 2);
+    assert_eq!(config.debug_max_file_size, Some(1048576));
+    assert_eq!(config.debug_max_files_retained, Some(3));
+    assert_eq!(config.debug_global_max_files, Some(50));
+    assert!(config.debug_proto_header);
+    assert_eq!(config.retry_max_attempts, 7);
+    assert_eq!(config.retry_base_delay_ms, 250);
+    assert_eq!(config.retry_max_delay_ms, 5000);
+    assert_eq!(config.connect_retry_max_attempts, Some(2));
+    assert_eq!(config.connect_retry_base_delay_ms, 100);
+    assert_eq!(config.connect_retry_max_delay_ms, 30000);
+    assert_eq!(config.retryable_error_patterns, vec!["briefly unavailable"]);
+    assert_eq!(
+        config.fatal_error_patterns,
+        vec!["does not exist", "permission denied"]
+    );
+}
+
+fn set_config_file_credentials() {
+    std::env::set_var("TEST_CONFIG_FILE_CLIENT_ID", "file_client_id");
+    std::env::set_var("TEST_CONFIG_FILE_CLIENT_SECRET", "file_client_secret");
+}
+
+#[test]
+fn test_from_toml_file_round_trip() {
+    set_config_file_credentials();
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("zerobus.toml");
+    fs::write(
+        &path,
+        r#"
+zerobus_endpoint = "https://test.cloud.databricks.com"
+table_name = "catalog.schema.events"
+unity_catalog_url = "https://unity-catalog-url"
+client_id_env = "TEST_CONFIG_FILE_CLIENT_ID"
+client_secret_env = "TEST_CONFIG_FILE_CLIENT_SECRET"
+zerobus_writer_disabled = true
+retryable_error_patterns = ["briefly unavailable"]
+fatal_error_patterns = ["does not exist", "permission denied"]
+
+[observability]
+enabled = true
+endpoint = "http://localhost:4317"
+write_interval_secs = 10
+log_level = "debug"
+
+[debug]
+arrow_enabled = true
+output_dir = "/tmp/zerobus-debug"
+flush_interval_secs = 2
+max_file_size = 1048576
+max_files_retained = 3
+global_max_files = 50
+proto_header = true
+
+[retry]
+max_attempts = 7
+base_delay_ms = 250
+max_delay_ms = 5000
+
+[connect_retry]
+max_attempts = 2
+"#,
+    )
+    .unwrap();
+
+    let config = WrapperConfiguration::from_toml_file(&path).unwrap();
+
+    assert_config_file_fields(&config);
+}
+
+#[test]
+fn test_from_yaml_file_round_trip() {
+    set_config_file_credentials();
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("zerobus.yaml");
+    fs::write(
+        &path,
+        r#"
+zerobus_endpoint: https://test.cloud.databricks.com
+table_name: catalog.schema.events
+unity_catalog_url: https://unity-catalog-url
+client_id_env: TEST_CONFIG_FILE_CLIENT_ID
+client_secret_env: TEST_CONFIG_FILE_CLIENT_SECRET
+zerobus_writer_disabled: true
+retryable_error_patterns: ["briefly unavailable"]
+fatal_error_patterns: ["does not exist", "permission denied"]
+observability:
+  enabled: true
+  endpoint: http://localhost:4317
+  write_interval_secs: 10
+  log_level: debug
+debug:
+  arrow_enabled: true
+  output_dir: /tmp/zerobus-debug
+  flush_interval_secs: 2
+  max_file_size: 1048576
+  max_files_retained: 3
+  global_max_files: 50
+  proto_header: true
+retry:
+  max_attempts: 7
+  base_delay_ms: 250
+  max_delay_ms: 5000
+connect_retry:
+  max_attempts: 2
+"#,
+    )
+    .unwrap();
+
+    let config = WrapperConfiguration::from_yaml_file(&path).unwrap();
+
+    assert_config_file_fields(&config);
+}
+
+#[test]
+fn test_from_toml_file_rejects_plaintext_secret() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("zerobus.toml");
+    fs::write(
+        &path,
+        r#"
+zerobus_endpoint = "https://test.cloud.databricks.com"
+table_name = "test_table"
+client_id = "plaintext_id"
+client_secret = "plaintext_secret"
+"#,
+    )
+    .unwrap();
+
+    let err = WrapperConfiguration::from_toml_file(&path).unwrap_err();
+
+    assert!(err.to_string().contains("unknown field"), "{}", err);
+}
+
+#[test]
+fn test_from_toml_file_missing_credential_env_var() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("zerobus.toml");
+    fs::write(
+        &path,
+        r#"
+zerobus_endpoint = "https://test.cloud.databricks.com"
+table_name = "test_table"
+client_id_env = "TEST_CONFIG_FILE_UNSET_CLIENT_ID"
+client_secret_env = "TEST_CONFIG_FILE_UNSET_CLIENT_SECRET"
+"#,
+    )
+    .unwrap();
+
+    let err = WrapperConfiguration::from_toml_file(&path).unwrap_err();
+
+    assert!(
+        err.to_string().contains("TEST_CONFIG_FILE_UNSET_CLIENT_ID"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn test_from_toml_file_validates_after_load() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("zerobus.toml");
+    fs::write(
+        &path,
+        r#"
+zerobus_endpoint = "invalid-endpoint"
+table_name = "test_table"
+"#,
+    )
+    .unwrap();
+
+    assert!(WrapperConfiguration::from_toml_file(&path).is_err());
+}
+
+#[test]
+fn test_telemetry_namespace_validation() {
+    let config = |namespace: &str| {
+        WrapperConfiguration::new(
+            "https://test.cloud.databricks.com".to_string(),
+            "test_table".to_string(),
+        )
+        .with_telemetry_namespace(namespace.to_string())
+    };
+
+    assert!(config("orders_ingest").validate().is_ok());
+    assert!(config("svc2").validate().is_ok());
+    for invalid in ["", "2fast", "orders.ingest", "orders-ingest", "_orders"] {
+        let err = config(invalid).validate().unwrap_err();
+        assert!(
+            err.to_string().contains("telemetry_namespace"),
+            "{}: {}",
+            invalid,
+            err
+        );
+    }
+}
+
+#[test]
+fn test_telemetry_names_use_namespace() {
+    use arrow_zerobus_sdk_wrapper::observability::{TelemetryNames, DEFAULT_TELEMETRY_NAMESPACE};
+
+    let default_names = TelemetryNames::default();
+    assert_eq!(default_names.namespace(), DEFAULT_TELEMETRY_NAMESPACE);
+    assert_eq!(default_names.send_batch_span(), "zerobus.send_batch");
+    assert_eq!(default_names.batch_size_bytes(), "zerobus.batch.size_bytes");
+
+    let names = TelemetryNames::new(Some("orders_ingest"));
+    assert_eq!(names.send_batch_span(), "orders_ingest.send_batch");
+    assert_eq!(names.batch_size_bytes(), "orders_ingest.batch.size_bytes");
+    assert_eq!(names.batch_success(), "orders_ingest.batch.success");
+    assert_eq!(names.batch_latency_ms(), "orders_ingest.batch.latency_ms");
+}
+
+#[test]
+fn test_idle_stream_timeout_validation() {
+    use std::time::Duration;
+
+    let config = WrapperConfiguration::new(
+        "https://workspace.cloud.databricks.com".to_string(),
+        "my_table".to_string(),
+    );
+    assert!(config.idle_stream_timeout.is_none());
+
+    let config = config.with_idle_stream_timeout(Duration::from_secs(300));
+    assert_eq!(config.idle_stream_timeout, Some(Duration::from_secs(300)));
+    assert!(config.validate().is_ok());
+
+    let err = config
+        .with_idle_stream_timeout(Duration::ZERO)
+        .validate()
+        .unwrap_err();
+    assert!(err.to_string().contains("idle_stream_timeout"));
+}
+
+#[test]
+fn test_descriptor_limits_validation() {
+    let config = WrapperConfiguration::new(
+        "https://workspace.cloud.databricks.com".to_string(),
+        "my_table".to_string(),
+    );
+    assert_eq!(config.descriptor_max_fields, 2000);
+    assert_eq!(config.descriptor_max_nesting_depth, 10);
+    assert!(config.descriptor_soft_max_fields.is_none());
+
+    // Hard limits may be lowered to any positive value, but not above the protocol maximums
+    assert!(config
+        .clone()
+        .with_descriptor_hard_limits(1, 1)
+        .validate()
+        .is_ok());
+    let err = config
+        .clone()
+        .with_descriptor_hard_limits(2001, 10)
+        .validate()
+        .unwrap_err();
+    assert!(err.to_string().contains("descriptor_max_fields"));
+    let err = config
+        .clone()
+        .with_descriptor_hard_limits(2000, 0)
+        .validate()
+        .unwrap_err();
+    assert!(err.to_string().contains("descriptor_max_nesting_depth"));
+
+    // The nesting depth may be raised up to the absolute maximum of 100
+    let raised = config.clone().with_max_nesting_depth(100);
+    assert_eq!(raised.descriptor_max_nesting_depth, 100);
+    assert!(raised.validate().is_ok());
+    let err = config
+        .clone()
+        .with_max_nesting_depth(101)
+        .validate()
+        .unwrap_err();
+    assert!(err.to_string().contains("descriptor_max_nesting_depth"));
+
+    // Soft limits may equal but not exceed the hard limits
+    let config = config.with_descriptor_hard_limits(100, 5);
+    assert!(config
+        .clone()
+        .with_descriptor_soft_limits(Some(100), Some(5))
+        .validate()
+        .is_ok());
+    let err = config
+        .clone()
+        .with_descriptor_soft_limits(Some(101), None)
+        .validate()
+        .unwrap_err();
+    assert!(err.to_string().contains("descriptor_soft_max_fields"));
+    let err = config
+        .with_descriptor_soft_limits(None, Some(6))
+        .validate()
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("descriptor_soft_max_nesting_depth"));
+}
+
+#[test]
+fn test_send_deadline_validation() {
+    use std::time::Duration;
+
+    let config = WrapperConfiguration::new(
+        "https://workspace.cloud.databricks.com".to_string(),
+        "my_table".to_string(),
+    );
+    assert!(config.send_deadline.is_none());
+
+    let config = config.with_send_deadline(Duration::from_millis(500));
+    assert_eq!(config.send_deadline, Some(Duration::from_millis(500)));
+    assert!(config.validate().is_ok());
+
+    let err = config
+        .with_send_deadline(Duration::ZERO)
+        .validate()
+        .unwrap_err();
+    assert!(err.to_string().contains("send_deadline"));
+}
+
+#[test]
+fn test_schema_propagation_wait_validation() {
+    use std::time::Duration;
+
+    let config = WrapperConfiguration::new(
+        "https://workspace.cloud.databricks.com".to_string(),
+        "my_table".to_string(),
+    );
+    assert!(config.schema_propagation_wait.is_none());
+
+    let config = config.with_schema_propagation_wait(Duration::from_secs(5));
+    assert_eq!(config.schema_propagation_wait, Some(Duration::from_secs(5)));
+    assert!(config.validate().is_ok());
+
+    let err = config
+        .with_schema_propagation_wait(Duration::ZERO)
+        .validate()
+        .unwrap_err();
+    assert!(err.to_string().contains("schema_propagation_wait"));
+}
+
+#[test]
+fn test_conversion_parallelism_validation() {
+    let config = WrapperConfiguration::new(
+        "https://workspace.cloud.databricks.com".to_string(),
+        "my_table".to_string(),
+    );
+    assert_eq!(config.conversion_parallelism, 1);
+
+    let config = config.with_conversion_parallelism(4);
+    assert_eq!(config.conversion_parallelism, 4);
+    assert!(config.validate().is_ok());
+
+    let err = config
+        .with_conversion_parallelism(0)
+        .validate()
+        .unwrap_err();
+    assert!(err.to_string().contains("conversion_parallelism"));
+}
+
+#[test]
+fn test_max_field_name_length_validation() {
+    let config = WrapperConfiguration::new(
+        "https://workspace.cloud.databricks.com".to_string(),
+        "my_table".to_string(),
+    );
+    assert_eq!(config.max_field_name_length, 255);
+    assert!(config
+        .clone()
+        .with_max_field_name_length(64)
+        .validate()
+        .is_ok());
+
+    let err = config.with_max_field_name_length(0).validate().unwrap_err();
+    assert!(err.to_string().contains("max_field_name_length"));
+}
+
+#[test]
+fn test_transmission_compression_validation() {
+    let config = WrapperConfiguration::new(
+        "https://workspace.cloud.databricks.com".to_string(),
+        "my_table".to_string(),
+    );
+    assert_eq!(config.transmission_compression, CompressionType::None);
+    assert!(config
+        .clone()
+        .with_transmission_compression(CompressionType::None)
+        .validate()
+        .is_ok());
+
+    // The SDK has no stream codec, so requesting one is a configuration error
+    for codec in [CompressionType::Gzip, CompressionType::Zstd] {
+        let err = config
+            .clone()
+            .with_transmission_compression(codec)
+            .validate()
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("transmission_compression"),
+            "{}",
+            err
+        );
+    }
+}
+
+#[test]
+fn test_error_patterns_validation() {
+    let config = WrapperConfiguration::new(
+        "https://workspace.cloud.databricks.com".to_string(),
+        "my_table".to_string(),
+    );
+    assert!(config.retryable_error_patterns.is_empty());
+    assert!(config.fatal_error_patterns.is_empty());
+    assert!(config
+        .clone()
+        .with_retryable_error_patterns(vec!["briefly unavailable".to_string()])
+        .with_fatal_error_patterns(vec!["does not exist".to_string()])
+        .validate()
+        .is_ok());
+
+    // An empty pattern would match every error
+    let err = config
+        .clone()
+        .with_retryable_error_patterns(vec![String::new()])
+        .validate()
+        .unwrap_err();
+    assert!(err.to_string().contains("retryable_error_patterns"));
+    let err = config
+        .with_fatal_error_patterns(vec![String::new()])
+        .validate()
+        .unwrap_err();
+    assert!(err.to_string().contains("fatal_error_patterns"));
+}
+
+#[test]
+fn test_configuration_builder() {
+    use std::time::Duration;
+
+    // Required fields may be set in either order
+    let config = WrapperConfiguration::builder()
+        .table_name("catalog.schema.events")
+        .credentials("client_id", "client_secret")
+        .endpoint("https://workspace.cloud.databricks.com")
+        .unity_catalog("https://unity.cloud.databricks.com")
+        .configure(|config| config.with_send_deadline(Duration::from_secs(30)))
+        .configure(|config| config.with_max_pending_futures(10))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        config.zerobus_endpoint,
+        "https://workspace.cloud.databricks.com"
+    );
+    assert_eq!(config.table_name, "catalog.schema.events");
+    use secrecy::ExposeSecret;
+    assert_eq!(
+        config
+            .client_id
+            .as_ref()
+            .map(|s| s.expose_secret().as_str()),
+        Some("client_id")
+    );
+    assert_eq!(
+        config.unity_catalog_url.as_deref(),
+        Some("https://unity.cloud.databricks.com")
+    );
+    assert_eq!(config.send_deadline, Some(Duration::from_secs(30)));
+    assert_eq!(config.max_pending_futures, 10);
+
+    // `build` validates the result
+    let err = WrapperConfiguration::builder()
+        .endpoint("https://workspace.cloud.databricks.com")
+        .table_name("my_table")
+        .configure(|config| config.with_max_pending_futures(0))
+        .build()
+        .unwrap_err();
+    assert!(matches!(err, ZerobusError::ConfigurationError(_)));
+    assert!(err.to_string().contains("max_pending_futures"));
+}
+
+#[test]
+fn test_max_batch_bytes_validation() {
+    use arrow_zerobus_sdk_wrapper::OversizedBatchPolicy;
+
+    let config = WrapperConfiguration::new(
+        "https://workspace.cloud.databricks.com".to_string(),
+        "my_table".to_string(),
+    );
+    assert!(config.max_batch_bytes.is_none());
+    assert_eq!(config.oversized_batch_policy, OversizedBatchPolicy::Reject);
+
+    let config = config
+        .with_max_batch_bytes(1024)
+        .with_oversized_batch_policy(OversizedBatchPolicy::AutoSplit);
+    assert_eq!(config.max_batch_bytes, Some(1024));
+    assert!(config.validate().is_ok());
+
+    let err = config.with_max_batch_bytes(0).validate().unwrap_err();
+    assert!(err.to_string().contains("max_batch_bytes"));
+}
+
+#[test]
+fn test_schema_metadata_keys_validation() {
+    let config = WrapperConfiguration::new(
+        "https://workspace.cloud.databricks.com".to_string(),
+        "my_table".to_string(),
+    );
+    assert!(config.schema_metadata_keys.is_empty());
+
+    // Propagation needs an explicit list of keys to copy
+    let err = config
+        .clone()
+        .with_propagate_schema_metadata(true)
+        .validate()
+        .unwrap_err();
+    assert!(err.to_string().contains("schema_metadata_keys"));
+
+    let config = config
+        .with_propagate_schema_metadata(true)
+        .with_schema_metadata_keys(vec!["source_system".to_string()]);
+    assert!(config.validate().is_ok());
+
+    let err = config
+        .with_schema_metadata_keys(vec!["ARROW:schema".to_string()])
+        .validate()
+        .unwrap_err();
+    assert!(matches!(err, ZerobusError::ConfigurationError(_)));
+    assert!(err.to_string().contains("ARROW:schema"));
+}
+
+#[test]
+fn test_config_endpoint_host_check_disabled_by_default() {
+    // Custom/self-hosted endpoints are accepted unless the check is opted into
+    let config = WrapperConfiguration::new(
+        "https://zerobus.internal.example.com".to_string(),
+        "test_table".to_string(),
+    );
+
+    assert!(config.allowed_endpoint_hosts.is_none());
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_config_allowed_endpoint_hosts_accepts_subdomain() {
+    let config = WrapperConfiguration::new(
+        "https://zerobus.internal.example.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_allowed_endpoint_hosts(vec!["Example.com".to_string()]);
+
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_config_allowed_endpoint_hosts_rejects_mismatch() {
+    let config = WrapperConfiguration::new(
+        "https://zerobus.exmaple.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_allowed_endpoint_hosts(vec!["example.com".to_string()]);
+
+    let err = config.validate().unwrap_err();
+    assert!(matches!(err, ZerobusError::ConfigurationError(_)));
+    assert!(
+        err.to_string()
+            .contains("zerobus_endpoint host 'zerobus.exmaple.com'"),
+        "Error should name the mismatched host, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_config_allowed_endpoint_hosts_does_not_match_suffix_without_dot() {
+    // "notexample.com" must not be treated as a subdomain of "example.com"
+    let config = WrapperConfiguration::new(
+        "https://notexample.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_allowed_endpoint_hosts(vec!["example.com".to_string()]);
+
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_config_databricks_endpoint_check() {
+    let config = WrapperConfiguration::new(
+        "https://1234.zerobus.us-west-2.cloud.databricks.com".to_string(),
+        "catalog.schema.table".to_string(),
+    )
+    .with_unity_catalog("https://dbc-1234.cloud.databricks.com".to_string())
+    .with_databricks_endpoint_check();
+
+    assert!(config.validate().is_ok());
+
+    // Typo'd domain is rejected
+    let config = WrapperConfiguration::new(
+        "https://1234.zerobus.us-west-2.cloud.databrick.com".to_string(),
+        "catalog.schema.table".to_string(),
+    )
+    .with_databricks_endpoint_check();
+
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_config_databricks_endpoint_check_rejects_cloud_mismatch() {
+    let config = WrapperConfiguration::new(
+        "https://1234.zerobus.us-west-2.cloud.databricks.com".to_string(),
+        "catalog.schema.table".to_string(),
+    )
+    .with_unity_catalog("https://adb-1234.5.azuredatabricks.net".to_string())
+    .with_databricks_endpoint_check();
+
+    let err = config.validate().unwrap_err();
+    assert!(
+        err.to_string().contains("different Databricks clouds"),
+        "Error should explain the mismatch, got: {}",
+        err
+    );
+}
+
+#[test]
+fn test_config_validate_invalid_mirror_table() {
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "events".to_string(),
+    );
+
+    let result = config
+        .clone()
+        .with_mirror_table("bad-table".to_string())
+        .validate();
+    assert!(
+        matches!(result, Err(ZerobusError::ConfigurationError(msg)) if msg.starts_with("mirror_table_name:"))
+    );
+    assert!(config
+        .with_mirror_table("events".to_string())
+        .validate()
+        .is_err());
+}
+
+#[test]
+fn test_config_validate_zero_memory_budget() {
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_memory_budget(0);
+
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_config_validate_rejects_invalid_options() {
+    use std::time::Duration;
+
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    );
+    let invalid = [
+        (
+            "zero shutdown_timeout",
+            config.clone().with_shutdown_timeout(Duration::ZERO),
+        ),
+        (
+            "zero connect retry attempts",
+            config.clone().with_connect_retry_config(0, 100, 1000),
+        ),
+        (
+            "connect retry max delay below base",
+            config.clone().with_connect_retry_config(3, 1000, 100),
+        ),
+        ("zero rate limit", config.clone().with_rate_limit(0)),
+        (
+            "stream recreate max delay below base",
+            config.clone().with_stream_recreate_backoff(500, 100),
+        ),
+        (
+            "zero max_buffer_age",
+            config.clone().with_max_buffer_age(Duration::ZERO),
+        ),
+        (
+            "startup schema check without expected schema",
+            config.clone().with_validate_table_schema_on_startup(true),
+        ),
+    ];
+    for (case, config) in invalid {
+        assert!(config.validate().is_err(), "{} should be rejected", case);
+    }
+
+    for package in ["", "com..example", "com.1example", "com-example"] {
+        let config = config.clone().with_proto_package(package.to_string());
+        assert!(
+            config.validate().is_err(),
+            "{:?} should be rejected",
+            package
+        );
+    }
+    assert!(config
+        .with_proto_package("com.example.events".to_string())
+        .validate()
+        .is_ok());
+}
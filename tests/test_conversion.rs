@@ -1,9 +1,12 @@
 //! Integration tests for Arrow to Protobuf conversion
 
 use arrow::array::{Float64Array, Int64Array, StringArray};
-use arrow::datatypes::{DataType, Field, Schema};
+use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
 use arrow::record_batch::RecordBatch;
 use arrow_zerobus_sdk_wrapper::wrapper::conversion;
+use arrow_zerobus_sdk_wrapper::{
+    CustomEncoder, FieldNameTransform, FloatPolicy, NullElementPolicy, ZerobusError,
+};
 use prost_types::{
     field_descriptor_proto::{Label, Type},
     DescriptorProto, FieldDescriptorProto,
@@ -268,3 +271,2580 @@ fn test_generate_descriptor_float_types() {
     assert_eq!(descriptor.field[0].r#type, Some(Type::Float as i32));
     assert_eq!(descriptor.field[1].r#type, Some(Type::Double as i32));
 }
+
+#[test]
+fn test_generate_descriptor_explicit_presence_nullable_field() {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("score", DataType::Float64, true),
+        Field::new(
+            "tags",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            true,
+        ),
+    ]);
+
+    let descriptor = conversion::generate_protobuf_descriptor_with_presence(&schema, true).unwrap();
+
+    // Only the nullable scalar field gets a synthetic oneof
+    assert_eq!(descriptor.oneof_decl.len(), 1);
+    assert_eq!(descriptor.oneof_decl[0].name, Some("_score".to_string()));
+
+    // Non-nullable field has no presence tracking
+    assert_eq!(descriptor.field[0].proto3_optional, None);
+    assert_eq!(descriptor.field[0].oneof_index, None);
+
+    // Nullable field is proto3 optional and points at its synthetic oneof
+    assert_eq!(descriptor.field[1].proto3_optional, Some(true));
+    assert_eq!(descriptor.field[1].oneof_index, Some(0));
+    assert_eq!(descriptor.field[1].label, Some(Label::Optional as i32));
+
+    // Repeated fields never carry presence
+    assert_eq!(descriptor.field[2].label, Some(Label::Repeated as i32));
+    assert_eq!(descriptor.field[2].proto3_optional, None);
+    assert_eq!(descriptor.field[2].oneof_index, None);
+
+    assert!(conversion::validate_protobuf_descriptor(&descriptor).is_ok());
+}
+
+#[test]
+fn test_validate_descriptor_rejects_missing_field_number() {
+    let mut descriptor = conversion::generate_protobuf_descriptor(&Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new(
+            "address",
+            DataType::Struct(vec![Field::new("city", DataType::Utf8, true)].into()),
+            true,
+        ),
+    ]))
+    .unwrap();
+    assert!(conversion::validate_protobuf_descriptor(&descriptor).is_ok());
+
+    // A nested field without a number would otherwise be encoded as field 0
+    descriptor.nested_type[0].field[0].number = None;
+    let err = conversion::validate_protobuf_descriptor(&descriptor).unwrap_err();
+    assert!(
+        matches!(err, ZerobusError::ConfigurationError(_)),
+        "{:?}",
+        err
+    );
+    assert!(err.to_string().contains("'city'"), "{}", err);
+    assert!(err.to_string().contains("has no field number"), "{}", err);
+
+    // Zero is reserved and rejected with the field named too
+    descriptor.nested_type[0].field[0].number = Some(0);
+    let err = conversion::validate_protobuf_descriptor(&descriptor).unwrap_err();
+    assert!(err.to_string().contains("'city'"), "{}", err);
+}
+
+#[test]
+fn test_generate_descriptor_explicit_presence_disabled_by_default() {
+    let schema = Schema::new(vec![Field::new("score", DataType::Float64, true)]);
+
+    let descriptor = conversion::generate_protobuf_descriptor(&schema).unwrap();
+    assert!(descriptor.oneof_decl.is_empty());
+    assert_eq!(descriptor.field[0].proto3_optional, None);
+    assert_eq!(descriptor.field[0].oneof_index, None);
+}
+
+#[test]
+fn test_validate_descriptor_against_schema_matching() {
+    let batch = create_test_batch();
+    let descriptor = conversion::generate_protobuf_descriptor(batch.schema().as_ref()).unwrap();
+
+    assert!(conversion::validate_descriptor_against_schema(&descriptor, &batch.schema()).is_ok());
+}
+
+#[test]
+fn test_validate_descriptor_against_schema_missing_field() {
+    let batch = create_test_batch();
+    let mut descriptor = conversion::generate_protobuf_descriptor(batch.schema().as_ref()).unwrap();
+    descriptor
+        .field
+        .retain(|f| f.name.as_deref() != Some("score"));
+
+    let mismatches =
+        conversion::validate_descriptor_against_schema(&descriptor, &batch.schema()).unwrap_err();
+    assert_eq!(
+        mismatches,
+        vec![conversion::DescriptorMismatch::MissingField {
+            field: "score".to_string()
+        }]
+    );
+}
+
+#[test]
+fn test_validate_descriptor_against_schema_type_mismatch() {
+    let batch = create_test_batch();
+    let mut descriptor = conversion::generate_protobuf_descriptor(batch.schema().as_ref()).unwrap();
+    descriptor.field[0].r#type = Some(Type::String as i32);
+
+    let mismatches =
+        conversion::validate_descriptor_against_schema(&descriptor, &batch.schema()).unwrap_err();
+    assert_eq!(
+        mismatches,
+        vec![conversion::DescriptorMismatch::TypeMismatch {
+            field: "id".to_string(),
+            arrow_type: "Int64".to_string(),
+            descriptor_type: "TYPE_STRING".to_string(),
+        }]
+    );
+}
+
+#[test]
+fn test_validate_descriptor_against_schema_label_mismatch() {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new(
+            "tags",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            true,
+        ),
+    ]);
+    let mut descriptor = conversion::generate_protobuf_descriptor(&schema).unwrap();
+    descriptor.field[0].label = Some(Label::Repeated as i32);
+    descriptor.field[1].label = Some(Label::Optional as i32);
+
+    let mismatches =
+        conversion::validate_descriptor_against_schema(&descriptor, &schema).unwrap_err();
+    assert_eq!(
+        mismatches,
+        vec![
+            conversion::DescriptorMismatch::LabelMismatch {
+                field: "id".to_string(),
+                arrow_is_list: false,
+                descriptor_is_repeated: true,
+            },
+            conversion::DescriptorMismatch::LabelMismatch {
+                field: "tags".to_string(),
+                arrow_is_list: true,
+                descriptor_is_repeated: false,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_validate_descriptor_against_schema_nested_struct() {
+    let schema = Schema::new(vec![Field::new(
+        "address",
+        DataType::Struct(
+            vec![
+                Field::new("city", DataType::Utf8, true),
+                Field::new("zip", DataType::Int32, true),
+            ]
+            .into(),
+        ),
+        true,
+    )]);
+    let mut descriptor = conversion::generate_protobuf_descriptor(&schema).unwrap();
+    descriptor.nested_type[0]
+        .field
+        .retain(|f| f.name.as_deref() != Some("zip"));
+
+    let mismatches =
+        conversion::validate_descriptor_against_schema(&descriptor, &schema).unwrap_err();
+    assert_eq!(
+        mismatches,
+        vec![conversion::DescriptorMismatch::MissingField {
+            field: "address.zip".to_string()
+        }]
+    );
+}
+
+#[test]
+fn test_field_order_independent_of_descriptor() {
+    use arrow::array::{Array, ArrayRef, Int32Array, ListArray, StructArray};
+    use arrow::buffer::OffsetBuffer;
+    use arrow::datatypes::Fields;
+    use prost::Message;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct Address {
+        #[prost(string, optional, tag = "1")]
+        city: Option<String>,
+        #[prost(int32, optional, tag = "2")]
+        zip: Option<i32>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct Item {
+        #[prost(string, optional, tag = "1")]
+        sku: Option<String>,
+        #[prost(int64, optional, tag = "2")]
+        qty: Option<i64>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct Row {
+        #[prost(int64, optional, tag = "1")]
+        id: Option<i64>,
+        #[prost(message, optional, tag = "2")]
+        address: Option<Address>,
+        #[prost(message, repeated, tag = "3")]
+        items: Vec<Item>,
+    }
+
+    let city: ArrayRef = Arc::new(StringArray::from(vec!["Oslo", "Lima"]));
+    let zip: ArrayRef = Arc::new(Int32Array::from(vec![150, 15001]));
+    let sku: ArrayRef = Arc::new(StringArray::from(vec!["a", "b", "c"]));
+    let qty: ArrayRef = Arc::new(Int64Array::from(vec![1, 2, 3]));
+    let id: ArrayRef = Arc::new(Int64Array::from(vec![7, 8]));
+
+    let address = |children: Vec<(&str, DataType, ArrayRef)>| -> (Field, ArrayRef) {
+        let array = StructArray::from(
+            children
+                .into_iter()
+                .map(|(name, data_type, array)| {
+                    (Arc::new(Field::new(name, data_type, true)), array)
+                })
+                .collect::<Vec<_>>(),
+        );
+        (
+            Field::new("address", array.data_type().clone(), true),
+            Arc::new(array),
+        )
+    };
+    let items = |children: Vec<(&str, DataType, ArrayRef)>| -> (Field, ArrayRef) {
+        let fields: Fields = children
+            .iter()
+            .map(|(name, data_type, _)| Field::new(*name, data_type.clone(), true))
+            .collect();
+        let values = StructArray::new(
+            fields.clone(),
+            children.into_iter().map(|(_, _, array)| array).collect(),
+            None,
+        );
+        let item_field = Arc::new(Field::new("item", DataType::Struct(fields), true));
+        let list = ListArray::new(
+            item_field.clone(),
+            OffsetBuffer::from_lengths([2, 1]),
+            Arc::new(values),
+            None,
+        );
+        (
+            Field::new("items", DataType::List(item_field), true),
+            Arc::new(list),
+        )
+    };
+    let batch_of = |columns: Vec<(Field, ArrayRef)>| {
+        let (fields, arrays): (Vec<_>, Vec<_>) = columns.into_iter().unzip();
+        RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays).unwrap()
+    };
+
+    // Descriptor generated from the in-order schema
+    let ordered = batch_of(vec![
+        (Field::new("id", DataType::Int64, true), id.clone()),
+        address(vec![
+            ("city", DataType::Utf8, city.clone()),
+            ("zip", DataType::Int32, zip.clone()),
+        ]),
+        items(vec![
+            ("sku", DataType::Utf8, sku.clone()),
+            ("qty", DataType::Int64, qty.clone()),
+        ]),
+    ]);
+    let descriptor = conversion::generate_protobuf_descriptor(ordered.schema().as_ref()).unwrap();
+
+    // Same data with top-level columns reversed and every struct's children shuffled
+    let shuffled = batch_of(vec![
+        items(vec![
+            ("qty", DataType::Int64, qty),
+            ("sku", DataType::Utf8, sku),
+        ]),
+        address(vec![
+            ("zip", DataType::Int32, zip),
+            ("city", DataType::Utf8, city),
+        ]),
+        (Field::new("id", DataType::Int64, true), id),
+    ]);
+    assert!(
+        conversion::validate_descriptor_against_schema(&descriptor, &shuffled.schema()).is_ok()
+    );
+
+    let decode = |batch: &RecordBatch| -> Vec<Row> {
+        let result = conversion::record_batch_to_protobuf_bytes(batch, &descriptor);
+        assert!(result.failed_rows.is_empty(), "{:?}", result.failed_rows);
+        result
+            .successful_bytes
+            .iter()
+            .map(|(_, bytes)| Row::decode(bytes.as_slice()).unwrap())
+            .collect()
+    };
+    let expected = decode(&ordered);
+    assert_eq!(
+        expected[0],
+        Row {
+            id: Some(7),
+            address: Some(Address {
+                city: Some("Oslo".to_string()),
+                zip: Some(150),
+            }),
+            items: vec![
+                Item {
+                    sku: Some("a".to_string()),
+                    qty: Some(1),
+                },
+                Item {
+                    sku: Some("b".to_string()),
+                    qty: Some(2),
+                },
+            ],
+        }
+    );
+    assert_eq!(decode(&shuffled), expected);
+
+    // Flat batches take a separate encoding path; reversed columns decode the same
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct FlatRow {
+        #[prost(int64, optional, tag = "1")]
+        id: Option<i64>,
+        #[prost(string, optional, tag = "2")]
+        name: Option<String>,
+        #[prost(double, optional, tag = "3")]
+        score: Option<f64>,
+    }
+    let flat_descriptor = create_test_descriptor();
+    let flat = create_test_batch();
+    let decode_flat = |batch: &RecordBatch| -> Vec<FlatRow> {
+        conversion::record_batch_to_protobuf_bytes(batch, &flat_descriptor)
+            .successful_bytes
+            .iter()
+            .map(|(_, bytes)| FlatRow::decode(bytes.as_slice()).unwrap())
+            .collect()
+    };
+    let expected_flat = decode_flat(&flat);
+    assert_eq!(expected_flat.len(), 3);
+    assert_eq!(expected_flat[0].name.as_deref(), Some("Alice"));
+    assert_eq!(
+        decode_flat(&flat.project(&[2, 1, 0]).unwrap()),
+        expected_flat
+    );
+}
+
+#[test]
+fn test_validate_descriptor_against_schema_allows_extra_descriptor_fields() {
+    let batch = create_test_batch();
+    let mut descriptor = conversion::generate_protobuf_descriptor(batch.schema().as_ref()).unwrap();
+    descriptor.field.push(FieldDescriptorProto {
+        name: Some("unused".to_string()),
+        number: Some(99),
+        label: Some(Label::Optional as i32),
+        r#type: Some(Type::String as i32),
+        ..Default::default()
+    });
+
+    assert!(conversion::validate_descriptor_against_schema(&descriptor, &batch.schema()).is_ok());
+}
+
+#[test]
+fn test_schema_metadata_options() {
+    let schema = Schema::new(vec![Field::new("id", DataType::Int64, false)]).with_metadata(
+        [
+            ("source_version".to_string(), "v2".to_string()),
+            ("source_system".to_string(), "orders".to_string()),
+        ]
+        .into_iter()
+        .collect(),
+    );
+
+    let keys = vec!["source_system".to_string(), "source_version".to_string()];
+    let options = conversion::schema_metadata_options(&schema, &keys).unwrap();
+    let entries: Vec<(String, Vec<u8>)> = options
+        .uninterpreted_option
+        .iter()
+        .map(|o| {
+            assert_eq!(o.name.len(), 1);
+            assert!(!o.name[0].is_extension);
+            (o.name[0].name_part.clone(), o.string_value.clone().unwrap())
+        })
+        .collect();
+
+    // Sorted by key for deterministic descriptors
+    assert_eq!(
+        entries,
+        vec![
+            ("source_system".to_string(), b"orders".to_vec()),
+            ("source_version".to_string(), b"v2".to_vec()),
+        ]
+    );
+}
+
+#[test]
+fn test_schema_metadata_options_empty_metadata() {
+    let schema = Schema::new(vec![Field::new("id", DataType::Int64, false)]);
+    assert!(conversion::schema_metadata_options(&schema, &["source".to_string()]).is_none());
+}
+
+#[test]
+fn test_schema_metadata_options_selected_keys_only() {
+    let schema = Schema::new(vec![Field::new("id", DataType::Int64, false)]).with_metadata(
+        [
+            ("ARROW:schema".to_string(), "...".to_string()),
+            ("source version".to_string(), "v2".to_string()),
+            ("source_system".to_string(), "orders".to_string()),
+            ("owner".to_string(), "team".to_string()),
+        ]
+        .into_iter()
+        .collect(),
+    );
+
+    // Unlisted keys, and listed keys that are not valid option names, are skipped
+    let keys = vec![
+        "source_system".to_string(),
+        "ARROW:schema".to_string(),
+        "source version".to_string(),
+    ];
+    let options = conversion::schema_metadata_options(&schema, &keys).unwrap();
+    let names: Vec<&str> = options
+        .uninterpreted_option
+        .iter()
+        .map(|o| o.name[0].name_part.as_str())
+        .collect();
+    assert_eq!(names, vec!["source_system"]);
+
+    assert!(conversion::schema_metadata_options(&schema, &["owner_id".to_string()]).is_none());
+    assert!(conversion::is_option_name("_source2"));
+    assert!(!conversion::is_option_name("2source"));
+    assert!(!conversion::is_option_name(""));
+}
+
+#[test]
+fn test_arrow_schema_to_databricks_ddl() {
+    use arrow::datatypes::TimeUnit;
+
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("amount", DataType::Decimal128(18, 2), true),
+        Field::new(
+            "created_at",
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            false,
+        ),
+        Field::new(
+            "local_time",
+            DataType::Timestamp(TimeUnit::Microsecond, None),
+            true,
+        ),
+        Field::new("day", DataType::Date32, true),
+        Field::new(
+            "tags",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            true,
+        ),
+        Field::new(
+            "address",
+            DataType::Struct(
+                vec![
+                    Field::new("city", DataType::Utf8, false),
+                    Field::new("zip", DataType::Int32, true),
+                ]
+                .into(),
+            ),
+            true,
+        ),
+    ]);
+
+    let ddl = conversion::arrow_schema_to_databricks_ddl(&schema, "main.default.events").unwrap();
+    assert_eq!(
+        ddl,
+        "CREATE TABLE `main`.`default`.`events` (\n\
+         \x20 `id` BIGINT NOT NULL,\n\
+         \x20 `name` STRING,\n\
+         \x20 `amount` DECIMAL(18, 2),\n\
+         \x20 `created_at` TIMESTAMP NOT NULL,\n\
+         \x20 `local_time` TIMESTAMP_NTZ,\n\
+         \x20 `day` DATE,\n\
+         \x20 `tags` ARRAY<STRING>,\n\
+         \x20 `address` STRUCT<`city`: STRING NOT NULL, `zip`: INT>\n\
+         )"
+    );
+}
+
+#[test]
+fn test_arrow_schema_to_databricks_ddl_invalid_input() {
+    let schema = Schema::new(vec![Field::new("bad-name", DataType::Int64, false)]);
+    assert!(conversion::arrow_schema_to_databricks_ddl(&schema, "events").is_err());
+
+    let schema = Schema::new(vec![Field::new("id", DataType::Int64, false)]);
+    assert!(conversion::arrow_schema_to_databricks_ddl(&schema, "main..events").is_err());
+    assert!(conversion::arrow_schema_to_databricks_ddl(&Schema::empty(), "events").is_err());
+}
+
+#[test]
+fn test_decimal128_round_trip_as_nested_message() {
+    use arrow::array::Decimal128Array;
+    use prost::Message;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct DecimalValue {
+        #[prost(bytes = "vec", tag = "1")]
+        unscaled: Vec<u8>,
+        #[prost(int32, tag = "2")]
+        scale: i32,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct Row {
+        #[prost(message, optional, tag = "1")]
+        amount: Option<DecimalValue>,
+    }
+
+    // Values beyond i64 range and negative values must survive exactly
+    let values = vec![
+        Some(i128::MAX),
+        Some(i128::MIN),
+        Some(-12_345_678_901_234_567_890_123_456_789i128),
+        Some(0),
+        None,
+    ];
+    let array = Decimal128Array::from(values.clone())
+        .with_precision_and_scale(38, 6)
+        .unwrap();
+    let schema = Schema::new(vec![Field::new(
+        "amount",
+        DataType::Decimal128(38, 6),
+        true,
+    )]);
+    let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(array)]).unwrap();
+
+    let descriptor = DescriptorProto {
+        name: Some("ZerobusMessage".to_string()),
+        field: vec![FieldDescriptorProto {
+            name: Some("amount".to_string()),
+            number: Some(1),
+            label: Some(Label::Optional as i32),
+            r#type: Some(Type::Message as i32),
+            type_name: Some(".ZerobusMessage.Decimal".to_string()),
+            ..Default::default()
+        }],
+        nested_type: vec![DescriptorProto {
+            name: Some("Decimal".to_string()),
+            field: vec![
+                FieldDescriptorProto {
+                    name: Some(conversion::DECIMAL_UNSCALED_FIELD.to_string()),
+                    number: Some(1),
+                    label: Some(Label::Optional as i32),
+                    r#type: Some(Type::Bytes as i32),
+                    ..Default::default()
+                },
+                FieldDescriptorProto {
+                    name: Some(conversion::DECIMAL_SCALE_FIELD.to_string()),
+                    number: Some(2),
+                    label: Some(Label::Optional as i32),
+                    r#type: Some(Type::Int32 as i32),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    assert!(conversion::validate_descriptor_against_schema(&descriptor, &schema).is_ok());
+
+    let result = conversion::record_batch_to_protobuf_bytes(&batch, &descriptor);
+    assert!(result.failed_rows.is_empty());
+    assert_eq!(result.successful_bytes.len(), values.len());
+
+    for ((row_idx, bytes), expected) in result.successful_bytes.iter().zip(values) {
+        let row = Row::decode(bytes.as_slice()).unwrap();
+        match expected {
+            Some(expected) => {
+                let amount = row.amount.expect("decimal message should be present");
+                assert_eq!(amount.scale, 6, "row {}", row_idx);
+                let unscaled: [u8; 16] = amount.unscaled.as_slice().try_into().unwrap();
+                assert_eq!(i128::from_be_bytes(unscaled), expected, "row {}", row_idx);
+            }
+            None => assert!(row.amount.is_none(), "row {}", row_idx),
+        }
+    }
+}
+
+#[test]
+fn test_encode_decimal128_as_message_rejects_non_decimal_message() {
+    let not_decimal = DescriptorProto {
+        name: Some("Point".to_string()),
+        field: vec![FieldDescriptorProto {
+            name: Some("x".to_string()),
+            number: Some(1),
+            r#type: Some(Type::Int32 as i32),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let mut buffer = Vec::new();
+    assert!(conversion::encode_decimal128_as_message(&mut buffer, 1, 42, 2, &not_decimal).is_err());
+}
+
+#[test]
+fn test_generate_descriptor_for_decimal128() {
+    use arrow::array::{Decimal128Array, StructArray};
+    use prost::Message;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct DecimalValue {
+        #[prost(bytes = "vec", tag = "1")]
+        unscaled: Vec<u8>,
+        #[prost(int32, tag = "2")]
+        scale: i32,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct Row {
+        #[prost(message, optional, tag = "1")]
+        price: Option<DecimalValue>,
+    }
+
+    let price_type = DataType::Decimal128(10, 2);
+    let prices = Decimal128Array::from(vec![Some(12_345), None])
+        .with_precision_and_scale(10, 2)
+        .unwrap();
+    let totals_fields = vec![Field::new("total", price_type.clone(), true)];
+    let totals = StructArray::new(
+        totals_fields.clone().into(),
+        vec![Arc::new(prices.clone())],
+        None,
+    );
+    let schema = Schema::new(vec![
+        Field::new("price", price_type.clone(), true),
+        Field::new("totals", DataType::Struct(totals_fields.into()), true),
+        Field::new("discount", price_type.clone(), true),
+    ]);
+    assert!(schema
+        .fields()
+        .iter()
+        .all(|field| conversion::is_type_supported(field.data_type())));
+
+    let descriptor = conversion::generate_protobuf_descriptor(&schema).unwrap();
+    conversion::validate_protobuf_descriptor(&descriptor).unwrap();
+    assert!(conversion::validate_descriptor_against_schema(&descriptor, &schema).is_ok());
+
+    // Both top-level decimal columns share one nested message named after precision and scale
+    let decimal = conversion::decimal_message_descriptor(10, 2);
+    assert_eq!(decimal.name.as_deref(), Some("Decimal_10_2"));
+    for field in [&descriptor.field[0], &descriptor.field[2]] {
+        assert_eq!(field.r#type, Some(Type::Message as i32));
+        assert_eq!(field.label, Some(Label::Optional as i32));
+        assert_eq!(
+            field.type_name.as_deref(),
+            Some(".ZerobusMessage.Decimal_10_2")
+        );
+    }
+    let nested_decimals: Vec<_> = descriptor
+        .nested_type
+        .iter()
+        .filter(|nested| nested.name == decimal.name)
+        .collect();
+    assert_eq!(nested_decimals, vec![&decimal]);
+    // Struct fields get their own copy inside the struct's message
+    let totals_message = descriptor
+        .nested_type
+        .iter()
+        .find(|nested| nested.name.as_deref() == Some("ZerobusMessage_totals"))
+        .unwrap();
+    assert_eq!(totals_message.nested_type, vec![decimal]);
+
+    // Negative scales are spelled out in the message name
+    assert_eq!(
+        conversion::decimal_message_descriptor(5, -3)
+            .name
+            .as_deref(),
+        Some("Decimal_5_n3")
+    );
+
+    // The generated descriptor encodes the exact decimal
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![Arc::new(prices.clone()), Arc::new(totals), Arc::new(prices)],
+    )
+    .unwrap();
+    let result = conversion::record_batch_to_protobuf_bytes(&batch, &descriptor);
+    assert!(result.failed_rows.is_empty());
+    let row = Row::decode(result.successful_bytes[0].1.as_slice()).unwrap();
+    let price = row.price.expect("decimal message should be present");
+    assert_eq!(price.scale, 2);
+    let unscaled: [u8; 16] = price.unscaled.as_slice().try_into().unwrap();
+    assert_eq!(i128::from_be_bytes(unscaled), 12_345);
+    let row = Row::decode(result.successful_bytes[1].1.as_slice()).unwrap();
+    assert!(row.price.is_none());
+}
+
+fn create_sensitive_batch_and_descriptor() -> (RecordBatch, DescriptorProto) {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("ssn", DataType::Utf8, false),
+    ]);
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int64Array::from(vec![1])),
+            Arc::new(StringArray::from(vec!["123-45-6789"])),
+        ],
+    )
+    .unwrap();
+
+    // Descriptor declares `ssn` as int64, so encoding the string column fails
+    let descriptor = DescriptorProto {
+        name: Some("ZerobusMessage".to_string()),
+        field: vec![
+            FieldDescriptorProto {
+                name: Some("id".to_string()),
+                number: Some(1),
+                r#type: Some(Type::Int64 as i32),
+                ..Default::default()
+            },
+            FieldDescriptorProto {
+                name: Some("ssn".to_string()),
+                number: Some(2),
+                r#type: Some(Type::Int64 as i32),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    (batch, descriptor)
+}
+
+#[test]
+fn test_conversion_error_redacts_sensitive_field() {
+    let (batch, descriptor) = create_sensitive_batch_and_descriptor();
+    let sensitive = vec!["ssn".to_string()];
+    let redaction = conversion::ErrorRedaction {
+        redact_values: true,
+        sensitive_fields: &sensitive,
+    };
+
+    let result =
+        conversion::record_batch_to_protobuf_bytes_with_redaction(&batch, &descriptor, redaction);
+    assert_eq!(result.failed_rows.len(), 1);
+
+    let (row_idx, error) = &result.failed_rows[0];
+    let message = error.to_string();
+    assert_eq!(*row_idx, 0);
+    assert!(message.contains("row=0"), "{}", message);
+    assert!(
+        message.contains(&conversion::mask_field_name("ssn")),
+        "{}",
+        message
+    );
+    assert!(message.contains("<redacted>"), "{}", message);
+    assert!(!message.contains("ssn"), "{}", message);
+    assert!(!message.contains("123-45-6789"), "{}", message);
+}
+
+#[test]
+fn test_conversion_error_without_redaction_keeps_detail() {
+    let (batch, descriptor) = create_sensitive_batch_and_descriptor();
+
+    let result = conversion::record_batch_to_protobuf_bytes(&batch, &descriptor);
+    assert_eq!(result.failed_rows.len(), 1);
+
+    let message = result.failed_rows[0].1.to_string();
+    assert!(message.contains("field='ssn'"), "{}", message);
+    assert!(!message.contains("<redacted>"), "{}", message);
+    // Encoders never include cell values, even without redaction
+    assert!(!message.contains("123-45-6789"), "{}", message);
+}
+
+#[test]
+fn test_mask_field_name_is_stable() {
+    assert_eq!(
+        conversion::mask_field_name("email"),
+        conversion::mask_field_name("email")
+    );
+    assert_ne!(
+        conversion::mask_field_name("email"),
+        conversion::mask_field_name("ssn")
+    );
+    assert!(conversion::mask_field_name("email").starts_with("<sensitive:"));
+}
+
+#[test]
+fn test_estimate_protobuf_size_close_to_actual() {
+    use arrow::array::{
+        ArrayRef, Float32Array, Int32Array, ListBuilder, StringBuilder, StructArray,
+    };
+    use arrow::buffer::NullBuffer;
+
+    let num_rows = 500;
+    let mut tags = ListBuilder::new(StringBuilder::new());
+    for i in 0..num_rows {
+        if i % 7 == 0 {
+            tags.append_null();
+        } else {
+            for j in 0..(i % 4) {
+                tags.values().append_value(format!("tag_{}_{}", i, j));
+            }
+            tags.append(true);
+        }
+    }
+    let address_fields = vec![
+        Field::new("city", DataType::Utf8, true),
+        Field::new("zip", DataType::Int32, true),
+    ];
+    let address = StructArray::new(
+        address_fields.clone().into(),
+        vec![
+            Arc::new(StringArray::from_iter_values(
+                (0..num_rows).map(|i| format!("city_{}", i % 37)),
+            )) as ArrayRef,
+            Arc::new(Int32Array::from_iter_values(
+                (0..num_rows).map(|i| 10_000 + i),
+            )),
+        ],
+        Some(NullBuffer::from_iter((0..num_rows).map(|i| i % 5 != 0))),
+    );
+
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("score", DataType::Float64, true),
+        Field::new("ratio", DataType::Float32, false),
+        Field::new(
+            "tags",
+            DataType::List(Arc::new(Field::new("item", DataType::Utf8, true))),
+            true,
+        ),
+        Field::new("address", DataType::Struct(address_fields.into()), true),
+    ]);
+    let batch = RecordBatch::try_new(
+        Arc::new(schema.clone()),
+        vec![
+            Arc::new(Int64Array::from_iter_values(
+                (0..num_rows as i64).map(|i| i * 1_000_003),
+            )),
+            Arc::new(StringArray::from_iter(
+                (0..num_rows).map(|i| (i % 3 != 0).then(|| "n".repeat((i % 50) as usize))),
+            )),
+            Arc::new(Float64Array::from_iter(
+                (0..num_rows).map(|i| (i % 2 == 0).then_some(i as f64 * 0.5)),
+            )),
+            Arc::new(Float32Array::from_iter_values(
+                (0..num_rows).map(|i| i as f32),
+            )),
+            Arc::new(tags.finish()),
+            Arc::new(address),
+        ],
+    )
+    .unwrap();
+
+    let descriptor = conversion::generate_protobuf_descriptor(&schema).unwrap();
+    let result = conversion::record_batch_to_protobuf_bytes(&batch, &descriptor);
+    assert!(result.failed_rows.is_empty());
+    let actual: usize = result.successful_bytes.iter().map(|(_, b)| b.len()).sum();
+
+    let estimate = conversion::estimate_protobuf_size(&batch, &descriptor);
+    let diff = (estimate as f64 - actual as f64).abs() / actual as f64;
+    assert!(
+        diff <= 0.10,
+        "estimate {} vs actual {} ({:.1}% off)",
+        estimate,
+        actual,
+        diff * 100.0
+    );
+}
+
+#[test]
+fn test_estimate_protobuf_size_empty_batch() {
+    let batch = create_test_batch().slice(0, 0);
+    let descriptor = conversion::generate_protobuf_descriptor(batch.schema().as_ref()).unwrap();
+    assert_eq!(conversion::estimate_protobuf_size(&batch, &descriptor), 0);
+}
+
+#[test]
+fn test_evolve_descriptor() {
+    let field = |name: &str, number: i32, label: Label| FieldDescriptorProto {
+        name: Some(name.to_string()),
+        number: Some(number),
+        label: Some(label as i32),
+        r#type: Some(Type::Int64 as i32),
+        ..Default::default()
+    };
+    let current = DescriptorProto {
+        name: Some("ZerobusMessage".to_string()),
+        field: vec![
+            field("a", 1, Label::Optional),
+            field("b", 2, Label::Optional),
+        ],
+        ..Default::default()
+    };
+
+    // Unchanged schema evolves to itself
+    assert_eq!(
+        conversion::evolve_descriptor(&current, &current).unwrap(),
+        current
+    );
+
+    // New optional field is appended; existing numbers win over the candidate's
+    let candidate = DescriptorProto {
+        field: vec![
+            field("c", 1, Label::Optional),
+            field("b", 2, Label::Optional),
+            field("a", 3, Label::Optional),
+        ],
+        ..current.clone()
+    };
+    let evolved = conversion::evolve_descriptor(&current, &candidate).unwrap();
+    let numbers: Vec<_> = evolved
+        .field
+        .iter()
+        .map(|f| (f.name.clone().unwrap(), f.number.unwrap()))
+        .collect();
+    assert_eq!(
+        numbers,
+        vec![
+            ("a".to_string(), 1),
+            ("b".to_string(), 2),
+            ("c".to_string(), 3)
+        ]
+    );
+
+    // New required fields are not add-only
+    let required = DescriptorProto {
+        field: vec![
+            field("a", 1, Label::Optional),
+            field("d", 2, Label::Required),
+        ],
+        ..current.clone()
+    };
+    assert!(conversion::evolve_descriptor(&current, &required).is_err());
+}
+
+#[test]
+fn test_record_batch_to_protobuf_bytes_with_maps_matches_uncached() {
+    let batch = create_test_batch();
+    let descriptor = conversion::generate_protobuf_descriptor(batch.schema().as_ref()).unwrap();
+    let maps = conversion::DescriptorFieldMaps::new(&descriptor);
+    assert!(maps.matches(&descriptor));
+    assert_eq!(maps.descriptor(), &descriptor);
+
+    let uncached = conversion::record_batch_to_protobuf_bytes(&batch, &descriptor);
+    // The same maps are reused across batches
+    for _ in 0..3 {
+        let cached = conversion::record_batch_to_protobuf_bytes_with_maps(
+            &batch,
+            &maps,
+            &FieldNameTransform::None,
+            FloatPolicy::Passthrough,
+            false,
+            false,
+            false,
+            NullElementPolicy::SkipNullElements,
+            TimeUnit::Microsecond,
+            conversion::ErrorRedaction::default(),
+            None,
+            1,
+            None,
+        );
+        assert_eq!(cached.successful_bytes, uncached.successful_bytes);
+        assert!(cached.failed_rows.is_empty());
+    }
+
+    let mut other = descriptor.clone();
+    other.field.pop();
+    assert!(!maps.matches(&other));
+}
+
+#[test]
+fn test_field_name_transform_matches_snake_case_descriptor() {
+    use arrow::array::{ArrayRef, StructArray};
+    use arrow::datatypes::Fields;
+
+    let batch_with_names = |id: &str, device: &str, os: &str| {
+        let os_field = Arc::new(Field::new(os, DataType::Utf8, true));
+        let device_array = StructArray::from(vec![(
+            os_field.clone(),
+            Arc::new(StringArray::from(vec!["ios", "android"])) as ArrayRef,
+        )]);
+        let schema = Schema::new(vec![
+            Field::new(id, DataType::Int64, false),
+            Field::new(device, DataType::Struct(Fields::from(vec![os_field])), true),
+        ]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int64Array::from(vec![1, 2])),
+                Arc::new(device_array),
+            ],
+        )
+        .unwrap()
+    };
+    let snake = batch_with_names("user_id", "device_info", "os_version");
+    let camel = batch_with_names("userId", "deviceInfo", "osVersion");
+
+    let snake_descriptor =
+        conversion::generate_protobuf_descriptor(snake.schema().as_ref()).unwrap();
+    let transform = FieldNameTransform::ToSnakeCase;
+    let descriptor = conversion::generate_protobuf_descriptor_with_field_names(
+        camel.schema().as_ref(),
+        false,
+        &transform,
+        conversion::MAX_FIELD_NAME_LENGTH,
+    )
+    .unwrap();
+    assert_eq!(descriptor, snake_descriptor);
+    assert!(
+        conversion::validate_descriptor_against_schema_with_field_names(
+            &snake_descriptor,
+            camel.schema().as_ref(),
+            &transform,
+        )
+        .is_ok()
+    );
+
+    // camelCase columns now match the snake_case descriptor fields, nested ones included
+    let maps = conversion::DescriptorFieldMaps::new(&snake_descriptor);
+    let expected = conversion::record_batch_to_protobuf_bytes(&snake, &snake_descriptor);
+    let result = conversion::record_batch_to_protobuf_bytes_with_maps(
+        &camel,
+        &maps,
+        &transform,
+        FloatPolicy::Passthrough,
+        false,
+        false,
+        false,
+        NullElementPolicy::SkipNullElements,
+        TimeUnit::Microsecond,
+        conversion::ErrorRedaction::default(),
+        None,
+        1,
+        None,
+    );
+    assert!(result.failed_rows.is_empty());
+    assert_eq!(result.successful_bytes, expected.successful_bytes);
+
+    // Without the transform every column is skipped
+    let untransformed = conversion::record_batch_to_protobuf_bytes(&camel, &snake_descriptor);
+    assert!(untransformed
+        .successful_bytes
+        .iter()
+        .all(|(_, bytes)| bytes.is_empty()));
+}
+
+#[test]
+fn test_field_name_transform_apply() {
+    let snake = FieldNameTransform::ToSnakeCase;
+    assert_eq!(snake.apply("userId"), "user_id");
+    assert_eq!(snake.apply("UserID"), "user_id");
+    assert_eq!(snake.apply("HTTPServer"), "http_server");
+    assert_eq!(snake.apply("already_snake"), "already_snake");
+
+    let camel = FieldNameTransform::ToCamelCase;
+    assert_eq!(camel.apply("user_id"), "userId");
+    assert_eq!(camel.apply("_private_field"), "_privateField");
+
+    let custom = FieldNameTransform::Custom(Arc::new(|name: &str| format!("src_{}", name)));
+    assert_eq!(custom.apply("id"), "src_id");
+    assert_eq!(FieldNameTransform::None.apply("userId"), "userId");
+
+    // Column names are validated after the transform
+    let schema = Schema::new(vec![Field::new("user-id", DataType::Int64, false)]);
+    assert!(conversion::generate_protobuf_descriptor(&schema).is_err());
+    let dashes_to_underscores =
+        FieldNameTransform::Custom(Arc::new(|name: &str| name.replace('-', "_")));
+    let descriptor = conversion::generate_protobuf_descriptor_with_field_names(
+        &schema,
+        false,
+        &dashes_to_underscores,
+        conversion::MAX_FIELD_NAME_LENGTH,
+    )
+    .unwrap();
+    assert_eq!(descriptor.field[0].name.as_deref(), Some("user_id"));
+}
+
+#[test]
+fn test_descriptor_to_file_descriptor_sets_package_and_syntax() {
+    let address = Field::new(
+        "address",
+        DataType::Struct(vec![Field::new("city", DataType::Utf8, true)].into()),
+        true,
+    );
+    let schema = Schema::new(vec![Field::new("id", DataType::Int64, false), address]);
+    let descriptor = conversion::generate_protobuf_descriptor(&schema).unwrap();
+
+    let file = conversion::descriptor_to_file_descriptor(&descriptor, Some("com.example"));
+    assert_eq!(file.name.as_deref(), Some("ZerobusMessage.proto"));
+    assert_eq!(file.package.as_deref(), Some("com.example"));
+    assert_eq!(file.syntax.as_deref(), Some("proto3"));
+    assert_eq!(file.message_type.len(), 1);
+
+    // Nested type names are re-qualified with the package
+    let nested_field = &file.message_type[0].field[1];
+    assert_eq!(
+        nested_field.type_name.as_deref(),
+        Some(".com.example.ZerobusMessage.ZerobusMessage_address")
+    );
+    assert_eq!(
+        descriptor.field[1].type_name.as_deref(),
+        Some(".ZerobusMessage.ZerobusMessage_address")
+    );
+
+    let unpackaged = conversion::descriptor_to_file_descriptor(&descriptor, None);
+    assert_eq!(unpackaged.package, None);
+    assert_eq!(unpackaged.syntax.as_deref(), Some("proto3"));
+    assert_eq!(unpackaged.message_type[0], descriptor);
+}
+
+#[test]
+fn test_flat_fast_path_matches_row_by_row_encoding() {
+    use arrow::array::{
+        ArrayRef, BinaryArray, BooleanArray, Date32Array, Float32Array, Int32Array,
+        TimestampMillisecondArray,
+    };
+    use arrow::datatypes::TimeUnit;
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, true),
+        Field::new("count", DataType::Int32, true),
+        Field::new("score", DataType::Float64, true),
+        Field::new("ratio", DataType::Float32, true),
+        Field::new("active", DataType::Boolean, true),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("payload", DataType::Binary, true),
+        Field::new("day", DataType::Date32, true),
+        Field::new(
+            "seen_at",
+            DataType::Timestamp(TimeUnit::Millisecond, None),
+            true,
+        ),
+    ]));
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(Int64Array::from(vec![Some(-1), None, Some(i64::MAX)])),
+        Arc::new(Int32Array::from(vec![Some(-5), Some(300), None])),
+        Arc::new(Float64Array::from(vec![Some(1.5), None, Some(-0.0)])),
+        Arc::new(Float32Array::from(vec![None, Some(2.5), Some(3.0)])),
+        Arc::new(BooleanArray::from(vec![Some(true), Some(false), None])),
+        Arc::new(StringArray::from(vec![Some("a"), None, Some("")])),
+        Arc::new(BinaryArray::from(vec![
+            None,
+            Some(b"\x00\x01".as_ref()),
+            Some(b"".as_ref()),
+        ])),
+        Arc::new(Date32Array::from(vec![Some(19000), None, Some(-1)])),
+        Arc::new(TimestampMillisecondArray::from(vec![
+            Some(1_700_000_000_000),
+            None,
+            Some(0),
+        ])),
+    ];
+    let batch = RecordBatch::try_new(schema.clone(), columns).unwrap();
+    let descriptor = conversion::generate_protobuf_descriptor(&schema).unwrap();
+
+    let fast = conversion::record_batch_to_protobuf_bytes(&batch, &descriptor);
+    let generic = conversion::record_batch_to_protobuf_bytes_row_by_row(&batch, &descriptor);
+    assert!(fast.failed_rows.is_empty());
+    assert_eq!(fast.successful_bytes, generic.successful_bytes);
+
+    // Enum-like sint fields stored as strings or ints, and columns missing from the
+    // descriptor, behave the same on both paths
+    let descriptor = DescriptorProto {
+        name: Some("ZerobusMessage".to_string()),
+        field: vec![
+            FieldDescriptorProto {
+                name: Some("count".to_string()),
+                number: Some(1),
+                r#type: Some(Type::Sint32 as i32),
+                ..Default::default()
+            },
+            FieldDescriptorProto {
+                name: Some("name".to_string()),
+                number: Some(2),
+                r#type: Some(Type::Sint64 as i32),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let fast = conversion::record_batch_to_protobuf_bytes(&batch, &descriptor);
+    let generic = conversion::record_batch_to_protobuf_bytes_row_by_row(&batch, &descriptor);
+    assert_eq!(fast.successful_bytes, generic.successful_bytes);
+    assert_eq!(fast.successful_bytes[0].1, vec![0x08, 9, 0x12, 1, b'a']);
+}
+
+#[test]
+fn test_flat_fast_path_falls_back_for_mismatched_types() {
+    // Descriptor says Int64 but the column is a string: the general path reports the row error
+    let batch = create_test_batch();
+    let descriptor = DescriptorProto {
+        name: Some("ZerobusMessage".to_string()),
+        field: vec![FieldDescriptorProto {
+            name: Some("name".to_string()),
+            number: Some(1),
+            r#type: Some(Type::Int64 as i32),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let result = conversion::record_batch_to_protobuf_bytes(&batch, &descriptor);
+    assert!(result.successful_bytes.is_empty());
+    assert_eq!(result.failed_rows.len(), batch.num_rows());
+}
+
+#[test]
+fn test_max_failures_aborts_conversion_early() {
+    // Descriptor says Int64 but the column is a string: every row fails
+    let batch = create_test_batch();
+    let descriptor = DescriptorProto {
+        name: Some("ZerobusMessage".to_string()),
+        field: vec![FieldDescriptorProto {
+            name: Some("name".to_string()),
+            number: Some(1),
+            r#type: Some(Type::Int64 as i32),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let maps = conversion::DescriptorFieldMaps::new(&descriptor);
+
+    let result = conversion::record_batch_to_protobuf_bytes_with_maps(
+        &batch,
+        &maps,
+        &FieldNameTransform::None,
+        FloatPolicy::Passthrough,
+        false,
+        false,
+        false,
+        NullElementPolicy::SkipNullElements,
+        TimeUnit::Microsecond,
+        conversion::ErrorRedaction::default(),
+        Some(0),
+        1,
+        None,
+    );
+    assert_eq!(result.failed_rows.len(), 1);
+    assert_eq!(result.aborted_at_row, Some(1));
+
+    // A threshold the batch never exceeds converts every row
+    let result = conversion::record_batch_to_protobuf_bytes_with_maps(
+        &batch,
+        &maps,
+        &FieldNameTransform::None,
+        FloatPolicy::Passthrough,
+        false,
+        false,
+        false,
+        NullElementPolicy::SkipNullElements,
+        TimeUnit::Microsecond,
+        conversion::ErrorRedaction::default(),
+        Some(batch.num_rows()),
+        1,
+        None,
+    );
+    assert_eq!(result.failed_rows.len(), batch.num_rows());
+    assert_eq!(result.aborted_at_row, None);
+}
+
+/// Encode a varint the way Protobuf does (for the custom encoder tests)
+fn push_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+#[test]
+fn test_custom_encoder_handles_unsupported_type() {
+    use arrow::array::{Int32Array, Int8Array};
+
+    // Int8 maps to an int32 field, but the built-in encoder rejects Int8 arrays
+    let int8_batch = RecordBatch::try_new(
+        Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("level", DataType::Int8, true),
+        ])),
+        vec![
+            Arc::new(Int64Array::from(vec![1, 2, 3])),
+            Arc::new(Int8Array::from(vec![Some(-5), None, Some(7)])),
+        ],
+    )
+    .unwrap();
+    let descriptor =
+        conversion::generate_protobuf_descriptor(int8_batch.schema().as_ref()).unwrap();
+    let maps = conversion::DescriptorFieldMaps::new(&descriptor);
+    let convert = |custom_encoder: Option<&CustomEncoder>| {
+        conversion::record_batch_to_protobuf_bytes_with_maps(
+            &int8_batch,
+            &maps,
+            &FieldNameTransform::None,
+            FloatPolicy::Passthrough,
+            false,
+            false,
+            false,
+            NullElementPolicy::SkipNullElements,
+            TimeUnit::Microsecond,
+            conversion::ErrorRedaction::default(),
+            None,
+            1,
+            custom_encoder,
+        )
+    };
+    assert_eq!(convert(None).failed_rows.len(), 2);
+
+    // The hook only takes Int8 arrays; every other column falls through to the default
+    let hook = CustomEncoder(Arc::new(|field, array, row, buffer| {
+        let values = array.as_any().downcast_ref::<Int8Array>()?;
+        push_varint(buffer, (field.number.unwrap_or(0) as u64) << 3);
+        push_varint(buffer, values.value(row) as i64 as u64);
+        Some(Ok(()))
+    }));
+    let result = convert(Some(&hook));
+    assert!(result.failed_rows.is_empty());
+
+    // Same bytes as the equivalent Int32 column
+    let int32_batch = RecordBatch::try_new(
+        Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("level", DataType::Int32, true),
+        ])),
+        vec![
+            Arc::new(Int64Array::from(vec![1, 2, 3])),
+            Arc::new(Int32Array::from(vec![Some(-5), None, Some(7)])),
+        ],
+    )
+    .unwrap();
+    let expected = conversion::record_batch_to_protobuf_bytes(&int32_batch, &descriptor);
+    assert_eq!(result.successful_bytes, expected.successful_bytes);
+
+    // An error from the hook fails the row
+    let failing = CustomEncoder(Arc::new(|_, array, _, _| {
+        array.as_any().downcast_ref::<Int8Array>()?;
+        Some(Err(ZerobusError::ConversionError(
+            "level out of range".to_string(),
+        )))
+    }));
+    let result = convert(Some(&failing));
+    assert_eq!(result.failed_rows.len(), 2);
+    assert!(result.failed_rows[0]
+        .1
+        .to_string()
+        .contains("level out of range"));
+}
+
+#[test]
+fn test_over_long_column_name_rejected() {
+    let name = "c".repeat(conversion::MAX_FIELD_NAME_LENGTH + 1);
+
+    let schema = Schema::new(vec![Field::new(&name, DataType::Int64, false)]);
+    match conversion::generate_protobuf_descriptor(&schema) {
+        Err(ZerobusError::ConfigurationError(message)) => {
+            assert!(message.contains(&name), "{}", message);
+            assert!(message.contains("256 characters"), "{}", message);
+        }
+        other => panic!("expected ConfigurationError, got {:?}", other),
+    }
+
+    // Nested struct fields are checked too
+    let nested = Schema::new(vec![Field::new(
+        "outer",
+        DataType::Struct(vec![Field::new(&name, DataType::Int64, false)].into()),
+        false,
+    )]);
+    assert!(conversion::generate_protobuf_descriptor(&nested).is_err());
+
+    // The limit itself is accepted, and can be lowered
+    let at_limit = Schema::new(vec![Field::new(
+        "c".repeat(conversion::MAX_FIELD_NAME_LENGTH),
+        DataType::Int64,
+        false,
+    )]);
+    assert!(conversion::generate_protobuf_descriptor(&at_limit).is_ok());
+    assert!(conversion::generate_protobuf_descriptor_with_field_names(
+        &at_limit,
+        false,
+        &FieldNameTransform::None,
+        64,
+    )
+    .is_err());
+}
+
+#[test]
+fn test_parallel_conversion_matches_sequential() {
+    // Every third row is NaN and fails under ErrorOnNonFinite; Passthrough takes the
+    // flat fast path
+    let values: Vec<f64> = (0..100)
+        .map(|i| if i % 3 == 0 { f64::NAN } else { i as f64 })
+        .collect();
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("score", DataType::Float64, false),
+    ]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(Int64Array::from_iter_values(0..100)),
+            Arc::new(Float64Array::from(values)),
+        ],
+    )
+    .unwrap();
+    let descriptor = conversion::generate_protobuf_descriptor(schema.as_ref()).unwrap();
+    let maps = conversion::DescriptorFieldMaps::new(&descriptor);
+    let convert = |float_policy, max_failures, parallelism| {
+        let result = conversion::record_batch_to_protobuf_bytes_with_maps(
+            &batch,
+            &maps,
+            &FieldNameTransform::None,
+            float_policy,
+            false,
+            false,
+            false,
+            NullElementPolicy::SkipNullElements,
+            TimeUnit::Microsecond,
+            conversion::ErrorRedaction::default(),
+            max_failures,
+            parallelism,
+            None,
+        );
+        let failed: Vec<(usize, String)> = result
+            .failed_rows
+            .iter()
+            .map(|(idx, e)| (*idx, e.to_string()))
+            .collect();
+        (result.successful_bytes, failed, result.aborted_at_row)
+    };
+
+    for float_policy in [FloatPolicy::Passthrough, FloatPolicy::ErrorOnNonFinite] {
+        for max_failures in [None, Some(5)] {
+            let sequential = convert(float_policy, max_failures, 1);
+            for parallelism in [2, 4, 7, 200] {
+                assert_eq!(convert(float_policy, max_failures, parallelism), sequential);
+            }
+        }
+    }
+    assert_eq!(
+        convert(FloatPolicy::ErrorOnNonFinite, Some(5), 4).2,
+        Some(16)
+    );
+}
+
+/// Batch of `id` plus Float64/Float32 columns holding `values` (row 0 is finite)
+fn float_batch(values: [Option<f64>; 4]) -> RecordBatch {
+    use arrow::array::Float32Array;
+
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("f64", DataType::Float64, true),
+        Field::new("f32", DataType::Float32, true),
+    ]);
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int64Array::from(vec![1, 2, 3, 4])),
+            Arc::new(Float64Array::from(values.to_vec())),
+            Arc::new(Float32Array::from(
+                values
+                    .iter()
+                    .map(|v| v.map(|v| v as f32))
+                    .collect::<Vec<_>>(),
+            )),
+        ],
+    )
+    .unwrap()
+}
+
+fn convert_with_float_policy(
+    batch: &RecordBatch,
+    policy: FloatPolicy,
+) -> conversion::ProtobufConversionResult {
+    let descriptor = conversion::generate_protobuf_descriptor(&batch.schema()).unwrap();
+    conversion::record_batch_to_protobuf_bytes_with_maps(
+        batch,
+        &conversion::DescriptorFieldMaps::new(&descriptor),
+        &FieldNameTransform::None,
+        policy,
+        false,
+        false,
+        false,
+        NullElementPolicy::SkipNullElements,
+        TimeUnit::Microsecond,
+        conversion::ErrorRedaction::default(),
+        None,
+        1,
+        None,
+    )
+}
+
+const NON_FINITE: [Option<f64>; 4] = [
+    Some(1.5),
+    Some(f64::NAN),
+    Some(f64::INFINITY),
+    Some(f64::NEG_INFINITY),
+];
+
+#[test]
+fn test_float_policy_passthrough_encodes_non_finite_values() {
+    let result = convert_with_float_policy(&float_batch(NON_FINITE), FloatPolicy::Passthrough);
+
+    assert!(result.failed_rows.is_empty());
+    assert_eq!(result.successful_bytes.len(), 4);
+    let (_, nan_row) = &result.successful_bytes[1];
+    let nan_bits = f64::NAN.to_le_bytes();
+    assert!(nan_row.windows(8).any(|w| w == nan_bits));
+    let (_, inf_row) = &result.successful_bytes[2];
+    let inf_bits = f32::INFINITY.to_le_bytes();
+    assert!(inf_row.windows(4).any(|w| w == inf_bits));
+}
+
+#[test]
+fn test_float_policy_error_on_non_finite_fails_rows() {
+    let result = convert_with_float_policy(&float_batch(NON_FINITE), FloatPolicy::ErrorOnNonFinite);
+
+    assert_eq!(result.successful_bytes.len(), 1);
+    assert_eq!(result.successful_bytes[0].0, 0);
+    let failed: Vec<usize> = result.failed_rows.iter().map(|(row, _)| *row).collect();
+    assert_eq!(failed, vec![1, 2, 3]);
+    for ((_, error), value) in result.failed_rows.iter().zip(["NaN", "inf", "-inf"]) {
+        let message = error.to_string();
+        assert!(message.contains("Non-finite float value"), "{}", message);
+        assert!(message.contains("field='f64'"), "{}", message);
+        assert!(
+            message.contains(&format!("value={},", value)),
+            "{}",
+            message
+        );
+    }
+}
+
+#[test]
+fn test_float_policy_coerce_to_null_omits_values() {
+    let result = convert_with_float_policy(&float_batch(NON_FINITE), FloatPolicy::CoerceToNull);
+    let expected = convert_with_float_policy(
+        &float_batch([Some(1.5), None, None, None]),
+        FloatPolicy::Passthrough,
+    );
+
+    assert!(result.failed_rows.is_empty());
+    assert_eq!(result.successful_bytes, expected.successful_bytes);
+}
+
+#[test]
+fn test_float_policy_coerce_to_zero_encodes_zero() {
+    let result = convert_with_float_policy(&float_batch(NON_FINITE), FloatPolicy::CoerceToZero);
+    let expected = convert_with_float_policy(
+        &float_batch([Some(1.5), Some(0.0), Some(0.0), Some(0.0)]),
+        FloatPolicy::Passthrough,
+    );
+
+    assert!(result.failed_rows.is_empty());
+    assert_eq!(result.successful_bytes, expected.successful_bytes);
+}
+
+/// Utf8 and LargeUtf8 columns whose row 1 holds an invalid UTF-8 sequence
+fn invalid_utf8_batch() -> RecordBatch {
+    use arrow::array::{ArrayRef, LargeStringArray};
+    use arrow::buffer::{Buffer, OffsetBuffer};
+
+    let values = Buffer::from(b"ok\xff\xfebad".as_ref());
+    // SAFETY: deliberately invalid UTF-8 to exercise validation; offsets are in bounds
+    let utf8 = unsafe {
+        StringArray::new_unchecked(
+            OffsetBuffer::new(vec![0i32, 2, 7].into()),
+            values.clone(),
+            None,
+        )
+    };
+    let large_utf8 = unsafe {
+        LargeStringArray::new_unchecked(OffsetBuffer::new(vec![0i64, 2, 7].into()), values, None)
+    };
+    let columns: Vec<ArrayRef> = vec![Arc::new(utf8), Arc::new(large_utf8)];
+    RecordBatch::try_new(
+        Arc::new(Schema::new(vec![
+            Field::new("name", DataType::Utf8, true),
+            Field::new("large_name", DataType::LargeUtf8, true),
+        ])),
+        columns,
+    )
+    .unwrap()
+}
+
+fn convert_with_utf8_validation(
+    batch: &RecordBatch,
+    validate_utf8: bool,
+) -> conversion::ProtobufConversionResult {
+    let descriptor = conversion::generate_protobuf_descriptor(&batch.schema()).unwrap();
+    conversion::record_batch_to_protobuf_bytes_with_maps(
+        batch,
+        &conversion::DescriptorFieldMaps::new(&descriptor),
+        &FieldNameTransform::None,
+        FloatPolicy::Passthrough,
+        validate_utf8,
+        false,
+        false,
+        NullElementPolicy::SkipNullElements,
+        TimeUnit::Microsecond,
+        conversion::ErrorRedaction::default(),
+        None,
+        1,
+        None,
+    )
+}
+
+#[test]
+fn test_validate_utf8_fails_rows_with_invalid_strings() {
+    let batch = invalid_utf8_batch();
+
+    let result = convert_with_utf8_validation(&batch, true);
+
+    assert_eq!(result.successful_bytes.len(), 1);
+    assert_eq!(
+        result.successful_bytes[0].1,
+        vec![0x0A, 2, b'o', b'k', 0x12, 2, b'o', b'k']
+    );
+    assert_eq!(result.failed_rows.len(), 1);
+    let (row, error) = &result.failed_rows[0];
+    assert_eq!(*row, 1);
+    let message = error.to_string();
+    assert!(
+        message.contains("Invalid UTF-8 string value"),
+        "{}",
+        message
+    );
+    assert!(message.contains("field='name'"), "{}", message);
+    assert!(message.contains("valid_up_to=0"), "{}", message);
+
+    // The LargeUtf8 column is validated too
+    let large_only = convert_with_utf8_validation(&batch.project(&[1]).unwrap(), true);
+    assert_eq!(large_only.failed_rows.len(), 1);
+    assert!(large_only.failed_rows[0]
+        .1
+        .to_string()
+        .contains("field='large_name'"));
+}
+
+#[test]
+fn test_validate_utf8_disabled_passes_bytes_through() {
+    let result = convert_with_utf8_validation(&invalid_utf8_batch(), false);
+
+    assert!(result.failed_rows.is_empty());
+    let invalid = [0xff, 0xfe, b'b', b'a', b'd'];
+    let (_, row) = &result.successful_bytes[1];
+    assert_eq!(row[..2], [0x0A, 5]);
+    assert_eq!(row[2..7], invalid);
+    assert_eq!(row[9..], invalid);
+}
+
+#[test]
+fn test_run_end_encoded_schema_uses_run_value_type() {
+    let run_end_encoded = |values: DataType| {
+        DataType::RunEndEncoded(
+            Arc::new(Field::new("run_ends", DataType::Int32, false)),
+            Arc::new(Field::new("values", values, true)),
+        )
+    };
+    let schema = Schema::new(vec![
+        Field::new("score", run_end_encoded(DataType::Float64), true),
+        Field::new(
+            "tags",
+            run_end_encoded(DataType::List(Arc::new(Field::new(
+                "item",
+                DataType::Utf8,
+                true,
+            )))),
+            true,
+        ),
+    ]);
+
+    let descriptor = conversion::generate_protobuf_descriptor(&schema).unwrap();
+    assert_eq!(descriptor.field[0].r#type, Some(Type::Double as i32));
+    assert_eq!(descriptor.field[1].r#type, Some(Type::String as i32));
+    assert_eq!(descriptor.field[1].label, Some(Label::Repeated as i32));
+    assert!(conversion::validate_descriptor_against_schema(&descriptor, &schema).is_ok());
+
+    let ddl = conversion::arrow_schema_to_databricks_ddl(&schema, "events").unwrap();
+    assert!(ddl.contains("`score` DOUBLE"), "{}", ddl);
+    assert!(ddl.contains("`tags` ARRAY<STRING>"), "{}", ddl);
+}
+
+#[test]
+fn test_dictionary_schema_uses_value_type() {
+    let dictionary =
+        |values: DataType| DataType::Dictionary(Box::new(DataType::Int32), Box::new(values));
+    let schema = Schema::new(vec![
+        Field::new("country", dictionary(DataType::Utf8), true),
+        Field::new("bucket", dictionary(DataType::Int64), true),
+    ]);
+
+    let descriptor = conversion::generate_protobuf_descriptor(&schema).unwrap();
+    assert_eq!(descriptor.field[0].r#type, Some(Type::String as i32));
+    assert_eq!(descriptor.field[1].r#type, Some(Type::Int64 as i32));
+    assert!(conversion::validate_descriptor_against_schema(&descriptor, &schema).is_ok());
+
+    let ddl = conversion::arrow_schema_to_databricks_ddl(&schema, "events").unwrap();
+    assert!(ddl.contains("`country` STRING"), "{}", ddl);
+    assert!(ddl.contains("`bucket` BIGINT"), "{}", ddl);
+}
+
+#[test]
+fn test_dictionary_columns_encode_like_materialized_values() {
+    use arrow::array::{ArrayRef, DictionaryArray, Int32Array, Int8Array};
+    use arrow::datatypes::{Int32Type, Int8Type};
+
+    // Null keys, a null dictionary value, and repeated keys
+    let countries = DictionaryArray::<Int32Type>::try_new(
+        Int32Array::from(vec![Some(0), Some(1), None, Some(0), Some(2), Some(2)]),
+        Arc::new(StringArray::from(vec![
+            Some("NL"),
+            None,
+            Some("United Kingdom"),
+        ])),
+    )
+    .unwrap();
+    let buckets = DictionaryArray::<Int8Type>::try_new(
+        Int8Array::from(vec![Some(1), None, Some(0), Some(1), Some(1), Some(0)]),
+        Arc::new(Int64Array::from(vec![-7, 300])),
+    )
+    .unwrap();
+    let dictionary_columns: Vec<ArrayRef> = vec![Arc::new(countries), Arc::new(buckets)];
+    let dictionary_batch = RecordBatch::try_new(
+        Arc::new(Schema::new(vec![
+            Field::new("country", dictionary_columns[0].data_type().clone(), true),
+            Field::new("bucket", dictionary_columns[1].data_type().clone(), true),
+        ])),
+        dictionary_columns.clone(),
+    )
+    .unwrap();
+
+    let plain_columns: Vec<ArrayRef> = vec![
+        arrow::compute::cast(&dictionary_columns[0], &DataType::Utf8).unwrap(),
+        arrow::compute::cast(&dictionary_columns[1], &DataType::Int64).unwrap(),
+    ];
+    let plain_schema = Arc::new(Schema::new(vec![
+        Field::new("country", DataType::Utf8, true),
+        Field::new("bucket", DataType::Int64, true),
+    ]));
+    let plain_batch = RecordBatch::try_new(plain_schema.clone(), plain_columns).unwrap();
+    let descriptor = conversion::generate_protobuf_descriptor(&plain_schema).unwrap();
+
+    let expected = conversion::record_batch_to_protobuf_bytes(&plain_batch, &descriptor);
+    let string_only_expected = conversion::record_batch_to_protobuf_bytes(
+        &plain_batch.project(&[0]).unwrap(),
+        &descriptor,
+    );
+    for (batch, expected) in [
+        // Int64 dictionary values go through the general encoder
+        (dictionary_batch.clone(), &expected),
+        // String-only dictionary batch takes the flat fast path
+        (
+            dictionary_batch.project(&[0]).unwrap(),
+            &string_only_expected,
+        ),
+    ] {
+        let fast = conversion::record_batch_to_protobuf_bytes(&batch, &descriptor);
+        let generic = conversion::record_batch_to_protobuf_bytes_row_by_row(&batch, &descriptor);
+        assert!(fast.failed_rows.is_empty());
+        assert_eq!(fast.successful_bytes, expected.successful_bytes);
+        assert_eq!(generic.successful_bytes, expected.successful_bytes);
+    }
+
+    // Sliced dictionary columns resolve keys relative to the slice
+    let sliced =
+        conversion::record_batch_to_protobuf_bytes(&dictionary_batch.slice(3, 3), &descriptor);
+    let sliced_expected =
+        conversion::record_batch_to_protobuf_bytes(&plain_batch.slice(3, 3), &descriptor);
+    assert_eq!(sliced.successful_bytes, sliced_expected.successful_bytes);
+}
+
+#[cfg(feature = "reflect")]
+#[test]
+fn test_record_batch_to_dynamic_messages_reads_fields() {
+    let batch = create_test_batch();
+    let descriptor = create_test_descriptor();
+
+    let messages = conversion::record_batch_to_dynamic_messages(&batch, &descriptor).unwrap();
+
+    assert_eq!(messages.len(), 3);
+    let (row_idx, message) = &messages[1];
+    assert_eq!(*row_idx, 1);
+    assert_eq!(message.get_field_by_name("id").unwrap().as_i64(), Some(2));
+    assert_eq!(
+        message.get_field_by_name("name").unwrap().as_str(),
+        Some("Bob")
+    );
+}
+
+fn convert_with_lenient_bools(
+    batch: &RecordBatch,
+    descriptor: &DescriptorProto,
+    lenient_bool_coercion: bool,
+) -> conversion::ProtobufConversionResult {
+    conversion::record_batch_to_protobuf_bytes_with_maps(
+        batch,
+        &conversion::DescriptorFieldMaps::new(descriptor),
+        &FieldNameTransform::None,
+        FloatPolicy::Passthrough,
+        false,
+        lenient_bool_coercion,
+        false,
+        NullElementPolicy::SkipNullElements,
+        TimeUnit::Microsecond,
+        conversion::ErrorRedaction::default(),
+        None,
+        1,
+        None,
+    )
+}
+
+#[test]
+fn test_lenient_bool_coercion_accepts_int_backed_booleans() {
+    use arrow::array::{BooleanArray, Int32Array, Int8Array};
+
+    let descriptor = DescriptorProto {
+        name: Some("Flags".to_string()),
+        field: vec![FieldDescriptorProto {
+            name: Some("active".to_string()),
+            number: Some(1),
+            label: Some(Label::Optional as i32),
+            r#type: Some(Type::Bool as i32),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let batch_of = |array: arrow::array::ArrayRef| {
+        RecordBatch::try_new(
+            Arc::new(Schema::new(vec![Field::new(
+                "active",
+                array.data_type().clone(),
+                true,
+            )])),
+            vec![array],
+        )
+        .unwrap()
+    };
+    let expected = convert_with_lenient_bools(
+        &batch_of(Arc::new(BooleanArray::from(vec![
+            Some(true),
+            Some(false),
+            Some(true),
+            None,
+        ]))),
+        &descriptor,
+        false,
+    );
+    assert!(expected.failed_rows.is_empty());
+
+    // Nonzero is true, including negative values
+    let int8_batch = batch_of(Arc::new(Int8Array::from(vec![
+        Some(1),
+        Some(0),
+        Some(-3),
+        None,
+    ])));
+    let int32_batch = batch_of(Arc::new(Int32Array::from(vec![
+        Some(7),
+        Some(0),
+        Some(1),
+        None,
+    ])));
+    for batch in [&int8_batch, &int32_batch] {
+        let lenient = convert_with_lenient_bools(batch, &descriptor, true);
+        assert!(lenient.failed_rows.is_empty());
+        assert_eq!(lenient.successful_bytes, expected.successful_bytes);
+    }
+
+    // Strict by default: integer-backed booleans fail every non-null row
+    let strict = convert_with_lenient_bools(&int8_batch, &descriptor, false);
+    let failed: Vec<usize> = strict.failed_rows.iter().map(|(idx, _)| *idx).collect();
+    assert_eq!(failed, vec![0, 1, 2]);
+    assert!(strict.failed_rows[0]
+        .1
+        .to_string()
+        .contains("Expected BooleanArray"));
+}
+
+/// Descriptor with `fields` Int64 fields and `depth` levels of nested messages below it
+fn descriptor_with(fields: usize, depth: usize) -> DescriptorProto {
+    let mut descriptor = DescriptorProto {
+        name: Some(format!("Level{}", depth)),
+        field: (1..=fields as i32)
+            .map(|number| FieldDescriptorProto {
+                name: Some(format!("f{}", number)),
+                number: Some(number),
+                label: Some(Label::Optional as i32),
+                r#type: Some(Type::Int64 as i32),
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    };
+    if depth > 0 {
+        descriptor.nested_type.push(descriptor_with(1, depth - 1));
+    }
+    descriptor
+}
+
+#[test]
+fn test_descriptor_soft_limits_by_mode() {
+    use arrow_zerobus_sdk_wrapper::DescriptorValidationMode;
+    use conversion::DescriptorLimits;
+
+    let strict = DescriptorLimits {
+        soft_max_fields: Some(10),
+        soft_max_nesting_depth: Some(2),
+        ..Default::default()
+    };
+    let warn = DescriptorLimits {
+        mode: DescriptorValidationMode::WarnSoftLimits,
+        ..strict
+    };
+
+    // At the soft limits both modes accept
+    for limits in [&strict, &warn] {
+        assert!(conversion::validate_protobuf_descriptor_with_limits(
+            &descriptor_with(10, 2),
+            limits
+        )
+        .is_ok());
+    }
+
+    // One past a soft limit: strict rejects, warn accepts
+    for descriptor in [descriptor_with(11, 0), descriptor_with(1, 3)] {
+        let err =
+            conversion::validate_protobuf_descriptor_with_limits(&descriptor, &strict).unwrap_err();
+        assert!(err.to_string().contains("soft limit"), "{}", err);
+        assert!(conversion::validate_protobuf_descriptor_with_limits(&descriptor, &warn).is_ok());
+    }
+}
+
+#[test]
+fn test_descriptor_hard_limits_always_error() {
+    use arrow_zerobus_sdk_wrapper::DescriptorValidationMode;
+    use conversion::{DescriptorLimits, ABSOLUTE_MAX_NESTING_DEPTH, MAX_FIELDS_PER_MESSAGE};
+
+    for mode in [
+        DescriptorValidationMode::Strict,
+        DescriptorValidationMode::WarnSoftLimits,
+    ] {
+        // Configured hard limits: at the limit passes, one past fails in either mode
+        let limits = DescriptorLimits {
+            mode,
+            max_fields: 10,
+            max_nesting_depth: 2,
+            ..Default::default()
+        };
+        assert!(conversion::validate_protobuf_descriptor_with_limits(
+            &descriptor_with(10, 2),
+            &limits
+        )
+        .is_ok());
+        for descriptor in [descriptor_with(11, 0), descriptor_with(1, 3)] {
+            let err = conversion::validate_protobuf_descriptor_with_limits(&descriptor, &limits)
+                .unwrap_err();
+            assert!(err.to_string().contains("exceeds maximum"), "{}", err);
+        }
+
+        // Absolute maximums apply even if the configured hard limits are higher
+        let limits = DescriptorLimits {
+            mode,
+            max_fields: usize::MAX,
+            max_nesting_depth: usize::MAX,
+            ..Default::default()
+        };
+        assert!(conversion::validate_protobuf_descriptor_with_limits(
+            &descriptor_with(MAX_FIELDS_PER_MESSAGE, ABSOLUTE_MAX_NESTING_DEPTH),
+            &limits,
+        )
+        .is_ok());
+        for descriptor in [
+            descriptor_with(MAX_FIELDS_PER_MESSAGE + 1, 0),
+            descriptor_with(1, ABSOLUTE_MAX_NESTING_DEPTH + 1),
+        ] {
+            let err = conversion::validate_protobuf_descriptor_with_limits(&descriptor, &limits)
+                .unwrap_err();
+            assert!(err.to_string().contains("exceeds maximum"), "{}", err);
+        }
+    }
+}
+
+#[test]
+fn test_configured_max_nesting_depth_boundary() {
+    use arrow_zerobus_sdk_wrapper::WrapperConfiguration;
+    use conversion::{DescriptorLimits, MAX_NESTING_DEPTH};
+
+    let limits_for = |config: &WrapperConfiguration| DescriptorLimits {
+        max_nesting_depth: config.descriptor_max_nesting_depth,
+        ..Default::default()
+    };
+    let config = WrapperConfiguration::new(
+        "https://workspace.cloud.databricks.com".to_string(),
+        "my_table".to_string(),
+    );
+
+    // The default matches the previous fixed maximum
+    let limits = limits_for(&config);
+    assert!(conversion::validate_protobuf_descriptor_with_limits(
+        &descriptor_with(1, MAX_NESTING_DEPTH),
+        &limits
+    )
+    .is_ok());
+    assert!(conversion::validate_protobuf_descriptor_with_limits(
+        &descriptor_with(1, MAX_NESTING_DEPTH + 1),
+        &limits
+    )
+    .is_err());
+
+    // A raised depth accepts descriptors up to it and rejects one level deeper
+    let limits = limits_for(&config.with_max_nesting_depth(15));
+    assert!(
+        conversion::validate_protobuf_descriptor_with_limits(&descriptor_with(1, 15), &limits)
+            .is_ok()
+    );
+    let err =
+        conversion::validate_protobuf_descriptor_with_limits(&descriptor_with(1, 16), &limits)
+            .unwrap_err();
+    assert!(err.to_string().contains("exceeds maximum"), "{}", err);
+}
+
+/// Two-row array of `data_type` with non-null values
+fn sample_array(data_type: &DataType) -> arrow::array::ArrayRef {
+    use arrow::array::{
+        BooleanArray, Int32Array, LargeListArray, ListArray, RunArray, StructArray,
+    };
+    use arrow::buffer::OffsetBuffer;
+    use arrow::compute::cast;
+
+    match data_type {
+        DataType::Boolean => Arc::new(BooleanArray::from(vec![true, false])),
+        DataType::Utf8 | DataType::LargeUtf8 | DataType::Binary | DataType::LargeBinary => {
+            cast(&StringArray::from(vec!["a", "b"]), data_type).unwrap()
+        }
+        DataType::Struct(fields) => Arc::new(StructArray::new(
+            fields.clone(),
+            fields.iter().map(|f| sample_array(f.data_type())).collect(),
+            None,
+        )),
+        DataType::List(field) => Arc::new(ListArray::new(
+            field.clone(),
+            OffsetBuffer::from_lengths([1, 1]),
+            sample_array(field.data_type()),
+            None,
+        )),
+        DataType::LargeList(field) => Arc::new(LargeListArray::new(
+            field.clone(),
+            OffsetBuffer::from_lengths([1, 1]),
+            sample_array(field.data_type()),
+            None,
+        )),
+        DataType::RunEndEncoded(_, values) => Arc::new(
+            RunArray::try_new(
+                &Int32Array::from(vec![1, 2]),
+                sample_array(values.data_type()).as_ref(),
+            )
+            .unwrap(),
+        ),
+        DataType::Dictionary(_, values) => cast(&sample_array(values), data_type).unwrap(),
+        _ => cast(&Int64Array::from(vec![1, 0]), data_type).unwrap(),
+    }
+}
+
+/// Whether a column of `data_type` converts without row failures using a generated descriptor
+fn converts_with_generated_descriptor(data_type: &DataType) -> bool {
+    let schema = Schema::new(vec![Field::new("value", data_type.clone(), true)]);
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![sample_array(data_type)]).unwrap();
+    let Ok(descriptor) = conversion::generate_protobuf_descriptor(&batch.schema()) else {
+        return false;
+    };
+    let result = conversion::record_batch_to_protobuf_bytes(&batch, &descriptor);
+    result.failed_rows.is_empty() && result.successful_bytes.len() == 2
+}
+
+#[test]
+fn test_supported_arrow_types_convert() {
+    use arrow::datatypes::TimeUnit;
+
+    let mut scalars = conversion::supported_arrow_types();
+    scalars.push(DataType::Timestamp(
+        TimeUnit::Microsecond,
+        Some("UTC".into()),
+    ));
+
+    for scalar in scalars {
+        let item = Arc::new(Field::new("item", scalar.clone(), true));
+        let row = DataType::Struct(vec![Field::new("inner", scalar.clone(), true)].into());
+        let mut types = vec![
+            scalar.clone(),
+            DataType::List(item),
+            row.clone(),
+            DataType::List(Arc::new(Field::new("item", row, true))),
+            DataType::RunEndEncoded(
+                Arc::new(Field::new("run_ends", DataType::Int32, false)),
+                Arc::new(Field::new("values", scalar.clone(), true)),
+            ),
+        ];
+        // Arrow cannot build boolean dictionaries
+        if scalar != DataType::Boolean {
+            types.push(DataType::Dictionary(
+                Box::new(DataType::Int32),
+                Box::new(scalar),
+            ));
+        }
+
+        for data_type in types {
+            assert!(conversion::is_type_supported(&data_type), "{:?}", data_type);
+            assert!(
+                converts_with_generated_descriptor(&data_type),
+                "{:?}",
+                data_type
+            );
+        }
+    }
+}
+
+#[test]
+fn test_unsupported_arrow_types() {
+    let item = |data_type: DataType| Arc::new(Field::new("item", data_type, true));
+    for data_type in [
+        DataType::Int8,
+        DataType::Int16,
+        DataType::UInt32,
+        DataType::UInt64,
+        DataType::Float16,
+        DataType::Decimal256(40, 2),
+        DataType::List(item(DataType::Decimal128(10, 2))),
+        DataType::LargeBinary,
+        DataType::LargeList(item(DataType::Int64)),
+        DataType::List(item(DataType::List(item(DataType::Int64)))),
+        DataType::List(item(DataType::Dictionary(
+            Box::new(DataType::Int32),
+            Box::new(DataType::Utf8),
+        ))),
+        DataType::Struct(vec![Field::new("inner", DataType::Int8, true)].into()),
+        DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::UInt8)),
+    ] {
+        assert!(
+            !conversion::is_type_supported(&data_type),
+            "{:?}",
+            data_type
+        );
+        assert!(
+            !converts_with_generated_descriptor(&data_type),
+            "{:?}",
+            data_type
+        );
+    }
+}
+
+#[test]
+fn test_duplicate_field_names_rejected() {
+    let expect_duplicate = |result: Result<(), ZerobusError>, name: &str| match result {
+        Err(ZerobusError::ConfigurationError(message)) => {
+            assert!(message.contains(&format!("'{}'", name)), "{}", message);
+            assert!(message.contains("more than once"), "{}", message);
+        }
+        other => panic!("expected ConfigurationError, got {:?}", other),
+    };
+
+    let top_level = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("value", DataType::Utf8, true),
+        Field::new("id", DataType::Int64, false),
+    ]);
+    expect_duplicate(
+        conversion::generate_protobuf_descriptor(&top_level).map(|_| ()),
+        "id",
+    );
+    expect_duplicate(
+        conversion::validate_unique_field_names(&top_level, &FieldNameTransform::None),
+        "id",
+    );
+
+    // Duplicates inside a struct are found too
+    let nested = Schema::new(vec![Field::new(
+        "meta",
+        DataType::Struct(
+            vec![
+                Field::new("key", DataType::Utf8, true),
+                Field::new("key", DataType::Utf8, true),
+            ]
+            .into(),
+        ),
+        true,
+    )]);
+    expect_duplicate(
+        conversion::generate_protobuf_descriptor(&nested).map(|_| ()),
+        "key",
+    );
+    expect_duplicate(
+        conversion::validate_unique_field_names(&nested, &FieldNameTransform::None),
+        "key",
+    );
+
+    // Distinct Arrow names that rename to the same column collide
+    let renamed = Schema::new(vec![
+        Field::new("userId", DataType::Int64, false),
+        Field::new("user_id", DataType::Int64, false),
+    ]);
+    assert!(conversion::validate_unique_field_names(&renamed, &FieldNameTransform::None).is_ok());
+    expect_duplicate(
+        conversion::validate_unique_field_names(&renamed, &FieldNameTransform::ToSnakeCase),
+        "user_id",
+    );
+    expect_duplicate(
+        conversion::generate_protobuf_descriptor_with_field_names(
+            &renamed,
+            false,
+            &FieldNameTransform::ToSnakeCase,
+            conversion::MAX_FIELD_NAME_LENGTH,
+        )
+        .map(|_| ()),
+        "user_id",
+    );
+}
+
+fn convert_with_emit_defaults(
+    batch: &RecordBatch,
+    emit_defaults: bool,
+) -> conversion::ProtobufConversionResult {
+    let descriptor = conversion::generate_protobuf_descriptor(batch.schema().as_ref()).unwrap();
+    conversion::record_batch_to_protobuf_bytes_with_maps(
+        batch,
+        &conversion::DescriptorFieldMaps::new(&descriptor),
+        &FieldNameTransform::None,
+        FloatPolicy::Passthrough,
+        false,
+        false,
+        emit_defaults,
+        NullElementPolicy::SkipNullElements,
+        TimeUnit::Microsecond,
+        conversion::ErrorRedaction::default(),
+        None,
+        1,
+        None,
+    )
+}
+
+#[test]
+fn test_emit_defaults_writes_zero_for_null_fields() {
+    use arrow::array::BooleanArray;
+
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, true),
+        Field::new("name", DataType::Utf8, true),
+        Field::new("active", DataType::Boolean, true),
+    ]);
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int64Array::from(vec![None, Some(5)])),
+            Arc::new(StringArray::from(vec![None, Some("a")])),
+            Arc::new(BooleanArray::from(vec![None, Some(true)])),
+        ],
+    )
+    .unwrap();
+
+    // Off: null fields are omitted
+    let omitted = convert_with_emit_defaults(&batch, false);
+    assert!(omitted.failed_rows.is_empty());
+    assert!(omitted.successful_bytes[0].1.is_empty());
+
+    // On: each null field is written with its default (0, empty string, false)
+    let emitted = convert_with_emit_defaults(&batch, true);
+    assert!(emitted.failed_rows.is_empty());
+    assert_eq!(
+        emitted.successful_bytes[0].1,
+        vec![0x08, 0, 0x12, 0, 0x18, 0]
+    );
+    // Non-null rows are unchanged
+    assert_eq!(emitted.successful_bytes[1], omitted.successful_bytes[1]);
+
+    // The general encoder (forced by a pass-through custom encoder) agrees with the fast path
+    let descriptor = conversion::generate_protobuf_descriptor(batch.schema().as_ref()).unwrap();
+    let pass_through = CustomEncoder(Arc::new(|_, _, _, _| None));
+    let general = conversion::record_batch_to_protobuf_bytes_with_maps(
+        &batch,
+        &conversion::DescriptorFieldMaps::new(&descriptor),
+        &FieldNameTransform::None,
+        FloatPolicy::Passthrough,
+        false,
+        false,
+        true,
+        NullElementPolicy::SkipNullElements,
+        TimeUnit::Microsecond,
+        conversion::ErrorRedaction::default(),
+        None,
+        1,
+        Some(&pass_through),
+    );
+    assert_eq!(general.successful_bytes, emitted.successful_bytes);
+}
+
+#[test]
+fn test_emit_defaults_skips_nested_messages() {
+    use arrow::array::{Int32Array, StructArray};
+
+    let count = Arc::new(Field::new("count", DataType::Int32, true));
+    let meta = StructArray::new(
+        vec![count].into(),
+        vec![Arc::new(Int32Array::from(vec![None, Some(1)]))],
+        Some(vec![true, false].into()),
+    );
+    let schema = Schema::new(vec![Field::new(
+        "meta",
+        DataType::Struct(meta.fields().clone()),
+        true,
+    )]);
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(meta)]).unwrap();
+
+    let emitted = convert_with_emit_defaults(&batch, true);
+    assert!(emitted.failed_rows.is_empty());
+    // A null scalar inside a present struct gets its default...
+    assert_eq!(emitted.successful_bytes[0].1, vec![0x0a, 2, 0x08, 0]);
+    // ...but a null struct is still omitted
+    assert!(emitted.successful_bytes[1].1.is_empty());
+
+    let omitted = convert_with_emit_defaults(&batch, false);
+    assert_eq!(omitted.successful_bytes[0].1, vec![0x0a, 0]);
+}
+
+fn convert_with_null_elements(
+    batch: &RecordBatch,
+    policy: NullElementPolicy,
+) -> conversion::ProtobufConversionResult {
+    let descriptor = conversion::generate_protobuf_descriptor(batch.schema().as_ref()).unwrap();
+    conversion::record_batch_to_protobuf_bytes_with_maps(
+        batch,
+        &conversion::DescriptorFieldMaps::new(&descriptor),
+        &FieldNameTransform::None,
+        FloatPolicy::Passthrough,
+        false,
+        false,
+        false,
+        policy,
+        TimeUnit::Microsecond,
+        conversion::ErrorRedaction::default(),
+        None,
+        1,
+        None,
+    )
+}
+
+#[test]
+fn test_null_element_policy_for_repeated_bool() {
+    use arrow::array::{Array, BooleanBuilder, ListBuilder};
+
+    let mut flags = ListBuilder::new(BooleanBuilder::new());
+    flags.append_value([Some(true), None, Some(false)]);
+    flags.append_value([None]);
+    let flags = flags.finish();
+    let schema = Schema::new(vec![Field::new("flags", flags.data_type().clone(), true)]);
+    let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(flags)]).unwrap();
+
+    // Default: null elements are dropped, shortening the list
+    let skipped = convert_with_null_elements(&batch, NullElementPolicy::default());
+    assert!(skipped.failed_rows.is_empty());
+    assert_eq!(skipped.successful_bytes[0].1, vec![0x08, 1, 0x08, 0]);
+    assert!(skipped.successful_bytes[1].1.is_empty());
+
+    // Defaults keep the list length, with `false` in place of each null
+    let emitted = convert_with_null_elements(&batch, NullElementPolicy::EmitDefaultForNull);
+    assert!(emitted.failed_rows.is_empty());
+    assert_eq!(
+        emitted.successful_bytes[0].1,
+        vec![0x08, 1, 0x08, 0, 0x08, 0]
+    );
+    assert_eq!(emitted.successful_bytes[1].1, vec![0x08, 0]);
+
+    // Errors fail every row with a null element, naming the element's list position
+    let failed = convert_with_null_elements(&batch, NullElementPolicy::ErrorOnNullElement);
+    assert!(failed.successful_bytes.is_empty());
+    assert_eq!(failed.failed_rows.len(), 2);
+    assert!(
+        failed.failed_rows[0].1.to_string().contains("element=1"),
+        "{}",
+        failed.failed_rows[0].1
+    );
+    assert!(
+        failed.failed_rows[1].1.to_string().contains("element=0"),
+        "{}",
+        failed.failed_rows[1].1
+    );
+}
+
+#[test]
+fn test_timestamp_target_unit_for_each_source_unit() {
+    use arrow::array::{
+        TimestampMicrosecondArray, TimestampMillisecondArray, TimestampNanosecondArray,
+        TimestampSecondArray,
+    };
+
+    let units = [
+        TimeUnit::Second,
+        TimeUnit::Millisecond,
+        TimeUnit::Microsecond,
+        TimeUnit::Nanosecond,
+    ];
+    // 2023-11-14T22:13:20.123456789Z at each column's precision
+    let sources = [
+        1_700_000_000,
+        1_700_000_000_123,
+        1_700_000_000_123_456,
+        1_700_000_000_123_456_789,
+    ];
+    let schema = Schema::new(
+        units
+            .iter()
+            .map(|unit| {
+                Field::new(
+                    format!("{:?}", unit).to_lowercase(),
+                    DataType::Timestamp(*unit, None),
+                    true,
+                )
+            })
+            .collect::<Vec<_>>(),
+    );
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(TimestampSecondArray::from(vec![sources[0]])),
+            Arc::new(TimestampMillisecondArray::from(vec![sources[1]])),
+            Arc::new(TimestampMicrosecondArray::from(vec![sources[2]])),
+            Arc::new(TimestampNanosecondArray::from(vec![sources[3]])),
+        ],
+    )
+    .unwrap();
+
+    // Expected value per target unit (rows) and source column (columns)
+    let expected: [(TimeUnit, [i64; 4]); 4] = [
+        (
+            TimeUnit::Second,
+            [1_700_000_000, 1_700_000_000, 1_700_000_000, 1_700_000_000],
+        ),
+        (
+            TimeUnit::Millisecond,
+            [
+                1_700_000_000_000,
+                1_700_000_000_123,
+                1_700_000_000_123,
+                1_700_000_000_123,
+            ],
+        ),
+        (
+            TimeUnit::Microsecond,
+            [
+                1_700_000_000_000_000,
+                1_700_000_000_123_000,
+                1_700_000_000_123_456,
+                1_700_000_000_123_456,
+            ],
+        ),
+        (
+            TimeUnit::Nanosecond,
+            [
+                1_700_000_000_000_000_000,
+                1_700_000_000_123_000_000,
+                1_700_000_000_123_456_000,
+                1_700_000_000_123_456_789,
+            ],
+        ),
+    ];
+
+    let descriptor = conversion::generate_protobuf_descriptor(batch.schema().as_ref()).unwrap();
+    let maps = conversion::DescriptorFieldMaps::new(&descriptor);
+    let pass_through = CustomEncoder(Arc::new(|_, _, _, _| None));
+    for (target, values) in expected {
+        let mut expected_bytes = Vec::new();
+        for (field_number, value) in (1..).zip(values) {
+            prost::encoding::encode_key(
+                field_number,
+                prost::encoding::WireType::Varint,
+                &mut expected_bytes,
+            );
+            prost::encoding::encode_varint(value as u64, &mut expected_bytes);
+        }
+        for ((source, source_value), value) in units.iter().zip(sources).zip(values) {
+            assert_eq!(
+                conversion::convert_timestamp_unit(source_value, *source, target),
+                value,
+                "{:?} -> {:?}",
+                source,
+                target
+            );
+        }
+
+        // Flat fast path and general encoder (forced by a pass-through custom encoder)
+        for custom_encoder in [None, Some(&pass_through)] {
+            let result = conversion::record_batch_to_protobuf_bytes_with_maps(
+                &batch,
+                &maps,
+                &FieldNameTransform::None,
+                FloatPolicy::Passthrough,
+                false,
+                false,
+                false,
+                NullElementPolicy::SkipNullElements,
+                target,
+                conversion::ErrorRedaction::default(),
+                None,
+                1,
+                custom_encoder,
+            );
+            assert!(result.failed_rows.is_empty());
+            assert_eq!(
+                result.successful_bytes[0].1, expected_bytes,
+                "target {:?}",
+                target
+            );
+        }
+    }
+}
+
+/// Batch with a nullable `nested` struct column:
+/// row 0 = null struct, row 1 = present struct with all-null children, row 2 = populated
+fn create_nullable_struct_batch() -> RecordBatch {
+    let nested_fields = arrow::datatypes::Fields::from(vec![
+        Field::new("nested_id", DataType::Int64, true),
+        Field::new("nested_name", DataType::Utf8, true),
+    ]);
+    let struct_array = arrow::array::StructArray::try_new(
+        nested_fields.clone(),
+        vec![
+            Arc::new(Int64Array::from(vec![None, None, Some(100)])),
+            Arc::new(StringArray::from(vec![None, None, Some("x")])),
+        ],
+        Some(arrow::buffer::NullBuffer::from(vec![false, true, true])),
+    )
+    .unwrap();
+
+    let parent_schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("nested", DataType::Struct(nested_fields), true),
+    ]);
+    RecordBatch::try_new(
+        Arc::new(parent_schema),
+        vec![
+            Arc::new(Int64Array::from(vec![1, 2, 3])),
+            Arc::new(struct_array),
+        ],
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_null_struct_omits_field() {
+    let batch = create_nullable_struct_batch();
+    let descriptor = conversion::generate_protobuf_descriptor(&batch.schema()).unwrap();
+    let result = conversion::record_batch_to_protobuf_bytes(&batch, &descriptor);
+    assert!(result.failed_rows.is_empty());
+
+    // Null struct: only `id` (field 1) is written, the nested field is absent
+    assert_eq!(result.successful_bytes[0], (0, vec![0x08, 1]));
+}
+
+#[test]
+fn test_present_struct_with_null_children_encodes_empty_message() {
+    let batch = create_nullable_struct_batch();
+    let descriptor = conversion::generate_protobuf_descriptor(&batch.schema()).unwrap();
+    let result = conversion::record_batch_to_protobuf_bytes(&batch, &descriptor);
+    assert!(result.failed_rows.is_empty());
+
+    // Present struct whose children are all null: field 2 is written as a zero-length message
+    assert_eq!(result.successful_bytes[1], (1, vec![0x08, 2, 0x12, 0x00]));
+
+    // Populated struct for comparison: nested_id = 100, nested_name = "x"
+    assert_eq!(
+        result.successful_bytes[2],
+        (2, vec![0x08, 3, 0x12, 5, 0x08, 100, 0x12, 1, b'x'])
+    );
+}
@@ -0,0 +1,114 @@
+//! Integration tests for aborting conversion of hopeless batches
+//!
+//! Uses writer disabled mode so batches are converted without real Zerobus streams.
+
+use arrow::array::{Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use arrow_zerobus_sdk_wrapper::{WrapperConfiguration, ZerobusError, ZerobusWrapper};
+use prost_types::field_descriptor_proto::Type;
+use prost_types::{DescriptorProto, FieldDescriptorProto};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+const NUM_ROWS: usize = 10;
+
+async fn create_wrapper(temp_dir: &TempDir, max_failures: Option<usize>) -> ZerobusWrapper {
+    let mut config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true);
+    if let Some(max_failures) = max_failures {
+        config = config.with_max_failures_before_abort(max_failures);
+    }
+    ZerobusWrapper::new(config).await.unwrap()
+}
+
+fn create_batch() -> RecordBatch {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, false),
+    ]);
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int64Array::from_iter_values(0..NUM_ROWS as i64)),
+            Arc::new(StringArray::from_iter_values(
+                (0..NUM_ROWS).map(|i| format!("name_{}", i)),
+            )),
+        ],
+    )
+    .unwrap()
+}
+
+/// Descriptor declaring `name` as Int64, so every row of the batch fails conversion
+fn wrong_type_descriptor() -> DescriptorProto {
+    let field = |name: &str, number: i32| FieldDescriptorProto {
+        name: Some(name.to_string()),
+        number: Some(number),
+        r#type: Some(Type::Int64 as i32),
+        ..Default::default()
+    };
+    DescriptorProto {
+        name: Some("ZerobusMessage".to_string()),
+        field: vec![field("id", 1), field("name", 2)],
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_all_failing_batch_aborts_after_threshold() {
+    let temp_dir = TempDir::new().unwrap();
+    let wrapper = create_wrapper(&temp_dir, Some(2)).await;
+
+    let result = wrapper
+        .send_batch_with_descriptor(create_batch(), Some(wrong_type_descriptor()))
+        .await
+        .unwrap();
+
+    assert!(!result.success);
+    assert_eq!(result.total_rows, NUM_ROWS);
+    assert_eq!(result.successful_count, 0);
+    assert_eq!(result.failed_count, NUM_ROWS);
+
+    // Three rows fail conversion (one more than tolerated), the rest are never converted
+    let failed_rows = result.failed_rows.unwrap();
+    let indices: Vec<usize> = failed_rows.iter().map(|(idx, _)| *idx).collect();
+    assert_eq!(indices, (0..NUM_ROWS).collect::<Vec<_>>());
+    for (idx, error) in &failed_rows {
+        let ZerobusError::ConversionError(msg) = error else {
+            panic!("expected ConversionError, got {:?}", error);
+        };
+        if *idx < 3 {
+            assert!(msg.contains("Field encoding failed"), "{}", msg);
+        } else {
+            assert!(msg.contains("Conversion aborted"), "{}", msg);
+            assert!(msg.contains("more than 2 rows"), "{}", msg);
+        }
+    }
+
+    wrapper.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_all_failing_batch_converts_every_row_without_threshold() {
+    let temp_dir = TempDir::new().unwrap();
+    let wrapper = create_wrapper(&temp_dir, None).await;
+
+    let result = wrapper
+        .send_batch_with_descriptor(create_batch(), Some(wrong_type_descriptor()))
+        .await
+        .unwrap();
+
+    assert_eq!(result.failed_count, NUM_ROWS);
+    assert!(result
+        .failed_rows
+        .unwrap()
+        .iter()
+        .all(|(_, e)| e.to_string().contains("Field encoding failed")));
+
+    wrapper.shutdown().await.unwrap();
+}
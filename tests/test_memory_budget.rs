@@ -0,0 +1,123 @@
+//! Integration tests for the per-wrapper memory budget
+//!
+//! Uses writer disabled mode so sends complete without real Zerobus streams.
+
+use arrow::array::{Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use arrow_zerobus_sdk_wrapper::{WrapperConfiguration, ZerobusWrapper};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn create_test_batch() -> RecordBatch {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, false),
+    ]);
+    let ids: Vec<i64> = (0..1000).collect();
+    let names: Vec<String> = ids.iter().map(|i| format!("name_{}", i)).collect();
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int64Array::from(ids)),
+            Arc::new(StringArray::from(names)),
+        ],
+    )
+    .unwrap()
+}
+
+fn create_config(debug_output_dir: std::path::PathBuf, budget: usize) -> WrapperConfiguration {
+    WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(debug_output_dir)
+    .with_zerobus_writer_disabled(true)
+    .with_memory_budget(budget)
+}
+
+#[tokio::test]
+async fn test_memory_budget_bounds_concurrent_sends() {
+    let temp_dir = TempDir::new().unwrap();
+    let batch_size = create_test_batch().get_array_memory_size();
+    // Room for two batches at a time, but not three
+    let budget = batch_size * 2 + batch_size / 2;
+
+    let wrapper = Arc::new(
+        ZerobusWrapper::new(create_config(temp_dir.path().to_path_buf(), budget))
+            .await
+            .unwrap(),
+    );
+    assert_eq!(wrapper.memory_in_use(), Some(0));
+
+    // Sample the reserved bytes while the sends are running
+    let done = Arc::new(AtomicBool::new(false));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let sampler = {
+        let wrapper = Arc::clone(&wrapper);
+        let done = Arc::clone(&done);
+        let peak = Arc::clone(&peak);
+        tokio::spawn(async move {
+            while !done.load(Ordering::SeqCst) {
+                let in_use = wrapper.memory_in_use().unwrap();
+                assert!(in_use <= budget, "{} bytes in use exceeds budget", in_use);
+                peak.fetch_max(in_use, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+            }
+        })
+    };
+
+    let handles: Vec<_> = (0..16)
+        .map(|_| {
+            let wrapper = Arc::clone(&wrapper);
+            tokio::spawn(async move { wrapper.send_batch(create_test_batch()).await })
+        })
+        .collect();
+    for handle in handles {
+        let result = handle.await.unwrap().unwrap();
+        assert!(result.success);
+    }
+
+    done.store(true, Ordering::SeqCst);
+    sampler.await.unwrap();
+
+    assert!(peak.load(Ordering::SeqCst) <= budget);
+    // Every reservation is released once its send completes
+    assert_eq!(wrapper.memory_in_use(), Some(0));
+
+    wrapper.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_memory_budget_smaller_than_batch_still_sends() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let wrapper = ZerobusWrapper::new(create_config(temp_dir.path().to_path_buf(), 1024))
+        .await
+        .unwrap();
+
+    // An oversized batch reserves the whole budget instead of waiting forever
+    let result = wrapper.send_batch(create_test_batch()).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.successful_count, 1000);
+    assert_eq!(wrapper.memory_in_use(), Some(0));
+
+    wrapper.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_memory_in_use_without_budget() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true);
+
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+    assert_eq!(wrapper.memory_in_use(), None);
+}
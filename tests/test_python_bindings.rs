@@ -56,6 +56,11 @@ mod python_tests {
             total_rows: 0,
             successful_count: 0,
             failed_count: 0,
+            row_sizes: None,
+            failed_row_bytes: None,
+            ack_ids: None,
+            mirror_result: None,
+            failures_truncated: false,
         };
 
         let py_result = PyTransmissionResult { inner: result };
@@ -238,3 +238,1917 @@ async fn test_user_journey_concurrent_access() {
         let _flush2 = wrapper_clone.flush().await;
     }
 }
+
+/// Accessors expose the effective connection info without reaching into the config
+#[tokio::test]
+async fn test_wrapper_accessors_return_normalized_values() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "  https://test.cloud.databricks.com \n".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true);
+
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+    assert_eq!(wrapper.endpoint(), "https://test.cloud.databricks.com");
+    assert_eq!(wrapper.table_name(), "test_table");
+    assert!(wrapper.writer_disabled());
+
+    // Clones share the same connection info
+    let clone = wrapper.clone();
+    assert_eq!(clone.endpoint(), wrapper.endpoint());
+}
+
+/// A flush before any send finds no stream and says so instead of silently succeeding
+#[tokio::test]
+async fn test_flush_on_fresh_wrapper_reports_no_stream() {
+    use arrow_zerobus_sdk_wrapper::FlushOutcome;
+
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_credentials("client_id".to_string(), "client_secret".to_string())
+    .with_unity_catalog("https://unity-catalog-url".to_string());
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    let outcome = wrapper.flush_with_outcome().await.unwrap();
+    assert_eq!(
+        outcome,
+        FlushOutcome {
+            stream_flushed: false,
+            debug_flushed: false,
+        }
+    );
+
+    // Writer disabled mode never has a stream, but debug files are flushed
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true);
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    let outcome = wrapper.flush_with_outcome().await.unwrap();
+    assert!(!outcome.stream_flushed);
+    assert!(outcome.debug_flushed);
+}
+
+/// A prepared batch sends with the same outcome as sending the batch directly
+#[tokio::test]
+async fn test_send_prepared_matches_send_batch() {
+    use arrow_zerobus_sdk_wrapper::FloatPolicy;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_float_policy(FloatPolicy::ErrorOnNonFinite)
+    .with_zerobus_writer_disabled(true);
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    // Row 1 fails conversion, so the prepared batch carries a failed row
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("score", DataType::Float64, true),
+    ]);
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int64Array::from(vec![1, 2, 3])),
+            Arc::new(Float64Array::from(vec![Some(1.5), Some(f64::NAN), None])),
+        ],
+    )
+    .unwrap();
+
+    let prepared = wrapper.prepare_batch(batch.clone(), None).await.unwrap();
+    assert_eq!(prepared.total_rows(), 3);
+    assert_eq!(prepared.successful_bytes().len(), 2);
+    assert_eq!(prepared.failed_rows().len(), 1);
+    assert_eq!(prepared.failed_rows()[0].0, 1);
+
+    let sent = wrapper.send_batch(batch).await.unwrap();
+    let from_prepared = wrapper.send_prepared(prepared).await.unwrap();
+
+    assert_eq!(from_prepared.success, sent.success);
+    assert_eq!(from_prepared.total_rows, sent.total_rows);
+    assert_eq!(from_prepared.successful_count, sent.successful_count);
+    assert_eq!(from_prepared.failed_count, sent.failed_count);
+    assert_eq!(from_prepared.successful_rows, sent.successful_rows);
+    assert_eq!(from_prepared.batch_size_bytes, sent.batch_size_bytes);
+    let failed_indices = |result: &TransmissionResult| {
+        result
+            .failed_rows
+            .as_ref()
+            .map(|rows| rows.iter().map(|(idx, _)| *idx).collect::<Vec<_>>())
+    };
+    assert_eq!(failed_indices(&from_prepared), Some(vec![1]));
+    assert_eq!(failed_indices(&from_prepared), failed_indices(&sent));
+}
+
+/// A submitted batch delivers every row's acknowledgment after the submit call returns
+#[tokio::test]
+async fn test_submit_batch_delivers_acks() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true);
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    let mut acks = wrapper
+        .submit_batch(create_test_record_batch(), None)
+        .await
+        .unwrap();
+
+    let mut acknowledged = Vec::new();
+    while let Some(row) = acks.next_ack().await {
+        assert!(row.outcome.is_ok(), "{:?}", row.outcome);
+        acknowledged.push(row.row_idx);
+    }
+    acknowledged.sort_unstable();
+    assert_eq!(acknowledged, vec![0, 1, 2, 3, 4]);
+
+    let result = acks.wait().await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.successful_count, 5);
+    assert_eq!(result.successful_rows, Some(vec![0, 1, 2, 3, 4]));
+}
+
+/// Without acknowledgments (writer disabled) tokens are not remembered, so a batch
+/// resent with the same token is sent again
+#[tokio::test]
+async fn test_send_batch_with_token_unacknowledged_not_remembered() {
+    use arrow_zerobus_sdk_wrapper::TokenSendOutcome;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true);
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    for sender in [wrapper.clone(), wrapper] {
+        let outcome = sender
+            .send_batch_with_token(create_test_record_batch(), "batch-1")
+            .await
+            .unwrap();
+        match outcome {
+            TokenSendOutcome::Sent(result) => assert_eq!(result.successful_count, 5),
+            TokenSendOutcome::Duplicate => panic!("unacknowledged token must not be remembered"),
+        }
+    }
+}
+
+/// A batch that drifted from the expected schema fails with the differences
+#[tokio::test]
+async fn test_expected_schema_reports_drift() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true);
+
+    // The test batch matches its own schema
+    let wrapper = ZerobusWrapper::new(
+        config
+            .clone()
+            .with_expected_schema(create_test_record_batch().schema()),
+    )
+    .await
+    .unwrap();
+    assert!(
+        wrapper
+            .send_batch(create_test_record_batch())
+            .await
+            .unwrap()
+            .success
+    );
+
+    // Producer added `name`, dropped `email` and widened `score`
+    let expected = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("score", DataType::Float32, true),
+        Field::new("email", DataType::Utf8, true),
+    ]));
+    let wrapper = ZerobusWrapper::new(config.with_expected_schema(expected))
+        .await
+        .unwrap();
+    let err = wrapper
+        .send_batch(create_test_record_batch())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(err, ZerobusError::ConfigurationError(_)),
+        "{}",
+        err
+    );
+    assert!(
+        err.to_string().contains(
+            "3 difference(s)): retyped column 'score': expected Float32, got Float64; \
+             removed column 'email' (Utf8); added column 'name' (Utf8)"
+        ),
+        "{}",
+        err
+    );
+}
+
+/// Batches over `max_batch_bytes` are rejected, split or truncated as configured
+#[tokio::test]
+async fn test_oversized_batch_policies() {
+    use arrow_zerobus_sdk_wrapper::OversizedBatchPolicy;
+
+    // 100 Int64 rows are 800 bytes; 256 bytes fit 32 rows
+    let batch = RecordBatch::try_new(
+        Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)])),
+        vec![Arc::new(Int64Array::from_iter_values(0..100))],
+    )
+    .unwrap();
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true)
+    .with_max_batch_bytes(256);
+
+    // Reject (default): nothing is sent
+    let wrapper = ZerobusWrapper::new(config.clone()).await.unwrap();
+    let err = wrapper.send_batch(batch.clone()).await.unwrap_err();
+    assert!(
+        matches!(err, ZerobusError::ConfigurationError(_)),
+        "{}",
+        err
+    );
+    assert!(
+        err.to_string().contains("exceeds max_batch_bytes"),
+        "{}",
+        err
+    );
+
+    // Batches within the limit are unaffected
+    assert!(
+        wrapper
+            .send_batch(batch.slice(0, 32))
+            .await
+            .unwrap()
+            .success
+    );
+
+    // AutoSplit: every row is sent, with the original row indices
+    let wrapper = ZerobusWrapper::new(
+        config
+            .clone()
+            .with_oversized_batch_policy(OversizedBatchPolicy::AutoSplit),
+    )
+    .await
+    .unwrap();
+    let result = wrapper.send_batch(batch.clone()).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.total_rows, 100);
+    assert_eq!(result.successful_count, 100);
+    assert_eq!(result.successful_rows, Some((0..100).collect()));
+    assert!(result.failed_rows.is_none());
+
+    // Truncate: the leading rows are sent and the rest reported as failed
+    let wrapper =
+        ZerobusWrapper::new(config.with_oversized_batch_policy(OversizedBatchPolicy::Truncate))
+            .await
+            .unwrap();
+    let result = wrapper.send_batch(batch).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.total_rows, 100);
+    assert_eq!(result.successful_rows, Some((0..32).collect()));
+    assert_eq!(
+        result.get_failed_row_indices(),
+        (32..100).collect::<Vec<_>>()
+    );
+    assert!(result.failed_rows.unwrap()[0]
+        .1
+        .to_string()
+        .contains("Row dropped"));
+}
+
+/// A null string and an empty string encode differently under either string null policy
+#[tokio::test]
+async fn test_string_null_policy_distinguishes_null_from_empty() {
+    use arrow_zerobus_sdk_wrapper::StringNullPolicy;
+
+    let batch = RecordBatch::try_new(
+        Arc::new(Schema::new(vec![Field::new("note", DataType::Utf8, true)])),
+        vec![Arc::new(StringArray::from(vec![Some(""), None]))],
+    )
+    .unwrap();
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true);
+    let row_bytes = |prepared: &arrow_zerobus_sdk_wrapper::PreparedBatch| {
+        prepared
+            .successful_bytes()
+            .iter()
+            .map(|(_, bytes)| bytes.clone())
+            .collect::<Vec<_>>()
+    };
+
+    // Omit (default): without presence, readers decode both rows as ""
+    let wrapper = ZerobusWrapper::new(config.clone()).await.unwrap();
+    let prepared = wrapper.prepare_batch(batch.clone(), None).await.unwrap();
+    assert_eq!(prepared.descriptor().field[0].proto3_optional, None);
+
+    // ExplicitPresence: the empty string is written, the null is not
+    let wrapper = ZerobusWrapper::new(
+        config
+            .clone()
+            .with_string_null_policy(StringNullPolicy::ExplicitPresence),
+    )
+    .await
+    .unwrap();
+    let prepared = wrapper.prepare_batch(batch.clone(), None).await.unwrap();
+    assert_eq!(prepared.descriptor().field[0].proto3_optional, Some(true));
+    let rows = row_bytes(&prepared);
+    assert!(!rows[0].is_empty());
+    assert!(rows[1].is_empty());
+
+    // Sentinel: the null is written as the sentinel value
+    let wrapper = ZerobusWrapper::new(
+        config.with_string_null_policy(StringNullPolicy::Sentinel("<null>".to_string())),
+    )
+    .await
+    .unwrap();
+    let prepared = wrapper.prepare_batch(batch, None).await.unwrap();
+    let rows = row_bytes(&prepared);
+    assert_ne!(rows[0], rows[1]);
+    assert!(rows[1].windows(6).any(|window| window == b"<null>"));
+}
+
+/// A field name resolver matches columns to a provided descriptor without changing it
+#[tokio::test]
+async fn test_field_name_resolver_matches_provided_descriptor() {
+    use arrow_zerobus_sdk_wrapper::wrapper::conversion::generate_protobuf_descriptor;
+
+    let batch = RecordBatch::try_new(
+        Arc::new(Schema::new(vec![
+            Field::new("ID", DataType::Int64, false),
+            Field::new("Name", DataType::Utf8, false),
+        ])),
+        vec![
+            Arc::new(Int64Array::from(vec![1, 2])),
+            Arc::new(StringArray::from(vec!["Alice", "Bob"])),
+        ],
+    )
+    .unwrap();
+    let descriptor = generate_protobuf_descriptor(&Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, false),
+    ]))
+    .unwrap();
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true);
+
+    // Without a resolver, no column matches the lowercase descriptor
+    let wrapper = ZerobusWrapper::new(config.clone()).await.unwrap();
+    let prepared = wrapper
+        .prepare_batch(batch.clone(), Some(descriptor.clone()))
+        .await
+        .unwrap();
+    assert!(prepared
+        .successful_bytes()
+        .iter()
+        .all(|(_, b)| b.is_empty()));
+
+    let wrapper = ZerobusWrapper::new(
+        config.with_field_name_resolver(Arc::new(|name: &str| name.to_lowercase())),
+    )
+    .await
+    .unwrap();
+    let prepared = wrapper
+        .prepare_batch(batch.clone(), Some(descriptor.clone()))
+        .await
+        .unwrap();
+    assert_eq!(prepared.descriptor(), &descriptor);
+    assert!(prepared.failed_rows().is_empty());
+    let rows = prepared.successful_bytes();
+    assert_eq!(rows.len(), 2);
+    // id = 1 (field 1, varint), name = "Alice" (field 2, length-delimited)
+    assert_eq!(rows[0].1, [&[0x08, 1, 0x12, 5][..], b"Alice"].concat());
+
+    // A generated descriptor is matched without the resolver, so every column is encoded
+    let prepared = wrapper.prepare_batch(batch, None).await.unwrap();
+    assert_eq!(
+        prepared.successful_bytes()[0].1,
+        [&[0x08, 1, 0x12, 5][..], b"Alice"].concat()
+    );
+}
+
+/// Failed rows are named by their key column value alongside the row index
+#[tokio::test]
+async fn test_error_key_column_names_failed_rows() {
+    use arrow_zerobus_sdk_wrapper::FloatPolicy;
+
+    // Rows 1 and 2 fail conversion; row 2 has a null key
+    let batch = RecordBatch::try_new(
+        Arc::new(Schema::new(vec![
+            Field::new("order_id", DataType::Utf8, true),
+            Field::new("score", DataType::Float64, false),
+        ])),
+        vec![
+            Arc::new(StringArray::from(vec![Some("A-1"), Some("A-2"), None])),
+            Arc::new(Float64Array::from(vec![1.0, f64::NAN, f64::INFINITY])),
+        ],
+    )
+    .unwrap();
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true)
+    .with_float_policy(FloatPolicy::ErrorOnNonFinite);
+
+    let wrapper = ZerobusWrapper::new(config.clone().with_error_key_column("order_id"))
+        .await
+        .unwrap();
+    let result = wrapper.send_batch(batch.clone()).await.unwrap();
+    let failed = result.failed_rows.clone().unwrap();
+    assert_eq!(failed.len(), 2);
+    assert_eq!(failed[0].0, 1);
+    assert!(
+        failed[0].1.to_string().ends_with("[key order_id=A-2]"),
+        "{}",
+        failed[0].1
+    );
+    assert_eq!(failed[1].0, 2);
+    assert!(
+        failed[1].1.to_string().ends_with("[key order_id=null]"),
+        "{}",
+        failed[1].1
+    );
+    assert_eq!(
+        result.failed_row_keys(&batch, "order_id"),
+        vec![(1, Some("A-2".to_string())), (2, None)]
+    );
+
+    // Redaction withholds key values too
+    let wrapper = ZerobusWrapper::new(
+        config
+            .clone()
+            .with_error_key_column("order_id")
+            .with_redact_values_in_errors(true),
+    )
+    .await
+    .unwrap();
+    let result = wrapper.send_batch(batch.clone()).await.unwrap();
+    let failed = result.failed_rows.unwrap();
+    assert!(failed
+        .iter()
+        .all(|(_, e)| e.to_string().ends_with("[key order_id=<redacted>]")));
+
+    // A key column missing from the batch leaves messages unchanged
+    let wrapper = ZerobusWrapper::new(config.with_error_key_column("customer_id"))
+        .await
+        .unwrap();
+    let result = wrapper.send_batch(batch.clone()).await.unwrap();
+    let failed = result.failed_rows.clone().unwrap();
+    assert_eq!(failed.len(), 2);
+    assert!(failed.iter().all(|(_, e)| !e.to_string().contains("[key")));
+    assert_eq!(
+        result.failed_row_keys(&batch, "customer_id"),
+        vec![(1, None), (2, None)]
+    );
+}
+
+/// An Arrow IPC file or stream is sent batch by batch with global row indices
+#[tokio::test]
+async fn test_send_ipc_reader_multi_batch() {
+    use arrow::ipc::writer::{FileWriter, StreamWriter};
+    use arrow_zerobus_sdk_wrapper::FloatPolicy;
+
+    // Three batches of 2, 3 and 1 rows; row 1 of the second batch (global row 3) fails
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("score", DataType::Float64, false),
+    ]));
+    let batches: Vec<RecordBatch> = [vec![1.0, 2.0], vec![3.0, f64::NAN, 5.0], vec![6.0]]
+        .into_iter()
+        .map(|scores| {
+            RecordBatch::try_new(
+                Arc::clone(&schema),
+                vec![
+                    Arc::new(Int64Array::from_iter_values(0..scores.len() as i64)),
+                    Arc::new(Float64Array::from(scores)),
+                ],
+            )
+            .unwrap()
+        })
+        .collect();
+    let mut stream_bytes = Vec::new();
+    let mut writer = StreamWriter::try_new(&mut stream_bytes, &schema).unwrap();
+    for batch in &batches {
+        writer.write(batch).unwrap();
+    }
+    writer.finish().unwrap();
+    let mut file_bytes = Vec::new();
+    let mut writer = FileWriter::try_new(&mut file_bytes, &schema).unwrap();
+    for batch in &batches {
+        writer.write(batch).unwrap();
+    }
+    writer.finish().unwrap();
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true)
+    .with_float_policy(FloatPolicy::ErrorOnNonFinite);
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    for bytes in [stream_bytes, file_bytes] {
+        let result = wrapper.send_ipc_reader(bytes.as_slice()).await.unwrap();
+        assert!(result.success);
+        assert!(result.error.is_none());
+        assert_eq!(result.total_rows, 6);
+        assert_eq!(result.successful_rows, Some(vec![0, 1, 2, 4, 5]));
+        assert_eq!(result.get_failed_row_indices(), vec![3]);
+    }
+
+    let err = wrapper
+        .send_ipc_reader(&b"not arrow"[..])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ZerobusError::ConversionError(_)), "{}", err);
+}
+
+/// A field name resolver does not affect the descriptor generated for IPC batches
+#[tokio::test]
+async fn test_send_ipc_reader_with_field_name_resolver_encodes_every_column() {
+    use arrow::ipc::writer::StreamWriter;
+
+    let batch = create_test_record_batch();
+    let mut bytes = Vec::new();
+    let mut writer = StreamWriter::try_new(&mut bytes, &batch.schema()).unwrap();
+    writer.write(&batch).unwrap();
+    writer.write(&batch).unwrap();
+    writer.finish().unwrap();
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true)
+    .with_track_row_sizes(true);
+    let expected = ZerobusWrapper::new(config.clone())
+        .await
+        .unwrap()
+        .send_batch(batch)
+        .await
+        .unwrap()
+        .row_sizes
+        .unwrap();
+    assert!(expected.iter().all(|(_, size)| *size > 0));
+
+    let wrapper = ZerobusWrapper::new(
+        config.with_field_name_resolver(Arc::new(|name: &str| name.to_uppercase())),
+    )
+    .await
+    .unwrap();
+    let result = wrapper.send_ipc_reader(bytes.as_slice()).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.successful_count, 10);
+    // Both batches encode every column, just as `send_batch` without a resolver
+    let sizes = result.row_sizes.unwrap();
+    let expected: Vec<(usize, usize)> = expected
+        .iter()
+        .chain(&expected)
+        .enumerate()
+        .map(|(idx, (_, size))| (idx, *size))
+        .collect();
+    assert_eq!(sizes, expected);
+}
+
+/// Table schema validation only touches the network when asked to
+#[tokio::test]
+async fn test_table_schema_validation_stays_offline_unless_requested() {
+    let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true)
+    .with_expected_schema(schema.clone())
+    .with_validate_table_schema_on_startup(true);
+    // Skipped in writer disabled mode, so creating the wrapper needs no Unity Catalog
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    // On demand, missing Unity Catalog settings fail before any request is made
+    let err = wrapper.fetch_table_schema().await.unwrap_err();
+    assert!(
+        matches!(err, ZerobusError::ConfigurationError(_)),
+        "{}",
+        err
+    );
+    let err = wrapper.validate_table_schema(&schema).await.unwrap_err();
+    assert!(
+        matches!(err, ZerobusError::ConfigurationError(_)),
+        "{}",
+        err
+    );
+}
+
+/// Records left unacknowledged by a crash are re-sent from the write-ahead log
+#[tokio::test]
+async fn test_recover_wal_resends_unacknowledged_records() {
+    use arrow_zerobus_sdk_wrapper::wrapper::wal::WriteAheadLog;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let wal_dir = temp_dir.path().join("wal");
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true)
+    .with_wal_dir(wal_dir.clone());
+
+    let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+    let batch =
+        RecordBatch::try_new(schema, vec![Arc::new(Int64Array::from(vec![1, 2, 3]))]).unwrap();
+    let wrapper = ZerobusWrapper::new(config.clone()).await.unwrap();
+    let prepared = wrapper.prepare_batch(batch, None).await.unwrap();
+
+    // Submit all three records, see only row 0 acknowledged, then crash
+    let wal = WriteAheadLog::new(wal_dir.clone(), "test_table").unwrap();
+    let segment = wal
+        .begin_segment(prepared.descriptor(), prepared.successful_bytes())
+        .await
+        .unwrap();
+    segment.acknowledge(&[0]).await.unwrap();
+    drop(segment);
+    drop(wrapper);
+
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+    let results = wrapper.recover_wal().await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert!(results[0].success);
+    assert_eq!(results[0].successful_rows, Some(vec![1, 2]));
+    assert_eq!(std::fs::read_dir(wal.dir()).unwrap().count(), 0);
+    // Nothing left on a second run
+    assert!(wrapper.recover_wal().await.unwrap().is_empty());
+
+    // Recovery needs a WAL directory
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true);
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+    let err = wrapper.recover_wal().await.unwrap_err();
+    assert!(
+        matches!(err, ZerobusError::ConfigurationError(_)),
+        "{}",
+        err
+    );
+}
+
+/// With fatal conversion errors, a partially convertible batch fails as a whole
+#[tokio::test]
+async fn test_conversion_errors_fatal() {
+    use arrow_zerobus_sdk_wrapper::FloatPolicy;
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("score", DataType::Float64, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(Int64Array::from(vec![1, 2, 3])),
+            Arc::new(Float64Array::from(vec![Some(1.5), Some(f64::NAN), None])),
+        ],
+    )
+    .unwrap();
+
+    for fatal in [false, true] {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = WrapperConfiguration::new(
+            "https://test.cloud.databricks.com".to_string(),
+            "test_table".to_string(),
+        )
+        .with_debug_arrow_enabled(true)
+        .with_debug_output(temp_dir.path().to_path_buf())
+        .with_float_policy(FloatPolicy::ErrorOnNonFinite)
+        .with_conversion_errors_fatal(fatal)
+        .with_zerobus_writer_disabled(true);
+        let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+        let result = wrapper.send_batch(batch.clone()).await.unwrap();
+        if fatal {
+            assert!(!result.success);
+            assert_eq!(result.successful_count, 0);
+            match result.error {
+                Some(ZerobusError::ConversionError(message)) => {
+                    assert!(message.contains("1 of 3 rows"), "{}", message);
+                    assert!(message.contains("row 1:"), "{}", message);
+                }
+                other => panic!("expected ConversionError, got {:?}", other),
+            }
+        } else {
+            // Default: the failed row is quarantined and the rest is sent
+            assert!(result.success);
+            assert!(result.error.is_none());
+            assert_eq!(result.successful_count, 2);
+            assert_eq!(result.failed_count, 1);
+        }
+    }
+}
+
+/// Failures beyond `max_tracked_failures` are counted but not stored
+#[tokio::test]
+async fn test_max_tracked_failures_truncates_failed_rows() {
+    use arrow_zerobus_sdk_wrapper::FloatPolicy;
+    use futures::StreamExt;
+
+    // Every row fails conversion except every 100th
+    let num_rows = 1000;
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("score", DataType::Float64, true),
+    ]));
+    let batch = RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(Int64Array::from_iter_values(0..num_rows as i64)),
+            Arc::new(Float64Array::from_iter_values((0..num_rows).map(|i| {
+                if i % 100 == 0 {
+                    1.0
+                } else {
+                    f64::NAN
+                }
+            }))),
+        ],
+    )
+    .unwrap();
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_float_policy(FloatPolicy::ErrorOnNonFinite)
+    .with_max_tracked_failures(5)
+    .with_zerobus_writer_disabled(true);
+    let wrapper = ZerobusWrapper::new(config.clone()).await.unwrap();
+
+    let result = wrapper.send_batch(batch.clone()).await.unwrap();
+    assert!(result.success);
+    assert!(result.failures_truncated);
+    assert_eq!(result.successful_count, 10);
+    assert_eq!(result.failed_count, 990);
+    assert_eq!(
+        result.get_failed_row_indices(),
+        vec![1, 2, 3, 4, 5],
+        "the lowest failed row indices are kept"
+    );
+
+    // Rows failed by an early abort are capped too
+    let wrapper = ZerobusWrapper::new(config.clone().with_max_failures_before_abort(2))
+        .await
+        .unwrap();
+    let result = wrapper.send_batch(batch.clone()).await.unwrap();
+    assert!(result.failures_truncated);
+    assert_eq!(result.successful_count, 1);
+    assert_eq!(result.failed_count, 999);
+    assert_eq!(result.failed_rows.as_ref().map(Vec::len), Some(5));
+
+    // Streaming results still yield every row once, untracked failures included
+    let wrapper = ZerobusWrapper::new(config.clone()).await.unwrap();
+    let results: Vec<_> = wrapper
+        .send_batch_streaming_results(batch.clone(), None)
+        .collect()
+        .await;
+    assert_eq!(results.len(), num_rows);
+    assert_eq!(results.iter().filter(|r| r.outcome.is_err()).count(), 990);
+
+    // Without the cap every failure is stored
+    let wrapper = ZerobusWrapper::new(config.with_max_tracked_failures(usize::MAX))
+        .await
+        .unwrap();
+    let result = wrapper.send_batch(batch).await.unwrap();
+    assert!(!result.failures_truncated);
+    assert_eq!(result.failed_rows.as_ref().map(Vec::len), Some(990));
+}
+
+/// Streaming results yield exactly one outcome per row
+#[tokio::test]
+async fn test_send_batch_streaming_results_yields_each_row() {
+    use futures::StreamExt;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true);
+
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+    let results: Vec<_> = wrapper
+        .send_batch_streaming_results(create_test_record_batch(), None)
+        .collect()
+        .await;
+
+    assert_eq!(results.len(), 5);
+    let mut indices: Vec<usize> = results.iter().map(|r| r.row_idx).collect();
+    indices.sort_unstable();
+    assert_eq!(indices, vec![0, 1, 2, 3, 4]);
+    assert!(results.iter().all(|r| r.outcome.is_ok()));
+}
+
+/// Conversion failures are yielded as per-row errors by streaming results
+#[tokio::test]
+async fn test_send_batch_streaming_results_reports_conversion_errors() {
+    use futures::StreamExt;
+    use prost_types::{field_descriptor_proto::Label, DescriptorProto, FieldDescriptorProto};
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true);
+
+    // Descriptor declares "id" as a string, but the Arrow column is Int64
+    let descriptor = DescriptorProto {
+        name: Some("ZerobusMessage".to_string()),
+        field: vec![FieldDescriptorProto {
+            name: Some("id".to_string()),
+            number: Some(1),
+            label: Some(Label::Optional as i32),
+            r#type: Some(9), // String
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+    let results: Vec<_> = wrapper
+        .send_batch_streaming_results(create_test_record_batch(), Some(descriptor))
+        .collect()
+        .await;
+
+    assert_eq!(results.len(), 5);
+    for result in &results {
+        assert!(
+            matches!(result.outcome, Err(ZerobusError::ConversionError(_))),
+            "Row {} should fail conversion, got: {:?}",
+            result.row_idx,
+            result.outcome
+        );
+    }
+}
+
+/// A provided descriptor with an unnumbered field is rejected instead of producing corrupt bytes
+#[tokio::test]
+async fn test_descriptor_without_field_number_is_rejected() {
+    use arrow_zerobus_sdk_wrapper::wrapper::conversion::generate_protobuf_descriptor;
+
+    let batch = create_test_record_batch();
+    let mut descriptor = generate_protobuf_descriptor(batch.schema().as_ref()).unwrap();
+    descriptor.field[1].number = None;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true);
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    let result = wrapper
+        .send_batch_with_descriptor(batch, Some(descriptor))
+        .await;
+    let error = match result {
+        Err(e) => e,
+        Ok(result) => result.error.expect("batch should fail"),
+    };
+    assert!(
+        matches!(error, ZerobusError::ConfigurationError(_)),
+        "{:?}",
+        error
+    );
+    assert!(
+        error.to_string().contains("has no field number"),
+        "{}",
+        error
+    );
+}
+
+/// Pre-serialized records are sent as-is, with oversized records reported as failed rows
+#[tokio::test]
+async fn test_send_serialized_records() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true);
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    let descriptor = prost_types::DescriptorProto {
+        name: Some("ZerobusMessage".to_string()),
+        field: vec![prost_types::FieldDescriptorProto {
+            name: Some("id".to_string()),
+            number: Some(1),
+            label: Some(prost_types::field_descriptor_proto::Label::Optional as i32),
+            r#type: Some(prost_types::field_descriptor_proto::Type::Int64 as i32),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let records = vec![vec![0x08, 1], vec![0; 5 * 1024 * 1024], vec![0x08, 2]];
+
+    let result = wrapper
+        .send_serialized(records, descriptor.clone())
+        .await
+        .unwrap();
+    assert_eq!(result.total_rows, 3);
+    assert_eq!(result.successful_count, 2);
+    assert_eq!(result.successful_rows, Some(vec![0, 2]));
+    let failed = result.failed_rows.unwrap();
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].0, 1);
+    assert!(matches!(failed[0].1, ZerobusError::ConversionError(_)));
+
+    // An invalid descriptor is rejected before anything is sent
+    let invalid = prost_types::DescriptorProto {
+        field: vec![prost_types::FieldDescriptorProto {
+            number: Some(0),
+            ..descriptor.field[0].clone()
+        }],
+        ..descriptor
+    };
+    assert!(matches!(
+        wrapper.send_serialized(vec![vec![0x08, 1]], invalid).await,
+        Err(ZerobusError::ConfigurationError(_))
+    ));
+}
+
+/// Health checks report failures in the status instead of erroring, and never send data
+#[tokio::test]
+async fn test_health_check_modes() {
+    use arrow_zerobus_sdk_wrapper::HealthCheckMode;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true);
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    let status = wrapper.health_check().await;
+    assert!(status.healthy);
+    assert_eq!(status.mode, HealthCheckMode::Disabled);
+    assert_eq!(status.mode.as_str(), "disabled");
+    assert!(status.latency_ms.is_none());
+    assert!(status.error.is_none());
+
+    // Nothing listens on port 1, so no OAuth token can be obtained
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "health_check_table".to_string(),
+    )
+    .with_credentials("client_id".to_string(), "client_secret".to_string())
+    .with_unity_catalog("https://127.0.0.1:1".to_string());
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    let status = wrapper.health_check().await;
+    assert!(!status.healthy);
+    assert_eq!(status.mode, HealthCheckMode::Live);
+    assert!(status.latency_ms.is_some());
+    assert!(matches!(
+        status.error,
+        Some(ZerobusError::TokenRefreshError(_))
+    ));
+}
+
+/// A provided descriptor that fails every row falls back to one generated from the schema
+#[tokio::test]
+async fn test_descriptor_fallback_recovers_broken_descriptor() {
+    use prost_types::{
+        field_descriptor_proto::{Label, Type},
+        DescriptorProto, FieldDescriptorProto,
+    };
+
+    // `id` is declared as a string, so no Int64 value converts
+    let broken = DescriptorProto {
+        name: Some("Broken".to_string()),
+        field: vec![
+            FieldDescriptorProto {
+                name: Some("id".to_string()),
+                number: Some(1),
+                label: Some(Label::Optional as i32),
+                r#type: Some(Type::String as i32),
+                ..Default::default()
+            },
+            FieldDescriptorProto {
+                name: Some("name".to_string()),
+                number: Some(2),
+                label: Some(Label::Optional as i32),
+                r#type: Some(Type::String as i32),
+                ..Default::default()
+            },
+            FieldDescriptorProto {
+                name: Some("score".to_string()),
+                number: Some(3),
+                label: Some(Label::Optional as i32),
+                r#type: Some(Type::Double as i32),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    for fallback in [false, true] {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = WrapperConfiguration::new(
+            "https://test.cloud.databricks.com".to_string(),
+            "test_table".to_string(),
+        )
+        .with_debug_arrow_enabled(true)
+        .with_debug_output(temp_dir.path().to_path_buf())
+        .with_descriptor_fallback(fallback)
+        .with_zerobus_writer_disabled(true);
+        let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+        let result = wrapper
+            .send_batch_with_descriptor(create_test_record_batch(), Some(broken.clone()))
+            .await
+            .unwrap();
+        if fallback {
+            assert!(result.success);
+            assert_eq!(result.successful_count, 5);
+            assert_eq!(result.failed_count, 0);
+        } else {
+            assert!(!result.success);
+            assert_eq!(result.failed_count, 5);
+        }
+    }
+}
+
+/// Row outcomes merge successful and failed rows in row order with per-row details
+#[test]
+fn test_row_outcomes_mixed() {
+    use arrow_zerobus_sdk_wrapper::{FailurePhase, RowStatus};
+
+    let result = TransmissionResult {
+        success: true,
+        error: None,
+        attempts: 1,
+        latency_ms: Some(5),
+        batch_size_bytes: 100,
+        failed_rows: Some(vec![
+            (1, ZerobusError::ConversionError("bad value".to_string())),
+            (
+                3,
+                ZerobusError::ConnectionError("stream closed".to_string()),
+            ),
+        ]),
+        successful_rows: Some(vec![0, 2]),
+        total_rows: 4,
+        successful_count: 2,
+        failed_count: 2,
+        row_sizes: Some(vec![(0, 12), (2, 14), (3, 9)]),
+        failed_row_bytes: None,
+        ack_ids: Some(vec![(0, 40), (2, 41)]),
+        mirror_result: None,
+        failures_truncated: false,
+    };
+
+    let outcomes = result.row_outcomes();
+    let indices: Vec<usize> = outcomes.iter().map(|o| o.row_idx).collect();
+    assert_eq!(indices, vec![0, 1, 2, 3]);
+    assert!(matches!(
+        outcomes[0].status,
+        RowStatus::Succeeded {
+            ack_id: Some(40),
+            bytes: Some(12)
+        }
+    ));
+    assert!(matches!(
+        outcomes[1].status,
+        RowStatus::Failed {
+            error: ZerobusError::ConversionError(_),
+            phase: FailurePhase::Conversion
+        }
+    ));
+    assert!(matches!(
+        outcomes[2].status,
+        RowStatus::Succeeded {
+            ack_id: Some(41),
+            bytes: Some(14)
+        }
+    ));
+    assert!(matches!(
+        outcomes[3].status,
+        RowStatus::Failed {
+            error: ZerobusError::ConnectionError(_),
+            phase: FailurePhase::Transmission
+        }
+    ));
+
+    // A batch-level error processed no rows
+    let batch_error = TransmissionResult {
+        success: false,
+        error: Some(ZerobusError::AuthenticationError("denied".to_string())),
+        failed_rows: None,
+        successful_rows: None,
+        successful_count: 0,
+        failed_count: 0,
+        row_sizes: None,
+        ack_ids: None,
+        ..result
+    };
+    assert!(batch_error.row_outcomes().is_empty());
+}
+
+/// Error statistics combine row and error type counts across results
+#[test]
+fn test_error_statistics_aggregate() {
+    use arrow_zerobus_sdk_wrapper::ErrorStatistics;
+
+    let partial = TransmissionResult {
+        success: true,
+        error: None,
+        attempts: 1,
+        latency_ms: Some(5),
+        batch_size_bytes: 100,
+        failed_rows: Some(vec![
+            (1, ZerobusError::ConversionError("bad value".to_string())),
+            (
+                3,
+                ZerobusError::ConnectionError("stream closed".to_string()),
+            ),
+        ]),
+        successful_rows: Some(vec![0, 2]),
+        total_rows: 4,
+        successful_count: 2,
+        failed_count: 2,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
+    };
+    let empty = TransmissionResult {
+        failed_rows: None,
+        successful_rows: None,
+        total_rows: 0,
+        successful_count: 0,
+        failed_count: 0,
+        ..partial.clone()
+    };
+    let all_failed = TransmissionResult {
+        success: false,
+        failed_rows: Some(
+            (0..4)
+                .map(|i| (i, ZerobusError::ConversionError(format!("bad value {}", i))))
+                .collect(),
+        ),
+        successful_rows: None,
+        successful_count: 0,
+        failed_count: 4,
+        ..partial.clone()
+    };
+    let all_succeeded = TransmissionResult {
+        failed_rows: None,
+        successful_rows: Some((0..8).collect()),
+        total_rows: 8,
+        successful_count: 8,
+        failed_count: 0,
+        ..partial.clone()
+    };
+
+    let stats = ErrorStatistics::aggregate(&[partial, empty.clone(), all_failed, all_succeeded]);
+    assert_eq!(stats.total_rows, 16);
+    assert_eq!(stats.successful_count, 10);
+    assert_eq!(stats.failed_count, 6);
+    assert_eq!(stats.success_rate, 0.625);
+    assert_eq!(stats.failure_rate, 0.375);
+    assert_eq!(stats.error_type_counts.len(), 2);
+    assert_eq!(stats.error_type_counts.get("ConversionError"), Some(&5));
+    assert_eq!(stats.error_type_counts.get("ConnectionError"), Some(&1));
+
+    // No rows at all: zero rates rather than NaN
+    for stats in [
+        ErrorStatistics::aggregate(&[]),
+        ErrorStatistics::aggregate(&[empty]),
+    ] {
+        assert_eq!(stats.total_rows, 0);
+        assert_eq!(stats.success_rate, 0.0);
+        assert_eq!(stats.failure_rate, 0.0);
+        assert!(stats.error_type_counts.is_empty());
+    }
+}
+
+/// In writer disabled mode rows are converted but never acknowledged
+#[tokio::test]
+async fn test_row_outcomes_writer_disabled() {
+    use arrow_zerobus_sdk_wrapper::{FailurePhase, FloatPolicy, RowStatus};
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_float_policy(FloatPolicy::ErrorOnNonFinite)
+    .with_track_row_sizes(true)
+    .with_zerobus_writer_disabled(true);
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    let schema = Schema::new(vec![Field::new("score", DataType::Float64, true)]);
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![Arc::new(Float64Array::from(vec![1.5, f64::INFINITY]))],
+    )
+    .unwrap();
+
+    let result = wrapper.send_batch(batch).await.unwrap();
+    assert!(result.ack_ids.is_none());
+    let outcomes = result.row_outcomes();
+    assert_eq!(outcomes.len(), 2);
+    assert!(matches!(
+        outcomes[0].status,
+        RowStatus::Succeeded {
+            ack_id: None,
+            bytes: Some(_)
+        }
+    ));
+    assert!(matches!(
+        outcomes[1].status,
+        RowStatus::Failed {
+            phase: FailurePhase::Conversion,
+            ..
+        }
+    ));
+}
+
+/// Check one line of Prometheus text: a `# HELP`/`# TYPE` comment or a numeric sample
+fn assert_valid_prometheus_line(line: &str) {
+    if let Some(comment) = line.strip_prefix("# ") {
+        let mut parts = comment.splitn(3, ' ');
+        let keyword = parts.next().unwrap();
+        assert!(
+            keyword == "HELP" || keyword == "TYPE",
+            "bad comment: {line}"
+        );
+        let name = parts.next().expect("comment without metric name");
+        assert!(is_valid_metric_name(name), "bad metric name: {line}");
+        if keyword == "TYPE" {
+            let kind = parts.next().unwrap();
+            assert!(
+                ["counter", "gauge", "histogram", "summary", "untyped"].contains(&kind),
+                "bad type: {line}"
+            );
+        }
+        return;
+    }
+
+    let (series, value) = line.rsplit_once(' ').expect("sample without value");
+    value
+        .parse::<f64>()
+        .unwrap_or_else(|_| panic!("bad value: {line}"));
+    let (name, labels) = match series.split_once('{') {
+        Some((name, labels)) => (name, labels.strip_suffix('}').expect("unclosed labels")),
+        None => (series, ""),
+    };
+    assert!(is_valid_metric_name(name), "bad metric name: {line}");
+    for label in labels.split(',').filter(|label| !label.is_empty()) {
+        let (key, value) = label.split_once('=').expect("label without value");
+        assert!(is_valid_metric_name(key), "bad label name: {line}");
+        assert!(
+            value.len() >= 2 && value.starts_with('"') && value.ends_with('"'),
+            "unquoted label value: {line}"
+        );
+    }
+}
+
+fn is_valid_metric_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+}
+
+#[tokio::test]
+async fn test_metrics_prometheus() {
+    use arrow_zerobus_sdk_wrapper::FloatPolicy;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_float_policy(FloatPolicy::ErrorOnNonFinite)
+    .with_telemetry_namespace("orders_ingest".to_string())
+    .with_zerobus_writer_disabled(true);
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    let schema = Schema::new(vec![Field::new("score", DataType::Float64, true)]);
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![Arc::new(Float64Array::from(vec![1.5, 2.5, f64::NAN]))],
+    )
+    .unwrap();
+    wrapper.send_batch(batch.clone()).await.unwrap();
+    wrapper.send_batch(batch).await.unwrap();
+
+    let text = wrapper.metrics_prometheus();
+    for line in text.lines() {
+        assert_valid_prometheus_line(line);
+    }
+    for name in [
+        "orders_ingest_batches_total",
+        "orders_ingest_rows_succeeded_total",
+        "orders_ingest_rows_failed_total",
+        "orders_ingest_bytes_total",
+        "orders_ingest_retries_total",
+        "orders_ingest_stream_reconnects_total",
+        "orders_ingest_batch_latency_ms",
+    ] {
+        assert!(
+            text.contains(&format!("# TYPE {name} ")),
+            "missing {name} in:\n{text}"
+        );
+    }
+    assert!(text.contains("orders_ingest_batches_total{table=\"test_table\"} 2\n"));
+    assert!(text.contains("orders_ingest_rows_succeeded_total{table=\"test_table\"} 4\n"));
+    assert!(text.contains(
+        "orders_ingest_rows_failed_total{table=\"test_table\",error_type=\"ConversionError\"} 2\n"
+    ));
+    assert!(text
+        .contains("orders_ingest_batch_latency_ms_bucket{table=\"test_table\",le=\"+Inf\"} 2\n"));
+    assert!(text.contains("orders_ingest_batch_latency_ms_count{table=\"test_table\"} 2\n"));
+}
+
+/// A send stuck in the SDK is abandoned once the send deadline passes
+#[tokio::test]
+async fn test_send_deadline_abandons_stuck_send() {
+    use std::time::{Duration, Instant};
+
+    // Both rustls providers are compiled in, so the SDK's TLS channel needs one chosen
+    let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+
+    // Accepts connections but never responds, so stream creation never completes
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        let mut connections = Vec::new();
+        while let Ok((socket, _)) = listener.accept().await {
+            connections.push(socket);
+        }
+    });
+
+    let config = WrapperConfiguration::new(
+        format!("https://{}", addr),
+        "main.default.send_deadline_table".to_string(),
+    )
+    .with_credentials("client_id".to_string(), "client_secret".to_string())
+    .with_unity_catalog(format!("http://{}", addr))
+    .with_send_deadline(Duration::from_millis(300));
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    let start = Instant::now();
+    let result = wrapper
+        .send_batch(create_test_record_batch())
+        .await
+        .unwrap();
+    assert!(start.elapsed() < Duration::from_secs(10));
+    assert!(!result.success);
+    assert!(result.attempts >= 1);
+    match result.error {
+        Some(ZerobusError::TransmissionError(message)) => {
+            assert!(message.contains("deadline"), "{}", message)
+        }
+        other => panic!("expected deadline error, got {:?}", other),
+    }
+
+    server.abort();
+}
+
+/// An empty batch sent first still writes the descriptor debug file for its schema,
+/// without connecting to Zerobus (nothing listens on the endpoint)
+#[tokio::test]
+async fn test_empty_batch_writes_descriptor() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://127.0.0.1:1".to_string(),
+        "main.default.empty_first".to_string(),
+    )
+    .with_credentials("client_id".to_string(), "client_secret".to_string())
+    .with_unity_catalog("http://127.0.0.1:1".to_string())
+    .with_debug_protobuf_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf());
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    let batch = create_test_record_batch().slice(0, 0);
+    let result = wrapper.send_batch(batch).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.total_rows, 0);
+
+    let descriptor_file = temp_dir
+        .path()
+        .join("zerobus/descriptors/main_default_empty_first.pb");
+    assert!(descriptor_file.exists());
+}
+
+/// A split batch whose sub-batch fails at batch level reports every row not sent as
+/// failed, with the sub-batch's error (nothing listens on the endpoint; the over-long
+/// column name fails each sub-batch before the network is reached)
+#[tokio::test]
+async fn test_auto_split_reports_rows_not_sent() {
+    use arrow_zerobus_sdk_wrapper::OversizedBatchPolicy;
+
+    let config = WrapperConfiguration::new(
+        "https://127.0.0.1:1".to_string(),
+        "main.default.split_failure".to_string(),
+    )
+    .with_credentials("client_id".to_string(), "client_secret".to_string())
+    .with_unity_catalog("http://127.0.0.1:1".to_string())
+    .with_max_field_name_length(16)
+    .with_max_batch_bytes(256)
+    .with_oversized_batch_policy(OversizedBatchPolicy::AutoSplit);
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    // 100 Int64 rows are 800 bytes, split into sub-batches of 32 rows
+    let schema = Schema::new(vec![Field::new(
+        "a_rather_long_column_name",
+        DataType::Int64,
+        false,
+    )]);
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![Arc::new(Int64Array::from_iter_values(0..100))],
+    )
+    .unwrap();
+
+    let result = wrapper.send_batch(batch).await.unwrap();
+    assert!(!result.success);
+    assert!(matches!(
+        result.error,
+        Some(ZerobusError::ConfigurationError(_))
+    ));
+    assert_eq!(result.total_rows, 100);
+    assert_eq!(result.failed_count, 100);
+    assert_eq!(
+        result.get_failed_row_indices(),
+        (0..100).collect::<Vec<_>>()
+    );
+    let failed_rows = result.failed_rows.unwrap();
+    assert!(failed_rows
+        .iter()
+        .all(|(_, e)| e.to_string().contains("a_rather_long_column_name")));
+}
+
+/// An over-long column name fails the send with a clear error before stream creation
+/// (nothing listens on the endpoint, so reaching the network would be a connection error)
+#[tokio::test]
+async fn test_over_long_column_name_fails_before_network() {
+    let config = WrapperConfiguration::new(
+        "https://127.0.0.1:1".to_string(),
+        "main.default.long_names".to_string(),
+    )
+    .with_credentials("client_id".to_string(), "client_secret".to_string())
+    .with_unity_catalog("http://127.0.0.1:1".to_string())
+    .with_max_field_name_length(16);
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    let name = "a_rather_long_column_name";
+    let schema = Schema::new(vec![Field::new(name, DataType::Int64, false)]);
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![Arc::new(Int64Array::from(vec![1, 2]))],
+    )
+    .unwrap();
+
+    let result = wrapper.send_batch(batch).await.unwrap();
+    assert!(!result.success);
+    match result.error {
+        Some(ZerobusError::ConfigurationError(message)) => {
+            assert!(message.contains(name), "{}", message);
+            assert!(message.contains("25 characters"), "{}", message);
+        }
+        other => panic!("expected ConfigurationError, got {:?}", other),
+    }
+}
+
+/// A custom encoder set on the configuration is used by `send_batch`
+#[tokio::test]
+async fn test_custom_encoder_used_by_send_batch() {
+    use arrow::array::Int8Array;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true);
+    let schema = Arc::new(Schema::new(vec![Field::new(
+        "level",
+        DataType::Int8,
+        false,
+    )]));
+    let batch = RecordBatch::try_new(schema, vec![Arc::new(Int8Array::from(vec![1, 2]))]).unwrap();
+
+    // Int8 is not supported by the built-in encoder
+    let wrapper = ZerobusWrapper::new(config.clone()).await.unwrap();
+    let result = wrapper.send_batch(batch.clone()).await.unwrap();
+    assert_eq!(result.failed_count, 2);
+
+    let config = config.with_custom_encoder(Arc::new(|field, array, row, buffer| {
+        let values = array.as_any().downcast_ref::<Int8Array>()?;
+        buffer.push((field.number.unwrap_or(0) as u8) << 3);
+        buffer.push(values.value(row) as u8);
+        Some(Ok(()))
+    }));
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+    let result = wrapper.send_batch(batch).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.successful_count, 2);
+}
+
+/// Duplicate column names fail the send even with a provided descriptor, which would
+/// otherwise encode only one of the columns
+#[tokio::test]
+async fn test_duplicate_column_names_fail_send() {
+    let config = WrapperConfiguration::new(
+        "https://127.0.0.1:1".to_string(),
+        "main.default.duplicates".to_string(),
+    )
+    .with_credentials("client_id".to_string(), "client_secret".to_string())
+    .with_unity_catalog("http://127.0.0.1:1".to_string());
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    let schema = Schema::new(vec![
+        Field::new("amount", DataType::Int64, false),
+        Field::new("amount", DataType::Int64, false),
+    ]);
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int64Array::from(vec![1, 2])),
+            Arc::new(Int64Array::from(vec![3, 4])),
+        ],
+    )
+    .unwrap();
+    let descriptor = prost_types::DescriptorProto {
+        name: Some("ZerobusMessage".to_string()),
+        field: vec![prost_types::FieldDescriptorProto {
+            name: Some("amount".to_string()),
+            number: Some(1),
+            label: Some(prost_types::field_descriptor_proto::Label::Optional as i32),
+            r#type: Some(prost_types::field_descriptor_proto::Type::Int64 as i32),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    let result = wrapper
+        .send_batch_with_descriptor(batch, Some(descriptor))
+        .await
+        .unwrap();
+    assert!(!result.success);
+    match result.error {
+        Some(ZerobusError::ConfigurationError(message)) => {
+            assert!(message.contains("'amount'"), "{}", message);
+        }
+        other => panic!("expected ConfigurationError, got {:?}", other),
+    }
+}
+
+/// A batch-level failure is retained as the table's last error
+#[tokio::test]
+async fn test_last_error_records_failed_batch() {
+    let config = WrapperConfiguration::new(
+        "https://127.0.0.1:1".to_string(),
+        "main.default.last_error".to_string(),
+    )
+    .with_credentials("client_id".to_string(), "client_secret".to_string())
+    .with_unity_catalog("http://127.0.0.1:1".to_string());
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+    assert!(wrapper.last_error("main.default.last_error").is_none());
+
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("id", DataType::Int64, false),
+    ]);
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int64Array::from(vec![1])),
+            Arc::new(Int64Array::from(vec![2])),
+        ],
+    )
+    .unwrap();
+    let before = std::time::Instant::now();
+    let result = wrapper.send_batch(batch).await.unwrap();
+    assert!(!result.success);
+
+    let (recorded_at, error) = wrapper
+        .last_error("main.default.last_error")
+        .expect("failed batch should be recorded");
+    assert!(recorded_at >= before);
+    assert!(
+        matches!(error, ZerobusError::ConfigurationError(_)),
+        "{:?}",
+        error
+    );
+    assert!(wrapper.last_error("main.default.other").is_none());
+}
+
+/// A codec the SDK cannot apply is refused when the wrapper is created, before any stream
+#[tokio::test]
+async fn test_unsupported_transmission_compression_rejected() {
+    use arrow_zerobus_sdk_wrapper::CompressionType;
+
+    let config = WrapperConfiguration::new(
+        "https://127.0.0.1:1".to_string(),
+        "main.default.compressed".to_string(),
+    )
+    .with_credentials("client_id".to_string(), "client_secret".to_string())
+    .with_unity_catalog("http://127.0.0.1:1".to_string())
+    .with_transmission_compression(CompressionType::Zstd);
+
+    match ZerobusWrapper::new(config).await {
+        Err(ZerobusError::ConfigurationError(message)) => {
+            assert!(message.contains("Zstd"), "{}", message);
+        }
+        other => panic!("expected ConfigurationError, got {:?}", other.map(|_| ())),
+    }
+}
+
+/// Rotations by writers of different tables share one global file cap, which deletes the
+/// oldest rotated files regardless of table while keeping every writer's active file
+#[tokio::test]
+async fn test_debug_global_max_files_across_tables() {
+    use arrow_zerobus_sdk_wrapper::wrapper::debug::DebugWriter;
+    use std::time::{Duration, SystemTime};
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let writer = |table: &str| {
+        DebugWriter::new(
+            temp_dir.path().to_path_buf(),
+            table.to_string(),
+            Duration::from_secs(5),
+            None,
+            None,
+        )
+        .unwrap()
+        .with_global_max_files(Some(2))
+    };
+    let writer_a = writer("table_a");
+    let writer_b = writer("table_b");
+
+    let batch = |rows: usize| {
+        let schema = Schema::new(vec![Field::new("id", DataType::Int64, false)]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(Int64Array::from_iter_values(0..rows as i64))],
+        )
+        .unwrap()
+    };
+    writer_a.write_arrow(&batch(3)).await.unwrap();
+    writer_b.write_arrow(&batch(3)).await.unwrap();
+
+    // Files left by earlier rotations, interleaved across tables, oldest first
+    let arrow_dir = temp_dir.path().join("zerobus/arrow");
+    let proto_dir = temp_dir.path().join("zerobus/proto");
+    let old_files = [
+        arrow_dir.join("table_a_20240101_000000.arrows"),
+        proto_dir.join("table_b_20240101_000100.proto"),
+        arrow_dir.join("table_a_20240101_000200.arrows"),
+        arrow_dir.join("table_b_20240101_000300.arrows"),
+    ];
+    for (age, path) in (1..=old_files.len()).rev().zip(&old_files) {
+        let file = std::fs::File::create(path).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(100 * age as u64))
+            .unwrap();
+    }
+
+    // Table A rotates: its previous file joins the four old ones, so three must go
+    writer_a.write_arrow(&batch(1000)).await.unwrap();
+    assert!(!old_files[0].exists());
+    assert!(!old_files[1].exists());
+    assert!(!old_files[2].exists());
+    assert!(old_files[3].exists());
+    assert!(arrow_dir.join("table_a.arrows").exists());
+    // Table B's active file is never deleted
+    assert!(arrow_dir.join("table_b.arrows").exists());
+
+    // Table B rotates: the oldest remaining file belongs to table B
+    writer_b.write_arrow(&batch(1000)).await.unwrap();
+    assert!(!old_files[3].exists());
+    assert!(arrow_dir.join("table_a.arrows").exists());
+    assert!(arrow_dir.join("table_b.arrows").exists());
+
+    // Two rotated files plus each writer's active file remain
+    assert_eq!(std::fs::read_dir(&arrow_dir).unwrap().count(), 4);
+}
+
+/// Propagated schema metadata lands in the debug descriptor's options only; the
+/// descriptor used for the stream carries no options
+#[tokio::test]
+async fn test_schema_metadata_only_in_debug_descriptor() {
+    use prost::Message;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "main.default.with_metadata".to_string(),
+    )
+    .with_debug_protobuf_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_propagate_schema_metadata(true)
+    .with_schema_metadata_keys(vec!["source_system".to_string()])
+    .with_zerobus_writer_disabled(true);
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    let schema = Schema::new(vec![Field::new("id", DataType::Int64, false)]).with_metadata(
+        [
+            ("source_system".to_string(), "orders".to_string()),
+            ("owner".to_string(), "team".to_string()),
+        ]
+        .into_iter()
+        .collect(),
+    );
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![Arc::new(Int64Array::from(vec![1, 2]))],
+    )
+    .unwrap();
+
+    let prepared = wrapper.prepare_batch(batch.clone(), None).await.unwrap();
+    assert!(prepared.descriptor().options.is_none());
+
+    let result = wrapper.send_batch(batch).await.unwrap();
+    assert!(result.success);
+
+    let bytes = std::fs::read(
+        temp_dir
+            .path()
+            .join("zerobus/descriptors/main_default_with_metadata.pb"),
+    )
+    .unwrap();
+    let file = prost_types::FileDescriptorProto::decode(bytes.as_slice()).unwrap();
+    let options = file.message_type[0].options.as_ref().unwrap();
+    let names: Vec<&str> = options
+        .uninterpreted_option
+        .iter()
+        .map(|o| o.name[0].name_part.as_str())
+        .collect();
+    assert_eq!(names, vec!["source_system"]);
+}
+
+#[tokio::test]
+async fn test_mirror_table_receives_every_batch() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let debug_output_dir = temp_dir.path().to_path_buf();
+
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "events".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(debug_output_dir.clone())
+    .with_zerobus_writer_disabled(true)
+    .with_mirror_table("events_v2".to_string());
+
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+    let result = wrapper
+        .send_batch(create_test_record_batch())
+        .await
+        .unwrap();
+
+    // Primary result
+    assert!(result.success);
+    assert_eq!(result.successful_count, 5);
+
+    // Mirror result is reported separately
+    let mirror_result = result.mirror_result.expect("mirror result should be set");
+    assert!(mirror_result.success);
+    assert_eq!(mirror_result.total_rows, 5);
+    assert_eq!(mirror_result.successful_count, 5);
+    assert!(mirror_result.mirror_result.is_none());
+
+    wrapper.flush().await.unwrap();
+    wrapper.shutdown().await.unwrap();
+
+    // Both tables got the batch
+    for table in ["events", "events_v2"] {
+        let arrow_file = debug_output_dir.join(format!("zerobus/arrow/{}.arrows", table));
+        assert!(
+            arrow_file.exists(),
+            "missing debug file for table {}",
+            table
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_no_mirror_result_without_mirror_table() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "events".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true);
+
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+    let result = wrapper
+        .send_batch(create_test_record_batch())
+        .await
+        .unwrap();
+
+    assert!(result.success);
+    assert!(result.mirror_result.is_none());
+}
+
+#[tokio::test]
+async fn test_mirror_table_must_differ_from_primary() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "events".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true)
+    .with_mirror_table("events".to_string());
+
+    assert!(ZerobusWrapper::new(config).await.is_err());
+}
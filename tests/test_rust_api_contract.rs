@@ -138,6 +138,11 @@ fn test_transmission_result_contract() {
         total_rows: 0,
         successful_count: 0,
         failed_count: 0,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     assert!(result.success);
@@ -0,0 +1,153 @@
+//! Integration tests for add-only schema evolution
+//!
+//! Uses writer disabled mode; the evolved descriptor is checked through the debug
+//! descriptor files. Stream recreation is covered by the inline tests in `wrapper`.
+
+use arrow::array::{Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use arrow_zerobus_sdk_wrapper::{
+    SchemaEvolution, WrapperConfiguration, ZerobusError, ZerobusWrapper,
+};
+use prost::Message;
+use prost_types::{DescriptorProto, FileDescriptorProto};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+async fn create_wrapper(temp_dir: &TempDir, mode: SchemaEvolution) -> ZerobusWrapper {
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "events".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true)
+    .with_schema_evolution(mode);
+    ZerobusWrapper::new(config).await.unwrap()
+}
+
+fn original_batch() -> RecordBatch {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, false),
+    ]);
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int64Array::from(vec![1, 2])),
+            Arc::new(StringArray::from(vec!["Alice", "Bob"])),
+        ],
+    )
+    .unwrap()
+}
+
+/// Same schema with a new nullable column inserted before `name`
+fn evolved_batch() -> RecordBatch {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("email", DataType::Utf8, true),
+        Field::new("name", DataType::Utf8, false),
+    ]);
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int64Array::from(vec![3, 4])),
+            Arc::new(StringArray::from(vec![Some("c@example.com"), None])),
+            Arc::new(StringArray::from(vec!["Carol", "Dave"])),
+        ],
+    )
+    .unwrap()
+}
+
+fn read_descriptors(temp_dir: &TempDir) -> Vec<DescriptorProto> {
+    let dir = temp_dir.path().join("zerobus/descriptors");
+    std::fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "pb"))
+        .map(|path| {
+            FileDescriptorProto::decode(std::fs::read(path).unwrap().as_slice())
+                .unwrap()
+                .message_type
+                .remove(0)
+        })
+        .collect()
+}
+
+fn field_number(descriptor: &DescriptorProto, name: &str) -> Option<i32> {
+    descriptor
+        .field
+        .iter()
+        .find(|f| f.name.as_deref() == Some(name))
+        .and_then(|f| f.number)
+}
+
+#[tokio::test]
+async fn test_add_only_evolution_appends_new_field() {
+    let temp_dir = TempDir::new().unwrap();
+    let wrapper = create_wrapper(&temp_dir, SchemaEvolution::AddOnly).await;
+
+    let first = wrapper.send_batch(original_batch()).await.unwrap();
+    assert!(first.success);
+    let second = wrapper.send_batch(evolved_batch()).await.unwrap();
+    assert!(second.success);
+    assert_eq!(second.successful_count, 2);
+
+    // Batches with the original schema still work after evolution
+    let third = wrapper.send_batch(original_batch()).await.unwrap();
+    assert!(third.success);
+
+    let descriptors = read_descriptors(&temp_dir);
+    assert_eq!(descriptors.len(), 2);
+    let evolved = descriptors
+        .iter()
+        .find(|d| d.field.len() == 3)
+        .expect("evolved descriptor should be written");
+    // Existing fields keep their numbers; the new one is appended
+    assert_eq!(field_number(evolved, "id"), Some(1));
+    assert_eq!(field_number(evolved, "name"), Some(2));
+    assert_eq!(field_number(evolved, "email"), Some(3));
+
+    wrapper.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_add_only_evolution_rejects_type_change() {
+    let temp_dir = TempDir::new().unwrap();
+    let wrapper = create_wrapper(&temp_dir, SchemaEvolution::AddOnly).await;
+    wrapper.send_batch(original_batch()).await.unwrap();
+
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("name", DataType::Utf8, false),
+    ]);
+    let changed = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(StringArray::from(vec!["1"])),
+            Arc::new(StringArray::from(vec!["Alice"])),
+        ],
+    )
+    .unwrap();
+
+    let result = wrapper.send_batch(changed).await.unwrap();
+    assert!(!result.success);
+    assert!(matches!(
+        result.error,
+        Some(ZerobusError::ConfigurationError(msg)) if msg.contains("not add-only")
+    ));
+}
+
+#[tokio::test]
+async fn test_strict_mode_does_not_evolve() {
+    let temp_dir = TempDir::new().unwrap();
+    let wrapper = create_wrapper(&temp_dir, SchemaEvolution::Strict).await;
+
+    wrapper.send_batch(original_batch()).await.unwrap();
+    wrapper.send_batch(evolved_batch()).await.unwrap();
+
+    // Each batch used its own generated descriptor, so `name` was renumbered
+    let descriptors = read_descriptors(&temp_dir);
+    let second = descriptors.iter().find(|d| d.field.len() == 3).unwrap();
+    assert_eq!(field_number(second, "name"), Some(3));
+}
@@ -0,0 +1,127 @@
+//! Integration tests for sending several batches in one call
+//!
+//! Uses writer disabled mode so batches are converted without real Zerobus streams.
+
+use arrow::array::{Int64Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use arrow_zerobus_sdk_wrapper::{WrapperConfiguration, ZerobusError, ZerobusWrapper};
+use std::sync::Arc;
+use tempfile::TempDir;
+
+fn create_batch(ids: std::ops::Range<i64>) -> RecordBatch {
+    let schema = Schema::new(vec![
+        Field::new("id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, false),
+    ]);
+    // Name length grows with the id, so each row's serialized size identifies it
+    let names: Vec<String> = ids.clone().map(|id| "x".repeat(id as usize)).collect();
+    RecordBatch::try_new(
+        Arc::new(schema),
+        vec![
+            Arc::new(Int64Array::from(ids.collect::<Vec<_>>())),
+            Arc::new(StringArray::from(names)),
+        ],
+    )
+    .unwrap()
+}
+
+async fn create_wrapper(temp_dir: &TempDir) -> ZerobusWrapper {
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true)
+    .with_track_row_sizes(true);
+    ZerobusWrapper::new(config).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_send_batches_aggregates_results() {
+    let temp_dir = TempDir::new().unwrap();
+    let wrapper = create_wrapper(&temp_dir).await;
+
+    let batches = vec![create_batch(1..4), create_batch(4..9), create_batch(9..11)];
+    let result = wrapper.send_batches(batches).await.unwrap();
+
+    assert!(result.success);
+    assert!(result.error.is_none());
+    assert_eq!(result.total_rows, 10);
+    assert_eq!(result.successful_count, 10);
+    assert_eq!(result.failed_count, 0);
+    assert!(result.failed_rows.is_none());
+    assert_eq!(
+        result.successful_rows.as_deref(),
+        Some((0..10).collect::<Vec<_>>().as_slice())
+    );
+
+    // Global row index i is id i + 1; its size grows with the id across batch boundaries
+    let sizes = result.row_sizes.expect("row sizes should be tracked");
+    assert_eq!(sizes.len(), 10);
+    for window in sizes.windows(2) {
+        assert_eq!(window[1].0, window[0].0 + 1);
+        assert!(window[1].1 > window[0].1, "rows out of order: {:?}", sizes);
+    }
+
+    wrapper.shutdown().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_send_batches_empty() {
+    let temp_dir = TempDir::new().unwrap();
+    let wrapper = create_wrapper(&temp_dir).await;
+
+    let result = wrapper.send_batches(Vec::new()).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.total_rows, 0);
+    assert_eq!(result.successful_count, 0);
+}
+
+#[tokio::test]
+async fn test_send_batches_rejects_mismatched_schemas() {
+    let temp_dir = TempDir::new().unwrap();
+    let wrapper = create_wrapper(&temp_dir).await;
+
+    let other_schema = Schema::new(vec![Field::new("value", DataType::Int64, false)]);
+    let other = RecordBatch::try_new(
+        Arc::new(other_schema),
+        vec![Arc::new(Int64Array::from(vec![1, 2]))],
+    )
+    .unwrap();
+
+    let result = wrapper.send_batches(vec![create_batch(1..3), other]).await;
+    assert!(matches!(result, Err(ZerobusError::ConversionError(_))));
+}
+
+/// A field name resolver only applies to provided descriptors; every column of the
+/// generated descriptor is still encoded
+#[tokio::test]
+async fn test_send_batches_with_field_name_resolver_encodes_every_column() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = WrapperConfiguration::new(
+        "https://test.cloud.databricks.com".to_string(),
+        "test_table".to_string(),
+    )
+    .with_debug_arrow_enabled(true)
+    .with_debug_output(temp_dir.path().to_path_buf())
+    .with_zerobus_writer_disabled(true)
+    .with_track_row_sizes(true)
+    .with_field_name_resolver(Arc::new(|name: &str| name.to_uppercase()));
+    let wrapper = ZerobusWrapper::new(config).await.unwrap();
+
+    let result = wrapper
+        .send_batches(vec![create_batch(1..4), create_batch(4..6)])
+        .await
+        .unwrap();
+    assert!(result.success);
+    assert_eq!(result.successful_count, 5);
+
+    // id (tag + varint) and name (tag + length + id bytes) are both encoded
+    let sizes = result.row_sizes.expect("row sizes should be tracked");
+    let expected: Vec<(usize, usize)> = (0..5).map(|idx| (idx, 4 + idx + 1)).collect();
+    assert_eq!(sizes, expected);
+
+    wrapper.shutdown().await.unwrap();
+}
@@ -20,6 +20,7 @@ fn test_protobuf_conversion_result_structure() {
     let result = ProtobufConversionResult {
         successful_bytes: successful_bytes.clone(),
         failed_rows: failed_rows.clone(),
+        aborted_at_row: None,
     };
 
     assert_eq!(result.successful_bytes.len(), 3);
@@ -37,6 +38,7 @@ fn test_protobuf_conversion_result_all_success() {
             (2, vec![5, 6]),
         ],
         failed_rows: vec![],
+        aborted_at_row: None,
     };
 
     assert_eq!(result.successful_bytes.len(), 3);
@@ -52,6 +54,7 @@ fn test_protobuf_conversion_result_all_failed() {
             (1, ZerobusError::ConversionError("error 2".to_string())),
             (2, ZerobusError::ConversionError("error 3".to_string())),
         ],
+        aborted_at_row: None,
     };
 
     assert_eq!(result.successful_bytes.len(), 0);
@@ -63,6 +66,7 @@ fn test_protobuf_conversion_result_partial_success() {
     let result = ProtobufConversionResult {
         successful_bytes: vec![(0, vec![1, 2]), (2, vec![5, 6])],
         failed_rows: vec![(1, ZerobusError::ConversionError("error".to_string()))],
+        aborted_at_row: None,
     };
 
     assert_eq!(result.successful_bytes.len(), 2);
@@ -74,6 +78,7 @@ fn test_protobuf_conversion_result_empty() {
     let result = ProtobufConversionResult {
         successful_bytes: vec![],
         failed_rows: vec![],
+        aborted_at_row: None,
     };
 
     assert_eq!(result.successful_bytes.len(), 0);
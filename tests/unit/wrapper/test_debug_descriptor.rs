@@ -2,7 +2,7 @@
 
 use arrow_zerobus_sdk_wrapper::wrapper::debug::DebugWriter;
 use arrow_zerobus_sdk_wrapper::ZerobusError;
-use prost_types::{DescriptorProto, FieldDescriptorProto, Type};
+use prost_types::{DescriptorProto, FieldDescriptorProto, FileDescriptorProto, Type};
 use std::time::Duration;
 use tempfile::TempDir;
 
@@ -103,7 +103,10 @@ async fn test_write_descriptor_file_format() {
     let file_bytes = std::fs::read(&descriptor_file).unwrap();
 
     // Parse back to DescriptorProto
-    let parsed_descriptor = DescriptorProto::decode(&file_bytes[..]).unwrap();
+    let parsed_descriptor = FileDescriptorProto::decode(&file_bytes[..])
+        .unwrap()
+        .message_type
+        .remove(0);
 
     // Verify contents match
     assert_eq!(
@@ -202,7 +205,10 @@ async fn test_write_descriptor_multiple_calls() {
 
     // Verify file content is valid (should be from first write)
     let file_bytes = std::fs::read(&descriptor_file).unwrap();
-    let parsed_descriptor = DescriptorProto::decode(&file_bytes[..]).unwrap();
+    let parsed_descriptor = FileDescriptorProto::decode(&file_bytes[..])
+        .unwrap()
+        .message_type
+        .remove(0);
     assert_eq!(
         descriptor.name,
         parsed_descriptor.name,
@@ -283,7 +289,10 @@ async fn test_write_descriptor_with_nested_types() {
         .path()
         .join("zerobus/descriptors/test_table.pb");
     let file_bytes = std::fs::read(&descriptor_file).unwrap();
-    let parsed_descriptor = DescriptorProto::decode(&file_bytes[..]).unwrap();
+    let parsed_descriptor = FileDescriptorProto::decode(&file_bytes[..])
+        .unwrap()
+        .message_type
+        .remove(0);
 
     // Verify nested types are preserved
     assert_eq!(
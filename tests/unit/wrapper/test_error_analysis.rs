@@ -26,6 +26,11 @@ fn test_group_errors_by_type() {
         total_rows: 10,
         successful_count: 5,
         failed_count: 5,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let grouped = result.group_errors_by_type();
@@ -49,6 +54,11 @@ fn test_group_errors_by_type_empty() {
         total_rows: 3,
         successful_count: 3,
         failed_count: 0,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let grouped = result.group_errors_by_type();
@@ -74,6 +84,11 @@ fn test_get_error_statistics() {
         total_rows: 10,
         successful_count: 5,
         failed_count: 5,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let stats = result.get_error_statistics();
@@ -102,6 +117,11 @@ fn test_get_error_statistics_all_success() {
         total_rows: 5,
         successful_count: 5,
         failed_count: 0,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let stats = result.get_error_statistics();
@@ -131,6 +151,11 @@ fn test_get_error_statistics_all_failed() {
         total_rows: 5,
         successful_count: 0,
         failed_count: 5,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let stats = result.get_error_statistics();
@@ -161,6 +186,11 @@ fn test_get_failed_row_indices_by_error_type_already_exists() {
         total_rows: 5,
         successful_count: 2,
         failed_count: 3,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let conversion_indices = result.get_failed_row_indices_by_error_type(|e| {
@@ -191,6 +221,11 @@ fn test_get_error_messages() {
         total_rows: 5,
         successful_count: 2,
         failed_count: 3,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let error_messages = result.get_error_messages();
@@ -214,6 +249,11 @@ fn test_get_error_messages_empty() {
         total_rows: 3,
         successful_count: 3,
         failed_count: 0,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let error_messages = result.get_error_messages();
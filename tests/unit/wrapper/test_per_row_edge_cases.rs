@@ -24,6 +24,11 @@ fn test_empty_batch_edge_case() {
         total_rows: 0,
         successful_count: 0,
         failed_count: 0,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     assert_eq!(result.total_rows, 0);
@@ -46,6 +51,11 @@ fn test_all_rows_succeed_edge_case() {
         total_rows: 5,
         successful_count: 5,
         failed_count: 0,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     assert_eq!(result.successful_count, result.total_rows);
@@ -72,6 +82,11 @@ fn test_all_rows_fail_edge_case() {
         total_rows: 3,
         successful_count: 0,
         failed_count: 3,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     assert_eq!(result.failed_count, result.total_rows);
@@ -94,6 +109,11 @@ fn test_batch_level_error_edge_case() {
         total_rows: 10,
         successful_count: 0,
         failed_count: 0, // Batch-level error, no per-row processing
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     assert!(result.error.is_some());
@@ -121,6 +141,11 @@ fn test_very_large_batch_edge_case() {
         total_rows: large_batch_size,
         successful_count: large_batch_size,
         failed_count: 0,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     assert_eq!(result.total_rows, large_batch_size);
@@ -150,6 +175,11 @@ fn test_mixed_error_types_edge_case() {
         total_rows: 6,
         successful_count: 3,
         failed_count: 3,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     assert_eq!(result.total_rows, 6);
@@ -212,6 +242,11 @@ fn test_consistency_validation_edge_case() {
             total_rows: total,
             successful_count: successful,
             failed_count: failed,
+            row_sizes: None,
+            failed_row_bytes: None,
+            ack_ids: None,
+            mirror_result: None,
+            failures_truncated: false,
         };
 
         // Consistency check: total_rows == successful_count + failed_count
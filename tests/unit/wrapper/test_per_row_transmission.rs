@@ -30,6 +30,11 @@ fn test_transmission_result_merges_conversion_and_transmission_errors() {
         total_rows: 5,
         successful_count: 3,
         failed_count: 2,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     assert_eq!(result.failed_rows.as_ref().unwrap().len(), 2);
@@ -56,6 +61,11 @@ fn test_transmission_continues_after_row_failure() {
         total_rows: 5,
         successful_count: 4,
         failed_count: 1,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     // Verify that we have both successful and failed rows (partial success)
@@ -83,6 +93,11 @@ fn test_transmission_error_types() {
         total_rows: 5,
         successful_count: 2,
         failed_count: 3,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let failed_rows = result.failed_rows.as_ref().unwrap();
@@ -121,6 +136,11 @@ fn test_transmission_row_indices_correct() {
         total_rows: 5,
         successful_count: 3,
         failed_count: 2,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let failed_indices: Vec<usize> = result.get_failed_row_indices();
@@ -161,6 +181,11 @@ fn test_transmission_retry_preserves_per_row_errors() {
         total_rows: 5,
         successful_count: 4,
         failed_count: 1,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     // Verify errors are still present after retries
@@ -42,6 +42,11 @@ fn test_get_failed_row_indices() {
         total_rows: 5,
         successful_count: 3,
         failed_count: 2,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let failed_indices = result.get_failed_row_indices();
@@ -61,6 +66,11 @@ fn test_get_failed_row_indices_empty() {
         total_rows: 3,
         successful_count: 3,
         failed_count: 0,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let failed_indices = result.get_failed_row_indices();
@@ -83,6 +93,11 @@ fn test_get_successful_row_indices() {
         total_rows: 5,
         successful_count: 3,
         failed_count: 2,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let successful_indices = result.get_successful_row_indices();
@@ -105,6 +120,11 @@ fn test_get_successful_row_indices_empty() {
         total_rows: 2,
         successful_count: 0,
         failed_count: 2,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let successful_indices = result.get_successful_row_indices();
@@ -128,6 +148,11 @@ fn test_extract_failed_batch() {
         total_rows: 5,
         successful_count: 3,
         failed_count: 2,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let failed_batch = result.extract_failed_batch(&batch).unwrap();
@@ -153,6 +178,11 @@ fn test_extract_failed_batch_empty() {
         failed_count: 0,
         retry_attempts: 0,
         latency_ms: 100,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let failed_batch = result.extract_failed_batch(&batch);
@@ -176,6 +206,11 @@ fn test_extract_successful_batch() {
         total_rows: 5,
         successful_count: 3,
         failed_count: 2,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let successful_batch = result.extract_successful_batch(&batch).unwrap();
@@ -205,6 +240,11 @@ fn test_extract_successful_batch_empty() {
         total_rows: 2,
         successful_count: 0,
         failed_count: 2,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let successful_batch = result.extract_successful_batch(&batch);
@@ -228,6 +268,11 @@ fn test_get_failed_row_indices_by_error_type() {
         failed_count: 3,
         retry_attempts: 0,
         latency_ms: 100,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let conversion_error_indices = result.get_failed_row_indices_by_error_type(|e| {
@@ -254,6 +299,11 @@ fn test_get_failed_row_indices_by_error_type_empty() {
         total_rows: 3,
         successful_count: 3,
         failed_count: 0,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     let indices = result.get_failed_row_indices_by_error_type(|_| true);
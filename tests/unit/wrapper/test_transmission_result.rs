@@ -17,6 +17,11 @@ fn test_transmission_result_new_fields_exist() {
         total_rows: 3,
         successful_count: 3,
         failed_count: 0,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     assert_eq!(result.total_rows, 3);
@@ -44,6 +49,11 @@ fn test_transmission_result_with_failed_rows() {
         total_rows: 5,
         successful_count: 3,
         failed_count: 2,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     assert_eq!(result.failed_rows, Some(failed_rows));
@@ -67,6 +77,11 @@ fn test_transmission_result_consistency_all_success() {
         total_rows: 5,
         successful_count: 5,
         failed_count: 0,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     assert_eq!(result.total_rows, result.successful_count + result.failed_count);
@@ -93,6 +108,11 @@ fn test_transmission_result_consistency_partial_success() {
         total_rows: 5,
         successful_count: 3,
         failed_count: 2,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     assert_eq!(result.total_rows, result.successful_count + result.failed_count);
@@ -124,6 +144,11 @@ fn test_transmission_result_consistency_all_failed() {
         total_rows: 3,
         successful_count: 0,
         failed_count: 3,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     assert_eq!(result.total_rows, result.successful_count + result.failed_count);
@@ -147,6 +172,11 @@ fn test_transmission_result_empty_batch() {
         total_rows: 0,
         successful_count: 0,
         failed_count: 0,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     assert_eq!(result.total_rows, 0);
@@ -171,6 +201,11 @@ fn test_transmission_result_batch_level_error() {
         total_rows: 10,
         successful_count: 0,
         failed_count: 0, // Batch-level error, no per-row processing
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     assert!(result.error.is_some());
@@ -192,6 +227,11 @@ fn test_transmission_result_backward_compatibility() {
         total_rows: 5,
         successful_count: 5,
         failed_count: 0,
+        row_sizes: None,
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
     };
 
     // Existing code that checks success should still work
@@ -200,3 +240,30 @@ fn test_transmission_result_backward_compatibility() {
         assert!(result.latency_ms.is_some());
     }
 }
+
+#[test]
+fn test_transmission_result_row_sizes() {
+    let result = TransmissionResult {
+        success: true,
+        error: None,
+        attempts: 1,
+        latency_ms: Some(100),
+        batch_size_bytes: 1024,
+        failed_rows: Some(vec![(1, ZerobusError::ConversionError("test error".to_string()))]),
+        successful_rows: Some(vec![0, 2]),
+        total_rows: 3,
+        successful_count: 2,
+        failed_count: 1,
+        row_sizes: Some(vec![(0, 12), (2, 15)]),
+        failed_row_bytes: None,
+        ack_ids: None,
+        mirror_result: None,
+        failures_truncated: false,
+    };
+
+    // Rows that failed conversion have no size entry
+    let sizes = result.row_sizes.as_ref().unwrap();
+    assert_eq!(sizes.len(), 2);
+    assert!(sizes.iter().all(|(idx, _)| *idx != 1));
+    assert_eq!(sizes.iter().map(|(_, size)| size).sum::<usize>(), 27);
+}